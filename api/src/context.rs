@@ -20,7 +20,11 @@ use aptos_config::config::{NodeConfig, RoleType};
 use aptos_crypto::HashValue;
 use aptos_db_indexer::table_info_reader::TableInfoReader;
 use aptos_gas_schedule::{AptosGasParameters, FromOnChainGasSchedule};
-use aptos_logger::{error, info, Schema};
+use aptos_logger::{
+    error, info,
+    otlp_tracing::{record_txns, txn_span, TxnStage},
+    Schema,
+};
 use aptos_mempool::{MempoolClientRequest, MempoolClientSender, SubmissionStatus};
 use aptos_storage_interface::{
     state_view::{DbStateView, DbStateViewAtVersion, LatestDbStateCheckpointView},
@@ -205,6 +209,8 @@ impl Context {
     }
 
     pub async fn submit_transaction(&self, txn: SignedTransaction) -> Result<SubmissionStatus> {
+        let span = txn_span(TxnStage::API_SUBMIT);
+        record_txns(&span, [txn.committed_hash()]);
         let (req_sender, callback) = oneshot::channel();
         self.mp_sender
             .clone()
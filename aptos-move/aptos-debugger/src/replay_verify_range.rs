@@ -0,0 +1,415 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Replays a range of committed transactions block by block and verifies the outputs against
+//! the on-chain transaction infos, like `db-tool replay-verify` does, but without requiring a
+//! full DB: a REST endpoint is enough.
+//!
+//! Transactions are replayed with the block executor, at their original block boundaries, and
+//! several blocks are replayed in parallel. The status, gas used, write set and events of each
+//! transaction are compared, and all divergences are collected in a JSON report.
+
+use crate::{
+    aptos_debugger::{is_reconfiguration, AptosDebugger},
+    common::Opts,
+};
+use anyhow::{bail, ensure, format_err, Result};
+use aptos_crypto::hash::CryptoHash;
+use aptos_rest_client::Client;
+use aptos_types::{
+    proof::accumulator::InMemoryEventAccumulator,
+    transaction::{
+        signature_verified_transaction::SignatureVerifiedTransaction, Transaction, TransactionInfo,
+        TransactionOutput, TransactionStatus, Version,
+    },
+};
+use aptos_vm::{AptosVM, VMExecutor};
+use clap::Parser;
+use futures::{stream, StreamExt};
+use serde::Serialize;
+use std::path::PathBuf;
+use url::Url;
+
+#[derive(Parser)]
+pub struct Command {
+    #[clap(flatten)]
+    opts: Opts,
+
+    #[clap(long)]
+    begin_version: u64,
+
+    #[clap(long)]
+    limit: u64,
+
+    /// Number of blocks replayed in parallel.
+    #[clap(long, default_value_t = 4)]
+    num_workers: usize,
+
+    /// Number of transactions fetched from the target at a time.
+    #[clap(long, default_value_t = 1000)]
+    batch_size: u16,
+
+    /// Where to write the JSON divergence report. It is printed to stdout if not set.
+    #[clap(long)]
+    report_path: Option<PathBuf>,
+}
+
+impl Command {
+    pub async fn run(self) -> Result<()> {
+        AptosVM::set_concurrency_level_once(self.opts.concurrency_level);
+
+        let debugger = if let Some(rest_endpoint) = self.opts.target.rest_endpoint {
+            AptosDebugger::rest_client(Client::new(Url::parse(&rest_endpoint)?))?
+        } else if let Some(db_path) = self.opts.target.db_path {
+            AptosDebugger::db(db_path)?
+        } else {
+            unreachable!("Must provide one target.");
+        };
+
+        let report = replay_verify_range(
+            &debugger,
+            self.begin_version,
+            self.limit,
+            self.batch_size,
+            self.num_workers,
+        )
+        .await?;
+
+        let json = serde_json::to_string_pretty(&report)?;
+        if let Some(report_path) = self.report_path {
+            std::fs::write(&report_path, json)?;
+            println!("Report written to {}.", report_path.display());
+        } else {
+            println!("{json}");
+        }
+
+        if !report.divergences.is_empty() {
+            bail!(
+                "Found {} divergences while replaying {} transactions in {} blocks",
+                report.divergences.len(),
+                report.num_transactions,
+                report.num_blocks
+            );
+        }
+        Ok(())
+    }
+}
+
+/// The result of replaying a range of transactions.
+#[derive(Debug, Default, Serialize)]
+pub struct ReplayReport {
+    pub begin_version: Version,
+    /// Exclusive
+    pub end_version: Version,
+    pub num_blocks: usize,
+    pub num_transactions: u64,
+    pub divergences: Vec<Divergence>,
+}
+
+/// A difference between the replayed output of a transaction and its on-chain transaction info.
+#[derive(Debug, Serialize)]
+pub struct Divergence {
+    pub version: Version,
+    /// First version of the block the transaction was replayed with.
+    pub block_begin_version: Version,
+    pub kind: DivergenceKind,
+    pub expected: String,
+    pub actual: String,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DivergenceKind {
+    Status,
+    GasUsed,
+    WriteSet,
+    Events,
+    /// The block could not be executed at all. Reported at the first version not executed.
+    ExecutionError,
+}
+
+/// Consecutive transactions of one block, along with their on-chain transaction infos.
+/// The first and last blocks of the range may be partial.
+#[derive(Debug)]
+struct Block {
+    begin_version: Version,
+    txns: Vec<Transaction>,
+    txn_infos: Vec<TransactionInfo>,
+}
+
+fn is_block_start(txn: &Transaction) -> bool {
+    matches!(
+        txn,
+        Transaction::BlockMetadata(_)
+            | Transaction::BlockMetadataExt(_)
+            | Transaction::GenesisTransaction(_)
+    )
+}
+
+/// Appends transactions starting at `begin_version` to the blocks, starting a new block before
+/// each block metadata transaction.
+fn split_into_blocks(
+    blocks: &mut Vec<Block>,
+    begin_version: Version,
+    txns: Vec<Transaction>,
+    txn_infos: Vec<TransactionInfo>,
+) {
+    for (idx, (txn, txn_info)) in txns.into_iter().zip(txn_infos).enumerate() {
+        if blocks.is_empty() || is_block_start(&txn) {
+            blocks.push(Block {
+                begin_version: begin_version + idx as Version,
+                txns: vec![],
+                txn_infos: vec![],
+            });
+        }
+        let block = blocks.last_mut().expect("There must be a block");
+        block.txns.push(txn);
+        block.txn_infos.push(txn_info);
+    }
+}
+
+pub async fn replay_verify_range(
+    debugger: &AptosDebugger,
+    begin_version: Version,
+    limit: u64,
+    batch_size: u16,
+    num_workers: usize,
+) -> Result<ReplayReport> {
+    ensure!(batch_size > 0, "Batch size must be positive");
+    ensure!(num_workers > 0, "Number of workers must be positive");
+    let end_version = begin_version
+        .checked_add(limit)
+        .ok_or_else(|| format_err!("Version range overflows"))?;
+    let mut report = ReplayReport {
+        begin_version,
+        end_version,
+        ..ReplayReport::default()
+    };
+
+    // The last block of a batch may continue in the next batch, so it is only replayed
+    // once the next batch has been fetched.
+    let mut pending_block = None;
+    let mut next_version = begin_version;
+    while next_version < end_version {
+        let batch_limit = std::cmp::min(batch_size as u64, end_version - next_version);
+        let (txns, txn_infos) = debugger
+            .get_committed_transactions(next_version, batch_limit)
+            .await?;
+        if txns.is_empty() {
+            bail!("No transactions found at version {}", next_version);
+        }
+
+        let mut blocks: Vec<Block> = pending_block.take().into_iter().collect();
+        let num_txns = txns.len() as u64;
+        split_into_blocks(&mut blocks, next_version, txns, txn_infos);
+        next_version += num_txns;
+        if next_version < end_version {
+            pending_block = blocks.pop();
+        }
+
+        println!(
+            "Replaying {} blocks, {} transactions remaining",
+            blocks.len(),
+            end_version - next_version
+        );
+        let results: Vec<_> = stream::iter(blocks)
+            .map(|block| replay_block(debugger, block))
+            .buffered(num_workers)
+            .collect()
+            .await;
+        for result in results {
+            let (num_txns, mut divergences) = result?;
+            report.num_blocks += 1;
+            report.num_transactions += num_txns;
+            report.divergences.append(&mut divergences);
+        }
+    }
+
+    Ok(report)
+}
+
+/// Replays a block and returns the number of transactions replayed along with the divergences.
+async fn replay_block(debugger: &AptosDebugger, block: Block) -> Result<(u64, Vec<Divergence>)> {
+    let Block {
+        begin_version: block_begin_version,
+        mut txns,
+        mut txn_infos,
+    } = block;
+    let num_txns = txns.len() as u64;
+    let mut divergences = vec![];
+
+    let mut version = block_begin_version;
+    while !txns.is_empty() {
+        let state_view = debugger.state_view_at_version(version);
+        let sig_verified_txns: Vec<SignatureVerifiedTransaction> =
+            txns.iter().cloned().map(Into::into).collect();
+        let outputs = match tokio::task::spawn_blocking(move || {
+            AptosVM::execute_block_no_limit(&sig_verified_txns, &state_view)
+        })
+        .await?
+        {
+            Ok(outputs) => outputs,
+            Err(err) => {
+                divergences.push(Divergence {
+                    version,
+                    block_begin_version,
+                    kind: DivergenceKind::ExecutionError,
+                    expected: String::new(),
+                    actual: format!("{:?}", err),
+                });
+                break;
+            },
+        };
+
+        // On chain, the transactions following a reconfiguration are retried in the next
+        // epoch, so only the outputs up to the reconfiguration are verified, and the remaining
+        // transactions are replayed again on top of the new epoch.
+        let num_committed = outputs
+            .iter()
+            .position(is_reconfiguration)
+            .map_or(outputs.len(), |idx| idx + 1);
+        for (idx, (output, txn_info)) in outputs
+            .iter()
+            .zip(txn_infos.iter())
+            .take(num_committed)
+            .enumerate()
+        {
+            divergences.extend(compare_output(
+                version + idx as Version,
+                block_begin_version,
+                output,
+                txn_info,
+            ));
+        }
+
+        version += num_committed as Version;
+        txns.drain(..num_committed);
+        txn_infos.drain(..num_committed);
+    }
+
+    Ok((num_txns, divergences))
+}
+
+fn compare_output(
+    version: Version,
+    block_begin_version: Version,
+    output: &TransactionOutput,
+    txn_info: &TransactionInfo,
+) -> Vec<Divergence> {
+    let mut divergences = vec![];
+    let mut divergence = |kind, expected: String, actual: String| {
+        divergences.push(Divergence {
+            version,
+            block_begin_version,
+            kind,
+            expected,
+            actual,
+        })
+    };
+
+    let expected_status: TransactionStatus = txn_info.status().clone().into();
+    if output.status() != &expected_status {
+        divergence(
+            DivergenceKind::Status,
+            format!("{:?}", expected_status),
+            format!("{:?}", output.status()),
+        );
+    }
+
+    if output.gas_used() != txn_info.gas_used() {
+        divergence(
+            DivergenceKind::GasUsed,
+            txn_info.gas_used().to_string(),
+            output.gas_used().to_string(),
+        );
+    }
+
+    let write_set_hash = CryptoHash::hash(output.write_set());
+    if write_set_hash != txn_info.state_change_hash() {
+        divergence(
+            DivergenceKind::WriteSet,
+            txn_info.state_change_hash().to_hex_literal(),
+            write_set_hash.to_hex_literal(),
+        );
+    }
+
+    let event_hashes: Vec<_> = output.events().iter().map(CryptoHash::hash).collect();
+    let event_root_hash = InMemoryEventAccumulator::from_leaves(&event_hashes).root_hash;
+    if event_root_hash != txn_info.event_root_hash() {
+        divergence(
+            DivergenceKind::Events,
+            txn_info.event_root_hash().to_hex_literal(),
+            event_root_hash.to_hex_literal(),
+        );
+    }
+
+    divergences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split_into_blocks, Block};
+    use aptos_crypto::HashValue;
+    use aptos_types::{
+        account_address::AccountAddress,
+        block_metadata::BlockMetadata,
+        transaction::{ExecutionStatus, Transaction, TransactionInfo},
+    };
+
+    fn block_metadata(round: u64) -> Transaction {
+        Transaction::BlockMetadata(BlockMetadata::new(
+            HashValue::random(),
+            1,
+            round,
+            AccountAddress::ONE,
+            vec![],
+            vec![],
+            round,
+        ))
+    }
+
+    fn state_checkpoint() -> Transaction {
+        Transaction::StateCheckpoint(HashValue::random())
+    }
+
+    fn txn_info() -> TransactionInfo {
+        TransactionInfo::new(
+            HashValue::zero(),
+            HashValue::zero(),
+            HashValue::zero(),
+            None,
+            0,
+            ExecutionStatus::Success,
+        )
+    }
+
+    fn block_versions(blocks: &[Block]) -> Vec<(u64, usize)> {
+        blocks
+            .iter()
+            .map(|block| (block.begin_version, block.txns.len()))
+            .collect()
+    }
+
+    #[test]
+    fn test_split_into_blocks() {
+        let mut blocks = vec![];
+
+        // The range starts in the middle of a block
+        let txns = vec![
+            state_checkpoint(),
+            block_metadata(1),
+            state_checkpoint(),
+            block_metadata(2),
+        ];
+        let txn_infos = txns.iter().map(|_| txn_info()).collect();
+        split_into_blocks(&mut blocks, 10, txns, txn_infos);
+        assert_eq!(block_versions(&blocks), vec![(10, 1), (11, 2), (13, 1)]);
+
+        // The last block continues in the next batch
+        let txns = vec![state_checkpoint(), block_metadata(3)];
+        let txn_infos = txns.iter().map(|_| txn_info()).collect();
+        let mut pending: Vec<Block> = blocks.pop().into_iter().collect();
+        split_into_blocks(&mut pending, 14, txns, txn_infos);
+        assert_eq!(block_versions(&pending), vec![(13, 2), (15, 1)]);
+    }
+}
@@ -118,6 +118,7 @@ impl ReleaseTarget {
                     references_file: Some("doc_template/references.md".to_string()),
                 }),
                 skip_fetch_latest_git_deps: true,
+                locked: false,
                 bytecode_version: None,
                 compiler_version: None,
                 language_version: None,
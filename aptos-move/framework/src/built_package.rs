@@ -77,6 +77,10 @@ pub struct BuildOptions {
     pub docgen_options: Option<DocgenOptions>,
     #[clap(long)]
     pub skip_fetch_latest_git_deps: bool,
+    /// Fail instead of resolving dependencies which are not pinned in Move.lock, or whose
+    /// sources no longer match their pinned digest.
+    #[clap(long)]
+    pub locked: bool,
     #[clap(long)]
     pub bytecode_version: Option<u32>,
     #[clap(long, value_parser = clap::value_parser!(CompilerVersion))]
@@ -108,6 +112,7 @@ impl Default for BuildOptions {
             // This is false by default, because it could accidentally pull new dependencies
             // while in a test (and cause some havoc)
             skip_fetch_latest_git_deps: false,
+            locked: false,
             bytecode_version: None,
             compiler_version: None,
             language_version: None,
@@ -150,6 +155,7 @@ pub fn build_model(
         force_recompilation: false,
         fetch_deps_only: false,
         skip_fetch_latest_git_deps: true,
+        locked: false,
         compiler_config: CompilerConfig {
             bytecode_version,
             compiler_version,
@@ -193,6 +199,7 @@ impl BuiltPackage {
             force_recompilation: false,
             fetch_deps_only: false,
             skip_fetch_latest_git_deps: options.skip_fetch_latest_git_deps,
+            locked: options.locked,
             compiler_config: CompilerConfig {
                 bytecode_version,
                 compiler_version,
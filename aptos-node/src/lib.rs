@@ -647,6 +647,7 @@ pub fn setup_environment_and_start_node(
 
     // Set up the networks and gather the application network handles
     let peers_and_metadata = network::create_peers_and_metadata(&node_config);
+    admin_service.set_peers_and_metadata(peers_and_metadata.clone());
     let (
         network_runtimes,
         consensus_network_interfaces,
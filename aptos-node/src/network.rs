@@ -3,6 +3,7 @@
 
 use crate::services::start_netbench_service;
 use aptos_channels::{self, aptos_channel, message_queues::QueueStyle};
+use aptos_compression::{client::CompressionClient, dictionary, dictionary::CompressionDictionary};
 use aptos_config::{
     config::{NetworkConfig, NodeConfig},
     network_id::NetworkId,
@@ -104,7 +105,15 @@ pub fn jwk_consensus_network_configuration(node_config: &NodeConfig) -> NetworkA
 
 /// Returns the network application config for the mempool client and service
 pub fn mempool_network_configuration(node_config: &NodeConfig) -> NetworkApplicationConfig {
-    let direct_send_protocols = vec![ProtocolId::MempoolDirectSend];
+    let mut direct_send_protocols = vec![
+        ProtocolId::MempoolDirectSendCompressed,
+        ProtocolId::MempoolDirectSend,
+    ]; // Protocols are sorted by preference (highest to lowest)
+    if node_config.mempool.compression_dictionary_path.is_some() {
+        // Only advertise the dictionary protocol if the dictionary is loaded.
+        // Peers without it will fall back to plain zstd compression.
+        direct_send_protocols.insert(0, ProtocolId::MempoolDirectSendDictionaryCompressed);
+    }
     let rpc_protocols = vec![]; // Mempool does not use RPC
 
     let network_client_config =
@@ -190,8 +199,17 @@ pub fn netbench_network_configuration(
     if !cfg.enabled {
         return None;
     }
-    let direct_send_protocols = vec![ProtocolId::NetbenchDirectSend];
-    let rpc_protocols = vec![ProtocolId::NetbenchRpc];
+    let (direct_send_protocols, rpc_protocols) = if cfg.enable_compression {
+        (
+            vec![ProtocolId::NetbenchDirectSendCompressed],
+            vec![ProtocolId::NetbenchRpcCompressed],
+        )
+    } else {
+        (
+            vec![ProtocolId::NetbenchDirectSend],
+            vec![ProtocolId::NetbenchRpc],
+        )
+    };
     let network_client_config =
         NetworkClientConfig::new(direct_send_protocols.clone(), rpc_protocols.clone());
     let max_network_channel_size = cfg.max_network_channel_size as usize;
@@ -235,6 +253,20 @@ pub fn create_peers_and_metadata(node_config: &NodeConfig) -> Arc<PeersAndMetada
     PeersAndMetadata::new(&network_ids)
 }
 
+/// Loads and registers the zstd compression dictionaries specified in the node config
+fn register_compression_dictionaries(node_config: &NodeConfig) {
+    if let Some(dictionary_path) = &node_config.mempool.compression_dictionary_path {
+        let mempool_dictionary =
+            CompressionDictionary::from_file(dictionary_path).unwrap_or_else(|error| {
+                panic!(
+                    "Failed to load the mempool compression dictionary! Error: {:?}",
+                    error
+                )
+            });
+        dictionary::register_dictionary(CompressionClient::Mempool, mempool_dictionary);
+    }
+}
+
 /// Sets up all networks and returns the appropriate application network interfaces
 pub fn setup_networks_and_get_interfaces(
     node_config: &NodeConfig,
@@ -251,6 +283,9 @@ pub fn setup_networks_and_get_interfaces(
     ApplicationNetworkInterfaces<StorageServiceMessage>,
     Option<ApplicationNetworkInterfaces<ObserverMessage>>,
 ) {
+    // Register the compression dictionaries (before any messages are sent)
+    register_compression_dictionaries(node_config);
+
     // Gather all network configs
     let network_configs = extract_network_configs(node_config);
     let observer_enabled = node_config.consensus_observer.publisher_enabled
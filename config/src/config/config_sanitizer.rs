@@ -6,7 +6,7 @@ use crate::config::{
     utils::{are_failpoints_enabled, get_config_name},
     AdminServiceConfig, ApiConfig, BaseConfig, ConsensusConfig, DagConsensusConfig, Error,
     ExecutionConfig, IndexerGrpcConfig, InspectionServiceConfig, LoggerConfig, MempoolConfig,
-    NetbenchConfig, NodeConfig, StateSyncConfig, StorageConfig,
+    NetbenchConfig, NetworkConfig, NodeConfig, StateSyncConfig, StorageConfig,
 };
 use aptos_types::chain_id::ChainId;
 use std::collections::HashSet;
@@ -146,6 +146,9 @@ fn sanitize_fullnode_network_configs(
                 ),
             ));
         }
+
+        // Verify the outbound traffic config
+        sanitize_outbound_traffic_config(&sanitizer_name, fullnode_network_config)?;
     }
 
     Ok(())
@@ -193,6 +196,36 @@ fn sanitize_validator_network_config(
                 "Mutual authentication must be enabled for the validator network!".into(),
             ));
         }
+
+        // Verify the outbound traffic config
+        sanitize_outbound_traffic_config(&sanitizer_name, validator_network_config)?;
+    }
+
+    Ok(())
+}
+
+/// Sanitize the outbound traffic config of the given network config
+fn sanitize_outbound_traffic_config(
+    sanitizer_name: &str,
+    network_config: &NetworkConfig,
+) -> Result<(), Error> {
+    // Verify that the peer bandwidth limit (if enabled) is valid
+    if let Some(bandwidth_limit) = &network_config.outbound_traffic_config.peer_bandwidth_limit {
+        if bandwidth_limit.enabled
+            && (bandwidth_limit.ip_byte_bucket_rate == 0
+                || bandwidth_limit.ip_byte_bucket_size < bandwidth_limit.ip_byte_bucket_rate
+                || bandwidth_limit.initial_bucket_fill_percentage > 100)
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name.to_string(),
+                format!(
+                    "The peer bandwidth limit must have a non-zero bucket rate, a bucket size \
+                    of at least the rate, and an initial fill percentage of at most 100! \
+                    Network: {}, limit: {:?}",
+                    network_config.network_id, bandwidth_limit
+                ),
+            ));
+        }
     }
 
     Ok(())
@@ -202,7 +235,7 @@ fn sanitize_validator_network_config(
 mod tests {
     use super::*;
     use crate::{
-        config::{node_startup_config::NodeStartupConfig, NetworkConfig},
+        config::{node_startup_config::NodeStartupConfig, OutboundTrafficConfig, RateLimitConfig},
         network_id::NetworkId,
     };
 
@@ -402,4 +435,85 @@ mod tests {
         .unwrap_err();
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
+
+    #[test]
+    fn test_sanitize_invalid_peer_bandwidth_limit() {
+        // Create a validator config with an invalid peer bandwidth limit
+        let node_config = NodeConfig {
+            validator_network: Some(NetworkConfig {
+                network_id: NetworkId::Validator,
+                mutual_authentication: true,
+                outbound_traffic_config: OutboundTrafficConfig {
+                    peer_bandwidth_limit: Some(RateLimitConfig {
+                        ip_byte_bucket_rate: 0,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = sanitize_validator_network_config(
+            &node_config,
+            NodeType::Validator,
+            Some(ChainId::testnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Create a validator config with a bucket smaller than the fill rate
+        let node_config = NodeConfig {
+            validator_network: Some(NetworkConfig {
+                network_id: NetworkId::Validator,
+                mutual_authentication: true,
+                outbound_traffic_config: OutboundTrafficConfig {
+                    peer_bandwidth_limit: Some(RateLimitConfig {
+                        ip_byte_bucket_rate: 1000,
+                        ip_byte_bucket_size: 999,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = sanitize_validator_network_config(
+            &node_config,
+            NodeType::Validator,
+            Some(ChainId::testnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Create a fullnode config with an invalid initial fill percentage
+        let node_config = NodeConfig {
+            full_node_networks: vec![NetworkConfig {
+                network_id: NetworkId::Public,
+                outbound_traffic_config: OutboundTrafficConfig {
+                    peer_bandwidth_limit: Some(RateLimitConfig {
+                        initial_bucket_fill_percentage: 101,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = sanitize_fullnode_network_configs(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::testnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
 }
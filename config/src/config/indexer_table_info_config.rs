@@ -2,10 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
 // Useful defaults
 pub const DEFAULT_PARSER_TASK_COUNT: u16 = 20;
 pub const DEFAULT_PARSER_BATCH_SIZE: u16 = 1000;
+pub const DEFAULT_SNAPSHOTS_TO_KEEP: usize = 3;
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
@@ -20,6 +22,9 @@ pub struct IndexerTableInfoConfig {
     pub parser_batch_size: u16,
 
     pub enable_expensive_logging: bool,
+
+    /// Snapshots of the table info db, so that new nodes don't have to parse all history
+    pub snapshot: Option<TableInfoSnapshotConfig>,
 }
 
 // Reminder, #[serde(default)] on IndexerTableInfoConfig means that the default values for
@@ -32,6 +37,39 @@ impl Default for IndexerTableInfoConfig {
             parser_task_count: DEFAULT_PARSER_TASK_COUNT,
             parser_batch_size: DEFAULT_PARSER_BATCH_SIZE,
             enable_expensive_logging: false,
+            snapshot: None,
         }
     }
 }
+
+/// Where the table info db snapshots are stored
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TableInfoSnapshotStorage {
+    /// A local (or mounted) directory
+    LocalFs(PathBuf),
+    /// A Google Cloud Storage bucket
+    Gcs(String),
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TableInfoSnapshotConfig {
+    pub storage: TableInfoSnapshotStorage,
+
+    /// Upload a snapshot of the table info db at most once per epoch
+    #[serde(default)]
+    pub enable_backup: bool,
+
+    /// On startup, restore the latest verified snapshot if it's ahead of the local db
+    #[serde(default)]
+    pub enable_restore: bool,
+
+    /// Number of snapshots to keep in the storage, the older ones are deleted after a backup
+    #[serde(default = "default_snapshots_to_keep")]
+    pub snapshots_to_keep: usize,
+}
+
+fn default_snapshots_to_keep() -> usize {
+    DEFAULT_SNAPSHOTS_TO_KEEP
+}
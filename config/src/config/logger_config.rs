@@ -4,8 +4,11 @@
 
 use crate::{
     config::{
-        config_optimizer::ConfigOptimizer, config_sanitizer::ConfigSanitizer,
-        node_config_loader::NodeType, utils::is_tokio_console_enabled, Error, NodeConfig,
+        config_optimizer::ConfigOptimizer,
+        config_sanitizer::ConfigSanitizer,
+        node_config_loader::NodeType,
+        utils::{is_otlp_tracing_enabled, is_tokio_console_enabled},
+        Error, NodeConfig,
     },
     utils,
 };
@@ -17,7 +20,7 @@ use serde_yaml::Value;
 // Useful constants for the logger config
 const DEFAULT_TOKIO_CONSOLE_PORT: u16 = 6669;
 
-#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct LoggerConfig {
     /// Channel size for asynchronous node logging
@@ -36,6 +39,9 @@ pub struct LoggerConfig {
     pub telemetry_level: Level,
     /// Tokio console port for local debugging
     pub tokio_console_port: Option<u16>,
+    /// OTLP/HTTP endpoint of the OpenTelemetry collector to export
+    /// transaction lifecycle traces to, e.g., http://localhost:4318/v1/traces
+    pub otlp_trace_endpoint: Option<String>,
 }
 
 impl Default for LoggerConfig {
@@ -53,6 +59,9 @@ impl Default for LoggerConfig {
             // Setting this to None will disable tokio-console
             // even if the "tokio-console" feature is enabled.
             tokio_console_port: None,
+
+            // Setting this requires the "otlp-tracing" feature.
+            otlp_trace_endpoint: None,
         }
     }
 }
@@ -91,6 +100,15 @@ impl ConfigSanitizer for LoggerConfig {
             ));
         }
 
+        // Verify that trace export is only configured if it is supported
+        if !is_otlp_tracing_enabled() && logger_config.otlp_trace_endpoint.is_some() {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The otlp-tracing feature is not enabled but the OTLP trace endpoint is set!"
+                    .into(),
+            ));
+        }
+
         Ok(())
     }
 }
@@ -202,4 +220,22 @@ mod tests {
                 .unwrap_err();
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
+
+    #[test]
+    fn test_sanitize_missing_otlp_tracing_feature() {
+        // Create a logger config with the OTLP trace endpoint set
+        let node_config = NodeConfig {
+            logger: LoggerConfig {
+                otlp_trace_endpoint: Some("http://localhost:4318/v1/traces".into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config fails sanitization (the otlp-tracing feature is missing!)
+        let error =
+            LoggerConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::testnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
 }
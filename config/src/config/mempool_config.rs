@@ -10,6 +10,7 @@ use aptos_global_constants::DEFAULT_BUCKETS;
 use aptos_types::chain_id::ChainId;
 use serde::{Deserialize, Serialize};
 use serde_yaml::Value;
+use std::path::PathBuf;
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
@@ -20,6 +21,11 @@ pub struct MempoolConfig {
     pub capacity_bytes: usize,
     /// Maximum number of transactions allowed in the Mempool per user
     pub capacity_per_user: usize,
+    /// The path to a trained zstd dictionary used to compress mempool broadcasts.
+    /// If given, the node advertises the dictionary compressed mempool protocol
+    /// (all nodes advertising it must load the same dictionary). Otherwise, the
+    /// broadcasts are compressed without a dictionary.
+    pub compression_dictionary_path: Option<PathBuf>,
     /// Number of failover peers to broadcast to when the primary network is alive
     pub default_failovers: usize,
     /// Whether or not to enable intelligent peer prioritization
@@ -75,6 +81,7 @@ impl Default for MempoolConfig {
             capacity: 2_000_000,
             capacity_bytes: 2 * 1024 * 1024 * 1024,
             capacity_per_user: 100,
+            compression_dictionary_path: None,
             default_failovers: 1,
             enable_intelligent_peer_prioritization: true,
             shared_mempool_peer_update_interval_ms: 1_000,
@@ -13,6 +13,7 @@ pub struct NetbenchConfig {
     pub enabled: bool,
     pub max_network_channel_size: u64, // Max num of pending network messages
     pub netbench_service_threads: Option<usize>, // Number of kernel threads for tokio runtime. None default for num-cores.
+    pub enable_compression: bool, // Whether or not to use the (zstd) compressed netbench protocols
 
     pub enable_direct_send_testing: bool, // Whether or not to enable direct send test mode
     pub direct_send_data_size: usize,     // The amount of data to send in each request
@@ -30,6 +31,7 @@ impl Default for NetbenchConfig {
             enabled: false,
             max_network_channel_size: 1000,
             netbench_service_threads: Some(2),
+            enable_compression: false,
 
             enable_direct_send_testing: false,
             direct_send_data_size: 100 * 1024, // 100 KB
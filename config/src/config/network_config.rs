@@ -127,6 +127,10 @@ pub struct NetworkConfig {
     pub max_parallel_deserialization_tasks: Option<usize>,
     /// Whether or not to enable latency aware peer dialing
     pub enable_latency_aware_dialing: bool,
+    /// Prioritization and bandwidth limits for outbound messages to each peer
+    pub outbound_traffic_config: OutboundTrafficConfig,
+    /// Reputation tracking and automatic banning of misbehaving peers
+    pub peer_reputation_config: PeerReputationConfig,
 }
 
 impl Default for NetworkConfig {
@@ -169,8 +173,14 @@ impl NetworkConfig {
             outbound_tx_buffer_size_bytes: None,
             max_parallel_deserialization_tasks: None,
             enable_latency_aware_dialing: true,
+            outbound_traffic_config: OutboundTrafficConfig::default(),
+            peer_reputation_config: PeerReputationConfig::default(),
         };
 
+        // Only peers on the public network are banned. The validator network is limited to
+        // the validator set, and the VFN network connects VFNs to their upstream validators.
+        config.peer_reputation_config.enable_peer_banning = network_id.is_public_network();
+
         // Configure the number of parallel deserialization tasks
         config.configure_num_deserialization_tasks();
 
@@ -351,6 +361,8 @@ pub enum DiscoveryMethod {
     Onchain,
     File(FileDiscovery),
     Rest(RestDiscovery),
+    Dns(DnsDiscovery),
+    Merged(MergedDiscovery),
     None,
 }
 
@@ -368,6 +380,45 @@ pub struct RestDiscovery {
     pub interval_secs: u64,
 }
 
+/// Discovers peers using DNS. The SRV records of `srv_name` list the
+/// host and port of each peer, and the TXT records of each host contain
+/// the peer's network public key (`pubkey=<hex>`) and, optionally, the
+/// peer id (`peer_id=<hex>`). If no peer id is given, it is derived from
+/// the public key.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DnsDiscovery {
+    pub srv_name: String,
+    pub interval_secs: u64,
+    /// The role assigned to all discovered peers
+    #[serde(default = "default_dns_peer_role")]
+    pub peer_role: PeerRole,
+}
+
+fn default_dns_peer_role() -> PeerRole {
+    PeerRole::Upstream
+}
+
+/// Merges the peers discovered by several sources into a single peer set.
+/// Sources with a higher priority take precedence (i.e., a peer discovered by
+/// several sources is taken from the highest priority one), and the peers of a source are dropped if the source has not
+/// successfully refreshed within `max_staleness_secs`.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct MergedDiscovery {
+    pub sources: Vec<MergedDiscoverySource>,
+    pub max_staleness_secs: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct MergedDiscoverySource {
+    /// The discovery method of the source (only file, rest and dns are supported)
+    pub method: DiscoveryMethod,
+    /// The priority of the source (higher values take precedence)
+    pub priority: u8,
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct RateLimitConfig {
@@ -392,6 +443,90 @@ impl Default for RateLimitConfig {
     }
 }
 
+/// The priority class of outbound messages for a protocol. Messages to a peer are
+/// written in priority order, and high priority messages are never throttled.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutboundPriority {
+    High,
+    Normal,
+    Low,
+}
+
+impl OutboundPriority {
+    /// Returns all priority classes, ordered from highest to lowest
+    pub fn all() -> &'static [OutboundPriority] {
+        &[
+            OutboundPriority::High,
+            OutboundPriority::Normal,
+            OutboundPriority::Low,
+        ]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutboundPriority::High => "high",
+            OutboundPriority::Normal => "normal",
+            OutboundPriority::Low => "low",
+        }
+    }
+}
+
+impl fmt::Display for OutboundPriority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutboundTrafficConfig {
+    /// Whether outbound messages to each peer are written in priority order (instead of
+    /// FIFO). This is disabled by default until prioritization is proven in production.
+    pub enable_prioritization: bool,
+    /// Overrides for the default priority class of protocols, keyed by protocol
+    /// name (e.g., "StorageServiceRpc")
+    pub protocol_priorities: HashMap<String, OutboundPriority>,
+    /// Bandwidth limit for normal and low priority messages sent to each peer (the
+    /// IP byte bucket is applied per peer). If not specified, no limit is applied.
+    pub peer_bandwidth_limit: Option<RateLimitConfig>,
+}
+
+impl Default for OutboundTrafficConfig {
+    fn default() -> Self {
+        Self {
+            enable_prioritization: false,
+            protocol_priorities: HashMap::new(),
+            peer_bandwidth_limit: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PeerReputationConfig {
+    /// Whether peers with a low reputation score are temporarily banned. This only
+    /// applies to the public network (peers on other networks are never banned).
+    pub enable_peer_banning: bool,
+    /// The score at (or below) which a peer is banned. Scores range from -100 to 100.
+    pub ban_threshold: i64,
+    /// The duration of the first ban for a peer (subsequent bans double in length)
+    pub ban_duration_secs: u64,
+    /// The maximum duration of a single ban
+    pub max_ban_duration_secs: u64,
+}
+
+impl Default for PeerReputationConfig {
+    fn default() -> Self {
+        Self {
+            enable_peer_banning: true,
+            ban_threshold: -50,
+            ban_duration_secs: 300,      // 5 minutes
+            max_ban_duration_secs: 3600, // 1 hour
+        }
+    }
+}
+
 pub type PeerSet = HashMap<PeerId, Peer>;
 
 // TODO: Combine with RoleType?
@@ -532,4 +667,48 @@ mod tests {
         network_config.configure_num_deserialization_tasks();
         assert_eq!(network_config.max_parallel_deserialization_tasks, Some(1));
     }
+
+    #[test]
+    fn test_parse_merged_discovery() {
+        let discovery_method: DiscoveryMethod = serde_yaml::from_str(
+            r#"
+            merged:
+                max_staleness_secs: 600
+                sources:
+                    - priority: 10
+                      method:
+                          dns:
+                              srv_name: "_aptos._tcp.fleet.example.com"
+                              interval_secs: 60
+                    - priority: 1
+                      method:
+                          file:
+                              path: "/opt/aptos/seeds.yaml"
+                              interval_secs: 30
+            "#,
+        )
+        .unwrap();
+
+        let expected_discovery_method = DiscoveryMethod::Merged(MergedDiscovery {
+            sources: vec![
+                MergedDiscoverySource {
+                    method: DiscoveryMethod::Dns(DnsDiscovery {
+                        srv_name: "_aptos._tcp.fleet.example.com".into(),
+                        interval_secs: 60,
+                        peer_role: PeerRole::Upstream,
+                    }),
+                    priority: 10,
+                },
+                MergedDiscoverySource {
+                    method: DiscoveryMethod::File(FileDiscovery {
+                        path: "/opt/aptos/seeds.yaml".into(),
+                        interval_secs: 30,
+                    }),
+                    priority: 1,
+                },
+            ],
+            max_staleness_secs: 600,
+        });
+        assert_eq!(discovery_method, expected_discovery_method);
+    }
 }
@@ -10,6 +10,7 @@ use crate::{
 };
 use aptos_consensus_types::block::Block;
 use aptos_executor_types::ExecutorResult;
+use aptos_logger::otlp_tracing::{record_block_id, record_txns, txn_span, TxnStage};
 use aptos_types::transaction::SignedTransaction;
 use std::sync::Arc;
 
@@ -36,8 +37,11 @@ impl BlockPreparer {
     }
 
     pub async fn prepare_block(&self, block: &Block) -> ExecutorResult<Vec<SignedTransaction>> {
+        let span = txn_span(TxnStage::BLOCK_PREPARE);
+        record_block_id(&span, block.id());
         let (txns, max_txns_from_block_to_execute) =
             self.payload_manager.get_transactions(block).await?;
+        record_txns(&span, txns.iter().map(|txn| txn.committed_hash()));
         let txn_filter = self.txn_filter.clone();
         let txn_deduper = self.txn_deduper.clone();
         let txn_shuffler = self.txn_shuffler.clone();
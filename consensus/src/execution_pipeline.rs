@@ -15,7 +15,10 @@ use aptos_executor_types::{
     ExecutorResult,
 };
 use aptos_experimental_runtimes::thread_manager::optimal_min_len;
-use aptos_logger::{debug, error};
+use aptos_logger::{
+    debug, error,
+    otlp_tracing::{record_block_id, record_txns, txn_span, Span, TxnStage},
+};
 use aptos_types::{
     block_executor::{config::BlockExecutorConfigFromOnchain, partitioner::ExecutableBlock},
     block_metadata_ext::BlockMetadataExt,
@@ -176,6 +179,9 @@ impl ExecutionPipeline {
         {
             let block_id = block.block_id;
             debug!("execute_stage received block {}.", block_id);
+            let span = txn_span(TxnStage::EXECUTION);
+            record_block_id(&span, block_id);
+            record_txns(&span, input_txns.iter().map(|txn| txn.committed_hash()));
             let executor = executor.clone();
             let state_checkpoint_output = monitor!(
                 "execute_block",
@@ -202,6 +208,7 @@ impl ExecutionPipeline {
                     parent_block_id,
                     state_checkpoint_output,
                     result_tx,
+                    span,
                 })
                 .expect("Failed to send block to ledger_apply stage.");
         }
@@ -218,6 +225,7 @@ impl ExecutionPipeline {
             parent_block_id,
             state_checkpoint_output,
             result_tx,
+            span,
         }) = block_rx.recv().await
         {
             debug!("ledger_apply stage received block {}.", block_id);
@@ -240,6 +248,8 @@ impl ExecutionPipeline {
                     "Failed to send back execution result for block {}: {:?}", block_id, err,
                 );
             });
+            // Execution of the block ends with its ledger update
+            drop(span);
         }
         debug!("ledger_apply stage quitting.");
     }
@@ -269,4 +279,6 @@ struct LedgerApplyCommand {
     parent_block_id: HashValue,
     state_checkpoint_output: ExecutorResult<StateCheckpointOutput>,
     result_tx: oneshot::Sender<ExecutorResult<PipelineExecutionResult>>,
+    // Span of the execution of the block, ended once the ledger is updated
+    span: Span,
 }
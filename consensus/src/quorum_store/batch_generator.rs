@@ -17,7 +17,10 @@ use aptos_consensus_types::{
     proof_of_store::{BatchId, BatchInfo},
 };
 use aptos_experimental_runtimes::thread_manager::optimal_min_len;
-use aptos_logger::prelude::*;
+use aptos_logger::{
+    otlp_tracing::{record_txns, txn_span, TxnStage},
+    prelude::*,
+};
 use aptos_mempool::QuorumStoreRequest;
 use aptos_types::{transaction::SignedTransaction, PeerId};
 use futures_channel::mpsc::Sender;
@@ -362,6 +365,8 @@ impl BatchGenerator {
         }
         counters::BATCH_CREATION_DURATION.observe_duration(self.last_end_batch_time.elapsed());
 
+        let span = txn_span(TxnStage::QUORUM_STORE_BATCH);
+        record_txns(&span, pulled_txns.iter().map(|txn| txn.committed_hash()));
         let bucket_compute_start = Instant::now();
         let expiry_time = aptos_infallible::duration_since_epoch().as_micros() as u64
             + self.config.batch_expiry_gap_when_init_usecs;
@@ -22,7 +22,10 @@ use aptos_consensus_types::{block::Block, common::Round, pipelined_block::Pipeli
 use aptos_crypto::HashValue;
 use aptos_executor_types::{BlockExecutorTrait, ExecutorResult, StateComputeResult};
 use aptos_infallible::RwLock;
-use aptos_logger::prelude::*;
+use aptos_logger::{
+    otlp_tracing::{record_block_ids, record_txns, txn_span, TxnStage},
+    prelude::*,
+};
 use aptos_types::{
     account_address::AccountAddress,
     block_executor::config::BlockExecutorConfigFromOnchain,
@@ -298,6 +301,14 @@ impl StateComputer for ExecutionProxy {
             subscribable_txn_events.extend(block.subscribable_events());
         }
 
+        let span = txn_span(TxnStage::COMMIT);
+        record_block_ids(&span, &block_ids);
+        record_txns(
+            &span,
+            txns.iter()
+                .filter_map(|txn| txn.try_as_signed_user_txn())
+                .map(|txn| txn.committed_hash()),
+        );
         let executor = self.executor.clone();
         let proof = finality_proof.clone();
         monitor!(
@@ -310,6 +321,7 @@ impl StateComputer for ExecutionProxy {
             .await
         )
         .expect("spawn_blocking failed");
+        drop(span);
 
         let blocks = blocks.to_vec();
         let wrapped_callback = move || {
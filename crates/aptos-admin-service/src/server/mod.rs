@@ -7,6 +7,7 @@ use aptos_consensus::{
 };
 use aptos_infallible::RwLock;
 use aptos_logger::info;
+use aptos_network::application::storage::PeersAndMetadata;
 use aptos_storage_interface::DbReaderWriter;
 use aptos_system_utils::utils::reply_with_status;
 #[cfg(target_os = "linux")]
@@ -26,6 +27,7 @@ use std::{
 use tokio::runtime::Runtime;
 
 mod consensus;
+mod network;
 
 #[derive(Default)]
 pub struct Context {
@@ -34,6 +36,7 @@ pub struct Context {
     aptos_db: RwLock<Option<Arc<DbReaderWriter>>>,
     consensus_db: RwLock<Option<Arc<StorageWriteProxy>>>,
     quorum_store_db: RwLock<Option<Arc<QuorumStoreDB>>>,
+    peers_and_metadata: RwLock<Option<Arc<PeersAndMetadata>>>,
 }
 
 impl Context {
@@ -49,6 +52,10 @@ impl Context {
         *self.consensus_db.write() = Some(consensus_db);
         *self.quorum_store_db.write() = Some(quorum_store_db);
     }
+
+    fn set_peers_and_metadata(&self, peers_and_metadata: Arc<PeersAndMetadata>) {
+        *self.peers_and_metadata.write() = Some(peers_and_metadata);
+    }
 }
 
 pub struct AdminService {
@@ -107,6 +114,10 @@ impl AdminService {
             .set_consensus_dbs(consensus_db, quorum_store_db)
     }
 
+    pub fn set_peers_and_metadata(&self, peers_and_metadata: Arc<PeersAndMetadata>) {
+        self.context.set_peers_and_metadata(peers_and_metadata)
+    }
+
     fn start(&self, address: SocketAddr, enabled: bool) {
         let context = self.context.clone();
         self.runtime.spawn(async move {
@@ -210,6 +221,17 @@ impl AdminService {
                     ))
                 }
             },
+            (hyper::Method::GET, "/debug/network/peer_reputations") => {
+                let peers_and_metadata = context.peers_and_metadata.read().clone();
+                if let Some(peers_and_metadata) = peers_and_metadata {
+                    network::handle_dump_peer_reputations_request(req, peers_and_metadata).await
+                } else {
+                    Ok(reply_with_status(
+                        StatusCode::NOT_FOUND,
+                        "Peers and metadata is not available.",
+                    ))
+                }
+            },
             _ => Ok(reply_with_status(StatusCode::NOT_FOUND, "Not found.")),
         }
     }
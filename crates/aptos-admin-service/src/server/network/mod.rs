@@ -0,0 +1,39 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_logger::info;
+use aptos_network::application::storage::PeersAndMetadata;
+use aptos_system_utils::utils::reply_with;
+use http::header::{HeaderValue, CONTENT_LENGTH};
+use hyper::{Body, Request, Response};
+use std::sync::Arc;
+
+pub async fn handle_dump_peer_reputations_request(
+    _req: Request<Body>,
+    peers_and_metadata: Arc<PeersAndMetadata>,
+) -> hyper::Result<Response<Body>> {
+    info!("Dumping peer reputations.");
+
+    let result = dump_peer_reputations(peers_and_metadata.as_ref());
+    let headers: Vec<(_, HeaderValue)> = vec![(CONTENT_LENGTH, HeaderValue::from(result.len()))];
+    Ok(reply_with(headers, result))
+}
+
+fn dump_peer_reputations(peers_and_metadata: &PeersAndMetadata) -> String {
+    let peer_reputations = peers_and_metadata.get_peer_reputations();
+    let num_banned_peers = peer_reputations
+        .iter()
+        .filter(|summary| summary.remaining_ban.is_some())
+        .count();
+
+    let mut body = String::new();
+    body.push_str(&format!(
+        "Peers with reputations: {}, banned peers: {}\n\n",
+        peer_reputations.len(),
+        num_banned_peers
+    ));
+    for summary in peer_reputations {
+        body.push_str(&format!("[{summary}]\n"));
+    }
+    body
+}
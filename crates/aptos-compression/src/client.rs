@@ -4,12 +4,13 @@
 /// A simple enum for identifying clients of the compression crate. This
 /// allows us to provide a runtime breakdown of compression metrics for
 /// each client.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum CompressionClient {
     Consensus,
     DKG,
     JWKConsensus,
     Mempool,
+    Netbench,
     StateSync,
 }
 
@@ -21,6 +22,7 @@ impl CompressionClient {
             Self::DKG => "dkg",
             Self::JWKConsensus => "jwk_consensus",
             Self::Mempool => "mempool",
+            Self::Netbench => "netbench",
             Self::StateSync => "state_sync",
         }
     }
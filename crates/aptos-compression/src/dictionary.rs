@@ -0,0 +1,99 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{client::CompressionClient, Error, Error::CompressionError};
+use aptos_infallible::RwLock;
+use once_cell::sync::Lazy;
+use std::{collections::HashMap, path::Path, sync::Arc};
+
+/// The registered compression dictionaries (indexed by client). Only
+/// zstd compression makes use of dictionaries.
+static DICTIONARIES: Lazy<RwLock<HashMap<CompressionClient, Arc<CompressionDictionary>>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// A zstd compression dictionary. Dictionaries are trained on samples of
+/// representative data (e.g., BCS-encoded blocks or transactions) and
+/// significantly improve the compression ratio of small messages.
+///
+/// Note: every dictionary carries a unique identifier (embedded in the
+/// dictionary header by the trainer). The identifier is written to each
+/// frame compressed with the dictionary, so that the receiver can select
+/// the same dictionary for decompression.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CompressionDictionary {
+    id: u32,
+    bytes: Vec<u8>,
+}
+
+impl CompressionDictionary {
+    /// Creates a new dictionary from the given (trained) dictionary bytes
+    pub fn new(bytes: Vec<u8>) -> Result<Self, Error> {
+        let id = match zstd::zstd_safe::get_dict_id_from_dict(&bytes) {
+            Some(id) => id.get(),
+            None => {
+                return Err(CompressionError(
+                    "The given dictionary does not have a valid identifier!".into(),
+                ))
+            },
+        };
+        Ok(Self { id, bytes })
+    }
+
+    /// Loads a (trained) dictionary from the file at the given path
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let bytes = std::fs::read(path.as_ref()).map_err(|error| {
+            CompressionError(format!(
+                "Failed to read the dictionary file {:?}: {}",
+                path.as_ref(),
+                error
+            ))
+        })?;
+        Self::new(bytes)
+    }
+
+    /// Trains a new dictionary on the given samples. The resulting
+    /// dictionary will be at most `max_size` bytes.
+    pub fn train<S: AsRef<[u8]>>(samples: &[S], max_size: usize) -> Result<Self, Error> {
+        let bytes = zstd::dict::from_samples(samples, max_size).map_err(|error| {
+            CompressionError(format!("Failed to train the dictionary: {}", error))
+        })?;
+        Self::new(bytes)
+    }
+
+    /// Returns the unique identifier of the dictionary
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the raw bytes of the dictionary
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+/// Registers the dictionary for the given client. All subsequent zstd
+/// compression operations for the client will use the dictionary. Frames
+/// compressed with the dictionary can be decompressed by any client
+/// (as long as the same dictionary is registered locally).
+pub fn register_dictionary(client: CompressionClient, dictionary: CompressionDictionary) {
+    DICTIONARIES.write().insert(client, Arc::new(dictionary));
+}
+
+/// Removes the registered dictionary for the given client (if any)
+pub fn unregister_dictionary(client: &CompressionClient) {
+    DICTIONARIES.write().remove(client);
+}
+
+/// Returns the registered dictionary for the given client (if any)
+pub(crate) fn get_dictionary(client: &CompressionClient) -> Option<Arc<CompressionDictionary>> {
+    DICTIONARIES.read().get(client).cloned()
+}
+
+/// Returns the registered dictionary with the given identifier (if any)
+pub(crate) fn get_dictionary_by_id(id: u32) -> Option<Arc<CompressionDictionary>> {
+    DICTIONARIES
+        .read()
+        .values()
+        .find(|dictionary| dictionary.id() == id)
+        .cloned()
+}
@@ -7,19 +7,25 @@ use crate::{
 };
 use aptos_logger::prelude::*;
 use lz4::block::CompressionMode;
+use serde::{Deserialize, Serialize};
 use std::time::Instant;
 use thiserror::Error;
 
 /// This crate provides a simple library interface for data compression.
 /// It is useful for compressing large data chunks that are
 /// sent across the network (e.g., by state sync and consensus).
-/// Internally, it uses LZ4 in fast mode to compress the data.
-/// See <https://github.com/10xGenomics/lz4-rs> for more information.
+/// Two compression algorithms are supported:
+/// 1. LZ4 in fast mode (the default).
+///    See <https://github.com/10xGenomics/lz4-rs> for more information.
+/// 2. Zstd, either plain or using a dictionary trained on representative
+///    data (e.g., BCS-encoded blocks and transactions). See the
+///    `dictionary` module and <https://github.com/gyscos/zstd-rs>.
 ///
 /// Note: the crate also exposes some basic compression metrics
 /// that can be used to track the cumulative compression ratio
 /// and compression/decompression durations during the runtime.
 pub mod client;
+pub mod dictionary;
 mod metrics;
 #[cfg(test)]
 mod tests;
@@ -28,6 +34,21 @@ mod tests;
 /// This was determined anecdotally.
 const ACCELERATION_PARAMETER: i32 = 1;
 
+/// The zstd compression level to use. Low levels favour speed
+/// over compression ratio (which suits latency sensitive network
+/// messages). This was determined anecdotally.
+const ZSTD_COMPRESSION_LEVEL: i32 = 3;
+
+/// The compression algorithms supported by the crate
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompressionAlgorithm {
+    #[default]
+    Lz4,
+    Zstd,
+    ZstdWithDictionary, // Requires a registered dictionary for the client
+}
+
 /// A useful wrapper for representing compressed data
 pub type CompressedData = Vec<u8>;
 
@@ -40,11 +61,30 @@ pub enum Error {
     DecompressionError(String),
 }
 
-/// Compresses the raw data stream
+/// Compresses the raw data stream (using LZ4)
 pub fn compress(
     raw_data: Vec<u8>,
     client: CompressionClient,
     max_bytes: usize,
+) -> Result<CompressedData, Error> {
+    compress_with_algorithm(raw_data, CompressionAlgorithm::Lz4, client, max_bytes)
+}
+
+/// Decompresses the compressed data stream (using LZ4)
+pub fn decompress(
+    compressed_data: &CompressedData,
+    client: CompressionClient,
+    max_size: usize,
+) -> Result<Vec<u8>, Error> {
+    decompress_with_algorithm(compressed_data, CompressionAlgorithm::Lz4, client, max_size)
+}
+
+/// Compresses the raw data stream using the specified algorithm
+pub fn compress_with_algorithm(
+    raw_data: Vec<u8>,
+    algorithm: CompressionAlgorithm,
+    client: CompressionClient,
+    max_bytes: usize,
 ) -> Result<CompressedData, Error> {
     // Start the compression timer
     let start_time = Instant::now();
@@ -60,8 +100,21 @@ pub fn compress(
     }
 
     // Compress the data
-    let compression_mode = CompressionMode::FAST(ACCELERATION_PARAMETER);
-    let compressed_data = match lz4::block::compress(&raw_data, Some(compression_mode), true) {
+    let compression_result = match algorithm {
+        CompressionAlgorithm::Lz4 => lz4_compress(&raw_data),
+        CompressionAlgorithm::Zstd => zstd_compress(&raw_data, None),
+        CompressionAlgorithm::ZstdWithDictionary => match dictionary::get_dictionary(&client) {
+            Some(dictionary) => zstd_compress(&raw_data, Some(&dictionary)),
+            None => {
+                let error_string = format!(
+                    "No compression dictionary registered for: {}",
+                    client.get_label()
+                );
+                return create_compression_error(&client, error_string);
+            },
+        },
+    };
+    let compressed_data = match compression_result {
         Ok(compressed_data) => compressed_data,
         Err(error) => {
             let error_string = format!("Failed to compress the data: {}", error);
@@ -88,29 +141,44 @@ pub fn compress(
     Ok(compressed_data)
 }
 
-/// Decompresses the compressed data stream
-pub fn decompress(
+/// Decompresses the compressed data stream using the specified algorithm
+pub fn decompress_with_algorithm(
     compressed_data: &CompressedData,
+    algorithm: CompressionAlgorithm,
     client: CompressionClient,
     max_size: usize,
 ) -> Result<Vec<u8>, Error> {
     // Start the decompression timer
     let start_time = Instant::now();
 
-    // Check size of the data and initialize raw_data
-    let decompressed_size = match get_decompressed_size(compressed_data, max_size) {
+    // Check size of the data
+    let decompressed_size = match algorithm {
+        CompressionAlgorithm::Lz4 => get_decompressed_size(compressed_data, max_size),
+        CompressionAlgorithm::Zstd | CompressionAlgorithm::ZstdWithDictionary => {
+            get_zstd_decompressed_size(compressed_data, max_size)
+        },
+    };
+    let decompressed_size = match decompressed_size {
         Ok(size) => size,
         Err(error) => {
             let error_string = format!("Failed to get decompressed size: {}", error);
             return create_decompression_error(&client, error_string);
         },
     };
-    let mut raw_data = vec![0u8; decompressed_size];
 
     // Decompress the data
-    if let Err(error) = lz4::block::decompress_to_buffer(compressed_data, None, &mut raw_data) {
-        let error_string = format!("Failed to decompress the data: {}", error);
-        return create_decompression_error(&client, error_string);
+    let decompression_result = match algorithm {
+        CompressionAlgorithm::Lz4 => lz4_decompress(compressed_data, decompressed_size),
+        CompressionAlgorithm::Zstd | CompressionAlgorithm::ZstdWithDictionary => {
+            zstd_decompress(compressed_data, decompressed_size)
+        },
+    };
+    let raw_data = match decompression_result {
+        Ok(raw_data) => raw_data,
+        Err(error) => {
+            let error_string = format!("Failed to decompress the data: {}", error);
+            return create_decompression_error(&client, error_string);
+        },
     };
 
     // Stop the timer and update the metrics
@@ -120,6 +188,63 @@ pub fn decompress(
     Ok(raw_data)
 }
 
+/// Compresses the given data using LZ4 (in fast mode). The
+/// compressed data is prefixed with the original data size.
+fn lz4_compress(raw_data: &[u8]) -> std::io::Result<CompressedData> {
+    let compression_mode = CompressionMode::FAST(ACCELERATION_PARAMETER);
+    lz4::block::compress(raw_data, Some(compression_mode), true)
+}
+
+/// Decompresses the given LZ4 data into a buffer of the specified size
+fn lz4_decompress(
+    compressed_data: &CompressedData,
+    decompressed_size: usize,
+) -> std::io::Result<Vec<u8>> {
+    let mut raw_data = vec![0u8; decompressed_size];
+    lz4::block::decompress_to_buffer(compressed_data, None, &mut raw_data)?;
+    Ok(raw_data)
+}
+
+/// Compresses the given data using zstd. If a dictionary is given, the
+/// dictionary is used for compression (and its identifier is recorded
+/// in the zstd frame header).
+fn zstd_compress(
+    raw_data: &[u8],
+    dictionary: Option<&dictionary::CompressionDictionary>,
+) -> std::io::Result<CompressedData> {
+    let mut compressor = match dictionary {
+        Some(dictionary) => {
+            zstd::bulk::Compressor::with_dictionary(ZSTD_COMPRESSION_LEVEL, dictionary.bytes())?
+        },
+        None => zstd::bulk::Compressor::new(ZSTD_COMPRESSION_LEVEL)?,
+    };
+    compressor.compress(raw_data)
+}
+
+/// Decompresses the given zstd data into a buffer of the specified size.
+/// If the frame was compressed using a dictionary, the dictionary is
+/// looked up (by identifier) from the registered dictionaries.
+fn zstd_decompress(
+    compressed_data: &CompressedData,
+    decompressed_size: usize,
+) -> std::io::Result<Vec<u8>> {
+    let dictionary_id = zstd::zstd_safe::get_dict_id_from_frame(compressed_data);
+    let mut decompressor = match dictionary_id {
+        Some(dictionary_id) => {
+            let dictionary =
+                dictionary::get_dictionary_by_id(dictionary_id.get()).ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::NotFound,
+                        format!("Unknown compression dictionary: {}", dictionary_id),
+                    )
+                })?;
+            zstd::bulk::Decompressor::with_dictionary(dictionary.bytes())?
+        },
+        None => zstd::bulk::Decompressor::new()?,
+    };
+    decompressor.decompress(compressed_data, decompressed_size)
+}
+
 /// A simple utility function that wraps the given error string in a compression error
 fn create_compression_error(
     client: &CompressionClient,
@@ -183,6 +308,40 @@ fn get_decompressed_size(
     Ok(size)
 }
 
+/// Returns the decompressed size of the given zstd frame. The size
+/// is read from the frame header (which is always populated by the
+/// compressor) and verified against the max size limit.
+fn get_zstd_decompressed_size(
+    compressed_data: &CompressedData,
+    max_size: usize,
+) -> Result<usize, Error> {
+    // Parse the content size from the frame header
+    let size = match zstd::zstd_safe::get_frame_content_size(compressed_data) {
+        Ok(Some(size)) => size,
+        Ok(None) => {
+            return Err(DecompressionError(
+                "The zstd frame does not specify a content size!".into(),
+            ))
+        },
+        Err(error) => {
+            return Err(DecompressionError(format!(
+                "Failed to parse the zstd frame header! Error: {:?}",
+                error
+            )))
+        },
+    };
+
+    // Ensure that the size is not greater than the max size limit
+    if size > max_size as u64 {
+        return Err(DecompressionError(format!(
+            "Parsed zstd content size is too big: {} > {}",
+            size, max_size
+        )));
+    }
+
+    Ok(size as usize)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -217,4 +376,38 @@ mod test {
         let result = get_decompressed_size(&compressed_data, max_compression_size);
         assert_eq!(result.unwrap(), raw_data.len());
     }
+
+    #[test]
+    fn test_get_zstd_decompressed_size() {
+        // Create some test data
+        let max_compression_size = 100;
+
+        // Verify that an error is returned for invalid frames
+        let result = get_zstd_decompressed_size(&vec![0u8; 3], max_compression_size);
+        assert!(result.is_err());
+
+        // Verify that an error is returned when the content size is too large
+        let raw_data = vec![0u8; max_compression_size + 1];
+        let compressed_data = compress_with_algorithm(
+            raw_data,
+            CompressionAlgorithm::Zstd,
+            CompressionClient::StateSync,
+            max_compression_size + 1,
+        )
+        .unwrap();
+        let result = get_zstd_decompressed_size(&compressed_data, max_compression_size);
+        assert!(result.is_err());
+
+        // Verify that the correct decompressed size is returned
+        let raw_data = vec![0u8; max_compression_size];
+        let compressed_data = compress_with_algorithm(
+            raw_data.clone(),
+            CompressionAlgorithm::Zstd,
+            CompressionClient::StateSync,
+            max_compression_size,
+        )
+        .unwrap();
+        let result = get_zstd_decompressed_size(&compressed_data, max_compression_size);
+        assert_eq!(result.unwrap(), raw_data.len());
+    }
 }
@@ -1,7 +1,9 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::CompressionClient;
+use crate::{
+    dictionary, dictionary::CompressionDictionary, CompressionAlgorithm, CompressionClient,
+};
 use aptos_crypto::{ed25519::Ed25519PrivateKey, hash::HashValue, PrivateKey, SigningKey, Uniform};
 use aptos_types::{
     account_address::AccountAddress,
@@ -74,25 +76,115 @@ fn test_compression_limits() {
     assert!(maybe_decompressed_bytes.is_err());
 }
 
-/// Ensures that the given object can be compressed and decompressed successfully
-/// when BCS encoded.
-fn test_compress_and_decompress<T: Debug + DeserializeOwned + PartialEq + Serialize>(object: T) {
-    let bcs_encoded_bytes = bcs::to_bytes(&object).unwrap();
-    let compressed_bytes = crate::compress(
-        bcs_encoded_bytes,
-        CompressionClient::StateSync,
+#[test]
+fn test_dictionary_compression() {
+    // Train a dictionary on BCS-encoded ledger infos
+    let samples: Vec<_> = (0..1000)
+        .map(|epoch| bcs::to_bytes(&create_test_ledger_info_with_sigs(epoch, epoch * 100)).unwrap())
+        .collect();
+    let dictionary = CompressionDictionary::train(&samples, 4 * 1024).unwrap();
+    let dictionary_id = dictionary.id();
+
+    // Verify that dictionary compression fails without a registered dictionary
+    let client = CompressionClient::Consensus;
+    let raw_bytes = bcs::to_bytes(&create_test_ledger_info_with_sigs(5000, 500_000)).unwrap();
+    let maybe_compressed_bytes = crate::compress_with_algorithm(
+        raw_bytes.clone(),
+        CompressionAlgorithm::ZstdWithDictionary,
+        client,
+        MAX_COMPRESSION_SIZE,
+    );
+    assert!(maybe_compressed_bytes.is_err());
+
+    // Register the dictionary and compress a new ledger info
+    dictionary::register_dictionary(client, dictionary);
+    let compressed_bytes = crate::compress_with_algorithm(
+        raw_bytes.clone(),
+        CompressionAlgorithm::ZstdWithDictionary,
+        client,
+        MAX_COMPRESSION_SIZE,
+    )
+    .unwrap();
+
+    // Verify that the dictionary was used for compression
+    let frame_dictionary_id = zstd::zstd_safe::get_dict_id_from_frame(&compressed_bytes);
+    assert_eq!(frame_dictionary_id.unwrap().get(), dictionary_id);
+
+    // Verify that plain zstd compression ignores the registered dictionary
+    let plain_compressed_bytes = crate::compress_with_algorithm(
+        raw_bytes.clone(),
+        CompressionAlgorithm::Zstd,
+        client,
         MAX_COMPRESSION_SIZE,
     )
     .unwrap();
-    let decompressed_bytes = crate::decompress(
+    assert!(zstd::zstd_safe::get_dict_id_from_frame(&plain_compressed_bytes).is_none());
+
+    // Verify that the data can be decompressed
+    let decompressed_bytes = crate::decompress_with_algorithm(
         &compressed_bytes,
-        CompressionClient::StateSync,
+        CompressionAlgorithm::ZstdWithDictionary,
+        client,
         MAX_COMPRESSION_SIZE,
     )
     .unwrap();
-    let decoded_object = bcs::from_bytes::<T>(&decompressed_bytes).unwrap();
+    assert_eq!(raw_bytes, decompressed_bytes);
+
+    // Unregister the dictionary and verify that decompression now fails
+    dictionary::unregister_dictionary(&client);
+    let maybe_decompressed_bytes = crate::decompress_with_algorithm(
+        &compressed_bytes,
+        CompressionAlgorithm::ZstdWithDictionary,
+        client,
+        MAX_COMPRESSION_SIZE,
+    );
+    assert!(maybe_decompressed_bytes.is_err());
+}
+
+#[test]
+fn test_dictionary_from_file() {
+    // Train a dictionary and write it to a file
+    let samples: Vec<_> = (0..1000)
+        .map(|epoch| bcs::to_bytes(&create_test_ledger_info_with_sigs(epoch, epoch * 100)).unwrap())
+        .collect();
+    let dictionary = CompressionDictionary::train(&samples, 4 * 1024).unwrap();
+    let temp_path = aptos_temppath::TempPath::new();
+    std::fs::write(temp_path.path(), dictionary.bytes()).unwrap();
+
+    // Verify that the dictionary can be loaded from the file
+    let loaded_dictionary = CompressionDictionary::from_file(temp_path.path()).unwrap();
+    assert_eq!(loaded_dictionary, dictionary);
+
+    // Verify that loading a missing or invalid file fails
+    let missing_path = aptos_temppath::TempPath::new();
+    assert!(CompressionDictionary::from_file(missing_path.path()).is_err());
+    std::fs::write(missing_path.path(), b"invalid dictionary").unwrap();
+    assert!(CompressionDictionary::from_file(missing_path.path()).is_err());
+}
 
-    assert_eq!(object, decoded_object);
+/// Ensures that the given object can be compressed and decompressed successfully
+/// when BCS encoded (using all compression algorithms).
+fn test_compress_and_decompress<T: Debug + DeserializeOwned + PartialEq + Serialize>(object: T) {
+    for algorithm in [CompressionAlgorithm::Lz4, CompressionAlgorithm::Zstd] {
+        let bcs_encoded_bytes = bcs::to_bytes(&object).unwrap();
+        let compressed_bytes = crate::compress_with_algorithm(
+            bcs_encoded_bytes,
+            algorithm,
+            CompressionClient::StateSync,
+            MAX_COMPRESSION_SIZE,
+        )
+        .unwrap();
+        let decompressed_bytes = crate::decompress_with_algorithm(
+            &compressed_bytes,
+            algorithm,
+            CompressionClient::StateSync,
+            MAX_COMPRESSION_SIZE,
+        )
+        .unwrap();
+        let decoded_object = bcs::from_bytes::<T>(&decompressed_bytes).unwrap();
+
+        assert_eq!(object, decoded_object);
+    }
 }
 
 /// Creates a test epoch change proof
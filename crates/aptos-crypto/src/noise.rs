@@ -392,9 +392,11 @@ impl NoiseConfig {
             .open_in_place(nonce, Aad::from(&h), &mut in_out)
             .map_err(|_| NoiseError::Decrypt)?;
 
+        mix_hash(&mut h, &received_message[offset..]);
+
         // split
         let (k1, k2) = hkdf(&ck, None)?;
-        let session = NoiseSession::new(k1, k2, rs);
+        let session = NoiseSession::new(k1, k2, rs, h);
 
         //
         Ok((plaintext.to_vec(), session))
@@ -547,7 +549,7 @@ impl NoiseConfig {
 
         // split
         let (k1, k2) = hkdf(&ck, None)?;
-        let session = NoiseSession::new(k2, k1, rs);
+        let session = NoiseSession::new(k2, k1, rs, h);
 
         //
         Ok(session)
@@ -595,10 +597,17 @@ pub struct NoiseSession {
     read_key: Vec<u8>,
     /// associated nonce (in practice the maximum u64 value cannot be reached)
     read_nonce: u64,
+    /// the hash of the handshake transcript (identical for both peers)
+    handshake_hash: Vec<u8>,
 }
 
 impl NoiseSession {
-    fn new(write_key: Vec<u8>, read_key: Vec<u8>, remote_public_key: x25519::PublicKey) -> Self {
+    fn new(
+        write_key: Vec<u8>,
+        read_key: Vec<u8>,
+        remote_public_key: x25519::PublicKey,
+        handshake_hash: Vec<u8>,
+    ) -> Self {
         Self {
             valid: true,
             remote_public_key,
@@ -606,6 +615,7 @@ impl NoiseSession {
             write_nonce: 0,
             read_key,
             read_nonce: 0,
+            handshake_hash,
         }
     }
 
@@ -616,6 +626,7 @@ impl NoiseSession {
             vec![0u8; 32],
             vec![0u8; 32],
             [0u8; x25519::PUBLIC_KEY_SIZE].into(),
+            vec![0u8; 32],
         )
     }
 
@@ -624,6 +635,12 @@ impl NoiseSession {
         self.remote_public_key
     }
 
+    /// obtain the handshake hash, which uniquely identifies the session
+    /// and can be used for channel binding (see section 11.2 of the specification)
+    pub fn handshake_hash(&self) -> &[u8] {
+        &self.handshake_hash
+    }
+
     /// encrypts a message for the other peers (post-handshake)
     /// the function encrypts in place, and returns the authentication tag as result
     pub fn write_message_in_place(&mut self, message: &mut [u8]) -> Result<Vec<u8>, NoiseError> {
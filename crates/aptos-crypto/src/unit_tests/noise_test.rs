@@ -79,6 +79,12 @@ fn simple_handshake() {
             .unwrap();
         assert_eq!(received_payload, b"payload2");
 
+        // both peers derive the same handshake hash
+        assert_eq!(
+            initiator_session.handshake_hash(),
+            responder_session.handshake_hash()
+        );
+
         // session usage
         let mut message_sent = b"payload".to_vec();
         for i in 0..10 {
@@ -6,26 +6,33 @@ mod google_captcha;
 mod ip_blocklist;
 mod magic_header;
 mod memory_ratelimit;
+mod proof_of_work;
 mod redis_ratelimit;
 mod referer_blocklist;
 mod tap_captcha;
 
-pub use self::tap_captcha::CaptchaManager;
 use self::{
     auth_token::AuthTokenChecker,
     google_captcha::{CaptchaChecker as GoogleCaptchaChecker, GoogleCaptchaCheckerConfig},
     ip_blocklist::IpBlocklistChecker,
     magic_header::{MagicHeaderChecker, MagicHeaderCheckerConfig},
     memory_ratelimit::{MemoryRatelimitChecker, MemoryRatelimitCheckerConfig},
+    proof_of_work::ProofOfWorkChecker,
     redis_ratelimit::{RedisRatelimitChecker, RedisRatelimitCheckerConfig},
     referer_blocklist::RefererBlocklistChecker,
     tap_captcha::{TapCaptchaChecker, TapCaptchaCheckerConfig},
 };
+pub use self::{
+    proof_of_work::{
+        solution_is_valid, solve_challenge, ProofOfWorkCheckerConfig, ProofOfWorkManager,
+    },
+    tap_captcha::CaptchaManager,
+};
 use crate::{
     common::{IpRangeManagerConfig, ListManagerConfig},
     endpoints::{AptosTapError, RejectionReason},
 };
-use anyhow::Result;
+use anyhow::{Context, Result};
 use aptos_sdk::types::account_address::AccountAddress;
 use async_trait::async_trait;
 use enum_dispatch::enum_dispatch;
@@ -96,6 +103,10 @@ pub enum CheckerConfig {
     /// Basic in memory ratelimiter that allows a single successful request per IP.
     MemoryRatelimit(MemoryRatelimitCheckerConfig),
 
+    /// Requires the solution to a proof of work challenge, with the difficulty
+    /// going up for IPs that have made many requests recently.
+    ProofOfWork(ProofOfWorkCheckerConfig),
+
     /// Ratelimiter that uses Redis.
     RedisRatelimit(RedisRatelimitCheckerConfig),
 
@@ -107,7 +118,11 @@ pub enum CheckerConfig {
 }
 
 impl CheckerConfig {
-    pub async fn build(self, captcha_manager: Arc<Mutex<CaptchaManager>>) -> Result<Checker> {
+    pub async fn build(
+        self,
+        captcha_manager: Arc<Mutex<CaptchaManager>>,
+        proof_of_work_manager: Option<Arc<Mutex<ProofOfWorkManager>>>,
+    ) -> Result<Checker> {
         Ok(match self {
             CheckerConfig::AuthToken(config) => Checker::from(AuthTokenChecker::new(config)?),
             CheckerConfig::GoogleCaptcha(config) => {
@@ -118,6 +133,9 @@ impl CheckerConfig {
             CheckerConfig::MemoryRatelimit(config) => {
                 Checker::from(MemoryRatelimitChecker::new(config))
             },
+            CheckerConfig::ProofOfWork(_) => Checker::from(ProofOfWorkChecker::new(
+                proof_of_work_manager.context("The ProofOfWorkManager was not built")?,
+            )),
             CheckerConfig::RedisRatelimit(config) => {
                 Checker::from(RedisRatelimitChecker::new(config).await?)
             },
@@ -139,6 +157,7 @@ pub enum Checker {
     IpBlocklistChecker,
     MagicHeaderChecker,
     MemoryRatelimitChecker,
+    ProofOfWorkChecker,
     RedisRatelimitChecker,
     RefererBlocklistChecker,
     TapCaptchaChecker,
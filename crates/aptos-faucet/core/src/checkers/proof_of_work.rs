@@ -0,0 +1,272 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Proof of work is an alternative to captchas that works for headless clients
+//! like CI. The client requests a challenge, finds a nonce such that the SHA3-256
+//! hash of `<challenge>:<nonce>` starts with at least `difficulty` zero bits, and
+//! includes the challenge and nonce in the call to `/fund`. The difficulty goes
+//! up for IPs that have requested many challenges recently.
+
+use super::{CheckerData, CheckerTrait};
+use crate::{
+    endpoints::{
+        AptosTapError, AptosTapErrorCode, RejectionReason, RejectionReasonCode,
+        PROOF_OF_WORK_CHALLENGE, PROOF_OF_WORK_NONCE,
+    },
+    helpers::get_current_time_secs,
+};
+use anyhow::{bail, Result};
+use aptos_sdk::crypto::HashValue;
+use async_trait::async_trait;
+use futures::lock::Mutex;
+use lru::LruCache;
+use serde::{Deserialize, Serialize};
+use std::{collections::VecDeque, net::IpAddr, num::NonZeroUsize, sync::Arc};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ProofOfWorkCheckerConfig {
+    /// The number of leading zero bits the hash must have for an IP that
+    /// hasn't requested any challenges recently.
+    pub base_difficulty: u8,
+
+    /// The difficulty never goes above this, no matter how many challenges
+    /// an IP has requested recently.
+    pub max_difficulty: u8,
+
+    /// The difficulty goes up by one bit (doubling the expected work) for
+    /// every this many challenges an IP has requested within the window.
+    #[serde(default = "ProofOfWorkCheckerConfig::default_requests_per_difficulty_increase")]
+    pub requests_per_difficulty_increase: u32,
+
+    /// How far back we look at the challenges an IP has requested.
+    #[serde(default = "ProofOfWorkCheckerConfig::default_window_secs")]
+    pub window_secs: u64,
+
+    /// How long a client has to solve a challenge.
+    #[serde(default = "ProofOfWorkCheckerConfig::default_challenge_ttl_secs")]
+    pub challenge_ttl_secs: u64,
+
+    /// To avoid OOMing the server, we set a limit on how many challenges and
+    /// IPs we keep track of. The least recently used ones are thrown out.
+    #[serde(default = "ProofOfWorkCheckerConfig::default_max_entries_in_map")]
+    pub max_entries_in_map: NonZeroUsize,
+}
+
+impl ProofOfWorkCheckerConfig {
+    fn default_requests_per_difficulty_increase() -> u32 {
+        5
+    }
+
+    fn default_window_secs() -> u64 {
+        3600
+    }
+
+    fn default_challenge_ttl_secs() -> u64 {
+        300
+    }
+
+    fn default_max_entries_in_map() -> NonZeroUsize {
+        NonZeroUsize::new(1000000).unwrap()
+    }
+}
+
+pub struct ProofOfWorkChecker {
+    /// Reference to the one proof of work manager. This must be passed in
+    /// because we need to be able to use it from the challenge endpoint too.
+    proof_of_work_manager: Arc<Mutex<ProofOfWorkManager>>,
+}
+
+impl ProofOfWorkChecker {
+    pub fn new(proof_of_work_manager: Arc<Mutex<ProofOfWorkManager>>) -> Self {
+        Self {
+            proof_of_work_manager,
+        }
+    }
+}
+
+#[async_trait]
+impl CheckerTrait for ProofOfWorkChecker {
+    async fn check(
+        &self,
+        data: CheckerData,
+        dry_run: bool,
+    ) -> Result<Vec<RejectionReason>, AptosTapError> {
+        let mut values = vec![];
+        for header in [PROOF_OF_WORK_CHALLENGE, PROOF_OF_WORK_NONCE] {
+            match data.headers.get(header) {
+                Some(header_value) => values.push(header_value.to_str().map_err(|e| {
+                    AptosTapError::new_with_error_code(e, AptosTapErrorCode::InvalidRequest)
+                })?),
+                None => {
+                    return Ok(vec![RejectionReason::new(
+                        format!("Proof of work header {} not found", header),
+                        RejectionReasonCode::ProofOfWorkInvalid,
+                    )])
+                },
+            }
+        }
+        let (challenge, nonce) = (values[0], values[1]);
+
+        let nonce = match nonce.parse::<u64>() {
+            Ok(nonce) => nonce,
+            Err(e) => {
+                return Ok(vec![RejectionReason::new(
+                    format!("Proof of work nonce not a number: {:#}", e),
+                    RejectionReasonCode::ProofOfWorkInvalid,
+                )])
+            },
+        };
+
+        // In dry run mode we don't use up the challenge, so the client can
+        // still use it for the real request.
+        if let Err(e) = self.proof_of_work_manager.lock().await.check_solution(
+            challenge,
+            nonce,
+            data.source_ip,
+            data.time_request_received_secs,
+            !dry_run,
+        ) {
+            return Ok(vec![RejectionReason::new(
+                format!("{:#}", e),
+                RejectionReasonCode::ProofOfWorkInvalid,
+            )]);
+        }
+
+        Ok(vec![])
+    }
+
+    fn cost(&self) -> u8 {
+        3
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ChallengeInfo {
+    /// Challenges can only be solved for the IP that requested them.
+    source_ip: IpAddr,
+    difficulty: u8,
+    expiration_time_secs: u64,
+}
+
+/// ProofOfWorkManager is responsible for creating proof of work challenges
+/// and later checking the solutions. Like the CaptchaManager, we do this in
+/// memory, so clients should use cookies to benefit from cookie based sticky
+/// routing.
+pub struct ProofOfWorkManager {
+    config: ProofOfWorkCheckerConfig,
+
+    /// Map from a challenge we've handed out to the information we need to
+    /// check the solution to it.
+    challenges: LruCache<String, ChallengeInfo>,
+
+    /// Map from IP to the times (in secs) it requested challenges within the
+    /// window, oldest first. We use this to scale up the difficulty.
+    ip_to_recent_requests: LruCache<IpAddr, VecDeque<u64>>,
+}
+
+impl ProofOfWorkManager {
+    pub fn new(config: ProofOfWorkCheckerConfig) -> Self {
+        Self {
+            challenges: LruCache::new(config.max_entries_in_map),
+            ip_to_recent_requests: LruCache::new(config.max_entries_in_map),
+            config,
+        }
+    }
+
+    /// Create a new challenge for the IP. Returns the challenge and the
+    /// difficulty the client must solve it with.
+    pub fn create_challenge(&mut self, source_ip: IpAddr) -> (String, u8) {
+        let now_secs = get_current_time_secs();
+
+        // Throw out the requests that have fallen out of the window and
+        // record this one.
+        let recent_requests = self
+            .ip_to_recent_requests
+            .get_or_insert_mut(source_ip, VecDeque::new);
+        while recent_requests
+            .front()
+            .is_some_and(|time_secs| time_secs + self.config.window_secs <= now_secs)
+        {
+            recent_requests.pop_front();
+        }
+        let num_recent_requests = recent_requests.len() as u32;
+        recent_requests.push_back(now_secs);
+
+        let difficulty = self.get_difficulty(num_recent_requests);
+        let challenge = HashValue::random().to_hex();
+        self.challenges.put(
+            challenge.clone(),
+            ChallengeInfo {
+                source_ip,
+                difficulty,
+                expiration_time_secs: now_secs + self.config.challenge_ttl_secs,
+            },
+        );
+
+        (challenge, difficulty)
+    }
+
+    /// The difficulty for an IP that has already requested this many
+    /// challenges within the window.
+    fn get_difficulty(&self, num_recent_requests: u32) -> u8 {
+        let increase = num_recent_requests / self.config.requests_per_difficulty_increase.max(1);
+        let difficulty = u32::from(self.config.base_difficulty).saturating_add(increase);
+        difficulty.min(u32::from(self.config.max_difficulty)) as u8
+    }
+
+    /// Check the solution to a challenge. If `consume` is set and the solution
+    /// is correct, the challenge can't be used again.
+    pub fn check_solution(
+        &mut self,
+        challenge: &str,
+        nonce: u64,
+        source_ip: IpAddr,
+        now_secs: u64,
+        consume: bool,
+    ) -> Result<()> {
+        let info = match self.challenges.peek(challenge) {
+            Some(info) => info.clone(),
+            None => bail!(
+                "Proof of work challenge unknown or already used: {}",
+                challenge
+            ),
+        };
+        if info.expiration_time_secs < now_secs {
+            self.challenges.pop(challenge);
+            bail!("Proof of work challenge expired: {}", challenge);
+        }
+        if info.source_ip != source_ip {
+            bail!(
+                "Proof of work challenge {} was requested from a different IP",
+                challenge
+            );
+        }
+        if !solution_is_valid(challenge, nonce, info.difficulty) {
+            bail!(
+                "Proof of work nonce {} does not solve challenge {} with difficulty {}",
+                nonce,
+                challenge,
+                info.difficulty
+            );
+        }
+        if consume {
+            self.challenges.pop(challenge);
+        }
+        Ok(())
+    }
+}
+
+/// Returns whether the hash of the challenge and nonce starts with at least
+/// `difficulty` zero bits.
+pub fn solution_is_valid(challenge: &str, nonce: u64, difficulty: u8) -> bool {
+    let hash = HashValue::sha3_256_of(format!("{}:{}", challenge, nonce).as_bytes());
+    hash.common_prefix_bits_len(HashValue::zero()) >= difficulty as usize
+}
+
+/// Finds a nonce that solves the challenge. This is what clients have to do,
+/// it's here for testing and for Rust clients to use.
+pub fn solve_challenge(challenge: &str, difficulty: u8) -> u64 {
+    (0..u64::MAX)
+        .find(|nonce| solution_is_valid(challenge, *nonce, difficulty))
+        .expect("Failed to solve proof of work challenge")
+}
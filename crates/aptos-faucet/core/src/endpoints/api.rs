@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use super::{basic::BasicApi, fund::FundApi, CaptchaApi};
+use super::{basic::BasicApi, fund::FundApi, CaptchaApi, ProofOfWorkApi};
 use poem_openapi::{ContactObject, LicenseObject, OpenApiService};
 
 const VERSION: &str = include_str!("../../../doc/.version");
@@ -10,7 +10,8 @@ pub fn build_openapi_service(
     basic_api: BasicApi,
     captcha_api: CaptchaApi,
     fund_api: FundApi,
-) -> OpenApiService<(BasicApi, CaptchaApi, FundApi), ()> {
+    proof_of_work_api: ProofOfWorkApi,
+) -> OpenApiService<(BasicApi, CaptchaApi, FundApi, ProofOfWorkApi), ()> {
     let version = VERSION.to_string();
     let license =
         LicenseObject::new("Apache 2.0").url("https://www.apache.org/licenses/LICENSE-2.0.html");
@@ -18,7 +19,7 @@ pub fn build_openapi_service(
         .name("Aptos Labs")
         .url("https://github.com/aptos-labs");
 
-    let apis = (basic_api, captcha_api, fund_api);
+    let apis = (basic_api, captcha_api, fund_api, proof_of_work_api);
 
     OpenApiService::new(apis, "Aptos Tap", version.trim())
         .server("/v1")
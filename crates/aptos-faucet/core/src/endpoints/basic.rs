@@ -70,4 +70,28 @@ impl BasicApi {
 
         Ok(PlainText("tap:ok".to_string()))
     }
+
+    /// Show funder status
+    ///
+    /// Returns the details the Funder gives about its state, e.g. the balance
+    /// of each account in the case of a PoolFunder. Unlike `/`, this returns
+    /// Ok even if the Funder can't process requests right now.
+    #[oai(
+        path = "/funder_status",
+        method = "get",
+        operation_id = "funder_status",
+        tag = "ApiTags::General"
+    )]
+    async fn funder_status(&self) -> PlainText<String> {
+        let funder_health = self.funder.is_healthy().await;
+        let status = if funder_health.can_process_requests {
+            "healthy"
+        } else {
+            "unhealthy"
+        };
+        match funder_health.message {
+            Some(message) => PlainText(format!("{}\n{}", status, message)),
+            None => PlainText(status.to_string()),
+        }
+    }
 }
@@ -0,0 +1,84 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This API is for the ProofOfWorkChecker.
+
+use super::{errors::AptosTapErrorResponse, ApiTags, AptosTapError, AptosTapErrorCode};
+use crate::checkers::ProofOfWorkManager;
+use futures::lock::Mutex;
+use poem::{web::RealIp, Result};
+use poem_openapi::{payload::Json, Object, OpenApi};
+use std::sync::Arc;
+
+pub struct ProofOfWorkApi {
+    /// This is only set if the ProofOfWorkChecker is enabled.
+    pub proof_of_work_manager: Option<Arc<Mutex<ProofOfWorkManager>>>,
+}
+
+pub const PROOF_OF_WORK_CHALLENGE: &str = "PROOF_OF_WORK_CHALLENGE";
+pub const PROOF_OF_WORK_NONCE: &str = "PROOF_OF_WORK_NONCE";
+
+#[derive(Clone, Debug, Object)]
+pub struct ProofOfWorkChallenge {
+    /// The challenge to solve, to include in the `PROOF_OF_WORK_CHALLENGE`
+    /// header in the call to `/fund`.
+    pub challenge: String,
+
+    /// The number of leading zero bits the SHA3-256 hash of
+    /// `<challenge>:<nonce>` must have, where the nonce is a decimal u64.
+    pub difficulty: u8,
+}
+
+#[OpenApi]
+impl ProofOfWorkApi {
+    /// Initiate proof of work flow
+    ///
+    /// With this endpoint you can initiate a proof of work flow. The response
+    /// contains a challenge and a difficulty. Find a nonce such that the SHA3-256
+    /// hash of `<challenge>:<nonce>` starts with at least `difficulty` zero bits,
+    /// and include the challenge and nonce in the `PROOF_OF_WORK_CHALLENGE` and
+    /// `PROOF_OF_WORK_NONCE` headers in the call to `/fund`. The difficulty goes
+    /// up the more challenges you request. This endpoint is only relevant if the
+    /// ProofOfWorkChecker is enabled.
+    #[oai(
+        path = "/request_proof_of_work",
+        method = "get",
+        operation_id = "request_proof_of_work",
+        tag = "ApiTags::ProofOfWork"
+    )]
+    async fn request_proof_of_work(
+        &self,
+        // This automagically uses FromRequest to get this data from the request.
+        // It takes into things like X-Forwarded-IP and X-Real-IP.
+        source_ip: RealIp,
+    ) -> Result<Json<ProofOfWorkChallenge>, AptosTapErrorResponse> {
+        let proof_of_work_manager = match &self.proof_of_work_manager {
+            Some(proof_of_work_manager) => proof_of_work_manager,
+            None => {
+                return Err(AptosTapError::new(
+                    "The ProofOfWorkChecker is not enabled".to_string(),
+                    AptosTapErrorCode::EndpointNotEnabled,
+                )
+                .into())
+            },
+        };
+        let source_ip = match source_ip.0 {
+            Some(ip) => ip,
+            None => {
+                return Err(AptosTapError::new(
+                    "No source IP found in the request".to_string(),
+                    AptosTapErrorCode::SourceIpMissing,
+                )
+                .into())
+            },
+        };
+        let (challenge, difficulty) = proof_of_work_manager
+            .lock()
+            .await
+            .create_challenge(source_ip);
+        Ok(Json(ProofOfWorkChallenge {
+            challenge,
+            difficulty,
+        }))
+    }
+}
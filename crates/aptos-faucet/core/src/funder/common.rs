@@ -25,7 +25,7 @@ use clap::Parser;
 use reqwest::Url;
 use serde::{Deserialize, Serialize};
 use std::{
-    path::PathBuf,
+    path::{Path, PathBuf},
     str::FromStr,
     sync::{
         atomic::{AtomicU64, Ordering},
@@ -96,27 +96,32 @@ impl ApiConnectionConfig {
         if let Some(ref key) = self.key {
             return Ok(key.private_key());
         }
-        let key_bytes = std::fs::read(self.key_file_path.as_path()).with_context(|| {
+        read_key_file(&self.key_file_path)
+    }
+}
+
+/// Reads an Ed25519PrivateKey from a file, encoded either as BCS or as hex.
+pub fn read_key_file(key_file_path: &Path) -> Result<Ed25519PrivateKey> {
+    let key_bytes = std::fs::read(key_file_path).with_context(|| {
+        format!(
+            "Failed to read key file: {}",
+            key_file_path.to_string_lossy()
+        )
+    })?;
+    // decode as bcs first, fall back to a file of hex
+    let result = aptos_sdk::bcs::from_bytes(&key_bytes); //.with_context(|| "bad bcs");
+    if let Ok(x) = result {
+        return Ok(x);
+    }
+    let keystr = String::from_utf8(key_bytes).map_err(|e| anyhow!(e))?;
+    Ok(ConfigKey::from_encoded_string(keystr.as_str())
+        .with_context(|| {
             format!(
-                "Failed to read key file: {}",
-                self.key_file_path.to_string_lossy()
+                "{}: key file failed as both bcs and hex",
+                key_file_path.to_string_lossy()
             )
-        })?;
-        // decode as bcs first, fall back to a file of hex
-        let result = aptos_sdk::bcs::from_bytes(&key_bytes); //.with_context(|| "bad bcs");
-        if let Ok(x) = result {
-            return Ok(x);
-        }
-        let keystr = String::from_utf8(key_bytes).map_err(|e| anyhow!(e))?;
-        Ok(ConfigKey::from_encoded_string(keystr.as_str())
-            .with_context(|| {
-                format!(
-                    "{}: key file failed as both bcs and hex",
-                    self.key_file_path.to_string_lossy()
-                )
-            })?
-            .private_key())
-    }
+        })?
+        .private_key())
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -4,13 +4,14 @@
 mod common;
 mod fake;
 mod mint;
+mod pool;
 mod transfer;
 
 pub use self::{
     common::{ApiConnectionConfig, TransactionSubmissionConfig},
     mint::MintFunderConfig,
 };
-use self::{fake::FakeFunderConfig, transfer::TransferFunderConfig};
+use self::{fake::FakeFunderConfig, pool::PoolFunderConfig, transfer::TransferFunderConfig};
 use crate::endpoints::AptosTapError;
 use anyhow::{Context, Result};
 use aptos_sdk::types::{account_address::AccountAddress, transaction::SignedTransaction};
@@ -18,8 +19,10 @@ use async_trait::async_trait;
 use enum_dispatch::enum_dispatch;
 pub use fake::FakeFunder;
 pub use mint::MintFunder;
+pub use pool::PoolFunder;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use tokio::task::JoinSet;
 pub use transfer::TransferFunder;
 
 /// explain
@@ -54,13 +57,20 @@ pub trait FunderTrait: Sync + Send + 'static {
     /// This should return whether the Funder is healthy and able to accept
     /// requests. With this a Funder can indicate some issue that will get
     /// exposed at the `/` (the healthcheck endpoint), e.g. that that it
-    /// doesn't have enough funds in the case of a TransferFunder.
+    /// doesn't have enough funds in the case of a TransferFunder. The message
+    /// is also exposed at `/funder_status`, whether healthy or not.
     async fn is_healthy(&self) -> FunderHealthMessage {
         FunderHealthMessage {
             can_process_requests: true,
             message: None,
         }
     }
+
+    /// This function will be called once at startup. In it, the trait implementation
+    /// should spawn any periodic tasks that it wants, e.g. rebalancing funds between
+    /// accounts. If tasks want to signal that there is an issue, all they have to do
+    /// is return. See CheckerTrait::spawn_periodic_tasks for more details.
+    fn spawn_periodic_tasks(&self, _join_set: &mut JoinSet<anyhow::Result<()>>) {}
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -75,6 +85,11 @@ pub enum FunderConfig {
     /// This funder creates and funds accounts by using + transferring
     /// coins from a pre-funded account provided in configuration.
     TransferFunder(TransferFunderConfig),
+
+    /// This funder creates and funds accounts by transferring coins from a
+    /// pool of pre-funded accounts round robin, topping them up from a
+    /// treasury account provided in configuration when their balances dip.
+    PoolFunder(PoolFunderConfig),
 }
 
 impl FunderConfig {
@@ -93,6 +108,12 @@ impl FunderConfig {
                     .await
                     .context("Failed to build TransferFunder")?,
             ))),
+            FunderConfig::PoolFunder(config) => Ok(Arc::new(Funder::from(
+                config
+                    .build_funder()
+                    .await
+                    .context("Failed to build PoolFunder")?,
+            ))),
         }
     }
 }
@@ -103,6 +124,7 @@ pub enum Funder {
     FakeFunder,
     MintFunder,
     TransferFunder,
+    PoolFunder,
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,615 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    common::{
+        read_key_file, submit_transaction, update_sequence_numbers, ApiConnectionConfig,
+        GasUnitPriceManager, TransactionSubmissionConfig,
+    },
+    transfer::{AmountToFund, MinimumFunds},
+    FunderHealthMessage, FunderTrait,
+};
+use crate::{
+    endpoints::{AptosTapError, AptosTapErrorCode, RejectionReason, RejectionReasonCode},
+    middleware::POOL_FUNDER_ACCOUNT_BALANCE,
+};
+use anyhow::{bail, Result};
+use aptos_logger::{info, warn};
+use aptos_sdk::{
+    crypto::{ed25519::Ed25519PrivateKey, PrivateKey},
+    rest_client::{error::RestError, Client},
+    transaction_builder::{aptos_stdlib, TransactionFactory},
+    types::{
+        account_address::AccountAddress,
+        chain_id::ChainId,
+        transaction::{authenticator::AuthenticationKey, SignedTransaction, TransactionPayload},
+        LocalAccount,
+    },
+};
+use async_trait::async_trait;
+use reqwest::{StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::{sync::RwLock, task::JoinSet};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PoolFunderConfig {
+    /// The key given here is for the treasury account, which is only used to
+    /// top up the pool accounts, never to fund requests directly.
+    #[serde(flatten)]
+    pub api_connection_config: ApiConnectionConfig,
+
+    #[serde(flatten)]
+    pub transaction_submission_config: TransactionSubmissionConfig,
+
+    /// Paths to the private keys of the pool accounts. Fund requests are spread
+    /// across these accounts round robin. Pool accounts that don't exist yet are
+    /// created the first time the treasury tops them up.
+    pub pool_account_key_file_paths: Vec<PathBuf>,
+
+    /// The minimum amount of coins a pool account should have. Pool accounts
+    /// below this are skipped until they are topped up. If all of them are
+    /// below this, the funder reports that it can't process requests.
+    pub minimum_funds: MinimumFunds,
+
+    /// The amount of coins to fund the receiver account.
+    pub amount_to_fund: AmountToFund,
+
+    /// When a pool account has fewer coins than this, the treasury tops it up
+    /// to `rebalance_target_funds`. This must be at least `minimum_funds`.
+    pub rebalance_threshold_funds: u64,
+
+    /// The amount of coins the treasury tops pool accounts up to.
+    pub rebalance_target_funds: u64,
+
+    /// How often to check the balances of the accounts and rebalance them.
+    #[serde(default = "PoolFunderConfig::default_rebalance_interval_secs")]
+    pub rebalance_interval_secs: u64,
+}
+
+impl PoolFunderConfig {
+    fn default_rebalance_interval_secs() -> u64 {
+        30
+    }
+
+    pub async fn build_funder(&self) -> Result<PoolFunder> {
+        if self.pool_account_key_file_paths.is_empty() {
+            bail!("At least one pool account key file path must be given");
+        }
+        if self.rebalance_threshold_funds < self.minimum_funds.0 {
+            bail!(
+                "rebalance_threshold_funds ({}) must be at least minimum_funds ({})",
+                self.rebalance_threshold_funds,
+                self.minimum_funds
+            );
+        }
+        if self.rebalance_target_funds <= self.rebalance_threshold_funds {
+            bail!(
+                "rebalance_target_funds ({}) must be greater than rebalance_threshold_funds ({})",
+                self.rebalance_target_funds,
+                self.rebalance_threshold_funds
+            );
+        }
+
+        // Read in the private keys and build local representations of the accounts.
+        let treasury = PoolAccount::new(self.api_connection_config.get_key()?);
+        let mut accounts = Vec::with_capacity(self.pool_account_key_file_paths.len());
+        for key_file_path in &self.pool_account_key_file_paths {
+            accounts.push(PoolAccount::new(read_key_file(key_file_path)?));
+        }
+
+        // Two accounts sharing a sequence number stream would defeat the purpose.
+        let mut addresses = HashSet::new();
+        for account in std::iter::once(&treasury).chain(accounts.iter()) {
+            if !addresses.insert(account.address) {
+                bail!(
+                    "Account {} is given more than once in the pool and treasury",
+                    account.address
+                );
+            }
+        }
+
+        let funder = PoolFunder::new(
+            treasury,
+            accounts,
+            self.api_connection_config.chain_id,
+            self.api_connection_config.node_url.clone(),
+            self.minimum_funds,
+            self.amount_to_fund,
+            self.rebalance_threshold_funds,
+            self.rebalance_target_funds,
+            Duration::from_secs(self.rebalance_interval_secs),
+            &self.transaction_submission_config,
+        );
+
+        // Check the balances and top up the pool accounts once before we start
+        // handling requests, so the pool is usable right away.
+        funder.state.rebalance().await;
+
+        Ok(funder)
+    }
+}
+
+/// What we know about the balance of an account as of the last check.
+#[derive(Clone, Debug)]
+enum AccountStatus {
+    /// We haven't checked the balance yet.
+    Unknown,
+    /// The balance of the account, reduced as the account funds requests.
+    Balance(u64),
+    /// We failed to get the balance of the account.
+    Error(String),
+}
+
+impl AccountStatus {
+    fn has_funds(&self, minimum_funds: u64) -> bool {
+        matches!(self, AccountStatus::Balance(balance) if *balance >= minimum_funds)
+    }
+
+    fn describe(&self, minimum_funds: u64) -> String {
+        match self {
+            AccountStatus::Unknown => "balance not checked yet".to_string(),
+            AccountStatus::Balance(balance) if *balance >= minimum_funds => {
+                format!("healthy, balance {}", balance)
+            },
+            AccountStatus::Balance(balance) => format!(
+                "insufficient funds, balance {} is below the minimum {}",
+                balance, minimum_funds
+            ),
+            AccountStatus::Error(error) => format!("failed to get balance: {}", error),
+        }
+    }
+}
+
+struct PoolAccount {
+    address: AccountAddress,
+
+    account: RwLock<LocalAccount>,
+
+    /// When recovering from being overloaded, this ensures we handle requests
+    /// for this account in the order they came in.
+    outstanding_requests: RwLock<Vec<(AccountAddress, u64)>>,
+
+    status: RwLock<AccountStatus>,
+}
+
+impl PoolAccount {
+    fn new(key: Ed25519PrivateKey) -> Self {
+        let address = AuthenticationKey::ed25519(&key.public_key()).account_address();
+        Self {
+            address,
+            account: RwLock::new(LocalAccount::new(address, key, 0)),
+            outstanding_requests: RwLock::new(vec![]),
+            status: RwLock::new(AccountStatus::Unknown),
+        }
+    }
+
+    async fn set_balance(&self, balance: u64) {
+        POOL_FUNDER_ACCOUNT_BALANCE
+            .with_label_values(&[&self.address.to_hex_literal()])
+            .set(balance as i64);
+        *self.status.write().await = AccountStatus::Balance(balance);
+    }
+}
+
+/// This funder spreads requests across a pool of accounts, so that throughput
+/// isn't limited by the sequence numbers of a single account and a single
+/// drained account doesn't take the faucet down. A periodic task tops up the
+/// pool accounts from a treasury account when their balances dip.
+pub struct PoolFunder {
+    state: Arc<PoolFunderState>,
+
+    /// Maximum amount we'll fund an account.
+    amount_to_fund: AmountToFund,
+
+    rebalance_interval: Duration,
+}
+
+/// The parts of the PoolFunder shared with the rebalancing task.
+struct PoolFunderState {
+    treasury: PoolAccount,
+
+    accounts: Vec<PoolAccount>,
+
+    /// Index of the next pool account to try to fund a request with.
+    next_account_index: AtomicUsize,
+
+    transaction_factory: TransactionFactory,
+
+    /// URL of an Aptos node API.
+    node_url: Url,
+
+    /// The minimum amount of funds a pool account should have to fund requests.
+    minimum_funds: MinimumFunds,
+
+    rebalance_threshold_funds: u64,
+
+    rebalance_target_funds: u64,
+
+    gas_unit_price_manager: GasUnitPriceManager,
+
+    /// If this is Some, we'll use this. If not, we'll get the gas_unit_price
+    /// from the gas_unit_price_manager.
+    gas_unit_price_override: Option<u64>,
+
+    /// Amount of time we'll wait for the seqnum to catch up before resetting it.
+    wait_for_outstanding_txns_secs: u64,
+
+    /// If set, we won't return responses until the transaction is processed.
+    wait_for_transactions: bool,
+}
+
+impl PoolFunder {
+    fn new(
+        treasury: PoolAccount,
+        accounts: Vec<PoolAccount>,
+        chain_id: ChainId,
+        node_url: Url,
+        minimum_funds: MinimumFunds,
+        amount_to_fund: AmountToFund,
+        rebalance_threshold_funds: u64,
+        rebalance_target_funds: u64,
+        rebalance_interval: Duration,
+        transaction_submission_config: &TransactionSubmissionConfig,
+    ) -> Self {
+        let gas_unit_price_manager = GasUnitPriceManager::new(
+            node_url.clone(),
+            transaction_submission_config.get_gas_unit_price_ttl_secs(),
+        );
+
+        let state = PoolFunderState {
+            treasury,
+            accounts,
+            next_account_index: AtomicUsize::new(0),
+            transaction_factory: TransactionFactory::new(chain_id)
+                .with_max_gas_amount(transaction_submission_config.max_gas_amount)
+                .with_transaction_expiration_time(
+                    transaction_submission_config.transaction_expiration_secs,
+                ),
+            node_url,
+            minimum_funds,
+            rebalance_threshold_funds,
+            rebalance_target_funds,
+            gas_unit_price_manager,
+            gas_unit_price_override: transaction_submission_config.gas_unit_price_override,
+            wait_for_outstanding_txns_secs: transaction_submission_config
+                .wait_for_outstanding_txns_secs,
+            wait_for_transactions: transaction_submission_config.wait_for_transactions,
+        };
+
+        Self {
+            state: Arc::new(state),
+            amount_to_fund,
+            rebalance_interval,
+        }
+    }
+}
+
+impl PoolFunderState {
+    /// Within a single request we should just call this once and use this client
+    /// the entire time because it uses cookies, ensuring we're talking to the same
+    /// node behind the LB every time.
+    fn get_api_client(&self) -> Client {
+        Client::new(self.node_url.clone())
+    }
+
+    async fn get_gas_unit_price(&self) -> Result<u64, AptosTapError> {
+        match self.gas_unit_price_override {
+            Some(gas_unit_price) => Ok(gas_unit_price),
+            None => self
+                .gas_unit_price_manager
+                .get_gas_unit_price()
+                .await
+                .map_err(|e| {
+                    AptosTapError::new_with_error_code(e, AptosTapErrorCode::AptosApiError)
+                }),
+        }
+    }
+
+    /// Returns the next pool account with sufficient funds, going round robin
+    /// from where the last request left off.
+    async fn next_funded_account(&self) -> Option<&PoolAccount> {
+        let start = self.next_account_index.fetch_add(1, Ordering::Relaxed);
+        for offset in 0..self.accounts.len() {
+            let account = &self.accounts[(start + offset) % self.accounts.len()];
+            if account.status.read().await.has_funds(self.minimum_funds.0) {
+                return Some(account);
+            }
+        }
+        None
+    }
+
+    /// This function builds, signs, submits, and potentially waits for a
+    /// transaction from the given account.
+    async fn execute_transaction(
+        &self,
+        client: &Client,
+        account: &PoolAccount,
+        payload: TransactionPayload,
+        receiver_address: &AccountAddress,
+        wait_for_transactions: bool,
+    ) -> Result<SignedTransaction, AptosTapError> {
+        let transaction_factory = self
+            .transaction_factory
+            .clone()
+            .with_gas_unit_price(self.get_gas_unit_price().await?);
+
+        let transaction_builder = transaction_factory.payload(payload);
+
+        let signed_transaction = account
+            .account
+            .write()
+            .await
+            .sign_with_transaction_builder(transaction_builder);
+
+        submit_transaction(
+            client,
+            &account.account,
+            signed_transaction,
+            receiver_address,
+            wait_for_transactions,
+        )
+        .await
+    }
+
+    /// Gets the balance of the account and updates its status. An account that
+    /// doesn't exist yet has a balance of zero.
+    async fn refresh_balance(&self, client: &Client, account: &PoolAccount) -> Option<u64> {
+        let balance = match client
+            .get_account_balance_bcs(account.address, "0x1::aptos_coin::AptosCoin")
+            .await
+        {
+            Ok(response) => response.into_inner(),
+            Err(RestError::Api(e)) if e.status_code == StatusCode::NOT_FOUND => 0,
+            Err(e) => {
+                warn!(
+                    address = account.address,
+                    event = "pool_funder_balance_check_failed",
+                    error_message = format!("{:#}", e)
+                );
+                *account.status.write().await = AccountStatus::Error(format!("{:#}", e));
+                return None;
+            },
+        };
+        account.set_balance(balance).await;
+        Some(balance)
+    }
+
+    /// Transfers coins from the treasury to the pool account, waiting for the
+    /// transaction so the new balance is reflected on chain.
+    async fn top_up(
+        &self,
+        client: &Client,
+        account: &PoolAccount,
+        amount: u64,
+    ) -> Result<(), AptosTapError> {
+        update_sequence_numbers(
+            client,
+            &self.treasury.account,
+            &self.treasury.outstanding_requests,
+            account.address,
+            amount,
+            self.wait_for_outstanding_txns_secs,
+        )
+        .await?;
+
+        self.execute_transaction(
+            client,
+            &self.treasury,
+            aptos_stdlib::aptos_account_transfer(account.address, amount),
+            &account.address,
+            true,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Checks the balances of the treasury and pool accounts, and tops up the
+    /// pool accounts that have dipped below the rebalance threshold.
+    async fn rebalance(&self) {
+        let client = self.get_api_client();
+        let mut treasury_balance = self.refresh_balance(&client, &self.treasury).await;
+
+        for account in &self.accounts {
+            let balance = match self.refresh_balance(&client, account).await {
+                Some(balance) => balance,
+                None => continue,
+            };
+            if balance >= self.rebalance_threshold_funds {
+                continue;
+            }
+
+            let amount = self.rebalance_target_funds - balance;
+            match treasury_balance {
+                Some(available) if available >= amount => {
+                    match self.top_up(&client, account, amount).await {
+                        Ok(()) => {
+                            info!(
+                                address = account.address,
+                                amount = amount,
+                                event = "pool_funder_account_topped_up"
+                            );
+                            account.set_balance(balance + amount).await;
+                            treasury_balance = Some(available - amount);
+                        },
+                        Err(e) => {
+                            warn!(
+                                address = account.address,
+                                amount = amount,
+                                event = "pool_funder_top_up_failed",
+                                error_message = format!("{:#}", e)
+                            );
+                        },
+                    }
+                },
+                _ => {
+                    warn!(
+                        address = account.address,
+                        amount = amount,
+                        treasury_balance = treasury_balance,
+                        event = "pool_funder_treasury_insufficient_funds"
+                    );
+                },
+            }
+        }
+
+        if let Some(balance) = treasury_balance {
+            self.treasury.set_balance(balance).await;
+        }
+    }
+}
+
+/// Periodically rebalances the pool. This never returns.
+async fn run_rebalancer(state: Arc<PoolFunderState>, rebalance_interval: Duration) -> Result<()> {
+    let mut interval = tokio::time::interval(rebalance_interval);
+    // The first tick completes immediately, and we already rebalanced on startup.
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        state.rebalance().await;
+    }
+}
+
+#[async_trait]
+impl FunderTrait for PoolFunder {
+    /// This works just like the TransferFunder, except the request is funded by
+    /// the next pool account that has sufficient funds.
+    async fn fund(
+        &self,
+        amount: Option<u64>,
+        receiver_address: AccountAddress,
+        check_only: bool,
+        did_bypass_checkers: bool,
+    ) -> Result<Vec<SignedTransaction>, AptosTapError> {
+        let account = self.state.next_funded_account().await.ok_or_else(|| {
+            AptosTapError::new(
+                "Tap PoolFunder is not able to handle requests right now: no pool account has sufficient funds".to_string(),
+                AptosTapErrorCode::FunderAccountProblem,
+            )
+        })?;
+
+        let client = self.state.get_api_client();
+
+        // Determine amount to fund.
+        let amount = self.get_amount(amount, did_bypass_checkers);
+
+        // Update the sequence numbers of the accounts.
+        let (_funder_seq_num, receiver_seq_num) = update_sequence_numbers(
+            &client,
+            &account.account,
+            &account.outstanding_requests,
+            receiver_address,
+            amount,
+            self.state.wait_for_outstanding_txns_secs,
+        )
+        .await?;
+
+        // When updating the sequence numbers, we expect that the receiver sequence
+        // number should be None, because the account should not exist yet.
+        if receiver_seq_num.is_some() {
+            return Err(AptosTapError::new(
+                "Account ineligible".to_string(),
+                AptosTapErrorCode::Rejected,
+            )
+            .rejection_reasons(vec![RejectionReason::new(
+                format!("Account {} already exists", receiver_address),
+                RejectionReasonCode::AccountAlreadyExists,
+            )]));
+        }
+
+        if check_only {
+            return Ok(vec![]);
+        }
+
+        let txn = self
+            .state
+            .execute_transaction(
+                &client,
+                account,
+                aptos_stdlib::aptos_account_transfer(receiver_address, amount),
+                &receiver_address,
+                self.state.wait_for_transactions,
+            )
+            .await?;
+        info!(
+            hash = txn.committed_hash().to_hex_literal(),
+            address = receiver_address,
+            funder_address = account.address,
+            amount = amount,
+            event = "transaction_submitted"
+        );
+
+        // Account for the transfer right away, so a drained account stops being
+        // used before the next rebalance.
+        let mut status = account.status.write().await;
+        if let AccountStatus::Balance(balance) = *status {
+            *status = AccountStatus::Balance(balance.saturating_sub(amount));
+        }
+
+        Ok(vec![txn])
+    }
+
+    fn get_amount(
+        &self,
+        amount: Option<u64>,
+        // Ignored, like with the TransferFunder.
+        _did_bypass_checkers: bool,
+    ) -> u64 {
+        match amount {
+            Some(amount) => std::cmp::min(amount, self.amount_to_fund.0),
+            None => self.amount_to_fund.0,
+        }
+    }
+
+    /// The funder can process requests as long as one pool account has the
+    /// minimum funds. The health of every account is included in the message.
+    async fn is_healthy(&self) -> FunderHealthMessage {
+        let minimum_funds = self.state.minimum_funds.0;
+        let mut num_funded = 0;
+        let mut lines = vec![];
+        for account in &self.state.accounts {
+            let status = account.status.read().await;
+            if status.has_funds(minimum_funds) {
+                num_funded += 1;
+            }
+            lines.push(format!(
+                "Pool account {}: {}",
+                account.address,
+                status.describe(minimum_funds)
+            ));
+        }
+        lines.push(format!(
+            "Treasury account {}: {}",
+            self.state.treasury.address,
+            self.state
+                .treasury
+                .status
+                .read()
+                .await
+                .describe(self.state.rebalance_target_funds)
+        ));
+
+        FunderHealthMessage {
+            can_process_requests: num_funded > 0,
+            message: Some(format!(
+                "{} of {} pool accounts have sufficient funds\n{}",
+                num_funded,
+                self.state.accounts.len(),
+                lines.join("\n")
+            )),
+        }
+    }
+
+    fn spawn_periodic_tasks(&self, join_set: &mut JoinSet<anyhow::Result<()>>) {
+        join_set.spawn(run_rebalancer(self.state.clone(), self.rebalance_interval));
+    }
+}
@@ -7,7 +7,7 @@ mod metrics;
 pub use self::{
     log::middleware_log,
     metrics::{
-        bump_rejection_reason_counters, NUM_OUTSTANDING_TRANSACTIONS,
+        bump_rejection_reason_counters, NUM_OUTSTANDING_TRANSACTIONS, POOL_FUNDER_ACCOUNT_BALANCE,
         TRANSFER_FUNDER_ACCOUNT_BALANCE,
     },
 };
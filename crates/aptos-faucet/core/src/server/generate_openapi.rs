@@ -3,7 +3,9 @@
 
 use crate::{
     checkers::CaptchaManager,
-    endpoints::{build_openapi_service, BasicApi, CaptchaApi, FundApi, FundApiComponents},
+    endpoints::{
+        build_openapi_service, BasicApi, CaptchaApi, FundApi, FundApiComponents, ProofOfWorkApi,
+    },
     funder::{FakeFunder, Funder},
 };
 use anyhow::Result;
@@ -40,6 +42,9 @@ impl GenerateOpenapi {
                 captcha_manager: Arc::new(Mutex::new(CaptchaManager::new())),
             },
             fund_api,
+            ProofOfWorkApi {
+                proof_of_work_manager: None,
+            },
         );
 
         let spec = match self.output_args.format {
@@ -4,12 +4,15 @@
 use super::server_args::ServerConfig;
 use crate::{
     bypasser::{Bypasser, BypasserConfig},
-    checkers::{CaptchaManager, Checker, CheckerConfig, CheckerTrait},
+    checkers::{CaptchaManager, Checker, CheckerConfig, CheckerTrait, ProofOfWorkManager},
     endpoints::{
         build_openapi_service, convert_error, mint, BasicApi, CaptchaApi, FundApi,
-        FundApiComponents,
+        FundApiComponents, ProofOfWorkApi,
+    },
+    funder::{
+        ApiConnectionConfig, FunderConfig, FunderTrait, MintFunderConfig,
+        TransactionSubmissionConfig,
     },
-    funder::{ApiConnectionConfig, FunderConfig, MintFunderConfig, TransactionSubmissionConfig},
     middleware::middleware_log,
 };
 use anyhow::{Context, Result};
@@ -96,6 +99,17 @@ impl RunConfig {
         // Create a CaptchaManager.
         let captcha_manager = Arc::new(Mutex::new(CaptchaManager::new()));
 
+        // Create a ProofOfWorkManager if the ProofOfWorkChecker is enabled.
+        let proof_of_work_manager = self.checker_configs.iter().find_map(|checker_config| {
+            if let CheckerConfig::ProofOfWork(config) = checker_config {
+                Some(Arc::new(Mutex::new(ProofOfWorkManager::new(
+                    config.clone(),
+                ))))
+            } else {
+                None
+            }
+        });
+
         // Build Bypassers.
         let mut bypassers: Vec<Bypasser> = Vec::new();
         for bypasser_config in &self.bypasser_configs {
@@ -108,13 +122,16 @@ impl RunConfig {
         // Create a periodic task manager.
         let mut join_set = JoinSet::new();
 
+        // Let the Funder spawn tasks on the periodic task manager if it wants.
+        funder.spawn_periodic_tasks(&mut join_set);
+
         // Build Checkers and let them spawn tasks on the periodic task
         // manager if they want.
         let mut checkers: Vec<Checker> = Vec::new();
         for checker_config in &self.checker_configs {
             let checker = checker_config
                 .clone()
-                .build(captcha_manager.clone())
+                .build(captcha_manager.clone(), proof_of_work_manager.clone())
                 .await
                 .with_context(|| {
                     format!("Failed to build Checker with args: {:?}", checker_config)
@@ -153,7 +170,13 @@ impl RunConfig {
             captcha_manager,
         };
 
-        let api_service = build_openapi_service(basic_api, captcha_api, fund_api);
+        // Build the ProofOfWorkApi.
+        let proof_of_work_api = ProofOfWorkApi {
+            proof_of_work_manager,
+        };
+
+        let api_service =
+            build_openapi_service(basic_api, captcha_api, fund_api, proof_of_work_api);
         let spec_json = api_service.spec_endpoint();
         let spec_yaml = api_service.spec_endpoint_yaml();
 
@@ -362,8 +385,10 @@ impl RunSimple {
 mod test {
     use super::*;
     use crate::{
+        checkers::solve_challenge,
         endpoints::{
-            AptosTapError, AptosTapErrorCode, FundRequest, FundResponse, RejectionReasonCode,
+            AptosTapError, AptosTapErrorCode, FundRequest, FundResponse, ProofOfWorkChallenge,
+            RejectionReasonCode, PROOF_OF_WORK_CHALLENGE, PROOF_OF_WORK_NONCE,
         },
         helpers::get_current_time_secs,
     };
@@ -604,6 +629,91 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_proof_of_work() -> Result<()> {
+        init();
+        let config_content = include_str!("../../../configs/testing_proof_of_work.yaml");
+        let (port, _handle) = start_server(config_content).await?;
+
+        // Assert that a request without a solution is rejected.
+        let response = reqwest::Client::new()
+            .post(get_fund_endpoint(port))
+            .body(get_fund_request(Some(10)).to_json_string())
+            .header(CONTENT_TYPE, "application/json")
+            .send()
+            .await?;
+        let aptos_error = AptosTapError::parse_from_json_string(&response.text().await?)
+            .expect("Failed to read response as AptosError");
+        let rejection_reason_codes: Vec<RejectionReasonCode> = aptos_error
+            .rejection_reasons
+            .into_iter()
+            .map(|r| r.get_code())
+            .collect();
+        assert_eq!(
+            rejection_reason_codes,
+            vec![RejectionReasonCode::ProofOfWorkInvalid]
+        );
+
+        // Request a challenge and solve it.
+        let response = unwrap_reqwest_result(
+            reqwest::Client::new()
+                .get(format!("{}/request_proof_of_work", get_root_endpoint(port)))
+                .send()
+                .await,
+        )
+        .await?;
+        let challenge = ProofOfWorkChallenge::parse_from_json_string(&response.text().await?)
+            .expect("Failed to read response as ProofOfWorkChallenge");
+        let nonce = solve_challenge(&challenge.challenge, challenge.difficulty);
+
+        // Assert that a request with the solution passes.
+        unwrap_reqwest_result(
+            reqwest::Client::new()
+                .post(get_fund_endpoint(port))
+                .body(get_fund_request(Some(10)).to_json_string())
+                .header(CONTENT_TYPE, "application/json")
+                .header(PROOF_OF_WORK_CHALLENGE, &challenge.challenge)
+                .header(PROOF_OF_WORK_NONCE, nonce.to_string())
+                .send()
+                .await,
+        )
+        .await?;
+
+        // Assert that the same solution can't be used twice.
+        let response = reqwest::Client::new()
+            .post(get_fund_endpoint(port))
+            .body(get_fund_request(Some(10)).to_json_string())
+            .header(CONTENT_TYPE, "application/json")
+            .header(PROOF_OF_WORK_CHALLENGE, &challenge.challenge)
+            .header(PROOF_OF_WORK_NONCE, nonce.to_string())
+            .send()
+            .await?;
+        let aptos_error = AptosTapError::parse_from_json_string(&response.text().await?)
+            .expect("Failed to read response as AptosError");
+        assert_eq!(
+            aptos_error.rejection_reasons[0].get_code(),
+            RejectionReasonCode::ProofOfWorkInvalid
+        );
+
+        // Assert that the difficulty goes up as the IP requests more challenges.
+        let mut difficulties = vec![];
+        for _ in 0..4 {
+            let response = unwrap_reqwest_result(
+                reqwest::Client::new()
+                    .get(format!("{}/request_proof_of_work", get_root_endpoint(port)))
+                    .send()
+                    .await,
+            )
+            .await?;
+            let challenge = ProofOfWorkChallenge::parse_from_json_string(&response.text().await?)
+                .expect("Failed to read response as ProofOfWorkChallenge");
+            difficulties.push(challenge.difficulty);
+        }
+        assert_eq!(difficulties, vec![8, 9, 9, 10]);
+
+        Ok(())
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
     async fn test_redis_ratelimiter() -> Result<()> {
         // Assert that a localnet is alive.
@@ -675,27 +785,23 @@ mod test {
         Ok(())
     }
 
-    // We skip this for now since we have no current need to use the TransferFunder.
-    #[ignore]
-    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
-    async fn test_transfer_health() -> Result<()> {
-        // Create a local account and store its private key at the path expected by
-        // the config for this test.
+    /// Creates a local account and stores its private key at the given path.
+    fn make_key_file(path: &str) -> Result<AccountAddress> {
         let private_key = Ed25519PrivateKey::generate(&mut StdRng::from_seed(OsRng.gen()));
         let serialized_keys = aptos_sdk::bcs::to_bytes(&private_key)?;
-        let mut key_file = std::fs::File::create("/tmp/transfer_funder_devnet.key")?;
+        let mut key_file = std::fs::File::create(path)?;
         key_file.write_all(&serialized_keys)?;
+        Ok(AuthenticationKey::ed25519(&private_key.public_key()).account_address())
+    }
 
-        // Create it on chain using the prod devnet faucet. We fund it with
-        // exactly the minimum funds set in the config.
-        let account_address =
-            AuthenticationKey::ed25519(&private_key.public_key()).account_address();
+    /// Creates the account on chain with the given amount using the prod devnet faucet.
+    async fn fund_on_devnet(account_address: AccountAddress, amount: u64) -> Result<()> {
         unwrap_reqwest_result(
             reqwest::Client::new()
                 .post("https://faucet.devnet.aptoslabs.com/fund")
                 .body(
                     FundRequest {
-                        amount: Some(10_000_000),
+                        amount: Some(amount),
                         address: Some(account_address.to_string()),
                         ..Default::default()
                     }
@@ -706,6 +812,20 @@ mod test {
                 .await,
         )
         .await?;
+        Ok(())
+    }
+
+    // We skip this for now since we have no current need to use the TransferFunder.
+    #[ignore]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_transfer_health() -> Result<()> {
+        // Create a local account and store its private key at the path expected by
+        // the config for this test.
+        let account_address = make_key_file("/tmp/transfer_funder_devnet.key")?;
+
+        // Create it on chain using the prod devnet faucet. We fund it with
+        // exactly the minimum funds set in the config.
+        fund_on_devnet(account_address, 10_000_000).await?;
 
         // Wait a few seconds for all the fullnodes to catch up.
         tokio::time::sleep(Duration::from_secs(5)).await;
@@ -767,6 +887,113 @@ mod test {
         Ok(())
     }
 
+    // We skip this for now since, like test_transfer_health, it relies on devnet.
+    #[ignore]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_pool_funder() -> Result<()> {
+        // These match testing_pool_funder.yaml.
+        let minimum_funds = 10_000_000;
+        let rebalance_target_funds = 50_000_000;
+
+        // Create the treasury and pool accounts and store their private keys at
+        // the paths expected by the config for this test.
+        let treasury_address = make_key_file("/tmp/pool_funder_treasury_devnet.key")?;
+        let pool_addresses = (0..3)
+            .map(|i| make_key_file(&format!("/tmp/pool_funder_account_{}_devnet.key", i)))
+            .collect::<Result<Vec<_>>>()?;
+
+        // The first pool account is already at the rebalance target. The second
+        // is below the minimum, so it has to be topped up. The third doesn't
+        // exist. The treasury can only afford to top up the second one, so the
+        // third stays drained.
+        fund_on_devnet(treasury_address, 60_000_000).await?;
+        fund_on_devnet(pool_addresses[0], rebalance_target_funds).await?;
+        fund_on_devnet(pool_addresses[1], minimum_funds / 2).await?;
+
+        // Wait a few seconds for all the fullnodes to catch up.
+        tokio::time::sleep(Duration::from_secs(5)).await;
+
+        // Start the server. It rebalances the pool before handling requests.
+        init();
+        let config_content = include_str!("../../../configs/testing_pool_funder.yaml");
+        let (port, _handle) = start_server(config_content).await?;
+
+        // Assert that `/` returns healthy, without any details.
+        let response = unwrap_reqwest_result(
+            reqwest::Client::new()
+                .get(get_root_endpoint(port))
+                .send()
+                .await,
+        )
+        .await?;
+        assert_eq!(response.text().await?, "tap:ok");
+
+        // Assert that the details of the pool are reported separately.
+        let response = unwrap_reqwest_result(
+            reqwest::Client::new()
+                .get(format!("{}/funder_status", get_root_endpoint(port)))
+                .send()
+                .await,
+        )
+        .await?;
+        let funder_status = response.text().await?;
+        assert!(
+            funder_status.contains("2 of 3 pool accounts have sufficient funds"),
+            "Unexpected funder status: {}",
+            funder_status
+        );
+
+        // Assert that the treasury topped up the second pool account.
+        let aptos_node_api_client = aptos_sdk::rest_client::Client::new(reqwest::Url::from_str(
+            "https://fullnode.devnet.aptoslabs.com",
+        )?);
+        let response = aptos_node_api_client
+            .get_account_balance(pool_addresses[1])
+            .await?;
+        assert_eq!(response.into_inner().get(), rebalance_target_funds);
+
+        // Make a few requests and check which pool account funded each of them.
+        let mut senders = HashSet::new();
+        for _ in 0..4 {
+            let response = unwrap_reqwest_result(
+                reqwest::Client::new()
+                    .post(get_fund_endpoint(port))
+                    .body(get_fund_request(None).to_json_string())
+                    .header(CONTENT_TYPE, "application/json")
+                    .send()
+                    .await,
+            )
+            .await?;
+            let fund_response = FundResponse::parse_from_json_string(&response.text().await?)
+                .expect("Failed to read response as FundResponse");
+            let response = aptos_node_api_client
+                .wait_for_transaction_by_hash(
+                    HashValue::from_str(&fund_response.txn_hashes[0])?,
+                    get_current_time_secs() + 30,
+                    None,
+                    None,
+                )
+                .await
+                .context("Failed to wait for transaction")?;
+            match response.into_inner() {
+                aptos_sdk::rest_client::Transaction::UserTransaction(txn) => {
+                    assert!(txn.info.success, "Transaction failed: {:#?}", txn);
+                    senders.insert(*txn.request.sender.inner());
+                },
+                txn => bail!("Unexpected transaction: {:#?}", txn),
+            }
+        }
+
+        // The requests were spread round robin across the funded accounts,
+        // skipping the drained one.
+        assert_eq!(
+            senders,
+            HashSet::from([pool_addresses[0], pool_addresses[1]])
+        );
+
+        Ok(())
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
     async fn test_mint_funder() -> Result<()> {
         // Assert that a localnet is alive.
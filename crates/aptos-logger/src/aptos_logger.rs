@@ -282,6 +282,7 @@ impl LogEntry {
 pub struct AptosDataBuilder {
     channel_size: usize,
     tokio_console_port: Option<u16>,
+    otlp_trace_endpoint: Option<String>,
     enable_backtrace: bool,
     level: Level,
     remote_level: Level,
@@ -299,6 +300,7 @@ impl AptosDataBuilder {
         Self {
             channel_size: CHANNEL_SIZE,
             tokio_console_port: None,
+            otlp_trace_endpoint: None,
             enable_backtrace: false,
             level: Level::Info,
             remote_level: Level::Info,
@@ -346,6 +348,11 @@ impl AptosDataBuilder {
         self
     }
 
+    pub fn otlp_trace_endpoint(&mut self, otlp_trace_endpoint: Option<String>) -> &mut Self {
+        self.otlp_trace_endpoint = otlp_trace_endpoint;
+        self
+    }
+
     pub fn remote_log_tx(
         &mut self,
         remote_log_tx: channel::mpsc::Sender<TelemetryLog>,
@@ -466,7 +473,11 @@ impl AptosDataBuilder {
             None
         };
 
-        crate::logger::set_global_logger(logger.clone(), tokio_console_port);
+        crate::logger::set_global_logger(
+            logger.clone(),
+            tokio_console_port,
+            self.otlp_trace_endpoint.clone(),
+        );
         logger
     }
 }
@@ -903,7 +914,7 @@ mod tests {
     fn set_test_logger() -> Receiver<LogEntry> {
         let (logger, receiver) = LogStream::new(true);
         let logger = Arc::new(logger);
-        crate::logger::set_global_logger(logger, None);
+        crate::logger::set_global_logger(logger, None, None);
         receiver
     }
 
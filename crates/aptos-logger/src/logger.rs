@@ -41,13 +41,36 @@ pub(crate) fn enabled(metadata: &Metadata) -> bool {
 }
 
 /// Sets the global `Logger` exactly once
-pub fn set_global_logger(logger: Arc<dyn Logger>, tokio_console_port: Option<u16>) {
+pub fn set_global_logger(
+    logger: Arc<dyn Logger>,
+    tokio_console_port: Option<u16>,
+    otlp_trace_endpoint: Option<String>,
+) {
     if LOGGER.set(logger).is_err() {
         eprintln!("Global logger has already been set");
         error!("Global logger has already been set");
         return;
     }
 
+    // If otlp-tracing is enabled and an endpoint is set, spans are also exported
+    // to the OpenTelemetry collector at that endpoint, alongside the layers below.
+    #[cfg(feature = "otlp-tracing")]
+    let otlp_layer = otlp_trace_endpoint.and_then(|endpoint| {
+        crate::otlp_tracing::build_otlp_layer(&endpoint)
+            .map_err(|e| {
+                eprintln!("Failed to export traces to {}: {}", endpoint, e);
+                error!("Failed to export traces to {}: {}", endpoint, e);
+            })
+            .ok()
+    });
+    #[cfg(not(feature = "otlp-tracing"))]
+    let otlp_layer: Option<tracing_subscriber::layer::Identity> = {
+        if otlp_trace_endpoint.is_some() {
+            error!("otlp_trace_endpoint was set but has no effect! Build the crate with the 'otlp-tracing' feature enabled!");
+        }
+        None
+    };
+
     // If tokio-console is enabled, all tracing::log events are captured by the
     // tokio-tracing infrastructure. Otherwise, aptos-logger intercepts all
     // tracing::log events. In both scenarios *all* aptos-logger::log events are
@@ -59,14 +82,18 @@ pub fn set_global_logger(logger: Arc<dyn Logger>, tokio_console_port: Option<u16
                 .server_addr(([0, 0, 0, 0], tokio_console_port))
                 .spawn();
 
-            tracing_subscriber::registry().with(console_layer).init();
+            tracing_subscriber::registry()
+                .with(otlp_layer)
+                .with(console_layer)
+                .init();
             return;
         }
     }
     if tokio_console_port.is_none() {
         let _ = tracing::subscriber::set_global_default(
-            crate::tracing_adapter::TracingToAptosDataLayer
-                .with_subscriber(tracing_subscriber::Registry::default()),
+            tracing_subscriber::Registry::default()
+                .with(otlp_layer)
+                .with(crate::tracing_adapter::TracingToAptosDataLayer),
         );
     } else {
         error!("tokio_console_port was set but has no effect! Build the crate with the 'tokio-console' feature enabled!");
@@ -0,0 +1,302 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tracing of the transaction lifecycle, exported to an OpenTelemetry collector over OTLP.
+//!
+//! Each stage a transaction goes through (API submission, mempool insertion, quorum store
+//! batching, block preparation, execution and commit) is traced as a span, with one `txn` event
+//! per transaction it handled. The stages of a transaction can then be found in the collector
+//! by searching for its hash in the `txn_hash` attribute of the events. The stages of a block
+//! (preparation, execution and commit) are also linked together, each to the one before it.
+//!
+//! Spans are only created when export is enabled (i.e., the crate is built with the
+//! `otlp-tracing` feature and an endpoint is configured), so tracing costs nothing otherwise.
+
+use std::{
+    fmt::Display,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use tracing::field::Empty;
+pub use tracing::Span;
+
+/// Whether spans are exported, i.e., whether the OTLP layer was installed.
+static TRACE_EXPORT_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The target of the per transaction events. These are only exported, not logged.
+pub(crate) const TXN_EVENT_TARGET: &str = "aptos_txn_lifecycle";
+
+/// The maximum number of transaction events exported per span. The events of
+/// any further transactions are dropped, but they are still counted in `num_txns`.
+pub const MAX_TXN_EVENTS_PER_SPAN: u32 = 20_000;
+
+/// The maximum number of blocks for which the last stage span is remembered,
+/// so that the next stage of the block can be linked to it.
+#[cfg(feature = "otlp-tracing")]
+const MAX_TRACKED_BLOCKS: usize = 1_000;
+
+/// The stages of the transaction lifecycle, used as span names.
+pub struct TxnStage;
+
+impl TxnStage {
+    pub const API_SUBMIT: &'static str = "api_submit";
+    pub const BLOCK_PREPARE: &'static str = "block_prepare";
+    pub const COMMIT: &'static str = "commit";
+    pub const EXECUTION: &'static str = "execution";
+    pub const MEMPOOL_INSERT: &'static str = "mempool_insert";
+    pub const QUORUM_STORE_BATCH: &'static str = "quorum_store_batch";
+}
+
+/// Returns true iff spans are exported to an OpenTelemetry collector
+pub fn is_trace_export_enabled() -> bool {
+    TRACE_EXPORT_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Creates a span for a stage of the transaction lifecycle. The span lasts until
+/// it is dropped. It is disabled if spans are not exported.
+pub fn txn_span(stage: &'static str) -> Span {
+    if !is_trace_export_enabled() {
+        return Span::none();
+    }
+    tracing::info_span!(
+        "txn_lifecycle",
+        otel.name = stage,
+        peer_id = aptos_node_identity::peer_id_as_str().unwrap_or(""),
+        id = Empty,
+        num_blocks = Empty,
+        num_txns = Empty,
+    )
+}
+
+/// Records the ID of the block handled by the stage, and links the span to the
+/// previous stage of the block.
+pub fn record_block_id(span: &Span, block_id: impl Display) {
+    record_block_ids(span, [block_id]);
+}
+
+/// Records the IDs of the blocks handled by the stage (the last one as the `id`),
+/// and links the span to the previous stage of each block.
+pub fn record_block_ids<I: Display>(span: &Span, block_ids: impl IntoIterator<Item = I>) {
+    if span.is_disabled() {
+        return;
+    }
+    let block_ids: Vec<_> = block_ids
+        .into_iter()
+        .map(|block_id| block_id.to_string())
+        .collect();
+    if let Some(last_block_id) = block_ids.last() {
+        span.record("id", last_block_id.as_str());
+    }
+    span.record("num_blocks", block_ids.len());
+    #[cfg(feature = "otlp-tracing")]
+    for block_id in block_ids {
+        block_links::link_to_previous_stage(span, block_id);
+    }
+}
+
+/// Records an event for each of the transactions handled by the stage, with
+/// the hash of the transaction. The hashes are not iterated over if the span
+/// is disabled.
+pub fn record_txns<H: Display>(span: &Span, txn_hashes: impl IntoIterator<Item = H>) {
+    if span.is_disabled() {
+        return;
+    }
+    let num_txns = txn_hashes
+        .into_iter()
+        .inspect(|txn_hash| {
+            tracing::info!(
+                target: TXN_EVENT_TARGET,
+                parent: span,
+                txn_hash = %txn_hash,
+                "txn"
+            )
+        })
+        .count();
+    span.record("num_txns", num_txns);
+}
+
+/// Links the stage spans of each block, by remembering the last stage span of
+/// the most recent blocks.
+#[cfg(feature = "otlp-tracing")]
+mod block_links {
+    use super::{Span, MAX_TRACKED_BLOCKS};
+    use aptos_infallible::Mutex;
+    use once_cell::sync::Lazy;
+    use opentelemetry::trace::{SpanContext, TraceContextExt};
+    use std::collections::{HashMap, VecDeque};
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    #[derive(Default)]
+    struct BlockStageSpans {
+        // The span context of the last stage of each block
+        last_stages: HashMap<String, SpanContext>,
+        // The blocks in the order they were first seen, to evict the oldest
+        block_ids: VecDeque<String>,
+    }
+
+    static BLOCK_STAGE_SPANS: Lazy<Mutex<BlockStageSpans>> =
+        Lazy::new(|| Mutex::new(BlockStageSpans::default()));
+
+    /// Links the span to the previous stage of the block (if any), and
+    /// remembers it as the last stage of the block.
+    pub(super) fn link_to_previous_stage(span: &Span, block_id: String) {
+        let span_context = span.context().span().span_context().clone();
+        let mut block_stage_spans = BLOCK_STAGE_SPANS.lock();
+        match block_stage_spans
+            .last_stages
+            .insert(block_id.clone(), span_context)
+        {
+            Some(previous_stage) => span.add_link(previous_stage),
+            None => {
+                block_stage_spans.block_ids.push_back(block_id);
+                if block_stage_spans.block_ids.len() > MAX_TRACKED_BLOCKS {
+                    if let Some(oldest_block_id) = block_stage_spans.block_ids.pop_front() {
+                        block_stage_spans.last_stages.remove(&oldest_block_id);
+                    }
+                }
+            },
+        }
+    }
+}
+
+/// Builds the layer exporting spans to the OTLP/HTTP endpoint of a collector,
+/// e.g., http://localhost:4318/v1/traces. Spans are exported in batches from a
+/// dedicated thread, so this doesn't need to be called from within a runtime.
+#[cfg(feature = "otlp-tracing")]
+pub(crate) fn build_otlp_layer<S>(
+    endpoint: &str,
+) -> Result<impl tracing_subscriber::Layer<S>, opentelemetry::trace::TraceError>
+where
+    S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+{
+    use opentelemetry::KeyValue;
+    use opentelemetry_otlp::WithExportConfig;
+    use opentelemetry_sdk::{runtime, trace, Resource};
+
+    let service_name = std::env::current_exe()
+        .ok()
+        .and_then(|path| Some(path.file_stem()?.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "aptos".to_string());
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .http()
+                .with_endpoint(endpoint),
+        )
+        .with_trace_config(
+            trace::config()
+                .with_max_events_per_span(MAX_TXN_EVENTS_PER_SPAN)
+                .with_resource(Resource::new(vec![KeyValue::new(
+                    "service.name",
+                    service_name,
+                )])),
+        )
+        .install_batch(runtime::TokioCurrentThread)?;
+
+    TRACE_EXPORT_ENABLED.store(true, Ordering::Relaxed);
+    Ok(tracing_opentelemetry::layer().with_tracer(tracer))
+}
+
+#[cfg(all(test, feature = "otlp-tracing"))]
+mod tests {
+    use super::*;
+    use opentelemetry::{trace::TracerProvider as _, Value};
+    use opentelemetry_sdk::{
+        export::trace::SpanData,
+        testing::trace::InMemorySpanExporter,
+        trace::{config, TracerProvider},
+    };
+    use tracing_subscriber::layer::SubscriberExt;
+
+    /// Runs the given function and returns the spans it exported
+    fn export_spans(f: impl FnOnce()) -> Vec<SpanData> {
+        let exporter = InMemorySpanExporter::default();
+        let provider = TracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .with_config(config().with_max_events_per_span(MAX_TXN_EVENTS_PER_SPAN))
+            .build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+
+        TRACE_EXPORT_ENABLED.store(true, Ordering::Relaxed);
+        tracing::subscriber::with_default(subscriber, f);
+        exporter.get_finished_spans().unwrap()
+    }
+
+    fn get_attribute<'a>(attributes: &'a [opentelemetry::KeyValue], key: &str) -> &'a Value {
+        &attributes
+            .iter()
+            .find(|attribute| attribute.key.as_str() == key)
+            .unwrap_or_else(|| panic!("Missing attribute: {}", key))
+            .value
+    }
+
+    #[test]
+    fn test_txn_span_events() {
+        let txn_hashes = vec!["0x1", "0x2", "0x3"];
+        let spans = export_spans(|| {
+            let span = txn_span(TxnStage::MEMPOOL_INSERT);
+            record_txns(&span, &txn_hashes);
+        });
+
+        // Verify the span is named after the stage and has an event per transaction
+        assert_eq!(spans.len(), 1);
+        let span = &spans[0];
+        assert_eq!(span.name, TxnStage::MEMPOOL_INSERT);
+        assert_eq!(
+            get_attribute(&span.attributes, "num_txns"),
+            &Value::I64(txn_hashes.len() as i64)
+        );
+        let event_hashes: Vec<_> = span
+            .events
+            .iter()
+            .map(|event| {
+                get_attribute(&event.attributes, "txn_hash")
+                    .as_str()
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(event_hashes, txn_hashes);
+    }
+
+    #[test]
+    fn test_block_stages_are_linked() {
+        let block_id = "block_for_test_block_stages_are_linked";
+        let spans = export_spans(|| {
+            for stage in [TxnStage::BLOCK_PREPARE, TxnStage::EXECUTION] {
+                let span = txn_span(stage);
+                record_block_id(&span, block_id);
+            }
+            let span = txn_span(TxnStage::COMMIT);
+            record_block_ids(
+                &span,
+                ["other_block_for_test_block_stages_are_linked", block_id],
+            );
+        });
+
+        // Verify that each stage of the block is linked to the previous one
+        assert_eq!(spans.len(), 3);
+        let (prepare, execution, commit) = (&spans[0], &spans[1], &spans[2]);
+        assert_eq!(prepare.name, TxnStage::BLOCK_PREPARE);
+        assert_eq!(prepare.links.iter().count(), 0);
+        let execution_links: Vec<_> = execution.links.iter().collect();
+        assert_eq!(execution_links.len(), 1);
+        assert_eq!(
+            execution_links[0].span_context.span_id(),
+            prepare.span_context.span_id()
+        );
+        let commit_links: Vec<_> = commit.links.iter().collect();
+        assert_eq!(commit_links.len(), 1);
+        assert_eq!(
+            commit_links[0].span_context.span_id(),
+            execution.span_context.span_id()
+        );
+
+        // Verify the last block is recorded as the ID of the commit
+        assert_eq!(get_attribute(&commit.attributes, "id").as_str(), block_id);
+        assert_eq!(
+            get_attribute(&commit.attributes, "num_blocks"),
+            &Value::I64(2)
+        );
+    }
+}
@@ -146,6 +146,11 @@ where
     }
 
     fn on_event(&self, event: &Event, ctx: Context<S>) {
+        // The transaction lifecycle events are only meant to be exported
+        if event.metadata().target() == crate::otlp_tracing::TXN_EVENT_TARGET {
+            return;
+        }
+
         let metadata = match translate_metadata(event.metadata()) {
             Some(metadata) => metadata,
             None => {
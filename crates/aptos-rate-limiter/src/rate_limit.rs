@@ -5,6 +5,7 @@
 use aptos_infallible::{Mutex, RwLock};
 use aptos_logger::debug;
 use aptos_metrics_core::HistogramVec;
+use aptos_time_service::{TimeService, TimeServiceTrait};
 use std::{cmp::min, collections::HashMap, fmt::Debug, hash::Hash, sync::Arc, time::Instant};
 use tokio::time::Duration;
 
@@ -197,6 +198,8 @@ pub struct Bucket {
     last_refresh_time: Instant,
     /// Determines whether the rate limiting should be ignored, useful for testing
     enabled: bool,
+    /// The time service used to refill the bucket (useful for mocking time in tests)
+    time_service: TimeService,
     /// Number of requests allowed through prior to next fill
     allowed_in_period: usize,
     /// Number of requests throttled prior to next fill
@@ -219,6 +222,7 @@ impl Bucket {
             "Bucket size must be greater than or equal to fill rate"
         );
         // Store the stringified version of the key for logging
+        let time_service = TimeService::real();
         Self {
             label,
             log_info,
@@ -226,16 +230,25 @@ impl Bucket {
             tokens: initial,
             size,
             rate,
-            last_refresh_time: Instant::now(),
+            last_refresh_time: time_service.now(),
             enabled: true,
+            time_service,
             allowed_in_period: 0,
             throttled_in_period: 0,
             metrics,
         }
     }
 
+    /// Uses the given time service to refill the bucket (instead of the real time)
+    pub fn with_time_service(mut self, time_service: TimeService) -> Self {
+        self.last_refresh_time = time_service.now();
+        self.time_service = time_service;
+        self
+    }
+
     /// A fully open rate limiter, to allow for ignoring rate limiting for tests
     pub fn open(label: String) -> Self {
+        let time_service = TimeService::real();
         Self {
             label,
             log_info: String::new(),
@@ -243,8 +256,9 @@ impl Bucket {
             tokens: std::usize::MAX,
             size: std::usize::MAX,
             rate: std::usize::MAX,
-            last_refresh_time: Instant::now(),
+            last_refresh_time: time_service.now(),
             enabled: false,
+            time_service,
             allowed_in_period: 0,
             throttled_in_period: 0,
             metrics: None,
@@ -253,7 +267,11 @@ impl Bucket {
 
     /// Refill tokens based on how many seconds have passed since last refresh
     pub(crate) fn refill(&mut self) {
-        let num_intervals = self.last_refresh_time.elapsed().as_secs();
+        let num_intervals = self
+            .time_service
+            .now()
+            .saturating_duration_since(self.last_refresh_time)
+            .as_secs();
         if num_intervals > 0 {
             // Log how many were throttled in the period before refill
             if self.allowed_in_period > 0 || self.throttled_in_period > 0 {
@@ -355,7 +373,7 @@ impl Bucket {
     /// all tokens are ready.  Returns `None` if it is never possible.
     pub fn time_of_tokens_needed(&self, requested: usize) -> Option<Instant> {
         if !self.enabled {
-            Some(Instant::now())
+            Some(self.time_service.now())
         } else if self.size < requested {
             // This means the batch can never succeed
             None
@@ -477,6 +495,35 @@ mod tests {
         bucket.acquire_tokens(1).unwrap();
     }
 
+    #[test]
+    fn test_refill_with_mock_time() {
+        let bucket_size = 5;
+        let bucket_rate = 2;
+        let time_service = TimeService::mock();
+        let mut bucket = Bucket::new(
+            "test".into(),
+            "test".into(),
+            "Key".into(),
+            bucket_size,
+            bucket_size,
+            bucket_rate,
+            None,
+        )
+        .with_time_service(time_service.clone());
+        assert_eq!(bucket_size, bucket.acquire_tokens(bucket_size).unwrap());
+
+        // Verify the bucket is not refilled before the end of the interval
+        let mock_time_service = time_service.into_mock();
+        mock_time_service.advance(Duration::from_millis(999));
+        bucket.acquire_tokens(1).expect_err("Expected time to wait");
+
+        // Verify the bucket is refilled once per interval (up to the bucket size)
+        mock_time_service.advance(Duration::from_millis(1));
+        assert_eq!(bucket_rate, bucket.acquire_tokens(bucket_size).unwrap());
+        mock_time_service.advance(Duration::from_secs(10));
+        assert_eq!(bucket_size, bucket.acquire_tokens(bucket_size + 1).unwrap());
+    }
+
     #[test]
     fn test_time_checks() {
         let bucket_size = 5;
@@ -8,8 +8,8 @@
 
 use crate::{
     common::{
-        check_network, get_block_index_from_request, handle_request, native_coin, native_coin_tag,
-        with_context,
+        check_network, currency_fa_address, get_block_index_from_request, handle_request,
+        native_coin, native_coin_fa_address, native_coin_tag, primary_store_address, with_context,
     },
     error::{ApiError, ApiResult},
     types::{AccountBalanceRequest, AccountBalanceResponse, Amount, Currency, *},
@@ -67,6 +67,7 @@ async fn account_balance(
     let balance_version = block_info.last_version;
 
     let (sequence_number, operators, balances, lockup_expiration) = get_balances(
+        &server_context,
         &rest_client,
         request.account_identifier,
         balance_version,
@@ -88,6 +89,7 @@ async fn account_balance(
 /// Retrieve the balances for an account
 #[allow(clippy::manual_retain)]
 async fn get_balances(
+    server_context: &RosettaContext,
     rest_client: &aptos_rest_client::Client,
     account: AccountIdentifier,
     version: u64,
@@ -142,6 +144,7 @@ async fn get_balances(
         let resources = response.into_inner();
         let mut maybe_sequence_number = None;
         let mut maybe_operators = None;
+        let mut maybe_native_coin_balance = None;
 
         // Iterate through resources, converting balances
         for (struct_tag, bytes) in resources {
@@ -161,16 +164,16 @@ async fn get_balances(
                         if let Some(coin_type) = struct_tag.type_params.first() {
                             // Only display supported coins
                             if coin_type == &native_coin_tag() {
-                                balances.push(Amount {
-                                    value: coin_store.coin().to_string(),
-                                    currency: native_coin(),
-                                });
+                                maybe_native_coin_balance = Some(coin_store.coin());
                             }
                         }
                     }
                 },
                 (AccountAddress::ONE, STAKING_CONTRACT_MODULE, STORE_RESOURCE) => {
-                    if account.is_base_account() || pool_address.is_some() {
+                    if account.is_base_account()
+                        || account.is_fungible_store()
+                        || pool_address.is_some()
+                    {
                         continue;
                     }
 
@@ -229,6 +232,65 @@ async fn get_balances(
             }
         }
 
+        if account.is_base_account() {
+            // The native coin migrates from the `CoinStore` to the primary fungible store, so the
+            // balance is the sum of both
+            let maybe_native_fa_balance = get_fungible_store(
+                rest_client,
+                primary_store_address(owner_address, native_coin_fa_address()),
+                version,
+            )
+            .await?
+            .map(|store| store.balance());
+            if maybe_native_coin_balance.is_some() || maybe_native_fa_balance.is_some() {
+                balances.push(Amount {
+                    value: maybe_native_coin_balance
+                        .unwrap_or_default()
+                        .saturating_add(maybe_native_fa_balance.unwrap_or_default())
+                        .to_string(),
+                    currency: native_coin(),
+                });
+            }
+
+            // Other fungible assets are only shown from the primary store on the base account
+            for currency in server_context.currencies.iter() {
+                if currency == &native_coin()
+                    || maybe_filter_currencies
+                        .as_ref()
+                        .is_some_and(|currencies| !currencies.contains(currency))
+                {
+                    continue;
+                }
+
+                if let Some(fa_address) = currency_fa_address(currency)? {
+                    if let Some(store) = get_fungible_store(
+                        rest_client,
+                        primary_store_address(owner_address, fa_address),
+                        version,
+                    )
+                    .await?
+                    {
+                        balances.push(Amount {
+                            value: store.balance().to_string(),
+                            currency: currency.clone(),
+                        });
+                    }
+                }
+            }
+        } else if account.is_fungible_store() {
+            if let Some(balance) = get_secondary_store_balance(
+                server_context,
+                rest_client,
+                owner_address,
+                account.fungible_store_address()?,
+                version,
+            )
+            .await?
+            {
+                balances.push(balance);
+            }
+        }
+
         let sequence_number = if let Some(sequence_number) = maybe_sequence_number {
             sequence_number
         } else {
@@ -277,3 +339,39 @@ async fn get_balances(
         ))
     }
 }
+
+/// Retrieve the balance of a secondary fungible store owned by the account
+async fn get_secondary_store_balance(
+    server_context: &RosettaContext,
+    rest_client: &aptos_rest_client::Client,
+    owner_address: AccountAddress,
+    store_address: AccountAddress,
+    version: u64,
+) -> ApiResult<Option<Amount>> {
+    let store = if let Some(store) = get_fungible_store(rest_client, store_address, version).await?
+    {
+        store
+    } else {
+        return Ok(None);
+    };
+
+    // Only show stores that belong to the account
+    let maybe_owner = get_object_owner(rest_client, store_address, version).await?;
+    if maybe_owner != Some(owner_address) {
+        warn!(
+            "Fungible store {} is not owned by {} at version {}, owner: {:?}",
+            store_address, owner_address, version, maybe_owner
+        );
+        return Ok(None);
+    }
+
+    // Only display supported currencies
+    if let Some(currency) = server_context.fa_currency(store.metadata()) {
+        Ok(Some(Amount {
+            value: store.balance().to_string(),
+            currency,
+        }))
+    } else {
+        Ok(None)
+    }
+}
@@ -6,11 +6,15 @@ use crate::{
         check_network, get_block_index_from_request, get_timestamp, handle_request, with_context,
         BlockHash, Y2K_MS,
     },
-    error::ApiResult,
-    types::{Block, BlockIdentifier, BlockRequest, BlockResponse, Transaction},
+    error::{ApiError, ApiResult},
+    types::{
+        Block, BlockIdentifier, BlockRequest, BlockResponse, BlockTransaction,
+        BlockTransactionRequest, BlockTransactionResponse, Transaction, TransactionIdentifier,
+    },
     RosettaContext,
 };
 use aptos_logger::{debug, trace};
+use aptos_rest_client::aptos_api_types::{TransactionData, TransactionOnChainData};
 use aptos_types::chain_id::ChainId;
 use std::sync::Arc;
 use warp::Filter;
@@ -69,6 +73,85 @@ async fn block(request: BlockRequest, server_context: RosettaContext) -> ApiResu
     Ok(BlockResponse { block })
 }
 
+pub fn block_transaction_route(
+    server_context: RosettaContext,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("block" / "transaction")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_context(server_context))
+        .and_then(handle_request(block_transaction))
+}
+
+/// Retrieves a single transaction in a block by its hash
+///
+/// [API Spec](https://www.rosetta-api.org/docs/BlockApi.html#blocktransaction)
+async fn block_transaction(
+    request: BlockTransactionRequest,
+    server_context: RosettaContext,
+) -> ApiResult<BlockTransactionResponse> {
+    debug!("/block/transaction");
+    trace!(
+        request = ?request,
+        server_context = ?server_context,
+        "/block/transaction",
+    );
+
+    check_network(request.network_identifier, &server_context)?;
+
+    let txn = get_committed_transaction(&server_context, &request.transaction_identifier)
+        .await?
+        .ok_or(ApiError::TransactionIsPending)?;
+    let block_transaction = build_block_transaction(&server_context, txn).await?;
+
+    // The transaction has to be in the block requested
+    if block_transaction.block_identifier != request.block_identifier {
+        return Err(ApiError::TransactionNotFound(Some(format!(
+            "Transaction {} is not in block {}",
+            request.transaction_identifier.hash, request.block_identifier.index
+        ))));
+    }
+
+    Ok(BlockTransactionResponse {
+        transaction: block_transaction.transaction,
+    })
+}
+
+/// Retrieves a transaction by hash, or `None` if it's still pending in mempool
+pub(crate) async fn get_committed_transaction(
+    server_context: &RosettaContext,
+    transaction_identifier: &TransactionIdentifier,
+) -> ApiResult<Option<TransactionOnChainData>> {
+    let hash = transaction_identifier.hash_value()?;
+    match server_context
+        .rest_client()?
+        .get_transaction_by_hash_bcs(hash)
+        .await?
+        .into_inner()
+    {
+        TransactionData::OnChain(txn) => Ok(Some(txn)),
+        TransactionData::Pending(_) => Ok(None),
+    }
+}
+
+/// Builds a committed transaction along with the identifier of the block it's in
+pub(crate) async fn build_block_transaction(
+    server_context: &RosettaContext,
+    txn: TransactionOnChainData,
+) -> ApiResult<BlockTransaction> {
+    let block = server_context
+        .block_cache()?
+        .get_block_by_version(txn.version)
+        .await?;
+    let block_identifier = BlockIdentifier::from_block(&block, server_context.chain_id);
+    let transaction = Transaction::from_transaction(server_context, txn).await?;
+
+    Ok(BlockTransaction {
+        block_identifier,
+        transaction,
+    })
+}
+
 /// Build up the transaction, which should contain the `operations` as the change set
 async fn build_block(
     server_context: &RosettaContext,
@@ -191,6 +274,18 @@ impl BlockRetriever {
         Ok(BlockInfo::from_block(&block, chain_id))
     }
 
+    /// Retrieves the block containing the version, without its transactions
+    pub async fn get_block_by_version(
+        &self,
+        version: u64,
+    ) -> ApiResult<aptos_rest_client::aptos_api_types::BcsBlock> {
+        Ok(self
+            .rest_client
+            .get_block_by_version_bcs(version, false)
+            .await?
+            .into_inner())
+    }
+
     pub async fn get_block_by_height(
         &self,
         height: u64,
@@ -16,7 +16,10 @@ use aptos_sdk::move_types::{
     ident_str,
     language_storage::{StructTag, TypeTag},
 };
-use aptos_types::{account_address::AccountAddress, chain_id::ChainId};
+use aptos_types::{
+    account_address::{create_derived_object_address, AccountAddress},
+    chain_id::ChainId,
+};
 use futures::future::BoxFuture;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{convert::Infallible, fmt::LowerHex, future::Future, str::FromStr};
@@ -144,7 +147,8 @@ pub fn native_coin() -> Currency {
         symbol: DEFAULT_COIN.to_string(),
         decimals: DEFAULT_DECIMALS,
         metadata: Some(CurrencyMetadata {
-            move_type: native_coin_tag().to_string(),
+            move_type: Some(native_coin_tag().to_string()),
+            fa_address: None,
         }),
     }
 }
@@ -166,6 +170,46 @@ pub fn is_native_coin(currency: &Currency) -> ApiResult<()> {
     }
 }
 
+/// The fungible asset paired with the native coin, balances migrate to it from the `CoinStore`
+pub fn native_coin_fa_address() -> AccountAddress {
+    AccountAddress::TEN
+}
+
+/// Retrieves the fungible asset metadata address of a currency, if it is a fungible asset
+pub fn currency_fa_address(currency: &Currency) -> ApiResult<Option<AccountAddress>> {
+    if currency == &native_coin() {
+        return Ok(Some(native_coin_fa_address()));
+    }
+
+    match currency
+        .metadata
+        .as_ref()
+        .and_then(|inner| inner.fa_address.as_ref())
+    {
+        Some(fa_address) => AccountAddress::from_str(fa_address).map(Some).map_err(|_| {
+            ApiError::InvalidInput(Some(format!(
+                "Invalid fungible asset address {} for currency {}",
+                fa_address, currency.symbol
+            )))
+        }),
+        None => Ok(None),
+    }
+}
+
+/// Checks that the currency is either the native coin or a fungible asset
+pub fn is_supported_currency(currency: &Currency) -> ApiResult<()> {
+    if currency_fa_address(currency)?.is_some() {
+        Ok(())
+    } else {
+        Err(ApiError::UnsupportedCurrency(Some(currency.symbol.clone())))
+    }
+}
+
+/// The address of the primary fungible store of an account for a fungible asset
+pub fn primary_store_address(owner: AccountAddress, metadata: AccountAddress) -> AccountAddress {
+    create_derived_object_address(owner, metadata)
+}
+
 /// Determines which block to pull for the request
 pub async fn get_block_index_from_request(
     server_context: &RosettaContext,
@@ -294,8 +338,18 @@ pub fn parse_currency(address: AccountAddress, module: &str, name: &str) -> ApiR
 
 #[cfg(test)]
 mod test {
-    use crate::common::BlockHash;
-    use aptos_types::chain_id::{ChainId, NamedChain};
+    use crate::{
+        common::{
+            currency_fa_address, is_supported_currency, native_coin, native_coin_fa_address,
+            primary_store_address, BlockHash,
+        },
+        types::{Currency, CurrencyMetadata},
+    };
+    use aptos_types::{
+        account_address::AccountAddress,
+        account_config::fungible_store::primary_store,
+        chain_id::{ChainId, NamedChain},
+    };
     use std::str::FromStr;
 
     #[test]
@@ -339,4 +393,53 @@ mod test {
             BlockHash::from_str(str).expect_err("Invalid block hash");
         }
     }
+
+    #[test]
+    pub fn native_coin_primary_store() {
+        let owner = AccountAddress::from_str("0x1234").unwrap();
+        assert_eq!(
+            primary_store(&owner),
+            primary_store_address(owner, native_coin_fa_address())
+        );
+    }
+
+    #[test]
+    pub fn currency_fa_addresses() {
+        assert_eq!(
+            Some(native_coin_fa_address()),
+            currency_fa_address(&native_coin()).unwrap()
+        );
+
+        let fa_currency = Currency {
+            symbol: "USDC".to_string(),
+            decimals: 6,
+            metadata: Some(CurrencyMetadata {
+                move_type: None,
+                fa_address: Some("0xbae".to_string()),
+            }),
+        };
+        assert_eq!(
+            Some(AccountAddress::from_str("0xbae").unwrap()),
+            currency_fa_address(&fa_currency).unwrap()
+        );
+        is_supported_currency(&fa_currency).expect("Fungible assets are supported");
+
+        let unknown_currency = Currency {
+            symbol: "UNKNOWN".to_string(),
+            decimals: 6,
+            metadata: None,
+        };
+        assert_eq!(None, currency_fa_address(&unknown_currency).unwrap());
+        is_supported_currency(&unknown_currency).expect_err("Currency is not a fungible asset");
+
+        let invalid_currency = Currency {
+            symbol: "INVALID".to_string(),
+            decimals: 6,
+            metadata: Some(CurrencyMetadata {
+                move_type: None,
+                fa_address: Some("not an address".to_string()),
+            }),
+        };
+        currency_fa_address(&invalid_currency).expect_err("Invalid fungible asset address");
+    }
 }
@@ -534,6 +534,14 @@ async fn construction_parse(
                 (AccountAddress::ONE, APTOS_ACCOUNT_MODULE, TRANSFER_FUNCTION) => {
                     parse_account_transfer_operation(sender, &type_args, &args)?
                 },
+                (AccountAddress::ONE, PRIMARY_FUNGIBLE_STORE_MODULE, TRANSFER_FUNCTION) => {
+                    parse_primary_fungible_store_transfer_operation(
+                        &server_context,
+                        sender,
+                        &type_args,
+                        &args,
+                    )?
+                },
                 (AccountAddress::ONE, APTOS_ACCOUNT_MODULE, CREATE_ACCOUNT_FUNCTION) => {
                     parse_create_account_operation(sender, &type_args, &args)?
                 },
@@ -741,6 +749,67 @@ fn parse_account_transfer_operation(
     Ok(operations)
 }
 
+fn parse_primary_fungible_store_transfer_operation(
+    server_context: &RosettaContext,
+    sender: AccountAddress,
+    type_args: &[TypeTag],
+    args: &[Vec<u8>],
+) -> ApiResult<Vec<Operation>> {
+    // The type argument is the type of the metadata object, which doesn't matter for the transfer
+    if type_args.len() != 1 {
+        return Err(ApiError::TransactionParseError(Some(format!(
+            "Primary fungible store transfer should have one type argument: {:?}",
+            type_args
+        ))));
+    }
+    let mut operations = Vec::new();
+
+    // Check the fungible asset is a supported currency
+    let fa_address: AccountAddress = if let Some(fa_address) = args.first() {
+        bcs::from_bytes(fa_address)?
+    } else {
+        return Err(ApiError::TransactionParseError(Some(
+            "No metadata in primary fungible store transfer".to_string(),
+        )));
+    };
+    let currency = server_context
+        .fa_currency(fa_address)
+        .ok_or_else(|| ApiError::UnsupportedCurrency(Some(fa_address.to_string())))?;
+
+    // Retrieve the args for the operations
+
+    let receiver: AccountAddress = if let Some(receiver) = args.get(1) {
+        bcs::from_bytes(receiver)?
+    } else {
+        return Err(ApiError::TransactionParseError(Some(
+            "No receiver in primary fungible store transfer".to_string(),
+        )));
+    };
+    let amount: u64 = if let Some(amount) = args.get(2) {
+        bcs::from_bytes(amount)?
+    } else {
+        return Err(ApiError::TransactionParseError(Some(
+            "No amount in primary fungible store transfer".to_string(),
+        )));
+    };
+
+    operations.push(Operation::withdraw(
+        0,
+        None,
+        AccountIdentifier::base_account(sender),
+        currency.clone(),
+        amount,
+    ));
+    operations.push(Operation::deposit(
+        1,
+        None,
+        AccountIdentifier::base_account(receiver),
+        currency,
+        amount,
+    ));
+    Ok(operations)
+}
+
 pub fn parse_function_arg<T: DeserializeOwned>(
     name: &str,
     args: &[Vec<u8>],
@@ -1363,6 +1432,9 @@ async fn construction_submit(
     let txn: SignedTransaction = decode_bcs(&request.signed_transaction, "SignedTransaction")?;
     let hash = txn.committed_hash();
     rest_client.submit_bcs(&txn).await?;
+    server_context
+        .submitted_transactions
+        .insert(hash, txn.expiration_timestamp_secs());
     Ok(ConstructionSubmitResponse {
         transaction_identifier: hash.into(),
     })
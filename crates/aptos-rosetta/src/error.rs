@@ -31,6 +31,7 @@ pub enum ApiError {
     TransactionParseError(Option<String>),
     InternalError(Option<String>),
     CoinTypeFailedToBeFetched(Option<String>),
+    UnsupportedOperation(Option<String>),
 
     // Below here are codes directly from the REST API
     AccountNotFound(Option<String>),
@@ -81,6 +82,7 @@ impl ApiError {
             TransactionParseError(None),
             InternalError(None),
             CoinTypeFailedToBeFetched(None),
+            UnsupportedOperation(None),
             AccountNotFound(None),
             ResourceNotFound(None),
             ModuleNotFound(None),
@@ -137,6 +139,7 @@ impl ApiError {
             MempoolIsFull(_) => 32,
             CoinTypeFailedToBeFetched(_) => 33,
             StateValueNotFound(_) => 34,
+            UnsupportedOperation(_) => 35,
         }
     }
 
@@ -194,6 +197,7 @@ impl ApiError {
             ApiError::VmError(_) => "Transaction submission failed due to VM error",
             ApiError::MempoolIsFull(_) => "Mempool is full all accounts",
             ApiError::GasEstimationFailed(_) => "Gas estimation failed",
+            ApiError::UnsupportedOperation(_) => "Operation is not supported",
         }
     }
 
@@ -224,6 +228,7 @@ impl ApiError {
             ApiError::MempoolIsFull(inner) => inner,
             ApiError::GasEstimationFailed(inner) => inner,
             ApiError::MaxGasFeeTooLow(inner) => inner,
+            ApiError::UnsupportedOperation(inner) => inner,
             _ => None,
         }
         .map(|details| ErrorDetails { details })
@@ -351,3 +356,31 @@ impl Reply for ApiError {
         warp::reply::json(&self.into_error()).into_response()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_error_codes_are_unique() {
+        let errors = ApiError::all();
+        let codes: HashSet<_> = errors.iter().map(ApiError::code).collect();
+        assert_eq!(errors.len(), codes.len());
+    }
+
+    #[test]
+    fn test_unsupported_operation() {
+        let error = ApiError::UnsupportedOperation(Some("Listing mempool".to_string()));
+        assert!(ApiError::all().contains(&ApiError::UnsupportedOperation(None)));
+        assert_eq!(35, error.code());
+        assert!(!error.retriable());
+
+        let error = error.into_error();
+        assert_eq!("Operation is not supported", error.message);
+        assert_eq!(
+            Some("Listing mempool".to_string()),
+            error.details.map(|details| details.details)
+        );
+    }
+}
@@ -7,15 +7,20 @@
 
 use crate::{
     block::BlockRetriever,
-    common::{handle_request, with_context},
+    common::{currency_fa_address, handle_request, native_coin, with_context},
     error::{ApiError, ApiResult},
-    types::Store,
+    mempool::SubmittedTransactions,
+    types::{Currency, Store},
 };
 use aptos_config::config::ApiConfig;
 use aptos_logger::{debug, warn};
 use aptos_types::{account_address::AccountAddress, chain_id::ChainId};
 use aptos_warp_webserver::{logger, Error, WebServer};
-use std::{collections::BTreeMap, convert::Infallible, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashSet},
+    convert::Infallible,
+    sync::Arc,
+};
 use tokio::task::JoinHandle;
 use warp::{
     http::{HeaderValue, Method, StatusCode},
@@ -25,7 +30,9 @@ use warp::{
 mod account;
 mod block;
 mod construction;
+mod mempool;
 mod network;
+mod search;
 
 pub mod client;
 pub mod common;
@@ -46,6 +53,10 @@ pub struct RosettaContext {
     pub block_cache: Option<Arc<BlockRetriever>>,
     pub owner_addresses: Vec<AccountAddress>,
     pub pool_address_to_owner: BTreeMap<AccountAddress, AccountAddress>,
+    /// Fungible asset currencies supported in addition to the native coin
+    pub currencies: HashSet<Currency>,
+    /// Transactions submitted through this instance, that may still be in mempool
+    submitted_transactions: Arc<SubmittedTransactions>,
 }
 
 impl RosettaContext {
@@ -54,6 +65,7 @@ impl RosettaContext {
         chain_id: ChainId,
         block_cache: Option<Arc<BlockRetriever>>,
         owner_addresses: Vec<AccountAddress>,
+        currencies: HashSet<Currency>,
     ) -> Self {
         let mut pool_address_to_owner = BTreeMap::new();
         if let Some(ref rest_client) = rest_client {
@@ -87,9 +99,20 @@ impl RosettaContext {
             block_cache,
             owner_addresses,
             pool_address_to_owner,
+            currencies,
+            submitted_transactions: Arc::new(SubmittedTransactions::default()),
         }
     }
 
+    /// Retrieves the supported currency of a fungible asset, if any
+    pub fn fa_currency(&self, fa_address: AccountAddress) -> Option<Currency> {
+        let native_coin = native_coin();
+        std::iter::once(&native_coin)
+            .chain(self.currencies.iter())
+            .find(|currency| currency_fa_address(currency).ok().flatten() == Some(fa_address))
+            .cloned()
+    }
+
     fn rest_client(&self) -> ApiResult<Arc<aptos_rest_client::Client>> {
         if let Some(ref client) = self.rest_client {
             Ok(client.clone())
@@ -113,6 +136,7 @@ pub fn bootstrap(
     api_config: ApiConfig,
     rest_client: Option<aptos_rest_client::Client>,
     owner_addresses: Vec<AccountAddress>,
+    currencies: HashSet<Currency>,
 ) -> anyhow::Result<tokio::runtime::Runtime> {
     let runtime = aptos_runtimes::spawn_named_runtime("rosetta".into(), None);
 
@@ -123,6 +147,7 @@ pub fn bootstrap(
         api_config,
         rest_client,
         owner_addresses,
+        currencies,
     ));
     Ok(runtime)
 }
@@ -133,6 +158,7 @@ pub async fn bootstrap_async(
     api_config: ApiConfig,
     rest_client: Option<aptos_rest_client::Client>,
     owner_addresses: Vec<AccountAddress>,
+    currencies: HashSet<Currency>,
 ) -> anyhow::Result<JoinHandle<()>> {
     debug!("Starting up Rosetta server with {:?}", api_config);
 
@@ -160,8 +186,14 @@ pub async fn bootstrap_async(
             ))
         });
 
-        let context =
-            RosettaContext::new(rest_client.clone(), chain_id, block_cache, owner_addresses).await;
+        let context = RosettaContext::new(
+            rest_client.clone(),
+            chain_id,
+            block_cache,
+            owner_addresses,
+            currencies,
+        )
+        .await;
         api.serve(routes(context)).await;
     });
     Ok(handle)
@@ -173,6 +205,7 @@ pub fn routes(
 ) -> impl Filter<Extract = (impl Reply,), Error = Infallible> + Clone {
     account::routes(context.clone())
         .or(block::block_route(context.clone()))
+        .or(block::block_transaction_route(context.clone()))
         .or(construction::combine_route(context.clone()))
         .or(construction::derive_route(context.clone()))
         .or(construction::hash_route(context.clone()))
@@ -181,9 +214,12 @@ pub fn routes(
         .or(construction::payloads_route(context.clone()))
         .or(construction::preprocess_route(context.clone()))
         .or(construction::submit_route(context.clone()))
+        .or(mempool::mempool_route(context.clone()))
+        .or(mempool::mempool_transaction_route(context.clone()))
         .or(network::list_route(context.clone()))
         .or(network::options_route(context.clone()))
         .or(network::status_route(context.clone()))
+        .or(search::search_transactions_route(context.clone()))
         .or(health_check_route(context))
         .with(
             warp::cors()
@@ -0,0 +1,235 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    common::{check_network, handle_request, with_context},
+    error::{ApiError, ApiResult},
+    types::{
+        MempoolRequest, MempoolResponse, MempoolTransactionRequest, MempoolTransactionResponse,
+        Transaction,
+    },
+    RosettaContext,
+};
+use aptos_crypto::HashValue;
+use aptos_infallible::Mutex;
+use aptos_logger::{debug, trace};
+use aptos_rest_client::aptos_api_types::TransactionData;
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use warp::Filter;
+
+/// Maximum number of submitted transactions that are tracked
+const MAX_SUBMITTED_TRANSACTIONS: usize = 1000;
+
+/// The transactions submitted through this instance (with `/construction/submit`) that may still
+/// be in mempool.  The fullnode doesn't expose the contents of its mempool, so these are the only
+/// pending transactions that can be listed.
+#[derive(Debug, Default)]
+pub struct SubmittedTransactions {
+    /// The expiration timestamp (in seconds) of each transaction, by hash
+    expiration_times: Mutex<HashMap<HashValue, u64>>,
+}
+
+impl SubmittedTransactions {
+    /// Tracks the submitted transaction, until it's committed or expires.  If too many are
+    /// tracked, the transaction that expires first is no longer tracked.
+    pub fn insert(&self, hash: HashValue, expiration_timestamp_secs: u64) {
+        let mut expiration_times = self.expiration_times.lock();
+        if expiration_times.len() >= MAX_SUBMITTED_TRANSACTIONS
+            && !expiration_times.contains_key(&hash)
+        {
+            let first_expiring = expiration_times
+                .iter()
+                .min_by_key(|(_, expiration_timestamp_secs)| **expiration_timestamp_secs)
+                .map(|(hash, _)| *hash);
+            if let Some(first_expiring) = first_expiring {
+                expiration_times.remove(&first_expiring);
+            }
+        }
+        expiration_times.insert(hash, expiration_timestamp_secs);
+    }
+
+    /// Stops tracking the transaction (e.g., once it's committed)
+    pub fn remove(&self, hash: &HashValue) {
+        self.expiration_times.lock().remove(hash);
+    }
+
+    /// Returns the hashes of the transactions that haven't expired at the given time, and
+    /// stops tracking the expired ones
+    pub fn unexpired(&self, now_secs: u64) -> Vec<HashValue> {
+        let mut expiration_times = self.expiration_times.lock();
+        expiration_times
+            .retain(|_, expiration_timestamp_secs| *expiration_timestamp_secs > now_secs);
+        let mut hashes: Vec<_> = expiration_times.keys().copied().collect();
+        hashes.sort();
+        hashes
+    }
+}
+
+pub fn mempool_route(
+    server_context: RosettaContext,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("mempool")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_context(server_context))
+        .and_then(handle_request(mempool))
+}
+
+pub fn mempool_transaction_route(
+    server_context: RosettaContext,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("mempool" / "transaction")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_context(server_context))
+        .and_then(handle_request(mempool_transaction))
+}
+
+/// Lists the transactions in mempool that were submitted through this instance
+///
+/// The fullnode doesn't expose the contents of its mempool, so only the transactions submitted
+/// with `/construction/submit` are listed (once the fullnode confirms they're still pending).
+/// Other pending transactions can still be looked up by hash with [`mempool_transaction`].
+///
+/// [API Spec](https://www.rosetta-api.org/docs/MempoolApi.html#mempool)
+async fn mempool(
+    request: MempoolRequest,
+    server_context: RosettaContext,
+) -> ApiResult<MempoolResponse> {
+    debug!("/mempool");
+    trace!(
+        request = ?request,
+        server_context = ?server_context,
+        "/mempool",
+    );
+
+    check_network(request.network_identifier, &server_context)?;
+    let rest_client = server_context.rest_client()?;
+
+    let now_secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|err| ApiError::InternalError(Some(err.to_string())))?
+        .as_secs();
+    let hashes = server_context.submitted_transactions.unexpired(now_secs);
+    let results = futures::future::join_all(
+        hashes
+            .iter()
+            .map(|hash| rest_client.get_transaction_by_hash_bcs(*hash)),
+    )
+    .await;
+
+    let mut transaction_identifiers = vec![];
+    for (hash, result) in hashes.into_iter().zip(results) {
+        match result.map(|response| response.into_inner()) {
+            Ok(TransactionData::Pending(_)) => transaction_identifiers.push(hash.into()),
+            Ok(TransactionData::OnChain(_)) => server_context.submitted_transactions.remove(&hash),
+            Err(err) => match ApiError::from(err) {
+                // The transaction was dropped from mempool (e.g., it was rejected)
+                ApiError::TransactionNotFound(_) => {
+                    server_context.submitted_transactions.remove(&hash)
+                },
+                err => return Err(err),
+            },
+        }
+    }
+
+    Ok(MempoolResponse {
+        transaction_identifiers,
+    })
+}
+
+/// Retrieves a transaction that is pending in mempool by its hash
+///
+/// Operations are estimated from the transaction payload, since it hasn't been executed yet
+///
+/// [API Spec](https://www.rosetta-api.org/docs/MempoolApi.html#mempooltransaction)
+async fn mempool_transaction(
+    request: MempoolTransactionRequest,
+    server_context: RosettaContext,
+) -> ApiResult<MempoolTransactionResponse> {
+    debug!("/mempool/transaction");
+    trace!(
+        request = ?request,
+        server_context = ?server_context,
+        "/mempool/transaction",
+    );
+
+    check_network(request.network_identifier, &server_context)?;
+
+    let hash = request.transaction_identifier.hash_value()?;
+    match server_context
+        .rest_client()?
+        .get_transaction_by_hash_bcs(hash)
+        .await?
+        .into_inner()
+    {
+        TransactionData::Pending(txn) => Ok(MempoolTransactionResponse {
+            transaction: Transaction::from_pending_transaction(&server_context, &txn),
+        }),
+        TransactionData::OnChain(_) => Err(ApiError::TransactionNotFound(Some(format!(
+            "Transaction {} is no longer in mempool",
+            request.transaction_identifier.hash
+        )))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::NetworkIdentifier;
+    use aptos_types::chain_id::ChainId;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_submitted_transactions() {
+        let submitted_transactions = SubmittedTransactions::default();
+        let hashes: Vec<_> = (0..MAX_SUBMITTED_TRANSACTIONS as u64 + 1)
+            .map(|i| HashValue::sha3_256_of(&i.to_le_bytes()))
+            .collect();
+        for (i, hash) in hashes.iter().enumerate() {
+            submitted_transactions.insert(*hash, 100 + i as u64);
+        }
+
+        // The transaction that expires first is no longer tracked once too many are
+        let unexpired = submitted_transactions.unexpired(0);
+        assert_eq!(MAX_SUBMITTED_TRANSACTIONS, unexpired.len());
+        assert!(!unexpired.contains(&hashes[0]));
+
+        // Expired and removed transactions are no longer tracked
+        submitted_transactions.remove(&hashes[MAX_SUBMITTED_TRANSACTIONS]);
+        let unexpired = submitted_transactions.unexpired(100 + 10);
+        assert_eq!(MAX_SUBMITTED_TRANSACTIONS - 11, unexpired.len());
+        assert!(!unexpired.contains(&hashes[10]));
+        assert!(unexpired.contains(&hashes[11]));
+        assert!(!unexpired.contains(&hashes[MAX_SUBMITTED_TRANSACTIONS]));
+    }
+
+    #[tokio::test]
+    async fn test_mempool_checks() {
+        let server_context =
+            RosettaContext::new(None, ChainId::test(), None, vec![], HashSet::new()).await;
+
+        // The network is checked first
+        let result = mempool(
+            MempoolRequest {
+                network_identifier: NetworkIdentifier::from(ChainId::new(200)),
+            },
+            server_context.clone(),
+        )
+        .await;
+        assert_eq!(Err(ApiError::NetworkIdentifierMismatch), result);
+
+        // Pending transactions can't be checked when offline
+        let result = mempool(
+            MempoolRequest {
+                network_identifier: NetworkIdentifier::from(ChainId::test()),
+            },
+            server_context,
+        )
+        .await;
+        assert_eq!(Err(ApiError::NodeIsOffline), result);
+    }
+}
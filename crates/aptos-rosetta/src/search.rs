@@ -0,0 +1,375 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    block::{build_block_transaction, get_committed_transaction},
+    common::{check_network, handle_request, with_context},
+    error::{ApiError, ApiResult},
+    types::{
+        BlockTransaction, SearchTransactionsRequest, SearchTransactionsResponse,
+        TransactionIdentifier,
+    },
+    AccountAddress, RosettaContext,
+};
+use aptos_logger::{debug, trace};
+use std::cmp::min;
+use warp::Filter;
+
+/// Default number of transactions returned in a search
+const DEFAULT_SEARCH_LIMIT: u64 = 25;
+/// Maximum number of transactions returned in a search, matching the REST API's page size
+const MAX_SEARCH_LIMIT: u64 = 100;
+
+pub fn search_transactions_route(
+    server_context: RosettaContext,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("search" / "transactions")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_context(server_context))
+        .and_then(handle_request(search_transactions))
+}
+
+/// A validated search, limited to what can be answered from the fullnode
+#[derive(Debug, PartialEq, Eq)]
+struct SearchQuery {
+    transaction_identifier: TransactionIdentifier,
+    account: Option<AccountAddress>,
+    offset: u64,
+    limit: u64,
+}
+
+impl TryFrom<SearchTransactionsRequest> for SearchQuery {
+    type Error = ApiError;
+
+    fn try_from(request: SearchTransactionsRequest) -> ApiResult<Self> {
+        if request
+            .operator
+            .as_ref()
+            .map_or(false, |operator| operator != "and")
+        {
+            return Err(ApiError::UnsupportedOperation(Some(
+                "Only the 'and' operator is supported".to_string(),
+            )));
+        }
+
+        // Operations aren't indexed, so filtering by them would silently miss transactions
+        let unsupported = [
+            ("max_block", request.max_block.is_some()),
+            ("coin_identifier", request.coin_identifier.is_some()),
+            ("currency", request.currency.is_some()),
+            ("status", request.status.is_some()),
+            ("type", request.operation_type.is_some()),
+            ("address", request.address.is_some()),
+            ("success", request.success.is_some()),
+        ];
+        if let Some((field, _)) = unsupported.iter().find(|(_, is_set)| *is_set) {
+            return Err(ApiError::UnsupportedOperation(Some(format!(
+                "Searching by {} is not supported, only by transaction_identifier (and \
+                optionally account_identifier)",
+                field
+            ))));
+        }
+
+        // Transfers received by an account aren't indexed, so an account's transactions can't
+        // all be found
+        let transaction_identifier = match request.transaction_identifier {
+            Some(transaction_identifier) => transaction_identifier,
+            None if request.account_identifier.is_some() => {
+                return Err(ApiError::UnsupportedOperation(Some(
+                    "Searching by account_identifier alone is not supported, as the transactions \
+                    that an account received aren't indexed. A transaction_identifier is required"
+                        .to_string(),
+                )))
+            },
+            None => {
+                return Err(ApiError::InvalidInput(Some(
+                    "A transaction_identifier is required".to_string(),
+                )))
+            },
+        };
+
+        let account = if let Some(account_identifier) = request.account_identifier {
+            if !account_identifier.is_base_account() {
+                return Err(ApiError::InvalidInput(Some(
+                    "Transactions can only be searched by base accounts".to_string(),
+                )));
+            }
+            Some(account_identifier.account_address()?)
+        } else {
+            None
+        };
+
+        Ok(SearchQuery {
+            transaction_identifier,
+            account,
+            offset: request.offset.unwrap_or_default(),
+            limit: min(
+                request.limit.unwrap_or(DEFAULT_SEARCH_LIMIT),
+                MAX_SEARCH_LIMIT,
+            ),
+        })
+    }
+}
+
+/// Searches for a committed transaction by hash, optionally filtered by an account
+///
+/// The account filter matches the transaction if any of its operations are for the account
+/// (e.g., the sender's fee, or a transfer received by the account).  Searching by account alone,
+/// and filters on operations, are rejected, since neither is indexed by the fullnode.
+///
+/// [API Spec](https://www.rosetta-api.org/docs/SearchApi.html#searchtransactions)
+async fn search_transactions(
+    request: SearchTransactionsRequest,
+    server_context: RosettaContext,
+) -> ApiResult<SearchTransactionsResponse> {
+    debug!("/search/transactions");
+    trace!(
+        request = ?request,
+        server_context = ?server_context,
+        "/search/transactions",
+    );
+
+    check_network(request.network_identifier.clone(), &server_context)?;
+    let SearchQuery {
+        transaction_identifier,
+        account,
+        offset,
+        limit,
+    } = SearchQuery::try_from(request)?;
+
+    // A hash matches at most one transaction, and pending transactions aren't in a block yet
+    let txn = match get_committed_transaction(&server_context, &transaction_identifier).await? {
+        Some(txn) => Some(build_block_transaction(&server_context, txn).await?),
+        None => None,
+    };
+    let matching_txns: Vec<_> = txn
+        .into_iter()
+        .filter(|txn| account.map_or(true, |account| is_account_involved(txn, account)))
+        .collect();
+
+    let total_count = matching_txns.len() as u64;
+    let transactions: Vec<_> = matching_txns
+        .into_iter()
+        .skip(offset as usize)
+        .take(limit as usize)
+        .collect();
+    let next_offset = offset + transactions.len() as u64;
+    let next_offset = if next_offset < total_count && !transactions.is_empty() {
+        Some(next_offset)
+    } else {
+        None
+    };
+
+    Ok(SearchTransactionsResponse {
+        transactions,
+        total_count,
+        next_offset,
+    })
+}
+
+/// Returns true iff any of the transaction's operations are for the given (base) account
+fn is_account_involved(txn: &BlockTransaction, account: AccountAddress) -> bool {
+    txn.transaction
+        .operations
+        .iter()
+        .filter_map(|operation| operation.account.as_ref())
+        .any(|account_identifier| {
+            account_identifier.is_base_account()
+                && account_identifier.account_address().ok() == Some(account)
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        common::native_coin,
+        types::{
+            AccountIdentifier, BlockIdentifier, CoinIdentifier, NetworkIdentifier, Operation,
+            OperationStatusType, Transaction, TransactionMetadata, TransactionType,
+        },
+    };
+    use aptos_types::chain_id::ChainId;
+
+    fn request(
+        transaction_identifier: Option<TransactionIdentifier>,
+        account_identifier: Option<AccountIdentifier>,
+    ) -> SearchTransactionsRequest {
+        SearchTransactionsRequest {
+            network_identifier: NetworkIdentifier::from(ChainId::test()),
+            transaction_identifier,
+            account_identifier,
+            offset: None,
+            limit: None,
+            operator: None,
+            max_block: None,
+            coin_identifier: None,
+            currency: None,
+            status: None,
+            operation_type: None,
+            address: None,
+            success: None,
+        }
+    }
+
+    fn transaction_identifier() -> TransactionIdentifier {
+        TransactionIdentifier::from(aptos_crypto::HashValue::zero())
+    }
+
+    #[test]
+    fn test_search_by_hash_and_sender() {
+        let account = AccountAddress::ONE;
+        let query = SearchQuery::try_from(request(
+            Some(transaction_identifier()),
+            Some(AccountIdentifier::base_account(account)),
+        ))
+        .unwrap();
+        assert_eq!(
+            SearchQuery {
+                transaction_identifier: transaction_identifier(),
+                account: Some(account),
+                offset: 0,
+                limit: DEFAULT_SEARCH_LIMIT,
+            },
+            query
+        );
+
+        let mut request = request(Some(transaction_identifier()), None);
+        request.operator = Some("and".to_string());
+        request.offset = Some(5);
+        request.limit = Some(MAX_SEARCH_LIMIT + 1);
+        let query = SearchQuery::try_from(request).unwrap();
+        assert_eq!(transaction_identifier(), query.transaction_identifier);
+        assert_eq!(None, query.account);
+        assert_eq!(5, query.offset);
+        assert_eq!(MAX_SEARCH_LIMIT, query.limit);
+    }
+
+    #[test]
+    fn test_search_requires_identifier() {
+        assert!(matches!(
+            SearchQuery::try_from(request(None, None)),
+            Err(ApiError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_search_rejects_account_only() {
+        // Transfers received by the account can't be found, so the results would be incomplete
+        let account = AccountIdentifier::base_account(AccountAddress::ONE);
+        assert!(matches!(
+            SearchQuery::try_from(request(None, Some(account))),
+            Err(ApiError::UnsupportedOperation(_))
+        ));
+    }
+
+    #[test]
+    fn test_search_rejects_sub_accounts() {
+        let store_account =
+            AccountIdentifier::fungible_store_account(AccountAddress::ONE, AccountAddress::TWO);
+        assert!(matches!(
+            SearchQuery::try_from(request(Some(transaction_identifier()), Some(store_account))),
+            Err(ApiError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_search_rejects_unsupported_filters() {
+        let account = AccountIdentifier::base_account(AccountAddress::ONE);
+        let base = request(Some(transaction_identifier()), Some(account));
+        let mut requests = vec![];
+
+        let mut or_request = base.clone();
+        or_request.operator = Some("or".to_string());
+        requests.push(or_request);
+        let mut max_block_request = base.clone();
+        max_block_request.max_block = Some(10);
+        requests.push(max_block_request);
+        let mut coin_request = base.clone();
+        coin_request.coin_identifier = Some(CoinIdentifier {
+            identifier: "coin".to_string(),
+        });
+        requests.push(coin_request);
+        let mut currency_request = base.clone();
+        currency_request.currency = Some(native_coin());
+        requests.push(currency_request);
+        let mut status_request = base.clone();
+        status_request.status = Some("success".to_string());
+        requests.push(status_request);
+        let mut type_request = base.clone();
+        type_request.operation_type = Some("deposit".to_string());
+        requests.push(type_request);
+        let mut address_request = base.clone();
+        address_request.address = Some(AccountAddress::ONE.to_string());
+        requests.push(address_request);
+        let mut success_request = base;
+        success_request.success = Some(true);
+        requests.push(success_request);
+
+        for request in requests {
+            assert!(
+                matches!(
+                    SearchQuery::try_from(request.clone()),
+                    Err(ApiError::UnsupportedOperation(_))
+                ),
+                "{:?} should be rejected",
+                request
+            );
+        }
+    }
+
+    #[test]
+    fn test_account_involvement() {
+        let sender = AccountAddress::ONE;
+        let receiver = AccountAddress::TWO;
+        let txn = BlockTransaction {
+            block_identifier: BlockIdentifier {
+                index: 1,
+                hash: "hash".to_string(),
+            },
+            transaction: Transaction {
+                transaction_identifier: transaction_identifier(),
+                operations: vec![
+                    Operation::withdraw(
+                        0,
+                        Some(OperationStatusType::Success),
+                        AccountIdentifier::base_account(sender),
+                        native_coin(),
+                        10,
+                    ),
+                    Operation::deposit(
+                        1,
+                        Some(OperationStatusType::Success),
+                        AccountIdentifier::base_account(receiver),
+                        native_coin(),
+                        10,
+                    ),
+                    Operation::gas_fee(2, sender, 1, 100),
+                ],
+                metadata: TransactionMetadata {
+                    transaction_type: TransactionType::User,
+                    version: 1u64.into(),
+                    failed: false,
+                    vm_status: "Executed successfully".to_string(),
+                },
+            },
+        };
+
+        // Both the sender and the receiver of a transfer are found
+        assert!(is_account_involved(&txn, sender));
+        assert!(is_account_involved(&txn, receiver));
+        assert!(!is_account_involved(&txn, AccountAddress::THREE));
+    }
+
+    #[test]
+    fn test_search_request_type_field() {
+        let request: SearchTransactionsRequest = serde_json::from_value(serde_json::json!({
+            "network_identifier": NetworkIdentifier::from(ChainId::test()),
+            "account_identifier": AccountIdentifier::base_account(AccountAddress::ONE),
+            "type": "deposit",
+        }))
+        .unwrap();
+        assert_eq!(Some("deposit".to_string()), request.operation_type);
+    }
+}
@@ -25,7 +25,7 @@ use std::{
 pub struct AccountIdentifier {
     /// Hex encoded AccountAddress beginning with 0x
     pub address: String,
-    /// Sub account only used for staking
+    /// Sub account only used for staking and secondary fungible stores
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sub_account: Option<SubAccountIdentifier>,
 }
@@ -98,6 +98,14 @@ impl AccountIdentifier {
         }
     }
 
+    /// A secondary fungible store owned by the account, the primary store is part of the base account
+    pub fn fungible_store_account(address: AccountAddress, store_address: AccountAddress) -> Self {
+        AccountIdentifier {
+            address: to_hex_lower(&address),
+            sub_account: Some(SubAccountIdentifier::new_fungible_store(store_address)),
+        }
+    }
+
     pub fn is_base_account(&self) -> bool {
         self.sub_account.is_none()
     }
@@ -172,7 +180,16 @@ impl AccountIdentifier {
                 || inner.is_active_stake()
                 || inner.is_pending_active_stake()
                 || inner.is_inactive_stake()
-                || inner.is_pending_inactive_stake())
+                || inner.is_pending_inactive_stake()
+                || inner.is_fungible_store())
+        } else {
+            false
+        }
+    }
+
+    pub fn is_fungible_store(&self) -> bool {
+        if let Some(ref inner) = self.sub_account {
+            inner.is_fungible_store()
         } else {
             false
         }
@@ -187,6 +204,16 @@ impl AccountIdentifier {
             )))
         }
     }
+
+    pub fn fungible_store_address(&self) -> ApiResult<AccountAddress> {
+        if let Some(ref inner) = self.sub_account {
+            inner.fungible_store_address()
+        } else {
+            Err(ApiError::InternalError(Some(
+                "Can't get store address of a non-fungible store account".to_string(),
+            )))
+        }
+    }
 }
 
 fn str_to_account_address(address: &str) -> Result<AccountAddress, ApiError> {
@@ -194,9 +221,10 @@ fn str_to_account_address(address: &str) -> Result<AccountAddress, ApiError> {
         .map_err(|_| ApiError::InvalidInput(Some("Invalid account address".to_string())))
 }
 
-/// There are two types of SubAccountIdentifiers
+/// There are three types of SubAccountIdentifiers
 /// 1. `stake` which is the total stake
 /// 2. `stake-<operator>` which is the stake on the operator
+/// 3. `fungible_store-<store>` which is a secondary fungible store owned by the account
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct SubAccountIdentifier {
     /// Hex encoded AccountAddress beginning with 0x
@@ -211,6 +239,7 @@ const PENDING_ACTIVE_STAKE: &str = "pending_active_stake";
 const ACTIVE_STAKE: &str = "active_stake";
 const PENDING_INACTIVE_STAKE: &str = "pending_inactive_stake";
 const INACTIVE_STAKE: &str = "inactive_stake";
+const FUNGIBLE_STORE: &str = "fungible_store";
 const ACCOUNT_SEPARATOR: char = '-';
 
 impl SubAccountIdentifier {
@@ -292,6 +321,13 @@ impl SubAccountIdentifier {
         }
     }
 
+    pub fn new_fungible_store(store: AccountAddress) -> SubAccountIdentifier {
+        SubAccountIdentifier {
+            address: format!("{}-{}", FUNGIBLE_STORE, to_hex_lower(&store)),
+            metadata: None,
+        }
+    }
+
     pub fn is_total_stake(&self) -> bool {
         self.address.as_str() == STAKE
     }
@@ -340,6 +376,30 @@ impl SubAccountIdentifier {
             self
         ))))
     }
+
+    pub fn is_fungible_store(&self) -> bool {
+        self.address
+            .split(ACCOUNT_SEPARATOR)
+            .next()
+            .is_some_and(|prefix| prefix == FUNGIBLE_STORE)
+    }
+
+    pub fn fungible_store_address(&self) -> ApiResult<AccountAddress> {
+        let mut parts = self.address.split(ACCOUNT_SEPARATOR);
+
+        if let Some(prefix) = parts.next() {
+            if prefix == FUNGIBLE_STORE {
+                if let Some(store) = parts.next() {
+                    return str_to_account_address(store);
+                }
+            }
+        }
+
+        Err(ApiError::InvalidInput(Some(format!(
+            "Sub account isn't a fungible store address {:?}",
+            self
+        ))))
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -380,6 +440,17 @@ impl BlockIdentifier {
     }
 }
 
+/// Identifier for a coin in UTXO based blockchains
+///
+/// Aptos is account based, so this is only accepted to reject requests that use it
+///
+/// [API Spec](https://www.rosetta-api.org/docs/models/CoinIdentifier.html)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CoinIdentifier {
+    /// Identifier of the coin
+    pub identifier: String,
+}
+
 /// Identifier for this specific network deployment
 ///
 /// [API Spec](https://www.rosetta-api.org/docs/models/NetworkIdentifier.html)
@@ -472,6 +543,18 @@ pub struct TransactionIdentifier {
     pub hash: String,
 }
 
+impl TransactionIdentifier {
+    /// Parses the hash of the transaction, with or without a leading 0x
+    pub fn hash_value(&self) -> ApiResult<aptos_crypto::HashValue> {
+        aptos_crypto::HashValue::from_str(self.hash.trim_start_matches("0x")).map_err(|err| {
+            ApiError::InvalidInput(Some(format!(
+                "Invalid transaction hash {}: {}",
+                self.hash, err
+            )))
+        })
+    }
+}
+
 impl From<&TransactionInfo> for TransactionIdentifier {
     fn from(txn: &TransactionInfo) -> Self {
         TransactionIdentifier {
@@ -555,4 +638,43 @@ mod test {
 
         assert!(stake.operator_address().is_err());
     }
+
+    #[test]
+    fn test_fungible_store_account_id() {
+        let account = AccountAddress::ONE;
+        let store = AccountAddress::TWO;
+
+        let store_account = AccountIdentifier::fungible_store_account(account, store);
+        assert!(!store_account.is_base_account());
+        assert!(store_account.is_fungible_store());
+        assert!(!store_account.is_operator_stake());
+        assert!(!store_account.is_total_stake());
+        assert_eq!(Ok(account), store_account.account_address());
+        assert_eq!(Ok(store), store_account.fungible_store_address());
+        assert!(store_account.operator_address().is_err());
+
+        let base_account = AccountIdentifier::base_account(account);
+        assert!(!base_account.is_fungible_store());
+        assert!(base_account.fungible_store_address().is_err());
+
+        let operator_stake_account = AccountIdentifier::operator_stake_account(account, store);
+        assert!(!operator_stake_account.is_fungible_store());
+        assert!(operator_stake_account.fungible_store_address().is_err());
+    }
+    #[test]
+    fn test_transaction_id_hash() {
+        let hash = aptos_crypto::HashValue::sha3_256_of(b"transaction");
+        let txn_id = TransactionIdentifier::from(hash);
+        assert_eq!(Ok(hash), txn_id.hash_value());
+
+        let prefixed = TransactionIdentifier {
+            hash: format!("0x{}", txn_id.hash),
+        };
+        assert_eq!(Ok(hash), prefixed.hash_value());
+
+        let invalid = TransactionIdentifier {
+            hash: "0xnothex".to_string(),
+        };
+        assert!(invalid.hash_value().is_err());
+    }
 }
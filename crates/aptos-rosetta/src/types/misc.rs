@@ -4,13 +4,13 @@
 use crate::{
     common::native_coin,
     error::ApiError,
-    types::{AccountIdentifier, Amount},
+    types::{AccountIdentifier, Amount, ObjectCore},
     AccountAddress, ApiResult,
 };
 use aptos_rest_client::aptos_api_types::{EntryFunctionId, ViewRequest};
-use aptos_types::stake_pool::StakePool;
+use aptos_types::{account_config::fungible_store::FungibleStoreResource, stake_pool::StakePool};
 use once_cell::sync::Lazy;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{
     convert::TryFrom,
     fmt::{Display, Formatter},
@@ -464,6 +464,54 @@ fn parse_commission_not_yet_unlocked(staking_contract_amounts: Vec<serde_json::V
         .unwrap_or(0);
 }
 
+/// Retrieves a fungible store at a version, if it exists
+pub async fn get_fungible_store(
+    rest_client: &aptos_rest_client::Client,
+    store_address: AccountAddress,
+    version: u64,
+) -> ApiResult<Option<FungibleStoreResource>> {
+    get_optional_resource(
+        rest_client,
+        store_address,
+        "0x1::fungible_asset::FungibleStore",
+        version,
+    )
+    .await
+}
+
+/// Retrieves the owner of an object at a version, if it exists
+pub async fn get_object_owner(
+    rest_client: &aptos_rest_client::Client,
+    object_address: AccountAddress,
+    version: u64,
+) -> ApiResult<Option<AccountAddress>> {
+    let object_core: Option<ObjectCore> = get_optional_resource(
+        rest_client,
+        object_address,
+        "0x1::object::ObjectCore",
+        version,
+    )
+    .await?;
+    Ok(object_core.map(|inner| inner.owner))
+}
+
+async fn get_optional_resource<T: DeserializeOwned>(
+    rest_client: &aptos_rest_client::Client,
+    address: AccountAddress,
+    resource_type: &str,
+    version: u64,
+) -> ApiResult<Option<T>> {
+    match rest_client
+        .get_account_resource_at_version_bcs::<T>(address, resource_type, version)
+        .await
+        .map_err(ApiError::from)
+    {
+        Ok(response) => Ok(Some(response.into_inner())),
+        Err(ApiError::ResourceNotFound(_)) | Err(ApiError::AccountNotFound(_)) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
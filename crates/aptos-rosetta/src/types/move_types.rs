@@ -16,6 +16,9 @@ pub const STAKING_PROXY_MODULE: &str = "staking_proxy";
 pub const STAKING_CONTRACT_MODULE: &str = "staking_contract";
 pub const VESTING_MODULE: &str = "vesting";
 pub const DELEGATION_POOL_MODULE: &str = "delegation_pool";
+pub const FUNGIBLE_ASSET_MODULE: &str = "fungible_asset";
+pub const OBJECT_MODULE: &str = "object";
+pub const PRIMARY_FUNGIBLE_STORE_MODULE: &str = "primary_fungible_store";
 
 pub const ACCOUNT_RESOURCE: &str = "Account";
 pub const APTOS_COIN_RESOURCE: &str = "AptosCoin";
@@ -28,6 +31,13 @@ pub const STAKING_GROUP_UPDATE_COMMISSION_RESOURCE: &str = "StakingGroupUpdateCo
 pub const VESTING_RESOURCE: &str = "Vesting";
 pub const DELEGATION_POOL_RESOURCE: &str = "DelegationPool";
 pub const WITHDRAW_STAKE_EVENT: &str = "WithdrawStakeEvent";
+pub const FUNGIBLE_STORE_RESOURCE: &str = "FungibleStore";
+pub const OBJECT_CORE_RESOURCE: &str = "ObjectCore";
+pub const OBJECT_GROUP_RESOURCE: &str = "ObjectGroup";
+pub const FA_DEPOSIT_EVENT: &str = "Deposit";
+pub const FA_WITHDRAW_EVENT: &str = "Withdraw";
+pub const FA_DEPOSIT_EVENT_V1: &str = "DepositEvent";
+pub const FA_WITHDRAW_EVENT_V1: &str = "WithdrawEvent";
 
 pub const CREATE_ACCOUNT_FUNCTION: &str = "create_account";
 pub const TRANSFER_FUNCTION: &str = "transfer";
@@ -244,3 +254,19 @@ pub struct WithdrawUndelegedEvent {
     pub delegator_address: AccountAddress,
     pub amount_withdrawn: u64,
 }
+
+// Fungible Asset
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ObjectCore {
+    pub guid_creation_num: u64,
+    pub owner: AccountAddress,
+    pub allow_ungated_transfer: bool,
+    pub transfer_events: EventHandle,
+}
+
+/// Both `0x1::fungible_asset::Deposit` and `0x1::fungible_asset::Withdraw` have this layout
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FungibleAssetEvent {
+    pub store: AccountAddress,
+    pub amount: u64,
+}
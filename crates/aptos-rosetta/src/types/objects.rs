@@ -6,7 +6,10 @@
 //! [Spec](https://www.rosetta-api.org/docs/api_objects.html)
 
 use crate::{
-    common::{is_native_coin, native_coin, native_coin_tag},
+    common::{
+        currency_fa_address, is_supported_currency, native_coin, native_coin_fa_address,
+        native_coin_tag, primary_store_address,
+    },
     construction::{
         parse_create_stake_pool_operation, parse_delegation_pool_add_stake_operation,
         parse_delegation_pool_unlock_operation, parse_delegation_pool_withdraw_operation,
@@ -16,8 +19,9 @@ use crate::{
     },
     error::ApiResult,
     types::{
-        move_types::*, AccountIdentifier, BlockIdentifier, Error, OperationIdentifier,
-        OperationStatus, OperationStatusType, OperationType, TransactionIdentifier,
+        get_fungible_store, get_object_owner, move_types::*, AccountIdentifier, BlockIdentifier,
+        Error, OperationIdentifier, OperationStatus, OperationStatusType, OperationType,
+        TransactionIdentifier,
     },
     ApiError, RosettaContext,
 };
@@ -28,17 +32,22 @@ use aptos_logger::warn;
 use aptos_rest_client::aptos_api_types::{TransactionOnChainData, U64};
 use aptos_types::{
     account_address::AccountAddress,
-    account_config::{AccountResource, CoinStoreResource, WithdrawEvent},
+    account_config::{
+        fungible_store::FungibleStoreResource, AccountResource, CoinStoreResource, WithdrawEvent,
+    },
     contract_event::{ContractEvent, FEE_STATEMENT_EVENT_TYPE},
     event::EventKey,
     fee_statement::FeeStatement,
     stake_pool::{SetOperatorEvent, StakePool},
     state_store::state_key::{inner::StateKeyInner, StateKey},
-    transaction::{EntryFunction, TransactionPayload},
+    transaction::{EntryFunction, SignedTransaction, TransactionPayload},
     write_set::{WriteOp, WriteSet},
 };
 use itertools::Itertools;
-use move_core_types::language_storage::TypeTag;
+use move_core_types::{
+    ident_str,
+    language_storage::{ModuleId, StructTag, TypeTag},
+};
 use serde::{Deserialize, Serialize};
 use std::{
     cmp::Ordering,
@@ -128,9 +137,9 @@ pub struct Block {
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct BlockTransaction {
     /// Block associated with transaction
-    block_identifier: BlockIdentifier,
+    pub block_identifier: BlockIdentifier,
     /// Transaction associated with block
-    transaction: Transaction,
+    pub transaction: Transaction,
 }
 
 /// Currency represented as atomic units including decimals
@@ -148,7 +157,12 @@ pub struct Currency {
 
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct CurrencyMetadata {
-    pub move_type: String,
+    /// Coin type of the currency, e.g. `0x1::aptos_coin::AptosCoin`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub move_type: Option<String>,
+    /// Address of the fungible asset metadata object of the currency
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fa_address: Option<String>,
 }
 
 /// Various signing curves supported by Rosetta.  We only use [`CurveType::Edwards25519`]
@@ -894,6 +908,25 @@ impl Transaction {
             ValidatorTransaction(_) => (TransactionType::Validator, None, txn.info, txn.events),
         };
 
+        // The native coin fungible asset event of the epilogue is already covered by the gas fee
+        // and storage fee refund operations
+        let events = if let Some(user_txn) = maybe_user_txn {
+            let storage_fee_refund = get_fee_statement_from_event(&events)
+                .iter()
+                .map(|event| event.storage_fee_refund())
+                .sum();
+            remove_gas_fee_fa_event(
+                events,
+                user_txn.sender(),
+                txn_info
+                    .gas_used()
+                    .saturating_mul(user_txn.gas_unit_price()),
+                storage_fee_refund,
+            )
+        } else {
+            events
+        };
+
         // Operations must be sequential and operation index must always be in the same order
         // with no gaps
         let successful = txn_info.status().is_success();
@@ -936,6 +969,7 @@ impl Transaction {
             // Parse all failed operations from the payload
             if let Some(user_txn) = maybe_user_txn {
                 let mut ops = parse_failed_operations_from_txn_payload(
+                    server_context,
                     operation_index,
                     user_txn.sender(),
                     user_txn.payload(),
@@ -973,6 +1007,36 @@ impl Transaction {
             },
         })
     }
+
+    /// Builds a transaction that is still pending in mempool
+    ///
+    /// There are no state changes yet, so the operations are an estimate from the payload with
+    /// no status, and the version is left as 0 since it hasn't been assigned
+    pub fn from_pending_transaction(
+        server_context: &RosettaContext,
+        txn: &SignedTransaction,
+    ) -> Transaction {
+        let mut operations = parse_failed_operations_from_txn_payload(
+            server_context,
+            0,
+            txn.sender(),
+            txn.payload(),
+        );
+        for operation in operations.iter_mut() {
+            operation.status = None;
+        }
+
+        Transaction {
+            transaction_identifier: txn.committed_hash().into(),
+            operations,
+            metadata: TransactionMetadata {
+                transaction_type: TransactionType::User,
+                version: 0u64.into(),
+                failed: false,
+                vm_status: "Pending".to_string(),
+            },
+        }
+    }
 }
 
 /// Parses operations from the transaction payload
@@ -980,6 +1044,7 @@ impl Transaction {
 /// This case only occurs if the transaction failed, and that's because it's less accurate
 /// than just following the state changes
 fn parse_failed_operations_from_txn_payload(
+    server_context: &RosettaContext,
     operation_index: u64,
     sender: AccountAddress,
     payload: &TransactionPayload,
@@ -1000,6 +1065,7 @@ fn parse_failed_operations_from_txn_payload(
                     if type_tag == &native_coin_tag() {
                         operations = parse_transfer_from_txn_payload(
                             inner,
+                            inner.args(),
                             native_coin(),
                             sender,
                             operation_index,
@@ -1009,8 +1075,31 @@ fn parse_failed_operations_from_txn_payload(
             },
             (AccountAddress::ONE, APTOS_ACCOUNT_MODULE, TRANSFER_FUNCTION) => {
                 // We could add a create here as well, but we don't know if it will actually happen
-                operations =
-                    parse_transfer_from_txn_payload(inner, native_coin(), sender, operation_index)
+                operations = parse_transfer_from_txn_payload(
+                    inner,
+                    inner.args(),
+                    native_coin(),
+                    sender,
+                    operation_index,
+                )
+            },
+            (AccountAddress::ONE, PRIMARY_FUNGIBLE_STORE_MODULE, TRANSFER_FUNCTION) => {
+                // Only put the transfer in if we can understand the fungible asset
+                if let Some(Ok(fa_address)) = inner
+                    .args()
+                    .first()
+                    .map(|encoded| bcs::from_bytes::<AccountAddress>(encoded))
+                {
+                    if let Some(currency) = server_context.fa_currency(fa_address) {
+                        operations = parse_transfer_from_txn_payload(
+                            inner,
+                            &inner.args()[1..],
+                            currency,
+                            sender,
+                            operation_index,
+                        )
+                    }
+                }
             },
             (AccountAddress::ONE, ACCOUNT_MODULE, CREATE_ACCOUNT_FUNCTION) => {
                 if let Some(Ok(address)) = inner
@@ -1152,15 +1241,16 @@ fn parse_failed_operations_from_txn_payload(
     operations
 }
 
+/// Parses a transfer from the receiver and amount arguments of the payload
 fn parse_transfer_from_txn_payload(
     payload: &EntryFunction,
+    args: &[Vec<u8>],
     currency: Currency,
     sender: AccountAddress,
     operation_index: u64,
 ) -> Vec<Operation> {
     let mut operations = vec![];
 
-    let args = payload.args();
     let maybe_receiver = args
         .first()
         .map(|encoded| bcs::from_bytes::<AccountAddress>(encoded));
@@ -1222,7 +1312,26 @@ async fn parse_operations_from_write_set(
 
     let bytes = match write_op.bytes() {
         Some(bytes) => bytes,
-        None => return Ok(vec![]),
+        None => {
+            // A store can be withdrawn from right before its object is deleted
+            if (
+                struct_tag.address,
+                struct_tag.module.as_str(),
+                struct_tag.name.as_str(),
+            ) == (AccountAddress::ONE, OBJECT_MODULE, OBJECT_GROUP_RESOURCE)
+            {
+                return parse_fungible_store_changes(
+                    server_context,
+                    version,
+                    address,
+                    None,
+                    events,
+                    operation_index,
+                )
+                .await;
+            }
+            return Ok(vec![]);
+        },
     };
     let data = &bytes;
 
@@ -1260,6 +1369,17 @@ async fn parse_operations_from_write_set(
             parse_delegation_pool_resource_changes(address, data, events, operation_index, changes)
                 .await
         },
+        (AccountAddress::ONE, OBJECT_MODULE, OBJECT_GROUP_RESOURCE, 0) => {
+            parse_fungible_store_changes(
+                server_context,
+                version,
+                address,
+                Some(data),
+                events,
+                operation_index,
+            )
+            .await
+        },
         (AccountAddress::ONE, COIN_MODULE, COIN_STORE_RESOURCE, 1) => {
             if let Some(type_tag) = struct_tag.type_params.first() {
                 // TODO: This will need to be updated to support more coins
@@ -1821,6 +1941,209 @@ async fn parse_coinstore_changes(
     Ok(operations)
 }
 
+/// Parses the fungible asset withdraws and deposits of a store
+///
+/// Stores live in objects, so the owner comes from the `ObjectCore` in the same resource group.
+/// Primary stores are part of the owner's base account, secondary stores are sub accounts.  If the
+/// store was deleted by the transaction, the owner and store are read from the previous version.
+async fn parse_fungible_store_changes(
+    server_context: &RosettaContext,
+    version: u64,
+    address: AccountAddress,
+    maybe_data: Option<&[u8]>,
+    events: &[ContractEvent],
+    mut operation_index: u64,
+) -> ApiResult<Vec<Operation>> {
+    // Most objects aren't stores, skip decoding them if there are no events for them
+    let store_events: Vec<_> = events
+        .iter()
+        .filter_map(parse_fungible_asset_event)
+        .filter(|(_, event)| event.store == address)
+        .collect();
+    if store_events.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut maybe_owner = None;
+    let mut maybe_store = None;
+    if let Some(data) = maybe_data {
+        let group: BTreeMap<StructTag, Vec<u8>> = if let Ok(group) = bcs::from_bytes(data) {
+            group
+        } else {
+            warn!(
+                "Object group failed to parse for address {} at version {}",
+                address, version
+            );
+            return Ok(vec![]);
+        };
+
+        for (struct_tag, bytes) in group {
+            match (
+                struct_tag.address,
+                struct_tag.module.as_str(),
+                struct_tag.name.as_str(),
+            ) {
+                (AccountAddress::ONE, OBJECT_MODULE, OBJECT_CORE_RESOURCE) => {
+                    maybe_owner = bcs::from_bytes::<ObjectCore>(&bytes)
+                        .ok()
+                        .map(|inner| inner.owner);
+                },
+                (AccountAddress::ONE, FUNGIBLE_ASSET_MODULE, FUNGIBLE_STORE_RESOURCE) => {
+                    maybe_store = bcs::from_bytes::<FungibleStoreResource>(&bytes).ok();
+                },
+                _ => {},
+            }
+        }
+    }
+
+    // The store or its whole object was deleted, so look at it before this transaction
+    if (maybe_owner.is_none() || maybe_store.is_none()) && version > 0 {
+        let rest_client = server_context.rest_client()?;
+        if maybe_owner.is_none() {
+            maybe_owner = get_object_owner(&rest_client, address, version - 1).await?;
+        }
+        if maybe_store.is_none() {
+            maybe_store = get_fungible_store(&rest_client, address, version - 1).await?;
+        }
+    }
+
+    let (owner, store) = if let (Some(owner), Some(store)) = (maybe_owner, maybe_store) {
+        (owner, store)
+    } else {
+        warn!(
+            "Fungible store {} failed to parse at version {}",
+            address, version
+        );
+        return Ok(vec![]);
+    };
+
+    // Only fungible assets with a supported currency are shown
+    let currency = if let Some(currency) = server_context.fa_currency(store.metadata()) {
+        currency
+    } else {
+        return Ok(vec![]);
+    };
+
+    let account = if address == primary_store_address(owner, store.metadata()) {
+        AccountIdentifier::base_account(owner)
+    } else {
+        AccountIdentifier::fungible_store_account(owner, address)
+    };
+
+    let mut operations = vec![];
+    for (operation_type, event) in store_events {
+        if operation_type == OperationType::Withdraw {
+            operations.push(Operation::withdraw(
+                operation_index,
+                Some(OperationStatusType::Success),
+                account.clone(),
+                currency.clone(),
+                event.amount,
+            ));
+        } else {
+            operations.push(Operation::deposit(
+                operation_index,
+                Some(OperationStatusType::Success),
+                account.clone(),
+                currency.clone(),
+                event.amount,
+            ));
+        }
+        operation_index += 1;
+    }
+
+    Ok(operations)
+}
+
+/// Parses a fungible asset withdraw or deposit event of a store
+///
+/// The `0x1::fungible_asset::Withdraw` and `Deposit` module events contain the store, while the
+/// older `WithdrawEvent` and `DepositEvent` are emitted on event handles created by the store.
+fn parse_fungible_asset_event(
+    event: &ContractEvent,
+) -> Option<(OperationType, FungibleAssetEvent)> {
+    let (operation_type, is_module_event) = match event.type_tag() {
+        TypeTag::Struct(struct_tag) => match (
+            struct_tag.address,
+            struct_tag.module.as_str(),
+            struct_tag.name.as_str(),
+        ) {
+            (AccountAddress::ONE, FUNGIBLE_ASSET_MODULE, FA_WITHDRAW_EVENT) => {
+                (OperationType::Withdraw, true)
+            },
+            (AccountAddress::ONE, FUNGIBLE_ASSET_MODULE, FA_DEPOSIT_EVENT) => {
+                (OperationType::Deposit, true)
+            },
+            (AccountAddress::ONE, FUNGIBLE_ASSET_MODULE, FA_WITHDRAW_EVENT_V1) => {
+                (OperationType::Withdraw, false)
+            },
+            (AccountAddress::ONE, FUNGIBLE_ASSET_MODULE, FA_DEPOSIT_EVENT_V1) => {
+                (OperationType::Deposit, false)
+            },
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    let maybe_event = match event {
+        ContractEvent::V2(_) if is_module_event => {
+            bcs::from_bytes::<FungibleAssetEvent>(event.event_data()).ok()
+        },
+        ContractEvent::V1(v1_event) if !is_module_event => {
+            // Both event handle events only have the amount, the same as the coin events
+            bcs::from_bytes::<WithdrawEvent>(event.event_data())
+                .ok()
+                .map(|inner| FungibleAssetEvent {
+                    store: v1_event.key().get_creator_address(),
+                    amount: inner.amount(),
+                })
+        },
+        _ => return None,
+    };
+
+    if maybe_event.is_none() {
+        warn!(
+            "Failed to parse fungible asset event {}!  Skipping",
+            event.type_tag()
+        );
+    }
+    maybe_event.map(|event| (operation_type, event))
+}
+
+/// Removes the epilogue event of the native coin fungible asset
+///
+/// The epilogue burns the gas fee less the storage fee refund from the sender's primary store
+/// (or mints the difference if the refund is larger).  Nothing else is emitted between it and the
+/// `FeeStatement`, so it's the event right before the fee statement, or the last event of the
+/// transaction if there is no fee statement.
+fn remove_gas_fee_fa_event(
+    mut events: Vec<ContractEvent>,
+    sender: AccountAddress,
+    gas_fee: u64,
+    storage_fee_refund: u64,
+) -> Vec<ContractEvent> {
+    let expected_type = match gas_fee.cmp(&storage_fee_refund) {
+        Ordering::Greater => OperationType::Withdraw,
+        Ordering::Less => OperationType::Deposit,
+        Ordering::Equal => return events,
+    };
+
+    let epilogue_end = events
+        .iter()
+        .rposition(|event| event.type_tag() == &*FEE_STATEMENT_EVENT_TYPE)
+        .unwrap_or(events.len());
+    if let Some(index) = epilogue_end.checked_sub(1) {
+        let store = primary_store_address(sender, native_coin_fa_address());
+        if parse_fungible_asset_event(&events[index]).is_some_and(|(operation_type, event)| {
+            operation_type == expected_type && event.store == store
+        }) {
+            events.remove(index);
+        }
+    }
+
+    events
+}
+
 /// Pulls the balance change from a withdraw or deposit event
 fn get_amount_from_event(events: &[ContractEvent], event_key: &EventKey) -> Vec<u64> {
     filter_events(events, event_key, |event_key, event| {
@@ -2182,11 +2505,25 @@ impl InternalOperation {
                 create_account.sender,
             ),
             InternalOperation::Transfer(transfer) => {
-                is_native_coin(&transfer.currency)?;
-                (
-                    aptos_stdlib::aptos_account_transfer(transfer.receiver, transfer.amount.0),
-                    transfer.sender,
-                )
+                if transfer.currency == native_coin() {
+                    (
+                        aptos_stdlib::aptos_account_transfer(transfer.receiver, transfer.amount.0),
+                        transfer.sender,
+                    )
+                } else if let Some(fa_address) = currency_fa_address(&transfer.currency)? {
+                    (
+                        primary_fungible_store_transfer(
+                            fa_address,
+                            transfer.receiver,
+                            transfer.amount.0,
+                        ),
+                        transfer.sender,
+                    )
+                } else {
+                    return Err(ApiError::UnsupportedCurrency(Some(
+                        transfer.currency.symbol.clone(),
+                    )));
+                }
             },
             InternalOperation::SetOperator(set_operator) => {
                 if set_operator.old_operator.is_none() {
@@ -2276,6 +2613,33 @@ impl InternalOperation {
     }
 }
 
+/// Builds `0x1::primary_fungible_store::transfer<0x1::object::ObjectCore>`, there is no
+/// generated builder for generic entry functions
+fn primary_fungible_store_transfer(
+    metadata: AccountAddress,
+    receiver: AccountAddress,
+    amount: u64,
+) -> TransactionPayload {
+    TransactionPayload::EntryFunction(EntryFunction::new(
+        ModuleId::new(
+            AccountAddress::ONE,
+            ident_str!(PRIMARY_FUNGIBLE_STORE_MODULE).to_owned(),
+        ),
+        ident_str!(TRANSFER_FUNCTION).to_owned(),
+        vec![TypeTag::Struct(Box::new(StructTag {
+            address: AccountAddress::ONE,
+            module: ident_str!(OBJECT_MODULE).to_owned(),
+            name: ident_str!(OBJECT_CORE_RESOURCE).to_owned(),
+            type_params: vec![],
+        }))],
+        vec![
+            bcs::to_bytes(&metadata).unwrap(),
+            bcs::to_bytes(&receiver).unwrap(),
+            bcs::to_bytes(&amount).unwrap(),
+        ],
+    ))
+}
+
 /// Operation to create an account
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct CreateAccount {
@@ -2283,7 +2647,7 @@ pub struct CreateAccount {
     pub new_account: AccountAddress,
 }
 
-/// Operation to transfer coins between accounts
+/// Operation to transfer coins or fungible assets between accounts
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Transfer {
     pub sender: AccountAddress,
@@ -2364,9 +2728,8 @@ impl Transfer {
             )));
         }
 
-        // Check that the currency is supported
-        // TODO: in future use currency, since there's more than just 1
-        is_native_coin(&withdraw_amount.currency)?;
+        // Check that the currency is supported, either the native coin or a fungible asset
+        is_supported_currency(&withdraw_amount.currency)?;
 
         let withdraw_value = i128::from_str(&withdraw_amount.value)
             .map_err(|_| ApiError::InvalidTransferOperations(Some("Withdraw amount is invalid")))?;
@@ -2471,3 +2834,123 @@ pub struct UnlockDelegatedStake {
     pub pool_address: AccountAddress,
     pub amount: u64,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aptos_types::event::EventKey;
+
+    fn fa_event(name: &str, store: AccountAddress, amount: u64) -> ContractEvent {
+        ContractEvent::new_v2_with_type_tag_str(
+            &format!("0x1::fungible_asset::{}", name),
+            bcs::to_bytes(&FungibleAssetEvent { store, amount }).unwrap(),
+        )
+    }
+
+    fn fa_handle_event(name: &str, store: AccountAddress, amount: u64) -> ContractEvent {
+        ContractEvent::new_v1(
+            EventKey::new(0, store),
+            0,
+            TypeTag::from_str(&format!("0x1::fungible_asset::{}", name)).unwrap(),
+            bcs::to_bytes(&amount).unwrap(),
+        )
+    }
+
+    fn fee_statement_event(storage_fee_refund: u64) -> ContractEvent {
+        ContractEvent::new_v2(
+            FEE_STATEMENT_EVENT_TYPE.clone(),
+            bcs::to_bytes(&FeeStatement::new(10, 5, 5, 0, storage_fee_refund)).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_parse_fungible_asset_event() {
+        let store = AccountAddress::TWO;
+        for (event, expected_type) in [
+            (
+                fa_event(FA_WITHDRAW_EVENT, store, 5),
+                OperationType::Withdraw,
+            ),
+            (fa_event(FA_DEPOSIT_EVENT, store, 5), OperationType::Deposit),
+            (
+                fa_handle_event(FA_WITHDRAW_EVENT_V1, store, 5),
+                OperationType::Withdraw,
+            ),
+            (
+                fa_handle_event(FA_DEPOSIT_EVENT_V1, store, 5),
+                OperationType::Deposit,
+            ),
+        ] {
+            let (operation_type, event) = parse_fungible_asset_event(&event).unwrap();
+            assert_eq!(expected_type, operation_type);
+            assert_eq!(store, event.store);
+            assert_eq!(5, event.amount);
+        }
+
+        // Module events and event handle events can't be mixed up
+        assert!(parse_fungible_asset_event(&fa_handle_event(FA_DEPOSIT_EVENT, store, 5)).is_none());
+        assert!(
+            parse_fungible_asset_event(&ContractEvent::new_v2_with_type_tag_str(
+                "0x1::fungible_asset::DepositEvent",
+                bcs::to_bytes(&5u64).unwrap(),
+            ))
+            .is_none()
+        );
+        assert!(parse_fungible_asset_event(&fee_statement_event(0)).is_none());
+    }
+
+    #[test]
+    fn test_remove_gas_fee_fa_event() {
+        let sender = AccountAddress::ONE;
+        let store = primary_store_address(sender, native_coin_fa_address());
+        let other_store = AccountAddress::TWO;
+
+        // A transfer of the same amount as the gas fee is kept, only the epilogue burn is removed
+        let transfer = fa_event(FA_WITHDRAW_EVENT, store, 100);
+        let events = vec![
+            transfer.clone(),
+            fa_event(FA_DEPOSIT_EVENT, other_store, 100),
+            fa_event(FA_WITHDRAW_EVENT, store, 100),
+            fee_statement_event(0),
+        ];
+        let events = remove_gas_fee_fa_event(events, sender, 100, 0);
+        assert_eq!(3, events.len());
+        assert_eq!(transfer, events[0]);
+        assert_eq!(fee_statement_event(0), events[2]);
+
+        // A larger storage fee refund is minted instead
+        let events = vec![
+            fa_event(FA_DEPOSIT_EVENT, store, 50),
+            fee_statement_event(150),
+        ];
+        let events = remove_gas_fee_fa_event(events, sender, 100, 150);
+        assert_eq!(vec![fee_statement_event(150)], events);
+
+        // Without a fee statement, the epilogue is the last event
+        let events = vec![
+            fa_event(FA_WITHDRAW_EVENT, store, 30),
+            fa_event(FA_WITHDRAW_EVENT, store, 100),
+        ];
+        let events = remove_gas_fee_fa_event(events, sender, 100, 0);
+        assert_eq!(vec![fa_event(FA_WITHDRAW_EVENT, store, 30)], events);
+
+        // Events of other stores, or in the other direction, aren't the epilogue
+        let events = vec![
+            fa_event(FA_WITHDRAW_EVENT, other_store, 100),
+            fee_statement_event(0),
+        ];
+        assert_eq!(2, remove_gas_fee_fa_event(events, sender, 100, 0).len());
+        let events = vec![
+            fa_event(FA_DEPOSIT_EVENT, store, 100),
+            fee_statement_event(0),
+        ];
+        assert_eq!(2, remove_gas_fee_fa_event(events, sender, 100, 0).len());
+
+        // Nothing is burned or minted if the refund covers the gas fee exactly
+        let events = vec![
+            fa_event(FA_WITHDRAW_EVENT, store, 100),
+            fee_statement_event(100),
+        ];
+        assert_eq!(2, remove_gas_fee_fa_event(events, sender, 100, 100).len());
+    }
+}
@@ -3,9 +3,10 @@
 
 use crate::{
     types::{
-        AccountIdentifier, Allow, Amount, Block, BlockIdentifier, Currency, InternalOperation,
-        NetworkIdentifier, Operation, PartialBlockIdentifier, Peer, PublicKey, Signature,
-        SigningPayload, SyncStatus, Transaction, TransactionIdentifier, Version,
+        AccountIdentifier, Allow, Amount, Block, BlockIdentifier, BlockTransaction, CoinIdentifier,
+        Currency, InternalOperation, NetworkIdentifier, Operation, PartialBlockIdentifier, Peer,
+        PublicKey, Signature, SigningPayload, SyncStatus, Transaction, TransactionIdentifier,
+        Version,
     },
     AccountAddress, ApiError,
 };
@@ -118,6 +119,28 @@ pub struct BlockResponse {
     pub block: Block,
 }
 
+/// Request for a single transaction within a block
+///
+/// [API Spec](https://www.rosetta-api.org/docs/models/BlockTransactionRequest.html)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BlockTransactionRequest {
+    /// Network identifier describing the blockchain and the chain id
+    pub network_identifier: NetworkIdentifier,
+    /// Block containing the transaction
+    pub block_identifier: BlockIdentifier,
+    /// Hash of the transaction to lookup
+    pub transaction_identifier: TransactionIdentifier,
+}
+
+/// Response with the transaction within the block
+///
+/// [API Spec](https://www.rosetta-api.org/docs/models/BlockTransactionResponse.html)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BlockTransactionResponse {
+    /// The transaction requested
+    pub transaction: Transaction,
+}
+
 /// Request to combine signatures and an unsigned transaction for submission as a
 /// [`aptos_types::transaction::SignedTransaction`]
 ///
@@ -559,6 +582,69 @@ pub struct NetworkStatusResponse {
     pub peers: Vec<Peer>,
 }
 
+/// Request to search for a committed transaction by hash, optionally filtered by account
+///
+/// Transactions can't be searched by account alone, since the transfers received by an account
+/// aren't indexed.  The remaining filters of the spec can't be answered either, so they're only
+/// accepted to reject requests that use them.
+///
+/// [API Spec](https://www.rosetta-api.org/docs/models/SearchTransactionsRequest.html)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SearchTransactionsRequest {
+    /// Network identifier describing the blockchain and the chain id
+    pub network_identifier: NetworkIdentifier,
+    /// Hash of the transaction to search for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_identifier: Option<TransactionIdentifier>,
+    /// Account that the operations of the transaction must be for (requires the hash)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_identifier: Option<AccountIdentifier>,
+    /// Offset into the results to start from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    /// Maximum number of transactions to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+    /// How to combine the filters, only `and` is supported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operator: Option<String>,
+    /// Newest block to search from (unsupported)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_block: Option<u64>,
+    /// Coin to search for (unsupported)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coin_identifier: Option<CoinIdentifier>,
+    /// Currency of the operations to search for (unsupported)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<Currency>,
+    /// Status of the operations to search for (unsupported)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// Type of the operations to search for (unsupported)
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub operation_type: Option<String>,
+    /// Address involved in the operations to search for (unsupported)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    /// Whether the operations to search for succeeded (unsupported)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+}
+
+/// Response with the transactions matching the search, and their blocks
+///
+/// [API Spec](https://www.rosetta-api.org/docs/models/SearchTransactionsResponse.html)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SearchTransactionsResponse {
+    /// Transactions found, in order
+    pub transactions: Vec<BlockTransaction>,
+    /// Total number of transactions matching the search
+    pub total_count: u64,
+    /// Offset to use for the next page, if there are more transactions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_offset: Option<u64>,
+}
+
 /// Response with a transaction that was hashed or submitted
 ///
 /// [API Spec](https://www.rosetta-api.org/docs/models/TransactionIdentifierResponse.html)
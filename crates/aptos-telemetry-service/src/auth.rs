@@ -19,6 +19,9 @@ use aptos_types::{chain_id::ChainId, PeerId};
 use reqwest::header::AUTHORIZATION;
 use warp::{filters::BoxedFilter, reject, reply, Filter, Rejection, Reply};
 
+/// The epoch of the peers of permissive chains, whose validator set isn't tracked.
+pub(crate) const PERMISSIVE_AUTH_EPOCH: u64 = 0;
+
 pub fn auth(context: Context) -> BoxedFilter<(impl Reply,)> {
     warp::path!("auth")
         .and(warp::post())
@@ -63,56 +66,27 @@ pub async fn handle_auth(context: Context, body: AuthRequest) -> Result<impl Rep
             )))
         })?;
 
-    let cache = if body.role_type == RoleType::Validator {
-        context.peers().validators()
+    let (epoch, peer_role) = if context.is_permissive_auth_chain(&body.chain_id) {
+        // The handshake proved that the peer holds the private key of remote_public_key,
+        // so the peer ID must be derived from it (otherwise, any key could claim any peer).
+        let derived_remote_peer_id =
+            aptos_types::account_address::from_identity_public_key(remote_public_key);
+        if derived_remote_peer_id != body.peer_id {
+            return Err(reject::custom(ServiceError::forbidden(
+                ServiceErrorCode::AuthError(AuthError::PublicKeyMismatch, body.chain_id),
+            )));
+        }
+
+        // Peers of permissive chains are trusted to have the role they claim.
+        let peer_role = match body.role_type {
+            RoleType::Validator => PeerRole::Validator,
+            RoleType::FullNode => PeerRole::ValidatorFullNode,
+        };
+        (PERMISSIVE_AUTH_EPOCH, peer_role)
     } else {
-        context.peers().validator_fullnodes()
+        authenticate_with_validator_set(&context, &body, &remote_public_key)?
     };
 
-    let (epoch, peer_role) = match cache.read().get(&body.chain_id) {
-        Some((epoch, peer_set)) => {
-            match peer_set.get(&body.peer_id) {
-                Some(peer) => {
-                    let remote_public_key = &remote_public_key;
-                    if !peer.keys.contains(remote_public_key) {
-                        warn!("peer found in peer set but public_key is not found. request body: {}, role_type: {}, peer_id: {}, received public_key: {}", body.chain_id, body.role_type, body.peer_id, remote_public_key);
-                        return Err(reject::custom(ServiceError::forbidden(
-                            ServiceErrorCode::AuthError(
-                                AuthError::PeerPublicKeyNotFound,
-                                body.chain_id,
-                            ),
-                        )));
-                    }
-                    Ok((*epoch, peer.role))
-                },
-                None => {
-                    // if not, verify that their peerid is constructed correctly from their public key
-                    let derived_remote_peer_id =
-                        aptos_types::account_address::from_identity_public_key(remote_public_key);
-                    if derived_remote_peer_id != body.peer_id {
-                        return Err(reject::custom(ServiceError::forbidden(
-                            ServiceErrorCode::AuthError(
-                                AuthError::PublicKeyMismatch,
-                                body.chain_id,
-                            ),
-                        )));
-                    } else {
-                        Ok((*epoch, PeerRole::Unknown))
-                    }
-                },
-            }
-        },
-        None => {
-            warn!(
-                "Validator set unavailable for Chain ID {}. Rejecting request.",
-                body.chain_id
-            );
-            Err(reject::custom(ServiceError::unauthorized(
-                ServiceErrorCode::AuthError(AuthError::ValidatorSetUnavailable, body.chain_id),
-            )))
-        },
-    }?;
-
     let node_type = match peer_role {
         PeerRole::Validator => NodeType::Validator,
         PeerRole::ValidatorFullNode => NodeType::ValidatorFullNode,
@@ -174,6 +148,63 @@ pub async fn handle_auth(context: Context, body: AuthRequest) -> Result<impl Rep
     }))
 }
 
+/// Looks up the peer in the validator set (or validator fullnode set) of its chain,
+/// returning the current epoch and the role of the peer.
+fn authenticate_with_validator_set(
+    context: &Context,
+    body: &AuthRequest,
+    remote_public_key: &x25519::PublicKey,
+) -> Result<(u64, PeerRole), Rejection> {
+    let cache = if body.role_type == RoleType::Validator {
+        context.peers().validators()
+    } else {
+        context.peers().validator_fullnodes()
+    };
+
+    match cache.read().get(&body.chain_id) {
+        Some((epoch, peer_set)) => {
+            match peer_set.get(&body.peer_id) {
+                Some(peer) => {
+                    if !peer.keys.contains(remote_public_key) {
+                        warn!("peer found in peer set but public_key is not found. request body: {}, role_type: {}, peer_id: {}, received public_key: {}", body.chain_id, body.role_type, body.peer_id, remote_public_key);
+                        return Err(reject::custom(ServiceError::forbidden(
+                            ServiceErrorCode::AuthError(
+                                AuthError::PeerPublicKeyNotFound,
+                                body.chain_id,
+                            ),
+                        )));
+                    }
+                    Ok((*epoch, peer.role))
+                },
+                None => {
+                    // if not, verify that their peerid is constructed correctly from their public key
+                    let derived_remote_peer_id =
+                        aptos_types::account_address::from_identity_public_key(*remote_public_key);
+                    if derived_remote_peer_id != body.peer_id {
+                        return Err(reject::custom(ServiceError::forbidden(
+                            ServiceErrorCode::AuthError(
+                                AuthError::PublicKeyMismatch,
+                                body.chain_id,
+                            ),
+                        )));
+                    } else {
+                        Ok((*epoch, PeerRole::Unknown))
+                    }
+                },
+            }
+        },
+        None => {
+            warn!(
+                "Validator set unavailable for Chain ID {}. Rejecting request.",
+                body.chain_id
+            );
+            Err(reject::custom(ServiceError::unauthorized(
+                ServiceErrorCode::AuthError(AuthError::ValidatorSetUnavailable, body.chain_id),
+            )))
+        },
+    }
+}
+
 pub fn with_auth(
     context: Context,
     roles: Vec<NodeType>,
@@ -0,0 +1,124 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+use reqwest::{Client as ReqwestClient, Url};
+use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
+use reqwest_retry::{policies::ExponentialBackoff, RetryTransientMiddleware};
+
+pub const USER_HEADER_NAME: &str = "X-ClickHouse-User";
+pub const PASSWORD_HEADER_NAME: &str = "X-ClickHouse-Key";
+
+/// Client inserting rows into a table through the HTTP interface of ClickHouse,
+/// or of any database compatible with it.
+#[derive(Clone)]
+pub struct InsertClient {
+    inner: ClientWithMiddleware,
+    base_url: Url,
+    table: String,
+    user: Option<String>,
+    password: Option<String>,
+}
+
+impl InsertClient {
+    pub fn new(
+        base_url: Url,
+        table: String,
+        user: Option<String>,
+        password: Option<String>,
+    ) -> Self {
+        let retry_policy = ExponentialBackoff::builder().build_with_max_retries(3);
+        let inner = ClientBuilder::new(ReqwestClient::new())
+            .with(RetryTransientMiddleware::new_with_policy(retry_policy))
+            .build();
+        Self {
+            inner,
+            base_url,
+            table,
+            user,
+            password,
+        }
+    }
+
+    /// Inserts rows given as newline delimited JSON objects, one per row.
+    pub async fn insert_json_rows(&self, rows: Vec<u8>) -> Result<(), anyhow::Error> {
+        let mut request = self
+            .inner
+            .post(self.base_url.clone())
+            .query(&[(
+                "query",
+                format!("INSERT INTO {} FORMAT JSONEachRow", self.table),
+            )])
+            .body(rows);
+        if let Some(user) = &self.user {
+            request = request.header(USER_HEADER_NAME, user);
+        }
+        if let Some(password) = &self.password {
+            request = request.header(PASSWORD_HEADER_NAME, password);
+        }
+
+        let res = request
+            .send()
+            .await
+            .map_err(|e| anyhow!("failed to insert rows: {}", e))?;
+        if !res.status().is_success() {
+            return Err(anyhow!(
+                "insert into {} failed: {}",
+                self.table,
+                res.error_for_status().err().unwrap()
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InsertClient, PASSWORD_HEADER_NAME, USER_HEADER_NAME};
+    use httpmock::MockServer;
+    use reqwest::Url;
+
+    fn insert_client(server: &MockServer) -> InsertClient {
+        InsertClient::new(
+            Url::parse(&server.base_url()).unwrap(),
+            "logs".into(),
+            Some("user".into()),
+            Some("password".into()),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_insert_json_rows() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method("POST")
+                .path("/")
+                .query_param("query", "INSERT INTO logs FORMAT JSONEachRow")
+                .header(USER_HEADER_NAME, "user")
+                .header(PASSWORD_HEADER_NAME, "password")
+                .body("{\"a\":1}\n{\"a\":2}\n");
+            then.status(200);
+        });
+
+        insert_client(&server)
+            .insert_json_rows(b"{\"a\":1}\n{\"a\":2}\n".to_vec())
+            .await
+            .unwrap();
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_insert_json_rows_failure() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method("POST").path("/");
+            then.status(400).body("Cannot parse input");
+        });
+
+        let result = insert_client(&server)
+            .insert_json_rows(b"not json\n".to_vec())
+            .await;
+        mock.assert();
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+use std::{fs::OpenOptions, path::Path, sync::Arc};
+use tokio::{fs::File, io::AsyncWriteExt, sync::Mutex};
+
+/// Writer appending rows to a local file, as newline delimited JSON objects.
+#[derive(Clone)]
+pub struct JsonLinesWriter {
+    file: Arc<Mutex<File>>,
+}
+
+impl JsonLinesWriter {
+    pub fn open(path: &Path) -> Result<Self, anyhow::Error> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| anyhow!("unable to open file {}: {}", path.display(), e))?;
+        Ok(Self {
+            file: Arc::new(Mutex::new(File::from_std(file))),
+        })
+    }
+
+    /// Appends rows given as newline delimited JSON objects. Rows written in
+    /// one call are never interleaved with rows written in another.
+    pub async fn append_json_rows(&self, rows: Vec<u8>) -> Result<(), anyhow::Error> {
+        let mut file = self.file.lock().await;
+        file.write_all(&rows)
+            .await
+            .map_err(|e| anyhow!("failed to append rows: {}", e))?;
+        file.flush()
+            .await
+            .map_err(|e| anyhow!("failed to flush rows: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JsonLinesWriter;
+
+    #[tokio::test]
+    async fn test_append_json_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rows.jsonl");
+
+        let writer = JsonLinesWriter::open(&path).unwrap();
+        writer
+            .append_json_rows(b"{\"a\":1}\n".to_vec())
+            .await
+            .unwrap();
+        writer
+            .append_json_rows(b"{\"a\":2}\n".to_vec())
+            .await
+            .unwrap();
+
+        // Reopening the file appends to it rather than truncating it
+        let writer = JsonLinesWriter::open(&path).unwrap();
+        writer
+            .append_json_rows(b"{\"a\":3}\n".to_vec())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "{\"a\":1}\n{\"a\":2}\n{\"a\":3}\n"
+        );
+    }
+}
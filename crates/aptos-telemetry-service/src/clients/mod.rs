@@ -1,7 +1,44 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod clickhouse;
 pub mod humio;
+pub mod local_file;
+
+pub mod sink {
+    use super::{clickhouse, local_file};
+    use serde::Serialize;
+
+    /// A backend storing telemetry as rows of JSON, for deployments
+    /// that don't send it to GCP or Humio.
+    #[derive(Clone)]
+    pub enum RowSink {
+        LocalFile(local_file::JsonLinesWriter),
+        ClickHouse(clickhouse::InsertClient),
+    }
+
+    impl RowSink {
+        pub fn name(&self) -> &'static str {
+            match self {
+                RowSink::LocalFile(_) => "local_file",
+                RowSink::ClickHouse(_) => "clickhouse",
+            }
+        }
+
+        pub async fn insert_rows<T: Serialize>(&self, rows: &[T]) -> Result<(), anyhow::Error> {
+            let mut body = Vec::new();
+            for row in rows {
+                serde_json::to_writer(&mut body, row)?;
+                body.push(b'\n');
+            }
+
+            match self {
+                RowSink::LocalFile(writer) => writer.append_json_rows(body).await,
+                RowSink::ClickHouse(client) => client.insert_json_rows(body).await,
+            }
+        }
+    }
+}
 
 pub mod victoria_metrics_api {
 
@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    clients::{big_query::TableWriteClient, humio, victoria_metrics_api::Client as MetricsClient},
+    clients::{
+        big_query::TableWriteClient, humio, sink::RowSink,
+        victoria_metrics_api::Client as MetricsClient,
+    },
     types::common::EpochedPeerStore,
     LogIngestConfig, MetricsEndpointsConfig,
 };
@@ -106,6 +109,8 @@ pub struct ClientTuple {
     bigquery_client: Option<TableWriteClient>,
     victoria_metrics_clients: Option<GroupedMetricsClients>,
     log_ingest_clients: Option<LogIngestClients>,
+    custom_event_sink: Option<RowSink>,
+    log_sink: Option<RowSink>,
 }
 
 impl ClientTuple {
@@ -118,8 +123,20 @@ impl ClientTuple {
             bigquery_client,
             victoria_metrics_clients,
             log_ingest_clients,
+            custom_event_sink: None,
+            log_sink: None,
         }
     }
+
+    pub(crate) fn with_sinks(
+        mut self,
+        custom_event_sink: Option<RowSink>,
+        log_sink: Option<RowSink>,
+    ) -> ClientTuple {
+        self.custom_event_sink = custom_event_sink;
+        self.log_sink = log_sink;
+        self
+    }
 }
 
 #[derive(Clone)]
@@ -165,6 +182,7 @@ pub struct Context {
     jwt_service: JsonWebTokenService,
     log_env_map: HashMap<ChainId, HashMap<PeerId, String>>,
     peer_identities: HashMap<ChainId, HashMap<PeerId, String>>,
+    permissive_auth_chains: HashSet<ChainId>,
 }
 
 impl Context {
@@ -175,6 +193,7 @@ impl Context {
         jwt_service: JsonWebTokenService,
         log_env_map: HashMap<ChainId, HashMap<PeerId, String>>,
         peer_identities: HashMap<ChainId, HashMap<PeerId, String>>,
+        permissive_auth_chains: HashSet<ChainId>,
     ) -> Self {
         Self {
             noise_config: Arc::new(noise::NoiseConfig::new(private_key)),
@@ -183,6 +202,7 @@ impl Context {
             jwt_service,
             log_env_map,
             peer_identities,
+            permissive_auth_chains,
         }
     }
 
@@ -211,20 +231,36 @@ impl Context {
         self.clients.victoria_metrics_clients.as_mut().unwrap()
     }
 
-    pub fn log_ingest_clients(&self) -> &LogIngestClients {
-        self.clients.log_ingest_clients.as_ref().unwrap()
+    pub fn log_ingest_clients(&self) -> Option<&LogIngestClients> {
+        self.clients.log_ingest_clients.as_ref()
     }
 
     pub(crate) fn bigquery_client(&self) -> Option<&TableWriteClient> {
         self.clients.bigquery_client.as_ref()
     }
 
+    pub(crate) fn custom_event_sink(&self) -> Option<&RowSink> {
+        self.clients.custom_event_sink.as_ref()
+    }
+
+    pub(crate) fn log_sink(&self) -> Option<&RowSink> {
+        self.clients.log_sink.as_ref()
+    }
+
     pub(crate) fn peer_identities(&self) -> &HashMap<ChainId, HashMap<PeerId, String>> {
         &self.peer_identities
     }
 
     pub fn chain_set(&self) -> HashSet<ChainId> {
-        self.peers.validators.read().keys().cloned().collect()
+        let mut chain_set: HashSet<ChainId> =
+            self.peers.validators.read().keys().cloned().collect();
+        chain_set.extend(self.permissive_auth_chains.iter().cloned());
+        chain_set
+    }
+
+    /// Returns true iff peers of the chain are authenticated without the validator set
+    pub fn is_permissive_auth_chain(&self, chain_id: &ChainId) -> bool {
+        self.permissive_auth_chains.contains(chain_id)
     }
 
     pub fn log_env_map(&self) -> &HashMap<ChainId, HashMap<PeerId, String>> {
@@ -235,4 +271,19 @@ impl Context {
     pub fn log_env_map_mut(&mut self) -> &mut HashMap<ChainId, HashMap<PeerId, String>> {
         &mut self.log_env_map
     }
+
+    #[cfg(test)]
+    pub fn permissive_auth_chains_mut(&mut self) -> &mut HashSet<ChainId> {
+        &mut self.permissive_auth_chains
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_sinks(
+        &mut self,
+        custom_event_sink: Option<RowSink>,
+        log_sink: Option<RowSink>,
+    ) {
+        self.clients.custom_event_sink = custom_event_sink;
+        self.clients.log_sink = log_sink;
+    }
 }
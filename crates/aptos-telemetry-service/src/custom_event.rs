@@ -6,11 +6,11 @@ use crate::{
     context::Context,
     debug, error,
     errors::{CustomEventIngestError, ServiceError},
-    metrics::BIG_QUERY_BACKEND_REQUEST_DURATION,
+    metrics::{BIG_QUERY_BACKEND_REQUEST_DURATION, SINK_REQUEST_DURATION},
     types::{
         auth::Claims,
         common::{EventIdentity, NodeType},
-        telemetry::{BigQueryRow, TelemetryDump},
+        telemetry::{BigQueryRow, CustomEventRow, TelemetryDump},
     },
 };
 use anyhow::anyhow;
@@ -90,6 +90,35 @@ pub(crate) async fn handle_custom_event(
             )
         })?);
 
+    if let Some(sink) = context.custom_event_sink() {
+        let row = CustomEventRow {
+            event_identity: EventIdentity::from(claims),
+            event_name: telemetry_event.name.clone(),
+            event_timestamp: duration.as_secs(),
+            event_params: telemetry_event.params.clone(),
+        };
+
+        let start_timer = Instant::now();
+
+        let res = sink.insert_rows(&[&row]).await;
+
+        SINK_REQUEST_DURATION
+            .with_label_values(&[
+                sink.name(),
+                "custom_events",
+                if res.is_ok() { "success" } else { "error" },
+            ])
+            .observe(start_timer.elapsed().as_secs_f64());
+        res.map_err(|e| {
+            error!("unable to store row in {} sink: {}", sink.name(), e);
+            ServiceError::internal(CustomEventIngestError::from(e).into())
+        })?;
+
+        debug!("row stored successfully: {:?}", &row);
+
+        return Ok(reply::with_status(reply::reply(), StatusCode::CREATED));
+    }
+
     let row = BigQueryRow {
         event_identity: EventIdentity::from(claims),
         event_name: telemetry_event.name.clone(),
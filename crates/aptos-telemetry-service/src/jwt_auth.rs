@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    auth::PERMISSIVE_AUTH_EPOCH,
     context::{Context, JsonWebTokenService},
     error,
     errors::{JwtAuthError, ServiceError},
@@ -54,13 +55,17 @@ pub async fn authorize_jwt(
     })?;
     let claims = decoded.claims;
 
-    let current_epoch = match context.peers().validators().read().get(&claims.chain_id) {
-        Some(info) => info.0,
-        None => {
-            return Err(reject::custom(ServiceError::unauthorized(
-                JwtAuthError::ExpiredAuthToken.into(),
-            )));
-        },
+    let current_epoch = if context.is_permissive_auth_chain(&claims.chain_id) {
+        PERMISSIVE_AUTH_EPOCH
+    } else {
+        match context.peers().validators().read().get(&claims.chain_id) {
+            Some(info) => info.0,
+            None => {
+                return Err(reject::custom(ServiceError::unauthorized(
+                    JwtAuthError::ExpiredAuthToken.into(),
+                )));
+            },
+        }
     };
 
     if !allow_roles.contains(&claims.node_type) {
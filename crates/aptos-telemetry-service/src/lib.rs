@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    clients::{big_query, humio, victoria_metrics_api::Client as MetricsClient},
+    clients::{
+        big_query, clickhouse, humio, local_file, sink::RowSink,
+        victoria_metrics_api::Client as MetricsClient,
+    },
     context::{ClientTuple, Context, JsonWebTokenService, LogIngestClients, PeerStoreTuple},
     index::routes,
     metrics::PrometheusExporter,
@@ -73,18 +76,27 @@ impl AptosTelemetryServiceArgs {
         )
         .expect("unable to form x25519::Private key from environment variable SERVER_PRIVATE_KEY");
 
-        let bigquery_client = BigQueryClient::from_service_account_key_file(
-            env::var("GOOGLE_APPLICATION_CREDENTIALS")
-                .expect("environment variable GOOGLE_APPLICATION_CREDENTIALS must be set")
-                .as_str(),
-        )
-        .await;
-        let bigquery_client = big_query::TableWriteClient::new(
-            bigquery_client,
-            config.custom_event_config.project_id.clone(),
-            config.custom_event_config.dataset_id.clone(),
-            config.custom_event_config.table_id.clone(),
-        );
+        let bigquery_client = match &config.custom_event_config {
+            Some(custom_event_config) => {
+                let bigquery_client = BigQueryClient::from_service_account_key_file(
+                    env::var("GOOGLE_APPLICATION_CREDENTIALS")
+                        .expect("environment variable GOOGLE_APPLICATION_CREDENTIALS must be set")
+                        .as_str(),
+                )
+                .await;
+                Some(big_query::TableWriteClient::new(
+                    bigquery_client,
+                    custom_event_config.project_id.clone(),
+                    custom_event_config.dataset_id.clone(),
+                    custom_event_config.table_id.clone(),
+                ))
+            },
+            None => None,
+        };
+        let custom_event_sink = config
+            .custom_event_sink
+            .as_ref()
+            .map(SinkConfig::make_client);
 
         let metrics_clients: GroupedMetricsClients = config.metrics_endpoints_config.clone().into();
 
@@ -92,10 +104,11 @@ impl AptosTelemetryServiceArgs {
             .telemetry_service_metrics_clients
             .values()
             .next()
-            .cloned()
-            .unwrap();
+            .cloned();
 
-        let log_ingest_clients: LogIngestClients = config.humio_ingest_config.clone().into();
+        let log_ingest_clients: Option<LogIngestClients> =
+            config.humio_ingest_config.clone().map(Into::into);
+        let log_sink = config.log_sink.as_ref().map(SinkConfig::make_client);
 
         let jwt_service = JsonWebTokenService::from_base64_secret(
             env::var("JWT_SIGNING_KEY")
@@ -114,14 +127,12 @@ impl AptosTelemetryServiceArgs {
                 validator_fullnodes.clone(),
                 public_fullnodes,
             ),
-            ClientTuple::new(
-                Some(bigquery_client),
-                Some(metrics_clients),
-                Some(log_ingest_clients),
-            ),
+            ClientTuple::new(bigquery_client, Some(metrics_clients), log_ingest_clients)
+                .with_sinks(custom_event_sink, log_sink),
             jwt_service,
             config.log_env_map.clone(),
             config.peer_identities.clone(),
+            config.permissive_auth_chains.clone(),
         );
 
         PeerSetCacheUpdater::new(
@@ -132,7 +143,10 @@ impl AptosTelemetryServiceArgs {
         )
         .run();
 
-        PrometheusExporter::new(telemetry_metrics_client).run();
+        // The service's own metrics are only exported if an endpoint is configured for them
+        if let Some(telemetry_metrics_client) = telemetry_metrics_client {
+            PrometheusExporter::new(telemetry_metrics_client).run();
+        }
 
         Self::serve(&config, routes(context)).await;
     }
@@ -281,6 +295,54 @@ impl LogIngestConfig {
     }
 }
 
+/// A row sink configuration, to store telemetry on premises rather than in GCP or Humio
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SinkConfig {
+    /// Appends rows to a local file, as newline delimited JSON
+    LocalFile { path: PathBuf },
+    /// Inserts rows into a table through a ClickHouse-compatible HTTP interface
+    #[serde(rename = "clickhouse")]
+    ClickHouse {
+        endpoint_url: Url,
+        table: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        user: Option<String>,
+        /// Environment variable that holds the password
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        password_env_var: Option<String>,
+    },
+}
+
+impl SinkConfig {
+    fn make_client(&self) -> RowSink {
+        match self {
+            SinkConfig::LocalFile { path } => RowSink::LocalFile(
+                local_file::JsonLinesWriter::open(path)
+                    .unwrap_or_else(|e| panic!("unable to open sink file: {}", e)),
+            ),
+            SinkConfig::ClickHouse {
+                endpoint_url,
+                table,
+                user,
+                password_env_var,
+            } => {
+                let password = password_env_var.as_ref().map(|password_env_var| {
+                    env::var(password_env_var).unwrap_or_else(|_| {
+                        panic!("environment variable {} must be set.", password_env_var)
+                    })
+                });
+                RowSink::ClickHouse(clickhouse::InsertClient::new(
+                    endpoint_url.clone(),
+                    table.clone(),
+                    user.clone(),
+                    password,
+                ))
+            },
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct TelemetryServiceConfig {
@@ -290,12 +352,27 @@ pub struct TelemetryServiceConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tls_key_path: Option<String>,
 
+    #[serde(default)]
     pub trusted_full_node_addresses: HashMap<ChainCommonName, String>,
     pub update_interval: u64,
     pub pfn_allowlist: HashMap<ChainId, HashMap<PeerId, x25519::PublicKey>>,
+    /// Chains whose peers are authenticated by the noise handshake alone, without being
+    /// looked up in the on-chain validator set, e.g., private chains.
+    #[serde(default)]
+    pub permissive_auth_chains: HashSet<ChainId>,
 
-    pub custom_event_config: CustomEventConfig,
-    pub humio_ingest_config: LogIngestConfig,
+    /// BigQuery table to store custom events in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_event_config: Option<CustomEventConfig>,
+    /// Sink to store custom events in, instead of BigQuery
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_event_sink: Option<SinkConfig>,
+    /// Humio endpoints to ingest logs into
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub humio_ingest_config: Option<LogIngestConfig>,
+    /// Sink to store logs in, instead of Humio
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_sink: Option<SinkConfig>,
 
     pub log_env_map: HashMap<ChainId, HashMap<PeerId, String>>,
     pub peer_identities: HashMap<ChainId, HashMap<PeerId, String>>,
@@ -321,13 +398,21 @@ impl TelemetryServiceConfig {
             )
         })?;
 
-        serde_yaml::from_str(&contents).map_err(|e| {
+        let config: Self = serde_yaml::from_str(&contents).map_err(|e| {
             anyhow::anyhow!(
                 "Unable to read yaml {}. Error: {}",
                 path.to_str().unwrap(),
                 e
             )
-        })
+        })?;
+
+        if config.custom_event_config.is_some() && config.custom_event_sink.is_some() {
+            anyhow::bail!("Only one of custom_event_config and custom_event_sink can be set");
+        }
+        if config.humio_ingest_config.is_some() && config.log_sink.is_some() {
+            anyhow::bail!("Only one of humio_ingest_config and log_sink can be set");
+        }
+        Ok(config)
     }
 }
 
@@ -3,16 +3,24 @@
 
 use crate::{
     auth::with_auth,
-    clients::humio::{
-        CHAIN_ID_TAG_NAME, EPOCH_FIELD_NAME, PEER_ID_FIELD_NAME, PEER_ROLE_TAG_NAME,
-        RUN_UUID_TAG_NAME,
+    clients::{
+        humio::{
+            CHAIN_ID_TAG_NAME, EPOCH_FIELD_NAME, PEER_ID_FIELD_NAME, PEER_ROLE_TAG_NAME,
+            RUN_UUID_TAG_NAME,
+        },
+        sink::RowSink,
     },
     constants::MAX_CONTENT_LENGTH,
     context::Context,
     debug, error,
     errors::{LogIngestError, ServiceError},
-    metrics::LOG_INGEST_BACKEND_REQUEST_DURATION,
-    types::{auth::Claims, common::NodeType, humio::UnstructuredLog},
+    metrics::{LOG_INGEST_BACKEND_REQUEST_DURATION, SINK_REQUEST_DURATION},
+    types::{
+        auth::Claims,
+        common::{EventIdentity, NodeType},
+        humio::UnstructuredLog,
+        telemetry::LogRow,
+    },
 };
 use flate2::bufread::GzDecoder;
 use reqwest::{header::CONTENT_ENCODING, StatusCode};
@@ -46,7 +54,10 @@ pub async fn handle_log_ingest(
 ) -> anyhow::Result<impl Reply, Rejection> {
     debug!("handling log ingest");
 
-    if let Some(blacklist) = &context.log_ingest_clients().blacklist {
+    if let Some(blacklist) = context
+        .log_ingest_clients()
+        .and_then(|clients| clients.blacklist.as_ref())
+    {
         if blacklist.contains(&claims.peer_id) {
             return Err(reject::custom(ServiceError::forbidden(
                 LogIngestError::Forbidden(claims.peer_id).into(),
@@ -54,13 +65,6 @@ pub async fn handle_log_ingest(
         }
     }
 
-    let client = match claims.node_type {
-        NodeType::Unknown | NodeType::UnknownValidator | NodeType::UnknownFullNode => {
-            &context.log_ingest_clients().unknown_logs_ingest_client
-        },
-        _ => &context.log_ingest_clients().known_logs_ingest_client,
-    };
-
     let log_messages: Vec<String> = if let Some(encoding) = encoding {
         if encoding.eq_ignore_ascii_case("gzip") {
             let decoder = GzDecoder::new(body.reader());
@@ -80,6 +84,24 @@ pub async fn handle_log_ingest(
         })?
     };
 
+    if let Some(sink) = context.log_sink() {
+        ingest_into_sink(sink, claims, log_messages).await?;
+        return Ok(reply::with_status(reply::reply(), StatusCode::CREATED));
+    }
+
+    let log_ingest_clients = context.log_ingest_clients().ok_or_else(|| {
+        error!("no log ingest backend is configured");
+        reject::custom(ServiceError::internal(
+            LogIngestError::IngestionError.into(),
+        ))
+    })?;
+    let client = match claims.node_type {
+        NodeType::Unknown | NodeType::UnknownValidator | NodeType::UnknownFullNode => {
+            &log_ingest_clients.unknown_logs_ingest_client
+        },
+        _ => &log_ingest_clients.known_logs_ingest_client,
+    };
+
     let mut fields = HashMap::new();
     fields.insert(PEER_ID_FIELD_NAME.into(), claims.peer_id.to_string());
     fields.insert(EPOCH_FIELD_NAME.into(), claims.epoch.to_string());
@@ -136,3 +158,151 @@ pub async fn handle_log_ingest(
 
     Ok(reply::with_status(reply::reply(), StatusCode::CREATED))
 }
+
+/// Stores each log message as a row of the sink
+async fn ingest_into_sink(
+    sink: &RowSink,
+    claims: Claims,
+    log_messages: Vec<String>,
+) -> Result<(), Rejection> {
+    let event_identity = EventIdentity::from(claims);
+    let rows: Vec<LogRow> = log_messages
+        .into_iter()
+        .map(|message| LogRow {
+            event_identity: event_identity.clone(),
+            message,
+        })
+        .collect();
+
+    let start_timer = Instant::now();
+
+    let res = sink.insert_rows(&rows).await;
+
+    SINK_REQUEST_DURATION
+        .with_label_values(&[
+            sink.name(),
+            "logs",
+            if res.is_ok() { "success" } else { "error" },
+        ])
+        .observe(start_timer.elapsed().as_secs_f64());
+    res.map_err(|e| {
+        error!("unable to store logs in {} sink: {}", sink.name(), e);
+        reject::custom(ServiceError::internal(
+            LogIngestError::IngestionError.into(),
+        ))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::handle_log_ingest;
+    use crate::{
+        clients::{clickhouse::InsertClient, local_file::JsonLinesWriter, sink::RowSink},
+        tests::test_context::new_test_context,
+        types::auth::Claims,
+    };
+    use httpmock::MockServer;
+    use reqwest::Url;
+    use serde_json::Value;
+    use warp::hyper::body::Bytes;
+
+    fn log_body(messages: &[&str]) -> Bytes {
+        Bytes::from(serde_json::to_vec(messages).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_log_ingest_into_local_file_sink() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs.jsonl");
+        let mut test_context = new_test_context().await;
+        test_context.inner.set_sinks(
+            None,
+            Some(RowSink::LocalFile(JsonLinesWriter::open(&path).unwrap())),
+        );
+
+        let claims = Claims::test();
+        let (peer_id, epoch) = (claims.peer_id, claims.epoch);
+        let result = handle_log_ingest(
+            test_context.inner,
+            claims,
+            None,
+            log_body(&["first log", "second log"]),
+        )
+        .await;
+        assert!(result.is_ok());
+
+        // Verify that each log message is stored as a row with the identity of the peer
+        let rows: Vec<Value> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(rows.len(), 2);
+        for (row, message) in rows.iter().zip(["first log", "second log"]) {
+            assert_eq!(row["message"], message);
+            assert_eq!(row["peer_id"], serde_json::to_value(peer_id).unwrap());
+            assert_eq!(row["epoch"], epoch);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_log_ingest_into_clickhouse_sink() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method("POST")
+                .path("/")
+                .query_param("query", "INSERT INTO logs FORMAT JSONEachRow")
+                .body_contains("\"message\":\"a log\"");
+            then.status(200);
+        });
+        let mut test_context = new_test_context().await;
+        test_context.inner.set_sinks(
+            None,
+            Some(RowSink::ClickHouse(InsertClient::new(
+                Url::parse(&server.base_url()).unwrap(),
+                "logs".into(),
+                None,
+                None,
+            ))),
+        );
+
+        let result = handle_log_ingest(
+            test_context.inner,
+            Claims::test(),
+            None,
+            log_body(&["a log"]),
+        )
+        .await;
+        mock.assert();
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_log_ingest_sink_failure() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method("POST").path("/");
+            then.status(400);
+        });
+        let mut test_context = new_test_context().await;
+        test_context.inner.set_sinks(
+            None,
+            Some(RowSink::ClickHouse(InsertClient::new(
+                Url::parse(&server.base_url()).unwrap(),
+                "logs".into(),
+                None,
+                None,
+            ))),
+        );
+
+        let result = handle_log_ingest(
+            test_context.inner,
+            Claims::test(),
+            None,
+            log_body(&["a log"]),
+        )
+        .await;
+        mock.assert();
+        assert!(result.is_err());
+    }
+}
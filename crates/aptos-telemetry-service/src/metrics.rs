@@ -57,6 +57,15 @@ pub(crate) static BIG_QUERY_BACKEND_REQUEST_DURATION: Lazy<HistogramVec> = Lazy:
     .unwrap()
 });
 
+pub(crate) static SINK_REQUEST_DURATION: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "telemetry_web_service_sink_request_duration",
+        "Number of row sink requests by sink, kind of telemetry and result",
+        &["sink", "kind", "result"]
+    )
+    .unwrap()
+});
+
 pub(crate) static METRICS_EXPORT_DURATION: Lazy<HistogramVec> = Lazy::new(|| {
     register_histogram_vec!(
         "telemetry_web_service_metrics_export_duration",
@@ -244,6 +244,74 @@ async fn test_auth_validatorfullnode() {
     },)
 }
 
+#[tokio::test]
+async fn test_auth_permissive_chain() {
+    let mut context = new_test_context().await;
+    let server_public_key = context.inner.noise_config().public_key();
+
+    let (mut rng, initiator, chain_id, peer_id, _, run_uuid) = init(PeerRole::Validator);
+
+    // The peer is not in any validator set, but the chain accepts any peer
+    context.inner.permissive_auth_chains_mut().insert(chain_id);
+
+    let (initiator_state, client_noise_msg) =
+        init_handshake(&mut rng, chain_id, peer_id, server_public_key, &initiator);
+
+    let req = json!({
+        "chain_id": chain_id,
+        "peer_id": peer_id,
+        "role_type": RoleType::Validator,
+        "server_public_key": server_public_key,
+        "handshake_msg": &client_noise_msg,
+        "run_uuid": run_uuid,
+    });
+    let resp = context.post("/api/v1/auth", req).await;
+
+    let decoded = finish_handshake(
+        context.inner.jwt_service(),
+        &initiator,
+        initiator_state,
+        resp,
+    );
+
+    assert_eq!(decoded.claims, Claims {
+        chain_id,
+        peer_id,
+        node_type: NodeType::Validator,
+        epoch: 0,
+        exp: decoded.claims.exp,
+        iat: decoded.claims.iat,
+        run_uuid,
+    },)
+}
+
+#[tokio::test]
+async fn test_auth_permissive_chain_wrong_peer_id() {
+    let mut context = new_test_context().await;
+    let server_public_key = context.inner.noise_config().public_key();
+
+    let (mut rng, initiator, chain_id, _, _, run_uuid) = init(PeerRole::Validator);
+    context.inner.permissive_auth_chains_mut().insert(chain_id);
+
+    // Claim a peer ID that is not derived from the public key of the initiator
+    let peer_id = PeerId::random();
+    let (_, client_noise_msg) =
+        init_handshake(&mut rng, chain_id, peer_id, server_public_key, &initiator);
+
+    let req = json!({
+        "chain_id": chain_id,
+        "peer_id": peer_id,
+        "role_type": RoleType::Validator,
+        "server_public_key": server_public_key,
+        "handshake_msg": &client_noise_msg,
+        "run_uuid": run_uuid,
+    });
+    context
+        .expect_status_code(403)
+        .post("/api/v1/auth", req)
+        .await;
+}
+
 #[tokio::test]
 #[should_panic]
 async fn test_auth_wrong_key() {
@@ -3,8 +3,11 @@
 
 use super::test_context::new_test_context;
 use crate::{
+    clients::{clickhouse::InsertClient, local_file::JsonLinesWriter, sink::RowSink},
+    custom_event::handle_custom_event,
     jwt_auth::create_jwt_token,
     types::{
+        auth::Claims,
         common::NodeType,
         telemetry::{TelemetryDump, TelemetryEvent},
     },
@@ -12,10 +15,27 @@ use crate::{
 use aptos_config::config::PeerSet;
 use aptos_types::{chain_id::ChainId, PeerId};
 use chrono::Utc;
-use serde_json::json;
+use httpmock::MockServer;
+use reqwest::Url;
+use serde_json::{json, Value};
 use std::collections::BTreeMap;
 use uuid::Uuid;
 
+/// Returns a custom event from the peer of the claims
+fn custom_event_body(claims: &Claims) -> TelemetryDump {
+    TelemetryDump {
+        client_id: "test-client".into(),
+        user_id: claims.peer_id.to_string(),
+        timestamp_micros: "1700000000000000".into(),
+        events: vec![TelemetryEvent {
+            name: "sample-event".into(),
+            params: [("key".to_string(), "value".to_string())]
+                .into_iter()
+                .collect(),
+        }],
+    }
+}
+
 #[tokio::test]
 async fn test_custom_event() {
     let test_context = new_test_context().await;
@@ -57,3 +77,61 @@ async fn test_custom_event() {
         .post("/api/v1/ingest/custom-event", json!(body))
         .await;
 }
+
+#[tokio::test]
+async fn test_custom_event_into_local_file_sink() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("custom_events.jsonl");
+    let mut test_context = new_test_context().await;
+    test_context.inner.set_sinks(
+        Some(RowSink::LocalFile(JsonLinesWriter::open(&path).unwrap())),
+        None,
+    );
+
+    let claims = Claims::test();
+    let peer_id = claims.peer_id;
+    let body = custom_event_body(&claims);
+    let result = handle_custom_event(test_context.inner, claims, body).await;
+    assert!(result.is_ok());
+
+    // Verify that the event is stored as a row with the identity of the peer
+    let rows: Vec<Value> = std::fs::read_to_string(&path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["event_name"], "sample-event");
+    assert_eq!(rows[0]["event_timestamp"], 1_700_000_000u64);
+    assert_eq!(rows[0]["event_params"], json!({"key": "value"}));
+    assert_eq!(rows[0]["peer_id"], serde_json::to_value(peer_id).unwrap());
+}
+
+#[tokio::test]
+async fn test_custom_event_into_clickhouse_sink() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method("POST")
+            .path("/")
+            .query_param("query", "INSERT INTO custom_events FORMAT JSONEachRow")
+            .body_contains("\"event_name\":\"sample-event\"");
+        then.status(200);
+    });
+    let mut test_context = new_test_context().await;
+    test_context.inner.set_sinks(
+        Some(RowSink::ClickHouse(InsertClient::new(
+            Url::parse(&server.base_url()).unwrap(),
+            "custom_events".into(),
+            None,
+            None,
+        ))),
+        None,
+    );
+
+    let claims = Claims::test();
+    let peer_id = claims.peer_id;
+    let body = custom_event_body(&claims);
+    let result = handle_custom_event(test_context.inner, claims, body).await;
+    mock.assert();
+    assert!(result.is_ok());
+}
@@ -10,7 +10,7 @@ use aptos_rest_client::aptos_api_types::mime_types;
 use rand::SeedableRng;
 use reqwest::header::AUTHORIZATION;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use warp::{
     http::{header::CONTENT_TYPE, Response},
     hyper::body::Bytes,
@@ -26,16 +26,19 @@ pub async fn new_test_context() -> TestContext {
         tls_key_path: None,
         trusted_full_node_addresses: HashMap::new(),
         update_interval: 60,
-        custom_event_config: CustomEventConfig {
+        permissive_auth_chains: HashSet::new(),
+        custom_event_config: Some(CustomEventConfig {
             project_id: String::from("1"),
             dataset_id: String::from("2"),
             table_id: String::from("3"),
-        },
+        }),
+        custom_event_sink: None,
         pfn_allowlist: HashMap::new(),
         log_env_map: HashMap::new(),
         peer_identities: HashMap::new(),
         metrics_endpoints_config: MetricsEndpointsConfig::default_for_test(),
-        humio_ingest_config: LogIngestConfig::default_for_test(),
+        humio_ingest_config: Some(LogIngestConfig::default_for_test()),
+        log_sink: None,
     };
 
     let peers = PeerStoreTuple::default();
@@ -50,6 +53,7 @@ pub async fn new_test_context() -> TestContext {
             jwt_service,
             HashMap::new(),
             HashMap::new(),
+            HashSet::new(),
         ),
     )
 }
@@ -29,3 +29,21 @@ pub(crate) struct BigQueryRow {
     pub event_timestamp: u64,
     pub event_params: Vec<serde_json::Value>,
 }
+
+/// A custom event, as stored in a row sink
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct CustomEventRow {
+    #[serde(flatten)]
+    pub event_identity: EventIdentity,
+    pub event_name: String,
+    pub event_timestamp: u64,
+    pub event_params: BTreeMap<String, String>,
+}
+
+/// A log line of a node, as stored in a row sink
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct LogRow {
+    #[serde(flatten)]
+    pub event_identity: EventIdentity,
+    pub message: String,
+}
@@ -1056,6 +1056,13 @@ pub struct MovePackageDir {
     #[clap(long)]
     pub(crate) skip_fetch_latest_git_deps: bool,
 
+    /// Require dependencies to match the versions pinned in Move.lock
+    ///
+    /// Fails if a dependency is not pinned, or if its sources no longer match
+    /// the pinned digest. Run `aptos move update-lock` to update the pins.
+    #[clap(long)]
+    pub locked: bool,
+
     /// Specify the version of the bytecode the compiler is going to emit.
     #[clap(long)]
     pub bytecode_version: Option<u32>,
@@ -1089,6 +1096,7 @@ impl MovePackageDir {
             output_dir: None,
             named_addresses: Default::default(),
             skip_fetch_latest_git_deps: true,
+            locked: false,
             bytecode_version: None,
             compiler_version: None,
             language_version: None,
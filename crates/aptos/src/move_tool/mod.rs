@@ -102,6 +102,7 @@ pub enum MoveTool {
     #[clap(subcommand, hide = true)]
     Show(show::ShowTool),
     Test(TestPackage),
+    UpdateLock(UpdateLockPackage),
     VerifyPackage(VerifyPackage),
     View(ViewFunction),
     Replay(Replay),
@@ -134,6 +135,7 @@ impl MoveTool {
             MoveTool::RunScript(tool) => tool.execute_serialized().await,
             MoveTool::Show(tool) => tool.execute_serialized().await,
             MoveTool::Test(tool) => tool.execute_serialized().await,
+            MoveTool::UpdateLock(tool) => tool.execute_serialized().await,
             MoveTool::VerifyPackage(tool) => tool.execute_serialized().await,
             MoveTool::View(tool) => tool.execute_serialized().await,
             MoveTool::Replay(tool) => tool.execute_serialized().await,
@@ -330,6 +332,7 @@ impl CliCommand<Vec<String>> for CompilePackage {
                 .build_options(
                     self.move_options.dev,
                     self.move_options.skip_fetch_latest_git_deps,
+                    self.move_options.locked,
                     self.move_options.named_addresses(),
                     self.move_options.bytecode_version,
                     self.move_options.compiler_version,
@@ -393,6 +396,7 @@ impl CompileScript {
             ..IncludedArtifacts::None.build_options(
                 self.move_options.dev,
                 self.move_options.skip_fetch_latest_git_deps,
+                self.move_options.locked,
                 self.move_options.named_addresses(),
                 self.move_options.bytecode_version,
                 self.move_options.compiler_version,
@@ -479,6 +483,7 @@ impl CliCommand<&'static str> for TestPackage {
             full_model_generation: self.move_options.check_test_code,
             install_dir: self.move_options.output_dir.clone(),
             skip_fetch_latest_git_deps: self.move_options.skip_fetch_latest_git_deps,
+            locked: self.move_options.locked,
             compiler_config: CompilerConfig {
                 known_attributes: known_attributes.clone(),
                 skip_attribute_checks: self.move_options.skip_attribute_checks,
@@ -613,6 +618,7 @@ impl CliCommand<&'static str> for DocumentPackage {
             named_addresses: move_options.named_addresses(),
             docgen_options: Some(docgen_options),
             skip_fetch_latest_git_deps: move_options.skip_fetch_latest_git_deps,
+            locked: move_options.locked,
             bytecode_version: move_options.bytecode_version,
             compiler_version: move_options.compiler_version,
             language_version: move_options.language_version,
@@ -681,6 +687,7 @@ impl TryInto<PackagePublicationData> for &PublishPackage {
             .build_options(
                 self.move_options.dev,
                 self.move_options.skip_fetch_latest_git_deps,
+                self.move_options.locked,
                 self.move_options.named_addresses(),
                 self.move_options.bytecode_version,
                 self.move_options.compiler_version,
@@ -752,6 +759,7 @@ impl IncludedArtifacts {
         self,
         dev: bool,
         skip_fetch_latest_git_deps: bool,
+        locked: bool,
         named_addresses: BTreeMap<String, AccountAddress>,
         bytecode_version: Option<u32>,
         compiler_version: Option<CompilerVersion>,
@@ -770,6 +778,7 @@ impl IncludedArtifacts {
                 with_error_map: true,
                 named_addresses,
                 skip_fetch_latest_git_deps,
+                locked,
                 bytecode_version,
                 compiler_version,
                 language_version,
@@ -786,6 +795,7 @@ impl IncludedArtifacts {
                 with_error_map: true,
                 named_addresses,
                 skip_fetch_latest_git_deps,
+                locked,
                 bytecode_version,
                 compiler_version,
                 language_version,
@@ -802,6 +812,7 @@ impl IncludedArtifacts {
                 with_error_map: true,
                 named_addresses,
                 skip_fetch_latest_git_deps,
+                locked,
                 bytecode_version,
                 compiler_version,
                 language_version,
@@ -922,6 +933,7 @@ impl CliCommand<TransactionSummary> for CreateObjectAndPublishPackage {
             .build_options(
                 self.move_options.dev,
                 self.move_options.skip_fetch_latest_git_deps,
+                self.move_options.locked,
                 self.move_options.named_addresses(),
                 self.move_options.bytecode_version,
                 self.move_options.compiler_version,
@@ -999,6 +1011,7 @@ impl CliCommand<TransactionSummary> for UpgradeObjectPackage {
             .build_options(
                 self.move_options.dev,
                 self.move_options.skip_fetch_latest_git_deps,
+                self.move_options.locked,
                 self.move_options.named_addresses(),
                 self.move_options.bytecode_version,
                 self.move_options.compiler_version,
@@ -1124,6 +1137,7 @@ impl CliCommand<TransactionSummary> for CreateResourceAccountAndPublishPackage {
         let options = included_artifacts_args.included_artifacts.build_options(
             move_options.dev,
             move_options.skip_fetch_latest_git_deps,
+            move_options.locked,
             move_options.named_addresses(),
             move_options.bytecode_version,
             move_options.compiler_version,
@@ -1276,6 +1290,7 @@ impl CliCommand<&'static str> for VerifyPackage {
             ..self.included_artifacts.build_options(
                 self.move_options.dev,
                 self.move_options.skip_fetch_latest_git_deps,
+                self.move_options.locked,
                 self.move_options.named_addresses(),
                 self.move_options.bytecode_version,
                 self.move_options.compiler_version,
@@ -1422,6 +1437,39 @@ impl CliCommand<&'static str> for CleanPackage {
     }
 }
 
+/// Pins the dependencies of a package in its Move.lock
+///
+/// Git dependencies are pinned to the commit they currently resolve to, and
+/// on-chain dependencies to their current upgrade number. Builds with
+/// `--locked` then fail if the dependencies no longer match the pins.
+#[derive(Parser)]
+pub struct UpdateLockPackage {
+    #[clap(flatten)]
+    pub(crate) move_options: MovePackageDir,
+}
+
+#[async_trait]
+impl CliCommand<&'static str> for UpdateLockPackage {
+    fn command_name(&self) -> &'static str {
+        "UpdateLockPackage"
+    }
+
+    async fn execute(self) -> CliTypedResult<&'static str> {
+        let path = self.move_options.get_package_path()?;
+        let config = BuildConfig {
+            additional_named_addresses: self.move_options.named_addresses(),
+            ..Default::default()
+        };
+        task::spawn_blocking(move || {
+            config.update_lock_file_for_package(&path, &mut std::io::stderr())
+        })
+        .await
+        .map_err(|err| CliError::UnexpectedError(err.to_string()))?
+        .map_err(|err| CliError::MoveCompilationError(format!("{:#}", err)))?;
+        Ok("succeeded")
+    }
+}
+
 /// Run a Move function
 #[derive(Parser)]
 pub struct RunFunction {
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{common::types::load_account_arg, move_tool::CachedPackageRegistry};
+use anyhow::Context;
 use aptos_framework::UPGRADE_POLICY_CUSTOM_FIELD;
 use futures::executor::block_on;
 use move_package::{
@@ -10,6 +11,11 @@ use move_package::{
 };
 use move_symbol_pool::Symbol;
 use reqwest::Url;
+use std::{fs, path::Path};
+
+/// File in the download location of a package which records the upgrade number of the package
+/// that was downloaded, so that it is known when the package is already present.
+const UPGRADE_NUMBER_FILE: &str = "UpgradeNumber";
 
 pub fn register_package_hooks() {
     move_package::package_hooks::register_package_hooks(Box::new(AptosPackageHooks {}))
@@ -33,23 +39,84 @@ impl PackageHooks for AptosPackageHooks {
     ) -> anyhow::Result<()> {
         block_on(maybe_download_package(info))
     }
+
+    fn custom_dependency_version(&self, info: &CustomDepInfo) -> anyhow::Result<Option<u64>> {
+        read_upgrade_number(&info.download_to)
+    }
 }
 
 async fn maybe_download_package(info: &CustomDepInfo) -> anyhow::Result<()> {
-    if !info
+    let is_cached = info
         .download_to
         .join(CompiledPackageLayout::BuildInfo.path())
-        .exists()
-    {
-        let registry = CachedPackageRegistry::create(
-            Url::parse(info.node_url.as_str())?,
-            load_account_arg(info.package_address.as_str())?,
-            false,
-        )
-        .await?;
-        let package = registry.get_package(info.package_name).await?;
-        package.save_package_to_disk(info.download_to.as_path())
-    } else {
-        Ok(())
+        .exists();
+    if is_cached && read_upgrade_number(&info.download_to)?.is_some() {
+        return Ok(());
+    }
+
+    let registry = CachedPackageRegistry::create(
+        Url::parse(info.node_url.as_str())?,
+        load_account_arg(info.package_address.as_str())?,
+        false,
+    )
+    .await?;
+    let package = registry.get_package(info.package_name).await?;
+    if !is_cached {
+        package.save_package_to_disk(info.download_to.as_path())?;
+    }
+    // A package cached without its upgrade number is assumed to be the one published on chain
+    write_upgrade_number(&info.download_to, package.upgrade_number())
+}
+
+/// Returns the upgrade number recorded for the package downloaded to `package_path`, if any.
+fn read_upgrade_number(package_path: &Path) -> anyhow::Result<Option<u64>> {
+    let path = package_path.join(UPGRADE_NUMBER_FILE);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let upgrade_number = fs::read_to_string(&path)?
+        .trim()
+        .parse()
+        .with_context(|| format!("Invalid upgrade number in {}", path.display()))?;
+    Ok(Some(upgrade_number))
+}
+
+fn write_upgrade_number(package_path: &Path, upgrade_number: u64) -> anyhow::Result<()> {
+    fs::create_dir_all(package_path)?;
+    fs::write(
+        package_path.join(UPGRADE_NUMBER_FILE),
+        upgrade_number.to_string(),
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{write_upgrade_number, AptosPackageHooks};
+    use move_package::{
+        compilation::package_layout::CompiledPackageLayout, package_hooks::PackageHooks,
+        source_package::parsed_manifest::CustomDepInfo,
+    };
+    use move_symbol_pool::Symbol;
+    use std::fs;
+
+    #[test]
+    fn test_cached_on_chain_dependency_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let info = CustomDepInfo {
+            node_url: Symbol::from("http://127.0.0.1:1"),
+            package_address: Symbol::from("0x1234"),
+            package_name: Symbol::from("OnChainDep"),
+            download_to: dir.path().to_path_buf(),
+        };
+        fs::write(dir.path().join(CompiledPackageLayout::BuildInfo.path()), "").unwrap();
+        write_upgrade_number(dir.path(), 3).unwrap();
+
+        // The cached package is used without querying the node, and keeps its version
+        let hooks = AptosPackageHooks {};
+        hooks
+            .resolve_custom_dependency(info.package_name, &info)
+            .unwrap();
+        assert_eq!(hooks.custom_dependency_version(&info).unwrap(), Some(3));
     }
 }
@@ -1091,6 +1091,7 @@ impl CliTestFramework {
             output_dir: None,
             named_addresses: Self::named_addresses(account_strs),
             skip_fetch_latest_git_deps: true,
+            locked: false,
             bytecode_version: None,
             compiler_version: None,
             language_version: None,
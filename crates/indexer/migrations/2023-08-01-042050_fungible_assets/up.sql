@@ -0,0 +1,67 @@
+-- Your SQL goes here
+-- Fungible asset metadata, keyed on the address of the metadata object
+CREATE TABLE IF NOT EXISTS fungible_asset_metadata (
+  asset_type VARCHAR(66) UNIQUE PRIMARY KEY NOT NULL,
+  creator_address VARCHAR(66) NOT NULL,
+  name VARCHAR(32) NOT NULL,
+  symbol VARCHAR(10) NOT NULL,
+  decimals INT NOT NULL,
+  icon_uri VARCHAR(512) NOT NULL,
+  project_uri VARCHAR(512) NOT NULL,
+  supply NUMERIC,
+  maximum_supply NUMERIC,
+  is_concurrent_supply BOOLEAN NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  last_transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS fam_creator_index ON fungible_asset_metadata (creator_address);
+CREATE INDEX IF NOT EXISTS fam_insat_index ON fungible_asset_metadata (inserted_at);
+-- Deposits, withdrawals and freezes of fungible stores
+CREATE TABLE IF NOT EXISTS fungible_asset_activities (
+  transaction_version BIGINT NOT NULL,
+  event_index BIGINT NOT NULL,
+  owner_address VARCHAR(66) NOT NULL,
+  storage_id VARCHAR(66) NOT NULL,
+  asset_type VARCHAR(66) NOT NULL,
+  is_frozen BOOLEAN,
+  amount NUMERIC,
+  type VARCHAR(50) NOT NULL,
+  entry_function_id_str VARCHAR(100),
+  block_height BIGINT NOT NULL,
+  transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (transaction_version, event_index)
+);
+CREATE INDEX IF NOT EXISTS faa_owner_type_index ON fungible_asset_activities (owner_address, type);
+CREATE INDEX IF NOT EXISTS faa_si_index ON fungible_asset_activities (storage_id);
+CREATE INDEX IF NOT EXISTS faa_at_index ON fungible_asset_activities (asset_type);
+CREATE INDEX IF NOT EXISTS faa_insat_index ON fungible_asset_activities (inserted_at);
+-- Balance of fungible stores, storage_id is the address of the store object
+CREATE TABLE IF NOT EXISTS fungible_asset_balances (
+  transaction_version BIGINT NOT NULL,
+  write_set_change_index BIGINT NOT NULL,
+  storage_id VARCHAR(66) NOT NULL,
+  owner_address VARCHAR(66) NOT NULL,
+  asset_type VARCHAR(66) NOT NULL,
+  is_frozen BOOLEAN NOT NULL,
+  amount NUMERIC NOT NULL,
+  transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (transaction_version, write_set_change_index)
+);
+CREATE INDEX IF NOT EXISTS fab_owner_at_index ON fungible_asset_balances (owner_address, asset_type);
+CREATE INDEX IF NOT EXISTS fab_insat_index ON fungible_asset_balances (inserted_at);
+CREATE TABLE IF NOT EXISTS current_fungible_asset_balances (
+  storage_id VARCHAR(66) UNIQUE PRIMARY KEY NOT NULL,
+  owner_address VARCHAR(66) NOT NULL,
+  asset_type VARCHAR(66) NOT NULL,
+  is_frozen BOOLEAN NOT NULL,
+  amount NUMERIC NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  last_transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS cfab_owner_at_index ON current_fungible_asset_balances (owner_address, asset_type);
+CREATE INDEX IF NOT EXISTS cfab_at_amount_index ON current_fungible_asset_balances (asset_type, amount);
+CREATE INDEX IF NOT EXISTS cfab_insat_index ON current_fungible_asset_balances (inserted_at);
@@ -7,18 +7,37 @@
 use crate::{
     models::{
         move_resources::MoveResource,
-        token_models::{token_utils::URI_LENGTH, v2_token_utils::ResourceReference},
+        token_models::{
+            token_utils::URI_LENGTH,
+            v2_token_utils::{AggregatorV2, ObjectWithMetadata, ResourceReference},
+        },
+        v2_objects::CurrentObjectPK,
     },
-    util::truncate_str,
+    util::{standardize_address, truncate_str},
 };
 use anyhow::{Context, Result};
-use aptos_api_types::{deserialize_from_string, WriteResource};
+use aptos_api_types::{deserialize_from_string, Event as APIEvent, WriteResource};
 use bigdecimal::BigDecimal;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 const FUNGIBLE_ASSET_LENGTH: usize = 32;
 const FUNGIBLE_ASSET_SYMBOL: usize = 10;
 
+/// Tracks all fungible asset related data in a hashmap for quick access (keyed on address of the object core)
+pub type FungibleAssetAggregatedDataMapping = HashMap<CurrentObjectPK, FungibleAssetAggregatedData>;
+
+/// All the fungible asset resources that can live in an object (metadata and supply live in the
+/// metadata object, stores are separate objects)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FungibleAssetAggregatedData {
+    pub object: ObjectWithMetadata,
+    pub fungible_asset_metadata: Option<FungibleAssetMetadata>,
+    pub fungible_asset_supply: Option<FungibleAssetSupply>,
+    pub concurrent_fungible_asset_supply: Option<ConcurrentFungibleAssetSupply>,
+    pub fungible_asset_store: Option<FungibleAssetStore>,
+}
+
 /* Section on fungible assets resources */
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct FungibleAssetMetadata {
@@ -68,7 +87,7 @@ impl FungibleAssetMetadata {
     }
 
     pub fn get_symbol(&self) -> String {
-        truncate_str(&self.name, FUNGIBLE_ASSET_SYMBOL)
+        truncate_str(&self.symbol, FUNGIBLE_ASSET_SYMBOL)
     }
 
     pub fn get_icon_uri(&self) -> String {
@@ -177,6 +196,56 @@ impl FungibleAssetSupply {
     }
 }
 
+/// Supply of a fungible asset that can be minted and burned in parallel. The maximum is the
+/// max value of the aggregator, u128::MAX if unlimited.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ConcurrentFungibleAssetSupply {
+    pub current: AggregatorV2,
+}
+
+impl ConcurrentFungibleAssetSupply {
+    pub fn from_write_resource(
+        write_resource: &WriteResource,
+        txn_version: i64,
+    ) -> anyhow::Result<Option<Self>> {
+        let type_str = format!(
+            "{}::{}::{}",
+            write_resource.data.typ.address,
+            write_resource.data.typ.module,
+            write_resource.data.typ.name
+        );
+        if !V2FungibleAssetResource::is_resource_supported(type_str.as_str()) {
+            return Ok(None);
+        }
+        let resource = MoveResource::from_write_resource(
+            write_resource,
+            0, // Placeholder, this isn't used anyway
+            txn_version,
+            0, // Placeholder, this isn't used anyway
+        );
+
+        if let V2FungibleAssetResource::ConcurrentFungibleAssetSupply(inner) =
+            V2FungibleAssetResource::from_resource(
+                &type_str,
+                resource.data.as_ref().unwrap(),
+                txn_version,
+            )?
+        {
+            Ok(Some(inner))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn get_maximum(&self) -> Option<BigDecimal> {
+        if self.current.max_value == BigDecimal::from(u128::MAX) {
+            None
+        } else {
+            Some(self.current.max_value.clone())
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DepositEvent {
     #[serde(deserialize_with = "deserialize_from_string")]
@@ -189,8 +258,36 @@ pub struct WithdrawEvent {
     pub amount: BigDecimal,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FrozenEvent {
+    pub frozen: bool,
+}
+
+/// Module events (i.e. `0x1::fungible_asset::Deposit`) don't have a guid pointing at the store so
+/// the store address is part of the event
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DepositEventV2 {
+    store: String,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub amount: BigDecimal,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WithdrawEventV2 {
+    store: String,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub amount: BigDecimal,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FrozenEventV2 {
+    store: String,
+    pub frozen: bool,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum V2FungibleAssetResource {
+    ConcurrentFungibleAssetSupply(ConcurrentFungibleAssetSupply),
     FungibleAssetMetadata(FungibleAssetMetadata),
     FungibleAssetStore(FungibleAssetStore),
     FungibleAssetSupply(FungibleAssetSupply),
@@ -201,6 +298,7 @@ impl V2FungibleAssetResource {
         matches!(
             data_type,
             "0x1::fungible_asset::Supply"
+                | "0x1::fungible_asset::ConcurrentSupply"
                 | "0x1::fungible_asset::Metadata"
                 | "0x1::fungible_asset::FungibleStore"
         )
@@ -214,6 +312,8 @@ impl V2FungibleAssetResource {
         match data_type {
             "0x1::fungible_asset::Supply" => serde_json::from_value(data.clone())
                 .map(|inner| Some(Self::FungibleAssetSupply(inner))),
+            "0x1::fungible_asset::ConcurrentSupply" => serde_json::from_value(data.clone())
+                .map(|inner| Some(Self::ConcurrentFungibleAssetSupply(inner))),
             "0x1::fungible_asset::Metadata" => serde_json::from_value(data.clone())
                 .map(|inner| Some(Self::FungibleAssetMetadata(inner))),
             "0x1::fungible_asset::FungibleStore" => serde_json::from_value(data.clone())
@@ -234,6 +334,10 @@ impl V2FungibleAssetResource {
 pub enum FungibleAssetEvent {
     DepositEvent(DepositEvent),
     WithdrawEvent(WithdrawEvent),
+    FrozenEvent(FrozenEvent),
+    DepositEventV2(DepositEventV2),
+    WithdrawEventV2(WithdrawEventV2),
+    FrozenEventV2(FrozenEventV2),
 }
 
 impl FungibleAssetEvent {
@@ -249,6 +353,18 @@ impl FungibleAssetEvent {
             "0x1::fungible_asset::WithdrawEvent" => {
                 serde_json::from_value(data.clone()).map(|inner| Some(Self::WithdrawEvent(inner)))
             },
+            "0x1::fungible_asset::FrozenEvent" => {
+                serde_json::from_value(data.clone()).map(|inner| Some(Self::FrozenEvent(inner)))
+            },
+            "0x1::fungible_asset::Deposit" => {
+                serde_json::from_value(data.clone()).map(|inner| Some(Self::DepositEventV2(inner)))
+            },
+            "0x1::fungible_asset::Withdraw" => {
+                serde_json::from_value(data.clone()).map(|inner| Some(Self::WithdrawEventV2(inner)))
+            },
+            "0x1::fungible_asset::Frozen" => {
+                serde_json::from_value(data.clone()).map(|inner| Some(Self::FrozenEventV2(inner)))
+            },
             _ => Ok(None),
         }
         .context(format!(
@@ -256,6 +372,19 @@ impl FungibleAssetEvent {
             txn_version, data_type, data
         ))
     }
+
+    /// Address of the fungible store the event is about. Event handle based events live in the
+    /// store object so we get it from the guid.
+    pub fn get_store_address(&self, event: &APIEvent) -> String {
+        match self {
+            Self::DepositEvent(_) | Self::WithdrawEvent(_) | Self::FrozenEvent(_) => {
+                standardize_address(&event.guid.account_address.to_string())
+            },
+            Self::DepositEventV2(inner) => standardize_address(&inner.store),
+            Self::WithdrawEventV2(inner) => standardize_address(&inner.store),
+            Self::FrozenEventV2(inner) => standardize_address(&inner.store),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -291,4 +420,37 @@ mod tests {
             panic!("Wrong type")
         }
     }
+
+    #[test]
+    fn test_concurrent_fungible_asset_supply() {
+        let test = r#"{"current": {"value": "100", "max_value": "340282366920938463463374607431768211455"}}"#;
+        let test: serde_json::Value = serde_json::from_str(test).unwrap();
+        let supply = serde_json::from_value(test)
+            .map(V2FungibleAssetResource::ConcurrentFungibleAssetSupply)
+            .unwrap();
+        if let V2FungibleAssetResource::ConcurrentFungibleAssetSupply(supply) = supply {
+            assert_eq!(supply.current.value, BigDecimal::from(100));
+            assert_eq!(supply.get_maximum(), None);
+        } else {
+            panic!("Wrong type")
+        }
+    }
+
+    #[test]
+    fn test_fungible_asset_module_event() {
+        let test = r#"{"store": "0xabc", "amount": "42"}"#;
+        let test: serde_json::Value = serde_json::from_str(test).unwrap();
+        let event = FungibleAssetEvent::from_event("0x1::fungible_asset::Withdraw", &test, 1)
+            .unwrap()
+            .unwrap();
+        if let FungibleAssetEvent::WithdrawEventV2(inner) = &event {
+            assert_eq!(inner.amount, BigDecimal::from(42));
+            assert_eq!(
+                standardize_address(&inner.store),
+                standardize_address("0xabc")
+            );
+        } else {
+            panic!("Wrong type")
+        }
+    }
 }
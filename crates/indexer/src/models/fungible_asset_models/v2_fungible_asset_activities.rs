@@ -0,0 +1,88 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+#![allow(clippy::unused_unit)]
+
+use crate::{
+    models::coin_models::v2_fungible_asset_utils::{
+        FungibleAssetAggregatedDataMapping, FungibleAssetEvent,
+    },
+    schema::fungible_asset_activities,
+};
+use aptos_api_types::Event as APIEvent;
+use bigdecimal::BigDecimal;
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(transaction_version, event_index))]
+#[diesel(table_name = fungible_asset_activities)]
+pub struct FungibleAssetActivity {
+    pub transaction_version: i64,
+    pub event_index: i64,
+    pub owner_address: String,
+    pub storage_id: String,
+    pub asset_type: String,
+    pub is_frozen: Option<bool>,
+    pub amount: Option<BigDecimal>,
+    pub type_: String,
+    pub entry_function_id_str: Option<String>,
+    pub block_height: i64,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+}
+
+impl FungibleAssetActivity {
+    /// We'll go from 0x1::fungible_asset deposit/withdraw/frozen events. We're guaranteed to find
+    /// the 0x1::fungible_asset::FungibleStore in the same transaction since its balance or frozen
+    /// flag changed, and the store has a pointer to the fungible asset metadata. The owner comes
+    /// from the ObjectCore of the store, which lives in the same resource group.
+    pub fn get_v2_from_parsed_event(
+        event: &APIEvent,
+        txn_version: i64,
+        block_height: i64,
+        txn_timestamp: chrono::NaiveDateTime,
+        event_index: i64,
+        entry_function_id_str: &Option<String>,
+        fungible_asset_metadata: &FungibleAssetAggregatedDataMapping,
+    ) -> anyhow::Result<Option<Self>> {
+        let event_type = event.typ.to_string();
+        if let Some(fa_event) =
+            &FungibleAssetEvent::from_event(event_type.as_str(), &event.data, txn_version)?
+        {
+            let storage_id = fa_event.get_store_address(event);
+            let (object, fungible_asset) = match fungible_asset_metadata.get(&storage_id) {
+                Some(metadata) => match metadata.fungible_asset_store.as_ref() {
+                    Some(fungible_asset) => (&metadata.object, fungible_asset),
+                    None => return Ok(None),
+                },
+                None => return Ok(None),
+            };
+
+            let (is_frozen, amount) = match fa_event {
+                FungibleAssetEvent::WithdrawEvent(inner) => (None, Some(inner.amount.clone())),
+                FungibleAssetEvent::DepositEvent(inner) => (None, Some(inner.amount.clone())),
+                FungibleAssetEvent::FrozenEvent(inner) => (Some(inner.frozen), None),
+                FungibleAssetEvent::WithdrawEventV2(inner) => (None, Some(inner.amount.clone())),
+                FungibleAssetEvent::DepositEventV2(inner) => (None, Some(inner.amount.clone())),
+                FungibleAssetEvent::FrozenEventV2(inner) => (Some(inner.frozen), None),
+            };
+
+            return Ok(Some(Self {
+                transaction_version: txn_version,
+                event_index,
+                owner_address: object.object_core.get_owner_address(),
+                storage_id,
+                asset_type: fungible_asset.metadata.get_reference_address(),
+                is_frozen,
+                amount,
+                type_: event_type,
+                entry_function_id_str: entry_function_id_str.clone(),
+                block_height,
+                transaction_timestamp: txn_timestamp,
+            }));
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,222 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+#![allow(clippy::unused_unit)]
+
+use crate::{
+    database::PgPoolConnection,
+    models::{
+        coin_models::v2_fungible_asset_utils::{
+            FungibleAssetAggregatedDataMapping, FungibleAssetStore,
+        },
+        token_models::collection_datas::{QUERY_RETRIES, QUERY_RETRY_DELAY_MS},
+    },
+    schema::{current_fungible_asset_balances, fungible_asset_balances},
+    util::standardize_address,
+};
+use aptos_api_types::{DeleteResource, WriteResource as APIWriteResource};
+use bigdecimal::{BigDecimal, Zero};
+use diesel::{prelude::*, ExpressionMethods};
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// PK of current_fungible_asset_balances, i.e. storage_id
+pub type CurrentFungibleAssetBalancePK = String;
+
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(transaction_version, write_set_change_index))]
+#[diesel(table_name = fungible_asset_balances)]
+pub struct FungibleAssetBalance {
+    pub transaction_version: i64,
+    pub write_set_change_index: i64,
+    pub storage_id: String,
+    pub owner_address: String,
+    pub asset_type: String,
+    pub is_frozen: bool,
+    pub amount: BigDecimal,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(storage_id))]
+#[diesel(table_name = current_fungible_asset_balances)]
+pub struct CurrentFungibleAssetBalance {
+    pub storage_id: String,
+    pub owner_address: String,
+    pub asset_type: String,
+    pub is_frozen: bool,
+    pub amount: BigDecimal,
+    pub last_transaction_version: i64,
+    pub last_transaction_timestamp: chrono::NaiveDateTime,
+}
+
+/// Need a separate struct for queryable because we don't want to define the inserted_at column (letting DB fill)
+#[derive(Debug, Identifiable, Queryable)]
+#[diesel(primary_key(storage_id))]
+#[diesel(table_name = current_fungible_asset_balances)]
+pub struct CurrentFungibleAssetBalanceQuery {
+    pub storage_id: String,
+    pub owner_address: String,
+    pub asset_type: String,
+    pub is_frozen: bool,
+    pub amount: BigDecimal,
+    pub last_transaction_version: i64,
+    pub last_transaction_timestamp: chrono::NaiveDateTime,
+    pub inserted_at: chrono::NaiveDateTime,
+}
+
+impl FungibleAssetBalance {
+    /// Balances are tracked per fungible store. The store is an object so the owner is the owner
+    /// of the object core, and the storage id is the address of the object.
+    pub fn get_v2_from_write_resource(
+        write_resource: &APIWriteResource,
+        write_set_change_index: i64,
+        txn_version: i64,
+        txn_timestamp: chrono::NaiveDateTime,
+        fungible_asset_metadata: &FungibleAssetAggregatedDataMapping,
+    ) -> anyhow::Result<Option<(Self, CurrentFungibleAssetBalance)>> {
+        if let Some(inner) = &FungibleAssetStore::from_write_resource(write_resource, txn_version)?
+        {
+            let storage_id = standardize_address(&write_resource.address.to_string());
+            // ObjectCore should not be missing, returning from entire function early
+            let object = match fungible_asset_metadata.get(&storage_id) {
+                Some(metadata) => &metadata.object,
+                None => return Ok(None),
+            };
+            let owner_address = object.object_core.get_owner_address();
+            let asset_type = inner.metadata.get_reference_address();
+
+            let fungible_asset_balance = Self {
+                transaction_version: txn_version,
+                write_set_change_index,
+                storage_id: storage_id.clone(),
+                owner_address: owner_address.clone(),
+                asset_type: asset_type.clone(),
+                is_frozen: inner.frozen,
+                amount: inner.balance.clone(),
+                transaction_timestamp: txn_timestamp,
+            };
+            let current_fungible_asset_balance = CurrentFungibleAssetBalance {
+                storage_id,
+                owner_address,
+                asset_type,
+                is_frozen: inner.frozen,
+                amount: inner.balance.clone(),
+                last_transaction_version: txn_version,
+                last_transaction_timestamp: txn_timestamp,
+            };
+            Ok(Some((
+                fungible_asset_balance,
+                current_fungible_asset_balance,
+            )))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// A deleted store no longer holds any balance, so it's zeroed.  The delete doesn't have the
+    /// owner or the asset type, so they come from the store's prior balance in this batch or in
+    /// the DB.  Deleting the whole object group deletes the store along with the object.
+    pub fn get_v2_from_delete_resource(
+        delete_resource: &DeleteResource,
+        write_set_change_index: i64,
+        txn_version: i64,
+        txn_timestamp: chrono::NaiveDateTime,
+        prior_balances: &HashMap<CurrentFungibleAssetBalancePK, CurrentFungibleAssetBalance>,
+        conn: &mut PgPoolConnection,
+    ) -> anyhow::Result<Option<(Self, CurrentFungibleAssetBalance)>> {
+        let resource_type = delete_resource.resource.to_string();
+        let is_store = resource_type == "0x1::fungible_asset::FungibleStore";
+        if !is_store && resource_type != "0x1::object::ObjectGroup" {
+            return Ok(None);
+        }
+
+        let storage_id = standardize_address(&delete_resource.address.to_string());
+        let prior_balance = match prior_balances.get(&storage_id) {
+            Some(inner) => inner.clone(),
+            None => {
+                match CurrentFungibleAssetBalanceQuery::get_by_storage_id(conn, &storage_id) {
+                    Ok(Some(inner)) => inner,
+                    // Most deleted objects aren't fungible stores
+                    Ok(None) if !is_store => return Ok(None),
+                    _ => {
+                        aptos_logger::error!(
+                            transaction_version = txn_version,
+                            lookup_key = &storage_id,
+                            "Failed to find balance for deleted fungible store. You probably should backfill db."
+                        );
+                        return Ok(None);
+                    },
+                }
+            },
+        };
+
+        Ok(Some((
+            Self {
+                transaction_version: txn_version,
+                write_set_change_index,
+                storage_id: storage_id.clone(),
+                owner_address: prior_balance.owner_address.clone(),
+                asset_type: prior_balance.asset_type.clone(),
+                is_frozen: prior_balance.is_frozen,
+                amount: BigDecimal::zero(),
+                transaction_timestamp: txn_timestamp,
+            },
+            CurrentFungibleAssetBalance {
+                storage_id,
+                owner_address: prior_balance.owner_address,
+                asset_type: prior_balance.asset_type,
+                is_frozen: prior_balance.is_frozen,
+                amount: BigDecimal::zero(),
+                last_transaction_version: txn_version,
+                last_transaction_timestamp: txn_timestamp,
+            },
+        )))
+    }
+}
+
+impl CurrentFungibleAssetBalanceQuery {
+    /// Gets the current balance of a store, if the store has been indexed
+    pub fn get_by_storage_id(
+        conn: &mut PgPoolConnection,
+        storage_id: &str,
+    ) -> anyhow::Result<Option<CurrentFungibleAssetBalance>> {
+        let mut retried = 0;
+        while retried < QUERY_RETRIES {
+            retried += 1;
+            match Self::get_by_storage_id_impl(conn, storage_id) {
+                Ok(inner) => {
+                    return Ok(inner.map(|inner| CurrentFungibleAssetBalance {
+                        storage_id: inner.storage_id,
+                        owner_address: inner.owner_address,
+                        asset_type: inner.asset_type,
+                        is_frozen: inner.is_frozen,
+                        amount: inner.amount,
+                        last_transaction_version: inner.last_transaction_version,
+                        last_transaction_timestamp: inner.last_transaction_timestamp,
+                    }))
+                },
+                Err(_) => {
+                    std::thread::sleep(std::time::Duration::from_millis(QUERY_RETRY_DELAY_MS));
+                },
+            }
+        }
+        Err(anyhow::anyhow!(
+            "Failed to get fungible asset balance by storage id: {}",
+            storage_id
+        ))
+    }
+
+    fn get_by_storage_id_impl(
+        conn: &mut PgPoolConnection,
+        storage_id: &str,
+    ) -> diesel::QueryResult<Option<Self>> {
+        current_fungible_asset_balances::table
+            .filter(current_fungible_asset_balances::storage_id.eq(storage_id))
+            .first::<Self>(conn)
+            .optional()
+    }
+}
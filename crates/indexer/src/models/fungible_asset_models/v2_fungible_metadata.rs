@@ -0,0 +1,90 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+#![allow(clippy::unused_unit)]
+
+use crate::{
+    models::coin_models::v2_fungible_asset_utils::{
+        FungibleAssetAggregatedDataMapping, FungibleAssetMetadata,
+    },
+    schema::fungible_asset_metadata,
+    util::standardize_address,
+};
+use aptos_api_types::WriteResource as APIWriteResource;
+use bigdecimal::BigDecimal;
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+
+// PK of fungible_asset_metadata, i.e. asset_type
+pub type FungibleAssetMetadataPK = String;
+
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(asset_type))]
+#[diesel(table_name = fungible_asset_metadata)]
+pub struct FungibleAssetMetadataModel {
+    pub asset_type: String,
+    pub creator_address: String,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: i32,
+    pub icon_uri: String,
+    pub project_uri: String,
+    pub supply: Option<BigDecimal>,
+    pub maximum_supply: Option<BigDecimal>,
+    pub is_concurrent_supply: bool,
+    pub last_transaction_version: i64,
+    pub last_transaction_timestamp: chrono::NaiveDateTime,
+}
+
+impl FungibleAssetMetadataModel {
+    /// Fungible asset metadata lives in an object, which also contains the supply (if tracked).
+    /// The asset type is the address of that object.
+    pub fn get_v2_from_write_resource(
+        write_resource: &APIWriteResource,
+        txn_version: i64,
+        txn_timestamp: chrono::NaiveDateTime,
+        fungible_asset_metadata: &FungibleAssetAggregatedDataMapping,
+    ) -> anyhow::Result<Option<Self>> {
+        if let Some(inner) =
+            &FungibleAssetMetadata::from_write_resource(write_resource, txn_version)?
+        {
+            let asset_type = standardize_address(&write_resource.address.to_string());
+            // ObjectCore should not be missing, returning from entire function early
+            let metadata = match fungible_asset_metadata.get(&asset_type) {
+                Some(metadata) => metadata,
+                None => return Ok(None),
+            };
+            let (supply, maximum_supply, is_concurrent_supply) =
+                if let Some(supply) = metadata.concurrent_fungible_asset_supply.as_ref() {
+                    (
+                        Some(supply.current.value.clone()),
+                        supply.get_maximum(),
+                        true,
+                    )
+                } else if let Some(supply) = metadata.fungible_asset_supply.as_ref() {
+                    (Some(supply.current.clone()), supply.get_maximum(), false)
+                } else {
+                    (None, None, false)
+                };
+
+            Ok(Some(Self {
+                asset_type,
+                creator_address: metadata.object.object_core.get_owner_address(),
+                name: inner.get_name(),
+                symbol: inner.get_symbol(),
+                decimals: inner.decimals,
+                icon_uri: inner.get_icon_uri(),
+                project_uri: inner.get_project_uri(),
+                supply,
+                maximum_supply,
+                is_concurrent_supply,
+                last_transaction_version: txn_version,
+                last_transaction_timestamp: txn_timestamp,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+}
@@ -110,6 +110,7 @@ impl CollectionV2 {
                 // Getting supply data (prefer fixed supply over unlimited supply although they should never appear at the same time anyway)
                 let fixed_supply = metadata.fixed_supply.as_ref();
                 let unlimited_supply = metadata.unlimited_supply.as_ref();
+                let concurrent_supply = metadata.concurrent_supply.as_ref();
                 if let Some(supply) = unlimited_supply {
                     (current_supply, max_supply, total_minted_v2) = (
                         supply.current_supply.clone(),
@@ -117,6 +118,13 @@ impl CollectionV2 {
                         Some(supply.total_minted.clone()),
                     );
                 }
+                if let Some(supply) = concurrent_supply {
+                    (current_supply, max_supply, total_minted_v2) = (
+                        supply.current_supply.value.clone(),
+                        supply.get_max_supply(),
+                        Some(supply.total_minted.value.clone()),
+                    );
+                }
                 if let Some(supply) = fixed_supply {
                     (current_supply, max_supply, total_minted_v2) = (
                         supply.current_supply.clone(),
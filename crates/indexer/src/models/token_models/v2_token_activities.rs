@@ -11,8 +11,12 @@ use super::{
     v2_token_utils::{TokenStandard, TokenV2AggregatedDataMapping, V2TokenEvent},
 };
 use crate::{
-    database::PgPoolConnection, models::coin_models::v2_fungible_asset_utils::FungibleAssetEvent,
-    schema::token_activities_v2, util::standardize_address,
+    database::PgPoolConnection,
+    models::coin_models::v2_fungible_asset_utils::{
+        DepositEvent, DepositEventV2, FungibleAssetEvent, WithdrawEvent, WithdrawEventV2,
+    },
+    schema::token_activities_v2,
+    util::standardize_address,
 };
 use aptos_api_types::Event as APIEvent;
 use bigdecimal::{BigDecimal, One, Zero};
@@ -78,10 +82,9 @@ impl TokenActivityV2 {
         if let Some(fa_event) =
             &FungibleAssetEvent::from_event(event_type.as_str(), &event.data, txn_version)?
         {
-            let event_account_address =
-                standardize_address(&event.guid.account_address.to_string());
-            // The event account address will also help us find fungible store which tells us where to find
+            // The store address will also help us find fungible store which tells us where to find
             // the metadata
+            let event_account_address = fa_event.get_store_address(event);
             if let Some(metadata) = token_v2_metadata.get(&event_account_address) {
                 let object_core = &metadata.object.object_core;
                 let fungible_asset = metadata.fungible_asset_store.as_ref().unwrap();
@@ -98,19 +101,29 @@ impl TokenActivityV2 {
                 }
 
                 let token_activity_helper = match fa_event {
-                    FungibleAssetEvent::WithdrawEvent(inner) => TokenActivityHelperV2 {
-                        from_address: Some(object_core.get_owner_address()),
-                        to_address: None,
-                        token_amount: inner.amount.clone(),
-                        before_value: None,
-                        after_value: None,
+                    FungibleAssetEvent::WithdrawEvent(WithdrawEvent { amount })
+                    | FungibleAssetEvent::WithdrawEventV2(WithdrawEventV2 { amount, .. }) => {
+                        TokenActivityHelperV2 {
+                            from_address: Some(object_core.get_owner_address()),
+                            to_address: None,
+                            token_amount: amount.clone(),
+                            before_value: None,
+                            after_value: None,
+                        }
                     },
-                    FungibleAssetEvent::DepositEvent(inner) => TokenActivityHelperV2 {
-                        from_address: None,
-                        to_address: Some(object_core.get_owner_address()),
-                        token_amount: inner.amount.clone(),
-                        before_value: None,
-                        after_value: None,
+                    FungibleAssetEvent::DepositEvent(DepositEvent { amount })
+                    | FungibleAssetEvent::DepositEventV2(DepositEventV2 { amount, .. }) => {
+                        TokenActivityHelperV2 {
+                            from_address: None,
+                            to_address: Some(object_core.get_owner_address()),
+                            token_amount: amount.clone(),
+                            before_value: None,
+                            after_value: None,
+                        }
+                    },
+                    // Freezing a store doesn't move any tokens
+                    FungibleAssetEvent::FrozenEvent(_) | FungibleAssetEvent::FrozenEventV2(_) => {
+                        return Ok(None)
                     },
                 };
 
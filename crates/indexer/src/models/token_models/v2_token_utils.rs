@@ -37,6 +37,7 @@ pub type EventIndex = i64;
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct TokenV2AggregatedData {
     pub aptos_collection: Option<AptosCollection>,
+    pub concurrent_supply: Option<ConcurrentSupply>,
     pub fixed_supply: Option<FixedSupply>,
     pub fungible_asset_metadata: Option<FungibleAssetMetadata>,
     pub fungible_asset_supply: Option<FungibleAssetSupply>,
@@ -323,6 +324,62 @@ impl UnlimitedSupply {
     }
 }
 
+/// Aggregators (v2) are stored inline in the resource so unlike aggregators v1 we don't need to
+/// look them up in a table
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AggregatorV2 {
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub value: BigDecimal,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub max_value: BigDecimal,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ConcurrentSupply {
+    pub current_supply: AggregatorV2,
+    pub total_minted: AggregatorV2,
+}
+
+impl ConcurrentSupply {
+    pub fn from_write_resource(
+        write_resource: &WriteResource,
+        txn_version: i64,
+    ) -> anyhow::Result<Option<Self>> {
+        let type_str = format!(
+            "{}::{}::{}",
+            write_resource.data.typ.address,
+            write_resource.data.typ.module,
+            write_resource.data.typ.name
+        );
+        if !V2TokenResource::is_resource_supported(type_str.as_str()) {
+            return Ok(None);
+        }
+        let resource = MoveResource::from_write_resource(
+            write_resource,
+            0, // Placeholder, this isn't used anyway
+            txn_version,
+            0, // Placeholder, this isn't used anyway
+        );
+
+        if let V2TokenResource::ConcurrentSupply(inner) =
+            V2TokenResource::from_resource(&type_str, resource.data.as_ref().unwrap(), txn_version)?
+        {
+            Ok(Some(inner))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// A max value of u64::MAX means the supply is unlimited
+    pub fn get_max_supply(&self) -> Option<BigDecimal> {
+        if self.current_supply.max_value == BigDecimal::from(u64::MAX) {
+            None
+        } else {
+            Some(self.current_supply.max_value.clone())
+        }
+    }
+}
+
 /* Section on Events */
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MintEvent {
@@ -442,6 +499,7 @@ impl PropertyMap {
 pub enum V2TokenResource {
     AptosCollection(AptosCollection),
     Collection(Collection),
+    ConcurrentSupply(ConcurrentSupply),
     FixedSupply(FixedSupply),
     ObjectCore(ObjectCore),
     UnlimitedSupply(UnlimitedSupply),
@@ -455,6 +513,7 @@ impl V2TokenResource {
             data_type,
             "0x1::object::ObjectCore"
                 | "0x4::collection::Collection"
+                | "0x4::collection::ConcurrentSupply"
                 | "0x4::collection::FixedSupply"
                 | "0x4::collection::UnlimitedSupply"
                 | "0x4::aptos_token::AptosCollection"
@@ -475,6 +534,8 @@ impl V2TokenResource {
             "0x4::collection::Collection" => {
                 serde_json::from_value(data.clone()).map(|inner| Some(Self::Collection(inner)))
             },
+            "0x4::collection::ConcurrentSupply" => serde_json::from_value(data.clone())
+                .map(|inner| Some(Self::ConcurrentSupply(inner))),
             "0x4::collection::FixedSupply" => {
                 serde_json::from_value(data.clone()).map(|inner| Some(Self::FixedSupply(inner)))
             },
@@ -0,0 +1,452 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    database::{
+        clean_data_for_db, execute_with_better_error, get_chunks, PgDbPool, PgPoolConnection,
+    },
+    indexer::{
+        errors::TransactionProcessingError, processing_result::ProcessingResult,
+        transaction_processor::TransactionProcessor,
+    },
+    models::{
+        coin_models::{
+            coin_activities::MAX_ENTRY_FUNCTION_LENGTH,
+            v2_fungible_asset_utils::{
+                ConcurrentFungibleAssetSupply, FungibleAssetAggregatedData,
+                FungibleAssetAggregatedDataMapping, FungibleAssetMetadata, FungibleAssetStore,
+                FungibleAssetSupply,
+            },
+        },
+        fungible_asset_models::{
+            v2_fungible_asset_activities::FungibleAssetActivity,
+            v2_fungible_asset_balances::{
+                CurrentFungibleAssetBalance, CurrentFungibleAssetBalancePK, FungibleAssetBalance,
+            },
+            v2_fungible_metadata::{FungibleAssetMetadataModel, FungibleAssetMetadataPK},
+        },
+        token_models::v2_token_utils::ObjectWithMetadata,
+    },
+    schema,
+    util::{parse_timestamp, standardize_address, truncate_str},
+};
+use aptos_api_types::{Transaction, TransactionPayload, WriteSetChange};
+use async_trait::async_trait;
+use diesel::{pg::upsert::excluded, result::Error, ExpressionMethods, PgConnection};
+use field_count::FieldCount;
+use std::{collections::HashMap, fmt::Debug};
+
+pub const NAME: &str = "fungible_asset_processor";
+pub struct FungibleAssetTransactionProcessor {
+    connection_pool: PgDbPool,
+}
+
+impl FungibleAssetTransactionProcessor {
+    pub fn new(connection_pool: PgDbPool) -> Self {
+        Self { connection_pool }
+    }
+}
+
+impl Debug for FungibleAssetTransactionProcessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = &self.connection_pool.state();
+        write!(
+            f,
+            "FungibleAssetTransactionProcessor {{ connections: {:?}  idle_connections: {:?} }}",
+            state.connections, state.idle_connections
+        )
+    }
+}
+
+fn insert_to_db_impl(
+    conn: &mut PgConnection,
+    fungible_asset_activities: &[FungibleAssetActivity],
+    fungible_asset_metadata: &[FungibleAssetMetadataModel],
+    fungible_asset_balances: &[FungibleAssetBalance],
+    current_fungible_asset_balances: &[CurrentFungibleAssetBalance],
+) -> Result<(), diesel::result::Error> {
+    insert_fungible_asset_activities(conn, fungible_asset_activities)?;
+    insert_fungible_asset_metadata(conn, fungible_asset_metadata)?;
+    insert_fungible_asset_balances(conn, fungible_asset_balances)?;
+    insert_current_fungible_asset_balances(conn, current_fungible_asset_balances)?;
+    Ok(())
+}
+
+fn insert_to_db(
+    conn: &mut PgPoolConnection,
+    name: &'static str,
+    start_version: u64,
+    end_version: u64,
+    fungible_asset_activities: Vec<FungibleAssetActivity>,
+    fungible_asset_metadata: Vec<FungibleAssetMetadataModel>,
+    fungible_asset_balances: Vec<FungibleAssetBalance>,
+    current_fungible_asset_balances: Vec<CurrentFungibleAssetBalance>,
+) -> Result<(), diesel::result::Error> {
+    aptos_logger::trace!(
+        name = name,
+        start_version = start_version,
+        end_version = end_version,
+        "Inserting to db",
+    );
+    match conn
+        .build_transaction()
+        .read_write()
+        .run::<_, Error, _>(|pg_conn| {
+            insert_to_db_impl(
+                pg_conn,
+                &fungible_asset_activities,
+                &fungible_asset_metadata,
+                &fungible_asset_balances,
+                &current_fungible_asset_balances,
+            )
+        }) {
+        Ok(_) => Ok(()),
+        Err(_) => conn
+            .build_transaction()
+            .read_write()
+            .run::<_, Error, _>(|pg_conn| {
+                let fungible_asset_activities = clean_data_for_db(fungible_asset_activities, true);
+                let fungible_asset_metadata = clean_data_for_db(fungible_asset_metadata, true);
+                let fungible_asset_balances = clean_data_for_db(fungible_asset_balances, true);
+                let current_fungible_asset_balances =
+                    clean_data_for_db(current_fungible_asset_balances, true);
+
+                insert_to_db_impl(
+                    pg_conn,
+                    &fungible_asset_activities,
+                    &fungible_asset_metadata,
+                    &fungible_asset_balances,
+                    &current_fungible_asset_balances,
+                )
+            }),
+    }
+}
+
+fn insert_fungible_asset_activities(
+    conn: &mut PgConnection,
+    item_to_insert: &[FungibleAssetActivity],
+) -> Result<(), diesel::result::Error> {
+    use schema::fungible_asset_activities::dsl::*;
+
+    let chunks = get_chunks(item_to_insert.len(), FungibleAssetActivity::field_count());
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::fungible_asset_activities::table)
+                .values(&item_to_insert[start_ind..end_ind])
+                .on_conflict((transaction_version, event_index))
+                .do_nothing(),
+            None,
+        )?;
+    }
+    Ok(())
+}
+
+fn insert_fungible_asset_metadata(
+    conn: &mut PgConnection,
+    item_to_insert: &[FungibleAssetMetadataModel],
+) -> Result<(), diesel::result::Error> {
+    use schema::fungible_asset_metadata::dsl::*;
+
+    let chunks = get_chunks(
+        item_to_insert.len(),
+        FungibleAssetMetadataModel::field_count(),
+    );
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::fungible_asset_metadata::table)
+                .values(&item_to_insert[start_ind..end_ind])
+                .on_conflict(asset_type)
+                .do_update()
+                .set((
+                    creator_address.eq(excluded(creator_address)),
+                    name.eq(excluded(name)),
+                    symbol.eq(excluded(symbol)),
+                    decimals.eq(excluded(decimals)),
+                    icon_uri.eq(excluded(icon_uri)),
+                    project_uri.eq(excluded(project_uri)),
+                    supply.eq(excluded(supply)),
+                    maximum_supply.eq(excluded(maximum_supply)),
+                    is_concurrent_supply.eq(excluded(is_concurrent_supply)),
+                    last_transaction_version.eq(excluded(last_transaction_version)),
+                    last_transaction_timestamp.eq(excluded(last_transaction_timestamp)),
+                    inserted_at.eq(excluded(inserted_at)),
+                )),
+            Some(" WHERE fungible_asset_metadata.last_transaction_version <= excluded.last_transaction_version "),
+        )?;
+    }
+    Ok(())
+}
+
+fn insert_fungible_asset_balances(
+    conn: &mut PgConnection,
+    item_to_insert: &[FungibleAssetBalance],
+) -> Result<(), diesel::result::Error> {
+    use schema::fungible_asset_balances::dsl::*;
+
+    let chunks = get_chunks(item_to_insert.len(), FungibleAssetBalance::field_count());
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::fungible_asset_balances::table)
+                .values(&item_to_insert[start_ind..end_ind])
+                .on_conflict((transaction_version, write_set_change_index))
+                .do_nothing(),
+            None,
+        )?;
+    }
+    Ok(())
+}
+
+fn insert_current_fungible_asset_balances(
+    conn: &mut PgConnection,
+    item_to_insert: &[CurrentFungibleAssetBalance],
+) -> Result<(), diesel::result::Error> {
+    use schema::current_fungible_asset_balances::dsl::*;
+
+    let chunks = get_chunks(
+        item_to_insert.len(),
+        CurrentFungibleAssetBalance::field_count(),
+    );
+    for (start_ind, end_ind) in chunks {
+        execute_with_better_error(
+            conn,
+            diesel::insert_into(schema::current_fungible_asset_balances::table)
+                .values(&item_to_insert[start_ind..end_ind])
+                .on_conflict(storage_id)
+                .do_update()
+                .set((
+                    owner_address.eq(excluded(owner_address)),
+                    asset_type.eq(excluded(asset_type)),
+                    is_frozen.eq(excluded(is_frozen)),
+                    amount.eq(excluded(amount)),
+                    last_transaction_version.eq(excluded(last_transaction_version)),
+                    last_transaction_timestamp.eq(excluded(last_transaction_timestamp)),
+                    inserted_at.eq(excluded(inserted_at)),
+                )),
+            Some(" WHERE current_fungible_asset_balances.last_transaction_version <= excluded.last_transaction_version "),
+        )?;
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl TransactionProcessor for FungibleAssetTransactionProcessor {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    async fn process_transactions(
+        &self,
+        transactions: Vec<Transaction>,
+        start_version: u64,
+        end_version: u64,
+    ) -> Result<ProcessingResult, TransactionProcessingError> {
+        let mut conn = self.get_conn();
+
+        let (
+            fungible_asset_activities,
+            fungible_asset_metadata,
+            fungible_asset_balances,
+            current_fungible_asset_balances,
+        ) = parse_v2_fungible_assets(&transactions, &mut conn);
+
+        let tx_result = insert_to_db(
+            &mut conn,
+            self.name(),
+            start_version,
+            end_version,
+            fungible_asset_activities,
+            fungible_asset_metadata,
+            fungible_asset_balances,
+            current_fungible_asset_balances,
+        );
+        match tx_result {
+            Ok(_) => Ok(ProcessingResult::new(
+                self.name(),
+                start_version,
+                end_version,
+            )),
+            Err(err) => Err(TransactionProcessingError::TransactionCommitError((
+                anyhow::Error::from(err),
+                start_version,
+                end_version,
+                self.name(),
+            ))),
+        }
+    }
+
+    fn connection_pool(&self) -> &PgDbPool {
+        &self.connection_pool
+    }
+}
+
+fn parse_v2_fungible_assets(
+    transactions: &[Transaction],
+    conn: &mut PgPoolConnection,
+) -> (
+    Vec<FungibleAssetActivity>,
+    Vec<FungibleAssetMetadataModel>,
+    Vec<FungibleAssetBalance>,
+    Vec<CurrentFungibleAssetBalance>,
+) {
+    let mut fungible_asset_activities = vec![];
+    let mut fungible_asset_balances = vec![];
+    let mut fungible_asset_metadata: HashMap<FungibleAssetMetadataPK, FungibleAssetMetadataModel> =
+        HashMap::new();
+    let mut current_fungible_asset_balances: HashMap<
+        CurrentFungibleAssetBalancePK,
+        CurrentFungibleAssetBalance,
+    > = HashMap::new();
+
+    for txn in transactions {
+        if let Transaction::UserTransaction(user_txn) = txn {
+            let txn_version = user_txn.info.version.0 as i64;
+            let block_height = user_txn.info.block_height.unwrap().0 as i64;
+            let txn_timestamp = parse_timestamp(user_txn.timestamp.0, txn_version);
+            let entry_function_id_str = match &user_txn.request.payload {
+                TransactionPayload::EntryFunctionPayload(payload) => Some(truncate_str(
+                    &payload.function.to_string(),
+                    MAX_ENTRY_FUNCTION_LENGTH,
+                )),
+                _ => None,
+            };
+            // Get Metadata for fungible assets by object
+            let mut fungible_asset_object_helper: FungibleAssetAggregatedDataMapping =
+                HashMap::new();
+
+            // Need to do a first pass to get all the objects
+            for wsc in user_txn.info.changes.iter() {
+                if let WriteSetChange::WriteResource(wr) = wsc {
+                    if let Some(object) =
+                        ObjectWithMetadata::from_write_resource(wr, txn_version).unwrap()
+                    {
+                        fungible_asset_object_helper.insert(
+                            standardize_address(&wr.address.to_string()),
+                            FungibleAssetAggregatedData {
+                                object,
+                                fungible_asset_metadata: None,
+                                fungible_asset_supply: None,
+                                concurrent_fungible_asset_supply: None,
+                                fungible_asset_store: None,
+                            },
+                        );
+                    }
+                }
+            }
+
+            // Need to do a second pass to get all the structs related to the object
+            for wsc in user_txn.info.changes.iter() {
+                if let WriteSetChange::WriteResource(wr) = wsc {
+                    let address = standardize_address(&wr.address.to_string());
+                    if let Some(aggregated_data) = fungible_asset_object_helper.get_mut(&address) {
+                        if let Some(fungible_asset_metadata) =
+                            FungibleAssetMetadata::from_write_resource(wr, txn_version).unwrap()
+                        {
+                            aggregated_data.fungible_asset_metadata = Some(fungible_asset_metadata);
+                        }
+                        if let Some(fungible_asset_supply) =
+                            FungibleAssetSupply::from_write_resource(wr, txn_version).unwrap()
+                        {
+                            aggregated_data.fungible_asset_supply = Some(fungible_asset_supply);
+                        }
+                        if let Some(concurrent_fungible_asset_supply) =
+                            ConcurrentFungibleAssetSupply::from_write_resource(wr, txn_version)
+                                .unwrap()
+                        {
+                            aggregated_data.concurrent_fungible_asset_supply =
+                                Some(concurrent_fungible_asset_supply);
+                        }
+                        if let Some(fungible_asset_store) =
+                            FungibleAssetStore::from_write_resource(wr, txn_version).unwrap()
+                        {
+                            aggregated_data.fungible_asset_store = Some(fungible_asset_store);
+                        }
+                    }
+                }
+            }
+
+            // Deposit, withdraw and frozen events
+            for (index, event) in user_txn.events.iter().enumerate() {
+                if let Some(activity) = FungibleAssetActivity::get_v2_from_parsed_event(
+                    event,
+                    txn_version,
+                    block_height,
+                    txn_timestamp,
+                    index as i64,
+                    &entry_function_id_str,
+                    &fungible_asset_object_helper,
+                )
+                .unwrap()
+                {
+                    fungible_asset_activities.push(activity);
+                }
+            }
+
+            // Metadata and balances
+            for (index, wsc) in user_txn.info.changes.iter().enumerate() {
+                if let WriteSetChange::DeleteResource(dr) = wsc {
+                    if let Some((balance, current_balance)) =
+                        FungibleAssetBalance::get_v2_from_delete_resource(
+                            dr,
+                            index as i64,
+                            txn_version,
+                            txn_timestamp,
+                            &current_fungible_asset_balances,
+                            conn,
+                        )
+                        .unwrap()
+                    {
+                        fungible_asset_balances.push(balance);
+                        current_fungible_asset_balances
+                            .insert(current_balance.storage_id.clone(), current_balance);
+                    }
+                }
+                if let WriteSetChange::WriteResource(wr) = wsc {
+                    if let Some(metadata) = FungibleAssetMetadataModel::get_v2_from_write_resource(
+                        wr,
+                        txn_version,
+                        txn_timestamp,
+                        &fungible_asset_object_helper,
+                    )
+                    .unwrap()
+                    {
+                        fungible_asset_metadata.insert(metadata.asset_type.clone(), metadata);
+                    }
+                    if let Some((balance, current_balance)) =
+                        FungibleAssetBalance::get_v2_from_write_resource(
+                            wr,
+                            index as i64,
+                            txn_version,
+                            txn_timestamp,
+                            &fungible_asset_object_helper,
+                        )
+                        .unwrap()
+                    {
+                        fungible_asset_balances.push(balance);
+                        current_fungible_asset_balances
+                            .insert(current_balance.storage_id.clone(), current_balance);
+                    }
+                }
+            }
+        }
+    }
+
+    // Getting list of values and sorting by pk in order to avoid postgres deadlock since we're doing multi threaded db writes
+    let mut fungible_asset_metadata = fungible_asset_metadata
+        .into_values()
+        .collect::<Vec<FungibleAssetMetadataModel>>();
+    let mut current_fungible_asset_balances = current_fungible_asset_balances
+        .into_values()
+        .collect::<Vec<CurrentFungibleAssetBalance>>();
+    fungible_asset_metadata.sort_by(|a, b| a.asset_type.cmp(&b.asset_type));
+    current_fungible_asset_balances.sort_by(|a, b| a.storage_id.cmp(&b.storage_id));
+
+    (
+        fungible_asset_activities,
+        fungible_asset_metadata,
+        fungible_asset_balances,
+        current_fungible_asset_balances,
+    )
+}
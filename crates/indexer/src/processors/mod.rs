@@ -3,17 +3,20 @@
 
 pub mod coin_processor;
 pub mod default_processor;
+pub mod fungible_asset_processor;
 pub mod stake_processor;
 pub mod token_processor;
 
 use self::{
     coin_processor::NAME as COIN_PROCESSOR_NAME, default_processor::NAME as DEFAULT_PROCESSOR_NAME,
+    fungible_asset_processor::NAME as FUNGIBLE_ASSET_PROCESSOR_NAME,
     stake_processor::NAME as STAKE_PROCESSOR_NAME, token_processor::NAME as TOKEN_PROCESSOR_NAME,
 };
 
 pub enum Processor {
     CoinProcessor,
     DefaultProcessor,
+    FungibleAssetProcessor,
     TokenProcessor,
     StakeProcessor,
 }
@@ -25,6 +28,7 @@ impl Processor {
             TOKEN_PROCESSOR_NAME => Self::TokenProcessor,
             COIN_PROCESSOR_NAME => Self::CoinProcessor,
             STAKE_PROCESSOR_NAME => Self::StakeProcessor,
+            FUNGIBLE_ASSET_PROCESSOR_NAME => Self::FungibleAssetProcessor,
             _ => panic!("Processor unsupported {}", input_str),
         }
     }
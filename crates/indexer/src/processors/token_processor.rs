@@ -37,9 +37,9 @@ use crate::{
                 TokenOwnershipV2,
             },
             v2_token_utils::{
-                AptosCollection, BurnEvent, FixedSupply, ObjectWithMetadata, PropertyMap, TokenV2,
-                TokenV2AggregatedData, TokenV2AggregatedDataMapping, TokenV2Burned, TransferEvent,
-                UnlimitedSupply,
+                AptosCollection, BurnEvent, ConcurrentSupply, FixedSupply, ObjectWithMetadata,
+                PropertyMap, TokenV2, TokenV2AggregatedData, TokenV2AggregatedDataMapping,
+                TokenV2Burned, TransferEvent, UnlimitedSupply,
             },
         },
     },
@@ -1099,6 +1099,7 @@ fn parse_v2_token(
                             standardize_address(&wr.address.to_string()),
                             TokenV2AggregatedData {
                                 aptos_collection: None,
+                                concurrent_supply: None,
                                 fixed_supply: None,
                                 object,
                                 unlimited_supply: None,
@@ -1129,6 +1130,11 @@ fn parse_v2_token(
                         {
                             aggregated_data.unlimited_supply = Some(unlimited_supply);
                         }
+                        if let Some(concurrent_supply) =
+                            ConcurrentSupply::from_write_resource(wr, txn_version).unwrap()
+                        {
+                            aggregated_data.concurrent_supply = Some(concurrent_supply);
+                        }
                         if let Some(aptos_collection) =
                             AptosCollection::from_write_resource(wr, txn_version).unwrap()
                         {
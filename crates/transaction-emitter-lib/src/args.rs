@@ -108,7 +108,7 @@ impl ClusterArgs {
 #[clap(group(
     ArgGroup::new("mode")
         .required(true)
-        .args(&["mempool_backlog", "target_tps"]),
+        .args(&["mempool_backlog", "target_tps", "workload_file"]),
 ))]
 pub struct EmitArgs {
     #[clap(long)]
@@ -122,6 +122,31 @@ pub struct EmitArgs {
     #[clap(long)]
     pub target_tps: Option<usize>,
 
+    /// YAML or TOML file describing the phases of the workload, with the load and
+    /// transaction mix of each. Replaces mempool_backlog/target_tps, the transaction
+    /// type arguments and duration, which is taken as the sum of the phase durations.
+    #[clap(long, conflicts_with_all = &["transaction_weights", "transaction_phases"])]
+    pub workload_file: Option<String>,
+
+    /// Send transactions at fixed arrivals of target_tps, without waiting for previous
+    /// transactions to commit, and record per-stage latencies from the scheduled arrival.
+    #[clap(long, conflicts_with = "mempool_backlog")]
+    pub open_loop: bool,
+
+    /// Recording of chain transactions (see record-transactions) to replay from freshly
+    /// minted accounts, instead of the transaction types.
+    #[clap(long, conflicts_with_all = &["workload_file", "transaction_weights", "transaction_phases"])]
+    pub replay_file: Option<String>,
+
+    /// Multiplier of the pace at which the recorded transactions were originally committed,
+    /// e.g. 2 replays them twice as fast. Defaults to the original pace.
+    #[clap(long, requires = "replay_file")]
+    pub replay_speedup: Option<f64>,
+
+    /// File to write the per-stage latency percentiles to, as JSON.
+    #[clap(long)]
+    pub latency_report_file: Option<String>,
+
     #[clap(long, default_value_t = 30)]
     pub txn_expiration_time_secs: u64,
 
@@ -236,6 +261,25 @@ pub struct CreateAccountsArgs {
     pub account_minter_seed: Option<String>,
 }
 
+#[derive(Clone, Debug, Deserialize, Parser, Serialize)]
+pub struct RecordTransactionsArgs {
+    /// REST endpoint of the network to record transactions from, e.g. a mainnet fullnode
+    #[clap(long)]
+    pub rest_url: Url,
+
+    /// First version to record
+    #[clap(long)]
+    pub start_version: u64,
+
+    /// Number of versions to record, only user transactions are kept
+    #[clap(long)]
+    pub num_versions: u64,
+
+    /// File to write the recording to, to be passed to emit-tx --replay-file
+    #[clap(long)]
+    pub output_file: String,
+}
+
 fn parse_target(target: &str) -> Result<Url> {
     let mut url = Url::try_from(target).map_err(|e| {
         format_err!(
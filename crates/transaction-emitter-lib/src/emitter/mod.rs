@@ -6,6 +6,7 @@ pub mod local_account_generator;
 pub mod stats;
 pub mod submission_worker;
 pub mod transaction_executor;
+pub mod transaction_recorder;
 
 use crate::emitter::{
     account_minter::{AccountMinter, SourceAccountManager},
@@ -15,7 +16,7 @@ use crate::emitter::{
     transaction_executor::RestApiReliableTransactionSubmitter,
 };
 use again::RetryPolicy;
-use anyhow::{ensure, format_err, Result};
+use anyhow::{bail, ensure, format_err, Result};
 use aptos_config::config::DEFAULT_MAX_SUBMIT_TRANSACTION_BATCH_SIZE;
 use aptos_logger::{debug, error, info, sample, sample::SampleRate, warn};
 use aptos_rest_client::{aptos_api_types::AptosErrorCode, error::RestError, Client as RestClient};
@@ -25,7 +26,8 @@ use aptos_sdk::{
     types::{transaction::SignedTransaction, LocalAccount},
 };
 use aptos_transaction_generator_lib::{
-    create_txn_generator_creator, AccountType, ReliableTransactionSubmitter, TransactionType,
+    create_txn_generator_creator, workload::WorkloadSpec, AccountType,
+    ReliableTransactionSubmitter, TransactionType,
 };
 use futures::future::{try_join_all, FutureExt};
 use once_cell::sync::Lazy;
@@ -48,6 +50,10 @@ const MAX_TXNS: u64 = 1_000_000_000;
 
 const MAX_RETRIES: usize = 12;
 
+// Keeps the transactions in flight per account well below the mempool limit per account,
+// even with latencies of several seconds.
+const OPEN_LOOP_MAX_TPS_PER_ACCOUNT: usize = 5;
+
 // This retry policy is used for important client calls necessary for setting
 // up the test (e.g. account creation) and collecting its results (e.g. checking
 // account sequence numbers). If these fail, the whole test fails. We do not use
@@ -72,6 +78,8 @@ pub struct EmitModeParams {
     pub wait_millis: u64,
     pub check_account_sequence_only_once_fraction: f32,
     pub check_account_sequence_sleep: Duration,
+    /// Time between transaction arrivals of a single worker, if the emitter is open-loop.
+    pub open_loop_arrival_interval: Option<Duration>,
 }
 
 #[derive(Clone, Debug)]
@@ -95,6 +103,11 @@ pub enum EmitJobMode {
         wave_ratio: f32,
         num_waves: usize,
     },
+    /// Transactions arrive on a fixed schedule, independently of when previous ones complete,
+    /// and per-stage latencies are measured from the scheduled arrival.
+    OpenLoopTps {
+        tps: usize,
+    },
 }
 
 impl EmitJobMode {
@@ -143,6 +156,10 @@ pub struct EmitJobRequest {
     mode: EmitJobMode,
 
     transaction_mix_per_phase: Vec<Vec<(TransactionType, usize)>>,
+    // Relative duration of each phase, phases have equal duration if empty.
+    phase_durations: Vec<Duration>,
+    // Relative load of each phase, all phases run at full load if empty.
+    load_per_phase: Vec<usize>,
     account_type: AccountType,
     max_gas_per_txn: u64,
     init_max_gas_per_txn: Option<u64>,
@@ -183,6 +200,8 @@ impl Default for EmitJobRequest {
                 mempool_backlog: 3000,
             },
             transaction_mix_per_phase: vec![vec![(TransactionType::default(), 1)]],
+            phase_durations: Vec::new(),
+            load_per_phase: Vec::new(),
             account_type: AccountType::Local,
             max_gas_per_txn: aptos_global_constants::MAX_GAS_AMOUNT,
             gas_price: aptos_global_constants::GAS_UNIT_PRICE,
@@ -280,6 +299,26 @@ impl EmitJobRequest {
         self
     }
 
+    /// Takes the mode, phases and transaction mixes from a declarative workload.
+    /// The job is sized for the highest load across phases, and phases with lower
+    /// load only use a proportional subset of the workers.
+    pub fn workload(mut self, workload: &WorkloadSpec) -> Result<Self> {
+        workload.validate()?;
+        let load_per_phase = workload.load_per_phase();
+        let max_load = load_per_phase.iter().copied().max().unwrap_or(0);
+        self.mode = if workload.uses_target_tps() {
+            EmitJobMode::ConstTps { tps: max_load }
+        } else {
+            EmitJobMode::MaxLoad {
+                mempool_backlog: max_load,
+            }
+        };
+        self.transaction_mix_per_phase = workload.transaction_mix_per_phase()?;
+        self.phase_durations = workload.phase_durations();
+        self.load_per_phase = load_per_phase;
+        Ok(self)
+    }
+
     pub fn account_type(mut self, account_type: AccountType) -> Self {
         self.account_type = account_type;
         self
@@ -289,11 +328,51 @@ impl EmitJobRequest {
         self.transaction_mix_per_phase.len()
     }
 
+    /// Splits the total duration across phases, proportionally to their configured durations.
+    pub fn get_phase_durations(&self, duration: Duration) -> Vec<Duration> {
+        let phases = self.get_num_phases();
+        if self.phase_durations.len() != phases {
+            return vec![duration.checked_div(phases as u32).unwrap(); phases];
+        }
+        let total = self.phase_durations.iter().sum::<Duration>().as_secs_f64();
+        self.phase_durations
+            .iter()
+            .map(|phase_duration| duration.mul_f64(phase_duration.as_secs_f64() / total))
+            .collect()
+    }
+
+    /// Number of workers submitting transactions in each phase, out of num_workers.
+    /// Empty if all workers are always active.
+    pub fn get_active_workers_per_phase(&self, num_workers: usize) -> Vec<usize> {
+        let max_load = self.load_per_phase.iter().copied().max().unwrap_or(0);
+        if max_load == 0 {
+            return Vec::new();
+        }
+        self.load_per_phase
+            .iter()
+            .map(|load| (num_workers * load).div_ceil(max_load))
+            .collect()
+    }
+
     pub fn mode(mut self, mode: EmitJobMode) -> Self {
         self.mode = mode;
         self
     }
 
+    /// Switches a constant TPS mode to open-loop arrivals at the same TPS.
+    pub fn open_loop(mut self) -> Result<Self> {
+        self.mode = match self.mode {
+            EmitJobMode::ConstTps { tps } | EmitJobMode::OpenLoopTps { tps } => {
+                EmitJobMode::OpenLoopTps { tps }
+            },
+            _ => bail!(
+                "Open-loop mode requires a constant target TPS, got {:?}",
+                self.mode
+            ),
+        };
+        Ok(self)
+    }
+
     pub fn txn_expiration_time_secs(mut self, txn_expiration_time_secs: u64) -> Self {
         self.txn_expiration_time_secs = txn_expiration_time_secs;
         self
@@ -412,6 +491,7 @@ impl EmitJobRequest {
                     endpoints: clients_count,
                     check_account_sequence_only_once_fraction: 0.0,
                     check_account_sequence_sleep: self.latency_polling_interval,
+                    open_loop_arrival_interval: None,
                 }
             },
             EmitJobMode::ConstTps { tps }
@@ -501,6 +581,38 @@ impl EmitJobRequest {
                     endpoints: clients_count,
                     check_account_sequence_only_once_fraction: 1.0 - sample_latency_fraction,
                     check_account_sequence_sleep: self.latency_polling_interval,
+                    open_loop_arrival_interval: None,
+                }
+            },
+            EmitJobMode::OpenLoopTps { tps } => {
+                // Each worker owns a single account, and submits a transaction at every arrival,
+                // without waiting for the previous ones to commit. The load per account is capped,
+                // so that the transactions in flight stay well within the mempool per-account limit.
+                assert!(tps > 0, "TPS needs to be larger than 0");
+                let num_accounts = match self.num_accounts_mode {
+                    NumAccountsMode::NumAccounts(num_accounts) => num_accounts,
+                    NumAccountsMode::TransactionsPerAccount(_) => {
+                        tps.div_ceil(OPEN_LOOP_MAX_TPS_PER_ACCOUNT)
+                    },
+                };
+                let arrival_interval = Duration::from_secs_f64(num_accounts as f64 / tps as f64);
+
+                info!(
+                    " Transaction emitter targeting {} TPS open-loop, with {} clients and {} accounts, each sending every {:?}",
+                    tps, clients_count, num_accounts, arrival_interval
+                );
+
+                EmitModeParams {
+                    wait_millis: arrival_interval.as_millis() as u64,
+                    txn_expiration_time_secs: self.txn_expiration_time_secs,
+                    num_accounts,
+                    transactions_per_account: 1,
+                    max_submit_batch_size: 1,
+                    worker_offset_mode: WorkerOffsetMode::Spread,
+                    endpoints: clients_count,
+                    check_account_sequence_only_once_fraction: 0.0,
+                    check_account_sequence_sleep: self.latency_polling_interval,
+                    open_loop_arrival_interval: Some(arrival_interval),
                 }
             },
         }
@@ -674,6 +786,11 @@ impl TxnEmitter {
         stats_tracking_phases: usize,
     ) -> Result<EmitJob> {
         ensure!(req.gas_price > 0, "gas_price is required to be non zero");
+        ensure!(
+            req.load_per_phase.is_empty() || req.load_per_phase.len() == req.get_num_phases(),
+            "load_per_phase needs to be set for each of the {} phases",
+            req.get_num_phases()
+        );
 
         let mode_params = req.calculate_mode_params();
         let num_accounts = mode_params.num_accounts;
@@ -741,7 +858,7 @@ impl TxnEmitter {
             &init_txn_factory,
             stats.get_cur_phase_obj(),
         )
-        .await;
+        .await?;
 
         if !req.coordination_delay_between_instances.is_zero() {
             info!(
@@ -767,6 +884,7 @@ impl TxnEmitter {
         );
 
         let all_start_sleep_durations = mode_params.get_all_start_sleep_durations(self.from_rng());
+        let active_workers_per_phase = req.get_active_workers_per_phase(num_accounts);
 
         // Creating workers is slow with many workers (TODO check why)
         // so we create them all first, before starting them - so they start at the right time for
@@ -790,6 +908,10 @@ impl TxnEmitter {
                 txn_generator,
                 all_start_sleep_durations[worker_index],
                 check_account_sequence_only_once_for.contains(&worker_index),
+                active_workers_per_phase
+                    .iter()
+                    .map(|active_workers| worker_index < *active_workers)
+                    .collect(),
                 self.from_rng(),
             );
             submission_workers.push(worker);
@@ -821,6 +943,7 @@ impl TxnEmitter {
         print_stats_interval: Option<u64>,
     ) -> Result<TxnStats> {
         let phases = emit_job_request.transaction_mix_per_phase.len();
+        let phase_durations = emit_job_request.get_phase_durations(duration);
 
         let mut job = self
             .start_job(source_account, emit_job_request, phases)
@@ -831,16 +954,15 @@ impl TxnEmitter {
             phases
         );
 
-        let per_phase_duration = duration.checked_div(phases as u32).unwrap();
-        for phase in 0..phases {
+        for (phase, phase_duration) in phase_durations.into_iter().enumerate() {
             if phase > 0 {
                 info!("Starting next phase");
                 job.start_next_phase();
             }
             if let Some(interval_secs) = print_stats_interval {
-                job.periodic_stat(per_phase_duration, interval_secs).await;
+                job.periodic_stat(phase_duration, interval_secs).await;
             } else {
-                time::sleep(per_phase_duration).await;
+                time::sleep(phase_duration).await;
             }
         }
         info!("Ran for {} secs, stopping job...", duration.as_secs());
@@ -1,9 +1,15 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use anyhow::{Context, Result};
+use aptos_infallible::Mutex;
+use hdrhistogram::Histogram;
+use serde::{Deserialize, Serialize};
 use std::{
-    fmt,
+    collections::BTreeMap,
+    fmt, fs,
     ops::{Add, Sub},
+    path::Path,
     sync::{
         atomic::{AtomicU64, AtomicUsize, Ordering},
         Arc,
@@ -20,6 +26,7 @@ pub struct TxnStats {
     pub latency: u64,
     pub latency_samples: u64,
     pub latency_buckets: AtomicHistogramSnapshot,
+    pub stage_latencies: StageLatencySnapshot,
     pub lasted: Duration,
 }
 
@@ -72,6 +79,11 @@ impl TxnStats {
             p99_latency: self.latency_buckets.percentile(99, 100),
         }
     }
+
+    /// Percentiles of the per-stage latencies, only recorded in the open-loop mode.
+    pub fn latency_report(&self) -> LatencyReport {
+        self.stage_latencies.report()
+    }
 }
 
 impl fmt::Display for TxnStats {
@@ -96,6 +108,7 @@ impl Sub for &TxnStats {
             latency: self.latency - other.latency,
             latency_samples: self.latency_samples - other.latency_samples,
             latency_buckets: &self.latency_buckets - &other.latency_buckets,
+            stage_latencies: &self.stage_latencies - &other.stage_latencies,
             lasted: self.lasted - other.lasted,
         }
     }
@@ -113,6 +126,7 @@ impl Add for &TxnStats {
             latency: self.latency + other.latency,
             latency_samples: self.latency_samples + other.latency_samples,
             latency_buckets: &self.latency_buckets + &other.latency_buckets,
+            stage_latencies: &self.stage_latencies + &other.stage_latencies,
             lasted: self.lasted + other.lasted,
         }
     }
@@ -127,6 +141,7 @@ pub struct StatsAccumulator {
     pub latency: AtomicU64,
     pub latency_samples: AtomicU64,
     pub latencies: Arc<AtomicHistogramAccumulator>,
+    pub stage_latencies: StageLatencyAccumulator,
 }
 
 impl StatsAccumulator {
@@ -139,6 +154,7 @@ impl StatsAccumulator {
             latency: self.latency.load(Ordering::Relaxed),
             latency_samples: self.latency_samples.load(Ordering::Relaxed),
             latency_buckets: self.latencies.snapshot(),
+            stage_latencies: self.stage_latencies.snapshot(),
             lasted,
         }
     }
@@ -275,6 +291,202 @@ impl AtomicHistogramSnapshot {
     }
 }
 
+/// Stages of a transaction's life, as observed by the emitter.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LatencyStage {
+    /// From sending the transaction, until mempool accepted it.
+    SubmitToAccept,
+    /// From mempool accepting the transaction, until its commit was observed.
+    AcceptToCommit,
+    /// From when the transaction was scheduled to be sent, until its commit was observed.
+    /// Measuring from the schedule (rather than the send) avoids coordinated omission.
+    EndToEnd,
+}
+
+impl LatencyStage {
+    pub const ALL: [LatencyStage; 3] = [
+        LatencyStage::SubmitToAccept,
+        LatencyStage::AcceptToCommit,
+        LatencyStage::EndToEnd,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LatencyStage::SubmitToAccept => "submit_to_accept",
+            LatencyStage::AcceptToCommit => "accept_to_commit",
+            LatencyStage::EndToEnd => "end_to_end",
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            LatencyStage::SubmitToAccept => 0,
+            LatencyStage::AcceptToCommit => 1,
+            LatencyStage::EndToEnd => 2,
+        }
+    }
+}
+
+// latencies are recorded in milliseconds, up to an hour, with 3 significant digits.
+const STAGE_HISTOGRAM_MAX_MILLIS: u64 = 3_600_000;
+const STAGE_HISTOGRAM_SIGNIFICANT_DIGITS: u8 = 3;
+
+fn new_stage_histogram() -> Histogram<u64> {
+    Histogram::new_with_bounds(
+        1,
+        STAGE_HISTOGRAM_MAX_MILLIS,
+        STAGE_HISTOGRAM_SIGNIFICANT_DIGITS,
+    )
+    .expect("Histogram bounds must be valid")
+}
+
+/// HDR histograms of the latency of each stage.
+#[derive(Debug)]
+pub struct StageLatencyAccumulator {
+    histograms: Vec<Mutex<Histogram<u64>>>,
+}
+
+impl Default for StageLatencyAccumulator {
+    fn default() -> Self {
+        Self {
+            histograms: LatencyStage::ALL
+                .iter()
+                .map(|_| Mutex::new(new_stage_histogram()))
+                .collect(),
+        }
+    }
+}
+
+impl StageLatencyAccumulator {
+    pub fn record(&self, stage: LatencyStage, latency: Duration) {
+        self.histograms[stage.index()]
+            .lock()
+            .saturating_record(latency.as_millis() as u64);
+    }
+
+    pub fn snapshot(&self) -> StageLatencySnapshot {
+        StageLatencySnapshot {
+            histograms: self
+                .histograms
+                .iter()
+                .map(|histogram| histogram.lock().clone())
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StageLatencySnapshot {
+    histograms: Vec<Histogram<u64>>,
+}
+
+impl Default for StageLatencySnapshot {
+    fn default() -> Self {
+        StageLatencyAccumulator::default().snapshot()
+    }
+}
+
+impl Sub for &StageLatencySnapshot {
+    type Output = StageLatencySnapshot;
+
+    fn sub(self, other: &StageLatencySnapshot) -> StageLatencySnapshot {
+        let mut result = self.clone();
+        for (histogram, other) in result.histograms.iter_mut().zip(other.histograms.iter()) {
+            histogram
+                .subtract(other)
+                .expect("Subtracted histogram must be an earlier snapshot");
+        }
+        result
+    }
+}
+
+impl Add for &StageLatencySnapshot {
+    type Output = StageLatencySnapshot;
+
+    fn add(self, other: &StageLatencySnapshot) -> StageLatencySnapshot {
+        let mut result = self.clone();
+        for (histogram, other) in result.histograms.iter_mut().zip(other.histograms.iter()) {
+            histogram
+                .add(other)
+                .expect("Histograms must have the same bounds");
+        }
+        result
+    }
+}
+
+impl StageLatencySnapshot {
+    /// Returns None if no latency was recorded for the stage.
+    pub fn percentiles(&self, stage: LatencyStage) -> Option<LatencyPercentiles> {
+        let histogram = &self.histograms[stage.index()];
+        if histogram.is_empty() {
+            return None;
+        }
+        Some(LatencyPercentiles {
+            samples: histogram.len(),
+            mean_ms: histogram.mean(),
+            p50_ms: histogram.value_at_quantile(0.5),
+            p90_ms: histogram.value_at_quantile(0.9),
+            p99_ms: histogram.value_at_quantile(0.99),
+            p999_ms: histogram.value_at_quantile(0.999),
+            max_ms: histogram.max(),
+        })
+    }
+
+    pub fn report(&self) -> LatencyReport {
+        LatencyReport {
+            stages: LatencyStage::ALL
+                .iter()
+                .filter_map(|stage| self.percentiles(*stage).map(|p| (*stage, p)))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct LatencyPercentiles {
+    pub samples: u64,
+    pub mean_ms: f64,
+    pub p50_ms: u64,
+    pub p90_ms: u64,
+    pub p99_ms: u64,
+    pub p999_ms: u64,
+    pub max_ms: u64,
+}
+
+impl fmt::Display for LatencyPercentiles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "p50: {} ms, p90: {} ms, p99: {} ms, p99.9: {} ms, max: {} ms, samples: {}",
+            self.p50_ms, self.p90_ms, self.p99_ms, self.p999_ms, self.max_ms, self.samples,
+        )
+    }
+}
+
+/// Per-stage latency percentiles, exported as a JSON artifact.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct LatencyReport {
+    pub stages: BTreeMap<LatencyStage, LatencyPercentiles>,
+}
+
+impl LatencyReport {
+    pub fn write_json(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json)
+            .with_context(|| format!("Failed to write latency report to {:?}", path))
+    }
+}
+
+impl fmt::Display for LatencyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (stage, percentiles) in &self.stages {
+            writeln!(f, "{:?} latency: {}", stage, percentiles)?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 pub struct DynamicStatsTracking {
     num_phases: usize,
@@ -335,8 +547,8 @@ impl DynamicStatsTracking {
 #[cfg(test)]
 mod test {
     use crate::emitter::stats::{
-        AtomicHistogramAccumulator, AtomicHistogramSnapshot, TxnStats, DEFAULT_HISTOGRAM_CAPACITY,
-        DEFAULT_HISTOGRAM_STEP_WIDTH,
+        AtomicHistogramAccumulator, AtomicHistogramSnapshot, LatencyStage, StageLatencyAccumulator,
+        TxnStats, DEFAULT_HISTOGRAM_CAPACITY, DEFAULT_HISTOGRAM_STEP_WIDTH,
     };
     use std::time::Duration;
 
@@ -405,9 +617,37 @@ mod test {
             latency: 0,
             latency_samples: 0,
             latency_buckets: histogram.snapshot(),
+            stage_latencies: Default::default(),
             lasted: Duration::from_secs(10),
         };
         let res = stat.latency_buckets.percentile(9, 10);
         assert_eq!(res, 900);
     }
+
+    #[test]
+    pub fn test_stage_latencies() {
+        let accumulator = StageLatencyAccumulator::default();
+        for i in 1..=100 {
+            accumulator.record(LatencyStage::EndToEnd, Duration::from_millis(i * 10));
+        }
+        accumulator.record(LatencyStage::SubmitToAccept, Duration::from_millis(5));
+        let first = accumulator.snapshot();
+        accumulator.record(LatencyStage::SubmitToAccept, Duration::from_millis(7));
+        let second = accumulator.snapshot();
+
+        let report = second.report();
+        assert!(!report.stages.contains_key(&LatencyStage::AcceptToCommit));
+        let end_to_end = &report.stages[&LatencyStage::EndToEnd];
+        assert_eq!(end_to_end.samples, 100);
+        assert_eq!(end_to_end.p50_ms, 500);
+        assert_eq!(end_to_end.max_ms, 1000);
+
+        let delta = (&second - &first).report();
+        assert!(!delta.stages.contains_key(&LatencyStage::EndToEnd));
+        assert_eq!(delta.stages[&LatencyStage::SubmitToAccept].samples, 1);
+        assert_eq!(delta.stages[&LatencyStage::SubmitToAccept].max_ms, 7);
+
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(json.contains("end_to_end"));
+    }
 }
@@ -3,13 +3,14 @@
 
 use crate::{
     emitter::{
-        stats::{DynamicStatsTracking, StatsAccumulator},
+        query_sequence_numbers,
+        stats::{DynamicStatsTracking, LatencyStage, StatsAccumulator},
         update_seq_num_and_get_num_expired, wait_for_accounts_sequence,
     },
     EmitModeParams,
 };
 use aptos_logger::{info, sample, sample::SampleRate, warn};
-use aptos_rest_client::Client as RestClient;
+use aptos_rest_client::{error::RestError, Client as RestClient};
 use aptos_sdk::{
     move_types::account_address::AccountAddress,
     types::{transaction::SignedTransaction, vm_status::StatusCode, LocalAccount},
@@ -21,15 +22,32 @@ use core::{
     sync::atomic::{AtomicBool, Ordering},
     time::Duration,
 };
-use futures::future::join_all;
+use futures::{
+    future::{join_all, BoxFuture},
+    stream::FuturesUnordered,
+    FutureExt, StreamExt,
+};
 use itertools::Itertools;
 use rand::seq::IteratorRandom;
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap, HashSet},
     sync::{atomic::AtomicU64, Arc},
-    time::Instant,
+    time::{Instant, SystemTime, UNIX_EPOCH},
 };
-use tokio::time::sleep;
+use tokio::time::{sleep, sleep_until};
+
+// Grace period after expiration, before an uncommitted open-loop transaction is counted as expired.
+const OPEN_LOOP_EXPIRATION_GRACE_SECS: u64 = 5;
+
+/// A transaction sent by the open-loop worker, whose commit hasn't been observed yet.
+struct OpenLoopTxn {
+    arrival: Instant,
+    sent: Instant,
+    accepted: Option<Instant>,
+    expiration_timestamp_secs: u64,
+}
+
+type OpenLoopSubmission = BoxFuture<'static, ((AccountAddress, u64), Result<(), RestError>)>;
 
 pub struct SubmissionWorker {
     pub(crate) accounts: Vec<LocalAccount>,
@@ -40,6 +58,8 @@ pub struct SubmissionWorker {
     txn_generator: Box<dyn TransactionGenerator>,
     start_sleep_duration: Duration,
     skip_latency_stats: bool,
+    // Whether the worker submits in each phase, always active if empty.
+    active_per_phase: Vec<bool>,
     rng: ::rand::rngs::StdRng,
 }
 
@@ -53,6 +73,7 @@ impl SubmissionWorker {
         txn_generator: Box<dyn TransactionGenerator>,
         start_sleep_duration: Duration,
         skip_latency_stats: bool,
+        active_per_phase: Vec<bool>,
         rng: ::rand::rngs::StdRng,
     ) -> Self {
         Self {
@@ -64,12 +85,24 @@ impl SubmissionWorker {
             txn_generator,
             start_sleep_duration,
             skip_latency_stats,
+            active_per_phase,
             rng,
         }
     }
 
+    fn is_active_in_cur_phase(&self) -> bool {
+        self.active_per_phase
+            .get(self.stats.get_cur_phase())
+            .copied()
+            .unwrap_or(true)
+    }
+
     #[allow(clippy::collapsible_if)]
     pub(crate) async fn run(mut self, start_instant: Instant) -> Vec<LocalAccount> {
+        if let Some(arrival_interval) = self.params.open_loop_arrival_interval {
+            return self.run_open_loop(start_instant, arrival_interval).await;
+        }
+
         let mut wait_until = start_instant + self.start_sleep_duration;
 
         let now = Instant::now();
@@ -98,7 +131,12 @@ impl SubmissionWorker {
             // always add expected cycle duration, to not drift from expected pace.
             wait_until += wait_duration;
 
-            let requests = self.gen_requests();
+            let active = self.is_active_in_cur_phase();
+            let requests = if active {
+                self.gen_requests()
+            } else {
+                Vec::new()
+            };
             if !requests.is_empty() {
                 let mut account_to_start_and_end_seq_num = HashMap::new();
                 for req in requests.iter() {
@@ -196,12 +234,209 @@ impl SubmissionWorker {
             let now = Instant::now();
             if wait_until > now {
                 self.sleep_check_done(wait_until - now).await;
+            } else if !active {
+                // don't spin while waiting for a phase this worker is active in
+                self.sleep_check_done(self.params.check_account_sequence_sleep)
+                    .await;
             }
         }
 
         self.accounts
     }
 
+    /// Sends transactions at fixed arrivals, regardless of whether previous ones completed,
+    /// and measures latencies from the scheduled arrival. This way, a slow system can't
+    /// hide its latency by slowing down the emitter (coordinated omission).
+    async fn run_open_loop(
+        mut self,
+        start_instant: Instant,
+        arrival_interval: Duration,
+    ) -> Vec<LocalAccount> {
+        let poll_interval = self.params.check_account_sequence_sleep;
+        let mut next_arrival = start_instant + self.start_sleep_duration;
+        let mut next_poll = Instant::now() + poll_interval;
+        let mut submissions = FuturesUnordered::<OpenLoopSubmission>::new();
+        let mut in_flight = BTreeMap::new();
+
+        while !self.stop.load(Ordering::Relaxed) {
+            tokio::select! {
+                _ = sleep_until(next_arrival.into()) => {
+                    // Arrivals stay on schedule, even if the worker fell behind.
+                    let arrival = next_arrival;
+                    next_arrival += arrival_interval;
+                    if self.is_active_in_cur_phase() {
+                        self.submit_open_loop(arrival, &mut submissions, &mut in_flight);
+                    }
+                },
+                Some((key, result)) = submissions.next() => {
+                    self.handle_open_loop_submission(key, result, &mut in_flight);
+                },
+                _ = sleep_until(next_poll.into()) => {
+                    self.poll_open_loop_commits(&mut in_flight).await;
+                    next_poll = Instant::now() + poll_interval;
+                },
+            }
+        }
+
+        // Account for the transactions still in flight, before returning the accounts.
+        while let Some((key, result)) = submissions.next().await {
+            self.handle_open_loop_submission(key, result, &mut in_flight);
+        }
+        self.poll_open_loop_commits(&mut in_flight).await;
+
+        self.accounts
+    }
+
+    fn submit_open_loop(
+        &mut self,
+        arrival: Instant,
+        submissions: &mut FuturesUnordered<OpenLoopSubmission>,
+        in_flight: &mut BTreeMap<(AccountAddress, u64), OpenLoopTxn>,
+    ) {
+        let txns = self
+            .txn_generator
+            .generate_transactions(&self.accounts[0], 1);
+        self.stats
+            .get_cur()
+            .submitted
+            .fetch_add(txns.len() as u64, Ordering::Relaxed);
+        for txn in txns {
+            let key = (txn.sender(), txn.sequence_number());
+            in_flight.insert(key, OpenLoopTxn {
+                arrival,
+                sent: Instant::now(),
+                accepted: None,
+                expiration_timestamp_secs: txn.expiration_timestamp_secs(),
+            });
+            let client = self.client.clone();
+            submissions.push(
+                async move {
+                    let result = client.submit_bcs(&txn).await.map(|_| ());
+                    (key, result)
+                }
+                .boxed(),
+            );
+        }
+    }
+
+    fn handle_open_loop_submission(
+        &self,
+        key: (AccountAddress, u64),
+        result: Result<(), RestError>,
+        in_flight: &mut BTreeMap<(AccountAddress, u64), OpenLoopTxn>,
+    ) {
+        let stats = self.stats.get_cur();
+        match result {
+            Ok(()) => {
+                if let Some(txn) = in_flight.get_mut(&key) {
+                    let accepted = Instant::now();
+                    stats
+                        .stage_latencies
+                        .record(LatencyStage::SubmitToAccept, accepted - txn.sent);
+                    txn.accepted = Some(accepted);
+                }
+            },
+            Err(e) => {
+                // Later transactions of the sender will expire, which resyncs its sequence number.
+                in_flight.remove(&key);
+                stats.failed_submission.fetch_add(1, Ordering::Relaxed);
+                sample!(
+                    SampleRate::Duration(Duration::from_secs(60)),
+                    warn!(
+                        "[{:?}] Failed to submit open-loop transaction: {:?}",
+                        self.client.path_prefix_string(),
+                        e
+                    )
+                );
+            },
+        }
+    }
+
+    async fn poll_open_loop_commits(
+        &mut self,
+        in_flight: &mut BTreeMap<(AccountAddress, u64), OpenLoopTxn>,
+    ) {
+        if in_flight.is_empty() {
+            return;
+        }
+        let senders = in_flight
+            .keys()
+            .map(|(sender, _)| *sender)
+            .collect::<HashSet<_>>();
+        let sequence_numbers = match query_sequence_numbers(&self.client, senders.iter()).await {
+            Ok((sequence_numbers, _)) => sequence_numbers.into_iter().collect::<HashMap<_, _>>(),
+            Err(e) => {
+                sample!(
+                    SampleRate::Duration(Duration::from_secs(60)),
+                    warn!(
+                        "[{:?}] Failed to query sequence numbers: {:?}",
+                        self.client.path_prefix_string(),
+                        e
+                    )
+                );
+                return;
+            },
+        };
+
+        let now = Instant::now();
+        let now_secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let stats = self.stats.get_cur();
+        let mut num_expired = 0;
+        let mut resync_senders = HashSet::new();
+        in_flight.retain(|(sender, sequence_number), txn| {
+            let Some(committed) = sequence_numbers.get(sender) else {
+                return true;
+            };
+            if sequence_number < committed {
+                let end_to_end = now - txn.arrival;
+                stats.committed.fetch_add(1, Ordering::Relaxed);
+                stats
+                    .latency
+                    .fetch_add(end_to_end.as_millis() as u64, Ordering::Relaxed);
+                stats.latency_samples.fetch_add(1, Ordering::Relaxed);
+                stats
+                    .latencies
+                    .record_data_point(end_to_end.as_millis() as u64, 1);
+                stats
+                    .stage_latencies
+                    .record(LatencyStage::EndToEnd, end_to_end);
+                if let Some(accepted) = txn.accepted {
+                    stats
+                        .stage_latencies
+                        .record(LatencyStage::AcceptToCommit, now - accepted);
+                }
+                false
+            } else if txn.expiration_timestamp_secs + OPEN_LOOP_EXPIRATION_GRACE_SECS < now_secs
+                || resync_senders.contains(sender)
+            {
+                // Once a transaction expired, the ones after it can't commit anymore either.
+                resync_senders.insert(*sender);
+                num_expired += 1;
+                false
+            } else {
+                true
+            }
+        });
+
+        if num_expired > 0 {
+            stats.expired.fetch_add(num_expired, Ordering::Relaxed);
+            self.txn_generator.resync_expired_senders(
+                &resync_senders
+                    .iter()
+                    .map(|sender| (*sender, sequence_numbers[sender]))
+                    .collect(),
+            );
+        }
+        for account in &self.accounts {
+            if resync_senders.contains(&account.address()) {
+                account.set_sequence_number(sequence_numbers[&account.address()]);
+            }
+        }
+    }
+
     // returns true if it returned early
     async fn sleep_check_done(&self, duration: Duration) {
         let start_time = Instant::now();
@@ -243,6 +478,20 @@ impl SubmissionWorker {
             )
             .await;
 
+        // Senders of expired transactions, some of which the generator may sign for itself.
+        let expired_senders: HashMap<_, _> = account_to_start_and_end_seq_num
+            .iter()
+            .filter_map(|(address, (_, end_seq_num))| {
+                latest_fetched_counts
+                    .get(address)
+                    .filter(|count| *count < end_seq_num)
+                    .map(|count| (*address, *count))
+            })
+            .collect();
+        if !expired_senders.is_empty() {
+            self.txn_generator.resync_expired_senders(&expired_senders);
+        }
+
         let (num_committed, num_expired) = update_seq_num_and_get_num_expired(
             &mut self.accounts,
             account_to_start_and_end_seq_num,
@@ -0,0 +1,54 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, Result};
+use aptos_logger::{info, sample, sample::SampleRate};
+use aptos_rest_client::Client as RestClient;
+use aptos_transaction_generator_lib::replay::TransactionRecording;
+use std::time::Duration;
+
+/// Maximum page size of the transactions API.
+const TRANSACTIONS_PAGE_SIZE: u16 = 100;
+
+/// Fetches num_versions transactions starting at start_version, and records the
+/// ones that can be replayed against another network.
+pub async fn record_transactions(
+    client: &RestClient,
+    start_version: u64,
+    num_versions: u64,
+) -> Result<TransactionRecording> {
+    let end_version = start_version + num_versions;
+    let mut transactions = Vec::with_capacity(num_versions as usize);
+    let mut version = start_version;
+    while version < end_version {
+        let limit = (end_version - version).min(TRANSACTIONS_PAGE_SIZE as u64) as u16;
+        let page = client
+            .get_transactions_bcs(Some(version), Some(limit))
+            .await?
+            .into_inner();
+        ensure!(
+            !page.is_empty(),
+            "No transactions available at version {}",
+            version
+        );
+        version += page.len() as u64;
+        transactions.extend(page.into_iter().map(|txn| txn.transaction));
+        sample!(
+            SampleRate::Duration(Duration::from_secs(10)),
+            info!(
+                "Fetched {} out of {} transactions",
+                transactions.len(),
+                num_versions
+            )
+        );
+    }
+
+    let recording = TransactionRecording::from_transactions(start_version, &transactions);
+    info!(
+        "Recorded {} replayable transactions from {} senders, spanning {:?}",
+        recording.transactions.len(),
+        recording.senders().len(),
+        recording.duration()
+    );
+    Ok(recording)
+}
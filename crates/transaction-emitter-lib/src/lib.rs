@@ -10,12 +10,16 @@ mod instance;
 mod wrappers;
 
 // These are the top level things you should need to run the emitter.
-pub use args::{ClusterArgs, CoinSourceArgs, CreateAccountsArgs, EmitArgs};
+pub use args::{ClusterArgs, CoinSourceArgs, CreateAccountsArgs, EmitArgs, RecordTransactionsArgs};
 // We export these if you want finer grained control.
 pub use cluster::Cluster;
 pub use emitter::{
     query_sequence_number, query_sequence_numbers,
-    stats::{TxnStats, TxnStatsRate},
+    stats::{LatencyPercentiles, LatencyReport, LatencyStage, TxnStats, TxnStatsRate},
+    transaction_recorder::record_transactions,
     EmitJob, EmitJobMode, EmitJobRequest, EmitModeParams, TxnEmitter,
 };
-pub use wrappers::{create_accounts_command, emit_transactions, emit_transactions_with_cluster};
+pub use wrappers::{
+    create_accounts_command, emit_transactions, emit_transactions_with_cluster,
+    record_transactions_command,
+};
@@ -2,11 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    args::{ClusterArgs, EmitArgs},
+    args::{ClusterArgs, EmitArgs, RecordTransactionsArgs},
     cluster::Cluster,
     emitter::{
         create_accounts, local_account_generator::PrivateKeyAccountGenerator, parse_seed,
-        stats::TxnStats, EmitJobMode, EmitJobRequest, NumAccountsMode, TxnEmitter,
+        stats::TxnStats, transaction_recorder::record_transactions, EmitJobMode, EmitJobRequest,
+        NumAccountsMode, TxnEmitter,
     },
     instance::Instance,
     CreateAccountsArgs,
@@ -14,10 +15,18 @@ use crate::{
 use anyhow::{bail, Context, Result};
 use aptos_config::config::DEFAULT_MAX_SUBMIT_TRANSACTION_BATCH_SIZE;
 use aptos_logger::{error, info};
+use aptos_rest_client::Client as RestClient;
 use aptos_sdk::transaction_builder::TransactionFactory;
-use aptos_transaction_generator_lib::{args::TransactionTypeArg, WorkflowProgress};
+use aptos_transaction_generator_lib::{
+    args::TransactionTypeArg, replay::TransactionRecording, workload::WorkloadSpec,
+    TransactionType, WorkflowProgress,
+};
 use rand::{rngs::StdRng, Rng, SeedableRng};
-use std::time::{Duration, Instant};
+use std::{
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 pub async fn emit_transactions(
     cluster_args: &ClusterArgs,
@@ -70,9 +79,15 @@ pub async fn emit_transactions_with_cluster(
     cluster: &Cluster,
     args: &EmitArgs,
 ) -> Result<TxnStats> {
-    let emitter_mode = EmitJobMode::create(args.mempool_backlog, args.target_tps);
-
-    let duration = Duration::from_secs(args.duration);
+    let workload = args
+        .workload_file
+        .as_ref()
+        .map(|path| WorkloadSpec::load(Path::new(path)))
+        .transpose()?;
+    let duration = match &workload {
+        Some(workload) => workload.total_duration(),
+        None => Duration::from_secs(args.duration),
+    };
     let client = cluster.random_instance().rest_client();
     let coin_source_account = cluster.load_coin_source_account(&client).await?;
     let emitter = TxnEmitter::new(
@@ -82,22 +97,38 @@ pub async fn emit_transactions_with_cluster(
         StdRng::from_entropy(),
     );
 
-    let transaction_mix_per_phase = TransactionTypeArg::args_to_transaction_mix_per_phase(
-        &args.transaction_type,
-        &args.transaction_weights,
-        &args.transaction_phases,
-        args.module_working_set_size.unwrap_or(1),
-        args.sender_use_account_pool.unwrap_or(false),
-        WorkflowProgress::when_done_default(),
-    );
     let mut emit_job_request =
-        EmitJobRequest::new(cluster.all_instances().map(Instance::rest_client).collect())
-            .mode(emitter_mode)
-            .transaction_mix_per_phase(transaction_mix_per_phase)
-            .txn_expiration_time_secs(args.txn_expiration_time_secs)
-            .coordination_delay_between_instances(Duration::from_secs(
-                args.coordination_delay_between_instances.unwrap_or(0),
-            ));
+        EmitJobRequest::new(cluster.all_instances().map(Instance::rest_client).collect());
+    emit_job_request = match &workload {
+        Some(workload) => emit_job_request.workload(workload)?,
+        None => emit_job_request
+            .mode(EmitJobMode::create(args.mempool_backlog, args.target_tps))
+            .transaction_mix_per_phase(match &args.replay_file {
+                Some(path) => vec![vec![(
+                    TransactionType::Replay {
+                        recording: Arc::new(TransactionRecording::load(Path::new(path))?),
+                        speedup: args.replay_speedup.unwrap_or(1.0),
+                    },
+                    1,
+                )]],
+                None => TransactionTypeArg::args_to_transaction_mix_per_phase(
+                    &args.transaction_type,
+                    &args.transaction_weights,
+                    &args.transaction_phases,
+                    args.module_working_set_size.unwrap_or(1),
+                    args.sender_use_account_pool.unwrap_or(false),
+                    WorkflowProgress::when_done_default(),
+                ),
+            }),
+    };
+    if args.open_loop {
+        emit_job_request = emit_job_request.open_loop()?;
+    }
+    emit_job_request = emit_job_request
+        .txn_expiration_time_secs(args.txn_expiration_time_secs)
+        .coordination_delay_between_instances(Duration::from_secs(
+            args.coordination_delay_between_instances.unwrap_or(0),
+        ));
 
     let num_accounts =
         NumAccountsMode::create(args.num_accounts, args.max_transactions_per_account);
@@ -162,9 +193,17 @@ pub async fn emit_transactions_with_cluster(
             &coin_source_account,
             emit_job_request,
             duration,
-            (args.duration / 10).clamp(1, 10),
+            (duration.as_secs() / 10).clamp(1, 10),
         )
         .await?;
+
+    let latency_report = stats.latency_report();
+    if !latency_report.stages.is_empty() {
+        info!("Per-stage latencies:\n{}", latency_report);
+    }
+    if let Some(path) = &args.latency_report_file {
+        latency_report.write_json(Path::new(path))?;
+    }
     Ok(stats)
 }
 
@@ -207,3 +246,15 @@ pub async fn create_accounts_command(
     .await?;
     Ok(())
 }
+
+pub async fn record_transactions_command(args: &RecordTransactionsArgs) -> Result<()> {
+    let client = RestClient::new(args.rest_url.clone());
+    let recording = record_transactions(&client, args.start_version, args.num_versions).await?;
+    recording.save(Path::new(&args.output_file))?;
+    info!(
+        "Wrote {} transactions to {}",
+        recording.transactions.len(),
+        args.output_file
+    );
+    Ok(())
+}
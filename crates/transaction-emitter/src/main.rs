@@ -7,7 +7,8 @@ mod diag;
 use anyhow::{Context, Result};
 use aptos_logger::{Level, Logger};
 use aptos_transaction_emitter_lib::{
-    create_accounts_command, emit_transactions, Cluster, ClusterArgs, CreateAccountsArgs, EmitArgs,
+    create_accounts_command, emit_transactions, record_transactions_command, Cluster, ClusterArgs,
+    CreateAccountsArgs, EmitArgs, RecordTransactionsArgs,
 };
 use clap::{Parser, Subcommand};
 use diag::diag;
@@ -28,6 +29,10 @@ enum TxnEmitterCommand {
     /// Create test accounts, for use with EmitTx
     CreateAccounts(CreateAccounts),
 
+    /// Record a range of transactions of a network (e.g. mainnet), to be replayed
+    /// against a test network with EmitTx --replay-file
+    RecordTransactions(RecordTransactionsArgs),
+
     /// This runs the transaction emitter in diag mode, where the focus is on
     /// FullNodes instead of ValidatorNodes. This performs a simple health check.
     Diag(Diag),
@@ -91,6 +96,12 @@ pub async fn main() -> Result<()> {
                 .unwrap();
             Ok(())
         },
+        TxnEmitterCommand::RecordTransactions(args) => {
+            record_transactions_command(&args)
+                .await
+                .context("Record transactions failed")?;
+            Ok(())
+        },
         TxnEmitterCommand::Diag(args) => {
             let cluster = Cluster::try_from_cluster_args(&args.cluster_args)
                 .await
@@ -2,9 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{ObjectPool, TransactionGenerator, TransactionGeneratorCreator};
-use aptos_sdk::types::{transaction::SignedTransaction, LocalAccount};
+use aptos_sdk::{
+    move_types::account_address::AccountAddress,
+    types::{transaction::SignedTransaction, LocalAccount},
+};
 use rand::{rngs::StdRng, SeedableRng};
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 /// Wrapper that allows inner transaction generator to have unique accounts
 /// for all transactions (instead of having 5-20 transactions per account, as default)
@@ -56,6 +59,14 @@ impl TransactionGenerator for AccountsPoolWrapperGenerator {
         }
         txns
     }
+
+    fn resync_expired_senders(
+        &mut self,
+        committed_sequence_numbers: &HashMap<AccountAddress, u64>,
+    ) {
+        self.generator
+            .resync_expired_senders(committed_sequence_numbers)
+    }
 }
 
 pub struct AccountsPoolWrapperCreator {
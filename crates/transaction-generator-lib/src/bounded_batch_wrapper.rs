@@ -2,7 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{TransactionGenerator, TransactionGeneratorCreator};
-use aptos_sdk::types::{transaction::SignedTransaction, LocalAccount};
+use aptos_sdk::{
+    move_types::account_address::AccountAddress,
+    types::{transaction::SignedTransaction, LocalAccount},
+};
+use std::collections::HashMap;
 
 struct BoundedBatchWrapperTransactionGenerator {
     batch_size: usize,
@@ -18,6 +22,14 @@ impl TransactionGenerator for BoundedBatchWrapperTransactionGenerator {
         self.generator
             .generate_transactions(account, num_to_create.min(self.batch_size))
     }
+
+    fn resync_expired_senders(
+        &mut self,
+        committed_sequence_numbers: &HashMap<AccountAddress, u64>,
+    ) {
+        self.generator
+            .resync_expired_senders(committed_sequence_numbers)
+    }
 }
 
 pub struct BoundedBatchWrapperTransactionGeneratorCreator {
@@ -0,0 +1,245 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{TransactionGenerator, TransactionGeneratorCreator};
+use anyhow::{bail, Context, Result};
+use aptos_sdk::{
+    bcs,
+    move_types::{
+        account_address::AccountAddress,
+        identifier::Identifier,
+        language_storage::{ModuleId, TypeTag},
+        parser::parse_type_tag,
+    },
+    transaction_builder::TransactionFactory,
+    types::{
+        transaction::{EntryFunction, SignedTransaction, TransactionPayload},
+        LocalAccount,
+    },
+};
+use rand::{
+    distributions::{Alphanumeric, Uniform},
+    rngs::StdRng,
+    Rng, SeedableRng,
+};
+use serde::{Deserialize, Serialize};
+use std::{str::FromStr, sync::Arc};
+
+/// Generator for a single argument of a custom entry function call.
+/// Integer ranges are inclusive, so a constant is expressed with `min == max`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ArgumentGenerator {
+    /// A fixed value, or a random one if no value is given.
+    Bool {
+        value: Option<bool>,
+    },
+    U8 {
+        min: u8,
+        max: u8,
+    },
+    U64 {
+        min: u64,
+        max: u64,
+    },
+    U128 {
+        min: u128,
+        max: u128,
+    },
+    Address {
+        value: AccountAddress,
+    },
+    /// Address of the account sending the transaction.
+    Sender,
+    /// Address that (most likely) doesn't exist on chain yet.
+    RandomAddress,
+    /// Random `vector<u8>` of the given length.
+    Bytes {
+        length: usize,
+    },
+    /// Random alphanumeric `String` of the given length.
+    String {
+        length: usize,
+    },
+}
+
+impl ArgumentGenerator {
+    fn validate(&self) -> Result<()> {
+        let valid = match self {
+            ArgumentGenerator::U8 { min, max } => min <= max,
+            ArgumentGenerator::U64 { min, max } => min <= max,
+            ArgumentGenerator::U128 { min, max } => min <= max,
+            _ => true,
+        };
+        if !valid {
+            bail!("Argument {:?} has min larger than max", self);
+        }
+        Ok(())
+    }
+
+    /// Returns the BCS serialized argument.
+    pub fn generate(&self, sender: AccountAddress, rng: &mut StdRng) -> Vec<u8> {
+        match self {
+            ArgumentGenerator::Bool { value } => {
+                bcs::to_bytes(&value.unwrap_or_else(|| rng.gen::<bool>()))
+            },
+            ArgumentGenerator::U8 { min, max } => {
+                bcs::to_bytes(&rng.sample(Uniform::new_inclusive(*min, *max)))
+            },
+            ArgumentGenerator::U64 { min, max } => {
+                bcs::to_bytes(&rng.sample(Uniform::new_inclusive(*min, *max)))
+            },
+            ArgumentGenerator::U128 { min, max } => {
+                bcs::to_bytes(&rng.sample(Uniform::new_inclusive(*min, *max)))
+            },
+            ArgumentGenerator::Address { value } => bcs::to_bytes(value),
+            ArgumentGenerator::Sender => bcs::to_bytes(&sender),
+            ArgumentGenerator::RandomAddress => bcs::to_bytes(&AccountAddress::new(rng.gen())),
+            ArgumentGenerator::Bytes { length } => {
+                bcs::to_bytes(&(0..*length).map(|_| rng.gen::<u8>()).collect::<Vec<_>>())
+            },
+            ArgumentGenerator::String { length } => bcs::to_bytes(
+                &(0..*length)
+                    .map(|_| rng.sample(Alphanumeric))
+                    .collect::<String>(),
+            ),
+        }
+        .expect("Argument serialization must succeed")
+    }
+}
+
+/// A parsed entry function call, with generators for its arguments.
+#[derive(Debug)]
+pub struct EntryFunctionTemplate {
+    module: ModuleId,
+    function: Identifier,
+    ty_args: Vec<TypeTag>,
+    args: Vec<ArgumentGenerator>,
+}
+
+impl EntryFunctionTemplate {
+    /// Creates a template from a fully qualified function name, e.g. `0x1::aptos_account::transfer`,
+    /// and type arguments, e.g. `0x1::aptos_coin::AptosCoin`.
+    pub fn new(function: &str, ty_args: &[String], args: Vec<ArgumentGenerator>) -> Result<Self> {
+        let parts = function.split("::").collect::<Vec<_>>();
+        let [address, module, name] = parts[..] else {
+            bail!(
+                "Entry function {} must be of the form <address>::<module>::<function>",
+                function
+            );
+        };
+        let address = AccountAddress::from_str(address)
+            .with_context(|| format!("Invalid address in entry function {}", function))?;
+        let module = ModuleId::new(address, Identifier::new(module)?);
+        let function = Identifier::new(name)?;
+        let ty_args = ty_args
+            .iter()
+            .map(|ty_arg| {
+                parse_type_tag(ty_arg).with_context(|| format!("Invalid type argument {}", ty_arg))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        for arg in &args {
+            arg.validate()?;
+        }
+        Ok(Self {
+            module,
+            function,
+            ty_args,
+            args,
+        })
+    }
+
+    pub fn create_payload(&self, sender: AccountAddress, rng: &mut StdRng) -> TransactionPayload {
+        TransactionPayload::EntryFunction(EntryFunction::new(
+            self.module.clone(),
+            self.function.clone(),
+            self.ty_args.clone(),
+            self.args
+                .iter()
+                .map(|arg| arg.generate(sender, rng))
+                .collect(),
+        ))
+    }
+}
+
+pub struct CustomEntryFunctionGenerator {
+    rng: StdRng,
+    txn_factory: TransactionFactory,
+    template: Arc<EntryFunctionTemplate>,
+}
+
+impl TransactionGenerator for CustomEntryFunctionGenerator {
+    fn generate_transactions(
+        &mut self,
+        account: &LocalAccount,
+        num_to_create: usize,
+    ) -> Vec<SignedTransaction> {
+        (0..num_to_create)
+            .map(|_| {
+                let payload = self
+                    .template
+                    .create_payload(account.address(), &mut self.rng);
+                account.sign_with_transaction_builder(self.txn_factory.payload(payload))
+            })
+            .collect()
+    }
+}
+
+pub struct CustomEntryFunctionGeneratorCreator {
+    txn_factory: TransactionFactory,
+    template: Arc<EntryFunctionTemplate>,
+}
+
+impl CustomEntryFunctionGeneratorCreator {
+    pub fn new(txn_factory: TransactionFactory, template: Arc<EntryFunctionTemplate>) -> Self {
+        Self {
+            txn_factory,
+            template,
+        }
+    }
+}
+
+impl TransactionGeneratorCreator for CustomEntryFunctionGeneratorCreator {
+    fn create_transaction_generator(&self) -> Box<dyn TransactionGenerator> {
+        Box::new(CustomEntryFunctionGenerator {
+            rng: StdRng::from_entropy(),
+            txn_factory: self.txn_factory.clone(),
+            template: self.template.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_function_template() {
+        let template = EntryFunctionTemplate::new(
+            "0x1::coin::transfer",
+            &["0x1::aptos_coin::AptosCoin".to_string()],
+            vec![
+                ArgumentGenerator::RandomAddress,
+                ArgumentGenerator::U64 { min: 5, max: 5 },
+            ],
+        )
+        .unwrap();
+        let mut rng = StdRng::seed_from_u64(0);
+        let payload = template.create_payload(AccountAddress::ONE, &mut rng);
+        let TransactionPayload::EntryFunction(entry_function) = payload else {
+            panic!("Expected an entry function payload");
+        };
+        assert_eq!(entry_function.module().name().as_str(), "coin");
+        assert_eq!(entry_function.function().as_str(), "transfer");
+        assert_eq!(entry_function.ty_args().len(), 1);
+        assert_eq!(entry_function.args()[1], bcs::to_bytes(&5u64).unwrap());
+
+        assert!(EntryFunctionTemplate::new("0x1::coin", &[], vec![]).is_err());
+        assert!(EntryFunctionTemplate::new(
+            "0x1::coin::transfer",
+            &[],
+            vec![ArgumentGenerator::U8 { min: 2, max: 1 }]
+        )
+        .is_err());
+    }
+}
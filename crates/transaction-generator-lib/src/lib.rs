@@ -3,7 +3,7 @@
 
 #![forbid(unsafe_code)]
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use aptos_infallible::{RwLock, RwLockWriteGuard};
 use aptos_logger::{info, sample, sample::SampleRate, warn};
 use aptos_sdk::{
@@ -31,12 +31,15 @@ pub mod args;
 mod batch_transfer;
 mod bounded_batch_wrapper;
 mod call_custom_modules;
+pub mod custom_entry_function;
 mod entry_points;
 mod p2p_transaction_generator;
 pub mod publish_modules;
 pub mod publishing;
+pub mod replay;
 mod transaction_mix_generator;
 mod workflow_delegator;
+pub mod workload;
 use self::{
     account_generator::AccountGeneratorCreator,
     call_custom_modules::CustomModulesDelegationGeneratorCreator,
@@ -47,14 +50,17 @@ use self::{
 use crate::{
     accounts_pool_wrapper::AccountsPoolWrapperCreator,
     batch_transfer::BatchTransferTransactionGeneratorCreator,
-    entry_points::EntryPointTransactionGenerator, p2p_transaction_generator::SamplingMode,
+    custom_entry_function::{CustomEntryFunctionGeneratorCreator, EntryFunctionTemplate},
+    entry_points::EntryPointTransactionGenerator,
+    p2p_transaction_generator::SamplingMode,
+    replay::{ReplayGeneratorCreator, TransactionRecording},
     workflow_delegator::WorkflowTxnGeneratorCreator,
 };
 pub use publishing::module_simple::EntryPoints;
 
 pub const SEND_AMOUNT: u64 = 1;
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub enum TransactionType {
     NonConflictingCoinTransfer {
         invalid_transaction_ratio: usize,
@@ -86,6 +92,15 @@ pub enum TransactionType {
         use_account_pool: bool,
         progress_type: WorkflowProgress,
     },
+    CustomEntryFunction {
+        template: Arc<EntryFunctionTemplate>,
+        use_account_pool: bool,
+    },
+    /// Replays recorded chain transactions, at their original pace multiplied by speedup.
+    Replay {
+        recording: Arc<TransactionRecording>,
+        speedup: f64,
+    },
 }
 
 #[derive(Debug, Copy, Clone, ValueEnum, Default, Deserialize, Parser, Serialize)]
@@ -126,6 +141,15 @@ pub trait TransactionGenerator: Sync + Send {
         account: &LocalAccount,
         num_to_create: usize,
     ) -> Vec<SignedTransaction>;
+
+    /// Called with the sequence numbers committed on chain for the senders of transactions
+    /// that expired, so that generators signing from their own accounts, rather than from
+    /// the account they are called with, can resync them.
+    fn resync_expired_senders(
+        &mut self,
+        _committed_sequence_numbers: &HashMap<AccountAddress, u64>,
+    ) {
+    }
 }
 
 #[async_trait]
@@ -243,11 +267,11 @@ pub async fn create_txn_generator_creator(
     txn_factory: &TransactionFactory,
     init_txn_factory: &TransactionFactory,
     cur_phase: Arc<AtomicUsize>,
-) -> (
+) -> Result<(
     Box<dyn TransactionGeneratorCreator>,
     Arc<ObjectPool<AccountAddress>>,
     Arc<ObjectPool<LocalAccount>>,
-) {
+)> {
     let addresses_pool = Arc::new(ObjectPool::new_initial(
         source_accounts
             .iter()
@@ -382,20 +406,43 @@ pub async fn create_txn_generator_creator(
                     )
                     .await,
                 ),
+                TransactionType::CustomEntryFunction {
+                    template,
+                    use_account_pool,
+                } => wrap_accounts_pool(
+                    Box::new(CustomEntryFunctionGeneratorCreator::new(
+                        txn_factory.clone(),
+                        template.clone(),
+                    )),
+                    *use_account_pool,
+                    &accounts_pool,
+                ),
+                TransactionType::Replay { recording, speedup } => Box::new(
+                    ReplayGeneratorCreator::new(
+                        txn_factory.clone(),
+                        init_txn_factory.clone(),
+                        &root_account,
+                        txn_executor,
+                        recording.clone(),
+                        *speedup,
+                    )
+                    .await
+                    .context("Failed to set up transaction replay")?,
+                ),
             };
             txn_generator_creator_mix.push((txn_generator_creator, *weight));
         }
         txn_generator_creator_mix_per_phase.push(txn_generator_creator_mix)
     }
 
-    (
+    Ok((
         Box::new(PhasedTxnMixGeneratorCreator::new(
             txn_generator_creator_mix_per_phase,
             cur_phase,
         )),
         addresses_pool,
         accounts_pool,
-    )
+    ))
 }
 
 /// Simple object pool structure, that you can add and remove from multiple threads.
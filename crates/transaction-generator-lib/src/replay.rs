@@ -0,0 +1,477 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Replay of recorded chain traffic (e.g. a range of mainnet transactions) against a test network.
+//!
+//! Every sender of the recording is remapped onto a freshly minted test account, which re-signs
+//! its transactions with the test chain id and its own sequence numbers. Arguments referring to
+//! recorded senders are remapped as well. Transactions are released at the pace they were
+//! originally committed at (by block timestamp), optionally sped up or slowed down.
+//! Once transactions of a test account expire, its sequence number is resynced to the one
+//! committed on chain, so that its later transactions don't wait behind a gap.
+
+use crate::{
+    create_account_transaction, ReliableTransactionSubmitter, RootAccountHandle,
+    TransactionGenerator, TransactionGeneratorCreator,
+};
+use anyhow::{ensure, Context, Result};
+use aptos_infallible::Mutex;
+use aptos_logger::{debug, info, sample, sample::SampleRate};
+use aptos_sdk::{
+    bcs,
+    move_types::account_address::AccountAddress,
+    transaction_builder::TransactionFactory,
+    types::{
+        transaction::{
+            EntryFunction, Script, SignedTransaction, Transaction, TransactionArgument,
+            TransactionPayload,
+        },
+        LocalAccount,
+    },
+};
+use rand::{rngs::StdRng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs,
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Mempool only accepts a limited number of transactions per sender,
+/// so the root account creates the replay senders in batches.
+const CREATE_ACCOUNTS_BATCH_SIZE: usize = 100;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct RecordedTransaction {
+    /// Timestamp of the block the transaction was committed in.
+    pub timestamp_usecs: u64,
+    pub sender: AccountAddress,
+    pub payload: TransactionPayload,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct TransactionRecording {
+    /// First version of the recorded range.
+    pub start_version: u64,
+    pub transactions: Vec<RecordedTransaction>,
+}
+
+impl TransactionRecording {
+    /// Records the user transactions that can be replayed from another account, i.e. entry
+    /// function and script calls. Each one is timestamped with the block it was committed in.
+    pub fn from_transactions(start_version: u64, transactions: &[Transaction]) -> Self {
+        // Transactions before the first block metadata of the range belong to the block
+        // that started before it, use the first known timestamp for them.
+        let mut timestamp_usecs = transactions
+            .iter()
+            .find_map(block_timestamp_usecs)
+            .unwrap_or(0);
+        let mut recorded = Vec::new();
+        for transaction in transactions {
+            if let Some(block_timestamp_usecs) = block_timestamp_usecs(transaction) {
+                timestamp_usecs = block_timestamp_usecs;
+            }
+            if let Transaction::UserTransaction(txn) = transaction {
+                if matches!(
+                    txn.payload(),
+                    TransactionPayload::EntryFunction(_) | TransactionPayload::Script(_)
+                ) {
+                    recorded.push(RecordedTransaction {
+                        timestamp_usecs,
+                        sender: txn.sender(),
+                        payload: txn.payload().clone(),
+                    });
+                }
+            }
+        }
+        Self {
+            start_version,
+            transactions: recorded,
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let bytes =
+            fs::read(path).with_context(|| format!("Failed to read recording {:?}", path))?;
+        bcs::from_bytes(&bytes).with_context(|| format!("Invalid recording {:?}", path))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, bcs::to_bytes(self)?)
+            .with_context(|| format!("Failed to write recording {:?}", path))
+    }
+
+    /// Number of recorded transactions of each sender, in order of first appearance.
+    pub fn senders(&self) -> Vec<(AccountAddress, usize)> {
+        let mut index = HashMap::new();
+        let mut senders: Vec<(AccountAddress, usize)> = Vec::new();
+        for txn in &self.transactions {
+            let position = *index.entry(txn.sender).or_insert_with(|| {
+                senders.push((txn.sender, 0));
+                senders.len() - 1
+            });
+            senders[position].1 += 1;
+        }
+        senders
+    }
+
+    /// Wall clock duration covered by the recording.
+    pub fn duration(&self) -> Duration {
+        match (self.transactions.first(), self.transactions.last()) {
+            (Some(first), Some(last)) => {
+                Duration::from_micros(last.timestamp_usecs.saturating_sub(first.timestamp_usecs))
+            },
+            _ => Duration::ZERO,
+        }
+    }
+}
+
+fn block_timestamp_usecs(transaction: &Transaction) -> Option<u64> {
+    match transaction {
+        Transaction::BlockMetadata(metadata) => Some(metadata.timestamp_usecs()),
+        Transaction::BlockMetadataExt(metadata) => Some(metadata.timestamp_usecs()),
+        _ => None,
+    }
+}
+
+/// Rewrites the addresses of recorded senders passed as arguments to the payload,
+/// so that transactions touching each other's senders keep doing so.
+fn remap_payload(
+    payload: &TransactionPayload,
+    accounts: &HashMap<AccountAddress, LocalAccount>,
+) -> TransactionPayload {
+    let remap = |address: AccountAddress| {
+        accounts
+            .get(&address)
+            .map_or(address, |account| account.address())
+    };
+    match payload {
+        TransactionPayload::EntryFunction(entry_function) => {
+            TransactionPayload::EntryFunction(EntryFunction::new(
+                entry_function.module().clone(),
+                entry_function.function().to_owned(),
+                entry_function.ty_args().to_vec(),
+                entry_function
+                    .args()
+                    .iter()
+                    .map(|arg| match bcs::from_bytes::<AccountAddress>(arg) {
+                        Ok(address) => bcs::to_bytes(&remap(address))
+                            .expect("Address serialization must succeed"),
+                        Err(_) => arg.clone(),
+                    })
+                    .collect(),
+            ))
+        },
+        TransactionPayload::Script(script) => TransactionPayload::Script(Script::new(
+            script.code().to_vec(),
+            script.ty_args().to_vec(),
+            script
+                .args()
+                .iter()
+                .map(|arg| match arg {
+                    TransactionArgument::Address(address) => {
+                        TransactionArgument::Address(remap(*address))
+                    },
+                    arg => arg.clone(),
+                })
+                .collect(),
+        )),
+        payload => payload.clone(),
+    }
+}
+
+struct ReplayCursor {
+    next: usize,
+    start: Option<Instant>,
+}
+
+struct ReplayState {
+    txn_factory: TransactionFactory,
+    recording: Arc<TransactionRecording>,
+    /// Test account replaying each recorded sender.
+    accounts: HashMap<AccountAddress, LocalAccount>,
+    speedup: f64,
+    cursor: Mutex<ReplayCursor>,
+}
+
+impl ReplayState {
+    /// When the transaction is due, relative to the start of the replay.
+    fn offset(&self, txn: &RecordedTransaction) -> Duration {
+        let first_timestamp_usecs = self.recording.transactions[0].timestamp_usecs;
+        Duration::from_micros(txn.timestamp_usecs.saturating_sub(first_timestamp_usecs))
+            .div_f64(self.speedup)
+    }
+
+    /// Returns up to num_to_create transactions that are due. Transactions are signed under
+    /// the lock, so each sender's transactions get sequence numbers in recording order.
+    fn take_due(&self, num_to_create: usize) -> Vec<SignedTransaction> {
+        let mut cursor = self.cursor.lock();
+        let elapsed = cursor.start.get_or_insert_with(Instant::now).elapsed();
+        let mut txns = Vec::new();
+        while txns.len() < num_to_create && cursor.next < self.recording.transactions.len() {
+            let recorded = &self.recording.transactions[cursor.next];
+            if self.offset(recorded) > elapsed {
+                break;
+            }
+            let account = &self.accounts[&recorded.sender];
+            txns.push(account.sign_with_transaction_builder(
+                self.txn_factory
+                    .payload(remap_payload(&recorded.payload, &self.accounts)),
+            ));
+            cursor.next += 1;
+        }
+        if cursor.next == self.recording.transactions.len() {
+            sample!(
+                SampleRate::Duration(Duration::from_secs(60)),
+                info!(
+                    "Replayed all {} recorded transactions",
+                    self.recording.transactions.len()
+                )
+            );
+        }
+        txns
+    }
+
+    /// Resets the test accounts whose transactions expired to their committed sequence number.
+    /// This is done under the lock, so that it doesn't interleave with signing.
+    fn resync(&self, committed_sequence_numbers: &HashMap<AccountAddress, u64>) {
+        let _cursor = self.cursor.lock();
+        for account in self.accounts.values() {
+            if let Some(&committed) = committed_sequence_numbers.get(&account.address()) {
+                if account.sequence_number() > committed {
+                    debug!(
+                        "Resyncing replay account {} from sequence number {} to {}",
+                        account.address(),
+                        account.sequence_number(),
+                        committed
+                    );
+                    account.set_sequence_number(committed);
+                }
+            }
+        }
+    }
+}
+
+/// Ignores the emitter account it is called with, and sends the due recorded
+/// transactions from the test accounts the recorded senders are mapped to.
+pub struct ReplayGenerator {
+    state: Arc<ReplayState>,
+}
+
+impl TransactionGenerator for ReplayGenerator {
+    fn generate_transactions(
+        &mut self,
+        _account: &LocalAccount,
+        num_to_create: usize,
+    ) -> Vec<SignedTransaction> {
+        self.state.take_due(num_to_create)
+    }
+
+    fn resync_expired_senders(
+        &mut self,
+        committed_sequence_numbers: &HashMap<AccountAddress, u64>,
+    ) {
+        self.state.resync(committed_sequence_numbers)
+    }
+}
+
+pub struct ReplayGeneratorCreator {
+    state: Arc<ReplayState>,
+}
+
+impl ReplayGeneratorCreator {
+    /// Mints a test account for every sender of the recording, funded to pay the
+    /// maximum gas of all of its transactions.
+    pub async fn new(
+        txn_factory: TransactionFactory,
+        init_txn_factory: TransactionFactory,
+        root_account: &dyn RootAccountHandle,
+        txn_executor: &dyn ReliableTransactionSubmitter,
+        recording: Arc<TransactionRecording>,
+        speedup: f64,
+    ) -> Result<Self> {
+        ensure!(
+            !recording.transactions.is_empty(),
+            "Recording has no transactions to replay"
+        );
+        ensure!(speedup > 0.0, "Replay speedup needs to be positive");
+
+        let mut rng = StdRng::from_entropy();
+        let gas_per_txn = txn_factory.get_gas_unit_price() * txn_factory.get_max_gas_amount();
+        let senders = recording.senders();
+        root_account
+            .approve_funds(
+                gas_per_txn * recording.transactions.len() as u64,
+                "funding replay senders",
+            )
+            .await;
+
+        let mut accounts = HashMap::new();
+        let mut requests_create = Vec::with_capacity(senders.len());
+        for (sender, num_txns) in senders {
+            let account = LocalAccount::generate(&mut rng);
+            requests_create.push(create_account_transaction(
+                root_account.get_root_account(),
+                account.address(),
+                &init_txn_factory,
+                gas_per_txn * num_txns as u64,
+            ));
+            accounts.insert(sender, account);
+        }
+        info!(
+            "Creating {} accounts to replay {} recorded transactions from version {}",
+            requests_create.len(),
+            recording.transactions.len(),
+            recording.start_version
+        );
+        for batch in requests_create.chunks(CREATE_ACCOUNTS_BATCH_SIZE) {
+            txn_executor.execute_transactions(batch).await?;
+        }
+
+        Ok(Self {
+            state: Arc::new(ReplayState {
+                txn_factory,
+                recording,
+                accounts,
+                speedup,
+                cursor: Mutex::new(ReplayCursor {
+                    next: 0,
+                    start: None,
+                }),
+            }),
+        })
+    }
+}
+
+impl TransactionGeneratorCreator for ReplayGeneratorCreator {
+    fn create_transaction_generator(&self) -> Box<dyn TransactionGenerator> {
+        Box::new(ReplayGenerator {
+            state: self.state.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_sdk::{transaction_builder::aptos_stdlib, types::chain_id::ChainId};
+
+    fn recorded(
+        sender: AccountAddress,
+        receiver: AccountAddress,
+        timestamp_secs: u64,
+    ) -> RecordedTransaction {
+        RecordedTransaction {
+            timestamp_usecs: timestamp_secs * 1_000_000,
+            sender,
+            payload: aptos_stdlib::aptos_account_transfer(receiver, 1),
+        }
+    }
+
+    #[test]
+    fn test_replay_remaps_and_paces_transactions() {
+        let alice = AccountAddress::random();
+        let bob = AccountAddress::random();
+        let outsider = AccountAddress::random();
+        let recording = Arc::new(TransactionRecording {
+            start_version: 0,
+            transactions: vec![
+                recorded(alice, bob, 100),
+                recorded(bob, outsider, 100),
+                recorded(alice, bob, 3700),
+            ],
+        });
+        assert_eq!(recording.senders(), vec![(alice, 2), (bob, 1)]);
+        assert_eq!(recording.duration(), Duration::from_secs(3600));
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let accounts: HashMap<_, _> = [alice, bob]
+            .into_iter()
+            .map(|sender| (sender, LocalAccount::generate(&mut rng)))
+            .collect();
+        let (test_alice, test_bob) = (accounts[&alice].address(), accounts[&bob].address());
+        let state = ReplayState {
+            txn_factory: TransactionFactory::new(ChainId::test()),
+            recording,
+            accounts,
+            speedup: 1.0,
+            cursor: Mutex::new(ReplayCursor {
+                next: 0,
+                start: None,
+            }),
+        };
+
+        // The third transaction is only due an hour into the replay.
+        let txns = state.take_due(10);
+        assert_eq!(txns.len(), 2);
+        assert!(state.take_due(10).is_empty());
+
+        assert_eq!(txns[0].sender(), test_alice);
+        assert_eq!(txns[0].sequence_number(), 0);
+        assert_eq!(txns[0].chain_id(), ChainId::test());
+        let TransactionPayload::EntryFunction(entry_function) = txns[0].payload() else {
+            panic!("Expected an entry function payload");
+        };
+        assert_eq!(entry_function.args()[0], bcs::to_bytes(&test_bob).unwrap());
+
+        assert_eq!(txns[1].sender(), test_bob);
+        let TransactionPayload::EntryFunction(entry_function) = txns[1].payload() else {
+            panic!("Expected an entry function payload");
+        };
+        assert_eq!(entry_function.args()[0], bcs::to_bytes(&outsider).unwrap());
+    }
+
+    #[test]
+    fn test_replay_resyncs_expired_senders() {
+        let alice = AccountAddress::random();
+        let bob = AccountAddress::random();
+        let recording = Arc::new(TransactionRecording {
+            start_version: 0,
+            transactions: vec![
+                recorded(alice, bob, 0),
+                recorded(alice, bob, 0),
+                recorded(alice, bob, 0),
+                recorded(alice, bob, 3600),
+            ],
+        });
+        let mut rng = StdRng::seed_from_u64(0);
+        let accounts: HashMap<_, _> = [(alice, LocalAccount::generate(&mut rng))]
+            .into_iter()
+            .collect();
+        let test_alice = accounts[&alice].address();
+        let mut generator = ReplayGenerator {
+            state: Arc::new(ReplayState {
+                txn_factory: TransactionFactory::new(ChainId::test()),
+                recording,
+                accounts,
+                // Makes the last transaction due right away
+                speedup: 1e9,
+                cursor: Mutex::new(ReplayCursor {
+                    next: 0,
+                    start: None,
+                }),
+            }),
+        };
+        let emitter_account = LocalAccount::generate(&mut rng);
+
+        let txns = generator.generate_transactions(&emitter_account, 3);
+        let sequence_numbers: Vec<_> = txns.iter().map(|txn| txn.sequence_number()).collect();
+        assert_eq!(sequence_numbers, vec![0, 1, 2]);
+
+        // Only the first transaction committed, the others expired. Unknown senders are ignored.
+        generator.resync_expired_senders(
+            &[(test_alice, 1), (AccountAddress::random(), 5)]
+                .into_iter()
+                .collect(),
+        );
+        let txns = generator.generate_transactions(&emitter_account, 3);
+        assert_eq!(txns.len(), 1);
+        assert_eq!(txns[0].sequence_number(), 1);
+
+        // Accounts are only moved back to their committed sequence number.
+        generator.resync_expired_senders(&[(test_alice, 5)].into_iter().collect());
+        assert_eq!(generator.state.accounts[&alice].sequence_number(), 2);
+    }
+}
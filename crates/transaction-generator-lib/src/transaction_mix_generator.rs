@@ -1,11 +1,17 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 use crate::{TransactionGenerator, TransactionGeneratorCreator};
-use aptos_sdk::types::{transaction::SignedTransaction, LocalAccount};
+use aptos_sdk::{
+    move_types::account_address::AccountAddress,
+    types::{transaction::SignedTransaction, LocalAccount},
+};
 use rand::{rngs::StdRng, Rng, SeedableRng};
-use std::sync::{
-    atomic::{AtomicUsize, Ordering},
-    Arc,
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
 };
 
 pub struct PhasedTxnMixGenerator {
@@ -60,6 +66,16 @@ impl TransactionGenerator for PhasedTxnMixGenerator {
             picked, self.total_weight_per_phase[phase], phase,
         );
     }
+
+    fn resync_expired_senders(
+        &mut self,
+        committed_sequence_numbers: &HashMap<AccountAddress, u64>,
+    ) {
+        // Transactions of any phase can expire after the phase is over
+        for (gen, _) in self.txn_mix_per_phase.iter_mut().flatten() {
+            gen.resync_expired_senders(committed_sequence_numbers);
+        }
+    }
 }
 
 pub struct PhasedTxnMixGeneratorCreator {
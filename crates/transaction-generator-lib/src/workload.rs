@@ -0,0 +1,322 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Declarative workloads, so that load test scenarios can be kept in versioned YAML or TOML files.
+//!
+//! A workload is a list of phases, each running a weighted mix of transactions at a given load:
+//! ```yaml
+//! module_working_set_size: 1
+//! phases:
+//!   - duration_secs: 60
+//!     target_tps: 1000
+//!     mix:
+//!       - transaction_type: CoinTransfer
+//!         weight: 3
+//!       - entry_function:
+//!           function: "0x1::aptos_account::transfer"
+//!           args:
+//!             - type: random_address
+//!             - type: u64
+//!               min: 1
+//!               max: 100
+//! ```
+
+use crate::{
+    args::TransactionTypeArg,
+    custom_entry_function::{ArgumentGenerator, EntryFunctionTemplate},
+    TransactionType, WorkflowProgress,
+};
+use anyhow::{bail, ensure, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path, sync::Arc, time::Duration};
+
+fn default_module_working_set_size() -> usize {
+    1
+}
+
+fn default_weight() -> usize {
+    1
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkloadSpec {
+    /// Number of copies of the modules that will be published, see `module_working_set_size`
+    /// in the emitter args.
+    #[serde(default = "default_module_working_set_size")]
+    pub module_working_set_size: usize,
+    /// Whether to use burner accounts for the sender.
+    #[serde(default)]
+    pub sender_use_account_pool: bool,
+    pub phases: Vec<WorkloadPhase>,
+}
+
+/// A phase runs for its duration, at either a target TPS or a mempool backlog (concurrency).
+/// All phases of a workload need to use the same kind of load.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkloadPhase {
+    pub duration_secs: u64,
+    pub target_tps: Option<usize>,
+    pub mempool_backlog: Option<usize>,
+    pub mix: Vec<WorkloadMixEntry>,
+}
+
+/// One transaction of a phase mix, either one of the predefined transaction types,
+/// or a call to an arbitrary entry function.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkloadMixEntry {
+    #[serde(default = "default_weight")]
+    pub weight: usize,
+    pub transaction_type: Option<TransactionTypeArg>,
+    pub entry_function: Option<EntryFunctionCall>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EntryFunctionCall {
+    /// Fully qualified function name, e.g. `0x1::aptos_account::transfer`
+    pub function: String,
+    #[serde(default)]
+    pub type_args: Vec<String>,
+    #[serde(default)]
+    pub args: Vec<ArgumentGenerator>,
+}
+
+impl EntryFunctionCall {
+    fn to_template(&self) -> Result<EntryFunctionTemplate> {
+        EntryFunctionTemplate::new(&self.function, &self.type_args, self.args.clone())
+    }
+}
+
+impl WorkloadSpec {
+    /// Loads a workload from a `.yaml`, `.yml` or `.toml` file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read workload file {:?}", path))?;
+        let spec = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("yaml") | Some("yml") => Self::from_yaml_str(&contents),
+            Some("toml") => Self::from_toml_str(&contents),
+            _ => bail!(
+                "Workload file {:?} must be a .yaml, .yml or .toml file",
+                path
+            ),
+        };
+        spec.with_context(|| format!("Invalid workload file {:?}", path))
+    }
+
+    pub fn from_yaml_str(contents: &str) -> Result<Self> {
+        let spec: Self = serde_yaml::from_str(contents)?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    pub fn from_toml_str(contents: &str) -> Result<Self> {
+        let spec: Self = toml::from_str(contents)?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        ensure!(!self.phases.is_empty(), "Workload needs at least one phase");
+        ensure!(
+            self.module_working_set_size > 0,
+            "module_working_set_size needs to be positive"
+        );
+        for (index, phase) in self.phases.iter().enumerate() {
+            ensure!(
+                phase.duration_secs > 0,
+                "Phase {} needs a positive duration",
+                index
+            );
+            match (phase.target_tps, phase.mempool_backlog) {
+                (Some(load), None) | (None, Some(load)) => {
+                    ensure!(load > 0, "Phase {} needs a positive load", index)
+                },
+                _ => bail!(
+                    "Phase {} needs exactly one of target_tps or mempool_backlog",
+                    index
+                ),
+            }
+            ensure!(
+                phase.target_tps.is_some() == self.phases[0].target_tps.is_some(),
+                "All phases need to use the same kind of load, but phase {} differs from phase 0",
+                index
+            );
+            ensure!(
+                !phase.mix.is_empty(),
+                "Phase {} needs at least one transaction",
+                index
+            );
+            for entry in &phase.mix {
+                ensure!(entry.weight > 0, "Phase {} has a zero weight", index);
+                match (&entry.transaction_type, &entry.entry_function) {
+                    (Some(_), None) => {},
+                    (None, Some(call)) => {
+                        call.to_template()?;
+                    },
+                    _ => bail!(
+                        "Each transaction in phase {} needs exactly one of transaction_type or entry_function",
+                        index
+                    ),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn uses_target_tps(&self) -> bool {
+        self.phases[0].target_tps.is_some()
+    }
+
+    /// Target TPS or mempool backlog of each phase.
+    pub fn load_per_phase(&self) -> Vec<usize> {
+        self.phases
+            .iter()
+            .map(|phase| phase.target_tps.or(phase.mempool_backlog).unwrap_or(0))
+            .collect()
+    }
+
+    pub fn phase_durations(&self) -> Vec<Duration> {
+        self.phases
+            .iter()
+            .map(|phase| Duration::from_secs(phase.duration_secs))
+            .collect()
+    }
+
+    pub fn total_duration(&self) -> Duration {
+        self.phase_durations().into_iter().sum()
+    }
+
+    pub fn transaction_mix_per_phase(&self) -> Result<Vec<Vec<(TransactionType, usize)>>> {
+        self.phases
+            .iter()
+            .map(|phase| {
+                phase
+                    .mix
+                    .iter()
+                    .map(|entry| {
+                        let transaction_type =
+                            match (&entry.transaction_type, &entry.entry_function) {
+                                (Some(transaction_type), _) => transaction_type.materialize(
+                                    self.module_working_set_size,
+                                    self.sender_use_account_pool,
+                                    WorkflowProgress::when_done_default(),
+                                ),
+                                (None, Some(call)) => TransactionType::CustomEntryFunction {
+                                    template: Arc::new(call.to_template()?),
+                                    use_account_pool: self.sender_use_account_pool,
+                                },
+                                (None, None) => bail!("Transaction has no type"),
+                            };
+                        Ok((transaction_type, entry.weight))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const YAML_WORKLOAD: &str = r#"
+phases:
+  - duration_secs: 60
+    target_tps: 1000
+    mix:
+      - transaction_type: CoinTransfer
+        weight: 3
+      - entry_function:
+          function: "0x1::aptos_account::transfer"
+          args:
+            - type: random_address
+            - type: u64
+              min: 1
+              max: 100
+  - duration_secs: 30
+    target_tps: 100
+    mix:
+      - transaction_type: NoOp
+"#;
+
+    const TOML_WORKLOAD: &str = r#"
+[[phases]]
+duration_secs = 60
+mempool_backlog = 5000
+
+[[phases.mix]]
+transaction_type = "CoinTransfer"
+weight = 3
+
+[[phases.mix]]
+entry_function = { function = "0x1::aptos_account::transfer", args = [{ type = "sender" }, { type = "u64", min = 1, max = 1 }] }
+"#;
+
+    #[test]
+    fn test_parse_yaml_workload() {
+        let spec = WorkloadSpec::from_yaml_str(YAML_WORKLOAD).unwrap();
+        assert!(spec.uses_target_tps());
+        assert_eq!(spec.load_per_phase(), vec![1000, 100]);
+        assert_eq!(spec.total_duration(), Duration::from_secs(90));
+
+        let mix_per_phase = spec.transaction_mix_per_phase().unwrap();
+        assert_eq!(mix_per_phase.len(), 2);
+        assert_eq!(mix_per_phase[0].len(), 2);
+        assert_eq!(mix_per_phase[0][0].1, 3);
+        assert!(matches!(
+            mix_per_phase[0][1].0,
+            TransactionType::CustomEntryFunction { .. }
+        ));
+        assert_eq!(mix_per_phase[1][0].1, 1);
+    }
+
+    #[test]
+    fn test_parse_toml_workload() {
+        let spec = WorkloadSpec::from_toml_str(TOML_WORKLOAD).unwrap();
+        assert!(!spec.uses_target_tps());
+        assert_eq!(spec.load_per_phase(), vec![5000]);
+        assert_eq!(spec.transaction_mix_per_phase().unwrap()[0].len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_workloads() {
+        // mixed kinds of load
+        assert!(WorkloadSpec::from_yaml_str(
+            r#"
+phases:
+  - duration_secs: 10
+    target_tps: 10
+    mix: [{ transaction_type: NoOp }]
+  - duration_secs: 10
+    mempool_backlog: 10
+    mix: [{ transaction_type: NoOp }]
+"#
+        )
+        .is_err());
+        // both a transaction type and an entry function
+        assert!(WorkloadSpec::from_yaml_str(
+            r#"
+phases:
+  - duration_secs: 10
+    target_tps: 10
+    mix:
+      - transaction_type: NoOp
+        entry_function: { function: "0x1::aptos_account::transfer" }
+"#
+        )
+        .is_err());
+        // malformed entry function
+        assert!(WorkloadSpec::from_yaml_str(
+            r#"
+phases:
+  - duration_secs: 10
+    target_tps: 10
+    mix: [{ entry_function: { function: "aptos_account::transfer" } }]
+"#
+        )
+        .is_err());
+    }
+}
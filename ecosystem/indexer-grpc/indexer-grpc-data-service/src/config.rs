@@ -5,7 +5,10 @@ use crate::service::RawDataServerWrapper;
 use anyhow::{bail, Result};
 use aptos_indexer_grpc_server_framework::RunnableConfig;
 use aptos_indexer_grpc_utils::{
-    compression_util::StorageFormat, config::IndexerGrpcFileStoreConfig, types::RedisUrl,
+    compression_util::StorageFormat,
+    config::IndexerGrpcFileStoreConfig,
+    in_memory_cache::{InMemoryCache, InMemoryCacheSizeConfig},
+    types::RedisUrl,
 };
 use aptos_protos::{
     indexer::v1::FILE_DESCRIPTOR_SET as INDEXER_V1_FILE_DESCRIPTOR_SET,
@@ -15,11 +18,14 @@ use aptos_protos::{
 use serde::{Deserialize, Serialize};
 use std::{collections::HashSet, net::SocketAddr, sync::Arc};
 use tonic::{codec::CompressionEncoding, transport::Server};
+use url::Url;
 
 pub const SERVER_NAME: &str = "idxdatasvc";
 
 // Default max response channel size.
 const DEFAULT_MAX_RESPONSE_CHANNEL_SIZE: usize = 3;
+// Default max size of the in-process cache: 3.5 GB.
+const DEFAULT_IN_PROCESS_CACHE_MAX_SIZE_IN_BYTES: u64 = 3_500_000_000;
 
 // HTTP2 ping interval and timeout.
 // This can help server to garbage collect dead connections.
@@ -43,6 +49,28 @@ pub struct NonTlsConfig {
     pub data_service_grpc_listen_address: SocketAddr,
 }
 
+/// Config of the in-process cache, which is fed directly by a fullnode instead of Redis.
+/// This is meant for single-instance setups, e.g., small deployments and local testing.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct InProcessCacheConfig {
+    /// The address of the fullnode's indexer gRPC stream.
+    pub fullnode_grpc_address: Url,
+    /// The max size of the cache; beyond it, the oldest transactions are evicted and served from the file store.
+    #[serde(default = "InProcessCacheConfig::default_max_cache_size_in_bytes")]
+    pub max_cache_size_in_bytes: u64,
+    /// The version to start caching from. If not set, the cache starts at the latest version
+    /// of the file store, or at genesis if the file store is empty.
+    #[serde(default)]
+    pub starting_version: Option<u64>,
+}
+
+impl InProcessCacheConfig {
+    pub const fn default_max_cache_size_in_bytes() -> u64 {
+        DEFAULT_IN_PROCESS_CACHE_MAX_SIZE_IN_BYTES
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct IndexerGrpcDataServiceConfig {
@@ -61,8 +89,12 @@ pub struct IndexerGrpcDataServiceConfig {
     pub disable_auth_check: bool,
     /// File store config.
     pub file_store_config: IndexerGrpcFileStoreConfig,
-    /// Redis read replica address.
-    pub redis_read_replica_address: RedisUrl,
+    /// Redis read replica address. Required unless the in-process cache is used.
+    #[serde(default)]
+    pub redis_read_replica_address: Option<RedisUrl>,
+    /// If given, the data service is fed directly by a fullnode without Redis.
+    #[serde(default)]
+    pub in_process_cache_config: Option<InProcessCacheConfig>,
     /// Support compressed cache data.
     #[serde(default = "IndexerGrpcDataServiceConfig::default_enable_cache_compression")]
     pub enable_cache_compression: bool,
@@ -78,7 +110,8 @@ impl IndexerGrpcDataServiceConfig {
         data_service_response_channel_size: Option<usize>,
         disable_auth_check: bool,
         file_store_config: IndexerGrpcFileStoreConfig,
-        redis_read_replica_address: RedisUrl,
+        redis_read_replica_address: Option<RedisUrl>,
+        in_process_cache_config: Option<InProcessCacheConfig>,
         enable_cache_compression: bool,
         sender_addresses_to_ignore: Vec<String>,
     ) -> Self {
@@ -91,6 +124,7 @@ impl IndexerGrpcDataServiceConfig {
             disable_auth_check,
             file_store_config,
             redis_read_replica_address,
+            in_process_cache_config,
             enable_cache_compression,
             sender_addresses_to_ignore,
         }
@@ -117,6 +151,11 @@ impl RunnableConfig for IndexerGrpcDataServiceConfig {
         {
             bail!("At least one of data_service_grpc_non_tls_config and data_service_grpc_tls_config must be set");
         }
+        if self.redis_read_replica_address.is_some() == self.in_process_cache_config.is_some() {
+            bail!(
+                "Exactly one of redis_read_replica_address and in_process_cache_config must be set"
+            );
+        }
         Ok(())
     }
 
@@ -139,17 +178,45 @@ impl RunnableConfig for IndexerGrpcDataServiceConfig {
             StorageFormat::Base64UncompressedProto
         };
 
-        let redis_conn = redis::Client::open(self.redis_read_replica_address.0.clone())?
-            .get_tokio_connection_manager()
-            .await?;
-
         // InMemoryCache.
-        let in_memory_cache =
-            aptos_indexer_grpc_utils::in_memory_cache::InMemoryCache::new_with_redis_connection(
-                redis_conn,
-                cache_storage_format,
-            )
-            .await?;
+        let in_memory_cache = match (
+            &self.redis_read_replica_address,
+            &self.in_process_cache_config,
+        ) {
+            (Some(redis_read_replica_address), _) => {
+                let redis_conn = redis::Client::open(redis_read_replica_address.0.clone())?
+                    .get_tokio_connection_manager()
+                    .await?;
+                InMemoryCache::new_with_redis_connection(redis_conn, cache_storage_format).await?
+            },
+            (None, Some(in_process_cache_config)) => {
+                let starting_version = match in_process_cache_config.starting_version {
+                    Some(starting_version) => starting_version,
+                    None => self
+                        .file_store_config
+                        .create()
+                        .get_latest_version()
+                        .await
+                        .unwrap_or(0),
+                };
+                tracing::info!(
+                    starting_version,
+                    fullnode_grpc_address = in_process_cache_config.fullnode_grpc_address.as_str(),
+                    "[Data Service] Starting the in-process cache without Redis."
+                );
+                InMemoryCache::new_with_fullnode_stream(
+                    in_process_cache_config.fullnode_grpc_address.clone(),
+                    starting_version,
+                    InMemoryCacheSizeConfig::with_max_capacity_in_bytes(
+                        in_process_cache_config.max_cache_size_in_bytes,
+                    ),
+                )
+                .await?
+            },
+            (None, None) => {
+                bail!("Either redis_read_replica_address or in_process_cache_config must be set")
+            },
+        };
         // Add authentication interceptor.
         let server = RawDataServerWrapper::new(
             self.redis_read_replica_address.clone(),
@@ -14,13 +14,14 @@ use aptos_indexer_grpc_utils::{
     compression_util::{CacheEntry, StorageFormat},
     config::IndexerGrpcFileStoreConfig,
     constants::{
-        IndexerGrpcRequestMetadata, GRPC_AUTH_TOKEN_HEADER, GRPC_REQUEST_NAME_HEADER,
-        MESSAGE_SIZE_LIMIT,
+        IndexerGrpcRequestMetadata, FILTERED_STREAM_PROGRESS_INTERVAL, GRPC_AUTH_TOKEN_HEADER,
+        GRPC_REQUEST_NAME_HEADER, MESSAGE_SIZE_LIMIT,
     },
     counters::{log_grpc_step, IndexerGrpcStep, NUM_MULTI_FETCH_OVERLAPPED_VERSIONS},
     file_store_operator::FileStoreOperator,
     in_memory_cache::InMemoryCache,
     time_diff_since_pb_timestamp_in_secs,
+    transaction_filter::BooleanTransactionFilter,
     types::RedisUrl,
 };
 use aptos_moving_average::MovingAverage;
@@ -77,7 +78,8 @@ const MAX_FETCH_TASKS_PER_REQUEST: u64 = 5;
 const TRANSACTIONS_PER_STORAGE_BLOCK: u64 = 1000;
 
 pub struct RawDataServerWrapper {
-    pub redis_client: Arc<redis::Client>,
+    /// Redis client; if None, the data is served from the in-process cache and the file store only.
+    pub redis_client: Option<Arc<redis::Client>>,
     pub file_store_config: IndexerGrpcFileStoreConfig,
     pub data_service_response_channel_size: usize,
     pub sender_addresses_to_ignore: HashSet<String>,
@@ -87,7 +89,7 @@ pub struct RawDataServerWrapper {
 
 impl RawDataServerWrapper {
     pub fn new(
-        redis_address: RedisUrl,
+        redis_address: Option<RedisUrl>,
         file_store_config: IndexerGrpcFileStoreConfig,
         data_service_response_channel_size: usize,
         sender_addresses_to_ignore: HashSet<String>,
@@ -95,11 +97,15 @@ impl RawDataServerWrapper {
         in_memory_cache: Arc<InMemoryCache>,
     ) -> anyhow::Result<Self> {
         Ok(Self {
-            redis_client: Arc::new(
-                redis::Client::open(redis_address.0.clone()).with_context(|| {
-                    format!("Failed to create redis client for {}", redis_address)
-                })?,
-            ),
+            redis_client: redis_address
+                .map(|redis_address| {
+                    redis::Client::open(redis_address.0.clone())
+                        .with_context(|| {
+                            format!("Failed to create redis client for {}", redis_address)
+                        })
+                        .map(Arc::new)
+                })
+                .transpose()?,
             file_store_config,
             data_service_response_channel_size,
             sender_addresses_to_ignore,
@@ -158,6 +164,22 @@ impl RawData for RawDataServerWrapper {
             },
         };
 
+        // Validate the transaction filter (if any) before starting the stream.
+        let transaction_filter = match request
+            .transaction_filter
+            .as_ref()
+            .map(BooleanTransactionFilter::new)
+            .transpose()
+        {
+            Ok(transaction_filter) => transaction_filter,
+            Err(e) => {
+                return Result::Err(Status::invalid_argument(format!(
+                    "Invalid transaction filter: {}",
+                    e
+                )));
+            },
+        };
+
         let file_store_operator: Box<dyn FileStoreOperator> = self.file_store_config.create();
         let file_store_operator = Arc::new(file_store_operator);
 
@@ -191,6 +213,7 @@ impl RawData for RawDataServerWrapper {
                     transactions_count,
                     tx,
                     sender_addresses_to_ignore,
+                    transaction_filter,
                     current_version,
                     in_memory_cache,
                 )
@@ -220,7 +243,7 @@ async fn get_data_with_tasks(
     start_version: u64,
     transactions_count: Option<u64>,
     chain_id: u64,
-    cache_operator: &mut CacheOperator<redis::aio::ConnectionManager>,
+    cache_operator: Option<&mut CacheOperator<redis::aio::ConnectionManager>>,
     file_store_operator: Arc<Box<dyn FileStoreOperator>>,
     request_metadata: Arc<IndexerGrpcRequestMetadata>,
     cache_storage_format: StorageFormat,
@@ -246,6 +269,19 @@ async fn get_data_with_tasks(
             MESSAGE_SIZE_LIMIT,
         ));
     }
+    let Some(cache_operator) = cache_operator else {
+        // Without Redis, the data that is not in the in-memory cache is served from the file store.
+        return match data_fetch_from_filestore(start_version, file_store_operator, request_metadata)
+            .await
+        {
+            Ok(transactions) => DataFetchSubTaskResult::BatchSuccess(vec![transactions]),
+            Err(e) => {
+                ERROR_COUNT.with_label_values(&["data_fetch_failed"]).inc();
+                data_fetch_error_handling(e, start_version, chain_id).await;
+                DataFetchSubTaskResult::NoResults
+            },
+        };
+    };
     let cache_coverage_status = cache_operator
         .check_cache_coverage_status(start_version)
         .await;
@@ -372,13 +408,14 @@ async fn get_data_in_task(
 
 // This is a task spawned off for servicing a users' request
 async fn data_fetcher_task(
-    redis_client: Arc<Client>,
+    redis_client: Option<Arc<Client>>,
     file_store_operator: Arc<Box<dyn FileStoreOperator>>,
     cache_storage_format: StorageFormat,
     request_metadata: Arc<IndexerGrpcRequestMetadata>,
     transactions_count: Option<u64>,
     tx: tokio::sync::mpsc::Sender<Result<TransactionsResponse, Status>>,
     sender_addresses_to_ignore: HashSet<String>,
+    transaction_filter: Option<BooleanTransactionFilter>,
     mut current_version: u64,
     in_memory_cache: Arc<InMemoryCache>,
 ) {
@@ -386,7 +423,11 @@ async fn data_fetcher_task(
     let mut transactions_count = transactions_count;
 
     // Establish redis connection
-    let conn = match redis_client.get_tokio_connection_manager().await {
+    let conn = match redis_client {
+        Some(redis_client) => redis_client.get_tokio_connection_manager().await.map(Some),
+        None => Ok(None),
+    };
+    let conn = match conn {
         Ok(conn) => conn,
         Err(e) => {
             ERROR_COUNT
@@ -408,11 +449,12 @@ async fn data_fetcher_task(
             return;
         },
     };
-    let mut cache_operator = CacheOperator::new(conn, cache_storage_format);
+    let mut cache_operator = conn.map(|conn| CacheOperator::new(conn, cache_storage_format));
 
     // Validate chain id
     let mut metadata = file_store_operator.get_file_store_metadata().await;
-    while metadata.is_none() {
+    // Without Redis, the file store is only a fallback and may be empty in single-instance setups.
+    while metadata.is_none() && cache_operator.is_some() {
         metadata = file_store_operator.get_file_store_metadata().await;
         tracing::warn!(
             "[File worker] File store metadata not found. Waiting for {} ms.",
@@ -424,11 +466,20 @@ async fn data_fetcher_task(
         .await;
     }
 
-    let metadata_chain_id = metadata.unwrap().chain_id;
+    let metadata_chain_id = metadata.map(|metadata| metadata.chain_id);
 
     // Validate redis chain id. Must be present by the time it gets here
-    let chain_id = match cache_operator.get_chain_id().await {
-        Ok(chain_id) => chain_id.unwrap(),
+    let chain_id = match cache_operator.as_mut() {
+        Some(cache_operator) => cache_operator
+            .get_chain_id()
+            .await
+            .map(|chain_id| chain_id.unwrap()),
+        None => Ok(in_memory_cache
+            .chain_id()
+            .expect("The in-memory cache must be fed by a fullnode without Redis.")),
+    };
+    let chain_id = match chain_id {
+        Ok(chain_id) => chain_id,
         Err(e) => {
             ERROR_COUNT
                 .with_label_values(&["redis_get_chain_id_failed"])
@@ -450,7 +501,7 @@ async fn data_fetcher_task(
         },
     };
 
-    if metadata_chain_id != chain_id {
+    if metadata_chain_id.is_some_and(|metadata_chain_id| metadata_chain_id != chain_id) {
         let _result = tx
             .send_timeout(
                 Err(Status::unavailable("[Data Service] Chain ID mismatch.")),
@@ -463,6 +514,7 @@ async fn data_fetcher_task(
 
     // Data service metrics.
     let mut tps_calculator = MovingAverage::new(MOVING_AVERAGE_WINDOW_SIZE);
+    let mut last_response_time = Instant::now();
 
     loop {
         // 1. Fetch data from cache and file store.
@@ -470,7 +522,7 @@ async fn data_fetcher_task(
             current_version,
             transactions_count,
             chain_id,
-            &mut cache_operator,
+            cache_operator.as_mut(),
             file_store_operator.clone(),
             request_metadata.clone(),
             cache_storage_format,
@@ -520,7 +572,38 @@ async fn data_fetcher_task(
             transaction_data,
             chain_id as u32,
             &sender_addresses_to_ignore,
+            transaction_filter.as_ref(),
         );
+        if resp_items.is_empty() {
+            // None of the transactions in the batch match the filter; move on to the next batch.
+            tps_calculator.tick_now(current_batch_size as u64);
+            current_version = end_of_batch_version + 1;
+            // Nothing is sent that would notice a disconnected client, so check for it here.
+            if tx.is_closed() {
+                warn!("[Data Service] Receiver is closed; exiting.");
+                break;
+            }
+            if last_response_time.elapsed() >= FILTERED_STREAM_PROGRESS_INTERVAL {
+                // Report the progress, so that the client doesn't scan these versions again.
+                let progress_response = TransactionsResponse {
+                    transactions: vec![],
+                    chain_id: Some(chain_id),
+                    processed_version: Some(end_of_batch_version),
+                };
+                if let Err(e) = tx
+                    .send_timeout(Ok(progress_response), RESPONSE_CHANNEL_SEND_TIMEOUT)
+                    .await
+                {
+                    warn!(
+                        "[Data Service] Failed to send the progress: {}; exiting.",
+                        e
+                    );
+                    break;
+                }
+                last_response_time = Instant::now();
+            }
+            continue;
+        }
         let data_latency_in_secs = resp_items
             .last()
             .unwrap()
@@ -578,6 +661,7 @@ async fn data_fetcher_task(
         // 3. Update the current version and record current tps.
         tps_calculator.tick_now(current_batch_size as u64);
         current_version = end_of_batch_version + 1;
+        last_response_time = Instant::now();
     }
     info!(
         request_identifier = request_metadata.request_identifier.as_str(),
@@ -685,15 +769,28 @@ fn get_transactions_responses_builder(
     transactions: Vec<Transaction>,
     chain_id: u32,
     sender_addresses_to_ignore: &HashSet<String>,
+    transaction_filter: Option<&BooleanTransactionFilter>,
 ) -> Vec<TransactionsResponse> {
-    let filtered_transactions =
+    let processed_version = transactions.last().map(|t| t.version);
+    let mut filtered_transactions =
         filter_transactions_for_sender_addresses(transactions, sender_addresses_to_ignore);
+    if let Some(transaction_filter) = transaction_filter {
+        filtered_transactions = transaction_filter.filter_transactions(filtered_transactions);
+    }
     let chunks = chunk_transactions(filtered_transactions, MESSAGE_SIZE_LIMIT);
+    let num_chunks = chunks.len();
     chunks
         .into_iter()
-        .map(|chunk| TransactionsResponse {
+        .enumerate()
+        .map(|(i, chunk)| TransactionsResponse {
             chain_id: Some(chain_id as u64),
             transactions: chunk,
+            // The last response covers the transactions filtered out after it
+            processed_version: if i + 1 == num_chunks {
+                processed_version
+            } else {
+                None
+            },
         })
         .collect()
 }
@@ -980,10 +1077,20 @@ fn filter_transactions_for_sender_addresses(
 
 #[cfg(test)]
 mod tests {
-    use super::{ensure_sequential_transactions, filter_transactions_for_sender_addresses};
-    use aptos_protos::transaction::v1::{
-        transaction::TxnData, Event, Signature, Transaction, TransactionInfo, TransactionPayload,
-        UserTransaction, UserTransactionRequest, WriteSetChange,
+    use super::{
+        ensure_sequential_transactions, filter_transactions_for_sender_addresses,
+        get_transactions_responses_builder,
+    };
+    use aptos_indexer_grpc_utils::transaction_filter::BooleanTransactionFilter;
+    use aptos_protos::{
+        indexer::v1::{
+            boolean_transaction_filter, transaction_filter,
+            BooleanTransactionFilter as BooleanTransactionFilterProto, TransactionFilter,
+        },
+        transaction::v1::{
+            transaction::TxnData, Event, Signature, Transaction, TransactionInfo,
+            TransactionPayload, UserTransaction, UserTransactionRequest, WriteSetChange,
+        },
     };
     use std::collections::HashSet;
 
@@ -1068,4 +1175,85 @@ mod tests {
         assert_eq!(user_transaction.events.len(), 0);
         assert_eq!(txn.info.as_ref().unwrap().changes.len(), 0);
     }
+
+    #[test]
+    fn test_transactions_responses_are_filtered_correctly() {
+        let transactions: Vec<Transaction> = ["0x1234", "0x5678", "0x1234"]
+            .iter()
+            .enumerate()
+            .map(|(version, sender)| Transaction {
+                version: version as u64,
+                txn_data: Some(TxnData::User(UserTransaction {
+                    request: Some(UserTransactionRequest {
+                        sender: sender.to_string(),
+                        ..Default::default()
+                    }),
+                    events: vec![Event {
+                        type_str: "0x1::coin::DepositEvent".to_string(),
+                        ..Default::default()
+                    }],
+                })),
+                info: Some(TransactionInfo::default()),
+                ..Default::default()
+            })
+            .collect();
+        let filter_for = |filter| {
+            BooleanTransactionFilter::new(&BooleanTransactionFilterProto {
+                filter: Some(boolean_transaction_filter::Filter::TransactionFilter(
+                    TransactionFilter {
+                        filter: Some(filter),
+                    },
+                )),
+            })
+            .unwrap()
+        };
+
+        // Only the transactions matching the filter are returned.
+        let sender_filter = filter_for(transaction_filter::Filter::Sender("0x1234".into()));
+        let responses = get_transactions_responses_builder(
+            transactions.clone(),
+            1,
+            &HashSet::new(),
+            Some(&sender_filter),
+        );
+        assert_eq!(responses.len(), 1);
+        let versions: Vec<u64> = responses[0]
+            .transactions
+            .iter()
+            .map(|t| t.version)
+            .collect();
+        assert_eq!(versions, vec![0, 2]);
+        assert_eq!(responses[0].processed_version, Some(2));
+
+        // The ignored senders are wiped before filtering, so their events never match.
+        let event_filter = filter_for(transaction_filter::Filter::EventType(
+            "0x1::coin::DepositEvent".into(),
+        ));
+        let ignore_hash_set: HashSet<String> = vec!["0x1234".to_string()].into_iter().collect();
+        let responses = get_transactions_responses_builder(
+            transactions.clone(),
+            1,
+            &ignore_hash_set,
+            Some(&event_filter),
+        );
+        assert_eq!(responses.len(), 1);
+        let versions: Vec<u64> = responses[0]
+            .transactions
+            .iter()
+            .map(|t| t.version)
+            .collect();
+        assert_eq!(versions, vec![1]);
+        // The last version is reported as processed, although it's filtered out.
+        assert_eq!(responses[0].processed_version, Some(2));
+
+        // No response is built if nothing matches.
+        let success_filter = filter_for(transaction_filter::Filter::Success(false));
+        assert!(get_transactions_responses_builder(
+            transactions,
+            1,
+            &HashSet::new(),
+            Some(&success_filter),
+        )
+        .is_empty());
+    }
 }
@@ -3,12 +3,14 @@
 
 pub mod metrics;
 pub mod processor;
+pub mod verifier;
 
 use anyhow::Result;
 use aptos_indexer_grpc_server_framework::RunnableConfig;
 use aptos_indexer_grpc_utils::{config::IndexerGrpcFileStoreConfig, types::RedisUrl};
 use processor::Processor;
 use serde::{Deserialize, Serialize};
+use verifier::{Verifier, VerifierConfig};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
@@ -19,6 +21,9 @@ pub struct IndexerGrpcFileStoreWorkerConfig {
     pub chain_id: u64,
     #[serde(default = "default_enable_cache_compression")]
     pub enable_cache_compression: bool,
+    /// If set, the worker verifies and backfills the existing files instead of uploading new ones.
+    #[serde(default)]
+    pub verifier_config: Option<VerifierConfig>,
 }
 
 const fn default_enable_cache_compression() -> bool {
@@ -32,6 +37,7 @@ impl IndexerGrpcFileStoreWorkerConfig {
         enable_expensive_logging: Option<bool>,
         chain_id: u64,
         enable_cache_compression: bool,
+        verifier_config: Option<VerifierConfig>,
     ) -> Self {
         Self {
             file_store_config,
@@ -39,6 +45,7 @@ impl IndexerGrpcFileStoreWorkerConfig {
             enable_expensive_logging,
             chain_id,
             enable_cache_compression,
+            verifier_config,
         }
     }
 }
@@ -46,6 +53,20 @@ impl IndexerGrpcFileStoreWorkerConfig {
 #[async_trait::async_trait]
 impl RunnableConfig for IndexerGrpcFileStoreWorkerConfig {
     async fn run(&self) -> Result<()> {
+        if let Some(verifier_config) = &self.verifier_config {
+            let mut verifier = Verifier::new(
+                self.file_store_config.clone(),
+                self.chain_id,
+                verifier_config.clone(),
+            )
+            .await
+            .expect("Failed to create file store verifier");
+            verifier
+                .run()
+                .await
+                .expect("File store verifier exited unexpectedly");
+            return Ok(());
+        }
         let mut processor = Processor::new(
             self.redis_main_instance_address.clone(),
             self.file_store_config.clone(),
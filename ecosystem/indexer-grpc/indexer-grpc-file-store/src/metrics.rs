@@ -1,7 +1,10 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use aptos_metrics_core::{register_int_counter, register_int_gauge, IntCounter, IntGauge};
+use aptos_metrics_core::{
+    register_int_counter, register_int_counter_vec, register_int_gauge, IntCounter, IntCounterVec,
+    IntGauge,
+};
 use once_cell::sync::Lazy;
 
 /// Latest version of transactions that have been stored.
@@ -39,3 +42,31 @@ pub static METADATA_UPLOAD_FAILURE_COUNT: Lazy<IntCounter> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+/// Number of issues that the file store verifier has found, by issue type.
+pub static VERIFIER_ISSUES_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "indexer_grpc_file_store_verifier_issues",
+        "Number of issues that the file store verifier has found",
+        &["issue_type"]
+    )
+    .unwrap()
+});
+
+/// Number of files that the file store verifier has backfilled from the fullnode.
+pub static BACKFILLED_FILES_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "indexer_grpc_file_store_verifier_backfilled_files",
+        "Number of files that the file store verifier has backfilled from the fullnode"
+    )
+    .unwrap()
+});
+
+/// Latest version of transactions that the file store verifier has verified.
+pub static LATEST_VERIFIED_VERSION: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "indexer_grpc_file_store_verifier_latest_verified_version",
+        "Latest version of transactions that the file store verifier has verified",
+    )
+    .unwrap()
+});
@@ -0,0 +1,404 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::metrics::{BACKFILLED_FILES_COUNT, LATEST_VERIFIED_VERSION, VERIFIER_ISSUES_COUNT};
+use anyhow::{bail, ensure, Context, Result};
+use aptos_indexer_grpc_utils::{
+    compression_util::{FileEntry, FILE_ENTRY_TRANSACTION_COUNT},
+    config::IndexerGrpcFileStoreConfig,
+    create_grpc_client,
+    file_store_operator::FileStoreOperator,
+};
+use aptos_protos::{
+    internal::fullnode::v1::{
+        stream_status::StatusType, transactions_from_node_response::Response,
+        GetTransactionsFromNodeRequest,
+    },
+    transaction::v1::Transaction,
+};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, time::Duration};
+use tracing::{error, info, warn};
+use url::Url;
+
+const SERVICE_TYPE: &str = "file_verifier";
+const FILE_FETCH_RETRIES: u8 = 3;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct VerifierConfig {
+    /// The first version to verify. It's rounded down to the start of its file.
+    #[serde(default)]
+    pub starting_version: u64,
+    /// The last version to verify, inclusive. If not set, the verifier keeps up with the
+    /// file store metadata.
+    #[serde(default)]
+    pub ending_version: Option<u64>,
+    /// If set, the files with issues are re-fetched from this fullnode and re-uploaded.
+    /// Otherwise the issues are only reported.
+    #[serde(default)]
+    pub fullnode_grpc_address: Option<Url>,
+    /// The number of files to verify in parallel.
+    #[serde(default = "VerifierConfig::default_max_concurrent_files")]
+    pub max_concurrent_files: usize,
+    /// How long to wait before scanning the files uploaded since the last scan.
+    #[serde(default = "VerifierConfig::default_rescan_interval_secs")]
+    pub rescan_interval_secs: u64,
+}
+
+impl VerifierConfig {
+    const fn default_max_concurrent_files() -> usize {
+        10
+    }
+
+    const fn default_rescan_interval_secs() -> u64 {
+        600
+    }
+}
+
+/// The problem found with a transactions file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileIssue {
+    /// The file couldn't be fetched from the file store.
+    Missing,
+    /// The file couldn't be decoded or didn't match its manifest.
+    Corrupted,
+    /// Some versions of the file are missing or don't belong to it.
+    Gap,
+    /// Some versions appear more than once in the file.
+    Duplicate,
+    /// The versions are all there but not in order.
+    OutOfOrder,
+}
+
+impl FileIssue {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FileIssue::Missing => "missing",
+            FileIssue::Corrupted => "corrupted",
+            FileIssue::Gap => "gap",
+            FileIssue::Duplicate => "duplicate",
+            FileIssue::OutOfOrder => "out_of_order",
+        }
+    }
+}
+
+/// Checks that the versions of a file are exactly `start_version..start_version + 1000`, in order.
+pub fn check_file_versions(start_version: u64, versions: &[u64]) -> Option<FileIssue> {
+    let mut sorted = versions.to_vec();
+    sorted.sort_unstable();
+    let len_before_dedup = sorted.len();
+    sorted.dedup();
+    if sorted.len() != len_before_dedup {
+        return Some(FileIssue::Duplicate);
+    }
+    let expected = start_version..start_version + FILE_ENTRY_TRANSACTION_COUNT;
+    if sorted.len() != FILE_ENTRY_TRANSACTION_COUNT as usize || !sorted.iter().copied().eq(expected)
+    {
+        return Some(FileIssue::Gap);
+    }
+    if sorted.as_slice() != versions {
+        return Some(FileIssue::OutOfOrder);
+    }
+    None
+}
+
+/// Verifier scans the transactions files in the file store, detects the missing, corrupted or
+/// incomplete ones and, if a fullnode is configured, re-fetches and re-uploads them.
+pub struct Verifier {
+    file_store_operator: Box<dyn FileStoreOperator>,
+    chain_id: u64,
+    config: VerifierConfig,
+}
+
+impl Verifier {
+    pub async fn new(
+        file_store_config: IndexerGrpcFileStoreConfig,
+        chain_id: u64,
+        config: VerifierConfig,
+    ) -> Result<Self> {
+        ensure!(
+            config.max_concurrent_files > 0,
+            "max_concurrent_files must be greater than 0."
+        );
+        let file_store_operator: Box<dyn FileStoreOperator> = file_store_config.create();
+        file_store_operator.verify_storage_bucket_existence().await;
+        Ok(Self {
+            file_store_operator,
+            chain_id,
+            config,
+        })
+    }
+
+    /// Starts the verification. The steps are
+    /// 1. Check chain id against the file store metadata
+    /// 2. Verify every file between the last scanned version and the file store version
+    /// 3. Backfill the files with issues, if a fullnode is configured
+    /// 4. Sleep and rescan the files uploaded since; files that failed to backfill are retried
+    pub async fn run(&mut self) -> Result<()> {
+        let mut next_version = self.config.starting_version / FILE_ENTRY_TRANSACTION_COUNT
+            * FILE_ENTRY_TRANSACTION_COUNT;
+        // The end of the range to verify, exclusive and aligned to the file boundary.
+        let ending_bound = self.config.ending_version.map(|version| {
+            (version / FILE_ENTRY_TRANSACTION_COUNT + 1) * FILE_ENTRY_TRANSACTION_COUNT
+        });
+        let mut pending_files: Vec<u64> = vec![];
+        loop {
+            let metadata = self
+                .file_store_operator
+                .get_file_store_metadata()
+                .await
+                .context("File store metadata not found.")?;
+            ensure!(metadata.chain_id == self.chain_id, "Chain ID mismatch.");
+
+            // The metadata version is the first version that's not uploaded yet.
+            let scan_end = match ending_bound {
+                Some(ending_bound) => ending_bound.min(metadata.version),
+                None => metadata.version,
+            };
+            let mut file_versions = std::mem::take(&mut pending_files);
+            if next_version < scan_end {
+                file_versions.extend(
+                    (next_version..scan_end).step_by(FILE_ENTRY_TRANSACTION_COUNT as usize),
+                );
+            }
+
+            let scan_start_time = std::time::Instant::now();
+            let files_count = file_versions.len();
+            let issues = self.scan_files(file_versions).await;
+            for (version, issue) in &issues {
+                VERIFIER_ISSUES_COUNT
+                    .with_label_values(&[issue.as_str()])
+                    .inc();
+                warn!(
+                    service_type = SERVICE_TYPE,
+                    start_version = version,
+                    issue = issue.as_str(),
+                    "[File verifier] Found an issue with a transactions file."
+                );
+            }
+
+            let mut backfilled_count = 0;
+            if let Some(fullnode_grpc_address) = &self.config.fullnode_grpc_address {
+                for (version, issue) in issues.iter() {
+                    match self.backfill_file(fullnode_grpc_address, *version).await {
+                        Ok(()) => {
+                            BACKFILLED_FILES_COUNT.inc();
+                            backfilled_count += 1;
+                            info!(
+                                service_type = SERVICE_TYPE,
+                                start_version = version,
+                                issue = issue.as_str(),
+                                "[File verifier] Backfilled a transactions file."
+                            );
+                        },
+                        Err(err) => {
+                            error!(
+                                service_type = SERVICE_TYPE,
+                                start_version = version,
+                                error = ?err,
+                                "[File verifier] Failed to backfill a transactions file. Retrying in the next scan."
+                            );
+                            pending_files.push(*version);
+                        },
+                    }
+                }
+            }
+
+            if next_version < scan_end {
+                next_version = scan_end;
+                LATEST_VERIFIED_VERSION.set(scan_end as i64 - 1);
+            }
+            info!(
+                service_type = SERVICE_TYPE,
+                files_count = files_count,
+                issues_count = issues.len(),
+                backfilled_count = backfilled_count,
+                verified_up_to = next_version,
+                duration_in_secs = scan_start_time.elapsed().as_secs_f64(),
+                "[File verifier] Scan finished."
+            );
+
+            // Keep running even once the ending version is reached, so the failed backfills are
+            // retried and the server stays up for its health checks.
+            tokio::time::sleep(Duration::from_secs(self.config.rescan_interval_secs)).await;
+        }
+    }
+
+    /// Verifies the given files in parallel and returns the ones with issues, sorted by version.
+    async fn scan_files(&self, file_versions: Vec<u64>) -> Vec<(u64, FileIssue)> {
+        let mut issues: Vec<(u64, FileIssue)> = futures::stream::iter(file_versions)
+            .map(|version| async move {
+                self.verify_file(version)
+                    .await
+                    .map(|issue| (version, issue))
+            })
+            .buffer_unordered(self.config.max_concurrent_files)
+            .filter_map(|issue| async move { issue })
+            .collect()
+            .await;
+        issues.sort_by_key(|(version, _)| *version);
+        issues
+    }
+
+    /// Fetches, verifies and decodes a single file. Returns its issue, if any.
+    async fn verify_file(&self, version: u64) -> Option<FileIssue> {
+        let bytes = match self
+            .file_store_operator
+            .get_raw_file_with_retries(version, FILE_FETCH_RETRIES)
+            .await
+        {
+            Ok(bytes) => bytes,
+            Err(_) => return Some(FileIssue::Missing),
+        };
+        if self
+            .file_store_operator
+            .verify_raw_file(version, &bytes)
+            .await
+            .is_err()
+        {
+            return Some(FileIssue::Corrupted);
+        }
+        let storage_format = self.file_store_operator.storage_format();
+        // Decoding panics on malformed files, so it's isolated in its own task.
+        let transactions_in_storage = match tokio::task::spawn_blocking(move || {
+            FileEntry::new(bytes, storage_format).into_transactions_in_storage()
+        })
+        .await
+        {
+            Ok(transactions_in_storage) => transactions_in_storage,
+            Err(_) => return Some(FileIssue::Corrupted),
+        };
+        if transactions_in_storage.starting_version != Some(version) {
+            return Some(FileIssue::Gap);
+        }
+        let versions: Vec<u64> = transactions_in_storage
+            .transactions
+            .iter()
+            .map(|transaction| transaction.version)
+            .collect();
+        check_file_versions(version, &versions)
+    }
+
+    /// Re-fetches the transactions of a file from the fullnode and re-uploads the file.
+    async fn backfill_file(&self, fullnode_grpc_address: &Url, version: u64) -> Result<()> {
+        let mut rpc_client = create_grpc_client(fullnode_grpc_address.clone()).await;
+        let request = tonic::Request::new(GetTransactionsFromNodeRequest {
+            starting_version: Some(version),
+            transactions_count: Some(FILE_ENTRY_TRANSACTION_COUNT),
+        });
+        let mut resp_stream = rpc_client
+            .get_transactions_from_node(request)
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to get transactions from node at starting version {}",
+                    version
+                )
+            })?
+            .into_inner();
+
+        // Data chunks may arrive out of order, so the transactions are keyed by version.
+        let mut transactions: BTreeMap<u64, Transaction> = BTreeMap::new();
+        while let Some(received) = resp_stream.next().await {
+            let received = received.context("Error while streaming from the fullnode.")?;
+            if received.chain_id as u64 != self.chain_id {
+                bail!(
+                    "Chain ID mismatch: the fullnode is on chain {}, the file store on chain {}.",
+                    received.chain_id,
+                    self.chain_id
+                );
+            }
+            match received.response {
+                Some(Response::Data(data)) => {
+                    for transaction in data.transactions {
+                        transactions.insert(transaction.version, transaction);
+                    }
+                },
+                Some(Response::Status(status)) => {
+                    if matches!(StatusType::try_from(status.r#type), Ok(StatusType::Init))
+                        && status.start_version != version
+                    {
+                        bail!(
+                            "The fullnode started streaming at version {} instead of {}.",
+                            status.start_version,
+                            version
+                        );
+                    }
+                },
+                None => {},
+            }
+            if transactions.len() >= FILE_ENTRY_TRANSACTION_COUNT as usize {
+                break;
+            }
+        }
+
+        let transactions: Vec<Transaction> = transactions
+            .into_values()
+            .filter(|transaction| transaction.version < version + FILE_ENTRY_TRANSACTION_COUNT)
+            .collect();
+        let versions: Vec<u64> = transactions
+            .iter()
+            .map(|transaction| transaction.version)
+            .collect();
+        if let Some(issue) = check_file_versions(version, &versions) {
+            bail!(
+                "The transactions fetched from the fullnode are incomplete: {}.",
+                issue.as_str()
+            );
+        }
+
+        let mut file_store_operator = self.file_store_operator.clone_box();
+        file_store_operator
+            .upload_transaction_batch(self.chain_id, transactions)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_versions(start_version: u64) -> Vec<u64> {
+        (start_version..start_version + FILE_ENTRY_TRANSACTION_COUNT).collect()
+    }
+
+    #[test]
+    fn test_check_file_versions_complete() {
+        assert_eq!(check_file_versions(2000, &file_versions(2000)), None);
+    }
+
+    #[test]
+    fn test_check_file_versions_gap() {
+        let mut versions = file_versions(2000);
+        versions.remove(500);
+        assert_eq!(check_file_versions(2000, &versions), Some(FileIssue::Gap));
+        // The versions of another file.
+        assert_eq!(
+            check_file_versions(2000, &file_versions(3000)),
+            Some(FileIssue::Gap)
+        );
+    }
+
+    #[test]
+    fn test_check_file_versions_duplicate() {
+        let mut versions = file_versions(0);
+        versions[10] = 9;
+        assert_eq!(
+            check_file_versions(0, &versions),
+            Some(FileIssue::Duplicate)
+        );
+    }
+
+    #[test]
+    fn test_check_file_versions_out_of_order() {
+        let mut versions = file_versions(0);
+        versions.swap(1, 2);
+        assert_eq!(
+            check_file_versions(0, &versions),
+            Some(FileIssue::OutOfOrder)
+        );
+    }
+}
@@ -2,13 +2,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{stream_coordinator::IndexerStreamCoordinator, ServiceContext};
+use aptos_indexer_grpc_utils::{
+    constants::FILTERED_STREAM_PROGRESS_INTERVAL, transaction_filter::BooleanTransactionFilter,
+};
 use aptos_logger::{error, info};
 use aptos_protos::{
     indexer::v1::{raw_data_server::RawData, GetTransactionsRequest, TransactionsResponse},
     internal::fullnode::v1::transactions_from_node_response,
 };
 use futures::Stream;
-use std::pin::Pin;
+use std::{pin::Pin, time::Instant};
 use tokio::sync::mpsc;
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status};
@@ -44,6 +47,12 @@ impl RawData for LocalnetDataService {
         let output_batch_size = self.service_context.output_batch_size;
         let ledger_chain_id = context.chain_id().id();
         let transactions_count = r.transactions_count;
+        let transaction_filter = r
+            .transaction_filter
+            .as_ref()
+            .map(BooleanTransactionFilter::new)
+            .transpose()
+            .map_err(|e| Status::invalid_argument(format!("Invalid transaction filter: {}", e)))?;
         // Creates a channel to send the stream to the client
         let (tx, mut rx) = mpsc::channel(TRANSACTION_CHANNEL_SIZE);
         let (external_service_tx, external_service_rx) = mpsc::channel(TRANSACTION_CHANNEL_SIZE);
@@ -83,6 +92,7 @@ impl RawData for LocalnetDataService {
         });
         tokio::spawn(async move {
             let mut response_transactions_count = transactions_count;
+            let mut last_response_time = Instant::now();
             while let Some(response) = rx.recv().await {
                 if let Some(count) = response_transactions_count.as_ref() {
                     if *count == 0 {
@@ -90,22 +100,35 @@ impl RawData for LocalnetDataService {
                     }
                 }
 
-                let response = response.map(|t| TransactionsResponse {
-                    chain_id: Some(ledger_chain_id as u64),
-                    transactions: match t.response.expect("Response must be set") {
-                        transactions_from_node_response::Response::Data(transaction_output) => {
-                            let mut transactions = transaction_output.transactions;
-                            let current_transactions_count = transactions.len() as u64;
-                            if let Some(count) = response_transactions_count.as_mut() {
-                                transactions =
-                                    transactions.into_iter().take(*count as usize).collect();
-                                *count = count.saturating_sub(current_transactions_count);
-                            }
-                            transactions
-                        },
-                        _ => panic!("Unexpected response type."),
+                let response = response.map(|t| match t.response.expect("Response must be set") {
+                    transactions_from_node_response::Response::Data(transaction_output) => {
+                        let mut transactions = transaction_output.transactions;
+                        let current_transactions_count = transactions.len() as u64;
+                        if let Some(count) = response_transactions_count.as_mut() {
+                            transactions = transactions.into_iter().take(*count as usize).collect();
+                            *count = count.saturating_sub(current_transactions_count);
+                        }
+                        let processed_version = transactions.last().map(|t| t.version);
+                        TransactionsResponse {
+                            chain_id: Some(ledger_chain_id as u64),
+                            transactions: match transaction_filter.as_ref() {
+                                Some(filter) => filter.filter_transactions(transactions),
+                                None => transactions,
+                            },
+                            processed_version,
+                        }
                     },
+                    _ => panic!("Unexpected response type."),
                 });
+                // Skip the batches in which no transaction matches the filter, but report the
+                // progress through them periodically.
+                if transaction_filter.is_some()
+                    && matches!(&response, Ok(response) if response.transactions.is_empty())
+                    && last_response_time.elapsed() < FILTERED_STREAM_PROGRESS_INTERVAL
+                {
+                    continue;
+                }
+                last_response_time = Instant::now();
                 match external_service_tx.send(response).await {
                     Ok(_) => {},
                     Err(e) => {
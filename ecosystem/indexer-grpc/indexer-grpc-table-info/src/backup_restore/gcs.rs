@@ -3,7 +3,9 @@
 
 use super::{
     fs_ops::{create_tar_gz, unpack_tar_gz},
-    generate_blob_name, BackupRestoreMetadata, JSON_FILE_TYPE, METADATA_FILE_NAME, TAR_FILE_TYPE,
+    generate_blob_name,
+    storage::SnapshotStorage,
+    BackupRestoreMetadata, JSON_FILE_TYPE, METADATA_FILE_NAME, TAR_FILE_TYPE,
 };
 use anyhow::Context;
 use aptos_db_indexer::db_v2::IndexerAsyncV2;
@@ -14,8 +16,10 @@ use google_cloud_storage::{
     http::{
         buckets::get::GetBucketRequest,
         objects::{
+            delete::DeleteObjectRequest,
             download::Range,
             get::GetObjectRequest,
+            list::ListObjectsRequest,
             upload::{Media, UploadObjectRequest, UploadType},
         },
         Error,
@@ -25,7 +29,7 @@ use hyper::StatusCode;
 use std::{
     borrow::Cow::Borrowed,
     env,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{
         atomic::{AtomicU64, Ordering},
         Arc,
@@ -301,3 +305,153 @@ impl GcsBackupRestoreOperator {
         self.metadata_epoch.load(Ordering::Relaxed)
     }
 }
+
+fn is_not_found(err: &Error) -> bool {
+    matches!(err, Error::HttpClient(err) if err.status() == Some(StatusCode::NOT_FOUND))
+        || matches!(err, Error::Response(err) if err.code == 404)
+}
+
+#[async_trait::async_trait]
+impl SnapshotStorage for GcsBackupRestoreOperator {
+    fn storage_name(&self) -> &str {
+        "gcs"
+    }
+
+    async fn read_object(&self, name: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        match self
+            .gcs_client
+            .download_object(
+                &GetObjectRequest {
+                    bucket: self.bucket_name.clone(),
+                    object: name.to_string(),
+                    ..Default::default()
+                },
+                &Range::default(),
+            )
+            .await
+        {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(err) if is_not_found(&err) => Ok(None),
+            Err(err) => Err(anyhow::Error::new(err))
+                .with_context(|| format!("Failed to read object {}", name)),
+        }
+    }
+
+    async fn write_object(&self, name: &str, bytes: Vec<u8>) -> anyhow::Result<()> {
+        loop {
+            match self
+                .gcs_client
+                .upload_object(
+                    &UploadObjectRequest {
+                        bucket: self.bucket_name.clone(),
+                        ..Default::default()
+                    },
+                    bytes.clone(),
+                    &UploadType::Simple(Media {
+                        name: name.to_string().into(),
+                        content_type: Borrowed(JSON_FILE_TYPE),
+                        content_length: None,
+                    }),
+                )
+                .await
+            {
+                Ok(_) => return Ok(()),
+                // Same rate limit as in `update_metadata`: one write per second on the same object.
+                Err(Error::Response(err)) if (err.is_retriable() && err.code == 429) => {
+                    tokio::time::sleep(Duration::from_millis(500)).await;
+                },
+                Err(err) => anyhow::bail!("Failed to write object {}: {}", name, err),
+            }
+        }
+    }
+
+    async fn upload_file(&self, name: &str, local_path: &Path) -> anyhow::Result<()> {
+        let file = File::open(local_path)
+            .await
+            .with_context(|| format!("Failed to open {:?} for reading", local_path))?;
+        let file_stream = tokio_util::io::ReaderStream::new(file);
+        self.gcs_client
+            .upload_streamed_object(
+                &UploadObjectRequest {
+                    bucket: self.bucket_name.clone(),
+                    ..Default::default()
+                },
+                file_stream,
+                &UploadType::Simple(Media {
+                    name: name.to_string().into(),
+                    content_type: Borrowed(TAR_FILE_TYPE),
+                    content_length: None,
+                }),
+            )
+            .await
+            .with_context(|| format!("Failed to upload object {}", name))?;
+        Ok(())
+    }
+
+    async fn download_file(&self, name: &str, local_path: &Path) -> anyhow::Result<()> {
+        let mut stream = self
+            .gcs_client
+            .download_streamed_object(
+                &GetObjectRequest {
+                    bucket: self.bucket_name.clone(),
+                    object: name.to_string(),
+                    ..Default::default()
+                },
+                &Range::default(),
+            )
+            .await
+            .with_context(|| format!("Failed to download object {}", name))?;
+        let mut file = File::create(local_path).await?;
+        while let Some(chunk) = stream.next().await {
+            file.write_all(&chunk?).await?;
+        }
+        file.sync_all().await?;
+        Ok(())
+    }
+
+    async fn list_objects(&self, prefix: &str) -> anyhow::Result<Vec<String>> {
+        let mut names = vec![];
+        let mut page_token = None;
+        loop {
+            let response = self
+                .gcs_client
+                .list_objects(&ListObjectsRequest {
+                    bucket: self.bucket_name.clone(),
+                    prefix: Some(prefix.to_string()),
+                    page_token,
+                    ..Default::default()
+                })
+                .await
+                .with_context(|| format!("Failed to list objects with prefix {}", prefix))?;
+            names.extend(
+                response
+                    .items
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|object| object.name),
+            );
+            match response.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    async fn delete_object(&self, name: &str) -> anyhow::Result<()> {
+        match self
+            .gcs_client
+            .delete_object(&DeleteObjectRequest {
+                bucket: self.bucket_name.clone(),
+                object: name.to_string(),
+                ..Default::default()
+            })
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(err) if is_not_found(&err) => Ok(()),
+            Err(err) => anyhow::bail!("Failed to delete object {}: {}", name, err),
+        }
+    }
+}
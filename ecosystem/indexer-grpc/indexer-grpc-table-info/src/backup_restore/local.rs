@@ -0,0 +1,150 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::storage::SnapshotStorage;
+use anyhow::{Context, Result};
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+use tokio::fs;
+
+/// LocalSnapshotStorage stores the snapshots in a local (or mounted) directory.
+pub struct LocalSnapshotStorage {
+    path: PathBuf,
+}
+
+impl LocalSnapshotStorage {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Writes to a temporary file first, so that a partially written object is never observed.
+    async fn prepare_object_path(&self, name: &str) -> Result<(PathBuf, PathBuf)> {
+        let object_path = self.path.join(name);
+        if let Some(parent) = object_path.parent() {
+            fs::create_dir_all(parent)
+                .await
+                .with_context(|| format!("Failed to create directory {:?}", parent))?;
+        }
+        let temp_path = object_path.with_extension("partial");
+        Ok((object_path, temp_path))
+    }
+}
+
+#[async_trait::async_trait]
+impl SnapshotStorage for LocalSnapshotStorage {
+    fn storage_name(&self) -> &str {
+        "local_fs"
+    }
+
+    async fn read_object(&self, name: &str) -> Result<Option<Vec<u8>>> {
+        match fs::read(self.path.join(name)).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err).with_context(|| format!("Failed to read object {}", name)),
+        }
+    }
+
+    async fn write_object(&self, name: &str, bytes: Vec<u8>) -> Result<()> {
+        let (object_path, temp_path) = self.prepare_object_path(name).await?;
+        fs::write(&temp_path, bytes)
+            .await
+            .with_context(|| format!("Failed to write object {}", name))?;
+        fs::rename(&temp_path, &object_path).await?;
+        Ok(())
+    }
+
+    async fn upload_file(&self, name: &str, local_path: &Path) -> Result<()> {
+        let (object_path, temp_path) = self.prepare_object_path(name).await?;
+        fs::copy(local_path, &temp_path)
+            .await
+            .with_context(|| format!("Failed to copy {:?} to object {}", local_path, name))?;
+        fs::rename(&temp_path, &object_path).await?;
+        Ok(())
+    }
+
+    async fn download_file(&self, name: &str, local_path: &Path) -> Result<()> {
+        fs::copy(self.path.join(name), local_path)
+            .await
+            .with_context(|| format!("Failed to copy object {} to {:?}", name, local_path))?;
+        Ok(())
+    }
+
+    async fn list_objects(&self, prefix: &str) -> Result<Vec<String>> {
+        // Only the directory part of the prefix is walked; the objects are flat within it.
+        let (dir, file_prefix) = match prefix.rsplit_once('/') {
+            Some((dir, _)) => (self.path.join(dir), format!("{}/", dir)),
+            None => (self.path.clone(), String::new()),
+        };
+        let mut entries = match fs::read_dir(&dir).await {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err).with_context(|| format!("Failed to list {:?}", dir)),
+        };
+        let mut names = vec![];
+        while let Some(entry) = entries.next_entry().await? {
+            if !entry.file_type().await?.is_file() {
+                continue;
+            }
+            let name = format!("{}{}", file_prefix, entry.file_name().to_string_lossy());
+            if name.starts_with(prefix) {
+                names.push(name);
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    async fn delete_object(&self, name: &str) -> Result<()> {
+        match fs::remove_file(self.path.join(name)).await {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err).with_context(|| format!("Failed to delete object {}", name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_local_snapshot_storage() -> Result<()> {
+        let dir = tempdir()?;
+        let storage = LocalSnapshotStorage::new(dir.path().to_path_buf());
+
+        assert_eq!(storage.read_object("metadata.json").await?, None);
+        storage
+            .write_object("metadata.json", b"metadata".to_vec())
+            .await?;
+        assert_eq!(
+            storage.read_object("metadata.json").await?,
+            Some(b"metadata".to_vec())
+        );
+
+        storage
+            .write_object("files/1.tar.gz", b"snapshot 1".to_vec())
+            .await?;
+        storage
+            .write_object("files/2.tar.gz", b"snapshot 2".to_vec())
+            .await?;
+        assert_eq!(
+            storage.list_objects("files/").await?,
+            vec!["files/1.tar.gz".to_string(), "files/2.tar.gz".to_string()]
+        );
+
+        let local_path = dir.path().join("downloaded");
+        storage.download_file("files/2.tar.gz", &local_path).await?;
+        assert_eq!(fs::read(&local_path).await?, b"snapshot 2".to_vec());
+
+        storage.delete_object("files/1.tar.gz").await?;
+        storage.delete_object("files/1.tar.gz").await?;
+        assert_eq!(
+            storage.list_objects("files/").await?,
+            vec!["files/2.tar.gz".to_string()]
+        );
+        Ok(())
+    }
+}
@@ -5,6 +5,9 @@ use serde::{Deserialize, Serialize};
 
 pub mod fs_ops;
 pub mod gcs;
+pub mod local;
+pub mod snapshot;
+pub mod storage;
 
 pub const FILE_FOLDER_NAME: &str = "files";
 pub const METADATA_FILE_NAME: &str = "metadata.json";
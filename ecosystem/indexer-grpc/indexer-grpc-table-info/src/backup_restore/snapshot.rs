@@ -0,0 +1,474 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    fs_ops::{create_tar_gz, rename_db_folders_and_cleanup, unpack_tar_gz},
+    gcs::GcsBackupRestoreOperator,
+    generate_blob_name,
+    local::LocalSnapshotStorage,
+    storage::SnapshotStorage,
+    BackupRestoreMetadata, FILE_FOLDER_NAME, METADATA_FILE_NAME,
+};
+use anyhow::{ensure, Context, Result};
+use aptos_config::config::{RocksdbConfig, TableInfoSnapshotConfig, TableInfoSnapshotStorage};
+use aptos_db_indexer::{db_ops::open_db, db_v2::IndexerAsyncV2};
+use aptos_logger::{info, warn};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    fs::File,
+    io::Read,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+};
+use tokio::{fs, task};
+
+const MANIFEST_FILE_SUFFIX: &str = ".manifest.json";
+const DOWNLOADED_SNAPSHOT_FILE_NAME: &str = "table_info_snapshot.tar.gz";
+const RESTORED_DB_FOLDER_NAME: &str = "table_info_restored_db";
+
+#[inline]
+pub fn generate_manifest_name(epoch: u64) -> String {
+    format!("{}/{}{}", FILE_FOLDER_NAME, epoch, MANIFEST_FILE_SUFFIX)
+}
+
+/// SnapshotManifest is uploaded next to each snapshot, e.g., `files/42.manifest.json`.
+/// A snapshot is only restored if it matches its manifest.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SnapshotManifest {
+    pub chain_id: u64,
+    pub epoch: u64,
+    // The next version the table info service has to process after restoring the snapshot.
+    pub next_version: u64,
+    // The size of the snapshot tar.gz file in bytes.
+    pub size: u64,
+    // The hex encoded SHA-256 hash of the snapshot tar.gz file.
+    pub sha256: String,
+}
+
+/// A db checkpoint that's taken but not uploaded yet.
+pub struct PreparedSnapshot {
+    pub epoch: u64,
+    pub next_version: u64,
+    pub path: PathBuf,
+}
+
+/// SnapshotOperator backs up and restores the table info db to and from any SnapshotStorage.
+/// Snapshots are versioned by epoch and the latest `snapshots_to_keep` of them are kept.
+pub struct SnapshotOperator {
+    storage: Box<dyn SnapshotStorage>,
+    chain_id: u64,
+    snapshots_to_keep: usize,
+    // Epoch of the latest snapshot, so that there is at most one snapshot per epoch.
+    latest_epoch: AtomicU64,
+    backup_in_progress: AtomicBool,
+}
+
+impl SnapshotOperator {
+    pub fn new(storage: Box<dyn SnapshotStorage>, chain_id: u64, snapshots_to_keep: usize) -> Self {
+        Self {
+            storage,
+            chain_id,
+            snapshots_to_keep: snapshots_to_keep.max(1),
+            latest_epoch: AtomicU64::new(0),
+            backup_in_progress: AtomicBool::new(false),
+        }
+    }
+
+    pub async fn from_config(config: &TableInfoSnapshotConfig, chain_id: u64) -> Result<Self> {
+        let storage: Box<dyn SnapshotStorage> = match &config.storage {
+            TableInfoSnapshotStorage::LocalFs(path) => {
+                Box::new(LocalSnapshotStorage::new(path.clone()))
+            },
+            TableInfoSnapshotStorage::Gcs(bucket_name) => {
+                let operator = GcsBackupRestoreOperator::new(bucket_name.clone()).await;
+                operator.verify_storage_bucket_existence().await;
+                Box::new(operator)
+            },
+        };
+        let operator = Self::new(storage, chain_id, config.snapshots_to_keep);
+        if let Some(metadata) = operator.get_metadata().await? {
+            operator
+                .latest_epoch
+                .store(metadata.epoch, Ordering::Relaxed);
+        }
+        Ok(operator)
+    }
+
+    pub async fn get_metadata(&self) -> Result<Option<BackupRestoreMetadata>> {
+        let metadata = match self.storage.read_object(METADATA_FILE_NAME).await? {
+            Some(bytes) => BackupRestoreMetadata::from(bytes),
+            None => return Ok(None),
+        };
+        ensure!(metadata.chain_id == self.chain_id, "Chain ID mismatch.");
+        Ok(Some(metadata))
+    }
+
+    /// Lists the manifests of the snapshots in the storage, the latest first.
+    /// Snapshots without a manifest, e.g., the ones uploaded by older versions, are ignored.
+    pub async fn list_snapshot_manifests(&self) -> Result<Vec<SnapshotManifest>> {
+        let mut manifests = vec![];
+        for name in self
+            .storage
+            .list_objects(&format!("{}/", FILE_FOLDER_NAME))
+            .await?
+        {
+            if !name.ends_with(MANIFEST_FILE_SUFFIX) {
+                continue;
+            }
+            let bytes = match self.storage.read_object(&name).await? {
+                Some(bytes) => bytes,
+                None => continue,
+            };
+            match serde_json::from_slice::<SnapshotManifest>(&bytes) {
+                Ok(manifest) if manifest.chain_id == self.chain_id => manifests.push(manifest),
+                Ok(_) => warn!(
+                    manifest = name,
+                    "[Table Info] Snapshot of another chain ignored"
+                ),
+                Err(err) => warn!(
+                    manifest = name,
+                    error = ?err,
+                    "[Table Info] Malformed snapshot manifest ignored"
+                ),
+            }
+        }
+        manifests.sort_by(|a, b| b.epoch.cmp(&a.epoch));
+        Ok(manifests)
+    }
+
+    /// Returns true if there is no snapshot of this epoch yet and no backup in progress.
+    pub fn should_backup(&self, epoch: u64) -> bool {
+        epoch > self.latest_epoch.load(Ordering::Relaxed)
+            && !self.backup_in_progress.load(Ordering::Relaxed)
+    }
+
+    /// Takes a checkpoint of the db. This has to be called while the db is not being written to,
+    /// so that the checkpoint matches the next version; the upload can then happen concurrently.
+    pub fn prepare_snapshot(
+        &self,
+        epoch: u64,
+        indexer_async_v2: &IndexerAsyncV2,
+        snapshot_path: PathBuf,
+    ) -> Result<PreparedSnapshot> {
+        ensure!(
+            !self.backup_in_progress.swap(true, Ordering::Relaxed),
+            "Another table info backup is in progress."
+        );
+        let next_version = indexer_async_v2.next_version();
+        indexer_async_v2
+            .create_checkpoint(&snapshot_path)
+            .with_context(|| format!("DB checkpoint failed at epoch {}", epoch))
+            .map_err(|err| {
+                self.backup_in_progress.store(false, Ordering::Relaxed);
+                err
+            })?;
+        Ok(PreparedSnapshot {
+            epoch,
+            next_version,
+            path: snapshot_path,
+        })
+    }
+
+    /// Uploads a prepared snapshot with its manifest, points the metadata to it and deletes the
+    /// snapshots beyond `snapshots_to_keep`. The local checkpoint is removed in any case.
+    pub async fn upload_snapshot(&self, snapshot: PreparedSnapshot) -> Result<SnapshotManifest> {
+        let snapshot_path = snapshot.path.clone();
+        let result = self.upload_snapshot_impl(snapshot).await;
+        fs::remove_dir_all(&snapshot_path).await.unwrap_or(());
+        self.backup_in_progress.store(false, Ordering::Relaxed);
+        result
+    }
+
+    async fn upload_snapshot_impl(&self, snapshot: PreparedSnapshot) -> Result<SnapshotManifest> {
+        let epoch = snapshot.epoch;
+        let snapshot_path = snapshot.path.clone();
+        let tar_file = task::spawn_blocking(move || {
+            let tar_file = create_tar_gz(snapshot_path, &epoch.to_string())?;
+            let (size, sha256) = hash_file(&tar_file)?;
+            Ok::<_, anyhow::Error>((tar_file, size, sha256))
+        })
+        .await?;
+        let (tar_file, size, sha256) = tar_file.context("Failed to create the snapshot tar.gz")?;
+
+        let manifest = SnapshotManifest {
+            chain_id: self.chain_id,
+            epoch,
+            next_version: snapshot.next_version,
+            size,
+            sha256,
+        };
+        self.storage
+            .upload_file(&generate_blob_name(epoch), &tar_file)
+            .await?;
+        // The manifest is written after the snapshot, so a listed manifest always has its snapshot.
+        self.storage
+            .write_object(
+                &generate_manifest_name(epoch),
+                serde_json::to_vec(&manifest).unwrap(),
+            )
+            .await?;
+        self.storage
+            .write_object(
+                METADATA_FILE_NAME,
+                serde_json::to_vec(&BackupRestoreMetadata::new(self.chain_id, epoch)).unwrap(),
+            )
+            .await?;
+        self.latest_epoch.store(epoch, Ordering::Relaxed);
+        info!(
+            epoch = epoch,
+            next_version = manifest.next_version,
+            size = size,
+            storage = self.storage.storage_name(),
+            "[Table Info] Snapshot uploaded"
+        );
+
+        if let Err(err) = self.prune_snapshots().await {
+            warn!(error = ?err, "[Table Info] Failed to delete old snapshots");
+        }
+        Ok(manifest)
+    }
+
+    async fn prune_snapshots(&self) -> Result<()> {
+        let manifests = self.list_snapshot_manifests().await?;
+        for manifest in manifests.iter().skip(self.snapshots_to_keep) {
+            // The manifest goes first, so that a snapshot is never listed without its file.
+            self.storage
+                .delete_object(&generate_manifest_name(manifest.epoch))
+                .await?;
+            self.storage
+                .delete_object(&generate_blob_name(manifest.epoch))
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Restores the latest snapshot that passes verification, if it's ahead of the local db.
+    /// Older snapshots are tried if the latest one fails. Returns the manifest of the restored
+    /// snapshot, or None if the local db is already up to date.
+    pub async fn restore_latest_snapshot(
+        &self,
+        db_path: &Path,
+        base_path: &Path,
+        local_next_version: u64,
+        rocksdb_config: &RocksdbConfig,
+    ) -> Result<Option<SnapshotManifest>> {
+        for manifest in self.list_snapshot_manifests().await? {
+            if manifest.next_version <= local_next_version {
+                return Ok(None);
+            }
+            match self
+                .restore_snapshot(&manifest, db_path, base_path, rocksdb_config)
+                .await
+            {
+                Ok(()) => return Ok(Some(manifest)),
+                Err(err) => warn!(
+                    epoch = manifest.epoch,
+                    error = ?err,
+                    "[Table Info] Failed to restore snapshot, trying an older one"
+                ),
+            }
+        }
+        Ok(None)
+    }
+
+    /// Downloads a snapshot, verifies it against its manifest and replaces the db with it.
+    pub async fn restore_snapshot(
+        &self,
+        manifest: &SnapshotManifest,
+        db_path: &Path,
+        base_path: &Path,
+        rocksdb_config: &RocksdbConfig,
+    ) -> Result<()> {
+        ensure!(manifest.chain_id == self.chain_id, "Chain ID mismatch.");
+        let temp_file_path = base_path.join(DOWNLOADED_SNAPSHOT_FILE_NAME);
+        let restored_db_path = base_path.join(RESTORED_DB_FOLDER_NAME);
+        let result = self
+            .restore_snapshot_impl(
+                manifest,
+                db_path,
+                &temp_file_path,
+                &restored_db_path,
+                rocksdb_config,
+            )
+            .await;
+        fs::remove_file(&temp_file_path).await.unwrap_or(());
+        fs::remove_dir_all(&restored_db_path).await.unwrap_or(());
+        result
+    }
+
+    async fn restore_snapshot_impl(
+        &self,
+        manifest: &SnapshotManifest,
+        db_path: &Path,
+        temp_file_path: &Path,
+        restored_db_path: &Path,
+        rocksdb_config: &RocksdbConfig,
+    ) -> Result<()> {
+        self.storage
+            .download_file(&generate_blob_name(manifest.epoch), temp_file_path)
+            .await?;
+
+        let manifest_clone = manifest.clone();
+        let temp_file_path = temp_file_path.to_path_buf();
+        let restored_db_path = restored_db_path.to_path_buf();
+        let rocksdb_config = *rocksdb_config;
+        let restored_db_path_clone = restored_db_path.clone();
+        task::spawn_blocking(move || {
+            let (size, sha256) = hash_file(&temp_file_path)?;
+            ensure!(
+                size == manifest_clone.size && sha256 == manifest_clone.sha256,
+                "Snapshot of epoch {} doesn't match its manifest.",
+                manifest_clone.epoch
+            );
+            unpack_tar_gz(&temp_file_path, &restored_db_path_clone)?;
+
+            // The restored db has to be at the version the manifest claims.
+            let db = open_db(&restored_db_path_clone, &rocksdb_config)?;
+            let next_version = IndexerAsyncV2::new(db)?.next_version();
+            ensure!(
+                next_version == manifest_clone.next_version,
+                "Snapshot of epoch {} is at version {} instead of {}.",
+                manifest_clone.epoch,
+                next_version,
+                manifest_clone.next_version
+            );
+            Ok(())
+        })
+        .await??;
+
+        if db_path.exists() {
+            rename_db_folders_and_cleanup(
+                &db_path.to_path_buf(),
+                &db_path.with_extension("old"),
+                &restored_db_path,
+            )?;
+        } else {
+            fs::rename(&restored_db_path, db_path).await?;
+        }
+        self.latest_epoch
+            .fetch_max(manifest.epoch, Ordering::Relaxed);
+        info!(
+            epoch = manifest.epoch,
+            next_version = manifest.next_version,
+            storage = self.storage.storage_name(),
+            "[Table Info] Snapshot restored"
+        );
+        Ok(())
+    }
+}
+
+/// Returns the size and the hex encoded SHA-256 hash of a file.
+fn hash_file(path: &Path) -> Result<(u64, String)> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1 << 20];
+    let mut size = 0;
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        size += read as u64;
+    }
+    Ok((size, hex::encode(hasher.finalize())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn open_indexer_async_v2(path: &Path) -> IndexerAsyncV2 {
+        let db = open_db(path, &RocksdbConfig::default()).unwrap();
+        IndexerAsyncV2::new(db).unwrap()
+    }
+
+    fn create_operator(storage_path: &Path, snapshots_to_keep: usize) -> SnapshotOperator {
+        SnapshotOperator::new(
+            Box::new(LocalSnapshotStorage::new(storage_path.to_path_buf())),
+            1,
+            snapshots_to_keep,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_backup_and_restore_snapshot() -> Result<()> {
+        let storage_dir = tempdir()?;
+        let source_dir = tempdir()?;
+        let target_dir = tempdir()?;
+        let operator = create_operator(storage_dir.path(), 2);
+
+        let indexer_async_v2 = open_indexer_async_v2(&source_dir.path().join("db"));
+        for (epoch, next_version) in [(1, 100), (2, 200), (3, 300)] {
+            indexer_async_v2.update_next_version(next_version)?;
+            assert!(operator.should_backup(epoch));
+            let snapshot = operator.prepare_snapshot(
+                epoch,
+                &indexer_async_v2,
+                source_dir.path().join("snapshot"),
+            )?;
+            let manifest = operator.upload_snapshot(snapshot).await?;
+            assert_eq!(manifest.next_version, next_version);
+            assert!(!operator.should_backup(epoch));
+        }
+
+        // Only the latest 2 snapshots are kept.
+        let epochs: Vec<u64> = operator
+            .list_snapshot_manifests()
+            .await?
+            .iter()
+            .map(|manifest| manifest.epoch)
+            .collect();
+        assert_eq!(epochs, vec![3, 2]);
+        assert_eq!(operator.get_metadata().await?.unwrap().epoch, 3);
+
+        // Nothing to restore if the local db is up to date.
+        let db_path = target_dir.path().join("db");
+        let restored = operator
+            .restore_latest_snapshot(&db_path, target_dir.path(), 300, &RocksdbConfig::default())
+            .await?;
+        assert!(restored.is_none());
+
+        let restored = operator
+            .restore_latest_snapshot(&db_path, target_dir.path(), 0, &RocksdbConfig::default())
+            .await?;
+        assert_eq!(restored.unwrap().epoch, 3);
+        assert_eq!(open_indexer_async_v2(&db_path).next_version(), 300);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_restore_falls_back_to_older_snapshot() -> Result<()> {
+        let storage_dir = tempdir()?;
+        let source_dir = tempdir()?;
+        let target_dir = tempdir()?;
+        let operator = create_operator(storage_dir.path(), 3);
+
+        let indexer_async_v2 = open_indexer_async_v2(&source_dir.path().join("db"));
+        for (epoch, next_version) in [(1, 100), (2, 200)] {
+            indexer_async_v2.update_next_version(next_version)?;
+            let snapshot = operator.prepare_snapshot(
+                epoch,
+                &indexer_async_v2,
+                source_dir.path().join("snapshot"),
+            )?;
+            operator.upload_snapshot(snapshot).await?;
+        }
+
+        // Corrupt the latest snapshot.
+        std::fs::write(
+            storage_dir.path().join(generate_blob_name(2)),
+            b"corrupted snapshot",
+        )?;
+
+        let db_path = target_dir.path().join("db");
+        let restored = operator
+            .restore_latest_snapshot(&db_path, target_dir.path(), 0, &RocksdbConfig::default())
+            .await?;
+        assert_eq!(restored.unwrap().epoch, 1);
+        assert_eq!(open_indexer_async_v2(&db_path).next_version(), 100);
+        Ok(())
+    }
+}
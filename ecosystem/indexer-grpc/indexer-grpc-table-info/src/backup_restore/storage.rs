@@ -0,0 +1,31 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use std::path::Path;
+
+/// SnapshotStorage is the object store the table info db snapshots are written to.
+/// Objects are addressed by a `/` separated name relative to the root of the storage.
+#[async_trait::async_trait]
+pub trait SnapshotStorage: Send + Sync {
+    /// Name of the storage, for logging.
+    fn storage_name(&self) -> &str;
+
+    /// Reads a small object, e.g., a manifest. Returns None if the object doesn't exist.
+    async fn read_object(&self, name: &str) -> Result<Option<Vec<u8>>>;
+
+    /// Writes a small object, replacing it if it exists.
+    async fn write_object(&self, name: &str, bytes: Vec<u8>) -> Result<()>;
+
+    /// Uploads a local file as an object, replacing it if it exists.
+    async fn upload_file(&self, name: &str, local_path: &Path) -> Result<()>;
+
+    /// Downloads an object to a local file.
+    async fn download_file(&self, name: &str, local_path: &Path) -> Result<()>;
+
+    /// Lists the names of the objects starting with the prefix.
+    async fn list_objects(&self, prefix: &str) -> Result<Vec<String>>;
+
+    /// Deletes an object. Deleting a missing object is not an error.
+    async fn delete_object(&self, name: &str) -> Result<()>;
+}
@@ -1,10 +1,11 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::table_info_service::TableInfoService;
+use crate::{backup_restore::snapshot::SnapshotOperator, table_info_service::TableInfoService};
 use aptos_api::context::Context;
 use aptos_config::config::NodeConfig;
 use aptos_db_indexer::{db_ops::open_db, db_v2::IndexerAsyncV2};
+use aptos_logger::{error, info};
 use aptos_mempool::MempoolClientSender;
 use aptos_storage_interface::DbReaderWriter;
 use aptos_types::chain_id::ChainId;
@@ -29,12 +30,63 @@ pub fn bootstrap(
 
     // Set up db config and open up the db initially to read metadata
     let node_config = config.clone();
-    let db_path = node_config
+    let base_path = node_config
         .storage
         .get_dir_paths()
         .default_root_path()
-        .join(INDEX_ASYNC_V2_DB_NAME);
+        .clone();
+    let db_path = base_path.join(INDEX_ASYNC_V2_DB_NAME);
     let rocksdb_config = node_config.storage.rocksdb_configs.index_db_config;
+
+    // Set up the snapshots and, if enabled, restore the latest one before opening the db
+    let snapshot_config = node_config.indexer_table_info.snapshot.clone();
+    let snapshot_operator = snapshot_config.as_ref().map(|snapshot_config| {
+        Arc::new(
+            runtime
+                .block_on(SnapshotOperator::from_config(
+                    snapshot_config,
+                    chain_id.id() as u64,
+                ))
+                .expect("Failed to set up the table info snapshot operator"),
+        )
+    });
+    if let (Some(snapshot_operator), Some(snapshot_config)) = (&snapshot_operator, &snapshot_config)
+    {
+        if snapshot_config.enable_restore {
+            let local_next_version = {
+                let db = open_db(&db_path, &rocksdb_config)
+                    .expect("Failed to open up indexer async v2 db initially");
+                IndexerAsyncV2::new(db)
+                    .expect("Failed to initialize indexer async v2")
+                    .next_version()
+            };
+            match runtime.block_on(snapshot_operator.restore_latest_snapshot(
+                &db_path,
+                &base_path,
+                local_next_version,
+                &rocksdb_config,
+            )) {
+                Ok(Some(manifest)) => info!(
+                    epoch = manifest.epoch,
+                    next_version = manifest.next_version,
+                    "[Table Info] Restored the table info db from a snapshot"
+                ),
+                Ok(None) => info!(
+                    local_next_version = local_next_version,
+                    "[Table Info] No table info snapshot ahead of the local db"
+                ),
+                Err(err) => error!(
+                    error = ?err,
+                    "[Table Info] Failed to restore the table info db, parsing from the local db"
+                ),
+            }
+        }
+    }
+    let snapshot_operator = snapshot_operator.filter(|_| {
+        snapshot_config
+            .as_ref()
+            .map_or(false, |snapshot_config| snapshot_config.enable_backup)
+    });
     let db =
         open_db(db_path, &rocksdb_config).expect("Failed to open up indexer async v2 db initially");
 
@@ -60,6 +112,9 @@ pub fn bootstrap(
             node_config.indexer_table_info.enable_expensive_logging,
             indexer_async_v2_clone,
         );
+        if let Some(snapshot_operator) = snapshot_operator {
+            parser = parser.with_snapshot_operator(snapshot_operator, base_path);
+        }
 
         parser.run().await;
     });
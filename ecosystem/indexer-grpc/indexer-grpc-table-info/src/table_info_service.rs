@@ -1,6 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::backup_restore::snapshot::SnapshotOperator;
 use anyhow::Error;
 use aptos_api::context::Context;
 use aptos_api_types::TransactionOnChainData;
@@ -9,14 +10,15 @@ use aptos_indexer_grpc_fullnode::stream_coordinator::{
     IndexerStreamCoordinator, TransactionBatchInfo,
 };
 use aptos_indexer_grpc_utils::counters::{log_grpc_step, IndexerGrpcStep};
-use aptos_logger::{debug, error, info, sample, sample::SampleRate};
+use aptos_logger::{debug, error, info, sample, sample::SampleRate, warn};
 use aptos_types::write_set::WriteSet;
-use std::{sync::Arc, time::Duration};
+use std::{path::PathBuf, sync::Arc, time::Duration};
 use tonic::Status;
 
 type EndVersion = u64;
 const LEDGER_VERSION_RETRY_TIME_MILLIS: u64 = 10;
 const SERVICE_TYPE: &str = "table_info_service";
+const SNAPSHOT_FOLDER_NAME: &str = "table_info_snapshot";
 
 pub struct TableInfoService {
     pub current_version: u64,
@@ -25,6 +27,8 @@ pub struct TableInfoService {
     pub context: Arc<Context>,
     pub enable_expensive_logging: bool,
     pub indexer_async_v2: Arc<IndexerAsyncV2>,
+    /// Only set if snapshot backups are enabled, with the folder to take the db checkpoints in.
+    pub snapshot_operator: Option<(Arc<SnapshotOperator>, PathBuf)>,
 }
 
 impl TableInfoService {
@@ -43,9 +47,20 @@ impl TableInfoService {
             context,
             enable_expensive_logging,
             indexer_async_v2,
+            snapshot_operator: None,
         }
     }
 
+    /// Enables snapshot backups; the db checkpoints are taken in a folder of `base_path`.
+    pub fn with_snapshot_operator(
+        mut self,
+        snapshot_operator: Arc<SnapshotOperator>,
+        base_path: PathBuf,
+    ) -> Self {
+        self.snapshot_operator = Some((snapshot_operator, base_path.join(SNAPSHOT_FOLDER_NAME)));
+        self
+    }
+
     /// 1. fetch new transactions
     /// 2. break them down into batches in parser_batch_size and spawn up threads in parser_task_count
     /// 3. parse write sets from transactions with move annotater to get table handle -> key, value type
@@ -77,9 +92,56 @@ impl TableInfoService {
             );
 
             self.current_version = max_version + 1;
+            self.try_backup_snapshot();
         }
     }
 
+    /// Takes a snapshot of the db at most once per epoch and uploads it in the background.
+    /// The checkpoint is taken here, between two loops, so that it matches the next version.
+    fn try_backup_snapshot(&self) {
+        let (snapshot_operator, snapshot_path) = match &self.snapshot_operator {
+            Some(snapshot_operator) => snapshot_operator,
+            None => return,
+        };
+        // The snapshot must be tagged with the epoch of the last version written
+        // to the db (the chain tip may already be in a later epoch).
+        let last_processed_version = match self.indexer_async_v2.next_version().checked_sub(1) {
+            Some(version) => version,
+            None => return,
+        };
+        let epoch = match self
+            .context
+            .db
+            .get_block_info_by_version(last_processed_version)
+        {
+            Ok((_, _, new_block_event)) => new_block_event.epoch(),
+            Err(err) => {
+                warn!(error = ?err, "[Table Info] Failed to get the epoch for the snapshot");
+                return;
+            },
+        };
+        if !snapshot_operator.should_backup(epoch) {
+            return;
+        }
+        let snapshot = match snapshot_operator.prepare_snapshot(
+            epoch,
+            &self.indexer_async_v2,
+            snapshot_path.clone(),
+        ) {
+            Ok(snapshot) => snapshot,
+            Err(err) => {
+                error!(error = ?err, epoch = epoch, "[Table Info] Failed to prepare the snapshot");
+                return;
+            },
+        };
+        let snapshot_operator = snapshot_operator.clone();
+        tokio::spawn(async move {
+            if let Err(err) = snapshot_operator.upload_snapshot(snapshot).await {
+                error!(error = ?err, epoch = epoch, "[Table Info] Failed to upload the snapshot");
+            }
+        });
+    }
+
     /// Fans out a bunch of threads and processes write sets from transactions in parallel.
     /// Pushes results in parallel to the stream, but only return that the batch is
     /// fully completed if every job in the batch is successful and no pending on items
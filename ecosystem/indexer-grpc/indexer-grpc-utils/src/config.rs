@@ -4,6 +4,7 @@
 use serde::{Deserialize, Serialize};
 /// Common configuration for Indexer GRPC Store.
 use std::path::PathBuf;
+use url::Url;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct GcsFileStore {
@@ -12,6 +13,8 @@ pub struct GcsFileStore {
     pub gcs_file_store_service_account_key_path: String,
     #[serde(default = "default_enable_compression")]
     pub enable_compression: bool,
+    #[serde(default)]
+    pub integrity: FileStoreIntegrityConfig,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -19,6 +22,48 @@ pub struct LocalFileStore {
     pub local_file_store_path: PathBuf,
     #[serde(default = "default_enable_compression")]
     pub enable_compression: bool,
+    #[serde(default)]
+    pub integrity: FileStoreIntegrityConfig,
+}
+
+/// S3-compatible file store, e.g., AWS S3, MinIO or any other store that implements the S3 API.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct S3FileStore {
+    pub s3_file_store_bucket_name: String,
+    // The endpoint of the store, e.g., https://s3.us-west-2.amazonaws.com or http://localhost:9000.
+    // Buckets are always accessed with path-style URLs.
+    pub s3_file_store_endpoint: Url,
+    #[serde(default = "default_s3_region")]
+    pub s3_file_store_region: String,
+    // If not set, the credentials are read from AWS_ACCESS_KEY_ID and AWS_SECRET_ACCESS_KEY.
+    #[serde(default)]
+    pub s3_file_store_access_key_id: Option<String>,
+    #[serde(default)]
+    pub s3_file_store_secret_access_key: Option<String>,
+    #[serde(default = "default_enable_compression")]
+    pub enable_compression: bool,
+    #[serde(default)]
+    pub integrity: FileStoreIntegrityConfig,
+}
+
+/// Integrity settings of the file store. When manifests are enabled, every transactions file
+/// is uploaded together with a manifest that has the content hash of the file (optionally
+/// signed), and the files are verified against their manifests when read.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileStoreIntegrityConfig {
+    // Writes the manifests on upload and verifies the files on read.
+    #[serde(default)]
+    pub enable_manifests: bool,
+    // If false, files without a manifest (e.g., uploaded before manifests were enabled) are still served.
+    #[serde(default)]
+    pub require_manifests: bool,
+    // The secret used to sign (and verify) the manifests with HMAC-SHA256. If not set, the manifests are unsigned.
+    #[serde(default)]
+    pub manifest_signing_key: Option<String>,
+}
+
+fn default_s3_region() -> String {
+    "us-east-1".to_string()
 }
 
 const fn default_enable_compression() -> bool {
@@ -30,6 +75,7 @@ const fn default_enable_compression() -> bool {
 pub enum IndexerGrpcFileStoreConfig {
     GcsFileStore(GcsFileStore),
     LocalFileStore(LocalFileStore),
+    S3FileStore(S3FileStore),
 }
 
 impl Default for IndexerGrpcFileStoreConfig {
@@ -37,6 +83,7 @@ impl Default for IndexerGrpcFileStoreConfig {
         IndexerGrpcFileStoreConfig::LocalFileStore(LocalFileStore {
             local_file_store_path: std::env::current_dir().unwrap(),
             enable_compression: false,
+            integrity: FileStoreIntegrityConfig::default(),
         })
     }
 }
@@ -44,20 +91,33 @@ impl Default for IndexerGrpcFileStoreConfig {
 impl IndexerGrpcFileStoreConfig {
     pub fn create(&self) -> Box<dyn crate::file_store_operator::FileStoreOperator> {
         match self {
-            IndexerGrpcFileStoreConfig::GcsFileStore(gcs_file_store) => {
-                Box::new(crate::file_store_operator::gcs::GcsFileStoreOperator::new(
+            IndexerGrpcFileStoreConfig::GcsFileStore(gcs_file_store) => Box::new(
+                crate::file_store_operator::gcs::GcsFileStoreOperator::new(
                     gcs_file_store.gcs_file_store_bucket_name.clone(),
                     gcs_file_store
                         .gcs_file_store_service_account_key_path
                         .clone(),
                     gcs_file_store.enable_compression,
-                ))
-            },
+                )
+                .with_integrity_config(gcs_file_store.integrity.clone()),
+            ),
             IndexerGrpcFileStoreConfig::LocalFileStore(local_file_store) => Box::new(
                 crate::file_store_operator::local::LocalFileStoreOperator::new(
                     local_file_store.local_file_store_path.clone(),
                     local_file_store.enable_compression,
-                ),
+                )
+                .with_integrity_config(local_file_store.integrity.clone()),
+            ),
+            IndexerGrpcFileStoreConfig::S3FileStore(s3_file_store) => Box::new(
+                crate::file_store_operator::s3::S3FileStoreOperator::new(
+                    s3_file_store.s3_file_store_bucket_name.clone(),
+                    s3_file_store.s3_file_store_endpoint.clone(),
+                    s3_file_store.s3_file_store_region.clone(),
+                    s3_file_store.s3_file_store_access_key_id.clone(),
+                    s3_file_store.s3_file_store_secret_access_key.clone(),
+                    s3_file_store.enable_compression,
+                )
+                .with_integrity_config(s3_file_store.integrity.clone()),
             ),
         }
     }
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 // Maximum number of threads for the file store
 pub const MAXIMUM_NUMBER_FILESTORE_THREADS: usize = 10;
@@ -13,6 +14,9 @@ pub const GRPC_REQUEST_NAME_HEADER: &str = "x-aptos-request-name";
 pub const GRPC_API_GATEWAY_API_KEY_HEADER: &str = "authorization";
 // Limit the message size to 15MB. By default the downstream can receive up to 15MB.
 pub const MESSAGE_SIZE_LIMIT: usize = 1024 * 1024 * 15;
+// How long a filtered stream goes without sending a response before it sends one without
+// transactions, to report the versions it scanned past while none matched the filter.
+pub const FILTERED_STREAM_PROGRESS_INTERVAL: Duration = Duration::from_secs(5);
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct IndexerGrpcRequestMetadata {
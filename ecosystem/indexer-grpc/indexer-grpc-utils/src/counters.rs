@@ -190,6 +190,16 @@ pub static TRANSACTION_STORE_FETCH_RETRIES: Lazy<IntCounterVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Number of transactions files that failed the integrity check, e.g., corrupted or truncated files
+pub static FILE_STORE_INTEGRITY_CHECK_FAILURES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "indexer_grpc_file_store_integrity_check_failures",
+        "Number of transactions files that failed the integrity check",
+        &["store"],
+    )
+    .unwrap()
+});
+
 /// Generic duration metric
 pub static DURATION_IN_SECS: Lazy<GaugeVec> = Lazy::new(|| {
     register_gauge_vec!("indexer_grpc_duration_in_secs", "Duration in seconds", &[
@@ -3,8 +3,9 @@
 
 use crate::{
     compression_util::{FileEntry, FileStoreMetadata, StorageFormat, FILE_ENTRY_TRANSACTION_COUNT},
+    config::FileStoreIntegrityConfig,
     counters::{log_grpc_step, IndexerGrpcStep},
-    file_store_operator::{FileStoreOperator, METADATA_FILE_NAME},
+    file_store_operator::{FileManifest, FileStoreOperator, METADATA_FILE_NAME},
 };
 use anyhow::bail;
 use aptos_protos::transaction::v1::Transaction;
@@ -21,6 +22,7 @@ pub struct GcsFileStoreOperator {
     bucket_name: String,
     file_store_metadata_last_updated: std::time::Instant,
     storage_format: StorageFormat,
+    integrity_config: FileStoreIntegrityConfig,
 }
 
 impl GcsFileStoreOperator {
@@ -39,8 +41,14 @@ impl GcsFileStoreOperator {
             bucket_name,
             file_store_metadata_last_updated: std::time::Instant::now(),
             storage_format,
+            integrity_config: FileStoreIntegrityConfig::default(),
         }
     }
+
+    pub fn with_integrity_config(mut self, integrity_config: FileStoreIntegrityConfig) -> Self {
+        self.integrity_config = integrity_config;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -65,6 +73,10 @@ impl FileStoreOperator for GcsFileStoreOperator {
         "GCS"
     }
 
+    fn integrity_config(&self) -> &FileStoreIntegrityConfig {
+        &self.integrity_config
+    }
+
     async fn get_raw_file(&self, version: u64) -> anyhow::Result<Vec<u8>> {
         let file_entry_key = FileEntry::build_key(version, self.storage_format).to_string();
         match Object::download(&self.bucket_name, file_entry_key.as_str()).await {
@@ -88,6 +100,20 @@ impl FileStoreOperator for GcsFileStoreOperator {
         }
     }
 
+    async fn get_raw_manifest(&self, version: u64) -> anyhow::Result<Option<Vec<u8>>> {
+        let manifest_key = FileManifest::build_key(version, self.storage_format);
+        match Object::download(&self.bucket_name, manifest_key.as_str()).await {
+            Ok(manifest) => Ok(Some(manifest)),
+            Err(cloud_storage::Error::Other(err)) if err.contains("No such object: ") => Ok(None),
+            Err(err) => {
+                anyhow::bail!(
+                    "[Indexer File] Error happens when downloading manifest file. {}",
+                    err
+                );
+            },
+        }
+    }
+
     /// Gets the metadata from the file store. Operator will panic if error happens when accessing the metadata file(except not found).
     async fn get_file_store_metadata(&self) -> Option<FileStoreMetadata> {
         match Object::download(&self.bucket_name, METADATA_FILE_NAME).await {
@@ -162,7 +188,7 @@ impl FileStoreOperator for GcsFileStoreOperator {
     /// Updates the file store metadata after the upload.
     async fn upload_transaction_batch(
         &mut self,
-        _chain_id: u64,
+        chain_id: u64,
         transactions: Vec<Transaction>,
     ) -> anyhow::Result<(u64, u64)> {
         let start_version = transactions.first().unwrap().version;
@@ -180,6 +206,17 @@ impl FileStoreOperator for GcsFileStoreOperator {
         let bucket_name = self.bucket_name.clone();
         let file_entry = FileEntry::from_transactions(transactions, self.storage_format);
         let file_entry_key = FileEntry::build_key(start_version, self.storage_format).to_string();
+        let file_bytes = file_entry.into_inner();
+        let manifest = self.integrity_config.enable_manifests.then(|| {
+            FileManifest::new(
+                chain_id,
+                start_version,
+                batch_size as u64,
+                self.storage_format,
+                &file_bytes,
+                self.integrity_config.manifest_signing_key.as_deref(),
+            )
+        });
         log_grpc_step(
             "file_worker",
             IndexerGrpcStep::FileStoreEncodedTxns,
@@ -194,11 +231,22 @@ impl FileStoreOperator for GcsFileStoreOperator {
         );
         Object::create(
             bucket_name.clone().as_str(),
-            file_entry.into_inner(),
+            file_bytes,
             file_entry_key.as_str(),
             JSON_FILE_TYPE,
         )
         .await?;
+        // The manifest is uploaded after the transactions file, so that a file is never
+        // verified against the manifest of a previous upload.
+        if let Some(manifest) = manifest {
+            Object::create(
+                bucket_name.as_str(),
+                manifest.into_bytes(),
+                FileManifest::build_key(start_version, self.storage_format).as_str(),
+                JSON_FILE_TYPE,
+            )
+            .await?;
+        }
         Ok((start_version, end_version))
     }
 
@@ -3,8 +3,9 @@
 
 use crate::{
     compression_util::{FileEntry, FileStoreMetadata, StorageFormat, FILE_ENTRY_TRANSACTION_COUNT},
+    config::FileStoreIntegrityConfig,
     file_store_operator::{
-        FileStoreOperator, FILE_STORE_UPDATE_FREQUENCY_SECS, METADATA_FILE_NAME,
+        FileManifest, FileStoreOperator, FILE_STORE_UPDATE_FREQUENCY_SECS, METADATA_FILE_NAME,
     },
 };
 use aptos_protos::transaction::v1::Transaction;
@@ -18,6 +19,7 @@ pub struct LocalFileStoreOperator {
     /// The timestamp of the latest metadata update; this is to avoid too frequent metadata update.
     latest_metadata_update_timestamp: Option<std::time::Instant>,
     storage_format: StorageFormat,
+    integrity_config: FileStoreIntegrityConfig,
 }
 
 impl LocalFileStoreOperator {
@@ -31,8 +33,14 @@ impl LocalFileStoreOperator {
             path,
             latest_metadata_update_timestamp: None,
             storage_format,
+            integrity_config: FileStoreIntegrityConfig::default(),
         }
     }
+
+    pub fn with_integrity_config(mut self, integrity_config: FileStoreIntegrityConfig) -> Self {
+        self.integrity_config = integrity_config;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -55,6 +63,10 @@ impl FileStoreOperator for LocalFileStoreOperator {
         "local"
     }
 
+    fn integrity_config(&self) -> &FileStoreIntegrityConfig {
+        &self.integrity_config
+    }
+
     async fn get_raw_file(&self, version: u64) -> anyhow::Result<Vec<u8>> {
         let file_entry_key = FileEntry::build_key(version, self.storage_format).to_string();
         let file_path = self.path.join(file_entry_key);
@@ -73,6 +85,20 @@ impl FileStoreOperator for LocalFileStoreOperator {
         }
     }
 
+    async fn get_raw_manifest(&self, version: u64) -> anyhow::Result<Option<Vec<u8>>> {
+        let manifest_path = self
+            .path
+            .join(FileManifest::build_key(version, self.storage_format));
+        match tokio::fs::read(manifest_path).await {
+            Ok(manifest) => Ok(Some(manifest)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => anyhow::bail!(
+                "[Indexer File] Error happens when reading manifest file. {}",
+                err
+            ),
+        }
+    }
+
     async fn get_file_store_metadata(&self) -> Option<FileStoreMetadata> {
         let metadata_path = self.path.join(METADATA_FILE_NAME);
         match tokio::fs::read(metadata_path).await {
@@ -167,6 +193,7 @@ impl FileStoreOperator for LocalFileStoreOperator {
         for i in transactions.chunks(FILE_ENTRY_TRANSACTION_COUNT as usize) {
             let current_batch = i.iter().cloned().collect_vec();
             let starting_version = current_batch.first().unwrap().version;
+            let transaction_count = current_batch.len() as u64;
             let file_entry = FileEntry::from_transactions(current_batch, self.storage_format);
             let file_entry_key =
                 FileEntry::build_key(starting_version, self.storage_format).to_string();
@@ -181,11 +208,32 @@ impl FileStoreOperator for LocalFileStoreOperator {
                 "Uploading transactions to {:?}",
                 txns_path.to_str().unwrap()
             );
+            let file_bytes = file_entry.into_inner();
+            // The manifest is written after the transactions file, so that a file is never
+            // verified against the manifest of a previous upload.
+            let manifest = self.integrity_config.enable_manifests.then(|| {
+                let manifest = FileManifest::new(
+                    chain_id,
+                    starting_version,
+                    transaction_count,
+                    self.storage_format,
+                    &file_bytes,
+                    self.integrity_config.manifest_signing_key.as_deref(),
+                );
+                (
+                    self.path.join(FileManifest::build_key(
+                        starting_version,
+                        self.storage_format,
+                    )),
+                    manifest.into_bytes(),
+                )
+            });
             let task = tokio::spawn(async move {
-                match tokio::fs::write(txns_path, file_entry.into_inner()).await {
-                    Ok(_) => Ok(()),
-                    Err(err) => Err(anyhow::Error::from(err)),
+                tokio::fs::write(txns_path, file_bytes).await?;
+                if let Some((manifest_path, manifest_bytes)) = manifest {
+                    tokio::fs::write(manifest_path, manifest_bytes).await?;
                 }
+                anyhow::Ok(())
             });
             tasks.push(task);
         }
@@ -0,0 +1,239 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::compression_util::{FileEntry, StorageFormat};
+use anyhow::{bail, ensure, Context, Result};
+use aptos_protos::indexer::v1::TransactionsInStorage;
+use ring::{digest, hmac};
+use serde::{Deserialize, Serialize};
+
+/// FileManifest is the integrity manifest of a transactions file.
+/// It's a JSON file stored next to the transactions file, e.g., `files/1000.json.manifest.json`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FileManifest {
+    pub chain_id: u64,
+    // The version of the first transaction in the file.
+    pub starting_version: u64,
+    // The number of transactions in the file.
+    pub transaction_count: u64,
+    pub storage_format: StorageFormat,
+    // The size of the file in bytes.
+    pub size: u64,
+    // The hex encoded SHA-256 hash of the file.
+    pub sha256: String,
+    // The hex encoded HMAC-SHA256 of the fields above; only present if a signing key is configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+impl FileManifest {
+    pub fn new(
+        chain_id: u64,
+        starting_version: u64,
+        transaction_count: u64,
+        storage_format: StorageFormat,
+        file_bytes: &[u8],
+        signing_key: Option<&str>,
+    ) -> Self {
+        let mut manifest = Self {
+            chain_id,
+            starting_version,
+            transaction_count,
+            storage_format,
+            size: file_bytes.len() as u64,
+            sha256: hex::encode(digest::digest(&digest::SHA256, file_bytes)),
+            signature: None,
+        };
+        manifest.signature = signing_key.map(|signing_key| {
+            hex::encode(hmac::sign(
+                &hmac::Key::new(hmac::HMAC_SHA256, signing_key.as_bytes()),
+                manifest.signing_message().as_bytes(),
+            ))
+        });
+        manifest
+    }
+
+    /// The key of the manifest of the file that contains the given version.
+    pub fn build_key(version: u64, storage_format: StorageFormat) -> String {
+        format!(
+            "{}.manifest.json",
+            FileEntry::build_key(version, storage_format)
+        )
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        serde_json::from_slice(bytes).context("FileManifest json deserialization failed.")
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        serde_json::to_vec(&self).expect("FileManifest json serialization failed.")
+    }
+
+    /// Verifies the signature of the manifest (if a signing key is given) and that the raw
+    /// file matches the size and hash in the manifest.
+    pub fn verify_file(&self, file_bytes: &[u8], signing_key: Option<&str>) -> Result<()> {
+        if let Some(signing_key) = signing_key {
+            let signature = match self.signature.as_ref() {
+                Some(signature) => {
+                    hex::decode(signature).context("Manifest signature is not valid hex.")?
+                },
+                None => bail!(
+                    "[Indexer File] Manifest of file {} is not signed.",
+                    self.starting_version
+                ),
+            };
+            hmac::verify(
+                &hmac::Key::new(hmac::HMAC_SHA256, signing_key.as_bytes()),
+                self.signing_message().as_bytes(),
+                &signature,
+            )
+            .map_err(|_| {
+                anyhow::anyhow!(
+                    "[Indexer File] Manifest signature mismatch for file {}.",
+                    self.starting_version
+                )
+            })?;
+        }
+        ensure!(
+            file_bytes.len() as u64 == self.size,
+            "[Indexer File] File {} is truncated or corrupted: expected {} bytes, got {} bytes.",
+            self.starting_version,
+            self.size,
+            file_bytes.len()
+        );
+        let sha256 = hex::encode(digest::digest(&digest::SHA256, file_bytes));
+        ensure!(
+            sha256 == self.sha256,
+            "[Indexer File] File {} is corrupted: expected sha256 {}, got {}.",
+            self.starting_version,
+            self.sha256,
+            sha256
+        );
+        Ok(())
+    }
+
+    /// Verifies that the decoded transactions match the manifest.
+    pub fn verify_transactions(
+        &self,
+        transactions_in_storage: &TransactionsInStorage,
+    ) -> Result<()> {
+        let transactions = &transactions_in_storage.transactions;
+        ensure!(
+            transactions.len() as u64 == self.transaction_count,
+            "[Indexer File] File {} has {} transactions; expected {}.",
+            self.starting_version,
+            transactions.len(),
+            self.transaction_count
+        );
+        for (i, transaction) in transactions.iter().enumerate() {
+            ensure!(
+                transaction.version == self.starting_version + i as u64,
+                "[Indexer File] File {} has transaction version {} at index {}.",
+                self.starting_version,
+                transaction.version,
+                i
+            );
+        }
+        Ok(())
+    }
+
+    fn signing_message(&self) -> String {
+        format!(
+            "{}:{}:{}:{:?}:{}:{}",
+            self.chain_id,
+            self.starting_version,
+            self.transaction_count,
+            self.storage_format,
+            self.size,
+            self.sha256
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_protos::transaction::v1::Transaction;
+
+    fn create_transactions(starting_version: u64, count: u64) -> Vec<Transaction> {
+        (starting_version..starting_version + count)
+            .map(|version| Transaction {
+                version,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_manifest_verifies_file() {
+        let file_bytes = FileEntry::from_transactions(
+            create_transactions(1000, 1000),
+            StorageFormat::Lz4CompressedProto,
+        )
+        .into_inner();
+        let manifest = FileManifest::new(
+            1,
+            1000,
+            1000,
+            StorageFormat::Lz4CompressedProto,
+            &file_bytes,
+            None,
+        );
+        let manifest = FileManifest::from_bytes(&manifest.into_bytes()).unwrap();
+        assert!(manifest.verify_file(&file_bytes, None).is_ok());
+
+        // Truncated file.
+        assert!(manifest
+            .verify_file(&file_bytes[..file_bytes.len() - 1], None)
+            .is_err());
+        // Corrupted file.
+        let mut corrupted = file_bytes.clone();
+        corrupted[0] ^= 0xff;
+        assert!(manifest.verify_file(&corrupted, None).is_err());
+        // Unsigned manifest when a signature is required.
+        assert!(manifest.verify_file(&file_bytes, Some("secret")).is_err());
+    }
+
+    #[test]
+    fn test_manifest_signature() {
+        let file_bytes = b"transactions".to_vec();
+        let manifest = FileManifest::new(
+            1,
+            0,
+            1000,
+            StorageFormat::Lz4CompressedProto,
+            &file_bytes,
+            Some("secret"),
+        );
+        assert!(manifest.verify_file(&file_bytes, Some("secret")).is_ok());
+        assert!(manifest.verify_file(&file_bytes, Some("other")).is_err());
+
+        // Tampering with any field invalidates the signature.
+        let mut tampered = manifest.clone();
+        tampered.chain_id = 2;
+        assert!(tampered.verify_file(&file_bytes, Some("secret")).is_err());
+    }
+
+    #[test]
+    fn test_manifest_verifies_transactions() {
+        let manifest =
+            FileManifest::new(1, 1000, 1000, StorageFormat::Lz4CompressedProto, &[], None);
+        let mut transactions_in_storage = TransactionsInStorage {
+            transactions: create_transactions(1000, 1000),
+            starting_version: Some(1000),
+        };
+        assert!(manifest
+            .verify_transactions(&transactions_in_storage)
+            .is_ok());
+
+        transactions_in_storage.transactions.pop();
+        assert!(manifest
+            .verify_transactions(&transactions_in_storage)
+            .is_err());
+
+        transactions_in_storage.transactions = create_transactions(2000, 1000);
+        assert!(manifest
+            .verify_transactions(&transactions_in_storage)
+            .is_err());
+    }
+}
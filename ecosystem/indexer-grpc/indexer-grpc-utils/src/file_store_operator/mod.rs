@@ -1,8 +1,9 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::compression_util::{
-    FileEntry, FileStoreMetadata, StorageFormat, FILE_ENTRY_TRANSACTION_COUNT,
+use crate::{
+    compression_util::{FileEntry, FileStoreMetadata, StorageFormat, FILE_ENTRY_TRANSACTION_COUNT},
+    config::FileStoreIntegrityConfig,
 };
 use anyhow::{Context, Result};
 use aptos_protos::transaction::v1::Transaction;
@@ -10,8 +11,12 @@ use aptos_protos::transaction::v1::Transaction;
 pub mod gcs;
 pub use gcs::*;
 pub mod local;
-use crate::counters::TRANSACTION_STORE_FETCH_RETRIES;
+use crate::counters::{FILE_STORE_INTEGRITY_CHECK_FAILURES, TRANSACTION_STORE_FETCH_RETRIES};
 pub use local::*;
+pub mod manifest;
+pub use manifest::*;
+pub mod s3;
+pub use s3::*;
 
 const METADATA_FILE_NAME: &str = "metadata.json";
 const FILE_STORE_UPDATE_FREQUENCY_SECS: u64 = 5;
@@ -26,6 +31,9 @@ pub trait FileStoreOperator: Send + Sync {
     /// The name of the store, for logging. Ex: "GCS", "Redis", etc
     fn store_name(&self) -> &str;
 
+    /// The integrity settings of the store, i.e., whether the files are verified against their manifests.
+    fn integrity_config(&self) -> &FileStoreIntegrityConfig;
+
     /// Gets the transactions files from the file store. version has to be a multiple of BLOB_STORAGE_SIZE.
     async fn get_transactions(&self, version: u64, retries: u8) -> Result<Vec<Transaction>> {
         let (transactions, _, _) = self
@@ -36,6 +44,41 @@ pub trait FileStoreOperator: Send + Sync {
 
     async fn get_raw_file(&self, version: u64) -> Result<Vec<u8>>;
 
+    /// Gets the raw manifest of the transactions file that contains the given version.
+    /// Returns None if the file has no manifest.
+    async fn get_raw_manifest(&self, version: u64) -> Result<Option<Vec<u8>>>;
+
+    /// Verifies the raw transactions file against its manifest. Returns the manifest, or None if
+    /// manifests are disabled or the file has no manifest (and manifests are not required).
+    async fn verify_raw_file(&self, version: u64, bytes: &[u8]) -> Result<Option<FileManifest>> {
+        let integrity_config = self.integrity_config();
+        if !integrity_config.enable_manifests {
+            return Ok(None);
+        }
+        let manifest = match self.get_raw_manifest(version).await? {
+            Some(manifest) => manifest,
+            None if integrity_config.require_manifests => {
+                FILE_STORE_INTEGRITY_CHECK_FAILURES
+                    .with_label_values(&[self.store_name()])
+                    .inc();
+                anyhow::bail!("[Indexer File] Manifest not found for version {}.", version);
+            },
+            None => return Ok(None),
+        };
+        let manifest = FileManifest::from_bytes(&manifest)
+            .and_then(|manifest| {
+                manifest.verify_file(bytes, integrity_config.manifest_signing_key.as_deref())?;
+                Ok(manifest)
+            })
+            .map_err(|err| {
+                FILE_STORE_INTEGRITY_CHECK_FAILURES
+                    .with_label_values(&[self.store_name()])
+                    .inc();
+                err
+            })?;
+        Ok(Some(manifest))
+    }
+
     async fn get_raw_file_with_retries(&self, version: u64, retries: u8) -> Result<Vec<u8>> {
         let mut retries = retries;
         loop {
@@ -63,6 +106,7 @@ pub trait FileStoreOperator: Send + Sync {
     ) -> Result<(Vec<Transaction>, f64, f64)> {
         let io_start_time = std::time::Instant::now();
         let bytes = self.get_raw_file_with_retries(version, retries).await?;
+        let manifest = self.verify_raw_file(version, &bytes).await?;
         let io_duration = io_start_time.elapsed().as_secs_f64();
         let decoding_start_time = std::time::Instant::now();
         let storage_format = self.storage_format();
@@ -72,6 +116,16 @@ pub trait FileStoreOperator: Send + Sync {
         })
         .await
         .context("Converting storage bytes to FileEntry transactions thread panicked")?;
+        if let Some(manifest) = manifest {
+            manifest
+                .verify_transactions(&transactions_in_storage)
+                .map_err(|err| {
+                    FILE_STORE_INTEGRITY_CHECK_FAILURES
+                        .with_label_values(&[self.store_name()])
+                        .inc();
+                    err
+                })?;
+        }
 
         let decoding_duration = decoding_start_time.elapsed().as_secs_f64();
         Ok((
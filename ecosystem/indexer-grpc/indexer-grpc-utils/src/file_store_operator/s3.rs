@@ -0,0 +1,631 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    compression_util::{FileEntry, FileStoreMetadata, StorageFormat, FILE_ENTRY_TRANSACTION_COUNT},
+    config::FileStoreIntegrityConfig,
+    counters::{log_grpc_step, IndexerGrpcStep},
+    file_store_operator::{FileManifest, FileStoreOperator, METADATA_FILE_NAME},
+};
+use anyhow::{bail, Context};
+use aptos_protos::transaction::v1::Transaction;
+use reqwest::{Method, StatusCode};
+use ring::{digest, hmac};
+use std::env;
+use url::Url;
+
+const JSON_FILE_TYPE: &str = "application/json";
+// The environment variables to read the credentials from, if not set in the config.
+const ACCESS_KEY_ID_ENV_VAR: &str = "AWS_ACCESS_KEY_ID";
+const SECRET_ACCESS_KEY_ENV_VAR: &str = "AWS_SECRET_ACCESS_KEY";
+const SESSION_TOKEN_ENV_VAR: &str = "AWS_SESSION_TOKEN";
+const FILE_STORE_METADATA_TIMEOUT_MILLIS: u128 = 200;
+const SIGNING_ALGORITHM: &str = "AWS4-HMAC-SHA256";
+
+/// File store operator for S3-compatible stores. Requests are signed with AWS Signature
+/// Version 4 and buckets are accessed with path-style URLs, e.g., `<endpoint>/<bucket>/<key>`.
+#[derive(Clone)]
+pub struct S3FileStoreOperator {
+    client: reqwest::Client,
+    endpoint: Url,
+    bucket_name: String,
+    region: String,
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+    file_store_metadata_last_updated: std::time::Instant,
+    storage_format: StorageFormat,
+    integrity_config: FileStoreIntegrityConfig,
+}
+
+impl S3FileStoreOperator {
+    pub fn new(
+        bucket_name: String,
+        endpoint: Url,
+        region: String,
+        access_key_id: Option<String>,
+        secret_access_key: Option<String>,
+        enable_compression: bool,
+    ) -> Self {
+        let access_key_id = access_key_id
+            .or_else(|| env::var(ACCESS_KEY_ID_ENV_VAR).ok())
+            .expect("S3 access key id is not set.");
+        let secret_access_key = secret_access_key
+            .or_else(|| env::var(SECRET_ACCESS_KEY_ENV_VAR).ok())
+            .expect("S3 secret access key is not set.");
+        let storage_format = if enable_compression {
+            StorageFormat::Lz4CompressedProto
+        } else {
+            StorageFormat::JsonBase64UncompressedProto
+        };
+        Self {
+            client: reqwest::Client::new(),
+            endpoint,
+            bucket_name,
+            region,
+            access_key_id,
+            secret_access_key,
+            session_token: env::var(SESSION_TOKEN_ENV_VAR).ok(),
+            file_store_metadata_last_updated: std::time::Instant::now(),
+            storage_format,
+            integrity_config: FileStoreIntegrityConfig::default(),
+        }
+    }
+
+    pub fn with_integrity_config(mut self, integrity_config: FileStoreIntegrityConfig) -> Self {
+        self.integrity_config = integrity_config;
+        self
+    }
+
+    /// Sends a signed request for the bucket (if key is None) or for an object in the bucket.
+    async fn send_request(
+        &self,
+        method: Method,
+        key: Option<&str>,
+        body: Vec<u8>,
+        content_type: Option<&str>,
+    ) -> anyhow::Result<reqwest::Response> {
+        let mut canonical_uri = format!(
+            "{}/{}",
+            self.endpoint.path().trim_end_matches('/'),
+            uri_encode(&self.bucket_name)
+        );
+        if let Some(key) = key {
+            canonical_uri.push('/');
+            canonical_uri.push_str(&key.split('/').map(uri_encode).collect::<Vec<_>>().join("/"));
+        }
+        let mut url = self.endpoint.clone();
+        url.set_path(&canonical_uri);
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            (None, _) => bail!("S3 endpoint has no host: {}", self.endpoint),
+        };
+
+        let headers = sign_request(
+            method.as_str(),
+            &host,
+            &canonical_uri,
+            &hex::encode(digest::digest(&digest::SHA256, &body)),
+            &chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string(),
+            &self.region,
+            &self.access_key_id,
+            &self.secret_access_key,
+            self.session_token.as_deref(),
+        );
+        let mut request = self.client.request(method, url).body(body);
+        for (name, value) in headers {
+            request = request.header(name, value);
+        }
+        if let Some(content_type) = content_type {
+            request = request.header(reqwest::header::CONTENT_TYPE, content_type);
+        }
+        request.send().await.context("S3 request failed.")
+    }
+
+    /// Downloads the object; returns None if the object doesn't exist.
+    async fn get_object(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        let response = self
+            .send_request(Method::GET, Some(key), vec![], None)
+            .await?;
+        match response.status() {
+            status if status.is_success() => Ok(Some(response.bytes().await?.to_vec())),
+            StatusCode::NOT_FOUND => Ok(None),
+            status => bail!(
+                "S3 GET {} failed with status {}: {}",
+                key,
+                status,
+                response.text().await.unwrap_or_default()
+            ),
+        }
+    }
+
+    async fn put_object(
+        &self,
+        key: &str,
+        bytes: Vec<u8>,
+        content_type: &str,
+    ) -> anyhow::Result<()> {
+        let response = self
+            .send_request(Method::PUT, Some(key), bytes, Some(content_type))
+            .await?;
+        if !response.status().is_success() {
+            bail!(
+                "S3 PUT {} failed with status {}: {}",
+                key,
+                response.status(),
+                response.text().await.unwrap_or_default()
+            );
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl FileStoreOperator for S3FileStoreOperator {
+    /// Bootstraps the file store operator. This is required before any other operations.
+    async fn verify_storage_bucket_existence(&self) {
+        tracing::info!(
+            bucket_name = self.bucket_name,
+            endpoint = self.endpoint.as_str(),
+            "Before file store operator starts, verify the bucket exists."
+        );
+        // Verifies the bucket exists.
+        let response = self
+            .send_request(Method::HEAD, None, vec![], None)
+            .await
+            .expect("Failed to read bucket.");
+        if !response.status().is_success() {
+            panic!("Failed to read bucket. Status: {}", response.status());
+        }
+    }
+
+    fn storage_format(&self) -> StorageFormat {
+        self.storage_format
+    }
+
+    fn store_name(&self) -> &str {
+        "S3"
+    }
+
+    fn integrity_config(&self) -> &FileStoreIntegrityConfig {
+        &self.integrity_config
+    }
+
+    async fn get_raw_file(&self, version: u64) -> anyhow::Result<Vec<u8>> {
+        let file_entry_key = FileEntry::build_key(version, self.storage_format);
+        match self.get_object(&file_entry_key).await {
+            Ok(Some(file)) => Ok(file),
+            Ok(None) => {
+                bail!("[Indexer File] Transactions file not found. Gap might happen between cache and file store. {}", file_entry_key)
+            },
+            Err(err) => {
+                bail!(
+                    "[Indexer File] Error happens when downloading transaction file. {}",
+                    err
+                );
+            },
+        }
+    }
+
+    async fn get_raw_manifest(&self, version: u64) -> anyhow::Result<Option<Vec<u8>>> {
+        self.get_object(&FileManifest::build_key(version, self.storage_format))
+            .await
+            .context("[Indexer File] Error happens when downloading manifest file.")
+    }
+
+    /// Gets the metadata from the file store. Operator will panic if error happens when accessing the metadata file(except not found).
+    async fn get_file_store_metadata(&self) -> Option<FileStoreMetadata> {
+        match self.get_object(METADATA_FILE_NAME).await {
+            Ok(Some(metadata)) => {
+                let metadata: FileStoreMetadata =
+                    serde_json::from_slice(&metadata).expect("Expected metadata to be valid JSON.");
+                Some(metadata)
+            },
+            // Metadata is not found.
+            Ok(None) => None,
+            Err(err) => {
+                panic!(
+                    "[Indexer File] Error happens when accessing metadata file. {}",
+                    err
+                );
+            },
+        }
+    }
+
+    /// If the file store is empty, the metadata will be created; otherwise, return the existing metadata.
+    async fn update_file_store_metadata_with_timeout(
+        &mut self,
+        expected_chain_id: u64,
+        version: u64,
+    ) -> anyhow::Result<()> {
+        if let Some(metadata) = self.get_file_store_metadata().await {
+            assert_eq!(metadata.chain_id, expected_chain_id, "Chain ID mismatch.");
+            assert_eq!(
+                metadata.storage_format, self.storage_format,
+                "Storage format mismatch."
+            );
+        }
+        if self.file_store_metadata_last_updated.elapsed().as_millis()
+            < FILE_STORE_METADATA_TIMEOUT_MILLIS
+        {
+            bail!("File store metadata is updated too frequently.")
+        }
+        self.update_file_store_metadata_internal(expected_chain_id, version)
+            .await?;
+        Ok(())
+    }
+
+    /// Updates the file store metadata. This is only performed by the operator when new file transactions are uploaded.
+    async fn update_file_store_metadata_internal(
+        &mut self,
+        chain_id: u64,
+        version: u64,
+    ) -> anyhow::Result<()> {
+        let metadata = FileStoreMetadata::new(chain_id, version, self.storage_format);
+        // If the metadata is not updated, the indexer will be restarted.
+        self.put_object(METADATA_FILE_NAME, metadata.into_bytes(), JSON_FILE_TYPE)
+            .await?;
+        self.file_store_metadata_last_updated = std::time::Instant::now();
+        Ok(())
+    }
+
+    /// Uploads the transactions to the file store. The transactions are grouped into batches of BLOB_STORAGE_SIZE.
+    /// Updates the file store metadata after the upload.
+    async fn upload_transaction_batch(
+        &mut self,
+        chain_id: u64,
+        transactions: Vec<Transaction>,
+    ) -> anyhow::Result<(u64, u64)> {
+        let start_version = transactions.first().unwrap().version;
+        let end_version = transactions.last().unwrap().version;
+        let batch_size = transactions.len();
+        anyhow::ensure!(
+            start_version % FILE_ENTRY_TRANSACTION_COUNT == 0,
+            "Starting version has to be a multiple of BLOB_STORAGE_SIZE."
+        );
+        anyhow::ensure!(
+            batch_size == FILE_ENTRY_TRANSACTION_COUNT as usize,
+            "The number of transactions to upload has to be multiplier of BLOB_STORAGE_SIZE."
+        );
+        let start_time = std::time::Instant::now();
+        let file_entry = FileEntry::from_transactions(transactions, self.storage_format);
+        let file_entry_key = FileEntry::build_key(start_version, self.storage_format);
+        let file_bytes = file_entry.into_inner();
+        let manifest = self.integrity_config.enable_manifests.then(|| {
+            FileManifest::new(
+                chain_id,
+                start_version,
+                batch_size as u64,
+                self.storage_format,
+                &file_bytes,
+                self.integrity_config.manifest_signing_key.as_deref(),
+            )
+        });
+        log_grpc_step(
+            "file_worker",
+            IndexerGrpcStep::FileStoreEncodedTxns,
+            Some(start_version as i64),
+            Some((start_version + FILE_ENTRY_TRANSACTION_COUNT - 1) as i64),
+            None,
+            None,
+            Some(start_time.elapsed().as_secs_f64()),
+            None,
+            Some(FILE_ENTRY_TRANSACTION_COUNT as i64),
+            None,
+        );
+        self.put_object(&file_entry_key, file_bytes, JSON_FILE_TYPE)
+            .await?;
+        // The manifest is uploaded after the transactions file, so that a file is never
+        // verified against the manifest of a previous upload.
+        if let Some(manifest) = manifest {
+            self.put_object(
+                &FileManifest::build_key(start_version, self.storage_format),
+                manifest.into_bytes(),
+                JSON_FILE_TYPE,
+            )
+            .await?;
+        }
+        Ok((start_version, end_version))
+    }
+
+    fn clone_box(&self) -> Box<dyn FileStoreOperator> {
+        Box::new(self.clone())
+    }
+}
+
+/// Returns the headers (including the authorization header) of a request signed with
+/// AWS Signature Version 4. The query string is always empty for the file store requests.
+#[allow(clippy::too_many_arguments)]
+fn sign_request(
+    method: &str,
+    host: &str,
+    canonical_uri: &str,
+    payload_sha256: &str,
+    amz_date: &str,
+    region: &str,
+    access_key_id: &str,
+    secret_access_key: &str,
+    session_token: Option<&str>,
+) -> Vec<(&'static str, String)> {
+    let mut headers = vec![
+        ("host", host.to_string()),
+        ("x-amz-content-sha256", payload_sha256.to_string()),
+        ("x-amz-date", amz_date.to_string()),
+    ];
+    if let Some(session_token) = session_token {
+        headers.push(("x-amz-security-token", session_token.to_string()));
+    }
+    let canonical_headers: String = headers
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+        .collect();
+    let signed_headers = headers
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(";");
+    let canonical_request = format!(
+        "{}\n{}\n\n{}\n{}\n{}",
+        method, canonical_uri, canonical_headers, signed_headers, payload_sha256
+    );
+
+    let date = &amz_date[..8];
+    let scope = format!("{}/{}/s3/aws4_request", date, region);
+    let string_to_sign = format!(
+        "{}\n{}\n{}\n{}",
+        SIGNING_ALGORITHM,
+        amz_date,
+        scope,
+        hex::encode(digest::digest(
+            &digest::SHA256,
+            canonical_request.as_bytes()
+        ))
+    );
+    let signing_key = signing_key(secret_access_key, date, region, "s3");
+    let signature = hex::encode(hmac::sign(
+        &hmac::Key::new(hmac::HMAC_SHA256, &signing_key),
+        string_to_sign.as_bytes(),
+    ));
+
+    // The host header is set by the client.
+    headers.remove(0);
+    headers.push((
+        "authorization",
+        format!(
+            "{} Credential={}/{}, SignedHeaders={}, Signature={}",
+            SIGNING_ALGORITHM, access_key_id, scope, signed_headers, signature
+        ),
+    ));
+    headers
+}
+
+/// Derives the signing key for the given date, region and service.
+fn signing_key(secret_access_key: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    [date, region, service, "aws4_request"].iter().fold(
+        format!("AWS4{}", secret_access_key).into_bytes(),
+        |key, data| {
+            hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, &key), data.as_bytes())
+                .as_ref()
+                .to_vec()
+        },
+    )
+}
+
+/// URI-encodes a path segment as required by Signature Version 4.
+fn uri_encode(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            },
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{collections::HashMap, sync::Arc};
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+        net::TcpListener,
+        sync::Mutex,
+    };
+
+    /// Starts a minimal S3 stand-in that serves HEAD, GET and PUT from memory, and
+    /// rejects unsigned requests. Returns the endpoint and the stored objects.
+    async fn start_fake_s3() -> (Url, Arc<Mutex<HashMap<String, Vec<u8>>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        let objects: Arc<Mutex<HashMap<String, Vec<u8>>>> = Arc::default();
+        let server_objects = objects.clone();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let objects = server_objects.clone();
+                tokio::spawn(async move {
+                    let mut stream = BufReader::new(stream);
+                    loop {
+                        let mut request_line = String::new();
+                        if stream.read_line(&mut request_line).await.unwrap_or(0) == 0 {
+                            return;
+                        }
+                        let mut parts = request_line.split_whitespace();
+                        let method = parts.next().unwrap().to_string();
+                        let path = parts.next().unwrap().to_string();
+                        let mut content_length = 0;
+                        let mut signed = false;
+                        loop {
+                            let mut header = String::new();
+                            stream.read_line(&mut header).await.unwrap();
+                            let header = header.trim_end();
+                            if header.is_empty() {
+                                break;
+                            }
+                            let (name, value) = header.split_once(':').unwrap();
+                            match name.to_ascii_lowercase().as_str() {
+                                "content-length" => content_length = value.trim().parse().unwrap(),
+                                "authorization" => signed = value.contains(SIGNING_ALGORITHM),
+                                _ => {},
+                            }
+                        }
+                        let mut body = vec![0; content_length];
+                        stream.read_exact(&mut body).await.unwrap();
+
+                        let (status, body) = match (signed, method.as_str()) {
+                            (false, _) => ("403 Forbidden", vec![]),
+                            (true, "HEAD") => ("200 OK", vec![]),
+                            (true, "PUT") => {
+                                objects.lock().await.insert(path, body);
+                                ("200 OK", vec![])
+                            },
+                            (true, "GET") => match objects.lock().await.get(&path) {
+                                Some(object) => ("200 OK", object.clone()),
+                                None => ("404 Not Found", vec![]),
+                            },
+                            _ => ("405 Method Not Allowed", vec![]),
+                        };
+                        let response = format!(
+                            "HTTP/1.1 {}\r\ncontent-length: {}\r\n\r\n",
+                            status,
+                            body.len()
+                        );
+                        let stream = stream.get_mut();
+                        stream.write_all(response.as_bytes()).await.unwrap();
+                        if method != "HEAD" {
+                            stream.write_all(&body).await.unwrap();
+                        }
+                    }
+                });
+            }
+        });
+        (endpoint, objects)
+    }
+
+    fn create_operator(
+        endpoint: Url,
+        integrity_config: FileStoreIntegrityConfig,
+    ) -> S3FileStoreOperator {
+        S3FileStoreOperator::new(
+            "bucket".to_string(),
+            endpoint,
+            "us-east-1".to_string(),
+            Some("access_key_id".to_string()),
+            Some("secret_access_key".to_string()),
+            true,
+        )
+        .with_integrity_config(integrity_config)
+    }
+
+    fn create_transactions(starting_version: u64) -> Vec<Transaction> {
+        (starting_version..starting_version + FILE_ENTRY_TRANSACTION_COUNT)
+            .map(|version| Transaction {
+                version,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_signing_key() {
+        // Test vector from the AWS Signature Version 4 documentation.
+        let signing_key = signing_key(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20120215",
+            "us-east-1",
+            "iam",
+        );
+        assert_eq!(
+            hex::encode(signing_key),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_s3_file_store_operator() {
+        let (endpoint, _objects) = start_fake_s3().await;
+        let mut operator = create_operator(endpoint, FileStoreIntegrityConfig::default());
+        operator.verify_storage_bucket_existence().await;
+
+        assert!(operator.get_file_store_metadata().await.is_none());
+        operator
+            .update_file_store_metadata_internal(1, 0)
+            .await
+            .unwrap();
+        assert_eq!(
+            operator.get_file_store_metadata().await.unwrap(),
+            FileStoreMetadata::new(1, 0, StorageFormat::Lz4CompressedProto)
+        );
+
+        assert_eq!(
+            operator
+                .upload_transaction_batch(1, create_transactions(1000))
+                .await
+                .unwrap(),
+            (1000, 1999)
+        );
+        let transactions = operator.get_transactions(1500, 0).await.unwrap();
+        assert_eq!(transactions.len(), 500);
+        assert_eq!(transactions.first().unwrap().version, 1500);
+        assert!(operator.get_transactions(2000, 0).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_s3_file_store_operator_detects_corrupted_files() {
+        let (endpoint, objects) = start_fake_s3().await;
+        let integrity_config = FileStoreIntegrityConfig {
+            enable_manifests: true,
+            require_manifests: true,
+            manifest_signing_key: Some("secret".to_string()),
+        };
+        let mut operator = create_operator(endpoint, integrity_config);
+        operator
+            .upload_transaction_batch(1, create_transactions(0))
+            .await
+            .unwrap();
+        operator
+            .upload_transaction_batch(1, create_transactions(1000))
+            .await
+            .unwrap();
+        assert_eq!(operator.get_transactions(0, 0).await.unwrap().len(), 1000);
+
+        // Truncate the first file.
+        let file_path = format!(
+            "/bucket/{}",
+            FileEntry::build_key(0, StorageFormat::Lz4CompressedProto)
+        );
+        objects
+            .lock()
+            .await
+            .get_mut(&file_path)
+            .unwrap()
+            .truncate(10);
+        assert!(operator.get_transactions(0, 0).await.is_err());
+
+        // Swap the second file's manifest with the first one.
+        let manifest_path = |version| {
+            format!(
+                "/bucket/{}",
+                FileManifest::build_key(version, StorageFormat::Lz4CompressedProto)
+            )
+        };
+        let first_manifest = objects.lock().await.remove(&manifest_path(0)).unwrap();
+        objects
+            .lock()
+            .await
+            .insert(manifest_path(1000), first_manifest);
+        assert!(operator.get_transactions(1000, 0).await.is_err());
+
+        // Files without a manifest are rejected when manifests are required.
+        objects.lock().await.remove(&manifest_path(1000));
+        assert!(operator.get_transactions(1000, 0).await.is_err());
+        operator.integrity_config.require_manifests = false;
+        assert_eq!(
+            operator.get_transactions(1000, 0).await.unwrap().len(),
+            1000
+        );
+    }
+}
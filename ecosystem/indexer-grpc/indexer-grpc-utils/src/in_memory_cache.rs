@@ -1,19 +1,31 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::compression_util::{CacheEntry, StorageFormat};
+use crate::{
+    compression_util::{CacheEntry, StorageFormat},
+    create_grpc_client,
+};
 use anyhow::Context;
-use aptos_protos::transaction::v1::Transaction;
+use aptos_protos::{
+    internal::fullnode::v1::{
+        stream_status::StatusType, transactions_from_node_response::Response,
+        GetTransactionsFromNodeRequest, TransactionsFromNodeResponse,
+    },
+    transaction::v1::Transaction,
+};
 use dashmap::DashMap;
 use itertools::Itertools;
 use prost::Message;
 use redis::AsyncCommands;
 use std::sync::Arc;
 use tokio::sync::RwLock;
+use url::Url;
 
 // Internal lookup retry interval for in-memory cache.
 const IN_MEMORY_CACHE_LOOKUP_RETRY_INTERVAL_MS: u64 = 10;
 const IN_MEMORY_CACHE_GC_INTERVAL_MS: u64 = 100;
+// Retry interval for reconnecting to the fullnode when the stream breaks.
+const FULLNODE_RECONNECT_RETRY_INTERVAL_MS: u64 = 1000;
 // Max cache size in bytes: 3 GB.
 const IN_MEMORY_CACHE_TARGET_MAX_CAPACITY_IN_BYTES: u64 = 3_000_000_000;
 // Eviction cache size in bytes: 3.5 GB. Evict the map to 3 GB.
@@ -31,11 +43,41 @@ struct CacheMetadata {
     first_version: u64,
 }
 
+/// The size limits of the cache. Once the cache grows beyond the eviction trigger size,
+/// the oldest transactions are evicted until the cache is within the target capacity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InMemoryCacheSizeConfig {
+    pub target_max_capacity_in_bytes: u64,
+    pub eviction_trigger_size_in_bytes: u64,
+}
+
+impl Default for InMemoryCacheSizeConfig {
+    fn default() -> Self {
+        Self {
+            target_max_capacity_in_bytes: IN_MEMORY_CACHE_TARGET_MAX_CAPACITY_IN_BYTES,
+            eviction_trigger_size_in_bytes: IN_MEMORY_CACHE_EVICTION_TRIGGER_SIZE_IN_BYTES,
+        }
+    }
+}
+
+impl InMemoryCacheSizeConfig {
+    /// Evicts the cache at the given max capacity, keeping the same ratio as the default config.
+    pub fn with_max_capacity_in_bytes(max_capacity_in_bytes: u64) -> Self {
+        Self {
+            target_max_capacity_in_bytes: max_capacity_in_bytes / 7 * 6,
+            eviction_trigger_size_in_bytes: max_capacity_in_bytes,
+        }
+    }
+}
+
 /// InMemoryCache is a simple in-memory cache that stores the protobuf Transaction.
+/// It's either fed by Redis (i.e., the cache worker) or directly by a fullnode's stream.
 pub struct InMemoryCache {
     /// Cache maps the cache key to the deserialized Transaction.
     cache: Arc<DashMap<u64, Arc<Transaction>>>,
     cache_metadata: Arc<RwLock<CacheMetadata>>,
+    /// The chain id of the fullnode; only known if the cache is fed by a fullnode.
+    chain_id: Option<u64>,
     _cancellation_token_drop_guard: tokio_util::sync::DropGuard,
 }
 
@@ -70,16 +112,67 @@ impl InMemoryCache {
         spawn_cleanup_task(
             cache.clone(),
             cache_metadata.clone(),
+            InMemoryCacheSizeConfig::default(),
             cancellation_token.clone(),
         );
         tracing::info!("In-memory cache is created");
         Ok(Self {
             cache,
             cache_metadata,
+            chain_id: None,
+            _cancellation_token_drop_guard: cancellation_token.drop_guard(),
+        })
+    }
+
+    /// Creates a cache that is fed directly by the fullnode's indexer gRPC stream, starting
+    /// at the given version, i.e., without Redis. If the stream breaks, it reconnects from
+    /// the latest cached version.
+    pub async fn new_with_fullnode_stream(
+        fullnode_grpc_address: Url,
+        starting_version: u64,
+        size_config: InMemoryCacheSizeConfig,
+    ) -> anyhow::Result<Self> {
+        let (chain_id, stream) =
+            connect_to_fullnode(fullnode_grpc_address.clone(), starting_version).await?;
+        let cache = Arc::new(DashMap::new());
+        let cancellation_token = tokio_util::sync::CancellationToken::new();
+        let cache_metadata = Arc::new(RwLock::new(CacheMetadata {
+            first_version: starting_version,
+            total_size_in_bytes: 0,
+            latest_version: starting_version,
+        }));
+        spawn_fullnode_update_task(
+            fullnode_grpc_address,
+            chain_id,
+            stream,
+            cache.clone(),
+            cache_metadata.clone(),
+            cancellation_token.clone(),
+        );
+        spawn_cleanup_task(
+            cache.clone(),
+            cache_metadata.clone(),
+            size_config,
+            cancellation_token.clone(),
+        );
+        tracing::info!(
+            chain_id,
+            starting_version,
+            "In-memory cache is created with the fullnode stream"
+        );
+        Ok(Self {
+            cache,
+            cache_metadata,
+            chain_id: Some(chain_id),
             _cancellation_token_drop_guard: cancellation_token.drop_guard(),
         })
     }
 
+    /// The chain id of the fullnode, if the cache is fed by a fullnode.
+    pub fn chain_id(&self) -> Option<u64> {
+        self.chain_id
+    }
+
     async fn latest_version(&self) -> u64 {
         self.cache_metadata.read().await.latest_version
     }
@@ -216,20 +309,149 @@ fn spawn_update_task<C>(
             for transaction in transactions {
                 cache.insert(transaction.version, Arc::new(transaction));
             }
-            let mut current_cache_metadata = { *cache_metadata.read().await };
-            current_cache_metadata.latest_version = end_version;
-            current_cache_metadata.total_size_in_bytes += newly_added_bytes;
             // Get the data available.
             {
-                *cache_metadata.write().await = current_cache_metadata;
+                let mut current_cache_metadata = cache_metadata.write().await;
+                current_cache_metadata.latest_version = end_version;
+                current_cache_metadata.total_size_in_bytes += newly_added_bytes;
             }
         }
     });
 }
 
+/// Connects to the fullnode and starts streaming at the given version.
+/// Returns the chain id from the init signal and the stream.
+async fn connect_to_fullnode(
+    fullnode_grpc_address: Url,
+    starting_version: u64,
+) -> anyhow::Result<(u64, tonic::Streaming<TransactionsFromNodeResponse>)> {
+    let mut client = create_grpc_client(fullnode_grpc_address).await;
+    let request = tonic::Request::new(GetTransactionsFromNodeRequest {
+        starting_version: Some(starting_version),
+        ..Default::default()
+    });
+    let mut stream = client
+        .get_transactions_from_node(request)
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to get transactions from node at starting version {}",
+                starting_version
+            )
+        })?
+        .into_inner();
+    let init_signal = stream
+        .message()
+        .await?
+        .context("Fullnode stream ended before the init signal")?;
+    match init_signal.response {
+        Some(Response::Status(status)) if status.r#type == StatusType::Init as i32 => {
+            anyhow::ensure!(
+                status.start_version == starting_version,
+                "Fullnode stream starts at version {}; expected {}",
+                status.start_version,
+                starting_version
+            );
+        },
+        _ => anyhow::bail!("Expected the init signal from the fullnode stream"),
+    }
+    Ok((init_signal.chain_id as u64, stream))
+}
+
+fn spawn_fullnode_update_task(
+    fullnode_grpc_address: Url,
+    chain_id: u64,
+    mut stream: tonic::Streaming<TransactionsFromNodeResponse>,
+    cache: Arc<DashMap<u64, Arc<Transaction>>>,
+    cache_metadata: Arc<RwLock<CacheMetadata>>,
+    cancellation_token: tokio_util::sync::CancellationToken,
+) {
+    tokio::spawn(async move {
+        loop {
+            let received = tokio::select! {
+                _ = cancellation_token.cancelled() => {
+                    tracing::info!("In-memory cache update task is cancelled.");
+                    return;
+                },
+                received = stream.message() => received,
+            };
+            match received {
+                Ok(Some(response)) => {
+                    if response.chain_id as u64 != chain_id {
+                        panic!(
+                            "Chain ID mismatch: fullnode chain id {}, expected {}",
+                            response.chain_id, chain_id
+                        );
+                    }
+                    // Status signals are only used for bookkeeping by the cache worker; the
+                    // contiguous versions are tracked by the cache itself.
+                    if let Some(Response::Data(data)) = response.response {
+                        insert_transactions(&cache, &cache_metadata, data.transactions).await;
+                    }
+                },
+                Ok(None) | Err(_) => {
+                    let latest_version = { cache_metadata.read().await.latest_version };
+                    tracing::warn!(
+                        latest_version,
+                        "Fullnode stream ended or failed; reconnecting."
+                    );
+                    match connect_to_fullnode(fullnode_grpc_address.clone(), latest_version).await {
+                        Ok((new_chain_id, new_stream)) => {
+                            if new_chain_id != chain_id {
+                                panic!(
+                                    "Chain ID mismatch: fullnode chain id {}, expected {}",
+                                    new_chain_id, chain_id
+                                );
+                            }
+                            stream = new_stream;
+                        },
+                        Err(e) => {
+                            tracing::error!(
+                                error = e.to_string(),
+                                "Failed to reconnect to the fullnode stream."
+                            );
+                            tokio::time::sleep(std::time::Duration::from_millis(
+                                FULLNODE_RECONNECT_RETRY_INTERVAL_MS,
+                            ))
+                            .await;
+                        },
+                    }
+                },
+            }
+        }
+    });
+}
+
+/// Inserts the transactions into the cache and advances the latest version over the
+/// contiguous versions. Transactions may arrive out of order (e.g., when the fullnode uses
+/// multiple processing threads); transactions before the latest version are ignored.
+async fn insert_transactions(
+    cache: &DashMap<u64, Arc<Transaction>>,
+    cache_metadata: &RwLock<CacheMetadata>,
+    transactions: Vec<Transaction>,
+) {
+    let mut current_cache_metadata = cache_metadata.write().await;
+    for transaction in transactions {
+        if transaction.version < current_cache_metadata.latest_version {
+            continue;
+        }
+        let size_in_bytes = transaction.encoded_len() as u64;
+        if cache
+            .insert(transaction.version, Arc::new(transaction))
+            .is_none()
+        {
+            current_cache_metadata.total_size_in_bytes += size_in_bytes;
+        }
+    }
+    while cache.contains_key(&current_cache_metadata.latest_version) {
+        current_cache_metadata.latest_version += 1;
+    }
+}
+
 fn spawn_cleanup_task(
     cache: Arc<DashMap<u64, Arc<Transaction>>>,
     cache_metadata: Arc<RwLock<CacheMetadata>>,
+    size_config: InMemoryCacheSizeConfig,
     cancellation_token: tokio_util::sync::CancellationToken,
 ) {
     tokio::spawn(async move {
@@ -238,10 +460,10 @@ fn spawn_cleanup_task(
                 tracing::info!("In-memory cache cleanup task is cancelled.");
                 return;
             }
-            let mut current_cache_metadata = { *cache_metadata.read().await };
+            let current_cache_metadata = { *cache_metadata.read().await };
             let should_evict = current_cache_metadata
                 .total_size_in_bytes
-                .saturating_sub(IN_MEMORY_CACHE_EVICTION_TRIGGER_SIZE_IN_BYTES)
+                .saturating_sub(size_config.eviction_trigger_size_in_bytes)
                 > 0;
             if !should_evict {
                 tokio::time::sleep(std::time::Duration::from_millis(
@@ -253,18 +475,22 @@ fn spawn_cleanup_task(
             let mut actual_bytes_removed = 0;
             let mut bytes_to_remove = current_cache_metadata
                 .total_size_in_bytes
-                .saturating_sub(IN_MEMORY_CACHE_TARGET_MAX_CAPACITY_IN_BYTES);
-            while bytes_to_remove > 0 {
-                let key_to_remove = current_cache_metadata.first_version;
+                .saturating_sub(size_config.target_max_capacity_in_bytes);
+            let mut first_version = current_cache_metadata.first_version;
+            // Only the contiguous versions are evicted; out-of-order transactions beyond the
+            // latest version are kept until the gap is filled.
+            while bytes_to_remove > 0 && first_version < current_cache_metadata.latest_version {
                 let (_k, v) = cache
-                    .remove(&key_to_remove)
+                    .remove(&first_version)
                     .expect("Failed to remove the key");
                 bytes_to_remove = bytes_to_remove.saturating_sub(v.encoded_len() as u64);
                 actual_bytes_removed += v.encoded_len() as u64;
-                current_cache_metadata.first_version += 1;
+                first_version += 1;
             }
+            // Only update the evicted range, since the update task may have added data meanwhile.
+            let mut current_cache_metadata = cache_metadata.write().await;
+            current_cache_metadata.first_version = first_version;
             current_cache_metadata.total_size_in_bytes -= actual_bytes_removed;
-            *cache_metadata.write().await = current_cache_metadata;
         }
     });
 }
@@ -456,4 +682,85 @@ mod tests {
         assert_eq!(txns.len(), 1);
         assert_eq!(txns[0].version, 1);
     }
+
+    fn generate_transactions(versions: std::ops::Range<u64>) -> Vec<Transaction> {
+        versions
+            .map(|version| Transaction {
+                version,
+                block_height: 1,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_insert_transactions_out_of_order() {
+        let cache = DashMap::new();
+        let cache_metadata = RwLock::new(CacheMetadata {
+            total_size_in_bytes: 0,
+            latest_version: 10,
+            first_version: 10,
+        });
+
+        // The latest version doesn't move until the gap is filled.
+        insert_transactions(&cache, &cache_metadata, generate_transactions(12..14)).await;
+        assert_eq!(cache_metadata.read().await.latest_version, 10);
+        // Transactions before the latest version are ignored.
+        insert_transactions(&cache, &cache_metadata, generate_transactions(5..12)).await;
+        assert_eq!(cache_metadata.read().await.latest_version, 14);
+        assert_eq!(cache.len(), 4);
+
+        // Transactions received twice (e.g., after reconnecting) are only counted once.
+        insert_transactions(&cache, &cache_metadata, generate_transactions(15..16)).await;
+        let total_size_in_bytes = cache_metadata.read().await.total_size_in_bytes;
+        insert_transactions(&cache, &cache_metadata, generate_transactions(15..16)).await;
+        let metadata = *cache_metadata.read().await;
+        assert_eq!(metadata.total_size_in_bytes, total_size_in_bytes);
+        assert_eq!(metadata.latest_version, 14);
+        assert_eq!(
+            metadata.total_size_in_bytes,
+            generate_transactions(10..14)
+                .iter()
+                .chain(generate_transactions(15..16).iter())
+                .map(|t| t.encoded_len() as u64)
+                .sum::<u64>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_task_evicts_the_oldest_transactions() {
+        let cache = Arc::new(DashMap::new());
+        let cache_metadata = Arc::new(RwLock::new(CacheMetadata {
+            total_size_in_bytes: 0,
+            latest_version: 0,
+            first_version: 0,
+        }));
+        insert_transactions(&cache, &cache_metadata, generate_transactions(0..100)).await;
+        let total_size_in_bytes = cache_metadata.read().await.total_size_in_bytes;
+        let size_config = InMemoryCacheSizeConfig {
+            target_max_capacity_in_bytes: total_size_in_bytes / 2,
+            eviction_trigger_size_in_bytes: total_size_in_bytes - 1,
+        };
+        let cancellation_token = tokio_util::sync::CancellationToken::new();
+        spawn_cleanup_task(
+            cache.clone(),
+            cache_metadata.clone(),
+            size_config,
+            cancellation_token.clone(),
+        );
+        for _ in 0..100 {
+            if cache_metadata.read().await.first_version > 0 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        cancellation_token.cancel();
+
+        let metadata = *cache_metadata.read().await;
+        assert!(metadata.first_version > 0);
+        assert!(metadata.total_size_in_bytes <= size_config.target_max_capacity_in_bytes);
+        assert_eq!(metadata.latest_version, 100);
+        assert!(!cache.contains_key(&(metadata.first_version - 1)));
+        assert_eq!(cache.len() as u64, 100 - metadata.first_version);
+    }
 }
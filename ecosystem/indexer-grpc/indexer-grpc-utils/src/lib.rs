@@ -8,6 +8,7 @@ pub mod constants;
 pub mod counters;
 pub mod file_store_operator;
 pub mod in_memory_cache;
+pub mod transaction_filter;
 pub mod types;
 
 use anyhow::{Context, Result};
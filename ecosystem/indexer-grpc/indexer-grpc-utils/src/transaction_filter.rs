@@ -0,0 +1,565 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, ensure, Result};
+use aptos_protos::{
+    indexer::v1::{
+        boolean_transaction_filter, transaction_filter,
+        BooleanTransactionFilter as BooleanTransactionFilterProto, EntryFunctionFilter,
+    },
+    transaction::v1::{
+        multisig_transaction_payload, transaction::TxnData, transaction_payload,
+        write_set_change::Change, EntryFunctionPayload, Event, Transaction,
+    },
+};
+
+/// The max depth of a transaction filter (to bound the cost of evaluating it).
+pub const MAX_TRANSACTION_FILTER_DEPTH: usize = 10;
+/// The max number of nodes (i.e., conditions and combinators) in a transaction filter.
+pub const MAX_TRANSACTION_FILTER_SIZE: usize = 100;
+
+/// A validated and normalized `BooleanTransactionFilter` that can be
+/// evaluated against the transactions served to the client.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BooleanTransactionFilter {
+    Filter(TransactionFilter),
+    And(Vec<BooleanTransactionFilter>),
+    Or(Vec<BooleanTransactionFilter>),
+    Not(Box<BooleanTransactionFilter>),
+}
+
+impl BooleanTransactionFilter {
+    /// Validates and normalizes the given proto filter
+    pub fn new(filter: &BooleanTransactionFilterProto) -> Result<Self> {
+        let mut size = 0;
+        Self::from_proto(filter, 1, &mut size)
+    }
+
+    fn from_proto(
+        filter: &BooleanTransactionFilterProto,
+        depth: usize,
+        size: &mut usize,
+    ) -> Result<Self> {
+        *size += 1;
+        ensure!(
+            depth <= MAX_TRANSACTION_FILTER_DEPTH,
+            "The transaction filter is too deep. Max depth: {}",
+            MAX_TRANSACTION_FILTER_DEPTH
+        );
+        ensure!(
+            *size <= MAX_TRANSACTION_FILTER_SIZE,
+            "The transaction filter is too large. Max size: {}",
+            MAX_TRANSACTION_FILTER_SIZE
+        );
+
+        let filter = match filter.filter.as_ref() {
+            Some(boolean_transaction_filter::Filter::TransactionFilter(filter)) => {
+                Self::Filter(TransactionFilter::from_proto(filter)?)
+            },
+            Some(boolean_transaction_filter::Filter::LogicalAnd(and)) => Self::And(
+                and.filters
+                    .iter()
+                    .map(|filter| Self::from_proto(filter, depth + 1, size))
+                    .collect::<Result<_>>()?,
+            ),
+            Some(boolean_transaction_filter::Filter::LogicalOr(or)) => Self::Or(
+                or.filters
+                    .iter()
+                    .map(|filter| Self::from_proto(filter, depth + 1, size))
+                    .collect::<Result<_>>()?,
+            ),
+            Some(boolean_transaction_filter::Filter::LogicalNot(not)) => {
+                Self::Not(Box::new(Self::from_proto(not, depth + 1, size)?))
+            },
+            None => bail!("The transaction filter is empty"),
+        };
+        Ok(filter)
+    }
+
+    /// Returns true iff the transaction matches the filter
+    pub fn matches(&self, transaction: &Transaction) -> bool {
+        match self {
+            Self::Filter(filter) => filter.matches(transaction),
+            Self::And(filters) => filters.iter().all(|filter| filter.matches(transaction)),
+            Self::Or(filters) => filters.iter().any(|filter| filter.matches(transaction)),
+            Self::Not(filter) => !filter.matches(transaction),
+        }
+    }
+
+    /// Returns the transactions that match the filter
+    pub fn filter_transactions(&self, transactions: Vec<Transaction>) -> Vec<Transaction> {
+        transactions
+            .into_iter()
+            .filter(|transaction| self.matches(transaction))
+            .collect()
+    }
+}
+
+/// A condition on a single property of a transaction
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TransactionFilter {
+    Sender(String),
+    EntryFunction {
+        address: Option<String>,
+        module_name: Option<String>,
+        function: Option<String>,
+    },
+    EventType(TypeFilter),
+    WriteSetResourceType(TypeFilter),
+    Success(bool),
+}
+
+impl TransactionFilter {
+    fn from_proto(filter: &aptos_protos::indexer::v1::TransactionFilter) -> Result<Self> {
+        let filter = match filter.filter.as_ref() {
+            Some(transaction_filter::Filter::Sender(sender)) => {
+                Self::Sender(normalize_address(sender)?)
+            },
+            Some(transaction_filter::Filter::EntryFunction(EntryFunctionFilter {
+                address,
+                module_name,
+                function,
+            })) => Self::EntryFunction {
+                address: address.as_deref().map(normalize_address).transpose()?,
+                module_name: module_name.clone(),
+                function: function.clone(),
+            },
+            Some(transaction_filter::Filter::EventType(event_type)) => {
+                Self::EventType(TypeFilter::new(event_type)?)
+            },
+            Some(transaction_filter::Filter::WriteSetResourceType(resource_type)) => {
+                Self::WriteSetResourceType(TypeFilter::new(resource_type)?)
+            },
+            Some(transaction_filter::Filter::Success(success)) => Self::Success(*success),
+            None => bail!("The transaction filter condition is empty"),
+        };
+        Ok(filter)
+    }
+
+    /// Returns true iff the transaction matches the condition
+    pub fn matches(&self, transaction: &Transaction) -> bool {
+        match self {
+            Self::Sender(sender) => match transaction.txn_data.as_ref() {
+                Some(TxnData::User(user_transaction)) => user_transaction
+                    .request
+                    .as_ref()
+                    .map_or(false, |request| &request.sender == sender),
+                _ => false,
+            },
+            Self::EntryFunction {
+                address,
+                module_name,
+                function,
+            } => get_entry_function_payload(transaction).map_or(false, |payload| {
+                let Some(function_id) = payload.function.as_ref() else {
+                    return false;
+                };
+                let Some(module_id) = function_id.module.as_ref() else {
+                    return false;
+                };
+                address.as_ref().map_or(true, |address| {
+                    // Entry function addresses aren't guaranteed to be in the short form
+                    normalize_address(&module_id.address).ok().as_ref() == Some(address)
+                }) && module_name
+                    .as_ref()
+                    .map_or(true, |module_name| &module_id.name == module_name)
+                    && function
+                        .as_ref()
+                        .map_or(true, |function| &function_id.name == function)
+            }),
+            Self::EventType(type_filter) => get_events(transaction)
+                .iter()
+                .any(|event| type_filter.matches(&event.type_str)),
+            Self::WriteSetResourceType(type_filter) => {
+                transaction.info.as_ref().map_or(false, |info| {
+                    info.changes
+                        .iter()
+                        .any(|change| match change.change.as_ref() {
+                            Some(Change::WriteResource(resource)) => {
+                                type_filter.matches(&resource.type_str)
+                            },
+                            Some(Change::DeleteResource(resource)) => {
+                                type_filter.matches(&resource.type_str)
+                            },
+                            _ => false,
+                        })
+                })
+            },
+            Self::Success(success) => transaction
+                .info
+                .as_ref()
+                .map_or(false, |info| info.success == *success),
+        }
+    }
+}
+
+/// A filter on a Move struct type (e.g., of an event or resource). If the
+/// filter has no generic type parameters, only the base types are compared.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TypeFilter {
+    type_str: String,
+    ignore_type_params: bool,
+}
+
+impl TypeFilter {
+    fn new(type_str: &str) -> Result<Self> {
+        let type_str = normalize_type(type_str)?;
+        ensure!(
+            type_str.split("::").count() >= 3,
+            "Invalid type in transaction filter: {}. Expected a fully qualified struct type, e.g., 0x1::coin::CoinStore",
+            type_str
+        );
+        let ignore_type_params = !type_str.contains('<');
+        Ok(Self {
+            type_str,
+            ignore_type_params,
+        })
+    }
+
+    fn matches(&self, type_str: &str) -> bool {
+        if self.ignore_type_params {
+            let base_type = type_str.split('<').next().unwrap_or(type_str);
+            base_type == self.type_str
+        } else {
+            type_str == self.type_str
+        }
+    }
+}
+
+/// Returns the entry function payload of the transaction (if any)
+fn get_entry_function_payload(transaction: &Transaction) -> Option<&EntryFunctionPayload> {
+    let Some(TxnData::User(user_transaction)) = transaction.txn_data.as_ref() else {
+        return None;
+    };
+    let payload = user_transaction.request.as_ref()?.payload.as_ref()?;
+    match payload.payload.as_ref()? {
+        transaction_payload::Payload::EntryFunctionPayload(payload) => Some(payload),
+        transaction_payload::Payload::MultisigPayload(multisig_payload) => {
+            match multisig_payload
+                .transaction_payload
+                .as_ref()?
+                .payload
+                .as_ref()?
+            {
+                multisig_transaction_payload::Payload::EntryFunctionPayload(payload) => {
+                    Some(payload)
+                },
+            }
+        },
+        _ => None,
+    }
+}
+
+/// Returns the events emitted by the transaction
+fn get_events(transaction: &Transaction) -> &[Event] {
+    match transaction.txn_data.as_ref() {
+        Some(TxnData::User(user_transaction)) => &user_transaction.events,
+        Some(TxnData::Genesis(genesis_transaction)) => &genesis_transaction.events,
+        Some(TxnData::BlockMetadata(block_metadata_transaction)) => {
+            &block_metadata_transaction.events
+        },
+        _ => &[],
+    }
+}
+
+/// Normalizes the given address to the short form used by the
+/// transactions (e.g., "0x0001" becomes "0x1").
+fn normalize_address(address: &str) -> Result<String> {
+    let hex = address.trim();
+    let hex = hex
+        .strip_prefix("0x")
+        .or_else(|| hex.strip_prefix("0X"))
+        .unwrap_or(hex);
+    ensure!(
+        !hex.is_empty() && hex.len() <= 64 && hex.chars().all(|c| c.is_ascii_hexdigit()),
+        "Invalid address in transaction filter: {}",
+        address
+    );
+    let hex = hex.trim_start_matches('0').to_ascii_lowercase();
+    if hex.is_empty() {
+        Ok("0x0".to_string())
+    } else {
+        Ok(format!("0x{}", hex))
+    }
+}
+
+/// Normalizes the addresses and whitespace in the given type, so that it
+/// has the same format as the types in the transactions.
+fn normalize_type(type_str: &str) -> Result<String> {
+    let mut normalized = String::new();
+    let mut token = String::new();
+    for c in type_str.chars().chain(std::iter::once(' ')) {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            token.push(c);
+            continue;
+        }
+
+        // Normalize the addresses in the type
+        if token.starts_with("0x") || token.starts_with("0X") {
+            normalized.push_str(&normalize_address(&token)?);
+        } else {
+            normalized.push_str(&token);
+        }
+        token.clear();
+
+        match c {
+            ',' => normalized.push_str(", "),
+            c if c.is_whitespace() => {},
+            c => normalized.push(c),
+        }
+    }
+    ensure!(
+        !normalized.is_empty(),
+        "The type in the transaction filter is empty"
+    );
+    Ok(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_protos::{
+        indexer::v1::{LogicalAndFilters, LogicalOrFilters},
+        transaction::v1::{
+            EntryFunctionId, MoveModuleId, TransactionInfo, TransactionPayload, UserTransaction,
+            UserTransactionRequest, WriteResource, WriteSetChange,
+        },
+    };
+
+    fn condition(filter: transaction_filter::Filter) -> BooleanTransactionFilterProto {
+        BooleanTransactionFilterProto {
+            filter: Some(boolean_transaction_filter::Filter::TransactionFilter(
+                aptos_protos::indexer::v1::TransactionFilter {
+                    filter: Some(filter),
+                },
+            )),
+        }
+    }
+
+    fn and(filters: Vec<BooleanTransactionFilterProto>) -> BooleanTransactionFilterProto {
+        BooleanTransactionFilterProto {
+            filter: Some(boolean_transaction_filter::Filter::LogicalAnd(
+                LogicalAndFilters { filters },
+            )),
+        }
+    }
+
+    fn or(filters: Vec<BooleanTransactionFilterProto>) -> BooleanTransactionFilterProto {
+        BooleanTransactionFilterProto {
+            filter: Some(boolean_transaction_filter::Filter::LogicalOr(
+                LogicalOrFilters { filters },
+            )),
+        }
+    }
+
+    fn not(filter: BooleanTransactionFilterProto) -> BooleanTransactionFilterProto {
+        BooleanTransactionFilterProto {
+            filter: Some(boolean_transaction_filter::Filter::LogicalNot(Box::new(
+                filter,
+            ))),
+        }
+    }
+
+    fn create_user_transaction(
+        version: u64,
+        sender: &str,
+        entry_function: (&str, &str, &str),
+        event_types: &[&str],
+        resource_types: &[&str],
+        success: bool,
+    ) -> Transaction {
+        let (address, module_name, function) = entry_function;
+        Transaction {
+            version,
+            txn_data: Some(TxnData::User(UserTransaction {
+                request: Some(UserTransactionRequest {
+                    sender: sender.to_string(),
+                    payload: Some(TransactionPayload {
+                        payload: Some(transaction_payload::Payload::EntryFunctionPayload(
+                            EntryFunctionPayload {
+                                function: Some(EntryFunctionId {
+                                    module: Some(MoveModuleId {
+                                        address: address.to_string(),
+                                        name: module_name.to_string(),
+                                    }),
+                                    name: function.to_string(),
+                                }),
+                                ..Default::default()
+                            },
+                        )),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                events: event_types
+                    .iter()
+                    .map(|event_type| Event {
+                        type_str: event_type.to_string(),
+                        ..Default::default()
+                    })
+                    .collect(),
+            })),
+            info: Some(TransactionInfo {
+                success,
+                changes: resource_types
+                    .iter()
+                    .map(|resource_type| WriteSetChange {
+                        change: Some(Change::WriteResource(WriteResource {
+                            type_str: resource_type.to_string(),
+                            ..Default::default()
+                        })),
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn create_transactions() -> Vec<Transaction> {
+        vec![
+            create_user_transaction(
+                0,
+                "0x1234",
+                ("0x1", "aptos_account", "transfer"),
+                &["0x1::coin::WithdrawEvent", "0x1::coin::DepositEvent"],
+                &["0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>"],
+                true,
+            ),
+            create_user_transaction(
+                1,
+                "0x5678",
+                ("0xabc", "marketplace", "list"),
+                &["0xabc::marketplace::ListEvent"],
+                &["0xabc::marketplace::Listing"],
+                true,
+            ),
+            create_user_transaction(
+                2,
+                "0x1234",
+                ("0xabc", "marketplace", "buy"),
+                &[],
+                &[],
+                false,
+            ),
+            Transaction {
+                version: 3,
+                ..Default::default()
+            },
+        ]
+    }
+
+    fn filter_versions(filter: BooleanTransactionFilterProto) -> Vec<u64> {
+        BooleanTransactionFilter::new(&filter)
+            .unwrap()
+            .filter_transactions(create_transactions())
+            .iter()
+            .map(|transaction| transaction.version)
+            .collect()
+    }
+
+    #[test]
+    fn test_transaction_filter_conditions() {
+        // Sender (with a non-normalized address)
+        let filter = condition(transaction_filter::Filter::Sender("0x001234".into()));
+        assert_eq!(filter_versions(filter), vec![0, 2]);
+
+        // Entry function
+        let filter = condition(transaction_filter::Filter::EntryFunction(
+            EntryFunctionFilter {
+                address: Some("0xABC".into()),
+                module_name: Some("marketplace".into()),
+                function: None,
+            },
+        ));
+        assert_eq!(filter_versions(filter), vec![1, 2]);
+
+        // Event type
+        let filter = condition(transaction_filter::Filter::EventType(
+            "0x01::coin::DepositEvent".into(),
+        ));
+        assert_eq!(filter_versions(filter), vec![0]);
+
+        // Write set resource type (with and without type params)
+        let filter = condition(transaction_filter::Filter::WriteSetResourceType(
+            "0x1::coin::CoinStore".into(),
+        ));
+        assert_eq!(filter_versions(filter), vec![0]);
+        let filter = condition(transaction_filter::Filter::WriteSetResourceType(
+            "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>".into(),
+        ));
+        assert_eq!(filter_versions(filter), vec![0]);
+        let filter = condition(transaction_filter::Filter::WriteSetResourceType(
+            "0x1::coin::CoinStore<0x1::other_coin::OtherCoin>".into(),
+        ));
+        assert!(filter_versions(filter).is_empty());
+
+        // Success
+        let filter = condition(transaction_filter::Filter::Success(false));
+        assert_eq!(filter_versions(filter), vec![2]);
+    }
+
+    #[test]
+    fn test_transaction_filter_combinators() {
+        let sender = || condition(transaction_filter::Filter::Sender("0x1234".into()));
+        let success = || condition(transaction_filter::Filter::Success(true));
+        let marketplace = || {
+            condition(transaction_filter::Filter::EntryFunction(
+                EntryFunctionFilter {
+                    address: Some("0xabc".into()),
+                    module_name: None,
+                    function: None,
+                },
+            ))
+        };
+
+        assert_eq!(filter_versions(and(vec![sender(), success()])), vec![0]);
+        assert_eq!(
+            filter_versions(or(vec![sender(), marketplace()])),
+            vec![0, 1, 2]
+        );
+        assert_eq!(filter_versions(not(sender())), vec![1, 3]);
+        assert_eq!(
+            filter_versions(and(vec![marketplace(), not(success())])),
+            vec![2]
+        );
+
+        // Empty combinators match everything (and) or nothing (or)
+        assert_eq!(filter_versions(and(vec![])), vec![0, 1, 2, 3]);
+        assert!(filter_versions(or(vec![])).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_transaction_filters() {
+        // Empty filters
+        assert!(BooleanTransactionFilter::new(&BooleanTransactionFilterProto::default()).is_err());
+        assert!(
+            BooleanTransactionFilter::new(&BooleanTransactionFilterProto {
+                filter: Some(boolean_transaction_filter::Filter::TransactionFilter(
+                    Default::default()
+                )),
+            })
+            .is_err()
+        );
+
+        // Invalid addresses and types
+        let filter = condition(transaction_filter::Filter::Sender("0xnothex".into()));
+        assert!(BooleanTransactionFilter::new(&filter).is_err());
+        let filter = condition(transaction_filter::Filter::EventType("coin".into()));
+        assert!(BooleanTransactionFilter::new(&filter).is_err());
+
+        // Filters that are too deep
+        let mut filter = condition(transaction_filter::Filter::Success(true));
+        for _ in 0..MAX_TRANSACTION_FILTER_DEPTH {
+            filter = not(filter);
+        }
+        assert!(BooleanTransactionFilter::new(&filter).is_err());
+
+        // Filters that are too large
+        let filter = or((0..MAX_TRANSACTION_FILTER_SIZE)
+            .map(|_| condition(transaction_filter::Filter::Success(true)))
+            .collect());
+        assert!(BooleanTransactionFilter::new(&filter).is_err());
+    }
+}
@@ -0,0 +1,140 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{CheckResult, Checker, CheckerError, CommonCheckerConfig};
+use crate::{
+    get_provider,
+    provider::{api_index::ApiIndexProvider, ProviderCollection},
+};
+use anyhow::{anyhow, Context, Result};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use tokio::time::{Duration, Instant};
+
+/// Checks that a REST endpoint of the target returns successfully,
+/// within the given latency, every time it is called.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiEndpointCheckerConfig {
+    #[serde(flatten)]
+    pub common: CommonCheckerConfig,
+
+    /// Name of the check, shown in the results. This distinguishes the
+    /// results when more than one ApiEndpoint checker is configured.
+    pub name: String,
+
+    /// Path of the endpoint relative to the versioned API, e.g. accounts/0x1/resources.
+    pub path: String,
+
+    /// The maximum latency of any call to the endpoint.
+    pub max_latency_ms: u64,
+
+    /// The number of times to call the endpoint.
+    #[serde(default = "ApiEndpointCheckerConfig::default_num_samples")]
+    pub num_samples: u16,
+
+    /// The delay between each call.
+    #[serde(default = "ApiEndpointCheckerConfig::default_delay_between_samples_ms")]
+    pub delay_between_samples_ms: u64,
+}
+
+impl ApiEndpointCheckerConfig {
+    const fn default_num_samples() -> u16 {
+        3
+    }
+
+    const fn default_delay_between_samples_ms() -> u64 {
+        50
+    }
+}
+
+#[derive(Debug)]
+pub struct ApiEndpointChecker {
+    config: ApiEndpointCheckerConfig,
+    client: reqwest::Client,
+}
+
+impl ApiEndpointChecker {
+    pub fn new(config: ApiEndpointCheckerConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    async fn get_latency_datapoint(&self, url: &Url) -> Result<Duration> {
+        let start = Instant::now();
+        let response = self
+            .client
+            .get(url.clone())
+            .send()
+            .await
+            .with_context(|| format!("Failed to get data from {}", url))?;
+        let latency = start.elapsed();
+        if !response.status().is_success() {
+            return Err(anyhow!("{} returned status {}", url, response.status()));
+        }
+        Ok(latency)
+    }
+}
+
+#[async_trait::async_trait]
+impl Checker for ApiEndpointChecker {
+    /// Assert that the endpoint of the target consistently returns within the max latency.
+    async fn check(
+        &self,
+        providers: &ProviderCollection,
+    ) -> Result<Vec<CheckResult>, CheckerError> {
+        let target_api_index_provider = get_provider!(
+            providers.target_api_index_provider,
+            self.config.common.required,
+            ApiIndexProvider
+        );
+
+        let url = target_api_index_provider
+            .client
+            .build_path(&self.config.path)
+            .map_err(|e| CheckerError::NonRetryableEndpointError("endpoint path", e.into()))?;
+
+        let mut max_latency = Duration::ZERO;
+        for _ in 0..self.config.num_samples {
+            match self.get_latency_datapoint(&url).await {
+                Ok(latency) => max_latency = max_latency.max(latency),
+                Err(e) => {
+                    return Ok(vec![Self::build_result(
+                        format!("{}: Endpoint returned an error", self.config.name),
+                        0,
+                        format!(
+                            "The node under investigation failed to serve {}: {:#}",
+                            url, e
+                        ),
+                    )])
+                },
+            }
+            tokio::time::sleep(Duration::from_millis(self.config.delay_between_samples_ms)).await;
+        }
+
+        let max_latency_ms = max_latency.as_millis() as u64;
+        let check_result = if max_latency_ms > self.config.max_latency_ms {
+            Self::build_result(
+                format!("{}: Endpoint latency too high", self.config.name),
+                50,
+                format!(
+                    "The slowest of {} calls to {} took {}ms, which is higher than the maximum allowed latency of {}ms.",
+                    self.config.num_samples, url, max_latency_ms, self.config.max_latency_ms
+                ),
+            )
+        } else {
+            Self::build_result(
+                format!("{}: Endpoint latency is good", self.config.name),
+                100,
+                format!(
+                    "The slowest of {} calls to {} took {}ms, which is below the maximum allowed latency of {}ms.",
+                    self.config.num_samples, url, max_latency_ms, self.config.max_latency_ms
+                ),
+            )
+        };
+
+        Ok(vec![check_result])
+    }
+}
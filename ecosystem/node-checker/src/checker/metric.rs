@@ -0,0 +1,232 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{CheckResult, Checker, CheckerError, CommonCheckerConfig};
+use crate::{
+    get_provider,
+    provider::{
+        metrics::{get_metric_value_f64, Label, MetricsProvider},
+        Provider, ProviderCollection,
+    },
+};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum ComparisonOperator {
+    #[serde(rename = "<")]
+    LessThan,
+    #[serde(rename = "<=")]
+    LessThanOrEqual,
+    #[serde(rename = "==")]
+    Equal,
+    #[serde(rename = ">=")]
+    GreaterThanOrEqual,
+    #[serde(rename = ">")]
+    GreaterThan,
+}
+
+impl ComparisonOperator {
+    pub fn evaluate(&self, left: f64, right: f64) -> bool {
+        match self {
+            ComparisonOperator::LessThan => left < right,
+            ComparisonOperator::LessThanOrEqual => left <= right,
+            ComparisonOperator::Equal => left == right,
+            ComparisonOperator::GreaterThanOrEqual => left >= right,
+            ComparisonOperator::GreaterThan => left > right,
+        }
+    }
+}
+
+impl Display for ComparisonOperator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let symbol = match self {
+            ComparisonOperator::LessThan => "<",
+            ComparisonOperator::LessThanOrEqual => "<=",
+            ComparisonOperator::Equal => "==",
+            ComparisonOperator::GreaterThanOrEqual => ">=",
+            ComparisonOperator::GreaterThan => ">",
+        };
+        write!(f, "{}", symbol)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetricLabel {
+    pub key: String,
+    pub value: String,
+}
+
+/// Checks that a metric of the target satisfies
+/// `target <operator> baseline * baseline_multiplier + baseline_offset`,
+/// e.g. that the target has at least 90% of the connections of the baseline.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetricCheckerConfig {
+    #[serde(flatten)]
+    pub common: CommonCheckerConfig,
+
+    /// Name of the check, shown in the results. This distinguishes the
+    /// results when more than one Metric checker is configured.
+    pub name: String,
+
+    /// Name of the Prometheus metric, e.g. aptos_connections.
+    pub metric_name: String,
+
+    /// If given, only the metric with this label is considered.
+    pub label: Option<MetricLabel>,
+
+    pub operator: ComparisonOperator,
+
+    #[serde(default = "MetricCheckerConfig::default_baseline_multiplier")]
+    pub baseline_multiplier: f64,
+
+    #[serde(default)]
+    pub baseline_offset: f64,
+
+    /// If set, the target metric is compared against baseline_offset alone,
+    /// without scraping the metrics of the baseline node.
+    #[serde(default)]
+    pub absolute: bool,
+}
+
+impl MetricCheckerConfig {
+    const fn default_baseline_multiplier() -> f64 {
+        1.0
+    }
+}
+
+#[derive(Debug)]
+pub struct MetricChecker {
+    config: MetricCheckerConfig,
+}
+
+impl MetricChecker {
+    pub fn new(config: MetricCheckerConfig) -> Self {
+        Self { config }
+    }
+
+    fn label(&self) -> Option<Label> {
+        self.config.label.as_ref().map(|label| Label {
+            key: &label.key,
+            value: &label.value,
+        })
+    }
+
+    fn metric_description(&self) -> String {
+        match &self.config.label {
+            Some(label) => format!(
+                "{}{{{}=\"{}\"}}",
+                self.config.metric_name, label.key, label.value
+            ),
+            None => self.config.metric_name.clone(),
+        }
+    }
+
+    fn build_check_result(&self, target_value: f64, threshold: f64) -> CheckResult {
+        let expression = format!(
+            "{} {} {}",
+            self.metric_description(),
+            self.config.operator,
+            threshold
+        );
+        if self.config.operator.evaluate(target_value, threshold) {
+            Self::build_result(
+                format!("{}: Metric is as expected", self.config.name),
+                100,
+                format!(
+                    "The node under investigation reported {} for {}, which satisfies {}.",
+                    target_value,
+                    self.metric_description(),
+                    expression
+                ),
+            )
+        } else {
+            Self::build_result(
+                format!("{}: Metric is not as expected", self.config.name),
+                0,
+                format!(
+                    "The node under investigation reported {} for {}, which does not satisfy {}.",
+                    target_value,
+                    self.metric_description(),
+                    expression
+                ),
+            )
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Checker for MetricChecker {
+    /// Assert that the metric of the target satisfies the configured comparison.
+    async fn check(
+        &self,
+        providers: &ProviderCollection,
+    ) -> Result<Vec<CheckResult>, CheckerError> {
+        let target_metrics_provider = get_provider!(
+            providers.target_metrics_provider,
+            self.config.common.required,
+            MetricsProvider
+        );
+
+        let threshold = if self.config.absolute {
+            self.config.baseline_offset
+        } else {
+            let baseline_metrics_provider = get_provider!(
+                providers.baseline_metrics_provider,
+                self.config.common.required,
+                MetricsProvider
+            );
+            // Failing to get the baseline value is an error with NHC or the baseline.
+            let baseline_scrape = baseline_metrics_provider.provide().await?;
+            let baseline_value = get_metric_value_f64(
+                &baseline_scrape,
+                &self.config.metric_name,
+                self.label().as_ref(),
+            )
+            .ok_or_else(|| {
+                CheckerError::MissingDataError(
+                    "baseline metric",
+                    anyhow!(
+                        "The baseline node is missing the metric {}",
+                        self.metric_description()
+                    ),
+                )
+            })?;
+            baseline_value * self.config.baseline_multiplier + self.config.baseline_offset
+        };
+
+        let target_scrape = match target_metrics_provider.provide().await {
+            Ok(scrape) => scrape,
+            Err(e) => {
+                return Ok(vec![Self::build_result(
+                    format!("{}: Failed to scrape metrics", self.config.name),
+                    0,
+                    format!("Failed to scrape metrics from your node: {:#}", e),
+                )])
+            },
+        };
+
+        let target_value = match get_metric_value_f64(
+            &target_scrape,
+            &self.config.metric_name,
+            self.label().as_ref(),
+        ) {
+            Some(value) => value,
+            None => {
+                return Ok(vec![Self::build_result(
+                    format!("{}: Metric missing", self.config.name),
+                    0,
+                    format!(
+                        "The metrics from the target node are missing the metric: {}",
+                        self.metric_description()
+                    ),
+                )])
+            },
+        };
+
+        Ok(vec![self.build_check_result(target_value, threshold)])
+    }
+}
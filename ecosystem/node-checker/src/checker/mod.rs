@@ -1,6 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+mod api_endpoint;
 mod build_version;
 mod consensus_proposals;
 mod consensus_round;
@@ -8,6 +9,7 @@ mod consensus_timeouts;
 mod handshake;
 mod hardware;
 mod latency;
+mod metric;
 mod minimum_peers;
 mod node_identity;
 mod state_sync_version;
@@ -15,8 +17,10 @@ mod tps;
 mod traits;
 mod transaction_correctness;
 mod types;
+mod view_function;
 
 use self::{
+    api_endpoint::{ApiEndpointChecker, ApiEndpointCheckerConfig},
     build_version::{BuildVersionChecker, BuildVersionCheckerConfig},
     consensus_proposals::{ConsensusProposalsChecker, ConsensusProposalsCheckerConfig},
     consensus_round::{ConsensusRoundChecker, ConsensusRoundCheckerConfig},
@@ -24,11 +28,13 @@ use self::{
     handshake::{HandshakeChecker, HandshakeCheckerConfig},
     hardware::{HardwareChecker, HardwareCheckerConfig},
     latency::{LatencyChecker, LatencyCheckerConfig},
+    metric::{MetricChecker, MetricCheckerConfig},
     minimum_peers::{MinimumPeersChecker, MinimumPeersCheckerConfig},
     node_identity::{NodeIdentityChecker, NodeIdentityCheckerConfig},
     state_sync_version::{StateSyncVersionChecker, StateSyncVersionCheckerConfig},
     tps::{TpsChecker, TpsCheckerConfig},
     transaction_correctness::{TransactionCorrectnessChecker, TransactionCorrectnessCheckerConfig},
+    view_function::{ViewFunctionChecker, ViewFunctionCheckerConfig},
 };
 use serde::{Deserialize, Serialize};
 pub use traits::{Checker, CheckerError};
@@ -40,6 +46,7 @@ pub use types::{CheckResult, CheckSummary};
 #[serde(tag = "type")]
 #[serde(deny_unknown_fields)]
 pub enum CheckerConfig {
+    ApiEndpoint(ApiEndpointCheckerConfig),
     BuildVersion(BuildVersionCheckerConfig),
     ConsensusProposals(ConsensusProposalsCheckerConfig),
     ConsensusRound(ConsensusRoundCheckerConfig),
@@ -47,16 +54,19 @@ pub enum CheckerConfig {
     Handshake(HandshakeCheckerConfig),
     Hardware(HardwareCheckerConfig),
     Latency(LatencyCheckerConfig),
+    Metric(MetricCheckerConfig),
     MinimumPeers(MinimumPeersCheckerConfig),
     NodeIdentity(NodeIdentityCheckerConfig),
     StateSyncVersion(StateSyncVersionCheckerConfig),
     Tps(TpsCheckerConfig),
     TransactionCorrectness(TransactionCorrectnessCheckerConfig),
+    ViewFunction(ViewFunctionCheckerConfig),
 }
 
 impl CheckerConfig {
     pub fn try_into_boxed_checker(self) -> Result<Box<dyn Checker>, anyhow::Error> {
         match self {
+            Self::ApiEndpoint(config) => Ok(Box::new(ApiEndpointChecker::new(config))),
             Self::BuildVersion(config) => Ok(Box::new(BuildVersionChecker::new(config))),
             Self::ConsensusProposals(config) => {
                 Ok(Box::new(ConsensusProposalsChecker::new(config)))
@@ -66,6 +76,7 @@ impl CheckerConfig {
             Self::Handshake(config) => Ok(Box::new(HandshakeChecker::new(config))),
             Self::Hardware(config) => Ok(Box::new(HardwareChecker::new(config))),
             Self::Latency(config) => Ok(Box::new(LatencyChecker::new(config))),
+            Self::Metric(config) => Ok(Box::new(MetricChecker::new(config))),
             Self::MinimumPeers(config) => Ok(Box::new(MinimumPeersChecker::new(config))),
             Self::NodeIdentity(config) => Ok(Box::new(NodeIdentityChecker::new(config))),
             Self::StateSyncVersion(config) => Ok(Box::new(StateSyncVersionChecker::new(config))),
@@ -73,6 +84,7 @@ impl CheckerConfig {
             Self::TransactionCorrectness(config) => {
                 Ok(Box::new(TransactionCorrectnessChecker::new(config)))
             },
+            Self::ViewFunction(config) => Ok(Box::new(ViewFunctionChecker::new(config))),
         }
     }
 }
@@ -4,7 +4,7 @@
 use poem_openapi::Object;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Deserialize, Object, Serialize)]
+#[derive(Clone, Debug, Deserialize, Object, PartialEq, Serialize)]
 pub struct CheckResult {
     /// Name of the Checker that created the result.
     pub checker_name: String,
@@ -39,7 +39,7 @@ impl CheckResult {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Object, Serialize)]
+#[derive(Clone, Debug, Deserialize, Object, PartialEq, Serialize)]
 pub struct CheckSummary {
     /// Results from all the Checkers NHC ran.
     pub check_results: Vec<CheckResult>,
@@ -0,0 +1,128 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{CheckResult, Checker, CheckerError, CommonCheckerConfig};
+use crate::{
+    get_provider,
+    provider::{api_index::ApiIndexProvider, ProviderCollection},
+};
+use anyhow::Result;
+use aptos_rest_client::aptos_api_types::{EntryFunctionId, MoveType, ViewRequest};
+use serde::{Deserialize, Serialize};
+
+/// Checks that a view function returns the same result on the target as on the
+/// baseline. Use functions whose result doesn't change from version to version,
+/// since the two nodes are unlikely to be queried at the exact same version.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ViewFunctionCheckerConfig {
+    #[serde(flatten)]
+    pub common: CommonCheckerConfig,
+
+    /// Name of the check, shown in the results. This distinguishes the
+    /// results when more than one ViewFunction checker is configured.
+    pub name: String,
+
+    /// Fully qualified function name, e.g. 0x1::chain_id::get.
+    pub function: EntryFunctionId,
+
+    #[serde(default)]
+    pub type_arguments: Vec<MoveType>,
+
+    /// Arguments in the JSON format accepted by the view endpoint of the API.
+    #[serde(default)]
+    pub arguments: Vec<serde_json::Value>,
+}
+
+#[derive(Debug)]
+pub struct ViewFunctionChecker {
+    config: ViewFunctionCheckerConfig,
+}
+
+impl ViewFunctionChecker {
+    pub fn new(config: ViewFunctionCheckerConfig) -> Self {
+        Self { config }
+    }
+
+    fn view_request(&self) -> ViewRequest {
+        ViewRequest {
+            function: self.config.function.clone(),
+            type_arguments: self.config.type_arguments.clone(),
+            arguments: self.config.arguments.clone(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Checker for ViewFunctionChecker {
+    /// Assert that the view function returns the same result on the target and the baseline.
+    async fn check(
+        &self,
+        providers: &ProviderCollection,
+    ) -> Result<Vec<CheckResult>, CheckerError> {
+        let baseline_api_index_provider = get_provider!(
+            providers.baseline_api_index_provider,
+            self.config.common.required,
+            ApiIndexProvider
+        );
+
+        let target_api_index_provider = get_provider!(
+            providers.target_api_index_provider,
+            self.config.common.required,
+            ApiIndexProvider
+        );
+
+        let request = self.view_request();
+
+        // We just let this error turn into a CheckerError since we want to
+        // return actual errors in the case of a failure in querying the baseline.
+        let baseline_result = baseline_api_index_provider
+            .client
+            .view(&request, None)
+            .await
+            .map_err(|e| CheckerError::RetryableEndpointError("/view", e.into()))?
+            .into_inner();
+
+        // As for the target, we return a CheckResult if something fails here.
+        let target_result = match target_api_index_provider.client.view(&request, None).await {
+            Ok(response) => response.into_inner(),
+            Err(err) => {
+                return Ok(vec![Self::build_result(
+                    format!("{}: Failed to call view function", self.config.name),
+                    0,
+                    format!(
+                        "There was an error calling the view function {} on your node: {:#}",
+                        self.config.function, err
+                    ),
+                )]);
+            },
+        };
+
+        let check_result = if baseline_result == target_result {
+            Self::build_result(
+                format!("{}: View function result matches", self.config.name),
+                100,
+                format!(
+                    "The node under investigation returned the same result for the view \
+                    function {} as the baseline node: {}",
+                    self.config.function,
+                    serde_json::Value::Array(target_result)
+                ),
+            )
+        } else {
+            Self::build_result(
+                format!("{}: View function result does not match", self.config.name),
+                0,
+                format!(
+                    "The node under investigation returned {} for the view function {}, \
+                    while the baseline returned {}. These values should match.",
+                    serde_json::Value::Array(target_result),
+                    self.config.function,
+                    serde_json::Value::Array(baseline_result)
+                ),
+            )
+        };
+
+        Ok(vec![check_result])
+    }
+}
@@ -112,6 +112,16 @@ fn get_metric_value(
     metric_name: &str,
     expected_label: Option<&Label>,
 ) -> Option<u64> {
+    get_metric_value_f64(metrics, metric_name, expected_label).map(|v| v.round() as u64)
+}
+
+/// Like get_metric_value, but without rounding the value, for metrics that
+/// aren't counts, e.g. ratios or durations in seconds.
+pub fn get_metric_value_f64(
+    metrics: &Scrape,
+    metric_name: &str,
+    expected_label: Option<&Label>,
+) -> Option<f64> {
     let mut discovered_sample = None;
     for sample in &metrics.samples {
         if sample.metric == metric_name {
@@ -134,9 +144,9 @@ fn get_metric_value(
     }
     match discovered_sample {
         Some(sample) => match &sample.value {
-            Value::Counter(v) => Some(v.round() as u64),
-            Value::Gauge(v) => Some(v.round() as u64),
-            Value::Untyped(v) => Some(v.round() as u64),
+            Value::Counter(v) => Some(*v),
+            Value::Gauge(v) => Some(*v),
+            Value::Untyped(v) => Some(*v),
             wildcard => {
                 warn!("Found unexpected metric type: {:?}", wildcard);
                 None
@@ -5,12 +5,14 @@ mod api;
 mod build;
 mod common;
 mod generate_openapi;
+mod monitor;
 mod node_information;
 mod run;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use generate_openapi::{generate_openapi, GenerateOpenapi};
+use monitor::{monitor, Monitor};
 pub use node_information::NodeInformation;
 use run::{run, Run};
 
@@ -27,12 +29,16 @@ enum Command {
 
     /// todo
     GenerateOpenapi(GenerateOpenapi),
+
+    /// Periodically check a list of nodes, keeping the history of the checks.
+    Monitor(Monitor),
 }
 
 pub async fn run_cmd(args: Server) -> Result<()> {
     let result: Result<()> = match args.cmd {
         Command::Run(args) => run(args).await,
         Command::GenerateOpenapi(args) => generate_openapi(args).await,
+        Command::Monitor(args) => monitor(args).await,
     };
     result
 }
@@ -0,0 +1,192 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    history::{CheckHistoryDb, CheckRecord},
+    metrics::checker_scores,
+    now_secs, MonitorTarget,
+};
+use anyhow::anyhow;
+use poem::http::StatusCode;
+use poem_openapi::{param::Query, payload::Json, Object, OpenApi};
+use std::{collections::BTreeMap, sync::Arc};
+
+const DEFAULT_WINDOW_SECS: u64 = 24 * 60 * 60;
+
+pub struct MonitorApi {
+    pub history: Arc<CheckHistoryDb>,
+    pub targets: Vec<MonitorTarget>,
+}
+
+impl MonitorApi {
+    fn get_target(&self, target: &str) -> poem::Result<&MonitorTarget> {
+        self.targets
+            .iter()
+            .find(|t| t.name == target)
+            .ok_or_else(|| {
+                poem::Error::from((
+                    StatusCode::BAD_REQUEST,
+                    anyhow!("Target {} is not monitored", target),
+                ))
+            })
+    }
+
+    fn get_records(
+        &self,
+        target: &str,
+        window_secs: Option<u64>,
+    ) -> poem::Result<Vec<CheckRecord>> {
+        let since_secs = now_secs().saturating_sub(window_secs.unwrap_or(DEFAULT_WINDOW_SECS));
+        self.history
+            .get_records(target, since_secs)
+            .map_err(|e| poem::Error::from((StatusCode::INTERNAL_SERVER_ERROR, e)))
+    }
+}
+
+#[OpenApi]
+impl MonitorApi {
+    /// Get the monitored targets, with the baseline configuration each is checked against.
+    #[oai(path = "/targets", method = "get")]
+    async fn targets(&self) -> Json<Vec<TargetDescriptor>> {
+        Json(
+            self.targets
+                .iter()
+                .map(|target| TargetDescriptor {
+                    name: target.name.clone(),
+                    baseline_configuration_id: target.baseline_configuration_id.clone(),
+                    node_url: target.node_address.url.to_string(),
+                })
+                .collect(),
+        )
+    }
+
+    /// Get the check records of a target within the window (default one day), oldest first.
+    #[oai(path = "/history", method = "get")]
+    async fn history(
+        &self,
+        /// Name of the monitored target.
+        target: Query<String>,
+        /// How far back to look, in seconds.
+        window_secs: Query<Option<u64>>,
+    ) -> poem::Result<Json<Vec<CheckRecord>>> {
+        self.get_target(&target.0)?;
+        Ok(Json(self.get_records(&target.0, window_secs.0)?))
+    }
+
+    /// Get the trend of the summary score of a target within the window (default one day).
+    #[oai(path = "/trend", method = "get")]
+    async fn trend(
+        &self,
+        /// Name of the monitored target.
+        target: Query<String>,
+        /// How far back to look, in seconds.
+        window_secs: Query<Option<u64>>,
+    ) -> poem::Result<Json<Trend>> {
+        self.get_target(&target.0)?;
+        let records = self.get_records(&target.0, window_secs.0)?;
+        Ok(Json(Trend::from_records(target.0, &records)))
+    }
+
+    /// Get the checkers whose score dropped in the latest check of each target,
+    /// compared to their best score within the window (default one day).
+    #[oai(path = "/regressions", method = "get")]
+    async fn regressions(
+        &self,
+        /// How far back to look, in seconds.
+        window_secs: Query<Option<u64>>,
+    ) -> poem::Result<Json<Vec<Regression>>> {
+        let mut regressions = vec![];
+        for target in &self.targets {
+            let records = self.get_records(&target.name, window_secs.0)?;
+            regressions.extend(find_regressions(&target.name, &records));
+        }
+        Ok(Json(regressions))
+    }
+}
+
+#[derive(Clone, Debug, Object)]
+struct TargetDescriptor {
+    /// Name of the monitored target.
+    pub name: String,
+    /// The baseline configuration the target is checked against.
+    pub baseline_configuration_id: String,
+    /// URL of the target node.
+    pub node_url: String,
+}
+
+#[derive(Clone, Debug, Object)]
+struct Trend {
+    /// Name of the monitored target.
+    pub target: String,
+    /// Number of checks within the window.
+    pub num_checks: u64,
+    /// Number of checks that failed to run, because of NHC or the baseline.
+    pub num_errors: u64,
+    /// Summary score of the oldest check within the window.
+    pub first_score: Option<u8>,
+    /// Summary score of the latest check.
+    pub latest_score: Option<u8>,
+    pub min_score: Option<u8>,
+    pub max_score: Option<u8>,
+    pub average_score: Option<f64>,
+}
+
+impl Trend {
+    fn from_records(target: String, records: &[CheckRecord]) -> Self {
+        let scores: Vec<u8> = records.iter().filter_map(CheckRecord::score).collect();
+        Self {
+            target,
+            num_checks: records.len() as u64,
+            num_errors: (records.len() - scores.len()) as u64,
+            first_score: scores.first().copied(),
+            latest_score: scores.last().copied(),
+            min_score: scores.iter().min().copied(),
+            max_score: scores.iter().max().copied(),
+            average_score: (!scores.is_empty()).then(|| {
+                scores.iter().map(|score| *score as f64).sum::<f64>() / scores.len() as f64
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Object)]
+struct Regression {
+    /// Name of the monitored target.
+    pub target: String,
+    /// Name of the checker whose score dropped.
+    pub checker_name: String,
+    /// Best score of the checker within the window, before the latest check.
+    pub previous_score: u8,
+    /// Score of the checker in the latest check.
+    pub latest_score: u8,
+    /// Unix timestamp of the latest check, in seconds.
+    pub timestamp_secs: u64,
+}
+
+fn find_regressions(target: &str, records: &[CheckRecord]) -> Vec<Regression> {
+    let mut successful = records.iter().filter(|record| record.summary.is_some());
+    let Some(latest) = successful.next_back() else {
+        return vec![];
+    };
+    let latest_scores = checker_scores(latest);
+    let mut best_scores = BTreeMap::new();
+    for record in successful {
+        for (checker_name, score) in checker_scores(record) {
+            let best = best_scores.entry(checker_name).or_insert(score);
+            *best = (*best).max(score);
+        }
+    }
+    latest_scores
+        .into_iter()
+        .filter_map(|(checker_name, latest_score)| {
+            let previous_score = *best_scores.get(&checker_name)?;
+            (latest_score < previous_score).then(|| Regression {
+                target: target.to_string(),
+                checker_name,
+                previous_score,
+                latest_score,
+                timestamp_secs: latest.timestamp_secs,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,156 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Local store of the check history of the monitored nodes.
+//!
+//! Check records are kept per target, ordered by time.
+//! ```text
+//! |<--------------key-------------->|<--value-->|
+//! | target name | 0 | timestamp_secs |   record  |
+//! ```
+
+use crate::CheckSummary;
+use anyhow::{ensure, Result};
+use aptos_logger::info;
+use aptos_schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+    ColumnFamilyName, Options, ReadOptions, SchemaBatch, DB, DEFAULT_COLUMN_FAMILY_NAME,
+};
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const CHECK_HISTORY_DB_NAME: &str = "check_history_db";
+
+const CHECK_HISTORY_CF_NAME: ColumnFamilyName = "check_history";
+
+/// Separates the target name from the timestamp in the key, so that
+/// the records of a target aren't interleaved with those of another.
+const KEY_SEPARATOR: u8 = 0;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CheckHistoryKey {
+    pub target: String,
+    pub timestamp_secs: u64,
+}
+
+/// The outcome of checking a target once.
+#[derive(Clone, Debug, Deserialize, Object, PartialEq, Serialize)]
+pub struct CheckRecord {
+    /// Unix timestamp of the check, in seconds.
+    pub timestamp_secs: u64,
+
+    /// The baseline configuration the target was checked against.
+    pub baseline_configuration_id: String,
+
+    /// The results of the check, if it could be run.
+    pub summary: Option<CheckSummary>,
+
+    /// The error that prevented the check from running, meaning something went
+    /// wrong with NHC or the baseline rather than with the target.
+    pub error: Option<String>,
+}
+
+impl CheckRecord {
+    pub fn score(&self) -> Option<u8> {
+        self.summary.as_ref().map(|summary| summary.summary_score)
+    }
+}
+
+define_schema!(
+    CheckHistorySchema,
+    CheckHistoryKey,
+    CheckRecord,
+    CHECK_HISTORY_CF_NAME
+);
+
+impl KeyCodec<CheckHistorySchema> for CheckHistoryKey {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let mut encoded = self.target.as_bytes().to_vec();
+        encoded.push(KEY_SEPARATOR);
+        encoded.extend_from_slice(&self.timestamp_secs.to_be_bytes());
+        Ok(encoded)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        ensure!(data.len() > 9, "Check history key is too short");
+        let (target, timestamp) = data.split_at(data.len() - 8);
+        Ok(Self {
+            target: String::from_utf8(target[..target.len() - 1].to_vec())?,
+            timestamp_secs: u64::from_be_bytes(timestamp.try_into()?),
+        })
+    }
+}
+
+impl ValueCodec<CheckHistorySchema> for CheckRecord {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(self)?)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Ok(serde_json::from_slice(data)?)
+    }
+}
+
+pub struct CheckHistoryDb {
+    db: DB,
+}
+
+impl CheckHistoryDb {
+    pub fn new(db_root_path: &Path) -> Result<Self> {
+        let column_families = vec![
+            /* UNUSED CF = */ DEFAULT_COLUMN_FAMILY_NAME,
+            CHECK_HISTORY_CF_NAME,
+        ];
+        let path = db_root_path.join(CHECK_HISTORY_DB_NAME);
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open(path.clone(), "check_history", column_families, &opts)?;
+        info!("Opened check history DB at {:?}", path);
+        Ok(Self { db })
+    }
+
+    pub fn put(&self, target: &str, record: &CheckRecord) -> Result<()> {
+        let key = CheckHistoryKey {
+            target: target.to_string(),
+            timestamp_secs: record.timestamp_secs,
+        };
+        self.db.put::<CheckHistorySchema>(&key, record)?;
+        Ok(())
+    }
+
+    /// Returns the records of the target from since_secs on, oldest first.
+    pub fn get_records(&self, target: &str, since_secs: u64) -> Result<Vec<CheckRecord>> {
+        let mut iter = self.db.iter::<CheckHistorySchema>(ReadOptions::default())?;
+        iter.seek(&CheckHistoryKey {
+            target: target.to_string(),
+            timestamp_secs: since_secs,
+        })?;
+        let mut records = Vec::new();
+        for item in iter {
+            let (key, record) = item?;
+            if key.target != target {
+                break;
+            }
+            records.push(record);
+        }
+        Ok(records)
+    }
+
+    /// Deletes the records of all targets older than before_secs.
+    pub fn prune(&self, before_secs: u64) -> Result<usize> {
+        let batch = SchemaBatch::new();
+        let mut num_pruned = 0;
+        for item in self.db.iter::<CheckHistorySchema>(ReadOptions::default())? {
+            let (key, _) = item?;
+            if key.timestamp_secs < before_secs {
+                batch.delete::<CheckHistorySchema>(&key)?;
+                num_pruned += 1;
+            }
+        }
+        self.db.write_schemas(batch)?;
+        Ok(num_pruned)
+    }
+}
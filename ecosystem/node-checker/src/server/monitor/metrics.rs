@@ -0,0 +1,92 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::history::CheckRecord;
+use aptos_metrics_core::{
+    register_int_counter_vec, register_int_gauge_vec, IntCounterVec, IntGaugeVec,
+};
+use once_cell::sync::Lazy;
+use poem::handler;
+use prometheus::{Encoder, TextEncoder};
+use std::collections::BTreeMap;
+
+static CHECK_RUNS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_node_checker_monitor_check_runs",
+        "Number of times each target was checked, by outcome (success or error).",
+        &["target", "outcome"]
+    )
+    .unwrap()
+});
+
+static SUMMARY_SCORE: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_node_checker_monitor_summary_score",
+        "Summary score of the latest check of each target.",
+        &["target", "baseline_configuration_id"]
+    )
+    .unwrap()
+});
+
+static CHECKER_SCORE: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_node_checker_monitor_checker_score",
+        "Lowest score of the results of each checker in the latest check of each target.",
+        &["target", "checker"]
+    )
+    .unwrap()
+});
+
+static LAST_CHECK_TIMESTAMP: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_node_checker_monitor_last_check_timestamp_secs",
+        "Unix timestamp of the latest check of each target.",
+        &["target"]
+    )
+    .unwrap()
+});
+
+/// Lowest score of the results of each checker, since one checker can return several results.
+pub fn checker_scores(record: &CheckRecord) -> BTreeMap<String, u8> {
+    let mut scores = BTreeMap::new();
+    for result in record
+        .summary
+        .iter()
+        .flat_map(|summary| summary.check_results.iter())
+    {
+        scores
+            .entry(result.checker_name.clone())
+            .and_modify(|score: &mut u8| *score = (*score).min(result.score))
+            .or_insert(result.score);
+    }
+    scores
+}
+
+pub fn update_metrics(target: &str, record: &CheckRecord) {
+    LAST_CHECK_TIMESTAMP
+        .with_label_values(&[target])
+        .set(record.timestamp_secs as i64);
+    match record.score() {
+        Some(score) => {
+            CHECK_RUNS.with_label_values(&[target, "success"]).inc();
+            SUMMARY_SCORE
+                .with_label_values(&[target, &record.baseline_configuration_id])
+                .set(score as i64);
+            for (checker, score) in checker_scores(record) {
+                CHECKER_SCORE
+                    .with_label_values(&[target, &checker])
+                    .set(score as i64);
+            }
+        },
+        None => CHECK_RUNS.with_label_values(&[target, "error"]).inc(),
+    }
+}
+
+#[handler]
+pub fn metrics() -> Vec<u8> {
+    let mut buffer = vec![];
+    TextEncoder
+        .encode(&aptos_metrics_core::gather(), &mut buffer)
+        .unwrap();
+    buffer
+}
@@ -0,0 +1,215 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Long running mode of NHC, which periodically checks a fixed list of target
+//! nodes, keeps the history of the checks and exposes it through the API and as
+//! Prometheus metrics.
+
+mod api;
+mod history;
+mod metrics;
+
+use super::{build::build_baseline_configuration_runners, common::ServerArgs};
+use crate::{configuration::NodeAddress, runner::Runner};
+use anyhow::{bail, Context, Result};
+use api::MonitorApi;
+use aptos_logger::{error, info};
+use clap::Parser;
+use futures::future::join_all;
+use history::{CheckHistoryDb, CheckRecord};
+use poem::{http::Method, listener::TcpListener, middleware::Cors, EndpointExt, Route, Server};
+use poem_openapi::OpenApiService;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    convert::TryInto,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use url::Url;
+
+#[derive(Clone, Debug, Parser)]
+pub struct Monitor {
+    #[clap(flatten)]
+    server_args: ServerArgs,
+
+    /// File paths leading to baseline configurations.
+    #[clap(
+        long,
+        required = true,
+        num_args = 1..,
+        use_value_delimiter = true,
+        value_parser
+    )]
+    pub baseline_config_paths: Vec<PathBuf>,
+
+    /// File path leading to the list of nodes to monitor.
+    #[clap(long, value_parser)]
+    pub targets_path: PathBuf,
+
+    /// Directory to keep the check history in.
+    #[clap(long, value_parser)]
+    pub history_path: PathBuf,
+
+    /// How often to check each target.
+    #[clap(long, default_value_t = 300)]
+    pub check_interval_secs: u64,
+
+    /// How long to keep check records for.
+    #[clap(long, default_value_t = 30 * 24 * 60 * 60)]
+    pub history_retention_secs: u64,
+}
+
+/// The nodes to monitor, e.g.
+/// ```yaml
+/// targets:
+///   - name: "my-fullnode"
+///     baseline_configuration_id: "mainnet_fullnode"
+///     node_address:
+///       url: "http://fullnode.mysite.com"
+///       api_port: 8080
+///       metrics_port: 9101
+/// ```
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MonitorTargets {
+    pub targets: Vec<MonitorTarget>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MonitorTarget {
+    /// Name of the target, used to look up its history and as a metrics label.
+    pub name: String,
+
+    /// The ID of the baseline node configuration to check the target against.
+    pub baseline_configuration_id: String,
+
+    pub node_address: NodeAddress,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs()
+}
+
+fn read_targets(path: &Path) -> Result<Vec<MonitorTarget>> {
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("Failed to open targets file {}", path.display()))?;
+    let targets: MonitorTargets = serde_yaml::from_reader(file)
+        .with_context(|| format!("Failed to parse targets file {}", path.display()))?;
+    let mut names = HashSet::new();
+    for target in &targets.targets {
+        if target.name.is_empty() || target.name.contains('\0') {
+            bail!("Invalid target name {:?}", target.name);
+        }
+        if !names.insert(target.name.clone()) {
+            bail!("Target name {} is used more than once", target.name);
+        }
+    }
+    Ok(targets.targets)
+}
+
+async fn check_target<R: Runner>(runner: &R, target: &MonitorTarget) -> CheckRecord {
+    let timestamp_secs = now_secs();
+    let (summary, error) = match runner.run(&target.node_address).await {
+        Ok(summary) => (Some(summary), None),
+        Err(e) => {
+            error!("Failed to check target {}: {:#}", target.name, e);
+            (None, Some(format!("{:#}", e)))
+        },
+    };
+    CheckRecord {
+        timestamp_secs,
+        baseline_configuration_id: target.baseline_configuration_id.clone(),
+        summary,
+        error,
+    }
+}
+
+pub async fn monitor(args: Monitor) -> Result<()> {
+    let baseline_configurations = build_baseline_configuration_runners(&args.baseline_config_paths)
+        .await
+        .context("Failed to build baseline node configurations")?;
+
+    let targets = read_targets(&args.targets_path)?;
+    for target in &targets {
+        if !baseline_configurations
+            .0
+            .contains_key(&target.baseline_configuration_id)
+        {
+            bail!(
+                "Target {} uses baseline configuration {} which does not exist",
+                target.name,
+                target.baseline_configuration_id
+            );
+        }
+    }
+    info!("Monitoring {} targets", targets.len());
+
+    let history = Arc::new(CheckHistoryDb::new(&args.history_path)?);
+
+    let check_loop = {
+        let history = history.clone();
+        let targets = targets.clone();
+        let mut interval = tokio::time::interval(Duration::from_secs(args.check_interval_secs));
+        async move {
+            loop {
+                interval.tick().await;
+                let records = join_all(targets.iter().map(|target| {
+                    let runner =
+                        &baseline_configurations.0[&target.baseline_configuration_id].runner;
+                    check_target(runner, target)
+                }))
+                .await;
+                for (target, record) in targets.iter().zip(records) {
+                    metrics::update_metrics(&target.name, &record);
+                    if let Err(e) = history.put(&target.name, &record) {
+                        error!("Failed to store check record of {}: {:#}", target.name, e);
+                    }
+                }
+                match history.prune(now_secs().saturating_sub(args.history_retention_secs)) {
+                    Ok(num_pruned) if num_pruned > 0 => {
+                        info!("Pruned {} check records", num_pruned)
+                    },
+                    Ok(_) => {},
+                    Err(e) => error!("Failed to prune check history: {:#}", e),
+                }
+            }
+        }
+    };
+    tokio::spawn(check_loop);
+
+    let api = MonitorApi { history, targets };
+
+    let api_endpoint = format!("/{}", args.server_args.api_path);
+    let version = std::env::var("CARGO_PKG_VERSION").unwrap_or_else(|_| "0.2.0".to_string());
+    let url: Url = args
+        .server_args
+        .clone()
+        .try_into()
+        .context("Failed to parse listen address")?;
+    let api_service = OpenApiService::new(api, "Aptos Node Checker Monitor", version).server(url);
+    let ui = api_service.swagger_ui();
+    let spec_json = api_service.spec_endpoint();
+
+    let cors = Cors::new().allow_methods(vec![Method::GET]);
+
+    Server::new(TcpListener::bind((
+        args.server_args.listen_address,
+        args.server_args.listen_port,
+    )))
+    .run(
+        Route::new()
+            .nest(api_endpoint, api_service)
+            .nest("/spec", ui)
+            .at("/spec.json", spec_json)
+            .at("/metrics", metrics::metrics)
+            .with(cors),
+    )
+    .await
+    .map_err(anyhow::Error::msg)
+}
@@ -283,6 +283,7 @@ where
             phase_clone,
         )
         .await
+        .expect("Failed to create the transaction generator")
     });
 
     pipeline.join();
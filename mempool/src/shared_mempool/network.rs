@@ -24,7 +24,10 @@ use aptos_infallible::RwLock;
 use aptos_logger::prelude::*;
 use aptos_netcore::transport::ConnectionOrigin;
 use aptos_network::{
-    application::{error::Error, interface::NetworkClientInterface, metadata::PeerMetadata},
+    application::{
+        error::Error, interface::NetworkClientInterface, metadata::PeerMetadata,
+        reputation::ReputationEvent,
+    },
     transport::ConnectionMetadata,
 };
 use aptos_time_service::TimeService;
@@ -448,6 +451,13 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
         self.network_client.send_to_peer(message, peer)
     }
 
+    /// Reports a reputation event for the given peer (e.g., for a malformed broadcast)
+    pub fn report_peer_event(&self, peer: PeerNetworkId, event: ReputationEvent) {
+        self.network_client
+            .get_peers_and_metadata()
+            .report_peer_event(peer, event);
+    }
+
     /// Updates the local tracker for a broadcast.  This is used to handle `DirectSend` tracking of
     /// responses
     fn update_broadcast_state(
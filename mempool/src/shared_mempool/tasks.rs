@@ -21,10 +21,13 @@ use aptos_consensus_types::common::RejectedTransactionSummary;
 use aptos_crypto::HashValue;
 use aptos_experimental_runtimes::thread_manager::optimal_min_len;
 use aptos_infallible::{Mutex, RwLock};
-use aptos_logger::prelude::*;
+use aptos_logger::{
+    otlp_tracing::{record_txns, txn_span, TxnStage},
+    prelude::*,
+};
 use aptos_mempool_notifications::CommittedTransaction;
 use aptos_metrics_core::HistogramTimer;
-use aptos_network::application::interface::NetworkClientInterface;
+use aptos_network::application::{interface::NetworkClientInterface, reputation::ReputationEvent};
 use aptos_storage_interface::state_view::LatestDbStateCheckpointView;
 use aptos_types::{
     mempool_status::{MempoolStatus, MempoolStatusCode},
@@ -182,6 +185,17 @@ pub(crate) async fn process_transaction_broadcast<NetworkClient, TransactionVali
     let results = process_incoming_transactions(&smp, transactions, timeline_state, false);
     log_txn_process_results(&results, Some(peer));
 
+    // Peers should only broadcast transactions that they have already validated,
+    // so they lose reputation for a batch with invalid signatures. This is milder
+    // than a malformed message, as the peer may have simply relayed the batch.
+    if results
+        .iter()
+        .any(|(_, (_, vm_status))| *vm_status == Some(DiscardedVMStatus::INVALID_SIGNATURE))
+    {
+        smp.network_interface
+            .report_peer_event(peer, ReputationEvent::InvalidSignature);
+    }
+
     let ack_response = gen_ack_response(request_id, results, &peer);
 
     // Respond to the peer with an ack. Note: ack response messages should be
@@ -263,6 +277,8 @@ where
     NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
     TransactionValidator: TransactionValidation,
 {
+    let span = txn_span(TxnStage::MEMPOOL_INSERT);
+    record_txns(&span, transactions.iter().map(|t| t.committed_hash()));
     let mut statuses = vec![];
 
     let start_storage_read = Instant::now();
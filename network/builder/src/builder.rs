@@ -12,10 +12,10 @@
 //! long as the latter is in its trusted peers set.
 use aptos_config::{
     config::{
-        DiscoveryMethod, NetworkConfig, Peer, PeerRole, PeerSet, RoleType, CONNECTION_BACKOFF_BASE,
-        CONNECTIVITY_CHECK_INTERVAL_MS, MAX_CONCURRENT_NETWORK_REQS, MAX_CONNECTION_DELAY_MS,
-        MAX_FRAME_SIZE, MAX_FULLNODE_OUTBOUND_CONNECTIONS, MAX_INBOUND_CONNECTIONS,
-        NETWORK_CHANNEL_SIZE,
+        DiscoveryMethod, NetworkConfig, OutboundTrafficConfig, Peer, PeerRole, PeerSet, RoleType,
+        CONNECTION_BACKOFF_BASE, CONNECTIVITY_CHECK_INTERVAL_MS, MAX_CONCURRENT_NETWORK_REQS,
+        MAX_CONNECTION_DELAY_MS, MAX_FRAME_SIZE, MAX_FULLNODE_OUTBOUND_CONNECTIONS,
+        MAX_INBOUND_CONNECTIONS, NETWORK_CHANNEL_SIZE,
     },
     network_id::NetworkContext,
 };
@@ -87,6 +87,7 @@ impl NetworkBuilder {
         max_concurrent_network_reqs: usize,
         inbound_connection_limit: usize,
         tcp_buffer_cfg: TCPBufferCfg,
+        outbound_traffic_config: OutboundTrafficConfig,
     ) -> Self {
         // A network cannot exist without a PeerManager
         // TODO:  construct this in create and pass it to new() as a parameter. The complication is manual construction of NetworkBuilder in various tests.
@@ -104,6 +105,7 @@ impl NetworkBuilder {
             enable_proxy_protocol,
             inbound_connection_limit,
             tcp_buffer_cfg,
+            outbound_traffic_config,
         );
 
         NetworkBuilder {
@@ -144,6 +146,7 @@ impl NetworkBuilder {
             MAX_CONCURRENT_NETWORK_REQS,
             MAX_INBOUND_CONNECTIONS,
             TCPBufferCfg::default(),
+            OutboundTrafficConfig::default(),
         );
 
         builder.add_connectivity_manager(
@@ -181,6 +184,15 @@ impl NetworkBuilder {
 
         let network_context = NetworkContext::new(role, config.network_id, peer_id);
 
+        // Configure peer reputation tracking (and banning) for the network. Peers are
+        // only banned on the public network: the validator network is limited to the
+        // validator set, and the VFN network connects VFNs to their upstream validators.
+        let mut peer_reputation_config = config.peer_reputation_config;
+        if !config.network_id.is_public_network() {
+            peer_reputation_config.enable_peer_banning = false;
+        }
+        peers_and_metadata.set_peer_reputation_config(config.network_id, peer_reputation_config);
+
         let mut network_builder = NetworkBuilder::new(
             chain_id,
             peers_and_metadata.clone(),
@@ -200,6 +212,7 @@ impl NetworkBuilder {
                 config.outbound_rx_buffer_size_bytes,
                 config.outbound_tx_buffer_size_bytes,
             ),
+            config.outbound_traffic_config.clone(),
         );
 
         network_builder.add_connection_monitoring(
@@ -389,6 +402,20 @@ impl NetworkBuilder {
                     Duration::from_secs(rest_discovery.interval_secs),
                     self.time_service.clone(),
                 ),
+                DiscoveryMethod::Dns(dns_discovery) => DiscoveryChangeListener::dns(
+                    self.network_context,
+                    conn_mgr_reqs_tx.clone(),
+                    dns_discovery.srv_name.clone(),
+                    dns_discovery.peer_role,
+                    Duration::from_secs(dns_discovery.interval_secs),
+                    self.time_service.clone(),
+                ),
+                DiscoveryMethod::Merged(merged_discovery) => DiscoveryChangeListener::merged(
+                    self.network_context,
+                    conn_mgr_reqs_tx.clone(),
+                    merged_discovery,
+                    self.time_service.clone(),
+                ),
                 DiscoveryMethod::None => {
                     continue;
                 },
@@ -2,10 +2,12 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use aptos_config::network_id::NetworkContext;
 use aptos_metrics_core::{
     op_counters::DurationHistogram, register_histogram, register_int_counter_vec,
     register_int_gauge_vec, IntCounterVec, IntGaugeVec,
 };
+use aptos_short_hex_str::AsShortHexStr;
 use once_cell::sync::Lazy;
 
 /// Histogram of busy time of spent in event processing loop
@@ -36,3 +38,27 @@ pub static NETWORK_KEY_MISMATCH: Lazy<IntGaugeVec> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+pub static DISCOVERY_SOURCE_HEALTHY: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_network_discovery_source_healthy",
+        "Gauge of whether each source of a merged discovery is healthy (i.e., not stale)",
+        &["role_type", "network_id", "peer_id", "source"]
+    )
+    .unwrap()
+});
+
+pub fn set_discovery_source_healthy(
+    network_context: &NetworkContext,
+    source_name: &str,
+    is_healthy: bool,
+) {
+    DISCOVERY_SOURCE_HEALTHY
+        .with_label_values(&[
+            network_context.role().as_str(),
+            network_context.network_id().as_str(),
+            network_context.peer_id().short_str().as_str(),
+            source_name,
+        ])
+        .set(is_healthy as i64)
+}
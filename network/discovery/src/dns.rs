@@ -0,0 +1,334 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::DiscoveryError;
+use aptos_config::config::{Peer, PeerRole, PeerSet, HANDSHAKE_VERSION};
+use aptos_crypto::{x25519, ValidCryptoMaterialStringExt};
+use aptos_logger::{info, warn};
+use aptos_time_service::{Interval, TimeService, TimeServiceTrait};
+use aptos_types::{
+    account_address::from_identity_public_key,
+    network_address::{DnsName, NetworkAddress, Protocol},
+    PeerId,
+};
+use futures::{future::BoxFuture, Future, FutureExt, Stream};
+use hickory_resolver::{
+    config::{ResolverConfig, ResolverOpts},
+    TokioAsyncResolver,
+};
+use std::{
+    collections::{hash_map::Entry, HashSet},
+    pin::Pin,
+    str::FromStr,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// The TXT record keys used to advertise a peer's identity
+const TXT_PEER_ID_KEY: &str = "peer_id";
+const TXT_PUBKEY_KEY: &str = "pubkey";
+
+/// A discovery stream that looks up the peers using DNS. The SRV records of
+/// the given name list the host and port of each peer, and the TXT records of
+/// each host advertise the peer's network public key (and optionally peer id).
+pub struct DnsStream {
+    resolver: Arc<TokioAsyncResolver>,
+    srv_name: String,
+    peer_role: PeerRole,
+    interval: Pin<Box<Interval>>,
+    pending_lookup: Option<BoxFuture<'static, Result<PeerSet, DiscoveryError>>>,
+}
+
+impl DnsStream {
+    pub(crate) fn new(
+        srv_name: String,
+        peer_role: PeerRole,
+        interval_duration: Duration,
+        time_service: TimeService,
+    ) -> Self {
+        let resolver = TokioAsyncResolver::tokio_from_system_conf().unwrap_or_else(|error| {
+            warn!(
+                "Failed to load the system DNS configuration, using the defaults: {:?}",
+                error
+            );
+            TokioAsyncResolver::tokio(ResolverConfig::default(), ResolverOpts::default())
+        });
+        DnsStream {
+            resolver: Arc::new(resolver),
+            srv_name,
+            peer_role,
+            interval: Box::pin(time_service.interval(interval_duration)),
+            pending_lookup: None,
+        }
+    }
+}
+
+impl Stream for DnsStream {
+    type Item = Result<PeerSet, DiscoveryError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // Wait for delay, and start a new lookup at the interval
+        if self.pending_lookup.is_none() {
+            futures::ready!(self.interval.as_mut().poll_next(cx));
+            let lookup = lookup_peers(self.resolver.clone(), self.srv_name.clone(), self.peer_role);
+            self.pending_lookup = Some(lookup.boxed());
+        }
+
+        // Wait for the lookup to complete
+        let pending_lookup = self
+            .pending_lookup
+            .as_mut()
+            .expect("The pending lookup should exist!");
+        let result = futures::ready!(pending_lookup.as_mut().poll(cx));
+        self.pending_lookup = None;
+
+        if let Err(error) = &result {
+            info!("Failed to discover peers using DNS: {:?}", error);
+        }
+        Poll::Ready(Some(result))
+    }
+}
+
+/// A peer host advertised by an SRV record, along with the
+/// contents of the host's TXT records.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct DnsPeerRecord {
+    host: String,
+    port: u16,
+    txt_entries: Vec<String>,
+}
+
+/// Looks up the SRV records for the given name (and the TXT records
+/// of each target host) and converts them into a peer set. Hosts whose
+/// TXT lookup fails are skipped (the other hosts are still discovered).
+async fn lookup_peers(
+    resolver: Arc<TokioAsyncResolver>,
+    srv_name: String,
+    peer_role: PeerRole,
+) -> Result<PeerSet, DiscoveryError> {
+    let srv_lookup = resolver
+        .srv_lookup(srv_name.as_str())
+        .await
+        .map_err(|error| DiscoveryError::Dns(error.to_string()))?;
+
+    let mut records = vec![];
+    for srv in srv_lookup.iter() {
+        let host = srv.target().to_utf8();
+        let txt_lookup = match resolver.txt_lookup(host.as_str()).await {
+            Ok(txt_lookup) => txt_lookup,
+            Err(error) => {
+                warn!(
+                    "Skipping DNS discovery host {} (TXT lookup failed): {}",
+                    host, error
+                );
+                continue;
+            },
+        };
+        let txt_entries = txt_lookup
+            .iter()
+            .flat_map(|txt| txt.txt_data().iter())
+            .map(|txt_data| String::from_utf8_lossy(txt_data).into_owned())
+            .collect();
+        records.push(DnsPeerRecord {
+            host,
+            port: srv.port(),
+            txt_entries,
+        });
+    }
+
+    Ok(build_peer_set(records, peer_role))
+}
+
+/// Converts the DNS records into a peer set. Records that are invalid
+/// are skipped (so that a single bad record can't break discovery).
+fn build_peer_set(records: Vec<DnsPeerRecord>, peer_role: PeerRole) -> PeerSet {
+    let mut peer_set = PeerSet::new();
+    for record in records {
+        let (peer_id, pubkey, address) = match parse_peer_record(&record) {
+            Ok(parsed_record) => parsed_record,
+            Err(error) => {
+                warn!(
+                    "Skipping invalid DNS discovery record for host {}: {:?}",
+                    record.host, error
+                );
+                continue;
+            },
+        };
+
+        // Multiple hosts may advertise the same peer (e.g., one per address)
+        match peer_set.entry(peer_id) {
+            Entry::Occupied(mut entry) => {
+                let peer = entry.get_mut();
+                peer.keys.insert(pubkey);
+                if !peer.addresses.contains(&address) {
+                    peer.addresses.push(address);
+                }
+            },
+            Entry::Vacant(entry) => {
+                entry.insert(Peer::new(vec![address], HashSet::from([pubkey]), peer_role));
+            },
+        }
+    }
+    peer_set
+}
+
+/// Parses the peer id, public key and network address from the given record
+fn parse_peer_record(
+    record: &DnsPeerRecord,
+) -> Result<(PeerId, x25519::PublicKey, NetworkAddress), DiscoveryError> {
+    // Parse the key value pairs in the TXT entries (separated by whitespace or semicolons)
+    let mut peer_id = None;
+    let mut pubkey = None;
+    for key_value in record
+        .txt_entries
+        .iter()
+        .flat_map(|entry| entry.split(|c: char| c == ';' || c.is_whitespace()))
+        .filter(|key_value| !key_value.is_empty())
+    {
+        match key_value.split_once('=') {
+            Some((TXT_PEER_ID_KEY, value)) => {
+                peer_id = Some(
+                    PeerId::from_str(value)
+                        .map_err(|error| DiscoveryError::Parsing(error.to_string()))?,
+                );
+            },
+            Some((TXT_PUBKEY_KEY, value)) => {
+                pubkey = Some(
+                    x25519::PublicKey::from_encoded_string(value)
+                        .map_err(|error| DiscoveryError::Parsing(error.to_string()))?,
+                );
+            },
+            _ => {}, // Ignore any unrelated TXT entries
+        }
+    }
+
+    // A public key is required, but the peer id can be derived from it
+    let pubkey = pubkey.ok_or_else(|| {
+        DiscoveryError::Parsing(format!(
+            "No {} entry found in the TXT records",
+            TXT_PUBKEY_KEY
+        ))
+    })?;
+    let peer_id = peer_id.unwrap_or_else(|| from_identity_public_key(pubkey));
+
+    // Build the network address (SRV targets are fully qualified, so trim the trailing dot)
+    let host = record.host.trim_end_matches('.').to_string();
+    let dns_name =
+        DnsName::try_from(host).map_err(|error| DiscoveryError::Parsing(error.to_string()))?;
+    let address =
+        NetworkAddress::from_protocols(vec![Protocol::Dns(dns_name), Protocol::Tcp(record.port)])
+            .map_err(|error| DiscoveryError::Parsing(error.to_string()))?
+            .append_prod_protos(pubkey, HANDSHAKE_VERSION);
+
+    Ok((peer_id, pubkey, address))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_crypto::{test_utils::TEST_SEED, Uniform};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn create_pubkey(rng: &mut StdRng) -> x25519::PublicKey {
+        x25519::PrivateKey::generate(rng).public_key()
+    }
+
+    fn create_record(host: &str, port: u16, txt_entries: Vec<String>) -> DnsPeerRecord {
+        DnsPeerRecord {
+            host: host.into(),
+            port,
+            txt_entries,
+        }
+    }
+
+    #[test]
+    fn test_build_peer_set() {
+        let mut rng = StdRng::from_seed(TEST_SEED);
+        let pubkey_1 = create_pubkey(&mut rng);
+        let pubkey_2 = create_pubkey(&mut rng);
+        let peer_id_2 = PeerId::random();
+
+        let records = vec![
+            // A peer with only a public key (the peer id is derived)
+            create_record(
+                "fn-1.fleet.example.com.",
+                6182,
+                vec![format!("pubkey={}", pubkey_1)],
+            ),
+            // A peer with an explicit peer id, advertised by two hosts
+            create_record(
+                "fn-2a.fleet.example.com.",
+                6182,
+                vec![format!("peer_id={}; pubkey={}", peer_id_2, pubkey_2)],
+            ),
+            create_record(
+                "fn-2b.fleet.example.com.",
+                6183,
+                vec![
+                    format!("peer_id={}", peer_id_2),
+                    format!("pubkey={}", pubkey_2),
+                    "v=unrelated".into(),
+                ],
+            ),
+            // An invalid record (missing the public key)
+            create_record(
+                "fn-3.fleet.example.com.",
+                6182,
+                vec![format!("peer_id={}", PeerId::random())],
+            ),
+        ];
+        let peer_set = build_peer_set(records, PeerRole::Upstream);
+
+        // Verify the peer set
+        assert_eq!(peer_set.len(), 2);
+        let peer_1 = peer_set.get(&from_identity_public_key(pubkey_1)).unwrap();
+        assert_eq!(peer_1.role, PeerRole::Upstream);
+        assert_eq!(peer_1.keys, HashSet::from([pubkey_1]));
+        assert_eq!(
+            peer_1.addresses,
+            vec![
+                NetworkAddress::from_str("/dns/fn-1.fleet.example.com/tcp/6182")
+                    .unwrap()
+                    .append_prod_protos(pubkey_1, HANDSHAKE_VERSION)
+            ]
+        );
+
+        let peer_2 = peer_set.get(&peer_id_2).unwrap();
+        assert_eq!(peer_2.keys, HashSet::from([pubkey_2]));
+        assert_eq!(peer_2.addresses.len(), 2);
+        assert_eq!(
+            peer_2.addresses[1].to_string(),
+            format!(
+                "/dns/fn-2b.fleet.example.com/tcp/6183/noise-ik/0x{}/handshake/0",
+                pubkey_2
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_records() {
+        let mut rng = StdRng::from_seed(TEST_SEED);
+        let pubkey = create_pubkey(&mut rng);
+
+        // Invalid public key
+        let record = create_record("fn.example.com", 6182, vec!["pubkey=0x1234".into()]);
+        assert!(parse_peer_record(&record).is_err());
+
+        // Invalid peer id
+        let record = create_record(
+            "fn.example.com",
+            6182,
+            vec![format!("peer_id=invalid pubkey={}", pubkey)],
+        );
+        assert!(parse_peer_record(&record).is_err());
+
+        // Invalid host
+        let record = create_record("fn/example.com", 6182, vec![format!("pubkey={}", pubkey)]);
+        assert!(parse_peer_record(&record).is_err());
+
+        // Valid record
+        let record = create_record("fn.example.com", 6182, vec![format!("pubkey={}", pubkey)]);
+        assert!(parse_peer_record(&record).is_ok());
+    }
+}
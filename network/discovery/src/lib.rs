@@ -3,10 +3,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    counters::DISCOVERY_COUNTS, file::FileStream, rest::RestStream,
+    counters::DISCOVERY_COUNTS,
+    dns::DnsStream,
+    file::FileStream,
+    merged::{MergedStream, PeerSetStream},
+    rest::RestStream,
     validator_set::ValidatorSetStream,
 };
-use aptos_config::{config::PeerSet, network_id::NetworkContext};
+use aptos_config::{
+    config::{DiscoveryMethod, MergedDiscovery, PeerRole, PeerSet},
+    network_id::NetworkContext,
+};
 use aptos_crypto::x25519;
 use aptos_event_notifications::ReconfigNotificationListener;
 use aptos_logger::prelude::*;
@@ -27,7 +34,9 @@ use std::{
 use tokio::runtime::Handle;
 
 mod counters;
+mod dns;
 mod file;
+mod merged;
 mod rest;
 mod validator_set;
 
@@ -36,6 +45,7 @@ pub enum DiscoveryError {
     IO(std::io::Error),
     Parsing(String),
     Rest(aptos_rest_client::error::RestError),
+    Dns(String),
 }
 
 /// A union type for all implementations of `DiscoveryChangeListenerTrait`
@@ -50,6 +60,8 @@ enum DiscoveryChangeStream<P: OnChainConfigProvider> {
     ValidatorSet(ValidatorSetStream<P>),
     File(FileStream),
     Rest(RestStream),
+    Dns(DnsStream),
+    Merged(MergedStream),
 }
 
 impl<P: OnChainConfigProvider> Stream for DiscoveryChangeStream<P> {
@@ -60,6 +72,8 @@ impl<P: OnChainConfigProvider> Stream for DiscoveryChangeStream<P> {
             Self::ValidatorSet(stream) => Pin::new(stream).poll_next(cx),
             Self::File(stream) => Pin::new(stream).poll_next(cx),
             Self::Rest(stream) => Pin::new(stream).poll_next(cx),
+            Self::Dns(stream) => Pin::new(stream).poll_next(cx),
+            Self::Merged(stream) => Pin::new(stream).poll_next(cx),
         }
     }
 }
@@ -125,6 +139,87 @@ impl<P: OnChainConfigProvider> DiscoveryChangeListener<P> {
         }
     }
 
+    pub fn dns(
+        network_context: NetworkContext,
+        update_channel: aptos_channels::Sender<ConnectivityRequest>,
+        srv_name: String,
+        peer_role: PeerRole,
+        interval_duration: Duration,
+        time_service: TimeService,
+    ) -> Self {
+        let source_stream = DiscoveryChangeStream::Dns(DnsStream::new(
+            srv_name,
+            peer_role,
+            interval_duration,
+            time_service,
+        ));
+        DiscoveryChangeListener {
+            discovery_source: DiscoverySource::Dns,
+            network_context,
+            update_channel,
+            source_stream,
+        }
+    }
+
+    pub fn merged(
+        network_context: NetworkContext,
+        update_channel: aptos_channels::Sender<ConnectivityRequest>,
+        merged_discovery: &MergedDiscovery,
+        time_service: TimeService,
+    ) -> Self {
+        // Create a stream for each of the sources
+        let mut sources = vec![];
+        for source in &merged_discovery.sources {
+            let stream: PeerSetStream = match &source.method {
+                DiscoveryMethod::File(file_discovery) => Box::pin(FileStream::new(
+                    file_discovery.path.as_path(),
+                    Duration::from_secs(file_discovery.interval_secs),
+                    time_service.clone(),
+                )),
+                DiscoveryMethod::Rest(rest_discovery) => Box::pin(RestStream::new(
+                    network_context,
+                    rest_discovery.url.clone(),
+                    Duration::from_secs(rest_discovery.interval_secs),
+                    time_service.clone(),
+                )),
+                DiscoveryMethod::Dns(dns_discovery) => Box::pin(DnsStream::new(
+                    dns_discovery.srv_name.clone(),
+                    dns_discovery.peer_role,
+                    Duration::from_secs(dns_discovery.interval_secs),
+                    time_service.clone(),
+                )),
+                method => {
+                    warn!(
+                        NetworkSchema::new(&network_context),
+                        "{} Unsupported merged discovery source: {:?}. Ignoring it!",
+                        network_context,
+                        method
+                    );
+                    continue;
+                },
+            };
+            let name = format!(
+                "{}_{}",
+                sources.len(),
+                discovery_method_label(&source.method)
+            );
+            sources.push((name, source.priority, stream));
+        }
+
+        let source_stream = DiscoveryChangeStream::Merged(MergedStream::new(
+            network_context,
+            sources,
+            Duration::from_secs(merged_discovery.max_staleness_secs),
+            time_service,
+        ));
+        DiscoveryChangeListener {
+            discovery_source: DiscoverySource::Merged,
+            network_context,
+            update_channel,
+            source_stream,
+        }
+    }
+
     pub fn start(self, executor: &Handle) {
         spawn_named!("DiscoveryChangeListener", executor, Box::pin(self).run());
     }
@@ -175,3 +270,15 @@ impl<P: OnChainConfigProvider> DiscoveryChangeListener<P> {
         self.discovery_source
     }
 }
+
+/// Returns a short label for the discovery method (e.g., for metrics)
+fn discovery_method_label(discovery_method: &DiscoveryMethod) -> &'static str {
+    match discovery_method {
+        DiscoveryMethod::Onchain => "onchain",
+        DiscoveryMethod::File(_) => "file",
+        DiscoveryMethod::Rest(_) => "rest",
+        DiscoveryMethod::Dns(_) => "dns",
+        DiscoveryMethod::Merged(_) => "merged",
+        DiscoveryMethod::None => "none",
+    }
+}
@@ -0,0 +1,293 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{counters, DiscoveryError};
+use aptos_config::{config::PeerSet, network_id::NetworkContext};
+use aptos_logger::prelude::*;
+use aptos_network::{counters::inc_by_with_context, logging::NetworkSchema};
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use futures::Stream;
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+/// A boxed stream of peer set updates from a single discovery source
+pub(crate) type PeerSetStream = Pin<Box<dyn Stream<Item = Result<PeerSet, DiscoveryError>> + Send>>;
+
+/// A single discovery source of a merged stream, along with its health
+struct MergedSource {
+    name: String,
+    priority: u8,
+    stream: PeerSetStream,
+    terminated: bool,
+
+    // The latest successfully discovered peers and the time they were received
+    peers: PeerSet,
+    last_success: Option<Instant>,
+    consecutive_failures: u64,
+}
+
+impl MergedSource {
+    /// Returns true iff the source has successfully refreshed within the max staleness
+    fn is_healthy(&self, now: Instant, max_staleness: Duration) -> bool {
+        self.last_success
+            .map(|last_success| now.duration_since(last_success) <= max_staleness)
+            .unwrap_or(false)
+    }
+}
+
+/// A discovery stream that merges the peers discovered by several sources.
+/// If several sources discover the same peer, the peer is taken from the
+/// source with the highest priority (its keys, addresses and role). The peers of a source are dropped
+/// once the source has failed to refresh for longer than the max staleness.
+pub struct MergedStream {
+    network_context: NetworkContext,
+    sources: Vec<MergedSource>,
+    max_staleness: Duration,
+    time_service: TimeService,
+}
+
+impl MergedStream {
+    pub(crate) fn new(
+        network_context: NetworkContext,
+        sources: Vec<(String, u8, PeerSetStream)>,
+        max_staleness: Duration,
+        time_service: TimeService,
+    ) -> Self {
+        let mut sources: Vec<_> = sources
+            .into_iter()
+            .map(|(name, priority, stream)| MergedSource {
+                name,
+                priority,
+                stream,
+                terminated: false,
+                peers: PeerSet::new(),
+                last_success: None,
+                consecutive_failures: 0,
+            })
+            .collect();
+
+        // Order the sources from highest to lowest priority (the sort is
+        // stable, so sources with the same priority keep the config order).
+        sources.sort_by(|source_1, source_2| source_2.priority.cmp(&source_1.priority));
+
+        MergedStream {
+            network_context,
+            sources,
+            max_staleness,
+            time_service,
+        }
+    }
+
+    /// Updates the health of the source at the given index using the update
+    fn handle_source_update(
+        &mut self,
+        index: usize,
+        update: Result<PeerSet, DiscoveryError>,
+        now: Instant,
+    ) {
+        let network_context = self.network_context;
+        let source = &mut self.sources[index];
+        match update {
+            Ok(peers) => {
+                source.peers = peers;
+                source.last_success = Some(now);
+                source.consecutive_failures = 0;
+            },
+            Err(error) => {
+                source.consecutive_failures += 1;
+                inc_by_with_context(
+                    &counters::DISCOVERY_COUNTS,
+                    &network_context,
+                    "merged_source_failure",
+                    1,
+                );
+                warn!(
+                    NetworkSchema::new(&network_context),
+                    "{} Merged discovery source {} failed! Consecutive failures: {}, error: {:?}",
+                    network_context,
+                    source.name,
+                    source.consecutive_failures,
+                    error
+                );
+            },
+        }
+    }
+
+    /// Merges the peers of all healthy sources (in priority order)
+    fn merge_peers(&self, now: Instant) -> PeerSet {
+        let mut merged_peers = PeerSet::new();
+        for source in &self.sources {
+            // Update the health metrics for the source
+            let is_healthy = source.is_healthy(now, self.max_staleness);
+            counters::set_discovery_source_healthy(&self.network_context, &source.name, is_healthy);
+            if !is_healthy {
+                continue;
+            }
+
+            // Add the peers that are absent from higher priority sources
+            for (peer_id, peer) in source.peers.iter() {
+                merged_peers.entry(*peer_id).or_insert_with(|| peer.clone());
+            }
+        }
+        merged_peers
+    }
+}
+
+impl Stream for MergedStream {
+    type Item = Result<PeerSet, DiscoveryError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let network_context = this.network_context;
+        let now = this.time_service.now();
+
+        // Poll each source once for updates
+        let mut updated = false;
+        for index in 0..this.sources.len() {
+            let source = &mut this.sources[index];
+            if source.terminated {
+                continue;
+            }
+            match source.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(update)) => {
+                    this.handle_source_update(index, update, now);
+                    updated = true;
+                },
+                Poll::Ready(None) => {
+                    warn!(
+                        NetworkSchema::new(&network_context),
+                        "{} Merged discovery source {} terminated!", network_context, source.name
+                    );
+                    source.terminated = true;
+                },
+                Poll::Pending => {},
+            }
+        }
+
+        // Send the merged peers whenever a source is updated
+        if updated {
+            Poll::Ready(Some(Ok(this.merge_peers(now))))
+        } else if this.sources.iter().all(|source| source.terminated) {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_config::config::{Peer, PeerRole};
+    use aptos_types::{network_address::NetworkAddress, PeerId};
+    use futures::{channel::mpsc, StreamExt};
+    use std::{collections::HashSet, str::FromStr};
+
+    type UpdateSender = mpsc::UnboundedSender<Result<PeerSet, DiscoveryError>>;
+
+    fn create_merged_stream(
+        priorities: &[u8],
+        max_staleness: Duration,
+    ) -> (MergedStream, Vec<UpdateSender>, TimeService) {
+        let time_service = TimeService::mock();
+        let mut senders = vec![];
+        let mut sources = vec![];
+        for (index, priority) in priorities.iter().enumerate() {
+            let (sender, receiver) = mpsc::unbounded();
+            senders.push(sender);
+            sources.push((
+                format!("source_{}", index),
+                *priority,
+                Box::pin(receiver) as PeerSetStream,
+            ));
+        }
+        let merged_stream = MergedStream::new(
+            NetworkContext::mock(),
+            sources,
+            max_staleness,
+            time_service.clone(),
+        );
+        (merged_stream, senders, time_service)
+    }
+
+    fn create_peer(address: &str, role: PeerRole) -> Peer {
+        let address = NetworkAddress::from_str(address).unwrap();
+        Peer::new(vec![address], HashSet::new(), role)
+    }
+
+    #[tokio::test]
+    async fn test_merge_by_priority() {
+        let (mut merged_stream, senders, _) =
+            create_merged_stream(&[1, 10], Duration::from_secs(60));
+
+        // Send the same peer from both sources
+        let peer_id = PeerId::random();
+        let low_priority_peer = create_peer("/ip4/1.1.1.1/tcp/6180", PeerRole::Known);
+        let high_priority_peer = create_peer("/ip4/2.2.2.2/tcp/6180", PeerRole::Upstream);
+        senders[0]
+            .unbounded_send(Ok(PeerSet::from([(peer_id, low_priority_peer)])))
+            .unwrap();
+        senders[1]
+            .unbounded_send(Ok(PeerSet::from([(peer_id, high_priority_peer.clone())])))
+            .unwrap();
+
+        // Verify the merged peer is taken from the high priority source
+        let merged_peers = merged_stream.next().await.unwrap().unwrap();
+        assert_eq!(merged_peers.get(&peer_id).unwrap(), &high_priority_peer);
+    }
+
+    #[tokio::test]
+    async fn test_stale_sources_are_dropped() {
+        let max_staleness = Duration::from_secs(60);
+        let (mut merged_stream, senders, time_service) =
+            create_merged_stream(&[1, 2], max_staleness);
+        let mock_time = time_service.into_mock();
+
+        // Send a different peer from each source
+        let peer_id_1 = PeerId::random();
+        let peer_id_2 = PeerId::random();
+        senders[0]
+            .unbounded_send(Ok(PeerSet::from([(
+                peer_id_1,
+                create_peer("/ip4/1.1.1.1/tcp/6180", PeerRole::Upstream),
+            )])))
+            .unwrap();
+        senders[1]
+            .unbounded_send(Ok(PeerSet::from([(
+                peer_id_2,
+                create_peer("/ip4/2.2.2.2/tcp/6180", PeerRole::Upstream),
+            )])))
+            .unwrap();
+        let merged_peers = merged_stream.next().await.unwrap().unwrap();
+        assert_eq!(merged_peers.len(), 2);
+
+        // Elapse some time and fail the first source (its peers are still used)
+        mock_time.advance(max_staleness / 2);
+        senders[0]
+            .unbounded_send(Err(DiscoveryError::Parsing("failure".into())))
+            .unwrap();
+        let merged_peers = merged_stream.next().await.unwrap().unwrap();
+        assert_eq!(merged_peers.len(), 2);
+
+        // Refresh the second source after the first source becomes stale
+        mock_time.advance(max_staleness);
+        senders[1]
+            .unbounded_send(Ok(PeerSet::from([(
+                peer_id_2,
+                create_peer("/ip4/2.2.2.2/tcp/6180", PeerRole::Upstream),
+            )])))
+            .unwrap();
+
+        // Verify only the peers of the second source remain
+        let merged_peers = merged_stream.next().await.unwrap().unwrap();
+        assert_eq!(merged_peers.len(), 1);
+        assert!(merged_peers.contains_key(&peer_id_2));
+
+        // Drop all sources and verify the stream terminates
+        drop(senders);
+        assert!(merged_stream.next().await.is_none());
+    }
+}
@@ -3,9 +3,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    application::{error::Error, storage::PeersAndMetadata},
+    application::{error::Error, reputation::ReputationEvent, storage::PeersAndMetadata},
     protocols::{
         network::{Message, NetworkEvents, NetworkSender},
+        rpc::error::RpcError,
         wire::handshake::v1::{ProtocolId, ProtocolIdSet},
     },
 };
@@ -220,9 +221,16 @@ impl<Message: NetworkMessageTrait> NetworkClientInterface<Message> for NetworkCl
         let network_sender = self.get_sender_for_network_id(&peer.network_id())?;
         let rpc_protocol_id =
             self.get_preferred_protocol_for_peer(&peer, &self.rpc_protocols_and_preferences)?;
-        Ok(network_sender
+        let result = network_sender
             .send_rpc(peer.peer_id(), rpc_protocol_id, message, rpc_timeout)
-            .await?)
+            .await;
+
+        // Timeouts are tracked centrally, so that unresponsive peers lose reputation
+        if let Err(RpcError::TimedOut) = result {
+            self.peers_and_metadata
+                .report_peer_event(peer, ReputationEvent::RpcTimeout);
+        }
+        Ok(result?)
     }
 }
 
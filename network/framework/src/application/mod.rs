@@ -5,6 +5,7 @@
 pub mod error;
 pub mod interface;
 pub mod metadata;
+pub mod reputation;
 pub mod storage;
 
 #[cfg(test)]
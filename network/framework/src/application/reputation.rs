@@ -0,0 +1,516 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::counters;
+use aptos_config::{
+    config::PeerReputationConfig,
+    network_id::{NetworkId, PeerNetworkId},
+};
+use aptos_infallible::RwLock;
+use aptos_logger::{info, warn};
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use std::{
+    collections::HashMap,
+    fmt,
+    time::{Duration, Instant},
+};
+
+/// The bounds of a peer's reputation score
+pub const MAX_REPUTATION_SCORE: i64 = 100;
+pub const MIN_REPUTATION_SCORE: i64 = -100;
+
+/// Events that applications can report about the behaviour of a peer
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReputationEvent {
+    /// The peer sent a response that failed verification (e.g., an invalid proof)
+    InvalidResponse,
+    /// The peer sent data with an invalid signature (e.g., a broadcast transaction)
+    InvalidSignature,
+    /// The peer sent a message that could not be deserialized or was otherwise malformed
+    MalformedMessage,
+    /// The peer sent a valid response that was not useful (e.g., missing data)
+    UnhelpfulResponse,
+    /// An RPC to the peer timed out
+    RpcTimeout,
+    /// The peer successfully served a request
+    SuccessfulResponse,
+}
+
+impl ReputationEvent {
+    /// Returns the score adjustment for the event
+    pub fn score_delta(&self) -> i64 {
+        match self {
+            ReputationEvent::InvalidResponse => -20,
+            ReputationEvent::InvalidSignature => -5,
+            ReputationEvent::MalformedMessage => -10,
+            ReputationEvent::UnhelpfulResponse => -2,
+            ReputationEvent::RpcTimeout => -1,
+            ReputationEvent::SuccessfulResponse => 1,
+        }
+    }
+
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            ReputationEvent::InvalidResponse => "invalid_response",
+            ReputationEvent::InvalidSignature => "invalid_signature",
+            ReputationEvent::MalformedMessage => "malformed_message",
+            ReputationEvent::UnhelpfulResponse => "unhelpful_response",
+            ReputationEvent::RpcTimeout => "rpc_timeout",
+            ReputationEvent::SuccessfulResponse => "successful_response",
+        }
+    }
+}
+
+/// The reputation of a single peer. Reputations outlive peer connections,
+/// so that misbehaving peers can't reset their score by reconnecting.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PeerReputation {
+    score: i64,
+    num_bans: u32,
+    banned_until: Option<Instant>,
+}
+
+impl PeerReputation {
+    pub fn score(&self) -> i64 {
+        self.score
+    }
+
+    pub fn num_bans(&self) -> u32 {
+        self.num_bans
+    }
+
+    /// Returns true iff the peer is banned at the given time
+    pub fn is_banned(&self, now: Instant) -> bool {
+        self.banned_until
+            .map(|banned_until| now < banned_until)
+            .unwrap_or(false)
+    }
+
+    /// Updates the score using the given event and returns the ban duration if
+    /// the event caused the peer to be banned. Events reported while the peer is
+    /// banned are ignored (they are likely to be for requests sent before the ban).
+    pub fn record_event(
+        &mut self,
+        event: ReputationEvent,
+        config: &PeerReputationConfig,
+        now: Instant,
+    ) -> Option<Duration> {
+        if self.is_banned(now) {
+            return None;
+        }
+
+        self.score =
+            (self.score + event.score_delta()).clamp(MIN_REPUTATION_SCORE, MAX_REPUTATION_SCORE);
+        if !config.enable_peer_banning || self.score > config.ban_threshold {
+            return None;
+        }
+
+        // Ban the peer (each repeat ban doubles in length, up to the max)
+        let ban_duration_secs = config
+            .ban_duration_secs
+            .saturating_mul(1u64 << self.num_bans.min(32))
+            .min(config.max_ban_duration_secs);
+        let ban_duration = Duration::from_secs(ban_duration_secs);
+        self.num_bans += 1;
+        self.banned_until = Some(now + ban_duration);
+
+        // Once the ban expires, the peer starts again with a neutral score
+        self.score = 0;
+
+        Some(ban_duration)
+    }
+
+    /// Returns true iff the reputation no longer carries any information at the
+    /// given time, i.e., the score is back at the default and the last ban (if
+    /// any) expired long enough ago that a new ban wouldn't be escalated.
+    fn is_stale(&self, config: &PeerReputationConfig, now: Instant) -> bool {
+        let ban_history_expired = self
+            .banned_until
+            .map(|banned_until| {
+                now >= banned_until + Duration::from_secs(config.max_ban_duration_secs)
+            })
+            .unwrap_or(true);
+        self.score == 0 && ban_history_expired
+    }
+
+    /// Returns a summary of the reputation at the given time
+    fn summary(&self, peer_network_id: PeerNetworkId, now: Instant) -> PeerReputationSummary {
+        let remaining_ban = self
+            .banned_until
+            .filter(|banned_until| now < *banned_until)
+            .map(|banned_until| banned_until.duration_since(now));
+        PeerReputationSummary {
+            peer_network_id,
+            score: self.score,
+            num_bans: self.num_bans,
+            remaining_ban,
+        }
+    }
+}
+
+/// A snapshot of a peer's reputation (e.g., for debugging and the admin service)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PeerReputationSummary {
+    pub peer_network_id: PeerNetworkId,
+    pub score: i64,
+    pub num_bans: u32,
+    pub remaining_ban: Option<Duration>,
+}
+
+impl fmt::Display for PeerReputationSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "network_id: {}, peer_id: {}, score: {}, num_bans: {}, banned_for_secs: {}",
+            self.peer_network_id.network_id(),
+            self.peer_network_id.peer_id(),
+            self.score,
+            self.num_bans,
+            self.remaining_ban
+                .map(|remaining_ban| remaining_ban.as_secs())
+                .unwrap_or(0),
+        )
+    }
+}
+
+/// Tracks the reputations of all peers across networks. Applications report
+/// events about peers and the networking stack (and peer selection logic)
+/// avoids peers that are currently banned.
+#[derive(Debug)]
+pub struct PeerReputations {
+    configs: RwLock<HashMap<NetworkId, PeerReputationConfig>>,
+    reputations: RwLock<HashMap<PeerNetworkId, PeerReputation>>,
+    time_service: TimeService, // Used to determine the ban expiry times
+}
+
+impl PeerReputations {
+    pub fn new(time_service: TimeService) -> Self {
+        Self {
+            configs: RwLock::new(HashMap::new()),
+            reputations: RwLock::new(HashMap::new()),
+            time_service,
+        }
+    }
+
+    /// Sets the reputation config for the given network. Networks without
+    /// a config track scores, but never ban peers.
+    pub fn set_config(&self, network_id: NetworkId, config: PeerReputationConfig) {
+        self.configs.write().insert(network_id, config);
+    }
+
+    fn get_config(&self, network_id: &NetworkId) -> PeerReputationConfig {
+        self.configs
+            .read()
+            .get(network_id)
+            .copied()
+            .unwrap_or(PeerReputationConfig {
+                enable_peer_banning: false,
+                ..Default::default()
+            })
+    }
+
+    /// Evicts the reputation of the given (disconnected) peer if it is stale
+    pub fn evict_if_stale(&self, peer_network_id: &PeerNetworkId) {
+        let config = self.get_config(&peer_network_id.network_id());
+        let now = self.time_service.now();
+        let mut reputations = self.reputations.write();
+        if let Some(reputation) = reputations.get(peer_network_id) {
+            if reputation.is_stale(&config, now) {
+                reputations.remove(peer_network_id);
+            }
+        }
+    }
+
+    /// Evicts the stale reputations of all peers on the given network that are
+    /// not connected. This prevents the reputations from growing without bound
+    /// as peers come and go (e.g., on the public network).
+    pub fn evict_stale_reputations(
+        &self,
+        network_id: NetworkId,
+        is_connected: impl Fn(&PeerNetworkId) -> bool,
+    ) {
+        let config = self.get_config(&network_id);
+        let now = self.time_service.now();
+        self.reputations
+            .write()
+            .retain(|peer_network_id, reputation| {
+                peer_network_id.network_id() != network_id
+                    || is_connected(peer_network_id)
+                    || !reputation.is_stale(&config, now)
+            });
+    }
+
+    /// Records the given event for the peer, and bans the peer if required
+    pub fn report_event(&self, peer_network_id: PeerNetworkId, event: ReputationEvent) {
+        let network_id = peer_network_id.network_id();
+        counters::peer_reputation_events(&network_id, event.get_label()).inc();
+
+        let config = self.get_config(&network_id);
+        let ban_duration = self
+            .reputations
+            .write()
+            .entry(peer_network_id)
+            .or_default()
+            .record_event(event, &config, self.time_service.now());
+
+        if let Some(ban_duration) = ban_duration {
+            counters::peer_bans(&network_id).inc();
+            warn!(
+                "Banning peer {} for {:?} due to a low reputation score! Last event: {:?}",
+                peer_network_id, ban_duration, event
+            );
+        }
+    }
+
+    /// Returns true iff the peer is currently banned
+    pub fn is_banned(&self, peer_network_id: &PeerNetworkId) -> bool {
+        let now = self.time_service.now();
+        self.reputations
+            .read()
+            .get(peer_network_id)
+            .map(|reputation| reputation.is_banned(now))
+            .unwrap_or(false)
+    }
+
+    /// Returns the reputation of the given peer (if one exists)
+    pub fn get_reputation(&self, peer_network_id: &PeerNetworkId) -> Option<PeerReputation> {
+        self.reputations.read().get(peer_network_id).cloned()
+    }
+
+    /// Returns summaries for all known peer reputations, ordered by peer
+    pub fn get_summaries(&self) -> Vec<PeerReputationSummary> {
+        let now = self.time_service.now();
+        let mut summaries: Vec<_> = self
+            .reputations
+            .read()
+            .iter()
+            .map(|(peer_network_id, reputation)| reputation.summary(*peer_network_id, now))
+            .collect();
+        summaries.sort_by_key(|summary| summary.peer_network_id);
+        summaries
+    }
+
+    /// Resets the reputation of the given peer (e.g., by an operator)
+    pub fn reset_reputation(&self, peer_network_id: &PeerNetworkId) {
+        if self.reputations.write().remove(peer_network_id).is_some() {
+            info!("Reset the reputation of peer: {}", peer_network_id);
+        }
+    }
+}
+
+impl Default for PeerReputations {
+    fn default() -> Self {
+        Self::new(TimeService::real())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::PeerId;
+
+    fn banning_config() -> PeerReputationConfig {
+        PeerReputationConfig {
+            enable_peer_banning: true,
+            ban_threshold: -50,
+            ban_duration_secs: 10,
+            max_ban_duration_secs: 25,
+        }
+    }
+
+    #[test]
+    fn test_score_is_clamped() {
+        let config = banning_config();
+        let now = Instant::now();
+        let mut reputation = PeerReputation::default();
+
+        for _ in 0..(2 * MAX_REPUTATION_SCORE) {
+            reputation.record_event(ReputationEvent::SuccessfulResponse, &config, now);
+        }
+        assert_eq!(reputation.score(), MAX_REPUTATION_SCORE);
+
+        let config = PeerReputationConfig {
+            enable_peer_banning: false,
+            ..config
+        };
+        for _ in 0..20 {
+            reputation.record_event(ReputationEvent::InvalidResponse, &config, now);
+        }
+        assert_eq!(reputation.score(), MIN_REPUTATION_SCORE);
+        assert!(!reputation.is_banned(now));
+    }
+
+    #[test]
+    fn test_ban_and_expiry() {
+        let config = banning_config();
+        let now = Instant::now();
+        let mut reputation = PeerReputation::default();
+
+        // Two invalid responses are not enough to ban the peer
+        for _ in 0..2 {
+            let ban = reputation.record_event(ReputationEvent::InvalidResponse, &config, now);
+            assert!(ban.is_none());
+        }
+        assert_eq!(reputation.score(), -40);
+
+        // The third invalid response bans the peer and resets the score
+        let ban = reputation.record_event(ReputationEvent::InvalidResponse, &config, now);
+        assert_eq!(ban, Some(Duration::from_secs(10)));
+        assert!(reputation.is_banned(now));
+        assert_eq!(reputation.score(), 0);
+        assert_eq!(reputation.num_bans(), 1);
+
+        // Events are ignored while the peer is banned
+        reputation.record_event(ReputationEvent::InvalidResponse, &config, now);
+        assert_eq!(reputation.score(), 0);
+
+        // The ban expires
+        let later = now + Duration::from_secs(10);
+        assert!(!reputation.is_banned(later));
+    }
+
+    #[test]
+    fn test_repeat_bans_are_longer() {
+        let config = banning_config();
+        let mut now = Instant::now();
+        let mut reputation = PeerReputation::default();
+
+        let mut ban_durations = vec![];
+        for _ in 0..3 {
+            loop {
+                if let Some(ban) =
+                    reputation.record_event(ReputationEvent::InvalidResponse, &config, now)
+                {
+                    ban_durations.push(ban.as_secs());
+                    now += ban;
+                    break;
+                }
+            }
+        }
+        assert_eq!(ban_durations, vec![10, 20, 25]);
+        assert_eq!(reputation.num_bans(), 3);
+    }
+
+    #[test]
+    fn test_stale_reputations() {
+        let config = banning_config();
+        let now = Instant::now();
+        let mut reputation = PeerReputation::default();
+        assert!(reputation.is_stale(&config, now));
+
+        // A peer with a non-default score isn't stale
+        reputation.record_event(ReputationEvent::InvalidSignature, &config, now);
+        assert!(!reputation.is_stale(&config, now));
+
+        // Ban the peer (which resets the score)
+        while reputation
+            .record_event(ReputationEvent::InvalidResponse, &config, now)
+            .is_none()
+        {}
+        assert_eq!(reputation.score(), 0);
+        assert!(!reputation.is_stale(&config, now));
+
+        // The ban history is kept for the max ban duration after the ban expires
+        let ban_expiry = now + Duration::from_secs(config.ban_duration_secs);
+        assert!(!reputation.is_stale(&config, ban_expiry));
+        let history_expiry = ban_expiry + Duration::from_secs(config.max_ban_duration_secs);
+        assert!(reputation.is_stale(&config, history_expiry));
+    }
+
+    #[test]
+    fn test_evict_stale_reputations() {
+        let reputations = PeerReputations::default();
+        reputations.set_config(NetworkId::Public, banning_config());
+
+        // Create a peer with a default score, a peer with a low score and a
+        // connected peer with a default score.
+        let default_peer = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+        let low_score_peer = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+        let connected_peer = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+        for peer_network_id in [default_peer, connected_peer] {
+            reputations.report_event(peer_network_id, ReputationEvent::SuccessfulResponse);
+            reputations.report_event(peer_network_id, ReputationEvent::RpcTimeout);
+        }
+        reputations.report_event(low_score_peer, ReputationEvent::RpcTimeout);
+
+        // A peer on another network with a default score
+        let validator_peer = PeerNetworkId::new(NetworkId::Validator, PeerId::random());
+        reputations.report_event(validator_peer, ReputationEvent::SuccessfulResponse);
+        reputations.report_event(validator_peer, ReputationEvent::RpcTimeout);
+
+        // Only the disconnected peer with a default score on the network is evicted
+        reputations.evict_stale_reputations(NetworkId::Public, |peer_network_id| {
+            *peer_network_id == connected_peer
+        });
+        assert!(reputations.get_reputation(&default_peer).is_none());
+        assert!(reputations.get_reputation(&low_score_peer).is_some());
+        assert!(reputations.get_reputation(&connected_peer).is_some());
+        assert!(reputations.get_reputation(&validator_peer).is_some());
+
+        // Evict the connected peer once it disconnects
+        reputations.evict_if_stale(&connected_peer);
+        assert!(reputations.get_reputation(&connected_peer).is_none());
+
+        // The low score peer isn't evicted
+        reputations.evict_if_stale(&low_score_peer);
+        assert!(reputations.get_reputation(&low_score_peer).is_some());
+    }
+
+    #[test]
+    fn test_bans_expire_with_time() {
+        let time_service = TimeService::mock();
+        let reputations = PeerReputations::new(time_service.clone());
+        reputations.set_config(NetworkId::Public, banning_config());
+
+        // Ban the peer
+        let peer_network_id = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+        for _ in 0..3 {
+            reputations.report_event(peer_network_id, ReputationEvent::InvalidResponse);
+        }
+        assert!(reputations.is_banned(&peer_network_id));
+        assert_eq!(
+            reputations.get_summaries()[0].remaining_ban,
+            Some(Duration::from_secs(10))
+        );
+
+        // Verify the peer is still banned just before the ban expires
+        let mock_time_service = time_service.into_mock();
+        mock_time_service.advance(Duration::from_secs(9));
+        assert!(reputations.is_banned(&peer_network_id));
+
+        // Verify the ban expires, but the reputation isn't evicted until the ban history does
+        mock_time_service.advance(Duration::from_secs(1));
+        assert!(!reputations.is_banned(&peer_network_id));
+        reputations.evict_if_stale(&peer_network_id);
+        assert!(reputations.get_reputation(&peer_network_id).is_some());
+        mock_time_service.advance(Duration::from_secs(banning_config().max_ban_duration_secs));
+        reputations.evict_if_stale(&peer_network_id);
+        assert!(reputations.get_reputation(&peer_network_id).is_none());
+    }
+
+    #[test]
+    fn test_networks_without_config_never_ban() {
+        let reputations = PeerReputations::default();
+        let peer_network_id = PeerNetworkId::random();
+        for _ in 0..10 {
+            reputations.report_event(peer_network_id, ReputationEvent::InvalidResponse);
+        }
+        assert!(!reputations.is_banned(&peer_network_id));
+
+        reputations.set_config(NetworkId::Public, banning_config());
+        for _ in 0..3 {
+            reputations.report_event(peer_network_id, ReputationEvent::InvalidResponse);
+        }
+        assert!(reputations.is_banned(&peer_network_id));
+
+        let summaries = reputations.get_summaries();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].peer_network_id, peer_network_id);
+        assert_eq!(summaries[0].num_bans, 1);
+        assert!(summaries[0].remaining_ban.is_some());
+
+        reputations.reset_reputation(&peer_network_id);
+        assert!(!reputations.is_banned(&peer_network_id));
+        assert!(reputations.get_summaries().is_empty());
+    }
+}
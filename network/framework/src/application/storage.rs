@@ -6,13 +6,14 @@ use crate::{
     application::{
         error::Error,
         metadata::{ConnectionState, PeerMetadata},
+        reputation::{PeerReputationSummary, PeerReputations, ReputationEvent},
     },
     peer_manager::ConnectionNotification,
     transport::{ConnectionId, ConnectionMetadata},
     ProtocolId,
 };
 use aptos_config::{
-    config::{Peer, PeerSet},
+    config::{Peer, PeerReputationConfig, PeerSet},
     network_id::{NetworkId, PeerNetworkId},
 };
 use aptos_infallible::{Mutex, RwLock};
@@ -51,6 +52,10 @@ pub struct PeersAndMetadata {
     cached_peers_and_metadata: Arc<ArcSwap<HashMap<NetworkId, HashMap<PeerId, PeerMetadata>>>>,
 
     subscribers: Mutex<Vec<tokio::sync::mpsc::Sender<ConnectionNotification>>>,
+
+    // Peer reputations are tracked separately from the peer metadata, as
+    // they must persist across disconnects (e.g., to enforce peer bans).
+    peer_reputations: PeerReputations,
 }
 
 impl PeersAndMetadata {
@@ -61,6 +66,7 @@ impl PeersAndMetadata {
             trusted_peers: HashMap::new(),
             cached_peers_and_metadata: Arc::new(ArcSwap::from(Arc::new(HashMap::new()))),
             subscribers: Mutex::new(vec![]),
+            peer_reputations: PeerReputations::default(),
         };
 
         // Initialize each network mapping and trusted peer set
@@ -258,6 +264,9 @@ impl PeersAndMetadata {
         // Update the cached peers and metadata
         self.set_cached_peers_and_metadata(peers_and_metadata.clone());
 
+        // Forget the reputation of the peer if it no longer carries any information
+        self.peer_reputations.evict_if_stale(&peer_network_id);
+
         Ok(peer_metadata)
     }
 
@@ -368,6 +377,44 @@ impl PeersAndMetadata {
         Ok(())
     }
 
+    /// Sets the peer reputation (and banning) config for the given network
+    pub fn set_peer_reputation_config(&self, network_id: NetworkId, config: PeerReputationConfig) {
+        self.peer_reputations.set_config(network_id, config);
+    }
+
+    /// Reports a reputation event for the given peer (e.g., an invalid
+    /// response). Peers with a low reputation are temporarily banned.
+    pub fn report_peer_event(&self, peer_network_id: PeerNetworkId, event: ReputationEvent) {
+        self.peer_reputations.report_event(peer_network_id, event);
+    }
+
+    /// Returns true iff the given peer is currently banned
+    pub fn is_peer_banned(&self, peer_network_id: &PeerNetworkId) -> bool {
+        self.peer_reputations.is_banned(peer_network_id)
+    }
+
+    /// Returns the reputation summaries of all peers with reported events
+    pub fn get_peer_reputations(&self) -> Vec<PeerReputationSummary> {
+        self.peer_reputations.get_summaries()
+    }
+
+    /// Evicts the stale reputations of all disconnected peers on the given network
+    pub fn evict_stale_peer_reputations(&self, network_id: NetworkId) {
+        let cached_peers_and_metadata = self.cached_peers_and_metadata.load();
+        let peers_for_network = cached_peers_and_metadata.get(&network_id);
+        self.peer_reputations
+            .evict_stale_reputations(network_id, |peer_network_id| {
+                peers_for_network
+                    .map(|peers| peers.contains_key(&peer_network_id.peer_id()))
+                    .unwrap_or(false)
+            });
+    }
+
+    /// Resets the reputation (and any ban) of the given peer
+    pub fn reset_peer_reputation(&self, peer_network_id: &PeerNetworkId) {
+        self.peer_reputations.reset_reputation(peer_network_id);
+    }
+
     fn broadcast(&self, event: ConnectionNotification) {
         let mut listeners = self.subscribers.lock();
         let mut to_del = vec![];
@@ -7,6 +7,7 @@ use crate::{
         error::Error,
         interface::{NetworkClient, NetworkClientInterface, NetworkServiceEvents},
         metadata::{ConnectionState, PeerMetadata},
+        reputation::ReputationEvent,
         storage::PeersAndMetadata,
     },
     peer_manager::{
@@ -22,7 +23,7 @@ use crate::{
 };
 use aptos_channels::{aptos_channel, message_queues::QueueStyle};
 use aptos_config::{
-    config::{Peer, PeerRole, PeerSet},
+    config::{Peer, PeerReputationConfig, PeerRole, PeerSet},
     network_id::{NetworkId, PeerNetworkId},
 };
 use aptos_peer_monitoring_service_types::PeerMonitoringMetadata;
@@ -279,6 +280,114 @@ fn test_peers_and_metadata_trusted_peers() {
     assert!(trusted_peers.is_empty());
 }
 
+#[test]
+fn test_peers_and_metadata_peer_reputations() {
+    // Create the peers and metadata container and enable banning on the public network
+    let network_ids = vec![NetworkId::Validator, NetworkId::Public];
+    let peers_and_metadata = PeersAndMetadata::new(&network_ids);
+    peers_and_metadata
+        .set_peer_reputation_config(NetworkId::Public, PeerReputationConfig::default());
+
+    // Create a peer on each network
+    let (validator_peer, validator_connection) = create_peer_and_connection(
+        NetworkId::Validator,
+        vec![ProtocolId::StorageServiceRpc],
+        peers_and_metadata.clone(),
+    );
+    let (public_peer, public_connection) = create_peer_and_connection(
+        NetworkId::Public,
+        vec![ProtocolId::StorageServiceRpc],
+        peers_and_metadata.clone(),
+    );
+
+    // Report several invalid responses for both peers
+    for _ in 0..5 {
+        for peer_network_id in [validator_peer, public_peer] {
+            peers_and_metadata.report_peer_event(peer_network_id, ReputationEvent::InvalidResponse);
+        }
+    }
+
+    // Verify that only the public peer is banned (the validator network has no banning config)
+    assert!(!peers_and_metadata.is_peer_banned(&validator_peer));
+    assert!(peers_and_metadata.is_peer_banned(&public_peer));
+
+    // Remove both peers and verify the reputations are preserved
+    remove_peer_metadata(
+        &peers_and_metadata,
+        validator_peer,
+        validator_connection.connection_id.get_inner(),
+    )
+    .unwrap();
+    remove_peer_metadata(
+        &peers_and_metadata,
+        public_peer,
+        public_connection.connection_id.get_inner(),
+    )
+    .unwrap();
+    assert!(peers_and_metadata.is_peer_banned(&public_peer));
+
+    // Verify the reputation summaries
+    let peer_reputations = peers_and_metadata.get_peer_reputations();
+    assert_eq!(peer_reputations.len(), 2);
+    for summary in peer_reputations {
+        if summary.peer_network_id == public_peer {
+            assert_eq!(summary.num_bans, 1);
+            assert!(summary.remaining_ban.is_some());
+        } else {
+            assert_eq!(summary.peer_network_id, validator_peer);
+            assert_eq!(summary.score, -100);
+            assert_eq!(summary.num_bans, 0);
+        }
+    }
+
+    // Reset the public peer's reputation and verify it is no longer banned
+    peers_and_metadata.reset_peer_reputation(&public_peer);
+    assert!(!peers_and_metadata.is_peer_banned(&public_peer));
+}
+
+#[test]
+fn test_peers_and_metadata_stale_peer_reputations() {
+    // Create the peers and metadata container
+    let network_ids = vec![NetworkId::Public];
+    let peers_and_metadata = PeersAndMetadata::new(&network_ids);
+
+    // Create two peers and report events that leave one at the default score
+    let (default_peer, default_connection) = create_peer_and_connection(
+        NetworkId::Public,
+        vec![ProtocolId::StorageServiceRpc],
+        peers_and_metadata.clone(),
+    );
+    let (low_score_peer, low_score_connection) = create_peer_and_connection(
+        NetworkId::Public,
+        vec![ProtocolId::StorageServiceRpc],
+        peers_and_metadata.clone(),
+    );
+    peers_and_metadata.report_peer_event(default_peer, ReputationEvent::RpcTimeout);
+    peers_and_metadata.report_peer_event(default_peer, ReputationEvent::SuccessfulResponse);
+    peers_and_metadata.report_peer_event(low_score_peer, ReputationEvent::InvalidSignature);
+
+    // Verify that stale reputations of connected peers are not evicted
+    peers_and_metadata.evict_stale_peer_reputations(NetworkId::Public);
+    assert_eq!(peers_and_metadata.get_peer_reputations().len(), 2);
+
+    // Remove both peers and verify that only the low score reputation is preserved
+    remove_peer_metadata(
+        &peers_and_metadata,
+        default_peer,
+        default_connection.connection_id.get_inner(),
+    )
+    .unwrap();
+    remove_peer_metadata(
+        &peers_and_metadata,
+        low_score_peer,
+        low_score_connection.connection_id.get_inner(),
+    )
+    .unwrap();
+    let peer_reputations = peers_and_metadata.get_peer_reputations();
+    assert_eq!(peer_reputations.len(), 1);
+    assert_eq!(peer_reputations[0].peer_network_id, low_score_peer);
+}
+
 #[test]
 fn test_peers_and_metadata_caching() {
     // Create the peers and metadata container
@@ -36,7 +36,7 @@ use crate::{
 };
 use aptos_config::{
     config::{Peer, PeerRole, PeerSet},
-    network_id::NetworkContext,
+    network_id::{NetworkContext, PeerNetworkId},
 };
 use aptos_crypto::x25519;
 use aptos_infallible::RwLock;
@@ -140,6 +140,8 @@ pub enum DiscoverySource {
     OnChainValidatorSet,
     File,
     Rest,
+    Dns,
+    Merged,
     Config,
 }
 
@@ -156,6 +158,8 @@ impl fmt::Display for DiscoverySource {
             DiscoverySource::File => "File",
             DiscoverySource::Config => "Config",
             DiscoverySource::Rest => "Rest",
+            DiscoverySource::Dns => "Dns",
+            DiscoverySource::Merged => "Merged",
         })
     }
 }
@@ -556,6 +560,62 @@ where
         }
     }
 
+    /// Disconnects from (and cancels pending dials to) any peers that
+    /// are currently banned due to a low reputation score.
+    async fn close_banned_connections(&mut self) {
+        // Cancel any pending dials to banned peers
+        let banned_peer_dials: Vec<PeerId> = self
+            .dial_queue
+            .keys()
+            .filter(|peer_id| self.is_peer_banned(peer_id))
+            .cloned()
+            .collect();
+        for banned_peer_dial in banned_peer_dials {
+            debug!(
+                NetworkSchema::new(&self.network_context).remote_peer(&banned_peer_dial),
+                "{} Cancelling dial to banned peer {}",
+                self.network_context,
+                banned_peer_dial.short_str()
+            );
+            self.dial_queue.remove(&banned_peer_dial);
+        }
+
+        // Close existing connections to banned peers
+        let banned_peers: Vec<PeerId> = self
+            .connected
+            .keys()
+            .filter(|peer_id| self.is_peer_banned(peer_id))
+            .cloned()
+            .collect();
+        for banned_peer in banned_peers {
+            info!(
+                NetworkSchema::new(&self.network_context).remote_peer(&banned_peer),
+                "{} Closing connection to banned peer {}",
+                self.network_context,
+                banned_peer.short_str()
+            );
+
+            if let Err(disconnect_error) =
+                self.connection_reqs_tx.disconnect_peer(banned_peer).await
+            {
+                info!(
+                    NetworkSchema::new(&self.network_context).remote_peer(&banned_peer),
+                    error = %disconnect_error,
+                    "{} Failed to close connection to banned peer {}, error: {}",
+                    self.network_context,
+                    banned_peer.short_str(),
+                    disconnect_error
+                );
+            }
+        }
+    }
+
+    /// Returns true iff the given peer is currently banned on this network
+    fn is_peer_banned(&self, peer_id: &PeerId) -> bool {
+        let peer_network_id = PeerNetworkId::new(self.network_context.network_id(), *peer_id);
+        self.peers_and_metadata.is_peer_banned(&peer_network_id)
+    }
+
     /// Identifies a set of peers to dial and queues them for dialing
     async fn dial_eligible_peers<'a>(
         &'a mut self,
@@ -580,6 +640,7 @@ where
                     && !self.connected.contains_key(peer_id) // The node is not already connected
                     && !self.dial_queue.contains_key(peer_id) // There is no pending dial to this node
                     && roles_to_dial.contains(&peer.role) // We can dial this role
+                    && !self.is_peer_banned(peer_id) // The node is not banned
             })
             .collect();
 
@@ -825,6 +886,11 @@ where
         self.cancel_stale_dials().await;
         // Disconnect from connected peers that are no longer eligible.
         self.close_stale_connections().await;
+        // Disconnect from peers that have been banned due to a low reputation.
+        self.close_banned_connections().await;
+        // Forget the reputations of disconnected peers that no longer carry any information.
+        self.peers_and_metadata
+            .evict_stale_peer_reputations(self.network_context.network_id());
         // Dial peers which are eligible but are neither connected nor queued for dialing in the
         // future.
         self.dial_eligible_peers(pending_dials).await;
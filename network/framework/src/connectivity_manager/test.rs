@@ -4,12 +4,13 @@
 
 use super::*;
 use crate::{
+    application::reputation::ReputationEvent,
     peer_manager::{conn_notifs_channel, ConnectionNotification, ConnectionRequest},
     transport::ConnectionMetadata,
 };
 use aptos_channels::{aptos_channel, message_queues::QueueStyle};
 use aptos_config::{
-    config::{Peer, PeerRole, PeerSet, HANDSHAKE_VERSION},
+    config::{Peer, PeerReputationConfig, PeerRole, PeerSet, HANDSHAKE_VERSION},
     network_id::NetworkId,
 };
 use aptos_crypto::{test_utils::TEST_SEED, x25519, Uniform};
@@ -908,6 +909,50 @@ async fn test_stale_peers_vfn_inbound() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_banned_peers_disconnected() {
+    // Create a connectivity manager and enable peer banning
+    let (mut mock, mut connectivity_manager) = TestHarness::new(HashMap::new());
+    let network_context = mock.network_context;
+    mock.peers_and_metadata.set_peer_reputation_config(
+        network_context.network_id(),
+        PeerReputationConfig::default(),
+    );
+
+    // Create and connect peers 1 and 2
+    let mut connection_metadatas = vec![];
+    for _ in 0..2 {
+        let connection_metadata = ConnectionMetadata::mock_with_role_and_origin(
+            PeerId::random(),
+            PeerRole::Validator,
+            ConnectionOrigin::Outbound,
+        );
+        let connection_notification = ConnectionNotification::NewPeer(
+            connection_metadata.clone(),
+            network_context.network_id(),
+        );
+        connectivity_manager.handle_control_notification(connection_notification);
+        connection_metadatas.push(connection_metadata);
+    }
+    assert_eq!(connectivity_manager.get_connected_peers().len(), 2);
+
+    // Report enough invalid responses from peer 1 to ban it
+    let peer_id_1 = connection_metadatas[0].remote_peer_id;
+    let peer_network_id_1 = PeerNetworkId::new(network_context.network_id(), peer_id_1);
+    while !mock.peers_and_metadata.is_peer_banned(&peer_network_id_1) {
+        mock.peers_and_metadata
+            .report_peer_event(peer_network_id_1, ReputationEvent::InvalidResponse);
+    }
+    assert!(connectivity_manager.is_peer_banned(&peer_id_1));
+    assert!(!connectivity_manager.is_peer_banned(&connection_metadatas[1].remote_peer_id));
+
+    // Close the banned connections and verify that only peer 1 is disconnected
+    tokio::join!(
+        connectivity_manager.close_banned_connections(),
+        mock.expect_disconnect_fail(peer_id_1, connection_metadatas[0].addr.clone())
+    );
+}
+
 /// Verifies that the trusted peers match the expected set
 fn verify_trusted_peers(
     peers_and_metadata: &Arc<PeersAndMetadata>,
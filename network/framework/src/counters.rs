@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::protocols::wire::handshake::v1::ProtocolId;
-use aptos_config::network_id::NetworkContext;
+use aptos_config::network_id::{NetworkContext, NetworkId};
 use aptos_metrics_core::{
     exponential_buckets, register_histogram_vec, register_int_counter_vec, register_int_gauge,
     register_int_gauge_vec, Histogram, HistogramTimer, HistogramVec, IntCounter, IntCounterVec,
@@ -332,6 +332,95 @@ pub fn direct_send_bytes(
     ])
 }
 
+pub static APTOS_NETWORK_OUTBOUND_PRIORITY_MESSAGES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_outbound_priority_messages",
+        "Number of outbound messages written to peers, by priority class",
+        &["role_type", "network_id", "peer_id", "priority"]
+    )
+    .unwrap()
+});
+
+pub fn outbound_priority_messages(
+    network_context: &NetworkContext,
+    priority_label: &'static str,
+) -> IntCounter {
+    APTOS_NETWORK_OUTBOUND_PRIORITY_MESSAGES.with_label_values(&[
+        network_context.role().as_str(),
+        network_context.network_id().as_str(),
+        network_context.peer_id().short_str().as_str(),
+        priority_label,
+    ])
+}
+
+pub static APTOS_NETWORK_OUTBOUND_PRIORITY_BYTES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_outbound_priority_bytes",
+        "Number of outbound bytes written to peers, by priority class",
+        &["role_type", "network_id", "peer_id", "priority"]
+    )
+    .unwrap()
+});
+
+pub fn outbound_priority_bytes(
+    network_context: &NetworkContext,
+    priority_label: &'static str,
+) -> IntCounter {
+    APTOS_NETWORK_OUTBOUND_PRIORITY_BYTES.with_label_values(&[
+        network_context.role().as_str(),
+        network_context.network_id().as_str(),
+        network_context.peer_id().short_str().as_str(),
+        priority_label,
+    ])
+}
+
+pub static APTOS_NETWORK_OUTBOUND_THROTTLED_MESSAGES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_outbound_throttled_messages",
+        "Number of times an outbound message was throttled by the peer bandwidth limit",
+        &["role_type", "network_id", "peer_id", "priority"]
+    )
+    .unwrap()
+});
+
+pub fn outbound_throttled_messages(
+    network_context: &NetworkContext,
+    priority_label: &'static str,
+) -> IntCounter {
+    APTOS_NETWORK_OUTBOUND_THROTTLED_MESSAGES.with_label_values(&[
+        network_context.role().as_str(),
+        network_context.network_id().as_str(),
+        network_context.peer_id().short_str().as_str(),
+        priority_label,
+    ])
+}
+
+pub static APTOS_NETWORK_PEER_REPUTATION_EVENTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_peer_reputation_events",
+        "Number of peer reputation events reported by applications",
+        &["network_id", "event"]
+    )
+    .unwrap()
+});
+
+pub fn peer_reputation_events(network_id: &NetworkId, event_label: &'static str) -> IntCounter {
+    APTOS_NETWORK_PEER_REPUTATION_EVENTS.with_label_values(&[network_id.as_str(), event_label])
+}
+
+pub static APTOS_NETWORK_PEER_BANS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_peer_bans",
+        "Number of times a peer was banned due to a low reputation score",
+        &["network_id"]
+    )
+    .unwrap()
+});
+
+pub fn peer_bans(network_id: &NetworkId) -> IntCounter {
+    APTOS_NETWORK_PEER_BANS.with_label_values(&[network_id.as_str()])
+}
+
 /// Counters(queued,dequeued,dropped) related to inbound network notifications for RPCs and
 /// DirectSends.
 pub static PENDING_NETWORK_NOTIFICATIONS: Lazy<IntCounterVec> = Lazy::new(|| {
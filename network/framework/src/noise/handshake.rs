@@ -182,11 +182,40 @@ impl NoiseUpgrader {
     /// Noise handshake payload. Currently this counter is always a millisecond-
     /// granularity unix epoch timestamp.
     pub async fn upgrade_outbound<TSocket, F>(
+        &self,
+        socket: TSocket,
+        remote_peer_id: PeerId,
+        remote_public_key: x25519::PublicKey,
+        time_provider: F,
+    ) -> Result<(NoiseStream<TSocket>, PeerRole), NoiseHandshakeError>
+    where
+        TSocket: AsyncRead + AsyncWrite + Debug + Unpin,
+        F: Fn() -> [u8; AntiReplayTimestamps::TIMESTAMP_SIZE],
+    {
+        self.upgrade_outbound_with_channel_binding(
+            socket,
+            remote_peer_id,
+            remote_public_key,
+            time_provider,
+            &[],
+        )
+        .await
+    }
+
+    /// Perform an outbound protocol upgrade on this connection, and bind the
+    /// handshake to the given channel binding (e.g., keying material exported
+    /// from the TLS session of a QUIC connection).
+    ///
+    /// The channel binding is mixed into the Noise prologue (but never sent), so the
+    /// handshake only succeeds if both peers provide the same channel binding. This
+    /// ensures that the underlying connection is not relayed by a third party.
+    pub async fn upgrade_outbound_with_channel_binding<TSocket, F>(
         &self,
         mut socket: TSocket,
         remote_peer_id: PeerId,
         remote_public_key: x25519::PublicKey,
         time_provider: F,
+        channel_binding: &[u8],
     ) -> Result<(NoiseStream<TSocket>, PeerRole), NoiseHandshakeError>
     where
         TSocket: AsyncRead + AsyncWrite + Debug + Unpin,
@@ -202,6 +231,9 @@ impl NoiseUpgrader {
 
         let (prologue_msg, client_noise_msg) = client_message.split_at_mut(Self::PROLOGUE_SIZE);
 
+        // the noise prologue also contains the channel binding (which is not sent)
+        let prologue = [&prologue_msg[..], channel_binding].concat();
+
         // craft 8-byte payload as current timestamp (in milliseconds)
         let payload = time_provider();
 
@@ -211,7 +243,7 @@ impl NoiseUpgrader {
             .noise_config
             .initiate_connection(
                 &mut rng,
-                prologue_msg,
+                &prologue,
                 remote_public_key,
                 Some(&payload),
                 client_noise_msg,
@@ -312,8 +344,21 @@ impl NoiseUpgrader {
     /// In addition, we will expect the client to include an anti replay attack
     /// counter in the Noise handshake payload in mutual auth scenarios.
     pub async fn upgrade_inbound<TSocket>(
+        &self,
+        socket: TSocket,
+    ) -> Result<(NoiseStream<TSocket>, PeerId, PeerRole), NoiseHandshakeError>
+    where
+        TSocket: AsyncRead + AsyncWrite + Debug + Unpin,
+    {
+        self.upgrade_inbound_with_channel_binding(socket, &[]).await
+    }
+
+    /// Perform an inbound protocol upgrade on this connection, and bind the
+    /// handshake to the given channel binding (see `upgrade_outbound_with_channel_binding`).
+    pub async fn upgrade_inbound_with_channel_binding<TSocket>(
         &self,
         mut socket: TSocket,
+        channel_binding: &[u8],
     ) -> Result<(NoiseStream<TSocket>, PeerId, PeerRole), NoiseHandshakeError>
     where
         TSocket: AsyncRead + AsyncWrite + Debug + Unpin,
@@ -357,11 +402,12 @@ impl NoiseUpgrader {
             ));
         }
 
-        // parse it
-        let (prologue, client_init_message) = client_message.split_at(Self::PROLOGUE_SIZE);
+        // parse it (the noise prologue also contains the channel binding)
+        let (prologue_msg, client_init_message) = client_message.split_at(Self::PROLOGUE_SIZE);
+        let prologue = [prologue_msg, channel_binding].concat();
         let (remote_public_key, handshake_state, payload) = self
             .noise_config
-            .parse_client_init_message(prologue, client_init_message)
+            .parse_client_init_message(&prologue, client_init_message)
             .map_err(|err| NoiseHandshakeError::ServerParseClient(remote_peer_short, err))?;
 
         // if mutual auth mode, verify the remote pubkey is in our set of trusted peers
@@ -705,6 +751,10 @@ mod test {
 
         assert_eq!(client_stream.get_remote_static(), server_public_key);
         assert_eq!(server_stream.get_remote_static(), client_public_key);
+        assert_eq!(
+            client_stream.handshake_hash(),
+            server_stream.handshake_hash()
+        );
     }
 
     #[test]
@@ -717,6 +767,73 @@ mod test {
         test_handshake_success(true /* is_mutual_auth */);
     }
 
+    /// helper to perform a noise handshake with the given channel bindings
+    fn perform_handshake_with_channel_binding(
+        client: &NoiseUpgrader,
+        server: &NoiseUpgrader,
+        server_public_key: x25519::PublicKey,
+        client_channel_binding: &[u8],
+        server_channel_binding: &[u8],
+    ) -> (
+        Result<(NoiseStream<MemorySocket>, PeerRole), NoiseHandshakeError>,
+        Result<(NoiseStream<MemorySocket>, PeerId, PeerRole), NoiseHandshakeError>,
+    ) {
+        let (dialer_socket, listener_socket) = MemorySocket::new_pair();
+        block_on(join(
+            client.upgrade_outbound_with_channel_binding(
+                dialer_socket,
+                server.network_context.peer_id(),
+                server_public_key,
+                AntiReplayTimestamps::now,
+                client_channel_binding,
+            ),
+            server.upgrade_inbound_with_channel_binding(listener_socket, server_channel_binding),
+        ))
+    }
+
+    #[test]
+    fn test_handshake_channel_binding() {
+        let ((client, _), (server, server_public_key)) = build_peers(true, None);
+
+        // The handshake succeeds if both peers use the same channel binding
+        let (client_res, server_res) = perform_handshake_with_channel_binding(
+            &client,
+            &server,
+            server_public_key,
+            b"binding",
+            b"binding",
+        );
+        let (client_stream, _) = client_res.unwrap();
+        let (server_stream, _, _) = server_res.unwrap();
+        assert_eq!(
+            client_stream.handshake_hash(),
+            server_stream.handshake_hash()
+        );
+
+        // The handshake hash depends on the channel binding
+        let (client_res, _) = perform_handshake(&client, &server, server_public_key);
+        assert_ne!(
+            client_res.unwrap().0.handshake_hash(),
+            client_stream.handshake_hash()
+        );
+
+        // The handshake fails if the channel bindings are different
+        for (client_channel_binding, server_channel_binding) in [
+            (&b"binding"[..], &b"relayed"[..]),
+            (&b"binding"[..], &b""[..]),
+        ] {
+            let (client_res, server_res) = perform_handshake_with_channel_binding(
+                &client,
+                &server,
+                server_public_key,
+                client_channel_binding,
+                server_channel_binding,
+            );
+            client_res.unwrap_err();
+            server_res.unwrap_err();
+        }
+    }
+
     fn test_handshake_self_fails(is_mutual_auth: bool) {
         let (_, (server, server_public_key)) = build_peers(is_mutual_auth, None);
         let (client_res, server_res) = perform_handshake(&server, &server, server_public_key);
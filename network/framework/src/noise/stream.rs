@@ -61,6 +61,11 @@ impl<TSocket> NoiseStream<TSocket> {
     pub fn get_remote_static(&self) -> x25519::PublicKey {
         self.session.get_remote_static()
     }
+
+    /// Returns the hash of the Noise handshake (identical for both peers of the stream)
+    pub fn handshake_hash(&self) -> &[u8] {
+        self.session.handshake_hash()
+    }
 }
 
 //
@@ -13,6 +13,11 @@
 //! [`Peer`] owns the actual underlying connection socket and is reponsible for
 //! the socket's shutdown, graceful or otherwise.
 //!
+//! If the connection has extra streams (i.e., QUIC connections), the messages of each
+//! priority class are written on their own stream (see [`extra_stream_priorities`]),
+//! so that a stalled stream (e.g., for state sync) never delays other messages (e.g.,
+//! for consensus). Messages are read from all streams.
+//!
 //! [`PeerManager`]: crate::peer_manager::PeerManager
 
 use crate::{
@@ -21,6 +26,7 @@ use crate::{
         FAILED_LABEL, RECEIVED_LABEL, SENT_LABEL,
     },
     logging::NetworkSchema,
+    peer::priority::{NextMessage, OutboundQueue, OutboundSender, ProtocolPriorities},
     peer_manager::{PeerManagerError, TransportNotification},
     protocols::{
         direct_send::Message,
@@ -28,15 +34,16 @@ use crate::{
         stream::{InboundStreamBuffer, OutboundStream, StreamMessage},
         wire::messaging::v1::{
             DirectSendMsg, ErrorCode, MultiplexMessage, MultiplexMessageSink,
-            MultiplexMessageStream, NetworkMessage, Priority, ReadError, WriteError,
+            MultiplexMessageStream, NetworkMessage, ReadError, WriteError,
         },
     },
-    transport::{self, Connection, ConnectionMetadata},
+    transport::{self, extra_stream_priorities, Connection, ConnectionMetadata},
     ProtocolId,
 };
 use aptos_channels::aptos_channel;
-use aptos_config::network_id::NetworkContext;
+use aptos_config::{config::OutboundTrafficConfig, network_id::NetworkContext};
 use aptos_logger::prelude::*;
+use aptos_netcore::transport::quic::QuicStream;
 use aptos_short_hex_str::AsShortHexStr;
 use aptos_time_service::{TimeService, TimeServiceTrait};
 use aptos_types::PeerId;
@@ -44,13 +51,14 @@ use bytes::Bytes;
 use futures::{
     self,
     channel::oneshot,
+    future::{self, FutureExt},
     io::{AsyncRead, AsyncWrite},
-    stream::StreamExt,
+    stream::{self, BoxStream, Stream, StreamExt},
     SinkExt,
 };
-use futures_util::stream::select;
+use futures_util::stream::{select, select_all};
 use serde::Serialize;
-use std::{fmt, panic, time::Duration};
+use std::{fmt, iter, panic, sync::Arc, time::Duration};
 use tokio::runtime::Handle;
 use tokio_util::compat::{
     FuturesAsyncReadCompatExt, TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt,
@@ -62,6 +70,12 @@ mod test;
 #[cfg(any(test, feature = "fuzzing"))]
 pub mod fuzzing;
 
+pub mod priority;
+
+/// The maximum number of outbound messages queued (per peer and priority class) for
+/// prioritization. Senders wait if their class is full (see `OutboundQueue`).
+const MAX_QUEUED_OUTBOUND_MESSAGES: usize = 1024;
+
 /// Requests [`Peer`] receives from the [`PeerManager`](crate::peer_manager::PeerManager).
 #[derive(Debug)]
 pub enum PeerRequest {
@@ -120,6 +134,8 @@ pub struct Peer<TSocket> {
     connection_metadata: ConnectionMetadata,
     /// Underlying connection.
     connection: Option<TSocket>,
+    /// The extra streams of the connection (if any), one per extra stream priority class.
+    extra_streams: Vec<QuicStream>,
     /// Channel to notify PeerManager that we've disconnected.
     connection_notifs_tx: aptos_channels::Sender<TransportNotification<TSocket>>,
     /// Channel to receive requests from PeerManager to send messages and rpcs.
@@ -136,8 +152,12 @@ pub struct Peer<TSocket> {
     max_frame_size: usize,
     /// The maximum size of an inbound or outbound request message
     max_message_size: usize,
-    /// Inbound stream buffer
-    inbound_stream: InboundStreamBuffer,
+    /// Inbound stream buffers, one per stream of the connection (the socket first)
+    inbound_streams: Vec<InboundStreamBuffer>,
+    /// The priority class of each protocol, used to prioritize outbound messages
+    protocol_priorities: Arc<ProtocolPriorities>,
+    /// Prioritization and bandwidth limits for outbound messages
+    outbound_traffic_config: OutboundTrafficConfig,
 }
 
 impl<TSocket> Peer<TSocket>
@@ -158,19 +178,28 @@ where
         max_concurrent_outbound_rpcs: u32,
         max_frame_size: usize,
         max_message_size: usize,
+        outbound_traffic_config: OutboundTrafficConfig,
     ) -> Self {
         let Connection {
             metadata: connection_metadata,
             socket,
+            extra_streams,
         } = connection;
         let remote_peer_id = connection_metadata.remote_peer_id;
         let max_fragments = max_message_size / max_frame_size;
+        let inbound_streams = iter::repeat_with(|| InboundStreamBuffer::new(max_fragments))
+            .take(1 + extra_streams.len())
+            .collect();
+        let protocol_priorities = Arc::new(ProtocolPriorities::new(
+            &outbound_traffic_config.protocol_priorities,
+        ));
         Self {
             network_context,
             executor,
             time_service: time_service.clone(),
             connection_metadata,
             connection: Some(socket),
+            extra_streams,
             connection_notifs_tx,
             peer_reqs_rx,
             peer_notifs_tx,
@@ -180,17 +209,21 @@ where
                 remote_peer_id,
                 inbound_rpc_timeout,
                 max_concurrent_inbound_rpcs,
+                protocol_priorities.clone(),
             ),
             outbound_rpcs: OutboundRpcs::new(
                 network_context,
                 time_service,
                 remote_peer_id,
                 max_concurrent_outbound_rpcs,
+                protocol_priorities.clone(),
             ),
             state: State::Connected,
             max_frame_size,
             max_message_size,
-            inbound_stream: InboundStreamBuffer::new(max_fragments),
+            inbound_streams,
+            protocol_priorities,
+            outbound_traffic_config,
         }
     }
 
@@ -212,22 +245,48 @@ where
         let (read_socket, write_socket) =
             tokio::io::split(self.connection.take().unwrap().compat());
 
-        let mut reader =
-            MultiplexMessageStream::new(read_socket.compat(), self.max_frame_size).fuse();
+        let reader = MultiplexMessageStream::new(read_socket.compat(), self.max_frame_size);
         let writer = MultiplexMessageSink::new(write_socket.compat_write(), self.max_frame_size);
 
-        // Start writer "process" as a separate task. We receive two handles to
-        // communicate with the task:
+        // Split each extra stream (if any) in the same way.
+        let max_frame_size = self.max_frame_size;
+        let (extra_readers, extra_writers): (Vec<_>, Vec<_>) = self
+            .extra_streams
+            .drain(..)
+            .map(|stream| {
+                let (read_stream, write_stream) = tokio::io::split(stream.compat());
+                (
+                    MultiplexMessageStream::new(read_stream.compat(), max_frame_size),
+                    MultiplexMessageSink::new(write_stream.compat_write(), max_frame_size),
+                )
+            })
+            .unzip();
+
+        // Read the messages of all streams (tagged with the index of their stream).
+        let mut reader = select_all(
+            iter::once(tag_messages(0, reader)).chain(
+                extra_readers
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, extra_reader)| tag_messages(index + 1, extra_reader)),
+            ),
+        )
+        .fuse();
+
+        // Start writer "processes" as separate tasks. We receive two handles to
+        // communicate with the tasks:
         //   1. `write_reqs_tx`: Queue of pending NetworkMessages to write.
-        //   2. `close_tx`: Handle to close the task and underlying connection.
-        let (mut write_reqs_tx, writer_close_tx) = Self::start_writer_task(
+        //   2. `writer_close_txs`: Handles to close the tasks and underlying connection.
+        let (mut write_reqs_tx, writer_close_txs) = Self::start_writer_tasks(
             &self.executor,
             self.time_service.clone(),
             self.connection_metadata.clone(),
             self.network_context,
             writer,
+            extra_writers,
             self.max_frame_size,
             self.max_message_size,
+            &self.outbound_traffic_config,
         );
 
         // Start main Peer event loop.
@@ -250,8 +309,8 @@ where
                 // the wire from the remote peer.
                 maybe_message = reader.next() => {
                     match maybe_message {
-                        Some(message) =>  {
-                            if let Err(err) = self.handle_inbound_message(message, &mut write_reqs_tx).await {
+                        Some((stream_index, Some(message))) =>  {
+                            if let Err(err) = self.handle_inbound_message(stream_index, message, &mut write_reqs_tx).await {
                                 warn!(
                                     NetworkSchema::new(&self.network_context)
                                         .connection_metadata(&self.connection_metadata),
@@ -263,8 +322,8 @@ where
                                 );
                             }
                         },
-                        // The socket was gracefully closed by the remote peer.
-                        None => self.shutdown(DisconnectReason::ConnectionLost),
+                        // The socket (or a stream) was gracefully closed by the remote peer.
+                        Some((_, None)) | None => self.shutdown(DisconnectReason::ConnectionLost),
                     }
                 },
                 // Drive the queue of pending inbound rpcs. When one is fulfilled
@@ -310,31 +369,108 @@ where
             }
         };
 
-        // Finish shutting down the connection. Close the writer task and notify
+        // Finish shutting down the connection. Close the writer tasks and notify
         // PeerManager that this connection has shutdown.
-        self.do_shutdown(writer_close_tx, reason).await;
+        self.do_shutdown(writer_close_txs, reason).await;
+    }
+
+    // Start the tasks which are responsible for writing outbound messages on the wire (see
+    // `start_writer_task`). If the connection has extra streams, each priority class has its
+    // own queue and writer task: high priority messages are written on the socket, and the
+    // messages of each other class on their extra stream (see `extra_stream_priorities`).
+    // Otherwise, all messages share the same queue and are written on the socket.
+    #[allow(clippy::too_many_arguments)]
+    fn start_writer_tasks(
+        executor: &Handle,
+        time_service: TimeService,
+        connection_metadata: ConnectionMetadata,
+        network_context: NetworkContext,
+        writer: MultiplexMessageSink<impl AsyncWrite + Unpin + Send + 'static>,
+        extra_writers: Vec<MultiplexMessageSink<impl AsyncWrite + Unpin + Send + 'static>>,
+        max_frame_size: usize,
+        max_message_size: usize,
+        outbound_traffic_config: &OutboundTrafficConfig,
+    ) -> (OutboundSender, Vec<oneshot::Sender<()>>) {
+        let remote_peer_id = connection_metadata.remote_peer_id;
+        if extra_writers.is_empty() {
+            let (write_reqs_tx, outbound_queue) = OutboundQueue::new(
+                network_context,
+                remote_peer_id,
+                outbound_traffic_config,
+                MAX_QUEUED_OUTBOUND_MESSAGES,
+                time_service.clone(),
+            );
+            let close_tx = Self::start_writer_task(
+                executor,
+                time_service,
+                connection_metadata,
+                network_context,
+                writer,
+                outbound_queue,
+                max_frame_size,
+                max_message_size,
+            );
+            return (write_reqs_tx, vec![close_tx]);
+        }
+
+        // The queues are ordered from highest to lowest priority, so the first queue
+        // (i.e., high priority) is written on the socket.
+        let (write_reqs_tx, mut outbound_queues) = OutboundQueue::new_per_class(
+            network_context,
+            remote_peer_id,
+            outbound_traffic_config,
+            MAX_QUEUED_OUTBOUND_MESSAGES,
+            time_service.clone(),
+        );
+        let extra_outbound_queues = outbound_queues.split_off(1);
+        debug_assert_eq!(extra_outbound_queues.len(), extra_stream_priorities().len());
+
+        let mut close_txs = vec![Self::start_writer_task(
+            executor,
+            time_service.clone(),
+            connection_metadata.clone(),
+            network_context,
+            writer,
+            outbound_queues.remove(0),
+            max_frame_size,
+            max_message_size,
+        )];
+        for (extra_writer, outbound_queue) in extra_writers.into_iter().zip(extra_outbound_queues) {
+            close_txs.push(Self::start_writer_task(
+                executor,
+                time_service.clone(),
+                connection_metadata.clone(),
+                network_context,
+                extra_writer,
+                outbound_queue,
+                max_frame_size,
+                max_message_size,
+            ));
+        }
+        (write_reqs_tx, close_txs)
     }
 
-    // Start a new task on the given executor which is responsible for writing outbound messages on
-    // the wire. The function returns two channels which can be used to send instructions to the
-    // task:
-    // 1. The first channel is used to send outbound NetworkMessages to the task
-    // 2. The second channel is used to instruct the task to close the connection and terminate.
+    // Start a new task on the given executor which is responsible for writing the outbound
+    // messages of the given queue on the wire (using the given writer). The function returns
+    // a channel which is used to instruct the task to close the writer and terminate.
     // If outbound messages are queued when the task receives a close instruction, it discards
-    // them and immediately closes the connection.
+    // them and immediately closes the writer.
+    // Outbound messages are written in priority order, and normal and low priority messages
+    // are subject to the peer bandwidth limit (see `OutboundQueue`).
+    #[allow(clippy::too_many_arguments)]
     fn start_writer_task(
         executor: &Handle,
         time_service: TimeService,
         connection_metadata: ConnectionMetadata,
         network_context: NetworkContext,
         mut writer: MultiplexMessageSink<impl AsyncWrite + Unpin + Send + 'static>,
+        mut outbound_queue: OutboundQueue,
         max_frame_size: usize,
         max_message_size: usize,
-    ) -> (aptos_channels::Sender<NetworkMessage>, oneshot::Sender<()>) {
+    ) -> oneshot::Sender<()> {
         let remote_peer_id = connection_metadata.remote_peer_id;
-        let (write_reqs_tx, mut write_reqs_rx): (aptos_channels::Sender<NetworkMessage>, _) =
-            aptos_channels::new(1024, &counters::PENDING_WIRE_MESSAGES);
         let (close_tx, mut close_rx) = oneshot::channel();
+        let multiplex_time_service = time_service.clone();
 
         let (mut msg_tx, msg_rx) = aptos_channels::new(1024, &counters::PENDING_MULTIPLEX_MESSAGE);
         let (stream_msg_tx, stream_msg_rx) =
@@ -403,32 +539,60 @@ where
             let mut outbound_stream =
                 OutboundStream::new(max_frame_size, max_message_size, stream_msg_tx);
             loop {
-                futures::select! {
-                    message = write_reqs_rx.select_next_some() => {
-                        // either channel full would block the other one
-                        let result = if outbound_stream.should_stream(&message) {
-                            outbound_stream.stream_message(message).await
-                        } else {
-                            msg_tx.send(MultiplexMessage::Message(message)).await.map_err(|_| anyhow::anyhow!("Writer task ended"))
-                        };
-                        if let Err(err) = result {
-                            warn!(
-                                error = %err,
-                                "{} Error in sending message to peer: {}",
-                                network_context,
-                                remote_peer_id.short_str(),
-                            );
+                // Stop immediately if the connection is being closed
+                if !matches!(close_rx.try_recv(), Ok(None)) {
+                    break;
+                }
+
+                // Select the next message from everything that is waiting to be
+                // sent (the queue only receives messages while it has room).
+                let message = match outbound_queue.pop() {
+                    NextMessage::Ready(message) => message,
+                    NextMessage::Throttled(ready_time) => {
+                        // Wait for the bandwidth to become available (or for
+                        // new messages that might not be throttled).
+                        let delay =
+                            ready_time.saturating_duration_since(multiplex_time_service.now());
+                        let throttle_delay = multiplex_time_service.sleep(delay).fuse();
+                        futures::pin_mut!(throttle_delay);
+                        futures::select! {
+                            _ = outbound_queue.receive().fuse() => {},
+                            _ = throttle_delay => {},
+                            _ = close_rx => break,
                         }
+                        continue;
                     },
-                    _ = close_rx => {
-                        break;
-                    }
+                    NextMessage::Empty => {
+                        futures::select! {
+                            _ = outbound_queue.receive().fuse() => {},
+                            _ = close_rx => break,
+                        }
+                        continue;
+                    },
+                };
+
+                // either channel full would block the other one
+                let result = if outbound_stream.should_stream(&message) {
+                    outbound_stream.stream_message(message).await
+                } else {
+                    msg_tx
+                        .send(MultiplexMessage::Message(message))
+                        .await
+                        .map_err(|_| anyhow::anyhow!("Writer task ended"))
+                };
+                if let Err(err) = result {
+                    warn!(
+                        error = %err,
+                        "{} Error in sending message to peer: {}",
+                        network_context,
+                        remote_peer_id.short_str(),
+                    );
                 }
             }
         };
         executor.spawn(writer_task);
         executor.spawn(multiplex_task);
-        (write_reqs_tx, close_tx)
+        close_tx
     }
 
     async fn handle_inbound_network_message(
@@ -472,14 +636,16 @@ where
 
     async fn handle_inbound_stream_message(
         &mut self,
+        stream_index: usize,
         message: StreamMessage,
     ) -> Result<(), PeerManagerError> {
+        let inbound_stream = &mut self.inbound_streams[stream_index];
         match message {
             StreamMessage::Header(header) => {
-                self.inbound_stream.new_stream(header)?;
+                inbound_stream.new_stream(header)?;
             },
             StreamMessage::Fragment(fragment) => {
-                if let Some(message) = self.inbound_stream.append_fragment(fragment)? {
+                if let Some(message) = inbound_stream.append_fragment(fragment)? {
                     self.handle_inbound_network_message(message).await?;
                 }
             },
@@ -489,8 +655,9 @@ where
 
     async fn handle_inbound_message(
         &mut self,
+        stream_index: usize,
         message: Result<MultiplexMessage, ReadError>,
-        write_reqs_tx: &mut aptos_channels::Sender<NetworkMessage>,
+        write_reqs_tx: &mut OutboundSender,
     ) -> Result<(), PeerManagerError> {
         trace!(
             NetworkSchema::new(&self.network_context)
@@ -527,7 +694,10 @@ where
             MultiplexMessage::Message(message) => {
                 self.handle_inbound_network_message(message).await
             },
-            MultiplexMessage::Stream(message) => self.handle_inbound_stream_message(message).await,
+            MultiplexMessage::Stream(message) => {
+                self.handle_inbound_stream_message(stream_index, message)
+                    .await
+            },
         }
     }
 
@@ -578,7 +748,7 @@ where
     async fn handle_outbound_request(
         &mut self,
         request: PeerRequest,
-        write_reqs_tx: &mut aptos_channels::Sender<NetworkMessage>,
+        write_reqs_tx: &mut OutboundSender,
     ) {
         trace!(
             "Peer {} PeerRequest::{:?}",
@@ -594,7 +764,7 @@ where
                 let protocol_id = message.protocol_id;
                 let message = NetworkMessage::DirectSendMsg(DirectSendMsg {
                     protocol_id,
-                    priority: Priority::default(),
+                    priority: self.protocol_priorities.wire_priority(protocol_id),
                     raw_msg: Vec::from(message.mdata.as_ref()),
                 });
 
@@ -653,7 +823,11 @@ where
         self.state = State::ShuttingDown(reason);
     }
 
-    async fn do_shutdown(mut self, writer_close_tx: oneshot::Sender<()>, reason: DisconnectReason) {
+    async fn do_shutdown(
+        mut self,
+        writer_close_txs: Vec<oneshot::Sender<()>>,
+        reason: DisconnectReason,
+    ) {
         let remote_peer_id = self.remote_peer_id();
 
         // Send a PeerDisconnected event to PeerManager.
@@ -676,18 +850,20 @@ where
             );
         }
 
-        // Send a close instruction to the writer task. On receipt of this
-        // instruction, the writer task drops all pending outbound messages and
-        // closes the connection.
-        if let Err(e) = writer_close_tx.send(()) {
-            info!(
-                NetworkSchema::new(&self.network_context)
-                    .connection_metadata(&self.connection_metadata),
-                error = ?e,
-                "{} Failed to send close instruction to writer task. It must already be terminating/terminated. Error: {:?}",
-                self.network_context,
-                e
-            );
+        // Send a close instruction to the writer tasks. On receipt of this
+        // instruction, each writer task drops all pending outbound messages and
+        // closes its writer (i.e., the connection).
+        for writer_close_tx in writer_close_txs {
+            if let Err(e) = writer_close_tx.send(()) {
+                info!(
+                    NetworkSchema::new(&self.network_context)
+                        .connection_metadata(&self.connection_metadata),
+                    error = ?e,
+                    "{} Failed to send close instruction to writer task. It must already be terminating/terminated. Error: {:?}",
+                    self.network_context,
+                    e
+                );
+            }
         }
 
         trace!(
@@ -699,3 +875,16 @@ where
         );
     }
 }
+
+/// Tags each message read from the given reader with the index of its stream. Once
+/// the reader ends (i.e., the stream is closed), `None` is returned for the stream.
+fn tag_messages(
+    stream_index: usize,
+    reader: impl Stream<Item = Result<MultiplexMessage, ReadError>> + Send + 'static,
+) -> BoxStream<'static, (usize, Option<Result<MultiplexMessage, ReadError>>)> {
+    reader
+        .map(Some)
+        .chain(stream::once(future::ready(None)))
+        .map(move |message| (stream_index, message))
+        .boxed()
+}
@@ -0,0 +1,809 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Prioritization and bandwidth limiting of outbound messages to a single peer.
+//!
+//! Each protocol is assigned an [`OutboundPriority`] class (see [`default_priority`]),
+//! which can be overridden in the [`OutboundTrafficConfig`]. The class is encoded into
+//! the `priority` field of each outbound wire message, and the [`OutboundQueue`] uses it
+//! to decide which queued message to write next. This ensures that latency sensitive
+//! messages (e.g., consensus votes) are not stuck behind large, bulk transfers (e.g.,
+//! state sync responses). Normal and low priority messages can also be throttled by a
+//! per-peer token bucket (see [`Bucket`]), while high priority messages are never throttled.
+//!
+//! Each priority class has its own bounded channel (see [`OutboundSender`]), and the
+//! [`OutboundQueue`] only pulls messages from a channel while the queue of that class has
+//! room. Thus, messages are never dropped: senders wait when their class is full (i.e.,
+//! backpressure), and a backlog of throttled messages never prevents high priority
+//! messages from being queued (and written).
+//!
+//! If the connection supports multiple streams (i.e., QUIC), each priority class has its
+//! own [`OutboundQueue`] (see [`OutboundQueue::new_per_class`]) and is written on its own
+//! stream, so a stalled stream never delays the messages of other classes.
+
+use crate::{
+    counters,
+    protocols::wire::messaging::v1::{NetworkMessage, Priority},
+    ProtocolId,
+};
+use aptos_config::{
+    config::{OutboundPriority, OutboundTrafficConfig, RateLimitConfig},
+    network_id::NetworkContext,
+};
+use aptos_infallible::Mutex;
+use aptos_logger::prelude::*;
+use aptos_rate_limiter::rate_limit::{Bucket, SharedBucket};
+use aptos_short_hex_str::AsShortHexStr;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use aptos_types::PeerId;
+use futures::{
+    channel::mpsc,
+    future::{self, FutureExt},
+    stream::StreamExt,
+    SinkExt,
+};
+use std::{
+    cmp::min,
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    task::{Context, Poll},
+    time::Instant,
+};
+
+/// The wire priorities of each priority class (higher values are more urgent)
+const HIGH_WIRE_PRIORITY: Priority = 2;
+const NORMAL_WIRE_PRIORITY: Priority = 1;
+const LOW_WIRE_PRIORITY: Priority = 0;
+
+/// Returns the default priority class for the given protocol
+pub fn default_priority(protocol_id: ProtocolId) -> OutboundPriority {
+    use ProtocolId::*;
+    match protocol_id {
+        ConsensusRpcBcs
+        | ConsensusDirectSendBcs
+        | ConsensusDirectSendJson
+        | ConsensusRpcJson
+        | ConsensusRpcCompressed
+        | ConsensusDirectSendCompressed
+        | DKGDirectSendCompressed
+        | DKGDirectSendBcs
+        | DKGDirectSendJson
+        | DKGRpcCompressed
+        | DKGRpcBcs
+        | DKGRpcJson
+        | JWKConsensusDirectSendCompressed
+        | JWKConsensusDirectSendBcs
+        | JWKConsensusDirectSendJson
+        | JWKConsensusRpcCompressed
+        | JWKConsensusRpcBcs
+        | JWKConsensusRpcJson
+        | HealthCheckerRpc => OutboundPriority::High,
+        MempoolDirectSend
+        | MempoolDirectSendCompressed
+        | MempoolDirectSendDictionaryCompressed
+        | MempoolRpc
+        | DiscoveryDirectSend
+        | PeerMonitoringServiceRpc
+        | ConsensusObserver => OutboundPriority::Normal,
+        StateSyncDirectSend
+        | StorageServiceRpc
+        | NetbenchDirectSend
+        | NetbenchRpc
+        | NetbenchDirectSendCompressed
+        | NetbenchRpcCompressed => OutboundPriority::Low,
+    }
+}
+
+/// Encodes the priority class into the `priority` field of a wire message
+pub fn to_wire_priority(priority: OutboundPriority) -> Priority {
+    match priority {
+        OutboundPriority::High => HIGH_WIRE_PRIORITY,
+        OutboundPriority::Normal => NORMAL_WIRE_PRIORITY,
+        OutboundPriority::Low => LOW_WIRE_PRIORITY,
+    }
+}
+
+/// Decodes the priority class from the `priority` field of a wire message
+pub fn from_wire_priority(priority: Priority) -> OutboundPriority {
+    if priority >= HIGH_WIRE_PRIORITY {
+        OutboundPriority::High
+    } else if priority == NORMAL_WIRE_PRIORITY {
+        OutboundPriority::Normal
+    } else {
+        OutboundPriority::Low
+    }
+}
+
+/// Returns the priority class of the given outbound message
+fn message_priority(message: &NetworkMessage) -> OutboundPriority {
+    match message {
+        NetworkMessage::Error(_) => OutboundPriority::High,
+        NetworkMessage::RpcRequest(request) => from_wire_priority(request.priority),
+        NetworkMessage::RpcResponse(response) => from_wire_priority(response.priority),
+        NetworkMessage::DirectSendMsg(message) => from_wire_priority(message.priority),
+    }
+}
+
+/// The priority class of each protocol, after applying the configured overrides
+#[derive(Clone, Debug)]
+pub struct ProtocolPriorities {
+    priorities: HashMap<ProtocolId, OutboundPriority>,
+}
+
+impl ProtocolPriorities {
+    pub fn new(overrides: &HashMap<String, OutboundPriority>) -> Self {
+        let priorities: HashMap<_, _> = ProtocolId::all()
+            .iter()
+            .map(|protocol_id| {
+                let priority = overrides
+                    .get(protocol_id.as_str())
+                    .copied()
+                    .unwrap_or_else(|| default_priority(*protocol_id));
+                (*protocol_id, priority)
+            })
+            .collect();
+
+        // Warn about any overrides for unknown protocols
+        for protocol_name in overrides.keys() {
+            if !ProtocolId::all()
+                .iter()
+                .any(|protocol_id| protocol_id.as_str() == protocol_name)
+            {
+                warn!(
+                    "Ignoring the outbound priority override for unknown protocol: {}",
+                    protocol_name
+                );
+            }
+        }
+
+        Self { priorities }
+    }
+
+    /// Returns the priority class of the given protocol
+    pub fn get(&self, protocol_id: ProtocolId) -> OutboundPriority {
+        self.priorities
+            .get(&protocol_id)
+            .copied()
+            .unwrap_or_else(|| default_priority(protocol_id))
+    }
+
+    /// Returns the wire priority for messages of the given protocol
+    pub fn wire_priority(&self, protocol_id: ProtocolId) -> Priority {
+        to_wire_priority(self.get(protocol_id))
+    }
+}
+
+impl Default for ProtocolPriorities {
+    fn default() -> Self {
+        Self::new(&HashMap::new())
+    }
+}
+
+/// The next message to write from the [`OutboundQueue`]
+#[derive(Debug)]
+pub enum NextMessage {
+    /// The message is ready to be written
+    Ready(NetworkMessage),
+    /// The next message is throttled by the bandwidth limit until the given time
+    Throttled(Instant),
+    /// There are no queued messages
+    Empty,
+}
+
+/// The sending half of an [`OutboundQueue`]. Messages are sent on the (bounded)
+/// channel of their priority class, so senders wait if the class is full.
+#[derive(Clone)]
+pub struct OutboundSender {
+    enable_prioritization: bool,
+    senders: Vec<aptos_channels::Sender<NetworkMessage>>, // Ordered from highest to lowest priority
+}
+
+impl OutboundSender {
+    /// Sends the message on the channel of its priority class (waiting for room, if required)
+    pub async fn send(&mut self, message: NetworkMessage) -> Result<(), mpsc::SendError> {
+        let queue_index = queue_index(self.enable_prioritization, message_priority(&message));
+        self.senders[queue_index].send(message).await
+    }
+}
+
+/// A queue of outbound messages for a single peer. If prioritization is enabled,
+/// messages are dequeued in priority order (and FIFO within each priority class),
+/// otherwise, all messages are dequeued in FIFO order.
+///
+/// Messages are received from the [`OutboundSender`], and each class queues at most
+/// `max_queued_messages_per_class` messages (in addition to those in its channel).
+pub struct OutboundQueue {
+    network_context: NetworkContext,
+    remote_peer_id: PeerId,
+    max_queued_messages_per_class: usize,
+    queues: Vec<VecDeque<NetworkMessage>>, // Ordered from highest to lowest priority
+    receivers: Vec<aptos_channels::Receiver<NetworkMessage>>, // One receiver per queue
+    bandwidth_limit: Option<SharedBucket>,
+}
+
+impl OutboundQueue {
+    /// Creates the outbound queue and the sender for the queue. Note: if prioritization
+    /// is disabled, all messages share a single queue (and channel).
+    pub fn new(
+        network_context: NetworkContext,
+        remote_peer_id: PeerId,
+        outbound_traffic_config: &OutboundTrafficConfig,
+        max_queued_messages_per_class: usize,
+        time_service: TimeService,
+    ) -> (OutboundSender, Self) {
+        let (outbound_sender, receivers) = create_outbound_channels(
+            outbound_traffic_config.enable_prioritization,
+            max_queued_messages_per_class,
+        );
+        let bandwidth_limit = create_bandwidth_limit(
+            &network_context,
+            remote_peer_id,
+            outbound_traffic_config,
+            time_service,
+        );
+
+        let outbound_queue = Self::from_receivers(
+            network_context,
+            remote_peer_id,
+            max_queued_messages_per_class,
+            receivers,
+            bandwidth_limit,
+        );
+        (outbound_sender, outbound_queue)
+    }
+
+    /// Creates one outbound queue per priority class (ordered from highest to lowest
+    /// priority) and the sender for the queues. This allows the messages of each class
+    /// to be written independently (e.g., on separate streams). Note: the queues share
+    /// the bandwidth limit, and prioritization is implied by the separate queues.
+    pub fn new_per_class(
+        network_context: NetworkContext,
+        remote_peer_id: PeerId,
+        outbound_traffic_config: &OutboundTrafficConfig,
+        max_queued_messages_per_class: usize,
+        time_service: TimeService,
+    ) -> (OutboundSender, Vec<Self>) {
+        let (outbound_sender, receivers) =
+            create_outbound_channels(true, max_queued_messages_per_class);
+        let bandwidth_limit = create_bandwidth_limit(
+            &network_context,
+            remote_peer_id,
+            outbound_traffic_config,
+            time_service,
+        );
+
+        let outbound_queues = receivers
+            .into_iter()
+            .map(|receiver| {
+                Self::from_receivers(
+                    network_context,
+                    remote_peer_id,
+                    max_queued_messages_per_class,
+                    vec![receiver],
+                    bandwidth_limit.clone(),
+                )
+            })
+            .collect();
+        (outbound_sender, outbound_queues)
+    }
+
+    fn from_receivers(
+        network_context: NetworkContext,
+        remote_peer_id: PeerId,
+        max_queued_messages_per_class: usize,
+        receivers: Vec<aptos_channels::Receiver<NetworkMessage>>,
+        bandwidth_limit: Option<SharedBucket>,
+    ) -> Self {
+        Self {
+            network_context,
+            remote_peer_id,
+            max_queued_messages_per_class,
+            queues: receivers.iter().map(|_| VecDeque::new()).collect(),
+            receivers,
+            bandwidth_limit,
+        }
+    }
+
+    /// Waits until new messages are received (for any queue with room), and queues
+    /// them. Note: if all senders have been dropped, this never completes.
+    pub async fn receive(&mut self) {
+        future::poll_fn(|cx| self.poll_receive(cx)).await
+    }
+
+    /// Queues all messages that are ready to be received (for any queue with room),
+    /// without waiting.
+    pub fn receive_ready(&mut self) {
+        let _ = self.receive().now_or_never();
+    }
+
+    /// Receives messages into each queue (while the queue has room). Returns ready if
+    /// any messages were received.
+    fn poll_receive(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        let mut received_messages = false;
+        for (queue, receiver) in self.queues.iter_mut().zip(self.receivers.iter_mut()) {
+            while queue.len() < self.max_queued_messages_per_class {
+                match receiver.poll_next_unpin(cx) {
+                    Poll::Ready(Some(message)) => {
+                        queue.push_back(message);
+                        received_messages = true;
+                    },
+                    Poll::Ready(None) | Poll::Pending => break,
+                }
+            }
+        }
+
+        if received_messages {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+
+    /// Returns the next message to write, if any (after receiving all ready messages).
+    /// High priority messages are always ready, but other messages must first acquire
+    /// enough bandwidth.
+    pub fn pop(&mut self) -> NextMessage {
+        self.receive_ready();
+        for queue_index in 0..self.queues.len() {
+            let message = match self.queues[queue_index].front() {
+                Some(message) => message,
+                None => continue,
+            };
+
+            // Acquire the bandwidth required by the message
+            let priority = message_priority(message);
+            let data_len = message.data_len();
+            if priority != OutboundPriority::High {
+                if let Some(bandwidth_limit) = self.bandwidth_limit.as_ref() {
+                    let result = acquire_bandwidth(&mut bandwidth_limit.lock(), data_len);
+                    if let Err(ready_time) = result {
+                        counters::outbound_throttled_messages(
+                            &self.network_context,
+                            priority.as_str(),
+                        )
+                        .inc();
+                        trace!(
+                            "{} Throttled outbound {} priority message to peer: {}",
+                            self.network_context,
+                            priority,
+                            self.remote_peer_id.short_str()
+                        );
+
+                        // High priority messages queued behind this one (i.e., when
+                        // prioritization is disabled) are never throttled.
+                        return self.pop_high_priority(queue_index, ready_time);
+                    }
+                }
+            }
+
+            // Dequeue the message and update the metrics
+            let message = self.queues[queue_index]
+                .pop_front()
+                .expect("The queue should not be empty!");
+            record_dequeued_message(&self.network_context, priority, data_len);
+            return NextMessage::Ready(message);
+        }
+
+        NextMessage::Empty
+    }
+
+    /// Dequeues the first high priority message in the given (throttled) queue, if
+    /// any. Otherwise, returns that the queue is throttled until the ready time.
+    fn pop_high_priority(&mut self, queue_index: usize, ready_time: Instant) -> NextMessage {
+        let queue = &mut self.queues[queue_index];
+        match queue
+            .iter()
+            .position(|message| message_priority(message) == OutboundPriority::High)
+        {
+            Some(message_index) => {
+                let message = queue
+                    .remove(message_index)
+                    .expect("The message index should be valid!");
+                record_dequeued_message(
+                    &self.network_context,
+                    OutboundPriority::High,
+                    message.data_len(),
+                );
+                NextMessage::Ready(message)
+            },
+            None => NextMessage::Throttled(ready_time),
+        }
+    }
+}
+
+/// Returns the index of the queue for the given priority class
+fn queue_index(enable_prioritization: bool, priority: OutboundPriority) -> usize {
+    if enable_prioritization {
+        OutboundPriority::all()
+            .iter()
+            .position(|queue_priority| *queue_priority == priority)
+            .expect("All priorities should have a queue!")
+    } else {
+        0 // All messages share the same queue
+    }
+}
+
+/// Updates the metrics for a message dequeued to be written
+fn record_dequeued_message(
+    network_context: &NetworkContext,
+    priority: OutboundPriority,
+    data_len: usize,
+) {
+    counters::outbound_priority_messages(network_context, priority.as_str()).inc();
+    counters::outbound_priority_bytes(network_context, priority.as_str()).inc_by(data_len as u64);
+}
+
+/// Creates the sender and receivers for the outbound channels (one per priority
+/// class if prioritization is enabled, otherwise, a single channel).
+fn create_outbound_channels(
+    enable_prioritization: bool,
+    max_queued_messages_per_class: usize,
+) -> (
+    OutboundSender,
+    Vec<aptos_channels::Receiver<NetworkMessage>>,
+) {
+    let num_channels = if enable_prioritization {
+        OutboundPriority::all().len()
+    } else {
+        1
+    };
+    let (senders, receivers) = (0..num_channels)
+        .map(|_| {
+            aptos_channels::new(
+                max_queued_messages_per_class,
+                &counters::PENDING_WIRE_MESSAGES,
+            )
+        })
+        .unzip();
+
+    let outbound_sender = OutboundSender {
+        enable_prioritization,
+        senders,
+    };
+    (outbound_sender, receivers)
+}
+
+/// Creates the token bucket limiting the outbound bandwidth to the peer (tokens are
+/// bytes), if the limit is enabled. Note: the config is expected to have been validated
+/// by the config sanitizer (i.e., with a non-zero rate, and a bucket size that is at
+/// least the rate).
+fn create_bandwidth_limit(
+    network_context: &NetworkContext,
+    remote_peer_id: PeerId,
+    outbound_traffic_config: &OutboundTrafficConfig,
+    time_service: TimeService,
+) -> Option<SharedBucket> {
+    let config: RateLimitConfig = outbound_traffic_config
+        .peer_bandwidth_limit
+        .filter(|config| config.enabled)?;
+    let bucket_size = config.ip_byte_bucket_size.max(1);
+    let fill_rate = config.ip_byte_bucket_rate.clamp(1, bucket_size);
+    let fill_percentage = min(config.initial_bucket_fill_percentage, 100) as usize;
+    let bucket = Bucket::new(
+        "outbound_bandwidth".into(),
+        network_context.to_string(),
+        remote_peer_id.short_str().to_string(),
+        bucket_size.saturating_mul(fill_percentage) / 100,
+        bucket_size,
+        fill_rate,
+        None,
+    )
+    .with_time_service(time_service);
+    Some(Arc::new(Mutex::new(bucket)))
+}
+
+/// Acquires the tokens required to send the given number of bytes. If the tokens are
+/// not yet available, the time at which they will be is returned. Messages larger than
+/// the bucket are sent as soon as any tokens are available (and drain the bucket).
+fn acquire_bandwidth(bandwidth_limit: &mut Bucket, num_bytes: usize) -> Result<(), Instant> {
+    match bandwidth_limit.acquire_all_tokens(num_bytes) {
+        Ok(()) => Ok(()),
+        Err(Some(ready_time)) => Err(ready_time),
+        Err(None) => bandwidth_limit.acquire_tokens(num_bytes).map(|_| ()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocols::wire::messaging::v1::{DirectSendMsg, ErrorCode, RpcResponse};
+    use std::time::Duration;
+
+    fn direct_send(protocol_id: ProtocolId, raw_msg: Vec<u8>) -> NetworkMessage {
+        NetworkMessage::DirectSendMsg(DirectSendMsg {
+            protocol_id,
+            priority: ProtocolPriorities::default().wire_priority(protocol_id),
+            raw_msg,
+        })
+    }
+
+    /// Sends the message without waiting. Returns false if the class is full.
+    fn try_send(outbound_sender: &mut OutboundSender, message: NetworkMessage) -> bool {
+        match outbound_sender.send(message).now_or_never() {
+            Some(result) => {
+                result.expect("The queue should not be dropped!");
+                true
+            },
+            None => false,
+        }
+    }
+
+    fn pop_ready(queue: &mut OutboundQueue) -> NetworkMessage {
+        match queue.pop() {
+            NextMessage::Ready(message) => message,
+            next_message => panic!("Expected a ready message, but got: {:?}", next_message),
+        }
+    }
+
+    #[test]
+    fn test_wire_priority_round_trip() {
+        for priority in OutboundPriority::all() {
+            assert_eq!(from_wire_priority(to_wire_priority(*priority)), *priority);
+        }
+        assert_eq!(from_wire_priority(Priority::MAX), OutboundPriority::High);
+    }
+
+    #[test]
+    fn test_protocol_priority_overrides() {
+        let overrides = [
+            ("StorageServiceRpc".to_string(), OutboundPriority::High),
+            ("UnknownProtocol".to_string(), OutboundPriority::Low),
+        ]
+        .into_iter()
+        .collect();
+        let protocol_priorities = ProtocolPriorities::new(&overrides);
+
+        assert_eq!(
+            protocol_priorities.get(ProtocolId::StorageServiceRpc),
+            OutboundPriority::High
+        );
+        assert_eq!(
+            protocol_priorities.get(ProtocolId::StateSyncDirectSend),
+            OutboundPriority::Low
+        );
+        assert_eq!(
+            protocol_priorities.get(ProtocolId::ConsensusDirectSendCompressed),
+            OutboundPriority::High
+        );
+    }
+
+    fn create_queue(
+        outbound_traffic_config: &OutboundTrafficConfig,
+        max_queued_messages_per_class: usize,
+        time_service: TimeService,
+    ) -> (OutboundSender, OutboundQueue) {
+        OutboundQueue::new(
+            NetworkContext::mock(),
+            PeerId::random(),
+            outbound_traffic_config,
+            max_queued_messages_per_class,
+            time_service,
+        )
+    }
+
+    fn bandwidth_limited_config() -> OutboundTrafficConfig {
+        OutboundTrafficConfig {
+            enable_prioritization: true,
+            peer_bandwidth_limit: Some(RateLimitConfig {
+                ip_byte_bucket_rate: 1000,
+                ip_byte_bucket_size: 1000,
+                initial_bucket_fill_percentage: 100,
+                enabled: true,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_prioritized_queue() {
+        let outbound_traffic_config = OutboundTrafficConfig {
+            enable_prioritization: true,
+            ..Default::default()
+        };
+        let (mut sender, mut queue) =
+            create_queue(&outbound_traffic_config, 10, TimeService::mock());
+
+        // Send messages of increasing priority
+        let storage_message = direct_send(ProtocolId::StorageServiceRpc, vec![0; 100]);
+        let mempool_message = direct_send(ProtocolId::MempoolDirectSend, vec![1]);
+        let consensus_message = direct_send(ProtocolId::ConsensusDirectSendBcs, vec![2]);
+        let error_message = NetworkMessage::Error(ErrorCode::parsing_error(0, 0));
+        for message in [
+            storage_message.clone(),
+            mempool_message.clone(),
+            consensus_message.clone(),
+            error_message.clone(),
+        ] {
+            assert!(try_send(&mut sender, message));
+        }
+
+        // Verify the messages are dequeued in priority order (and FIFO within a class)
+        assert_eq!(pop_ready(&mut queue), consensus_message);
+        assert_eq!(pop_ready(&mut queue), error_message);
+        assert_eq!(pop_ready(&mut queue), mempool_message);
+        assert_eq!(pop_ready(&mut queue), storage_message);
+        assert!(matches!(queue.pop(), NextMessage::Empty));
+    }
+
+    #[test]
+    fn test_fifo_queue() {
+        let outbound_traffic_config = OutboundTrafficConfig {
+            enable_prioritization: false,
+            ..Default::default()
+        };
+        let (mut sender, mut queue) =
+            create_queue(&outbound_traffic_config, 10, TimeService::mock());
+
+        // Send messages of different priorities
+        let storage_message = direct_send(ProtocolId::StorageServiceRpc, vec![0]);
+        let mempool_message = direct_send(ProtocolId::MempoolDirectSend, vec![1]);
+        let consensus_message = direct_send(ProtocolId::ConsensusRpcBcs, vec![2]);
+        for message in [
+            storage_message.clone(),
+            mempool_message.clone(),
+            consensus_message.clone(),
+        ] {
+            assert!(try_send(&mut sender, message));
+        }
+
+        // Verify the messages are dequeued in FIFO order
+        assert_eq!(pop_ready(&mut queue), storage_message);
+        assert_eq!(pop_ready(&mut queue), mempool_message);
+        assert_eq!(pop_ready(&mut queue), consensus_message);
+        assert!(matches!(queue.pop(), NextMessage::Empty));
+    }
+
+    #[test]
+    fn test_bounded_priority_classes() {
+        let time_service = TimeService::mock();
+        let (mut sender, mut queue) =
+            create_queue(&bandwidth_limited_config(), 2, time_service.clone());
+
+        // Exhaust the bandwidth with a low priority message
+        let storage_message = direct_send(ProtocolId::StorageServiceRpc, vec![0; 1000]);
+        assert!(try_send(&mut sender, storage_message.clone()));
+        assert_eq!(pop_ready(&mut queue), storage_message);
+
+        // Send low priority messages until the class is full (i.e., the sender must wait)
+        let mut num_queued_messages = 0;
+        while try_send(&mut sender, storage_message.clone()) {
+            num_queued_messages += 1;
+            assert!(matches!(queue.pop(), NextMessage::Throttled(_)));
+            assert!(
+                num_queued_messages < 10,
+                "The low priority class should be bounded!"
+            );
+        }
+
+        // Verify other classes are not blocked by the full class
+        let mempool_message = direct_send(ProtocolId::MempoolDirectSend, vec![1]);
+        assert!(try_send(&mut sender, mempool_message.clone()));
+        let consensus_messages: Vec<_> = (0..5)
+            .map(|i| direct_send(ProtocolId::ConsensusDirectSendBcs, vec![i; 100]))
+            .collect();
+        for consensus_message in &consensus_messages {
+            assert!(try_send(&mut sender, consensus_message.clone()));
+        }
+        for consensus_message in consensus_messages {
+            assert_eq!(pop_ready(&mut queue), consensus_message);
+        }
+        assert!(matches!(queue.pop(), NextMessage::Throttled(_)));
+
+        // Verify that no messages are dropped once the bandwidth is available
+        let mock_time_service = time_service.into_mock();
+        mock_time_service.advance(Duration::from_secs(1));
+        assert_eq!(pop_ready(&mut queue), mempool_message);
+        for _ in 0..num_queued_messages {
+            mock_time_service.advance(Duration::from_secs(1));
+            assert_eq!(pop_ready(&mut queue), storage_message);
+        }
+        assert!(matches!(queue.pop(), NextMessage::Empty));
+    }
+
+    #[test]
+    fn test_bandwidth_limit() {
+        let time_service = TimeService::mock();
+        let (mut sender, mut queue) =
+            create_queue(&bandwidth_limited_config(), 10, time_service.clone());
+
+        // Send a low priority message that consumes most of the bandwidth
+        let storage_message = direct_send(ProtocolId::StorageServiceRpc, vec![0; 900]);
+        assert!(try_send(&mut sender, storage_message.clone()));
+        assert_eq!(pop_ready(&mut queue), storage_message);
+
+        // Verify the next low priority message is throttled until enough tokens are refilled
+        let response_message = NetworkMessage::RpcResponse(RpcResponse {
+            request_id: 0,
+            priority: to_wire_priority(OutboundPriority::Low),
+            raw_response: vec![0; 200],
+        });
+        assert!(try_send(&mut sender, response_message.clone()));
+        let ready_time = match queue.pop() {
+            NextMessage::Throttled(ready_time) => ready_time,
+            next_message => panic!("Expected a throttled message, but got: {:?}", next_message),
+        };
+        assert_eq!(
+            ready_time.duration_since(time_service.now()),
+            Duration::from_secs(1)
+        );
+
+        // Verify high priority messages are never throttled
+        let consensus_message = direct_send(ProtocolId::ConsensusDirectSendBcs, vec![0; 2000]);
+        assert!(try_send(&mut sender, consensus_message.clone()));
+        assert_eq!(pop_ready(&mut queue), consensus_message);
+        assert!(matches!(queue.pop(), NextMessage::Throttled(_)));
+
+        // Verify the throttled message is sent once the bandwidth is refilled
+        time_service
+            .clone()
+            .into_mock()
+            .advance(Duration::from_millis(500));
+        assert!(matches!(queue.pop(), NextMessage::Throttled(_)));
+        time_service.into_mock().advance(Duration::from_millis(500));
+        assert_eq!(pop_ready(&mut queue), response_message);
+        assert!(matches!(queue.pop(), NextMessage::Empty));
+    }
+
+    #[test]
+    fn test_per_class_queues() {
+        let time_service = TimeService::mock();
+        let outbound_traffic_config = OutboundTrafficConfig {
+            enable_prioritization: false,
+            ..bandwidth_limited_config()
+        };
+        let (mut sender, mut queues) = OutboundQueue::new_per_class(
+            NetworkContext::mock(),
+            PeerId::random(),
+            &outbound_traffic_config,
+            10,
+            time_service.clone(),
+        );
+        assert_eq!(queues.len(), OutboundPriority::all().len());
+
+        // Send messages of each class
+        let storage_message = direct_send(ProtocolId::StorageServiceRpc, vec![0; 1000]);
+        let mempool_message = direct_send(ProtocolId::MempoolDirectSend, vec![1; 100]);
+        let consensus_message = direct_send(ProtocolId::ConsensusDirectSendBcs, vec![2; 100]);
+        for message in [
+            storage_message.clone(),
+            mempool_message.clone(),
+            consensus_message.clone(),
+        ] {
+            assert!(try_send(&mut sender, message));
+        }
+
+        // Verify each message is received by the queue of its class
+        assert_eq!(pop_ready(&mut queues[2]), storage_message);
+        assert_eq!(pop_ready(&mut queues[0]), consensus_message);
+
+        // Verify the queues share the bandwidth limit
+        assert!(matches!(queues[1].pop(), NextMessage::Throttled(_)));
+        time_service.into_mock().advance(Duration::from_secs(1));
+        assert_eq!(pop_ready(&mut queues[1]), mempool_message);
+        for queue in queues.iter_mut() {
+            assert!(matches!(queue.pop(), NextMessage::Empty));
+        }
+    }
+
+    #[test]
+    fn test_fifo_high_priority_not_throttled() {
+        let outbound_traffic_config = OutboundTrafficConfig {
+            enable_prioritization: false,
+            ..bandwidth_limited_config()
+        };
+        let (mut sender, mut queue) =
+            create_queue(&outbound_traffic_config, 10, TimeService::mock());
+
+        // Exhaust the bandwidth and queue a throttled message
+        let storage_message = direct_send(ProtocolId::StorageServiceRpc, vec![0; 1000]);
+        assert!(try_send(&mut sender, storage_message.clone()));
+        assert!(try_send(&mut sender, storage_message.clone()));
+        assert_eq!(pop_ready(&mut queue), storage_message);
+
+        // Verify a high priority message is not stuck behind the throttled message
+        let consensus_message = direct_send(ProtocolId::ConsensusDirectSendBcs, vec![1]);
+        assert!(try_send(&mut sender, consensus_message.clone()));
+        assert_eq!(pop_ready(&mut queue), consensus_message);
+        assert!(matches!(queue.pop(), NextMessage::Throttled(_)));
+    }
+}
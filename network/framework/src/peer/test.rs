@@ -7,7 +7,7 @@ use crate::{
         INBOUND_RPC_TIMEOUT_MS, MAX_CONCURRENT_INBOUND_RPCS, MAX_CONCURRENT_OUTBOUND_RPCS,
         MAX_FRAME_SIZE, MAX_MESSAGE_SIZE, NETWORK_CHANNEL_SIZE,
     },
-    peer::{DisconnectReason, Peer, PeerNotification, PeerRequest},
+    peer::{priority::ProtocolPriorities, DisconnectReason, Peer, PeerNotification, PeerRequest},
     peer_manager::TransportNotification,
     protocols::{
         direct_send::Message,
@@ -16,7 +16,7 @@ use crate::{
             handshake::v1::{MessagingProtocolVersion, ProtocolIdSet},
             messaging::v1::{
                 DirectSendMsg, MultiplexMessage, MultiplexMessageSink, MultiplexMessageStream,
-                NetworkMessage, RpcRequest, RpcResponse,
+                NetworkMessage, Priority, RpcRequest, RpcResponse,
             },
         },
     },
@@ -24,7 +24,10 @@ use crate::{
     ProtocolId,
 };
 use aptos_channels::{self, aptos_channel, message_queues::QueueStyle};
-use aptos_config::{config::PeerRole, network_id::NetworkContext};
+use aptos_config::{
+    config::{OutboundTrafficConfig, PeerRole},
+    network_id::NetworkContext,
+};
 use aptos_memsocket::MemorySocket;
 use aptos_netcore::transport::ConnectionOrigin;
 use aptos_time_service::{MockTimeService, TimeService};
@@ -45,6 +48,11 @@ use tokio_util::compat::{
 
 static PROTOCOL: ProtocolId = ProtocolId::MempoolDirectSend;
 
+/// Returns the wire priority of outbound messages for the test protocol
+fn protocol_priority() -> Priority {
+    ProtocolPriorities::default().wire_priority(PROTOCOL)
+}
+
 fn build_test_peer(
     executor: Handle,
     time_service: TimeService,
@@ -69,6 +77,7 @@ fn build_test_peer(
             PeerRole::Unknown,
         ),
         socket: a,
+        extra_streams: vec![],
     };
 
     let (connection_notifs_tx, connection_notifs_rx) = aptos_channels::new_test(1);
@@ -90,6 +99,7 @@ fn build_test_peer(
         MAX_CONCURRENT_OUTBOUND_RPCS,
         MAX_FRAME_SIZE,
         MAX_MESSAGE_SIZE,
+        OutboundTrafficConfig::default(),
     );
     let peer_handle = PeerHandle(peer_reqs_tx);
 
@@ -214,7 +224,7 @@ fn peer_send_message() {
     };
     let recv_msg = MultiplexMessage::Message(NetworkMessage::DirectSendMsg(DirectSendMsg {
         protocol_id: PROTOCOL,
-        priority: 0,
+        priority: protocol_priority(),
         raw_msg: Vec::from("hello world"),
     }));
 
@@ -361,7 +371,7 @@ fn peer_recv_rpc() {
     });
     let resp_msg = MultiplexMessage::Message(NetworkMessage::RpcResponse(RpcResponse {
         request_id: 123,
-        priority: 0,
+        priority: protocol_priority(),
         raw_response: Vec::from("goodbye world"),
     }));
 
@@ -420,7 +430,7 @@ fn peer_recv_rpc_concurrent() {
     });
     let resp_msg = MultiplexMessage::Message(NetworkMessage::RpcResponse(RpcResponse {
         request_id: 123,
-        priority: 0,
+        priority: protocol_priority(),
         raw_response: Vec::from("goodbye world"),
     }));
 
@@ -610,7 +620,7 @@ fn peer_send_rpc() {
             };
 
             assert_eq!(received.protocol_id, PROTOCOL);
-            assert_eq!(received.priority, 0);
+            assert_eq!(received.priority, protocol_priority());
             assert_eq!(received.raw_request, b"hello world");
 
             assert!(
@@ -679,7 +689,7 @@ fn peer_send_rpc_concurrent() {
             };
 
             assert_eq!(received.protocol_id, PROTOCOL);
-            assert_eq!(received.priority, 0);
+            assert_eq!(received.priority, protocol_priority());
             assert_eq!(received.raw_request, b"hello world");
 
             assert!(
@@ -734,7 +744,7 @@ fn peer_send_rpc_cancel() {
         };
 
         assert_eq!(received.protocol_id, PROTOCOL);
-        assert_eq!(received.priority, 0);
+        assert_eq!(received.priority, protocol_priority());
         assert_eq!(received.raw_request, b"hello world");
 
         // Request should still be live. Ok(_) means the sender is not dropped.
@@ -795,7 +805,7 @@ fn peer_send_rpc_timeout() {
         };
 
         assert_eq!(received.protocol_id, PROTOCOL);
-        assert_eq!(received.priority, 0);
+        assert_eq!(received.priority, protocol_priority());
         assert_eq!(received.raw_request, b"hello world");
 
         // Request should still be live. Ok(_) means the sender is not dropped.
@@ -51,10 +51,13 @@ use crate::{
         RECEIVED_LABEL, REQUEST_LABEL, RESPONSE_LABEL, SENT_LABEL,
     },
     logging::NetworkSchema,
-    peer::PeerNotification,
+    peer::{
+        priority::{OutboundSender, ProtocolPriorities},
+        PeerNotification,
+    },
     protocols::{
         network::SerializedRequest,
-        wire::messaging::v1::{NetworkMessage, Priority, RequestId, RpcRequest, RpcResponse},
+        wire::messaging::v1::{NetworkMessage, RequestId, RpcRequest, RpcResponse},
     },
     ProtocolId,
 };
@@ -71,11 +74,10 @@ use error::RpcError;
 use futures::{
     channel::oneshot,
     future::{BoxFuture, FusedFuture, Future, FutureExt},
-    sink::SinkExt,
     stream::{FuturesUnordered, StreamExt},
 };
 use serde::Serialize;
-use std::{cmp::PartialEq, collections::HashMap, fmt::Debug, time::Duration};
+use std::{cmp::PartialEq, collections::HashMap, fmt::Debug, sync::Arc, time::Duration};
 
 pub mod error;
 
@@ -184,6 +186,8 @@ pub struct InboundRpcs {
     /// Only allow this many concurrent inbound rpcs at one time from this remote
     /// peer.  New inbound requests exceeding this limit will be dropped.
     max_concurrent_inbound_rpcs: u32,
+    /// The priority class of each protocol, used to prioritize outbound responses.
+    protocol_priorities: Arc<ProtocolPriorities>,
 }
 
 impl InboundRpcs {
@@ -193,6 +197,7 @@ impl InboundRpcs {
         remote_peer_id: PeerId,
         inbound_rpc_timeout: Duration,
         max_concurrent_inbound_rpcs: u32,
+        protocol_priorities: Arc<ProtocolPriorities>,
     ) -> Self {
         Self {
             network_context,
@@ -201,6 +206,7 @@ impl InboundRpcs {
             inbound_rpc_tasks: FuturesUnordered::new(),
             inbound_rpc_timeout,
             max_concurrent_inbound_rpcs,
+            protocol_priorities,
         }
     }
 
@@ -227,7 +233,9 @@ impl InboundRpcs {
 
         let protocol_id = request.protocol_id;
         let request_id = request.request_id;
-        let priority = request.priority;
+        // Responses are prioritized using the local priority of the protocol
+        // (instead of the priority requested by the remote peer).
+        let priority = self.protocol_priorities.wire_priority(protocol_id);
 
         trace!(
             NetworkSchema::new(network_context).remote_peer(&self.remote_peer_id),
@@ -326,7 +334,7 @@ impl InboundRpcs {
     /// the outbound write queue.
     pub async fn send_outbound_response(
         &mut self,
-        write_reqs_tx: &mut aptos_channels::Sender<NetworkMessage>,
+        write_reqs_tx: &mut OutboundSender,
         maybe_response: Result<(RpcResponse, ProtocolId), RpcError>,
     ) -> Result<(), RpcError> {
         let network_context = &self.network_context;
@@ -412,6 +420,8 @@ pub struct OutboundRpcs {
     /// Only allow this many concurrent outbound rpcs at one time from this remote
     /// peer. New outbound requests exceeding this limit will be dropped.
     max_concurrent_outbound_rpcs: u32,
+    /// The priority class of each protocol, used to prioritize outbound requests.
+    protocol_priorities: Arc<ProtocolPriorities>,
 }
 
 impl OutboundRpcs {
@@ -420,6 +430,7 @@ impl OutboundRpcs {
         time_service: TimeService,
         remote_peer_id: PeerId,
         max_concurrent_outbound_rpcs: u32,
+        protocol_priorities: Arc<ProtocolPriorities>,
     ) -> Self {
         Self {
             network_context,
@@ -429,6 +440,7 @@ impl OutboundRpcs {
             outbound_rpc_tasks: FuturesUnordered::new(),
             pending_outbound_rpcs: HashMap::new(),
             max_concurrent_outbound_rpcs,
+            protocol_priorities,
         }
     }
 
@@ -436,7 +448,7 @@ impl OutboundRpcs {
     pub async fn handle_outbound_request(
         &mut self,
         request: OutboundRpcRequest,
-        write_reqs_tx: &mut aptos_channels::Sender<NetworkMessage>,
+        write_reqs_tx: &mut OutboundSender,
     ) -> Result<(), RpcError> {
         let network_context = &self.network_context;
         let peer_id = &self.remote_peer_id;
@@ -496,7 +508,7 @@ impl OutboundRpcs {
         let message = NetworkMessage::RpcRequest(RpcRequest {
             protocol_id,
             request_id,
-            priority: Priority::default(),
+            priority: self.protocol_priorities.wire_priority(protocol_id),
             raw_request: Vec::from(request_data.as_ref()),
         });
         write_reqs_tx.send(message).await?;
@@ -15,7 +15,7 @@
 
 use crate::counters::{start_serialization_timer, DESERIALIZATION_LABEL, SERIALIZATION_LABEL};
 use anyhow::anyhow;
-use aptos_compression::client::CompressionClient;
+use aptos_compression::{client::CompressionClient, CompressionAlgorithm};
 use aptos_config::{config::MAX_APPLICATION_MESSAGE_SIZE, network_id::NetworkId};
 use aptos_types::chain_id::ChainId;
 #[cfg(any(test, feature = "fuzzing"))]
@@ -72,12 +72,16 @@ pub enum ProtocolId {
     JWKConsensusRpcBcs = 25,
     JWKConsensusRpcJson = 26,
     ConsensusObserver = 27,
+    MempoolDirectSendCompressed = 28,
+    NetbenchDirectSendCompressed = 29,
+    NetbenchRpcCompressed = 30,
+    MempoolDirectSendDictionaryCompressed = 31, // Requires the mempool dictionary
 }
 
 /// The encoding types for Protocols
 enum Encoding {
     Bcs(usize),
-    CompressedBcs(usize),
+    CompressedBcs(usize, CompressionAlgorithm),
     Json,
 }
 
@@ -113,6 +117,10 @@ impl ProtocolId {
             JWKConsensusRpcBcs => "JWKConsensusRpcBcs",
             JWKConsensusRpcJson => "JWKConsensusRpcJson",
             ConsensusObserver => "ConsensusObserver",
+            MempoolDirectSendCompressed => "MempoolDirectSendCompressed",
+            NetbenchDirectSendCompressed => "NetbenchDirectSendCompressed",
+            NetbenchRpcCompressed => "NetbenchRpcCompressed",
+            MempoolDirectSendDictionaryCompressed => "MempoolDirectSendDictionaryCompressed",
         }
     }
 
@@ -147,6 +155,10 @@ impl ProtocolId {
             ProtocolId::JWKConsensusRpcBcs,
             ProtocolId::JWKConsensusRpcJson,
             ProtocolId::ConsensusObserver,
+            ProtocolId::MempoolDirectSendCompressed,
+            ProtocolId::NetbenchDirectSendCompressed,
+            ProtocolId::NetbenchRpcCompressed,
+            ProtocolId::MempoolDirectSendDictionaryCompressed,
         ]
     }
 
@@ -155,15 +167,29 @@ impl ProtocolId {
         match self {
             ProtocolId::ConsensusDirectSendJson | ProtocolId::ConsensusRpcJson => Encoding::Json,
             ProtocolId::ConsensusDirectSendCompressed | ProtocolId::ConsensusRpcCompressed => {
-                Encoding::CompressedBcs(RECURSION_LIMIT)
+                Encoding::CompressedBcs(RECURSION_LIMIT, CompressionAlgorithm::Lz4)
             },
             ProtocolId::DKGDirectSendCompressed | ProtocolId::DKGRpcCompressed => {
-                Encoding::CompressedBcs(RECURSION_LIMIT)
+                Encoding::CompressedBcs(RECURSION_LIMIT, CompressionAlgorithm::Lz4)
             },
             ProtocolId::JWKConsensusDirectSendCompressed
-            | ProtocolId::JWKConsensusRpcCompressed => Encoding::CompressedBcs(RECURSION_LIMIT),
-            ProtocolId::MempoolDirectSend => Encoding::CompressedBcs(USER_INPUT_RECURSION_LIMIT),
+            | ProtocolId::JWKConsensusRpcCompressed => {
+                Encoding::CompressedBcs(RECURSION_LIMIT, CompressionAlgorithm::Lz4)
+            },
+            ProtocolId::MempoolDirectSend => {
+                Encoding::CompressedBcs(USER_INPUT_RECURSION_LIMIT, CompressionAlgorithm::Lz4)
+            },
+            ProtocolId::MempoolDirectSendCompressed => {
+                Encoding::CompressedBcs(USER_INPUT_RECURSION_LIMIT, CompressionAlgorithm::Zstd)
+            },
+            ProtocolId::MempoolDirectSendDictionaryCompressed => Encoding::CompressedBcs(
+                USER_INPUT_RECURSION_LIMIT,
+                CompressionAlgorithm::ZstdWithDictionary,
+            ),
             ProtocolId::MempoolRpc => Encoding::Bcs(USER_INPUT_RECURSION_LIMIT),
+            ProtocolId::NetbenchDirectSendCompressed | ProtocolId::NetbenchRpcCompressed => {
+                Encoding::CompressedBcs(RECURSION_LIMIT, CompressionAlgorithm::Zstd)
+            },
             _ => Encoding::Bcs(RECURSION_LIMIT),
         }
     }
@@ -174,7 +200,12 @@ impl ProtocolId {
             ProtocolId::ConsensusDirectSendCompressed | ProtocolId::ConsensusRpcCompressed => {
                 CompressionClient::Consensus
             },
-            ProtocolId::MempoolDirectSend => CompressionClient::Mempool,
+            ProtocolId::MempoolDirectSend
+            | ProtocolId::MempoolDirectSendCompressed
+            | ProtocolId::MempoolDirectSendDictionaryCompressed => CompressionClient::Mempool,
+            ProtocolId::NetbenchDirectSendCompressed | ProtocolId::NetbenchRpcCompressed => {
+                CompressionClient::Netbench
+            },
             ProtocolId::DKGDirectSendCompressed | ProtocolId::DKGRpcCompressed => {
                 CompressionClient::DKG
             },
@@ -196,11 +227,12 @@ impl ProtocolId {
         // Serialize the message
         let result = match self.encoding() {
             Encoding::Bcs(limit) => self.bcs_encode(value, limit),
-            Encoding::CompressedBcs(limit) => {
+            Encoding::CompressedBcs(limit, compression_algorithm) => {
                 let compression_client = self.get_compression_client();
                 let bcs_bytes = self.bcs_encode(value, limit)?;
-                aptos_compression::compress(
+                aptos_compression::compress_with_algorithm(
                     bcs_bytes,
+                    compression_algorithm,
                     compression_client,
                     MAX_APPLICATION_MESSAGE_SIZE,
                 )
@@ -226,10 +258,11 @@ impl ProtocolId {
         // Deserialize the message
         let result = match self.encoding() {
             Encoding::Bcs(limit) => self.bcs_decode(bytes, limit),
-            Encoding::CompressedBcs(limit) => {
+            Encoding::CompressedBcs(limit, compression_algorithm) => {
                 let compression_client = self.get_compression_client();
-                let raw_bytes = aptos_compression::decompress(
+                let raw_bytes = aptos_compression::decompress_with_algorithm(
                     &bytes.to_vec(),
+                    compression_algorithm,
                     compression_client,
                     MAX_APPLICATION_MESSAGE_SIZE,
                 )
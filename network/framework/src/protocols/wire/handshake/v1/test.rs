@@ -183,3 +183,34 @@ fn ignore_unknown_protocols() {
         ProtocolIdSet::empty(),
     );
 }
+
+#[test]
+fn compressed_protocols_round_trip() {
+    // Create a test message
+    let message: Vec<u64> = (0..10_000).map(|value| value % 7).collect();
+
+    // Verify that the message can be serialized and deserialized for all
+    // compressed protocols (including both lz4 and zstd encodings).
+    for protocol in [
+        ProtocolId::MempoolDirectSend,
+        ProtocolId::MempoolDirectSendCompressed,
+        ProtocolId::NetbenchRpcCompressed,
+    ] {
+        let bytes = protocol.to_bytes(&message).unwrap();
+        assert!(bytes.len() < bcs::to_bytes(&message).unwrap().len());
+        assert_eq!(protocol.from_bytes::<Vec<u64>>(&bytes).unwrap(), message);
+    }
+
+    // Verify that zstd encoded messages cannot be decoded as lz4
+    let bytes = ProtocolId::MempoolDirectSendCompressed
+        .to_bytes(&message)
+        .unwrap();
+    assert!(ProtocolId::MempoolDirectSend
+        .from_bytes::<Vec<u64>>(&bytes)
+        .is_err());
+
+    // Verify that the dictionary protocol can't be used without the dictionary
+    assert!(ProtocolId::MempoolDirectSendDictionaryCompressed
+        .to_bytes(&message)
+        .is_err());
+}
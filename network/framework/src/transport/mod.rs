@@ -11,25 +11,31 @@ use crate::{
     },
 };
 use aptos_config::{
-    config::{PeerRole, HANDSHAKE_VERSION},
+    config::{OutboundPriority, PeerRole, HANDSHAKE_VERSION},
     network_id::{NetworkContext, NetworkId},
 };
-use aptos_crypto::x25519;
+use aptos_crypto::{x25519, HashValue};
 use aptos_id_generator::{IdGenerator, U32IdGenerator};
 use aptos_logger::prelude::*;
 // Re-exposed for aptos-network-checker
 pub use aptos_netcore::transport::tcp::{resolve_and_connect, TCPBufferCfg, TcpSocket};
-use aptos_netcore::transport::{proxy_protocol, tcp, ConnectionOrigin, Transport};
+use aptos_netcore::transport::{
+    proxy_protocol,
+    quic::{self, QuicStream, QuicStreamHandle},
+    tcp, ConnectionOrigin, MultiStreamSocket, Transport,
+};
 use aptos_short_hex_str::AsShortHexStr;
 use aptos_time_service::{timeout, TimeService, TimeServiceTrait};
 use aptos_types::{
     chain_id::ChainId,
-    network_address::{parse_dns_tcp, parse_ip_tcp, parse_memory, NetworkAddress},
+    network_address::{
+        parse_dns_tcp, parse_dns_udp, parse_ip_tcp, parse_ip_udp, parse_memory, NetworkAddress,
+    },
     PeerId,
 };
 use futures::{
     future::{Future, FutureExt},
-    io::{AsyncRead, AsyncWrite},
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
     stream::{Stream, StreamExt, TryStreamExt},
 };
 use serde::{Deserialize, Serialize};
@@ -45,6 +51,9 @@ pub const TRANSPORT_TIMEOUT: Duration = Duration::from_secs(30);
 /// TODO: Add ability to support more than one messaging protocol.
 pub const SUPPORTED_MESSAGING_PROTOCOL: MessagingProtocolVersion = MessagingProtocolVersion::V1;
 
+/// The label used to derive the tokens that bind extra streams to the Noise session
+const STREAM_BINDING_LABEL: &[u8] = b"APTOSNET_STREAM_BINDING";
+
 /// Global connection-id generator.
 static CONNECTION_ID_GENERATOR: ConnectionIdGenerator = ConnectionIdGenerator::new();
 
@@ -58,6 +67,17 @@ pub const APTOS_TCP_TRANSPORT: tcp::TcpTransport = tcp::TcpTransport {
     tcp_buff_cfg: tcp::TCPBufferCfg::new(),
 };
 
+/// Returns a quic::Transport with Aptos-specific configuration applied. Each
+/// transport dials from its own client endpoint, shared by all its connections.
+pub fn aptos_quic_transport() -> quic::QuicTransport {
+    quic::QuicTransport::new(
+        // Send keep-alives to avoid idle timeouts on quiet connections.
+        Some(Duration::from_secs(10)),
+        // Use the default idle timeout.
+        None,
+    )
+}
+
 /// A trait alias for "socket-like" things.
 pub trait TSocket: AsyncRead + AsyncWrite + Send + fmt::Debug + Unpin + 'static {}
 
@@ -189,6 +209,96 @@ impl fmt::Display for ConnectionMetadata {
 pub struct Connection<TSocket> {
     pub socket: TSocket,
     pub metadata: ConnectionMetadata,
+    /// The extra streams of the connection (if supported by the transport, i.e., QUIC),
+    /// one for each of the `extra_stream_priorities`. Otherwise, this is empty.
+    pub extra_streams: Vec<QuicStream>,
+}
+
+/// Returns the priority classes that are written on extra streams (if the connection
+/// supports them), ordered from highest to lowest priority. High priority messages are
+/// always written on the socket (i.e., the primary stream).
+pub fn extra_stream_priorities() -> &'static [OutboundPriority] {
+    &OutboundPriority::all()[1..]
+}
+
+/// Returns the token that binds the extra stream of the given priority class to the
+/// Noise session. The handshake hash is bound to the TLS session of the connection
+/// (see `channel_binding`), so the token cannot be derived by anyone else.
+fn stream_binding_token(handshake_hash: &[u8], priority: OutboundPriority) -> HashValue {
+    HashValue::sha3_256_of(
+        &[
+            STREAM_BINDING_LABEL,
+            handshake_hash,
+            priority.as_str().as_bytes(),
+        ]
+        .concat(),
+    )
+}
+
+/// Returns the channel binding for the Noise handshake, i.e., the keying material
+/// exported from the TLS session (if the connection supports extra streams).
+fn channel_binding(stream_handle: Option<&QuicStreamHandle>) -> io::Result<Vec<u8>> {
+    match stream_handle {
+        Some(stream_handle) => Ok(stream_handle.export_channel_binding()?.to_vec()),
+        None => Ok(vec![]),
+    }
+}
+
+/// Opens the extra streams of the connection (one per extra stream priority class), and
+/// authenticates each stream by writing its binding token. Note: the streams are opened
+/// by the dialer, after the Noise handshake completes.
+async fn open_extra_streams(
+    stream_handle: &QuicStreamHandle,
+    handshake_hash: &[u8],
+) -> io::Result<Vec<QuicStream>> {
+    let mut extra_streams = vec![];
+    for priority in extra_stream_priorities() {
+        let mut stream = stream_handle.open_stream().await?;
+        stream
+            .write_all(stream_binding_token(handshake_hash, *priority).as_ref())
+            .await?;
+        stream.flush().await?;
+        extra_streams.push(stream);
+    }
+    Ok(extra_streams)
+}
+
+/// Accepts the extra streams opened by the dialer, and orders them by priority class.
+/// Streams without a valid binding token for the Noise session are rejected.
+async fn accept_extra_streams(
+    stream_handle: &QuicStreamHandle,
+    handshake_hash: &[u8],
+) -> io::Result<Vec<QuicStream>> {
+    let priorities = extra_stream_priorities();
+    let mut extra_streams: Vec<Option<QuicStream>> = priorities.iter().map(|_| None).collect();
+    for _ in priorities {
+        let mut stream = stream_handle.accept_stream().await?;
+        let mut token = [0u8; HashValue::LENGTH];
+        stream.read_exact(&mut token).await?;
+
+        // Identify the priority class of the stream using the token
+        let stream_index = priorities
+            .iter()
+            .position(|priority| {
+                stream_binding_token(handshake_hash, *priority).as_ref() == token.as_slice()
+            })
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "extra stream is not bound to the noise session",
+                )
+            })?;
+        if extra_streams[stream_index].replace(stream).is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "duplicate extra stream for priority class: {}",
+                    priorities[stream_index]
+                ),
+            ));
+        }
+    }
+    Ok(extra_streams.into_iter().flatten().collect())
 }
 
 /// Convenience function for adding a timeout to a Future that returns an `io::Result`.
@@ -247,7 +357,7 @@ fn add_pp_addr(proxy_protocol_enabled: bool, error: io::Error, addr: &NetworkAdd
 /// `ctxt.noise.auth_mode` is `HandshakeAuthMode::Mutual( anti_replay_timestamps , trusted_peers )`,
 /// then we will only allow connections from peers with a pubkey in the `trusted_peers`
 /// set. Otherwise, we will allow inbound connections from any pubkey.
-async fn upgrade_inbound<T: TSocket>(
+async fn upgrade_inbound<T: TSocket + MultiStreamSocket>(
     ctxt: Arc<UpgradeContext>,
     fut_socket: impl Future<Output = io::Result<T>>,
     addr: NetworkAddress,
@@ -274,9 +384,14 @@ async fn upgrade_inbound<T: TSocket>(
         addr
     };
 
-    // try authenticating via noise handshake
-    let (mut socket, remote_peer_id, peer_role) =
-        ctxt.noise.upgrade_inbound(socket).await.map_err(|err| {
+    // try authenticating via noise handshake (bound to the connection, if supported)
+    let stream_handle = socket.stream_handle();
+    let channel_binding = channel_binding(stream_handle.as_ref())?;
+    let (mut socket, remote_peer_id, peer_role) = ctxt
+        .noise
+        .upgrade_inbound_with_channel_binding(socket, &channel_binding)
+        .await
+        .map_err(|err| {
             if err.should_security_log() {
                 sample!(
                     SampleRate::Duration(Duration::from_secs(15)),
@@ -321,9 +436,18 @@ async fn upgrade_inbound<T: TSocket>(
             )
         })?;
 
+    // accept the extra streams opened by the dialer (if supported)
+    let extra_streams = match stream_handle {
+        Some(stream_handle) => accept_extra_streams(&stream_handle, socket.handshake_hash())
+            .await
+            .map_err(|err| add_pp_addr(proxy_protocol_enabled, err, &addr))?,
+        None => vec![],
+    };
+
     // return successful connection
     Ok(Connection {
         socket,
+        extra_streams,
         metadata: ConnectionMetadata::new(
             remote_peer_id,
             CONNECTION_ID_GENERATOR.next(),
@@ -338,7 +462,7 @@ async fn upgrade_inbound<T: TSocket>(
 
 /// Upgrade an outbound connection. This means we run a Noise IK handshake for
 /// authentication and then negotiate common supported protocols.
-pub async fn upgrade_outbound<T: TSocket>(
+pub async fn upgrade_outbound<T: TSocket + MultiStreamSocket>(
     ctxt: Arc<UpgradeContext>,
     fut_socket: impl Future<Output = io::Result<T>>,
     addr: NetworkAddress,
@@ -348,14 +472,17 @@ pub async fn upgrade_outbound<T: TSocket>(
     let origin = ConnectionOrigin::Outbound;
     let socket = fut_socket.await?;
 
-    // noise handshake
+    // noise handshake (bound to the connection, if supported)
+    let stream_handle = socket.stream_handle();
+    let channel_binding = channel_binding(stream_handle.as_ref())?;
     let (mut socket, peer_role) = ctxt
         .noise
-        .upgrade_outbound(
+        .upgrade_outbound_with_channel_binding(
             socket,
             remote_peer_id,
             remote_pubkey,
             AntiReplayTimestamps::now,
+            &channel_binding,
         )
         .await
         .map_err(|err| {
@@ -396,9 +523,16 @@ pub async fn upgrade_outbound<T: TSocket>(
             io::Error::new(io::ErrorKind::Other, e)
         })?;
 
+    // open the extra streams (if supported)
+    let extra_streams = match stream_handle {
+        Some(stream_handle) => open_extra_streams(&stream_handle, socket.handshake_hash()).await?,
+        None => vec![],
+    };
+
     // return successful connection
     Ok(Connection {
         socket,
+        extra_streams,
         metadata: ConnectionMetadata::new(
             remote_peer_id,
             CONNECTION_ID_GENERATOR.next(),
@@ -415,7 +549,7 @@ pub async fn upgrade_outbound<T: TSocket>(
 ///
 /// The base transport layer is pluggable, so long as it provides a reliable,
 /// ordered, connection-oriented, byte-stream abstraction (e.g., TCP). We currently
-/// use either `MemoryTransport` or `TcpTransport` as this base layer.
+/// use either `MemoryTransport`, `TcpTransport` or `QuicTransport` as this base layer.
 ///
 /// Inbound and outbound connections are first established with the `base_transport`
 /// and then negotiate a secure, authenticated transport layer (currently Noise
@@ -434,7 +568,7 @@ pub struct AptosNetTransport<TTransport> {
 impl<TTransport> AptosNetTransport<TTransport>
 where
     TTransport: Transport<Error = io::Error>,
-    TTransport::Output: TSocket,
+    TTransport::Output: TSocket + MultiStreamSocket,
     TTransport::Outbound: Send + 'static,
     TTransport::Inbound: Send + 'static,
     TTransport::Listener: Send + 'static,
@@ -487,13 +621,15 @@ where
         let (base_transport_protos, base_transport_suffix) = parse_ip_tcp(protos)
             .map(|x| (&protos[..2], x.1))
             .or_else(|| parse_dns_tcp(protos).map(|x| (&protos[..2], x.1)))
+            .or_else(|| parse_ip_udp(protos).map(|x| (&protos[..2], x.1)))
+            .or_else(|| parse_dns_udp(protos).map(|x| (&protos[..2], x.1)))
             .or_else(|| parse_memory(protos).map(|x| (&protos[..1], x.1)))
             .ok_or_else(|| {
                 io::Error::new(
                     io::ErrorKind::InvalidInput,
                     format!(
                         "Unexpected dialing network address: '{}', expected: \
-                         memory, ip+tcp, or dns+tcp",
+                         memory, ip+tcp, dns+tcp, ip+udp or dns+udp",
                         addr
                     ),
                 )
@@ -539,6 +675,9 @@ where
     /// `/dns/<ipaddr>/tcp/<port>` or
     /// `/dns4/<ipaddr>/tcp/<port>` or
     /// `/dns6/<ipaddr>/tcp/<port>`
+    ///
+    /// If the base transport is `QuicTransport`, then `/<base_transport>` is
+    /// the same as for `TcpTransport`, but with `/udp/<port>` instead of `/tcp/<port>`.
     pub fn dial(
         &self,
         peer_id: PeerId,
@@ -585,6 +724,11 @@ where
     ///
     /// `/ip4/<ipaddr>/tcp/<port>` or
     /// `/ip6/<ipaddr>/tcp/<port>`
+    ///
+    /// If the base transport is `QuicTransport`, then we expect:
+    ///
+    /// `/ip4/<ipaddr>/udp/<port>` or
+    /// `/ip6/<ipaddr>/udp/<port>`
     pub fn listen_on(
         &self,
         addr: NetworkAddress,
@@ -641,7 +785,7 @@ where
 impl<TTransport: Transport> Transport for AptosNetTransport<TTransport>
 where
     TTransport: Transport<Error = io::Error> + Send + 'static,
-    TTransport::Output: TSocket,
+    TTransport::Output: TSocket + MultiStreamSocket,
     TTransport::Outbound: Send + 'static,
     TTransport::Inbound: Send + 'static,
     TTransport::Listener: Send + 'static,
@@ -4,15 +4,31 @@
 
 use crate::{
     application::storage::PeersAndMetadata,
-    protocols::wire::handshake::v1::{MessagingProtocolVersion, ProtocolId, ProtocolIdSet},
+    constants::{
+        INBOUND_RPC_TIMEOUT_MS, MAX_CONCURRENT_INBOUND_RPCS, MAX_CONCURRENT_OUTBOUND_RPCS,
+        MAX_FRAME_SIZE, MAX_MESSAGE_SIZE, NETWORK_CHANNEL_SIZE,
+    },
+    peer::{self, priority::ProtocolPriorities, PeerRequest},
+    protocols::{
+        direct_send::Message,
+        wire::{
+            handshake::v1::{MessagingProtocolVersion, ProtocolId, ProtocolIdSet},
+            messaging::v1::{
+                DirectSendMsg, MultiplexMessage, MultiplexMessageStream, NetworkMessage,
+            },
+        },
+    },
     testutils,
     transport::*,
 };
-use aptos_config::config::{Peer, PeerRole, PeerSet, HANDSHAKE_VERSION};
+use aptos_channels::{aptos_channel, message_queues::QueueStyle};
+use aptos_config::config::{
+    OutboundPriority, OutboundTrafficConfig, Peer, PeerRole, PeerSet, HANDSHAKE_VERSION,
+};
 use aptos_crypto::{test_utils::TEST_SEED, traits::Uniform, x25519, x25519::PrivateKey};
 use aptos_netcore::{
     framing::{read_u16frame, write_u16frame},
-    transport::{memory, ConnectionOrigin, Transport},
+    transport::{memory, ConnectionOrigin, MultiStreamSocket, Transport},
 };
 use aptos_time_service::MockTimeService;
 use aptos_types::{
@@ -22,9 +38,13 @@ use aptos_types::{
     PeerId,
 };
 use bytes::{Bytes, BytesMut};
-use futures::{future, io::AsyncWriteExt, stream::StreamExt};
+use futures::{
+    future,
+    io::{AsyncReadExt, AsyncWriteExt},
+    stream::StreamExt,
+};
 use rand::{rngs::StdRng, SeedableRng};
-use std::{io, iter::FromIterator, sync::Arc};
+use std::{io, iter::FromIterator, sync::Arc, time::Duration};
 use tokio::runtime::Runtime;
 
 /// Helper to add the trusted peer to the set
@@ -58,7 +78,7 @@ fn setup<TTransport>(
 )
 where
     TTransport: Transport<Error = io::Error> + Clone,
-    TTransport::Output: TSocket,
+    TTransport::Output: TSocket + MultiStreamSocket,
     TTransport::Outbound: Send + 'static,
     TTransport::Inbound: Send + 'static,
     TTransport::Listener: Send + 'static,
@@ -223,6 +243,14 @@ fn expect_ip4_tcp_noise_addr(addr: &NetworkAddress) {
     );
 }
 
+fn expect_ip4_udp_noise_addr(addr: &NetworkAddress) {
+    assert!(
+        matches!(addr.as_slice(), [Ip4(_), Udp(_), NoiseIK(_), Handshake(_)]),
+        "addr: '{}'",
+        addr
+    );
+}
+
 fn test_transport_success<TTransport>(
     base_transport: TTransport,
     auth: Auth,
@@ -230,7 +258,7 @@ fn test_transport_success<TTransport>(
     expect_formatted_addr: fn(&NetworkAddress),
 ) where
     TTransport: Transport<Error = io::Error> + Clone,
-    TTransport::Output: TSocket,
+    TTransport::Output: TSocket + MultiStreamSocket,
     TTransport::Outbound: Send + 'static,
     TTransport::Inbound: Send + 'static,
     TTransport::Listener: Send + 'static,
@@ -313,7 +341,7 @@ fn test_transport_rejects_unauthed_dialer<TTransport>(
     expect_formatted_addr: fn(&NetworkAddress),
 ) where
     TTransport: Transport<Error = io::Error> + Clone,
-    TTransport::Output: TSocket,
+    TTransport::Output: TSocket + MultiStreamSocket,
     TTransport::Outbound: Send + 'static,
     TTransport::Inbound: Send + 'static,
     TTransport::Listener: Send + 'static,
@@ -372,7 +400,7 @@ fn test_transport_maybe_mutual<TTransport>(
     expect_formatted_addr: fn(&NetworkAddress),
 ) where
     TTransport: Transport<Error = io::Error> + Clone,
-    TTransport::Output: TSocket,
+    TTransport::Output: TSocket + MultiStreamSocket,
     TTransport::Outbound: Send + 'static,
     TTransport::Inbound: Send + 'static,
     TTransport::Listener: Send + 'static,
@@ -587,6 +615,194 @@ fn test_tcp_transport_rejects_unauthed_dialer() {
     );
 }
 
+//////////////////////////////////////
+// AptosNetTransport<QuicTransport> //
+//////////////////////////////////////
+
+#[test]
+fn test_quic_transport_mutual_auth() {
+    test_transport_success(
+        aptos_quic_transport(),
+        Auth::Mutual,
+        "/ip4/127.0.0.1/udp/0",
+        expect_ip4_udp_noise_addr,
+    );
+}
+
+#[test]
+fn test_quic_transport_server_only_auth() {
+    test_transport_success(
+        aptos_quic_transport(),
+        Auth::ServerOnly,
+        "/ip4/127.0.0.1/udp/0",
+        expect_ip4_udp_noise_addr,
+    );
+}
+
+#[test]
+fn test_quic_transport_rejects_unauthed_dialer() {
+    test_transport_rejects_unauthed_dialer(
+        aptos_quic_transport(),
+        "/ip4/127.0.0.1/udp/0",
+        expect_ip4_udp_noise_addr,
+    );
+}
+
+/// Establishes a QUIC connection between two peers, and returns the runtime, the
+/// dialer's (i.e., outbound) connection and the listener's (i.e., inbound) connection.
+fn connect_quic_peers() -> (
+    Runtime,
+    Connection<NoiseStream<quic::QuicSocket>>,
+    Connection<NoiseStream<quic::QuicSocket>>,
+) {
+    let (rt, _mock_time, (listener_peer_id, listener_transport), (_, dialer_transport), _, _) =
+        setup(aptos_quic_transport(), Auth::Mutual);
+
+    let (outbound_connection, inbound_connection) = {
+        let _guard = rt.enter();
+        let (mut inbounds, listener_addr) = listener_transport
+            .listen_on("/ip4/127.0.0.1/udp/0".parse().unwrap())
+            .unwrap();
+        let listener_task = async move {
+            let (inbound, _dialer_addr) = inbounds.next().await.unwrap().unwrap();
+            inbound.await.unwrap()
+        };
+        let dialer_task = async move {
+            dialer_transport
+                .dial(listener_peer_id, listener_addr)
+                .unwrap()
+                .await
+                .unwrap()
+        };
+        let (inbound_connection, outbound_connection) =
+            rt.block_on(future::join(listener_task, dialer_task));
+        (outbound_connection, inbound_connection)
+    };
+
+    (rt, outbound_connection, inbound_connection)
+}
+
+#[test]
+fn test_quic_transport_extra_streams() {
+    let (rt, mut outbound_connection, mut inbound_connection) = connect_quic_peers();
+
+    // Both peers have an extra stream for each extra stream priority class
+    let num_extra_streams = extra_stream_priorities().len();
+    assert_eq!(outbound_connection.extra_streams.len(), num_extra_streams);
+    assert_eq!(inbound_connection.extra_streams.len(), num_extra_streams);
+
+    // Verify each extra stream of the dialer is connected to the
+    // extra stream of the same priority class of the listener
+    rt.block_on(async move {
+        for (index, (outbound_stream, inbound_stream)) in outbound_connection
+            .extra_streams
+            .iter_mut()
+            .zip(inbound_connection.extra_streams.iter_mut())
+            .enumerate()
+        {
+            let message = [index as u8; 8];
+            outbound_stream.write_all(&message).await.unwrap();
+            outbound_stream.flush().await.unwrap();
+
+            let mut buf = [0; 8];
+            inbound_stream.read_exact(&mut buf).await.unwrap();
+            assert_eq!(buf, message);
+        }
+    });
+}
+
+#[test]
+fn test_quic_stalled_stream_does_not_delay_consensus() {
+    let (rt, outbound_connection, mut inbound_connection) = connect_quic_peers();
+
+    // Start a peer actor for the dialer's connection
+    let (connection_notifs_tx, _connection_notifs_rx) = aptos_channels::new_test(1);
+    let (mut peer_reqs_tx, peer_reqs_rx) =
+        aptos_channel::new(QueueStyle::FIFO, NETWORK_CHANNEL_SIZE, None);
+    let (peer_notifs_tx, _peer_notifs_rx) =
+        aptos_channel::new(QueueStyle::FIFO, NETWORK_CHANNEL_SIZE, None);
+    let peer = peer::Peer::new(
+        NetworkContext::mock(),
+        rt.handle().clone(),
+        TimeService::real(),
+        outbound_connection,
+        connection_notifs_tx,
+        peer_reqs_rx,
+        peer_notifs_tx,
+        Duration::from_millis(INBOUND_RPC_TIMEOUT_MS),
+        MAX_CONCURRENT_INBOUND_RPCS,
+        MAX_CONCURRENT_OUTBOUND_RPCS,
+        MAX_FRAME_SIZE,
+        MAX_MESSAGE_SIZE,
+        OutboundTrafficConfig::default(),
+    );
+    rt.spawn(peer.start());
+
+    // Send more state sync data than the listener's stream receive window. The
+    // listener never reads the low priority stream, so the stream stalls.
+    let num_state_sync_messages = 16;
+    let state_sync_data = Bytes::from(vec![7; 1024 * 1024]);
+    for _ in 0..num_state_sync_messages {
+        let message = Message {
+            protocol_id: ProtocolId::StateSyncDirectSend,
+            mdata: state_sync_data.clone(),
+        };
+        peer_reqs_tx
+            .push(message.protocol_id, PeerRequest::SendDirectSend(message))
+            .unwrap();
+    }
+
+    // Send a consensus message after the state sync messages
+    let consensus_message = Message {
+        protocol_id: ProtocolId::ConsensusDirectSendBcs,
+        mdata: Bytes::from_static(b"vote"),
+    };
+    peer_reqs_tx
+        .push(
+            consensus_message.protocol_id,
+            PeerRequest::SendDirectSend(consensus_message.clone()),
+        )
+        .unwrap();
+
+    // Verify the consensus message is received (on the primary stream)
+    // without waiting for the stalled state sync stream
+    let expected_consensus_message =
+        MultiplexMessage::Message(NetworkMessage::DirectSendMsg(DirectSendMsg {
+            protocol_id: consensus_message.protocol_id,
+            priority: ProtocolPriorities::default().wire_priority(consensus_message.protocol_id),
+            raw_msg: consensus_message.mdata.to_vec(),
+        }));
+    let mut reader = MultiplexMessageStream::new(&mut inbound_connection.socket, MAX_FRAME_SIZE);
+    let received_message = rt
+        .block_on(tokio::time::timeout(Duration::from_secs(10), reader.next()))
+        .expect("The consensus message should not be delayed by the stalled stream!")
+        .unwrap()
+        .unwrap();
+    assert_eq!(received_message, expected_consensus_message);
+
+    // Verify the state sync messages were sent on (and are still pending
+    // on) the low priority stream
+    let low_priority_index = extra_stream_priorities()
+        .iter()
+        .position(|priority| *priority == OutboundPriority::Low)
+        .unwrap();
+    let low_priority_stream = &mut inbound_connection.extra_streams[low_priority_index];
+    let mut reader = MultiplexMessageStream::new(low_priority_stream, MAX_FRAME_SIZE);
+    for _ in 0..num_state_sync_messages {
+        let received_message = rt.block_on(reader.next()).unwrap().unwrap();
+        match received_message {
+            MultiplexMessage::Message(NetworkMessage::DirectSendMsg(message)) => {
+                assert_eq!(message.protocol_id, ProtocolId::StateSyncDirectSend);
+                assert_eq!(message.raw_msg, state_sync_data.to_vec());
+            },
+            message => panic!(
+                "Unexpected message on the low priority stream: {:?}",
+                message
+            ),
+        }
+    }
+}
+
 /// Inserts the given peers into the trusted peer set for the specified network
 fn insert_trusted_peers(
     peers_and_metadata: &Arc<PeersAndMetadata>,
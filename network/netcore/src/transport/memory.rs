@@ -2,7 +2,7 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::transport::Transport;
+use crate::transport::{MultiStreamSocket, Transport};
 use aptos_memsocket::{MemoryListener, MemorySocket};
 use aptos_types::{
     network_address::{parse_memory, NetworkAddress, Protocol},
@@ -15,6 +15,8 @@ use std::{
     task::{Context, Poll},
 };
 
+impl MultiStreamSocket for MemorySocket {}
+
 /// Transport to build in-memory connections
 #[derive(Clone, Debug, Default)]
 pub struct MemoryTransport;
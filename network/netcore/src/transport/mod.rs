@@ -22,6 +22,7 @@ pub mod boxed;
 #[cfg(any(test, feature = "testing", feature = "fuzzing"))]
 pub mod memory;
 pub mod proxy_protocol;
+pub mod quic;
 pub mod tcp;
 
 /// Origin of how a Connection was established.
@@ -115,6 +116,17 @@ pub trait Transport {
         Self: Sized;
 }
 
+/// A connection that may support additional streams to the remote peer (i.e.,
+/// QUIC connections). Data on each stream is delivered independently, so a
+/// stalled stream does not delay the others (i.e., no head-of-line blocking).
+pub trait MultiStreamSocket {
+    /// Returns the handle to open and accept additional streams on the
+    /// connection, or `None` if the connection only supports a single stream.
+    fn stream_handle(&self) -> Option<quic::QuicStreamHandle> {
+        None
+    }
+}
+
 impl<T: ?Sized> TransportExt for T where T: Transport {}
 
 /// An extension trait for [`Transport`]s that provides a variety of convenient
@@ -0,0 +1,624 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! QUIC Transport
+//!
+//! Connections are established using QUIC (see <https://github.com/quinn-rs/quinn>).
+//! Each connection exposes a primary bidirectional stream (the [`QuicSocket`] itself),
+//! which the AptosNet transport upgrades with Noise IK and the handshake protocol
+//! (exactly as it does for TCP sockets).
+//!
+//! Additional streams can be opened and accepted using the [`QuicStreamHandle`] of the
+//! socket. Each stream is delivered independently, so the AptosNet transport uses them
+//! to remove head-of-line blocking between priority classes.
+//!
+//! Note: QUIC mandates TLS 1.3. However, peer identities in AptosNet are established
+//! by the Noise IK handshake that runs over the primary stream (not TLS). As a result,
+//! each listener uses an ephemeral self-signed certificate and dialers do not verify
+//! the certificate chain. This preserves the existing Noise IK identity semantics.
+//! To authenticate the TLS session (and thus, all streams of the connection), the
+//! AptosNet transport binds the Noise IK handshake to keying material exported from
+//! the TLS session (see [`QuicStreamHandle::export_channel_binding`]).
+use crate::transport::{MultiStreamSocket, Transport};
+use aptos_infallible::Mutex;
+use aptos_types::{
+    network_address::{parse_dns_udp, parse_ip_udp, NetworkAddress},
+    PeerId,
+};
+use futures::{
+    future::Future,
+    io::{AsyncRead, AsyncWrite},
+    stream::{BoxStream, Stream, StreamExt},
+};
+use quinn::{ClientConfig, Connection, Endpoint, RecvStream, SendStream, ServerConfig};
+use std::{
+    io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::{Duration, SystemTime},
+};
+use tokio::net::lookup_host;
+
+/// The ALPN protocol identifier used for AptosNet QUIC connections
+const APTOSNET_ALPN: &[u8] = b"aptosnet";
+
+/// The (dummy) server name used for TLS. Certificates are not verified,
+/// so this only needs to match the name in the self-signed certificate.
+const APTOSNET_SERVER_NAME: &str = "aptosnet";
+
+/// The label used to export the channel binding from the TLS session (see RFC 5705)
+const CHANNEL_BINDING_LABEL: &[u8] = b"EXPORTER-aptosnet-channel-binding";
+
+/// The size (in bytes) of the channel binding exported from the TLS session
+pub const CHANNEL_BINDING_SIZE: usize = 32;
+
+/// Transport to build QUIC connections
+#[derive(Debug, Clone)]
+pub struct QuicTransport {
+    /// The interval at which keep-alive packets are sent, or `None` to disable keep-alives.
+    pub keep_alive_interval: Option<Duration>,
+    /// The maximum duration of inactivity before a connection is timed out,
+    /// or `None` to keep the default.
+    pub max_idle_timeout: Option<Duration>,
+    /// The client endpoints shared by all outbound connections (and clones) of the transport
+    client_endpoints: Arc<Mutex<ClientEndpoints>>,
+}
+
+/// The client endpoints (i.e., UDP sockets) used to dial, one per address
+/// family. Each endpoint is created on the first dial to its address family.
+#[derive(Debug, Default)]
+struct ClientEndpoints {
+    ipv4: Option<Endpoint>,
+    ipv6: Option<Endpoint>,
+}
+
+impl Default for QuicTransport {
+    fn default() -> Self {
+        Self::new(None, None)
+    }
+}
+
+impl QuicTransport {
+    pub fn new(keep_alive_interval: Option<Duration>, max_idle_timeout: Option<Duration>) -> Self {
+        Self {
+            keep_alive_interval,
+            max_idle_timeout,
+            client_endpoints: Arc::new(Mutex::new(ClientEndpoints::default())),
+        }
+    }
+
+    /// Returns the client endpoint to dial the given socket address, creating it if needed.
+    /// Note: this must be called from within a tokio runtime.
+    fn client_endpoint(&self, socket_addr: SocketAddr) -> io::Result<Endpoint> {
+        let mut client_endpoints = self.client_endpoints.lock();
+        let (client_endpoint, bind_addr) = match socket_addr.ip() {
+            IpAddr::V4(_) => (
+                &mut client_endpoints.ipv4,
+                SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0),
+            ),
+            IpAddr::V6(_) => (
+                &mut client_endpoints.ipv6,
+                SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), 0),
+            ),
+        };
+        if let Some(endpoint) = client_endpoint {
+            return Ok(endpoint.clone());
+        }
+
+        // Bind the client endpoint to an unspecified address of the same family
+        let mut endpoint = Endpoint::client(bind_addr)?;
+        endpoint.set_default_client_config(self.client_config()?);
+        *client_endpoint = Some(endpoint.clone());
+        Ok(endpoint)
+    }
+
+    /// Returns the transport config to use for both inbound and outbound connections
+    fn transport_config(&self) -> io::Result<Arc<quinn::TransportConfig>> {
+        let mut transport_config = quinn::TransportConfig::default();
+        transport_config.keep_alive_interval(self.keep_alive_interval);
+        if let Some(max_idle_timeout) = self.max_idle_timeout {
+            let max_idle_timeout = quinn::IdleTimeout::try_from(max_idle_timeout)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+            transport_config.max_idle_timeout(Some(max_idle_timeout));
+        }
+        Ok(Arc::new(transport_config))
+    }
+
+    /// Creates a server config using an ephemeral self-signed certificate
+    fn server_config(&self) -> io::Result<ServerConfig> {
+        let certificate = rcgen::generate_simple_self_signed(vec![APTOSNET_SERVER_NAME.into()])
+            .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+        let certificate_der = certificate
+            .serialize_der()
+            .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+        let private_key_der = certificate.serialize_private_key_der();
+
+        let mut crypto = rustls::ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_single_cert(
+                vec![rustls::Certificate(certificate_der)],
+                rustls::PrivateKey(private_key_der),
+            )
+            .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+        crypto.alpn_protocols = vec![APTOSNET_ALPN.to_vec()];
+
+        let mut server_config = ServerConfig::with_crypto(Arc::new(crypto));
+        server_config.transport_config(self.transport_config()?);
+        Ok(server_config)
+    }
+
+    /// Creates a client config that skips certificate verification
+    /// (peers are authenticated by Noise IK instead).
+    fn client_config(&self) -> io::Result<ClientConfig> {
+        let mut crypto = rustls::ClientConfig::builder()
+            .with_safe_defaults()
+            .with_custom_certificate_verifier(Arc::new(SkipServerVerification))
+            .with_no_client_auth();
+        crypto.alpn_protocols = vec![APTOSNET_ALPN.to_vec()];
+
+        let mut client_config = ClientConfig::new(Arc::new(crypto));
+        client_config.transport_config(self.transport_config()?);
+        Ok(client_config)
+    }
+}
+
+impl Transport for QuicTransport {
+    type Error = ::std::io::Error;
+    type Inbound = Pin<Box<dyn Future<Output = io::Result<QuicSocket>> + Send + 'static>>;
+    type Listener = QuicListenerStream;
+    type Outbound = Pin<Box<dyn Future<Output = io::Result<QuicSocket>> + Send + 'static>>;
+    type Output = QuicSocket;
+
+    fn listen_on(
+        &self,
+        addr: NetworkAddress,
+    ) -> Result<(Self::Listener, NetworkAddress), Self::Error> {
+        let ((ipaddr, port), addr_suffix) =
+            parse_ip_udp(addr.as_slice()).ok_or_else(|| invalid_addr_error(&addr))?;
+        if !addr_suffix.is_empty() {
+            return Err(invalid_addr_error(&addr));
+        }
+
+        let endpoint = Endpoint::server(self.server_config()?, SocketAddr::new(ipaddr, port))?;
+        let listen_addr = udp_network_address(endpoint.local_addr()?);
+
+        Ok((QuicListenerStream::new(endpoint), listen_addr))
+    }
+
+    fn dial(&self, _peer_id: PeerId, addr: NetworkAddress) -> Result<Self::Outbound, Self::Error> {
+        let protos = addr.as_slice();
+
+        // ensure addr is well formed to save some work before potentially
+        // spawning a dial task that will fail anyway.
+        parse_ip_udp(protos)
+            .map(|_| ())
+            .or_else(|| parse_dns_udp(protos).map(|_| ()))
+            .ok_or_else(|| invalid_addr_error(&addr))?;
+
+        Ok(Box::pin(resolve_and_connect(addr, self.clone())))
+    }
+}
+
+/// Resolves the given address and connects to the first reachable socket
+/// address. Once connected, the primary stream is opened.
+async fn resolve_and_connect(
+    addr: NetworkAddress,
+    transport: QuicTransport,
+) -> io::Result<QuicSocket> {
+    let protos = addr.as_slice();
+
+    let socket_addrs: Vec<SocketAddr> =
+        if let Some(((ipaddr, port), _addr_suffix)) = parse_ip_udp(protos) {
+            vec![SocketAddr::new(ipaddr, port)]
+        } else if let Some(((ip_filter, dns_name, port), _addr_suffix)) = parse_dns_udp(protos) {
+            lookup_host((dns_name.as_ref(), port))
+                .await?
+                .filter(|socket_addr| ip_filter.matches(socket_addr.ip()))
+                .collect()
+        } else {
+            return Err(invalid_addr_error(&addr));
+        };
+
+    // try to connect until the first succeeds
+    let mut last_err = None;
+    for socket_addr in socket_addrs {
+        match connect(socket_addr, &transport).await {
+            Ok(socket) => return Ok(socket),
+            Err(err) => last_err = Some(err),
+        }
+    }
+
+    Err(last_err.unwrap_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("could not resolve address to any socket address: {}", addr),
+        )
+    }))
+}
+
+/// Connects to the given socket address and opens the primary stream
+async fn connect(socket_addr: SocketAddr, transport: &QuicTransport) -> io::Result<QuicSocket> {
+    // Establish the connection
+    let connection = transport
+        .client_endpoint(socket_addr)?
+        .connect(socket_addr, APTOSNET_SERVER_NAME)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?
+        .await?;
+
+    // Open the primary stream. Note: the stream is only announced to the
+    // listener once data is written (which the Noise IK initiator does first).
+    let (send, recv) = connection.open_bi().await?;
+    Ok(QuicSocket::new(connection, send, recv))
+}
+
+/// Accepts the primary stream on an inbound connection
+async fn accept(connecting: quinn::Connecting) -> io::Result<QuicSocket> {
+    let connection = connecting.await?;
+    let (send, recv) = connection.accept_bi().await?;
+    Ok(QuicSocket::new(connection, send, recv))
+}
+
+/// Converts the given socket address into a `/ip*/<addr>/udp/<port>` network address
+fn udp_network_address(socket_addr: SocketAddr) -> NetworkAddress {
+    use aptos_types::network_address::Protocol;
+
+    let protocols = vec![
+        Protocol::from(socket_addr.ip()),
+        Protocol::Udp(socket_addr.port()),
+    ];
+    NetworkAddress::try_from(protocols).expect("protocols is always non-empty")
+}
+
+fn invalid_addr_error(addr: &NetworkAddress) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("Invalid NetworkAddress: '{}'", addr),
+    )
+}
+
+/// A certificate verifier that accepts all server certificates. This is safe
+/// because peers are mutually authenticated by Noise IK on the primary stream.
+struct SkipServerVerification;
+
+impl rustls::client::ServerCertVerifier for SkipServerVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &rustls::Certificate,
+        _intermediates: &[rustls::Certificate],
+        _server_name: &rustls::ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> Result<rustls::client::ServerCertVerified, rustls::Error> {
+        Ok(rustls::client::ServerCertVerified::assertion())
+    }
+}
+
+#[must_use = "streams do nothing unless polled"]
+pub struct QuicListenerStream {
+    inner: BoxStream<'static, quinn::Connecting>,
+}
+
+impl QuicListenerStream {
+    fn new(endpoint: Endpoint) -> Self {
+        let inner = futures::stream::unfold(endpoint, |endpoint| async move {
+            let connecting = endpoint.accept().await?;
+            Some((connecting, endpoint))
+        })
+        .boxed();
+        Self { inner }
+    }
+}
+
+impl Stream for QuicListenerStream {
+    type Item = io::Result<(
+        Pin<Box<dyn Future<Output = io::Result<QuicSocket>> + Send + 'static>>,
+        NetworkAddress,
+    )>;
+
+    fn poll_next(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<Option<Self::Item>> {
+        match self.inner.poll_next_unpin(context) {
+            Poll::Ready(Some(connecting)) => {
+                let dialer_addr = udp_network_address(connecting.remote_address());
+                let inbound: Pin<Box<dyn Future<Output = _> + Send + 'static>> =
+                    Box::pin(accept(connecting));
+                Poll::Ready(Some(Ok((inbound, dialer_addr))))
+            },
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// A bidirectional stream on a QUIC connection
+#[derive(Debug)]
+pub struct QuicStream {
+    send: SendStream,
+    recv: RecvStream,
+}
+
+impl QuicStream {
+    fn new(send: SendStream, recv: RecvStream) -> Self {
+        Self { send, recv }
+    }
+}
+
+impl AsyncRead for QuicStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        context: &mut Context,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        AsyncRead::poll_read(Pin::new(&mut self.recv), context, buf)
+    }
+}
+
+impl AsyncWrite for QuicStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        context: &mut Context,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        AsyncWrite::poll_write(Pin::new(&mut self.send), context, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<io::Result<()>> {
+        AsyncWrite::poll_flush(Pin::new(&mut self.send), context)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<io::Result<()>> {
+        // Closing waits for the remote peer to acknowledge all written data. If the
+        // connection has already been closed by the remote peer, the stream is
+        // closed anyway, so we don't treat this as an error (similar to TCP).
+        match AsyncWrite::poll_close(Pin::new(&mut self.send), context) {
+            Poll::Ready(Err(error)) if error.kind() == io::ErrorKind::NotConnected => {
+                Poll::Ready(Ok(()))
+            },
+            result => result,
+        }
+    }
+}
+
+/// A QUIC connection and its primary bidirectional stream.
+///
+/// The socket reads from and writes to the primary stream, so it can be
+/// used anywhere a TCP socket is used (e.g., upgraded with Noise IK).
+#[derive(Debug)]
+pub struct QuicSocket {
+    connection: Connection,
+    primary_stream: QuicStream,
+}
+
+impl QuicSocket {
+    fn new(connection: Connection, send: SendStream, recv: RecvStream) -> Self {
+        Self {
+            connection,
+            primary_stream: QuicStream::new(send, recv),
+        }
+    }
+
+    /// Returns the address of the remote peer
+    pub fn remote_address(&self) -> SocketAddr {
+        self.connection.remote_address()
+    }
+}
+
+impl MultiStreamSocket for QuicSocket {
+    fn stream_handle(&self) -> Option<QuicStreamHandle> {
+        Some(QuicStreamHandle {
+            connection: self.connection.clone(),
+        })
+    }
+}
+
+/// A handle to open and accept additional streams on a QUIC connection
+#[derive(Clone, Debug)]
+pub struct QuicStreamHandle {
+    connection: Connection,
+}
+
+impl QuicStreamHandle {
+    /// Opens a new bidirectional stream. Note: the stream is only
+    /// announced to the remote peer once data is written.
+    pub async fn open_stream(&self) -> io::Result<QuicStream> {
+        let (send, recv) = self.connection.open_bi().await?;
+        Ok(QuicStream::new(send, recv))
+    }
+
+    /// Accepts the next bidirectional stream opened by the remote peer
+    pub async fn accept_stream(&self) -> io::Result<QuicStream> {
+        let (send, recv) = self.connection.accept_bi().await?;
+        Ok(QuicStream::new(send, recv))
+    }
+
+    /// Exports keying material from the TLS session of the connection. Both peers
+    /// export the same value, which cannot be derived by anyone else (e.g., a relay
+    /// that terminates the TLS sessions of both peers would export different values).
+    pub fn export_channel_binding(&self) -> io::Result<[u8; CHANNEL_BINDING_SIZE]> {
+        let mut channel_binding = [0; CHANNEL_BINDING_SIZE];
+        self.connection
+            .export_keying_material(&mut channel_binding, CHANNEL_BINDING_LABEL, APTOSNET_ALPN)
+            .map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::Other,
+                    "failed to export the channel binding from the TLS session",
+                )
+            })?;
+        Ok(channel_binding)
+    }
+}
+
+impl AsyncRead for QuicSocket {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        context: &mut Context,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.primary_stream).poll_read(context, buf)
+    }
+}
+
+impl AsyncWrite for QuicSocket {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        context: &mut Context,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.primary_stream).poll_write(context, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.primary_stream).poll_flush(context)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.primary_stream).poll_close(context)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::transport::{ConnectionOrigin, TransportExt};
+    use futures::{
+        future::{join, FutureExt},
+        io::{AsyncReadExt, AsyncWriteExt},
+    };
+
+    #[tokio::test]
+    async fn simple_listen_and_dial() -> Result<(), ::std::io::Error> {
+        let t = QuicTransport::default().and_then(|mut out, _addr, origin| async move {
+            match origin {
+                ConnectionOrigin::Inbound => {
+                    let mut buf = [0; 5];
+                    out.read_exact(&mut buf).await?;
+                    assert_eq!(&buf, b"Earth");
+                    out.write_all(b"Air").await?;
+                    out.flush().await?;
+                },
+                ConnectionOrigin::Outbound => {
+                    out.write_all(b"Earth").await?;
+                    out.flush().await?;
+                    let mut buf = [0; 3];
+                    out.read_exact(&mut buf).await?;
+                    assert_eq!(&buf, b"Air");
+                },
+            }
+            Ok(out)
+        });
+
+        let (listener, addr) = t.listen_on("/ip4/127.0.0.1/udp/0".parse().unwrap())?;
+        let peer_id = PeerId::random();
+        let dial = t.dial(peer_id, addr)?;
+        let listener = listener.into_future().then(|(maybe_result, _stream)| {
+            let (incoming, _addr) = maybe_result.unwrap().unwrap();
+            incoming.map(Result::unwrap)
+        });
+
+        let (outgoing, _incoming) = join(dial, listener).await;
+        assert!(outgoing.is_ok());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn dials_share_client_endpoint() -> Result<(), ::std::io::Error> {
+        let t = QuicTransport::default();
+        let (mut listener, addr) = t.listen_on("/ip6/::1/udp/0".parse().unwrap())?;
+
+        // Dial twice (with clones of the transport) and write to the primary
+        // streams so that the listener can accept them
+        let mut dialer_addrs = vec![];
+        for transport in [t.clone(), t.clone()] {
+            let dial = transport.dial(PeerId::random(), addr.clone())?;
+            let outbound = async move {
+                let mut socket = dial.await?;
+                socket.write_all(b"primary").await?;
+                socket.flush().await?;
+                Ok::<_, io::Error>(socket)
+            };
+            let inbound = async {
+                let (inbound, _addr) = listener.next().await.unwrap()?;
+                inbound.await
+            };
+            let (outbound, inbound) = join(outbound, inbound).await;
+            let (_outbound, mut inbound) = (outbound?, inbound?);
+            let mut buf = [0; 7];
+            inbound.read_exact(&mut buf).await?;
+            assert_eq!(&buf, b"primary");
+            dialer_addrs.push(inbound.remote_address());
+        }
+
+        // Both connections were dialed from the same client endpoint (i.e., UDP socket)
+        assert_eq!(dialer_addrs[0], dialer_addrs[1]);
+        let client_endpoints = t.client_endpoints.lock();
+        assert!(client_endpoints.ipv4.is_none());
+        assert_eq!(
+            client_endpoints.ipv6.as_ref().unwrap().local_addr()?.port(),
+            dialer_addrs[0].port()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn open_and_accept_streams() -> Result<(), ::std::io::Error> {
+        let t = QuicTransport::default();
+        let (mut listener, addr) = t.listen_on("/ip4/127.0.0.1/udp/0".parse().unwrap())?;
+
+        // Establish the connection (by writing to the primary stream)
+        let outbound = async {
+            let mut socket = t.dial(PeerId::random(), addr).unwrap().await?;
+            socket.write_all(b"primary").await?;
+            socket.flush().await?;
+            Ok::<_, io::Error>(socket)
+        };
+        let inbound = async {
+            let (inbound, _addr) = listener.next().await.unwrap()?;
+            inbound.await
+        };
+        let (outbound, inbound) = join(outbound, inbound).await;
+        let (outbound, mut inbound) = (outbound?, inbound?);
+        let mut buf = [0; 7];
+        inbound.read_exact(&mut buf).await?;
+
+        // Both peers export the same channel binding
+        let outbound_handle = outbound.stream_handle().unwrap();
+        let inbound_handle = inbound.stream_handle().unwrap();
+        assert_eq!(
+            outbound_handle.export_channel_binding()?,
+            inbound_handle.export_channel_binding()?
+        );
+
+        // Open an additional stream and write to it (without using the primary stream)
+        let mut outbound_stream = outbound_handle.open_stream().await?;
+        outbound_stream.write_all(b"extra").await?;
+        outbound_stream.flush().await?;
+        let mut inbound_stream = inbound_handle.accept_stream().await?;
+        let mut buf = [0; 5];
+        inbound_stream.read_exact(&mut buf).await?;
+        assert_eq!(&buf, b"extra");
+
+        // Streams are bidirectional
+        inbound_stream.write_all(b"reply").await?;
+        inbound_stream.flush().await?;
+        outbound_stream.read_exact(&mut buf).await?;
+        assert_eq!(&buf, b"reply");
+
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_multiaddrs() {
+        let t = QuicTransport::default();
+
+        let result = t.listen_on("/ip4/127.0.0.1/tcp/0".parse().unwrap());
+        assert!(result.is_err());
+
+        let peer_id = PeerId::random();
+        let result = t.dial(peer_id, "/memory/22".parse().unwrap());
+        assert!(result.is_err());
+    }
+}
@@ -17,7 +17,7 @@ from aptos.transaction.v1 import (
 )
 
 DESCRIPTOR = _descriptor_pool.Default().AddSerializedFile(
-    b'\n\x1f\x61ptos/indexer/v1/raw_data.proto\x12\x10\x61ptos.indexer.v1\x1a&aptos/transaction/v1/transaction.proto"\x84\x01\n\x15TransactionsInStorage\x12\x37\n\x0ctransactions\x18\x01 \x03(\x0b\x32!.aptos.transaction.v1.Transaction\x12\x1d\n\x10starting_version\x18\x02 \x01(\x04H\x00\x88\x01\x01\x42\x13\n\x11_starting_version"\xb4\x01\n\x16GetTransactionsRequest\x12!\n\x10starting_version\x18\x01 \x01(\x04\x42\x02\x30\x01H\x00\x88\x01\x01\x12#\n\x12transactions_count\x18\x02 \x01(\x04\x42\x02\x30\x01H\x01\x88\x01\x01\x12\x17\n\nbatch_size\x18\x03 \x01(\x04H\x02\x88\x01\x01\x42\x13\n\x11_starting_versionB\x15\n\x13_transactions_countB\r\n\x0b_batch_size"w\n\x14TransactionsResponse\x12\x37\n\x0ctransactions\x18\x01 \x03(\x0b\x32!.aptos.transaction.v1.Transaction\x12\x19\n\x08\x63hain_id\x18\x02 \x01(\x04\x42\x02\x30\x01H\x00\x88\x01\x01\x42\x0b\n\t_chain_id2p\n\x07RawData\x12\x65\n\x0fGetTransactions\x12(.aptos.indexer.v1.GetTransactionsRequest\x1a&.aptos.indexer.v1.TransactionsResponse0\x01\x62\x06proto3'
+    b'\n\x1f\x61ptos/indexer/v1/raw_data.proto\x12\x10\x61ptos.indexer.v1\x1a&aptos/transaction/v1/transaction.proto"\x84\x01\n\x15TransactionsInStorage\x12\x37\n\x0ctransactions\x18\x01 \x03(\x0b\x32!.aptos.transaction.v1.Transaction\x12\x1d\n\x10starting_version\x18\x02 \x01(\x04H\x00\x88\x01\x01\x42\x13\n\x11_starting_version"\xfc\x01\n\x16GetTransactionsRequest\x12!\n\x10starting_version\x18\x01 \x01(\x04\x42\x02\x30\x01H\x00\x88\x01\x01\x12#\n\x12transactions_count\x18\x02 \x01(\x04\x42\x02\x30\x01H\x01\x88\x01\x01\x12\x17\n\nbatch_size\x18\x03 \x01(\x04H\x02\x88\x01\x01\x12\x46\n\x12transaction_filter\x18\x04 \x01(\x0b\x32*.aptos.indexer.v1.BooleanTransactionFilterB\x13\n\x11_starting_versionB\x15\n\x13_transactions_countB\r\n\x0b_batch_size"\x85\x01\n\x13\x45ntryFunctionFilter\x12\x14\n\x07\x61\x64\x64ress\x18\x01 \x01(\tH\x00\x88\x01\x01\x12\x18\n\x0bmodule_name\x18\x02 \x01(\tH\x01\x88\x01\x01\x12\x15\n\x08\x66unction\x18\x03 \x01(\tH\x02\x88\x01\x01\x42\n\n\x08_addressB\x0e\n\x0c_module_nameB\x0b\n\t_function"\xbc\x01\n\x11TransactionFilter\x12\x10\n\x06sender\x18\x01 \x01(\tH\x00\x12?\n\x0e\x65ntry_function\x18\x02 \x01(\x0b\x32%.aptos.indexer.v1.EntryFunctionFilterH\x00\x12\x14\n\nevent_type\x18\x03 \x01(\tH\x00\x12!\n\x17write_set_resource_type\x18\x04 \x01(\tH\x00\x12\x11\n\x07success\x18\x05 \x01(\x08H\x00\x42\x08\n\x06\x66ilter"P\n\x11LogicalAndFilters\x12;\n\x07\x66ilters\x18\x01 \x03(\x0b\x32*.aptos.indexer.v1.BooleanTransactionFilter"O\n\x10LogicalOrFilters\x12;\n\x07\x66ilters\x18\x01 \x03(\x0b\x32*.aptos.indexer.v1.BooleanTransactionFilter"\xa0\x02\n\x18\x42ooleanTransactionFilter\x12\x41\n\x12transaction_filter\x18\x01 \x01(\x0b\x32#.aptos.indexer.v1.TransactionFilterH\x00\x12:\n\x0blogical_and\x18\x02 \x01(\x0b\x32#.aptos.indexer.v1.LogicalAndFiltersH\x00\x12\x38\n\nlogical_or\x18\x03 \x01(\x0b\x32".aptos.indexer.v1.LogicalOrFiltersH\x00\x12\x41\n\x0blogical_not\x18\x04 \x01(\x0b\x32*.aptos.indexer.v1.BooleanTransactionFilterH\x00\x42\x08\n\x06\x66ilter"\xb1\x01\n\x14TransactionsResponse\x12\x37\n\x0ctransactions\x18\x01 \x03(\x0b\x32!.aptos.transaction.v1.Transaction\x12\x19\n\x08\x63hain_id\x18\x02 \x01(\x04\x42\x02\x30\x01H\x00\x88\x01\x01\x12"\n\x11processed_version\x18\x03 \x01(\x04\x42\x02\x30\x01H\x01\x88\x01\x01\x42\x0b\n\t_chain_idB\x14\n\x12_processed_version2p\n\x07RawData\x12\x65\n\x0fGetTransactions\x12(.aptos.indexer.v1.GetTransactionsRequest\x1a&.aptos.indexer.v1.TransactionsResponse0\x01\x62\x06proto3'
 )
 
 _globals = globals()
@@ -37,12 +37,26 @@ if _descriptor._USE_C_DESCRIPTORS == False:
     ]._serialized_options = b"0\001"
     _TRANSACTIONSRESPONSE.fields_by_name["chain_id"]._options = None
     _TRANSACTIONSRESPONSE.fields_by_name["chain_id"]._serialized_options = b"0\001"
+    _TRANSACTIONSRESPONSE.fields_by_name["processed_version"]._options = None
+    _TRANSACTIONSRESPONSE.fields_by_name[
+        "processed_version"
+    ]._serialized_options = b"0\001"
     _globals["_TRANSACTIONSINSTORAGE"]._serialized_start = 94
     _globals["_TRANSACTIONSINSTORAGE"]._serialized_end = 226
     _globals["_GETTRANSACTIONSREQUEST"]._serialized_start = 229
-    _globals["_GETTRANSACTIONSREQUEST"]._serialized_end = 409
-    _globals["_TRANSACTIONSRESPONSE"]._serialized_start = 411
-    _globals["_TRANSACTIONSRESPONSE"]._serialized_end = 530
-    _globals["_RAWDATA"]._serialized_start = 532
-    _globals["_RAWDATA"]._serialized_end = 644
+    _globals["_GETTRANSACTIONSREQUEST"]._serialized_end = 481
+    _globals["_ENTRYFUNCTIONFILTER"]._serialized_start = 484
+    _globals["_ENTRYFUNCTIONFILTER"]._serialized_end = 617
+    _globals["_TRANSACTIONFILTER"]._serialized_start = 620
+    _globals["_TRANSACTIONFILTER"]._serialized_end = 808
+    _globals["_LOGICALANDFILTERS"]._serialized_start = 810
+    _globals["_LOGICALANDFILTERS"]._serialized_end = 890
+    _globals["_LOGICALORFILTERS"]._serialized_start = 892
+    _globals["_LOGICALORFILTERS"]._serialized_end = 971
+    _globals["_BOOLEANTRANSACTIONFILTER"]._serialized_start = 974
+    _globals["_BOOLEANTRANSACTIONFILTER"]._serialized_end = 1262
+    _globals["_TRANSACTIONSRESPONSE"]._serialized_start = 1265
+    _globals["_TRANSACTIONSRESPONSE"]._serialized_end = 1442
+    _globals["_RAWDATA"]._serialized_start = 1444
+    _globals["_RAWDATA"]._serialized_end = 1556
 # @@protoc_insertion_point(module_scope)
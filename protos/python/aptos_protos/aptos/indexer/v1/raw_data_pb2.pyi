@@ -28,32 +28,121 @@ class TransactionsInStorage(_message.Message):
     ) -> None: ...
 
 class GetTransactionsRequest(_message.Message):
-    __slots__ = ["starting_version", "transactions_count", "batch_size"]
+    __slots__ = [
+        "starting_version",
+        "transactions_count",
+        "batch_size",
+        "transaction_filter",
+    ]
     STARTING_VERSION_FIELD_NUMBER: _ClassVar[int]
     TRANSACTIONS_COUNT_FIELD_NUMBER: _ClassVar[int]
     BATCH_SIZE_FIELD_NUMBER: _ClassVar[int]
+    TRANSACTION_FILTER_FIELD_NUMBER: _ClassVar[int]
     starting_version: int
     transactions_count: int
     batch_size: int
+    transaction_filter: BooleanTransactionFilter
     def __init__(
         self,
         starting_version: _Optional[int] = ...,
         transactions_count: _Optional[int] = ...,
         batch_size: _Optional[int] = ...,
+        transaction_filter: _Optional[_Union[BooleanTransactionFilter, _Mapping]] = ...,
+    ) -> None: ...
+
+class EntryFunctionFilter(_message.Message):
+    __slots__ = ["address", "module_name", "function"]
+    ADDRESS_FIELD_NUMBER: _ClassVar[int]
+    MODULE_NAME_FIELD_NUMBER: _ClassVar[int]
+    FUNCTION_FIELD_NUMBER: _ClassVar[int]
+    address: str
+    module_name: str
+    function: str
+    def __init__(
+        self,
+        address: _Optional[str] = ...,
+        module_name: _Optional[str] = ...,
+        function: _Optional[str] = ...,
+    ) -> None: ...
+
+class TransactionFilter(_message.Message):
+    __slots__ = [
+        "sender",
+        "entry_function",
+        "event_type",
+        "write_set_resource_type",
+        "success",
+    ]
+    SENDER_FIELD_NUMBER: _ClassVar[int]
+    ENTRY_FUNCTION_FIELD_NUMBER: _ClassVar[int]
+    EVENT_TYPE_FIELD_NUMBER: _ClassVar[int]
+    WRITE_SET_RESOURCE_TYPE_FIELD_NUMBER: _ClassVar[int]
+    SUCCESS_FIELD_NUMBER: _ClassVar[int]
+    sender: str
+    entry_function: EntryFunctionFilter
+    event_type: str
+    write_set_resource_type: str
+    success: bool
+    def __init__(
+        self,
+        sender: _Optional[str] = ...,
+        entry_function: _Optional[_Union[EntryFunctionFilter, _Mapping]] = ...,
+        event_type: _Optional[str] = ...,
+        write_set_resource_type: _Optional[str] = ...,
+        success: bool = ...,
+    ) -> None: ...
+
+class LogicalAndFilters(_message.Message):
+    __slots__ = ["filters"]
+    FILTERS_FIELD_NUMBER: _ClassVar[int]
+    filters: _containers.RepeatedCompositeFieldContainer[BooleanTransactionFilter]
+    def __init__(
+        self,
+        filters: _Optional[_Iterable[_Union[BooleanTransactionFilter, _Mapping]]] = ...,
+    ) -> None: ...
+
+class LogicalOrFilters(_message.Message):
+    __slots__ = ["filters"]
+    FILTERS_FIELD_NUMBER: _ClassVar[int]
+    filters: _containers.RepeatedCompositeFieldContainer[BooleanTransactionFilter]
+    def __init__(
+        self,
+        filters: _Optional[_Iterable[_Union[BooleanTransactionFilter, _Mapping]]] = ...,
+    ) -> None: ...
+
+class BooleanTransactionFilter(_message.Message):
+    __slots__ = ["transaction_filter", "logical_and", "logical_or", "logical_not"]
+    TRANSACTION_FILTER_FIELD_NUMBER: _ClassVar[int]
+    LOGICAL_AND_FIELD_NUMBER: _ClassVar[int]
+    LOGICAL_OR_FIELD_NUMBER: _ClassVar[int]
+    LOGICAL_NOT_FIELD_NUMBER: _ClassVar[int]
+    transaction_filter: TransactionFilter
+    logical_and: LogicalAndFilters
+    logical_or: LogicalOrFilters
+    logical_not: BooleanTransactionFilter
+    def __init__(
+        self,
+        transaction_filter: _Optional[_Union[TransactionFilter, _Mapping]] = ...,
+        logical_and: _Optional[_Union[LogicalAndFilters, _Mapping]] = ...,
+        logical_or: _Optional[_Union[LogicalOrFilters, _Mapping]] = ...,
+        logical_not: _Optional[_Union[BooleanTransactionFilter, _Mapping]] = ...,
     ) -> None: ...
 
 class TransactionsResponse(_message.Message):
-    __slots__ = ["transactions", "chain_id"]
+    __slots__ = ["transactions", "chain_id", "processed_version"]
     TRANSACTIONS_FIELD_NUMBER: _ClassVar[int]
     CHAIN_ID_FIELD_NUMBER: _ClassVar[int]
+    PROCESSED_VERSION_FIELD_NUMBER: _ClassVar[int]
     transactions: _containers.RepeatedCompositeFieldContainer[
         _transaction_pb2.Transaction
     ]
     chain_id: int
+    processed_version: int
     def __init__(
         self,
         transactions: _Optional[
             _Iterable[_Union[_transaction_pb2.Transaction, _Mapping]]
         ] = ...,
         chain_id: _Optional[int] = ...,
+        processed_version: _Optional[int] = ...,
     ) -> None: ...
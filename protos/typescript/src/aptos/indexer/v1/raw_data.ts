@@ -38,7 +38,75 @@ export interface GetTransactionsRequest {
    * Optional; number of transactions in each `TransactionsResponse` for current stream.
    * If not present, default to 1000. If larger than 1000, request will be rejected.
    */
-  batchSize?: bigint | undefined;
+  batchSize?:
+    | bigint
+    | undefined;
+  /**
+   * Optional; if present, only the transactions matching the filter are returned.
+   * Note that `transactions_count` still counts all the versions scanned by the stream.
+   */
+  transactionFilter?: BooleanTransactionFilter | undefined;
+}
+
+/** Filters on a user transaction's entry function. Unset fields match anything. */
+export interface EntryFunctionFilter {
+  /** Optional; the address of the module, e.g., "0x1". */
+  address?:
+    | string
+    | undefined;
+  /** Optional; the name of the module, e.g., "coin". */
+  moduleName?:
+    | string
+    | undefined;
+  /** Optional; the name of the function, e.g., "transfer". */
+  function?: string | undefined;
+}
+
+/** A filter on a single property of a transaction. */
+export interface TransactionFilter {
+  /** Matches user transactions sent by the given address. */
+  sender?:
+    | string
+    | undefined;
+  /** Matches user transactions calling the given entry function. */
+  entryFunction?:
+    | EntryFunctionFilter
+    | undefined;
+  /**
+   * Matches transactions emitting an event of the given type, e.g., "0x1::coin::DepositEvent".
+   * If the type has no generic type parameters, the event's type parameters are ignored.
+   */
+  eventType?:
+    | string
+    | undefined;
+  /**
+   * Matches transactions writing (or deleting) a resource of the given type, e.g.,
+   * "0x1::coin::CoinStore". If the type has no generic type parameters, the
+   * resource's type parameters are ignored.
+   */
+  writeSetResourceType?:
+    | string
+    | undefined;
+  /** Matches transactions that were (or were not) successfully executed. */
+  success?: boolean | undefined;
+}
+
+/** Matches if all of the filters match. */
+export interface LogicalAndFilters {
+  filters?: BooleanTransactionFilter[] | undefined;
+}
+
+/** Matches if any of the filters match. */
+export interface LogicalOrFilters {
+  filters?: BooleanTransactionFilter[] | undefined;
+}
+
+/** A boolean combination of transaction filters. */
+export interface BooleanTransactionFilter {
+  transactionFilter?: TransactionFilter | undefined;
+  logicalAnd?: LogicalAndFilters | undefined;
+  logicalOr?: LogicalOrFilters | undefined;
+  logicalNot?: BooleanTransactionFilter | undefined;
 }
 
 /** TransactionsResponse is a batch of transactions. */
@@ -48,7 +116,15 @@ export interface TransactionsResponse {
     | Transaction[]
     | undefined;
   /** Required; chain id. */
-  chainId?: bigint | undefined;
+  chainId?:
+    | bigint
+    | undefined;
+  /**
+   * Optional; the last version scanned by the stream, which is past the last transaction of
+   * the response if later transactions don't match the transaction filter. Responses without
+   * transactions are sent periodically to report progress while nothing matches the filter.
+   */
+  processedVersion?: bigint | undefined;
 }
 
 function createBaseTransactionsInStorage(): TransactionsInStorage {
@@ -167,7 +243,12 @@ export const TransactionsInStorage = {
 };
 
 function createBaseGetTransactionsRequest(): GetTransactionsRequest {
-  return { startingVersion: undefined, transactionsCount: undefined, batchSize: undefined };
+  return {
+    startingVersion: undefined,
+    transactionsCount: undefined,
+    batchSize: undefined,
+    transactionFilter: undefined,
+  };
 }
 
 export const GetTransactionsRequest = {
@@ -190,6 +271,9 @@ export const GetTransactionsRequest = {
       }
       writer.uint32(24).uint64(message.batchSize.toString());
     }
+    if (message.transactionFilter !== undefined) {
+      BooleanTransactionFilter.encode(message.transactionFilter, writer.uint32(34).fork()).ldelim();
+    }
     return writer;
   },
 
@@ -221,6 +305,13 @@ export const GetTransactionsRequest = {
 
           message.batchSize = longToBigint(reader.uint64() as Long);
           continue;
+        case 4:
+          if (tag !== 34) {
+            break;
+          }
+
+          message.transactionFilter = BooleanTransactionFilter.decode(reader, reader.uint32());
+          continue;
       }
       if ((tag & 7) === 4 || tag === 0) {
         break;
@@ -269,6 +360,9 @@ export const GetTransactionsRequest = {
       startingVersion: isSet(object.startingVersion) ? BigInt(object.startingVersion) : undefined,
       transactionsCount: isSet(object.transactionsCount) ? BigInt(object.transactionsCount) : undefined,
       batchSize: isSet(object.batchSize) ? BigInt(object.batchSize) : undefined,
+      transactionFilter: isSet(object.transactionFilter)
+        ? BooleanTransactionFilter.fromJSON(object.transactionFilter)
+        : undefined,
     };
   },
 
@@ -283,6 +377,9 @@ export const GetTransactionsRequest = {
     if (message.batchSize !== undefined) {
       obj.batchSize = message.batchSize.toString();
     }
+    if (message.transactionFilter !== undefined) {
+      obj.transactionFilter = BooleanTransactionFilter.toJSON(message.transactionFilter);
+    }
     return obj;
   },
 
@@ -294,12 +391,637 @@ export const GetTransactionsRequest = {
     message.startingVersion = object.startingVersion ?? undefined;
     message.transactionsCount = object.transactionsCount ?? undefined;
     message.batchSize = object.batchSize ?? undefined;
+    message.transactionFilter = (object.transactionFilter !== undefined && object.transactionFilter !== null)
+      ? BooleanTransactionFilter.fromPartial(object.transactionFilter)
+      : undefined;
+    return message;
+  },
+};
+
+function createBaseEntryFunctionFilter(): EntryFunctionFilter {
+  return { address: undefined, moduleName: undefined, function: undefined };
+}
+
+export const EntryFunctionFilter = {
+  encode(message: EntryFunctionFilter, writer: _m0.Writer = _m0.Writer.create()): _m0.Writer {
+    if (message.address !== undefined) {
+      writer.uint32(10).string(message.address);
+    }
+    if (message.moduleName !== undefined) {
+      writer.uint32(18).string(message.moduleName);
+    }
+    if (message.function !== undefined) {
+      writer.uint32(26).string(message.function);
+    }
+    return writer;
+  },
+
+  decode(input: _m0.Reader | Uint8Array, length?: number): EntryFunctionFilter {
+    const reader = input instanceof _m0.Reader ? input : _m0.Reader.create(input);
+    let end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseEntryFunctionFilter();
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1:
+          if (tag !== 10) {
+            break;
+          }
+
+          message.address = reader.string();
+          continue;
+        case 2:
+          if (tag !== 18) {
+            break;
+          }
+
+          message.moduleName = reader.string();
+          continue;
+        case 3:
+          if (tag !== 26) {
+            break;
+          }
+
+          message.function = reader.string();
+          continue;
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skipType(tag & 7);
+    }
+    return message;
+  },
+
+  // encodeTransform encodes a source of message objects.
+  // Transform<EntryFunctionFilter, Uint8Array>
+  async *encodeTransform(
+    source:
+      | AsyncIterable<EntryFunctionFilter | EntryFunctionFilter[]>
+      | Iterable<EntryFunctionFilter | EntryFunctionFilter[]>,
+  ): AsyncIterable<Uint8Array> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [EntryFunctionFilter.encode(p).finish()];
+        }
+      } else {
+        yield* [EntryFunctionFilter.encode(pkt as any).finish()];
+      }
+    }
+  },
+
+  // decodeTransform decodes a source of encoded messages.
+  // Transform<Uint8Array, EntryFunctionFilter>
+  async *decodeTransform(
+    source: AsyncIterable<Uint8Array | Uint8Array[]> | Iterable<Uint8Array | Uint8Array[]>,
+  ): AsyncIterable<EntryFunctionFilter> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [EntryFunctionFilter.decode(p)];
+        }
+      } else {
+        yield* [EntryFunctionFilter.decode(pkt as any)];
+      }
+    }
+  },
+
+  fromJSON(object: any): EntryFunctionFilter {
+    return {
+      address: isSet(object.address) ? globalThis.String(object.address) : undefined,
+      moduleName: isSet(object.moduleName) ? globalThis.String(object.moduleName) : undefined,
+      function: isSet(object.function) ? globalThis.String(object.function) : undefined,
+    };
+  },
+
+  toJSON(message: EntryFunctionFilter): unknown {
+    const obj: any = {};
+    if (message.address !== undefined) {
+      obj.address = message.address;
+    }
+    if (message.moduleName !== undefined) {
+      obj.moduleName = message.moduleName;
+    }
+    if (message.function !== undefined) {
+      obj.function = message.function;
+    }
+    return obj;
+  },
+
+  create(base?: DeepPartial<EntryFunctionFilter>): EntryFunctionFilter {
+    return EntryFunctionFilter.fromPartial(base ?? {});
+  },
+  fromPartial(object: DeepPartial<EntryFunctionFilter>): EntryFunctionFilter {
+    const message = createBaseEntryFunctionFilter();
+    message.address = object.address ?? undefined;
+    message.moduleName = object.moduleName ?? undefined;
+    message.function = object.function ?? undefined;
+    return message;
+  },
+};
+
+function createBaseTransactionFilter(): TransactionFilter {
+  return {
+    sender: undefined,
+    entryFunction: undefined,
+    eventType: undefined,
+    writeSetResourceType: undefined,
+    success: undefined,
+  };
+}
+
+export const TransactionFilter = {
+  encode(message: TransactionFilter, writer: _m0.Writer = _m0.Writer.create()): _m0.Writer {
+    if (message.sender !== undefined) {
+      writer.uint32(10).string(message.sender);
+    }
+    if (message.entryFunction !== undefined) {
+      EntryFunctionFilter.encode(message.entryFunction, writer.uint32(18).fork()).ldelim();
+    }
+    if (message.eventType !== undefined) {
+      writer.uint32(26).string(message.eventType);
+    }
+    if (message.writeSetResourceType !== undefined) {
+      writer.uint32(34).string(message.writeSetResourceType);
+    }
+    if (message.success !== undefined) {
+      writer.uint32(40).bool(message.success);
+    }
+    return writer;
+  },
+
+  decode(input: _m0.Reader | Uint8Array, length?: number): TransactionFilter {
+    const reader = input instanceof _m0.Reader ? input : _m0.Reader.create(input);
+    let end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseTransactionFilter();
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1:
+          if (tag !== 10) {
+            break;
+          }
+
+          message.sender = reader.string();
+          continue;
+        case 2:
+          if (tag !== 18) {
+            break;
+          }
+
+          message.entryFunction = EntryFunctionFilter.decode(reader, reader.uint32());
+          continue;
+        case 3:
+          if (tag !== 26) {
+            break;
+          }
+
+          message.eventType = reader.string();
+          continue;
+        case 4:
+          if (tag !== 34) {
+            break;
+          }
+
+          message.writeSetResourceType = reader.string();
+          continue;
+        case 5:
+          if (tag !== 40) {
+            break;
+          }
+
+          message.success = reader.bool();
+          continue;
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skipType(tag & 7);
+    }
+    return message;
+  },
+
+  // encodeTransform encodes a source of message objects.
+  // Transform<TransactionFilter, Uint8Array>
+  async *encodeTransform(
+    source: AsyncIterable<TransactionFilter | TransactionFilter[]> | Iterable<TransactionFilter | TransactionFilter[]>,
+  ): AsyncIterable<Uint8Array> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [TransactionFilter.encode(p).finish()];
+        }
+      } else {
+        yield* [TransactionFilter.encode(pkt as any).finish()];
+      }
+    }
+  },
+
+  // decodeTransform decodes a source of encoded messages.
+  // Transform<Uint8Array, TransactionFilter>
+  async *decodeTransform(
+    source: AsyncIterable<Uint8Array | Uint8Array[]> | Iterable<Uint8Array | Uint8Array[]>,
+  ): AsyncIterable<TransactionFilter> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [TransactionFilter.decode(p)];
+        }
+      } else {
+        yield* [TransactionFilter.decode(pkt as any)];
+      }
+    }
+  },
+
+  fromJSON(object: any): TransactionFilter {
+    return {
+      sender: isSet(object.sender) ? globalThis.String(object.sender) : undefined,
+      entryFunction: isSet(object.entryFunction) ? EntryFunctionFilter.fromJSON(object.entryFunction) : undefined,
+      eventType: isSet(object.eventType) ? globalThis.String(object.eventType) : undefined,
+      writeSetResourceType: isSet(object.writeSetResourceType)
+        ? globalThis.String(object.writeSetResourceType)
+        : undefined,
+      success: isSet(object.success) ? globalThis.Boolean(object.success) : undefined,
+    };
+  },
+
+  toJSON(message: TransactionFilter): unknown {
+    const obj: any = {};
+    if (message.sender !== undefined) {
+      obj.sender = message.sender;
+    }
+    if (message.entryFunction !== undefined) {
+      obj.entryFunction = EntryFunctionFilter.toJSON(message.entryFunction);
+    }
+    if (message.eventType !== undefined) {
+      obj.eventType = message.eventType;
+    }
+    if (message.writeSetResourceType !== undefined) {
+      obj.writeSetResourceType = message.writeSetResourceType;
+    }
+    if (message.success !== undefined) {
+      obj.success = message.success;
+    }
+    return obj;
+  },
+
+  create(base?: DeepPartial<TransactionFilter>): TransactionFilter {
+    return TransactionFilter.fromPartial(base ?? {});
+  },
+  fromPartial(object: DeepPartial<TransactionFilter>): TransactionFilter {
+    const message = createBaseTransactionFilter();
+    message.sender = object.sender ?? undefined;
+    message.entryFunction = (object.entryFunction !== undefined && object.entryFunction !== null)
+      ? EntryFunctionFilter.fromPartial(object.entryFunction)
+      : undefined;
+    message.eventType = object.eventType ?? undefined;
+    message.writeSetResourceType = object.writeSetResourceType ?? undefined;
+    message.success = object.success ?? undefined;
+    return message;
+  },
+};
+
+function createBaseLogicalAndFilters(): LogicalAndFilters {
+  return { filters: [] };
+}
+
+export const LogicalAndFilters = {
+  encode(message: LogicalAndFilters, writer: _m0.Writer = _m0.Writer.create()): _m0.Writer {
+    if (message.filters !== undefined && message.filters.length !== 0) {
+      for (const v of message.filters) {
+        BooleanTransactionFilter.encode(v!, writer.uint32(10).fork()).ldelim();
+      }
+    }
+    return writer;
+  },
+
+  decode(input: _m0.Reader | Uint8Array, length?: number): LogicalAndFilters {
+    const reader = input instanceof _m0.Reader ? input : _m0.Reader.create(input);
+    let end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseLogicalAndFilters();
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1:
+          if (tag !== 10) {
+            break;
+          }
+
+          message.filters!.push(BooleanTransactionFilter.decode(reader, reader.uint32()));
+          continue;
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skipType(tag & 7);
+    }
+    return message;
+  },
+
+  // encodeTransform encodes a source of message objects.
+  // Transform<LogicalAndFilters, Uint8Array>
+  async *encodeTransform(
+    source: AsyncIterable<LogicalAndFilters | LogicalAndFilters[]> | Iterable<LogicalAndFilters | LogicalAndFilters[]>,
+  ): AsyncIterable<Uint8Array> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [LogicalAndFilters.encode(p).finish()];
+        }
+      } else {
+        yield* [LogicalAndFilters.encode(pkt as any).finish()];
+      }
+    }
+  },
+
+  // decodeTransform decodes a source of encoded messages.
+  // Transform<Uint8Array, LogicalAndFilters>
+  async *decodeTransform(
+    source: AsyncIterable<Uint8Array | Uint8Array[]> | Iterable<Uint8Array | Uint8Array[]>,
+  ): AsyncIterable<LogicalAndFilters> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [LogicalAndFilters.decode(p)];
+        }
+      } else {
+        yield* [LogicalAndFilters.decode(pkt as any)];
+      }
+    }
+  },
+
+  fromJSON(object: any): LogicalAndFilters {
+    return {
+      filters: globalThis.Array.isArray(object?.filters)
+        ? object.filters.map((e: any) => BooleanTransactionFilter.fromJSON(e))
+        : [],
+    };
+  },
+
+  toJSON(message: LogicalAndFilters): unknown {
+    const obj: any = {};
+    if (message.filters?.length) {
+      obj.filters = message.filters.map((e) => BooleanTransactionFilter.toJSON(e));
+    }
+    return obj;
+  },
+
+  create(base?: DeepPartial<LogicalAndFilters>): LogicalAndFilters {
+    return LogicalAndFilters.fromPartial(base ?? {});
+  },
+  fromPartial(object: DeepPartial<LogicalAndFilters>): LogicalAndFilters {
+    const message = createBaseLogicalAndFilters();
+    message.filters = object.filters?.map((e) => BooleanTransactionFilter.fromPartial(e)) || [];
+    return message;
+  },
+};
+
+function createBaseLogicalOrFilters(): LogicalOrFilters {
+  return { filters: [] };
+}
+
+export const LogicalOrFilters = {
+  encode(message: LogicalOrFilters, writer: _m0.Writer = _m0.Writer.create()): _m0.Writer {
+    if (message.filters !== undefined && message.filters.length !== 0) {
+      for (const v of message.filters) {
+        BooleanTransactionFilter.encode(v!, writer.uint32(10).fork()).ldelim();
+      }
+    }
+    return writer;
+  },
+
+  decode(input: _m0.Reader | Uint8Array, length?: number): LogicalOrFilters {
+    const reader = input instanceof _m0.Reader ? input : _m0.Reader.create(input);
+    let end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseLogicalOrFilters();
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1:
+          if (tag !== 10) {
+            break;
+          }
+
+          message.filters!.push(BooleanTransactionFilter.decode(reader, reader.uint32()));
+          continue;
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skipType(tag & 7);
+    }
+    return message;
+  },
+
+  // encodeTransform encodes a source of message objects.
+  // Transform<LogicalOrFilters, Uint8Array>
+  async *encodeTransform(
+    source: AsyncIterable<LogicalOrFilters | LogicalOrFilters[]> | Iterable<LogicalOrFilters | LogicalOrFilters[]>,
+  ): AsyncIterable<Uint8Array> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [LogicalOrFilters.encode(p).finish()];
+        }
+      } else {
+        yield* [LogicalOrFilters.encode(pkt as any).finish()];
+      }
+    }
+  },
+
+  // decodeTransform decodes a source of encoded messages.
+  // Transform<Uint8Array, LogicalOrFilters>
+  async *decodeTransform(
+    source: AsyncIterable<Uint8Array | Uint8Array[]> | Iterable<Uint8Array | Uint8Array[]>,
+  ): AsyncIterable<LogicalOrFilters> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [LogicalOrFilters.decode(p)];
+        }
+      } else {
+        yield* [LogicalOrFilters.decode(pkt as any)];
+      }
+    }
+  },
+
+  fromJSON(object: any): LogicalOrFilters {
+    return {
+      filters: globalThis.Array.isArray(object?.filters)
+        ? object.filters.map((e: any) => BooleanTransactionFilter.fromJSON(e))
+        : [],
+    };
+  },
+
+  toJSON(message: LogicalOrFilters): unknown {
+    const obj: any = {};
+    if (message.filters?.length) {
+      obj.filters = message.filters.map((e) => BooleanTransactionFilter.toJSON(e));
+    }
+    return obj;
+  },
+
+  create(base?: DeepPartial<LogicalOrFilters>): LogicalOrFilters {
+    return LogicalOrFilters.fromPartial(base ?? {});
+  },
+  fromPartial(object: DeepPartial<LogicalOrFilters>): LogicalOrFilters {
+    const message = createBaseLogicalOrFilters();
+    message.filters = object.filters?.map((e) => BooleanTransactionFilter.fromPartial(e)) || [];
+    return message;
+  },
+};
+
+function createBaseBooleanTransactionFilter(): BooleanTransactionFilter {
+  return { transactionFilter: undefined, logicalAnd: undefined, logicalOr: undefined, logicalNot: undefined };
+}
+
+export const BooleanTransactionFilter = {
+  encode(message: BooleanTransactionFilter, writer: _m0.Writer = _m0.Writer.create()): _m0.Writer {
+    if (message.transactionFilter !== undefined) {
+      TransactionFilter.encode(message.transactionFilter, writer.uint32(10).fork()).ldelim();
+    }
+    if (message.logicalAnd !== undefined) {
+      LogicalAndFilters.encode(message.logicalAnd, writer.uint32(18).fork()).ldelim();
+    }
+    if (message.logicalOr !== undefined) {
+      LogicalOrFilters.encode(message.logicalOr, writer.uint32(26).fork()).ldelim();
+    }
+    if (message.logicalNot !== undefined) {
+      BooleanTransactionFilter.encode(message.logicalNot, writer.uint32(34).fork()).ldelim();
+    }
+    return writer;
+  },
+
+  decode(input: _m0.Reader | Uint8Array, length?: number): BooleanTransactionFilter {
+    const reader = input instanceof _m0.Reader ? input : _m0.Reader.create(input);
+    let end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseBooleanTransactionFilter();
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1:
+          if (tag !== 10) {
+            break;
+          }
+
+          message.transactionFilter = TransactionFilter.decode(reader, reader.uint32());
+          continue;
+        case 2:
+          if (tag !== 18) {
+            break;
+          }
+
+          message.logicalAnd = LogicalAndFilters.decode(reader, reader.uint32());
+          continue;
+        case 3:
+          if (tag !== 26) {
+            break;
+          }
+
+          message.logicalOr = LogicalOrFilters.decode(reader, reader.uint32());
+          continue;
+        case 4:
+          if (tag !== 34) {
+            break;
+          }
+
+          message.logicalNot = BooleanTransactionFilter.decode(reader, reader.uint32());
+          continue;
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skipType(tag & 7);
+    }
+    return message;
+  },
+
+  // encodeTransform encodes a source of message objects.
+  // Transform<BooleanTransactionFilter, Uint8Array>
+  async *encodeTransform(
+    source:
+      | AsyncIterable<BooleanTransactionFilter | BooleanTransactionFilter[]>
+      | Iterable<BooleanTransactionFilter | BooleanTransactionFilter[]>,
+  ): AsyncIterable<Uint8Array> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [BooleanTransactionFilter.encode(p).finish()];
+        }
+      } else {
+        yield* [BooleanTransactionFilter.encode(pkt as any).finish()];
+      }
+    }
+  },
+
+  // decodeTransform decodes a source of encoded messages.
+  // Transform<Uint8Array, BooleanTransactionFilter>
+  async *decodeTransform(
+    source: AsyncIterable<Uint8Array | Uint8Array[]> | Iterable<Uint8Array | Uint8Array[]>,
+  ): AsyncIterable<BooleanTransactionFilter> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [BooleanTransactionFilter.decode(p)];
+        }
+      } else {
+        yield* [BooleanTransactionFilter.decode(pkt as any)];
+      }
+    }
+  },
+
+  fromJSON(object: any): BooleanTransactionFilter {
+    return {
+      transactionFilter: isSet(object.transactionFilter)
+        ? TransactionFilter.fromJSON(object.transactionFilter)
+        : undefined,
+      logicalAnd: isSet(object.logicalAnd) ? LogicalAndFilters.fromJSON(object.logicalAnd) : undefined,
+      logicalOr: isSet(object.logicalOr) ? LogicalOrFilters.fromJSON(object.logicalOr) : undefined,
+      logicalNot: isSet(object.logicalNot) ? BooleanTransactionFilter.fromJSON(object.logicalNot) : undefined,
+    };
+  },
+
+  toJSON(message: BooleanTransactionFilter): unknown {
+    const obj: any = {};
+    if (message.transactionFilter !== undefined) {
+      obj.transactionFilter = TransactionFilter.toJSON(message.transactionFilter);
+    }
+    if (message.logicalAnd !== undefined) {
+      obj.logicalAnd = LogicalAndFilters.toJSON(message.logicalAnd);
+    }
+    if (message.logicalOr !== undefined) {
+      obj.logicalOr = LogicalOrFilters.toJSON(message.logicalOr);
+    }
+    if (message.logicalNot !== undefined) {
+      obj.logicalNot = BooleanTransactionFilter.toJSON(message.logicalNot);
+    }
+    return obj;
+  },
+
+  create(base?: DeepPartial<BooleanTransactionFilter>): BooleanTransactionFilter {
+    return BooleanTransactionFilter.fromPartial(base ?? {});
+  },
+  fromPartial(object: DeepPartial<BooleanTransactionFilter>): BooleanTransactionFilter {
+    const message = createBaseBooleanTransactionFilter();
+    message.transactionFilter = (object.transactionFilter !== undefined && object.transactionFilter !== null)
+      ? TransactionFilter.fromPartial(object.transactionFilter)
+      : undefined;
+    message.logicalAnd = (object.logicalAnd !== undefined && object.logicalAnd !== null)
+      ? LogicalAndFilters.fromPartial(object.logicalAnd)
+      : undefined;
+    message.logicalOr = (object.logicalOr !== undefined && object.logicalOr !== null)
+      ? LogicalOrFilters.fromPartial(object.logicalOr)
+      : undefined;
+    message.logicalNot = (object.logicalNot !== undefined && object.logicalNot !== null)
+      ? BooleanTransactionFilter.fromPartial(object.logicalNot)
+      : undefined;
     return message;
   },
 };
 
 function createBaseTransactionsResponse(): TransactionsResponse {
-  return { transactions: [], chainId: undefined };
+  return { transactions: [], chainId: undefined, processedVersion: undefined };
 }
 
 export const TransactionsResponse = {
@@ -315,6 +1037,12 @@ export const TransactionsResponse = {
       }
       writer.uint32(16).uint64(message.chainId.toString());
     }
+    if (message.processedVersion !== undefined) {
+      if (BigInt.asUintN(64, message.processedVersion) !== message.processedVersion) {
+        throw new globalThis.Error("value provided for field message.processedVersion of type uint64 too large");
+      }
+      writer.uint32(24).uint64(message.processedVersion.toString());
+    }
     return writer;
   },
 
@@ -339,6 +1067,13 @@ export const TransactionsResponse = {
 
           message.chainId = longToBigint(reader.uint64() as Long);
           continue;
+        case 3:
+          if (tag !== 24) {
+            break;
+          }
+
+          message.processedVersion = longToBigint(reader.uint64() as Long);
+          continue;
       }
       if ((tag & 7) === 4 || tag === 0) {
         break;
@@ -388,6 +1123,7 @@ export const TransactionsResponse = {
         ? object.transactions.map((e: any) => Transaction.fromJSON(e))
         : [],
       chainId: isSet(object.chainId) ? BigInt(object.chainId) : undefined,
+      processedVersion: isSet(object.processedVersion) ? BigInt(object.processedVersion) : undefined,
     };
   },
 
@@ -399,6 +1135,9 @@ export const TransactionsResponse = {
     if (message.chainId !== undefined) {
       obj.chainId = message.chainId.toString();
     }
+    if (message.processedVersion !== undefined) {
+      obj.processedVersion = message.processedVersion.toString();
+    }
     return obj;
   },
 
@@ -409,13 +1148,17 @@ export const TransactionsResponse = {
     const message = createBaseTransactionsResponse();
     message.transactions = object.transactions?.map((e) => Transaction.fromPartial(e)) || [];
     message.chainId = object.chainId ?? undefined;
+    message.processedVersion = object.processedVersion ?? undefined;
     return message;
   },
 };
 
 export type RawDataService = typeof RawDataService;
 export const RawDataService = {
-  /** Get transactions batch without any filtering from starting version and end if transaction count is present. */
+  /**
+   * Get transactions batch from starting version and end if transaction count is present.
+   * If a transaction filter is present, only the matching transactions are returned.
+   */
   getTransactions: {
     path: "/aptos.indexer.v1.RawData/GetTransactions",
     requestStream: false,
@@ -428,12 +1171,18 @@ export const RawDataService = {
 } as const;
 
 export interface RawDataServer extends UntypedServiceImplementation {
-  /** Get transactions batch without any filtering from starting version and end if transaction count is present. */
+  /**
+   * Get transactions batch from starting version and end if transaction count is present.
+   * If a transaction filter is present, only the matching transactions are returned.
+   */
   getTransactions: handleServerStreamingCall<GetTransactionsRequest, TransactionsResponse>;
 }
 
 export interface RawDataClient extends Client {
-  /** Get transactions batch without any filtering from starting version and end if transaction count is present. */
+  /**
+   * Get transactions batch from starting version and end if transaction count is present.
+   * If a transaction filter is present, only the matching transactions are returned.
+   */
   getTransactions(
     request: GetTransactionsRequest,
     options?: Partial<CallOptions>,
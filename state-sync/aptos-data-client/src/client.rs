@@ -27,7 +27,9 @@ use aptos_id_generator::{IdGenerator, U64IdGenerator};
 use aptos_infallible::Mutex;
 use aptos_logger::{info, sample, sample::SampleRate, trace, warn};
 use aptos_network::{
-    application::{interface::NetworkClient, storage::PeersAndMetadata},
+    application::{
+        interface::NetworkClient, reputation::ReputationEvent, storage::PeersAndMetadata,
+    },
     protocols::network::RpcError,
 };
 use aptos_storage_interface::DbReader;
@@ -545,9 +547,15 @@ impl AptosDataClient {
             .collect()
     }
 
-    /// Returns all peers connected to us
+    /// Returns all peers connected to us (excluding any banned peers)
     fn get_all_connected_peers(&self) -> crate::error::Result<HashSet<PeerNetworkId>, Error> {
-        let connected_peers = self.storage_service_client.get_available_peers()?;
+        let peers_and_metadata = self.get_peers_and_metadata();
+        let connected_peers: HashSet<_> = self
+            .storage_service_client
+            .get_available_peers()?
+            .into_iter()
+            .filter(|peer| !peers_and_metadata.is_peer_banned(peer))
+            .collect();
         if connected_peers.is_empty() {
             return Err(Error::NoConnectedPeers(
                 "No available peers found!".to_owned(),
@@ -801,6 +809,8 @@ impl AptosDataClient {
                 // is successful or failed but not both; on the other hand, this
                 // feels simpler for the consumer.
                 self.peer_states.update_score_success(peer);
+                self.get_peers_and_metadata()
+                    .report_peer_event(peer, ReputationEvent::SuccessfulResponse);
 
                 // Package up all of the context needed to fully report an error
                 // with this RPC.
@@ -1148,6 +1158,12 @@ impl ResponseCallback for AptosNetResponseCallback {
         let error_type = ErrorType::from(error);
         self.data_client
             .notify_bad_response(self.id, self.peer, &self.request, error_type);
+
+        // Also report the bad response to the shared peer reputations. Note: RPC
+        // errors (e.g., timeouts) are already reported by the network client.
+        self.data_client
+            .get_peers_and_metadata()
+            .report_peer_event(self.peer, ReputationEvent::from(error_type));
     }
 }
 
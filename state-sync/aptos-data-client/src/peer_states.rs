@@ -12,6 +12,7 @@ use aptos_config::{
     network_id::{NetworkId, PeerNetworkId},
 };
 use aptos_logger::prelude::*;
+use aptos_network::application::reputation::ReputationEvent;
 use aptos_storage_service_types::{
     requests::StorageServiceRequest, responses::StorageServerSummary,
 };
@@ -42,6 +43,7 @@ const MALICIOUS_MULTIPLIER: f64 = 0.8;
 /// Ignore a peer when their score dips below this threshold.
 const IGNORE_PEER_THRESHOLD: f64 = 25.0;
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum ErrorType {
     /// A response or error that's not actively malicious but also doesn't help
     /// us make progress, e.g., timeouts, remote errors, invalid data, etc...
@@ -62,6 +64,15 @@ impl From<ResponseError> for ErrorType {
     }
 }
 
+impl From<ErrorType> for ReputationEvent {
+    fn from(error_type: ErrorType) -> Self {
+        match error_type {
+            ErrorType::NotUseful => ReputationEvent::UnhelpfulResponse,
+            ErrorType::Malicious => ReputationEvent::InvalidResponse,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct PeerState {
     /// The data client configuration
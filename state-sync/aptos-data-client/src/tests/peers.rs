@@ -11,9 +11,10 @@ use crate::{
     tests::{mock::MockNetwork, utils},
 };
 use aptos_config::{
-    config::{AptosDataClientConfig, AptosDataMultiFetchConfig},
+    config::{AptosDataClientConfig, AptosDataMultiFetchConfig, PeerReputationConfig},
     network_id::{NetworkId, PeerNetworkId},
 };
+use aptos_network::application::reputation::ReputationEvent;
 use aptos_storage_service_server::network::NetworkRequest;
 use aptos_storage_service_types::{
     requests::DataRequest,
@@ -465,6 +466,44 @@ async fn disable_ignoring_low_score_peers() {
     }
 }
 
+#[tokio::test]
+async fn banned_peers_are_not_selected() {
+    // Create a base config for a validator
+    let base_config = utils::create_validator_base_config();
+
+    // Create the mock network and client
+    let (mut mock_network, _, client, _) = MockNetwork::new(Some(base_config), None, None);
+
+    // Enable peer banning on the public network
+    let peers_and_metadata = client.get_peers_and_metadata();
+    peers_and_metadata
+        .set_peer_reputation_config(NetworkId::Public, PeerReputationConfig::default());
+
+    // Connect several low priority peers (on the public network)
+    let peer_1 = mock_network.add_peer(PeerPriority::LowPriority);
+    let peer_2 = mock_network.add_peer(PeerPriority::LowPriority);
+
+    // Verify both peers are regular peers
+    let (priority_peers, regular_peers) = client.get_priority_and_regular_peers().unwrap();
+    assert!(priority_peers.is_empty());
+    assert_eq!(regular_peers, hashset![peer_1, peer_2]);
+
+    // Report invalid responses for peer 1 until it is banned
+    while !peers_and_metadata.is_peer_banned(&peer_1) {
+        peers_and_metadata.report_peer_event(peer_1, ReputationEvent::InvalidResponse);
+    }
+
+    // Verify that only peer 2 is now selectable
+    let (priority_peers, regular_peers) = client.get_priority_and_regular_peers().unwrap();
+    assert!(priority_peers.is_empty());
+    assert_eq!(regular_peers, hashset![peer_2]);
+
+    // Reset the reputation of peer 1 and verify it is selectable again
+    peers_and_metadata.reset_peer_reputation(&peer_1);
+    let (_, regular_peers) = client.get_priority_and_regular_peers().unwrap();
+    assert_eq!(regular_peers, hashset![peer_1, peer_2]);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn disconnected_peers_garbage_collection() {
     // Ensure the properties hold for all peer priorities
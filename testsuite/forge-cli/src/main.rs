@@ -146,6 +146,11 @@ enum OperatorCommand {
 struct LocalSwarm {
     #[clap(long, help = "directory to build local swarm under")]
     swarmdir: Option<String>,
+    #[clap(
+        long,
+        help = "Put the validators behind proxies, so that chaos can be injected in the validator network"
+    )]
+    enable_chaos_proxies: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -319,10 +324,14 @@ fn main() -> Result<()> {
                             mempool_backlog: 5000,
                         }));
                     let swarm_dir = local_cfg.swarmdir.clone();
+                    let mut factory = LocalFactory::from_workspace(swarm_dir)?;
+                    if local_cfg.enable_chaos_proxies {
+                        factory = factory.with_chaos_proxies();
+                    }
                     run_forge(
                         duration,
                         test_suite,
-                        LocalFactory::from_workspace(swarm_dir)?,
+                        factory,
                         &args.options,
                         args.changelog.clone(),
                     )
@@ -1585,9 +1594,9 @@ fn individual_workload_tests(test_name: String) -> ForgeConfig {
                 };
                 job.transaction_mix_per_phase(vec![
                     // warmup
+                    vec![(account_creation_type.clone(), 1)],
                     vec![(account_creation_type, 1)],
-                    vec![(account_creation_type, 1)],
-                    vec![(write_type, 1)],
+                    vec![(write_type.clone(), 1)],
                     // cooldown
                     vec![(write_type, 1)],
                 ])
@@ -0,0 +1,592 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Chaos for the local swarm. Every validator is moved behind a userspace TCP proxy that
+//! listens on the validator network address advertised at genesis and forwards to the
+//! validator's actual listen address. Every validator network connection goes through the proxy
+//! of the dialed node, which applies the faults of the active chaos to the link it carries.
+//!
+//! The dialer is identified from the first bytes of the connection: the noise handshake starts
+//! with the client's peer id in the clear.
+//!
+//! The proxies are opt-in (see `LocalSwarm::build`), as they add a hop to every validator
+//! connection. Their coverage is limited:
+//! - Only the validator network is proxied. The VFN and public networks, and thus the traffic of
+//!   fullnodes, are never affected by chaos.
+//! - Only the validators created with the swarm are proxied. Chaos can't be applied to the links
+//!   of a validator added afterwards.
+//! - As the proxy sits above TCP, packet loss can't drop bytes without breaking the stream, so a
+//!   lost packet is emulated as the fixed retransmission delay it would cause
+//!   (`RETRANSMISSION_DELAY`, 200ms), not as actual loss.
+
+use super::LocalNode;
+use crate::{SwarmChaos, SwarmNetEm, SwarmNetworkDelay};
+use anyhow::{anyhow, bail, Result};
+use aptos_config::config::{OverrideNodeConfig, PersistableConfig};
+use aptos_infallible::RwLock;
+use aptos_logger::{debug, info, warn};
+use aptos_sdk::types::{
+    network_address::{NetworkAddress, Protocol},
+    PeerId,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    net::{Ipv4Addr, SocketAddr, TcpListener as StdTcpListener},
+    sync::Arc,
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+        TcpListener, TcpStream,
+    },
+    runtime::Runtime,
+    sync::mpsc,
+    time::Instant,
+};
+
+/// How long a lost packet delays the stream, i.e., the minimum TCP retransmission timeout.
+const RETRANSMISSION_DELAY: Duration = Duration::from_millis(200);
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+const BYTES_PER_MEGABYTE: u64 = 1024 * 1024;
+
+/// The faults applied to the traffic from one node to another.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LinkFaults {
+    pub blocked: bool,
+    pub delay: Duration,
+    pub jitter: Duration,
+    pub loss_percentage: u64,
+    pub loss_correlation_percentage: u64,
+    pub rate_bytes_per_sec: Option<u64>,
+}
+
+impl LinkFaults {
+    /// Combines the faults of two chaos applied to the same link.
+    fn merge(&mut self, other: &LinkFaults) {
+        self.blocked |= other.blocked;
+        self.delay += other.delay;
+        self.jitter += other.jitter;
+        if other.loss_percentage > self.loss_percentage {
+            self.loss_percentage = other.loss_percentage;
+            self.loss_correlation_percentage = other.loss_correlation_percentage;
+        }
+        self.rate_bytes_per_sec = match (self.rate_bytes_per_sec, other.rate_bytes_per_sec) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+    }
+}
+
+/// The faults of every link, derived from the active chaos.
+#[derive(Clone, Debug, Default)]
+pub struct ChaosRules {
+    links: HashMap<(PeerId, PeerId), LinkFaults>,
+}
+
+impl ChaosRules {
+    /// Builds the rules for the given chaos. `validators` are sorted by index.
+    pub fn new(chaos: &[SwarmChaos], validators: &[PeerId]) -> Result<Self> {
+        let mut rules = Self::default();
+        for chaos in chaos {
+            match chaos {
+                SwarmChaos::Delay(SwarmNetworkDelay {
+                    group_network_delays,
+                }) => {
+                    for group in group_network_delays {
+                        rules.add_between(
+                            &group.source_nodes,
+                            &group.target_nodes,
+                            LinkFaults {
+                                delay: Duration::from_millis(group.latency_ms),
+                                jitter: Duration::from_millis(group.jitter_ms),
+                                ..Default::default()
+                            },
+                        );
+                    }
+                },
+                SwarmChaos::Partition(partition) => {
+                    // Same as the k8s backend: the partitioned validators are cut off from all
+                    // the others, including each other.
+                    let partitioned_count =
+                        validators.len() * partition.partition_percentage as usize / 100;
+                    let partitioned = &validators[..partitioned_count];
+                    rules.add_between(
+                        partitioned,
+                        validators,
+                        LinkFaults {
+                            blocked: true,
+                            ..Default::default()
+                        },
+                    );
+                },
+                SwarmChaos::Bandwidth(bandwidth) => {
+                    for group in &bandwidth.group_network_bandwidths {
+                        rules.add_between(
+                            validators,
+                            validators,
+                            LinkFaults {
+                                rate_bytes_per_sec: Some(group.rate * BYTES_PER_MEGABYTE),
+                                ..Default::default()
+                            },
+                        );
+                    }
+                },
+                SwarmChaos::Loss(loss) => {
+                    rules.add_between(
+                        validators,
+                        validators,
+                        LinkFaults {
+                            loss_percentage: loss.loss_percentage,
+                            loss_correlation_percentage: loss.correlation_percentage,
+                            ..Default::default()
+                        },
+                    );
+                },
+                SwarmChaos::NetEm(SwarmNetEm { group_netems }) => {
+                    for group in group_netems {
+                        rules.add_between(
+                            &group.source_nodes,
+                            &group.target_nodes,
+                            LinkFaults {
+                                delay: Duration::from_millis(group.delay_latency_ms),
+                                jitter: Duration::from_millis(group.delay_jitter_ms),
+                                loss_percentage: group.loss_percentage,
+                                loss_correlation_percentage: group.loss_correlation_percentage,
+                                rate_bytes_per_sec: (group.rate_in_mbps > 0)
+                                    .then(|| group.rate_in_mbps * BYTES_PER_MEGABYTE),
+                                ..Default::default()
+                            },
+                        );
+                    }
+                },
+                SwarmChaos::CpuStress(_) => {
+                    bail!("CpuStress chaos is not supported by the local swarm")
+                },
+            }
+        }
+        Ok(rules)
+    }
+
+    /// Adds the faults to the traffic between the two groups, in both directions.
+    /// A link that's in both groups gets the faults once.
+    fn add_between(&mut self, sources: &[PeerId], targets: &[PeerId], faults: LinkFaults) {
+        let mut links = HashSet::new();
+        for source in sources {
+            for target in targets {
+                if source != target {
+                    links.insert((*source, *target));
+                    links.insert((*target, *source));
+                }
+            }
+        }
+        for link in links {
+            self.links.entry(link).or_default().merge(&faults);
+        }
+    }
+
+    pub fn link(&self, source: PeerId, target: PeerId) -> LinkFaults {
+        self.links
+            .get(&(source, target))
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// Schedules the chunks of one direction of a connection, emulating the link faults.
+#[derive(Debug)]
+pub struct LinkScheduler {
+    // When the link is done transmitting the previous chunks, for the rate limit.
+    next_free: Instant,
+    // The deadlines never go back in time, as the stream is ordered.
+    last_deadline: Instant,
+    last_lost: bool,
+}
+
+impl LinkScheduler {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            next_free: now,
+            last_deadline: now,
+            last_lost: false,
+        }
+    }
+
+    /// Returns when a chunk of `len` bytes read at `now` is to be forwarded.
+    /// `random` returns uniformly distributed values in [0, 1).
+    pub fn schedule(
+        &mut self,
+        faults: &LinkFaults,
+        len: usize,
+        now: Instant,
+        mut random: impl FnMut() -> f64,
+    ) -> Instant {
+        let mut sent = now.max(self.next_free);
+        if let Some(rate) = faults.rate_bytes_per_sec {
+            sent += Duration::from_secs_f64(len as f64 / rate.max(1) as f64);
+        }
+        self.next_free = sent;
+
+        let mut delay = faults.delay;
+        if !faults.jitter.is_zero() {
+            let jitter = faults.jitter.mul_f64(random() * 2.0);
+            delay = (delay + jitter).saturating_sub(faults.jitter);
+        }
+        let lost = if random() * 100.0 < faults.loss_correlation_percentage as f64 {
+            self.last_lost
+        } else {
+            random() * 100.0 < faults.loss_percentage as f64
+        };
+        self.last_lost = lost;
+        if lost {
+            delay += RETRANSMISSION_DELAY;
+        }
+
+        self.last_deadline = self.last_deadline.max(sent + delay);
+        self.last_deadline
+    }
+}
+
+/// The proxies of the local swarm and the chaos they apply.
+pub struct LocalSwarmChaos {
+    active_chaos: Vec<SwarmChaos>,
+    rules: Arc<RwLock<ChaosRules>>,
+    // The proxies run on their own runtime, so that they keep forwarding whatever runtime (if
+    // any) the test is blocked on.
+    runtime: Option<Runtime>,
+}
+
+impl fmt::Debug for LocalSwarmChaos {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalSwarmChaos")
+            .field("active_chaos", &self.active_chaos)
+            .finish()
+    }
+}
+
+impl LocalSwarmChaos {
+    pub fn new() -> Result<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .thread_name("local-swarm-chaos")
+            .worker_threads(2)
+            .enable_all()
+            .build()?;
+        Ok(Self {
+            active_chaos: vec![],
+            rules: Arc::new(RwLock::new(ChaosRules::default())),
+            runtime: Some(runtime),
+        })
+    }
+
+    /// Moves the validator network of the (not yet started) validator to a new port and starts a
+    /// proxy on the port advertised at genesis.
+    pub fn interpose(&self, validator: &mut LocalNode) -> Result<()> {
+        let mut override_config = OverrideNodeConfig::load_config(validator.config_path())?;
+        let config = override_config.override_config_mut();
+        let network = config
+            .validator_network
+            .as_mut()
+            .ok_or_else(|| anyhow!("Validator {} has no validator network", validator.name()))?;
+        let advertised_port = network.listen_address.find_port().ok_or_else(|| {
+            anyhow!(
+                "Validator {} listens on a non TCP address: {}",
+                validator.name(),
+                network.listen_address
+            )
+        })?;
+        let listen_port = aptos_config::utils::get_available_port();
+        network.listen_address = replace_port(&network.listen_address, listen_port)?;
+        *validator.config_mut() = config.clone();
+        override_config.save_config(validator.config_path())?;
+
+        // Bind now, so that the advertised port is held until the node starts.
+        let listener = StdTcpListener::bind((Ipv4Addr::UNSPECIFIED, advertised_port))?;
+        listener.set_nonblocking(true)?;
+        let upstream = SocketAddr::from((Ipv4Addr::LOCALHOST, listen_port));
+        let proxy = ChaosProxy {
+            peer_id: validator.peer_id(),
+            upstream,
+            rules: self.rules.clone(),
+        };
+        info!(
+            "Node {}: validator network proxied from port {} to port {}",
+            validator.name(),
+            advertised_port,
+            listen_port
+        );
+        self.runtime()?.spawn(proxy.run(listener));
+        Ok(())
+    }
+
+    pub fn inject(&mut self, chaos: SwarmChaos, validators: &[PeerId]) -> Result<()> {
+        let mut active_chaos = self.active_chaos.clone();
+        active_chaos.push(chaos);
+        self.update(active_chaos, validators)
+    }
+
+    pub fn remove(&mut self, chaos: &SwarmChaos, validators: &[PeerId]) -> Result<()> {
+        let mut active_chaos = self.active_chaos.clone();
+        let index = active_chaos
+            .iter()
+            .position(|active| active == chaos)
+            .ok_or_else(|| anyhow!("Chaos is not active: {:?}", chaos))?;
+        active_chaos.remove(index);
+        self.update(active_chaos, validators)
+    }
+
+    pub fn remove_all(&mut self) {
+        self.active_chaos.clear();
+        *self.rules.write() = ChaosRules::default();
+    }
+
+    fn update(&mut self, active_chaos: Vec<SwarmChaos>, validators: &[PeerId]) -> Result<()> {
+        let rules = ChaosRules::new(&active_chaos, validators)?;
+        *self.rules.write() = rules;
+        self.active_chaos = active_chaos;
+        Ok(())
+    }
+
+    fn runtime(&self) -> Result<&Runtime> {
+        self.runtime
+            .as_ref()
+            .ok_or_else(|| anyhow!("Local swarm chaos runtime is shut down"))
+    }
+}
+
+impl Drop for LocalSwarmChaos {
+    fn drop(&mut self) {
+        // The swarm may be dropped within an async context, where a runtime can't be dropped.
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+fn replace_port(address: &NetworkAddress, port: u16) -> Result<NetworkAddress> {
+    let protocols = address
+        .as_slice()
+        .iter()
+        .map(|protocol| match protocol {
+            Protocol::Tcp(_) => Protocol::Tcp(port),
+            protocol => protocol.clone(),
+        })
+        .collect();
+    Ok(NetworkAddress::from_protocols(protocols)?)
+}
+
+/// Forwards the validator network connections to one validator.
+struct ChaosProxy {
+    peer_id: PeerId,
+    upstream: SocketAddr,
+    rules: Arc<RwLock<ChaosRules>>,
+}
+
+impl ChaosProxy {
+    async fn run(self, listener: StdTcpListener) {
+        let listener = match TcpListener::from_std(listener) {
+            Ok(listener) => listener,
+            Err(err) => {
+                warn!(
+                    "Failed to start the chaos proxy of {}: {}",
+                    self.peer_id, err
+                );
+                return;
+            },
+        };
+        let proxy = Arc::new(self);
+        loop {
+            match listener.accept().await {
+                Ok((inbound, _)) => {
+                    let proxy = proxy.clone();
+                    tokio::spawn(async move {
+                        if let Err(err) = proxy.handle_connection(inbound).await {
+                            debug!(
+                                "Chaos proxy of {} connection closed: {}",
+                                proxy.peer_id, err
+                            );
+                        }
+                    });
+                },
+                Err(err) => warn!("Chaos proxy of {} failed to accept: {}", proxy.peer_id, err),
+            }
+        }
+    }
+
+    async fn handle_connection(&self, mut inbound: TcpStream) -> Result<()> {
+        // The noise handshake starts with the client's peer id.
+        let mut dialer = [0u8; PeerId::LENGTH];
+        inbound.read_exact(&mut dialer).await?;
+        let dialer_peer_id = PeerId::new(dialer);
+        if self.rules.read().link(dialer_peer_id, self.peer_id).blocked {
+            return Ok(());
+        }
+
+        let mut outbound = TcpStream::connect(self.upstream).await?;
+        outbound.write_all(&dialer).await?;
+        inbound.set_nodelay(true)?;
+        outbound.set_nodelay(true)?;
+
+        let (inbound_reader, inbound_writer) = inbound.into_split();
+        let (outbound_reader, outbound_writer) = outbound.into_split();
+        // Whichever direction ends first closes the connection.
+        tokio::select! {
+            result = self.forward(inbound_reader, outbound_writer, dialer_peer_id, self.peer_id) => result,
+            result = self.forward(outbound_reader, inbound_writer, self.peer_id, dialer_peer_id) => result,
+        }
+    }
+
+    async fn forward(
+        &self,
+        mut reader: OwnedReadHalf,
+        mut writer: OwnedWriteHalf,
+        source: PeerId,
+        target: PeerId,
+    ) -> Result<()> {
+        let (sender, mut receiver) = mpsc::unbounded_channel::<(Instant, Vec<u8>)>();
+        let write_task = async move {
+            while let Some((deadline, bytes)) = receiver.recv().await {
+                tokio::time::sleep_until(deadline).await;
+                writer.write_all(&bytes).await?;
+            }
+            writer.shutdown().await?;
+            Ok::<_, anyhow::Error>(())
+        };
+        let read_task = async move {
+            let mut scheduler = LinkScheduler::new(Instant::now());
+            let mut buffer = vec![0u8; READ_BUFFER_SIZE];
+            loop {
+                let read = reader.read(&mut buffer).await?;
+                if read == 0 {
+                    return Ok(());
+                }
+                let faults = self.rules.read().link(source, target);
+                if faults.blocked {
+                    bail!("Link from {} to {} is partitioned", source, target);
+                }
+                let deadline =
+                    scheduler.schedule(&faults, read, Instant::now(), rand::random::<f64>);
+                if sender.send((deadline, buffer[..read].to_vec())).is_err() {
+                    return Ok(());
+                }
+            }
+        };
+        let (read_result, write_result) = tokio::join!(read_task, write_task);
+        read_result.and(write_result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        GroupNetworkBandwidth, GroupNetworkDelay, SwarmNetworkBandwidth, SwarmNetworkLoss,
+        SwarmNetworkPartition,
+    };
+
+    fn validators(count: usize) -> Vec<PeerId> {
+        (0..count).map(|_| PeerId::random()).collect()
+    }
+
+    #[test]
+    fn test_partition_rules() {
+        let validators = validators(4);
+        let rules = ChaosRules::new(
+            &[SwarmChaos::Partition(SwarmNetworkPartition {
+                partition_percentage: 50,
+            })],
+            &validators,
+        )
+        .unwrap();
+
+        // The first half is cut off from everyone, the second half is not affected.
+        assert!(rules.link(validators[0], validators[1]).blocked);
+        assert!(rules.link(validators[0], validators[2]).blocked);
+        assert!(rules.link(validators[3], validators[1]).blocked);
+        assert!(!rules.link(validators[2], validators[3]).blocked);
+    }
+
+    #[test]
+    fn test_merged_rules() {
+        let validators = validators(3);
+        let delay = SwarmChaos::Delay(SwarmNetworkDelay {
+            group_network_delays: vec![GroupNetworkDelay {
+                name: "delay".to_string(),
+                source_nodes: vec![validators[0]],
+                target_nodes: vec![validators[1]],
+                latency_ms: 100,
+                jitter_ms: 10,
+                correlation_percentage: 0,
+            }],
+        });
+        let loss = SwarmChaos::Loss(SwarmNetworkLoss {
+            loss_percentage: 5,
+            correlation_percentage: 10,
+        });
+        let bandwidth = SwarmChaos::Bandwidth(SwarmNetworkBandwidth {
+            group_network_bandwidths: vec![GroupNetworkBandwidth {
+                name: "bandwidth".to_string(),
+                rate: 10,
+                limit: 0,
+                buffer: 0,
+            }],
+        });
+        let rules = ChaosRules::new(&[delay, loss, bandwidth], &validators).unwrap();
+
+        // The delay applies in both directions of the link.
+        for link in [
+            (validators[0], validators[1]),
+            (validators[1], validators[0]),
+        ] {
+            assert_eq!(
+                rules.link(link.0, link.1),
+                LinkFaults {
+                    blocked: false,
+                    delay: Duration::from_millis(100),
+                    jitter: Duration::from_millis(10),
+                    loss_percentage: 5,
+                    loss_correlation_percentage: 10,
+                    rate_bytes_per_sec: Some(10 * BYTES_PER_MEGABYTE),
+                }
+            );
+        }
+        assert_eq!(
+            rules.link(validators[0], validators[2]).delay,
+            Duration::ZERO
+        );
+        assert!(ChaosRules::new(&[], &validators)
+            .unwrap()
+            .link(validators[0], validators[1])
+            .eq(&LinkFaults::default()));
+    }
+
+    #[test]
+    fn test_link_scheduler() {
+        let now = Instant::now();
+        let faults = LinkFaults {
+            delay: Duration::from_millis(100),
+            rate_bytes_per_sec: Some(1000),
+            ..Default::default()
+        };
+        let mut scheduler = LinkScheduler::new(now);
+
+        // 500 bytes take 500ms to transmit at 1000 bytes/s, then the delay applies.
+        let deadline = scheduler.schedule(&faults, 500, now, || 0.5);
+        assert_eq!(deadline, now + Duration::from_millis(600));
+        // The next chunk waits for the previous one to be transmitted.
+        let deadline = scheduler.schedule(&faults, 500, now, || 0.5);
+        assert_eq!(deadline, now + Duration::from_millis(1100));
+
+        // A lost chunk is retransmitted, and later chunks are not reordered before it.
+        let lossy_faults = LinkFaults {
+            loss_percentage: 100,
+            ..Default::default()
+        };
+        let mut scheduler = LinkScheduler::new(now);
+        let deadline = scheduler.schedule(&lossy_faults, 1, now, || 0.5);
+        assert_eq!(deadline, now + RETRANSMISSION_DELAY);
+        let deadline = scheduler.schedule(&LinkFaults::default(), 1, now, || 0.5);
+        assert_eq!(deadline, now + RETRANSMISSION_DELAY);
+    }
+}
@@ -18,6 +18,7 @@ use std::{
 };
 
 mod cargo;
+mod chaos;
 mod node;
 mod swarm;
 pub use self::swarm::ActiveNodesGuard;
@@ -48,6 +49,7 @@ impl LocalVersion {
 pub struct LocalFactory {
     versions: Arc<HashMap<Version, LocalVersion>>,
     swarm_dir: Option<String>,
+    enable_chaos_proxies: bool,
 }
 
 impl LocalFactory {
@@ -55,9 +57,17 @@ impl LocalFactory {
         Self {
             versions: Arc::new(versions),
             swarm_dir,
+            enable_chaos_proxies: false,
         }
     }
 
+    /// Puts the validators of the swarms launched by this factory behind the chaos proxies, so
+    /// that tests can inject chaos in the validator network.
+    pub fn with_chaos_proxies(mut self) -> Self {
+        self.enable_chaos_proxies = true;
+        self
+    }
+
     pub fn from_workspace(swarm_dir: Option<String>) -> Result<Self> {
         let mut versions = HashMap::new();
         let new_version = cargo::get_aptos_node_binary_from_worktree().map(|(revision, bin)| {
@@ -124,6 +134,7 @@ impl LocalFactory {
         vfn_config: Option<NodeConfig>,
         init_genesis_stake: Option<InitGenesisStakeFn>,
         init_genesis_config: Option<InitGenesisConfigFn>,
+        enable_chaos_proxies: bool,
         guard: ActiveNodesGuard,
     ) -> Result<LocalSwarm>
     where
@@ -141,6 +152,7 @@ impl LocalFactory {
             init_genesis_config,
             swarmdir,
             genesis_framework,
+            enable_chaos_proxies,
             guard,
         )?;
 
@@ -209,6 +221,7 @@ impl Factory for LocalFactory {
                 None,
                 None,
                 None,
+                self.enable_chaos_proxies,
                 guard,
             )
             .await?;
@@ -2,6 +2,7 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use super::chaos::LocalSwarmChaos;
 use crate::{
     ChainInfo, FullNode, HealthCheckError, LocalNode, LocalVersion, Node, Swarm, SwarmChaos,
     SwarmExt, Validator, Version,
@@ -99,6 +100,8 @@ pub struct LocalSwarm {
     root_account: LocalAccount,
     chain_id: ChainId,
     root_key: ConfigKey<Ed25519PrivateKey>,
+    // Only set if the swarm was built with the chaos proxies enabled
+    chaos: Option<LocalSwarmChaos>,
 
     launched: bool,
     #[allow(dead_code)]
@@ -106,6 +109,9 @@ pub struct LocalSwarm {
 }
 
 impl LocalSwarm {
+    /// Builds the swarm. If `enable_chaos_proxies` is set, the validator network of every
+    /// validator is put behind a proxy, so that `SwarmChaos` can be injected (see the `chaos`
+    /// module for what the proxies cover).
     pub fn build<R>(
         rng: R,
         number_of_validators: NonZeroUsize,
@@ -116,6 +122,7 @@ impl LocalSwarm {
         init_genesis_config: Option<InitGenesisConfigFn>,
         dir: Option<PathBuf>,
         genesis_framework: Option<ReleaseBundle>,
+        enable_chaos_proxies: bool,
         guard: ActiveNodesGuard,
     ) -> Result<LocalSwarm>
     where
@@ -221,6 +228,17 @@ impl LocalSwarm {
             })
             .collect::<Result<HashMap<_, _>>>()?;
 
+        // Put every validator behind a proxy, so that chaos can be injected in the validator network
+        let chaos = if enable_chaos_proxies {
+            let chaos = LocalSwarmChaos::new()?;
+            for validator in validators.values_mut() {
+                chaos.interpose(validator)?;
+            }
+            Some(chaos)
+        } else {
+            None
+        };
+
         // We print out the root key to make it easy for users to deploy a local faucet
         let encoded_root_key = EncodingType::Hex.encode_key("root_key", &root_key)?;
         info!(
@@ -258,6 +276,7 @@ impl LocalSwarm {
             root_account,
             chain_id: ChainId::test(),
             root_key,
+            chaos,
             launched: false,
             guard,
         })
@@ -464,6 +483,18 @@ impl LocalSwarm {
     pub fn dir(&self) -> &Path {
         self.dir.as_ref()
     }
+
+    fn validator_peer_ids(&self) -> Vec<PeerId> {
+        self.validators()
+            .map(|validator| validator.peer_id())
+            .collect()
+    }
+
+    fn chaos_mut(&mut self) -> Result<&mut LocalSwarmChaos> {
+        self.chaos.as_mut().ok_or_else(|| {
+            anyhow!("Chaos requires the local swarm to be built with the chaos proxies enabled")
+        })
+    }
 }
 
 impl Drop for LocalSwarm {
@@ -616,16 +647,23 @@ impl Swarm for LocalSwarm {
         self.dir.display().to_string()
     }
 
-    async fn inject_chaos(&mut self, _chaos: SwarmChaos) -> Result<()> {
-        todo!()
+    async fn inject_chaos(&mut self, chaos: SwarmChaos) -> Result<()> {
+        info!("Injecting chaos: {:?}", chaos);
+        let validators = self.validator_peer_ids();
+        self.chaos_mut()?.inject(chaos, &validators)
     }
 
-    async fn remove_chaos(&mut self, _chaos: SwarmChaos) -> Result<()> {
-        todo!()
+    async fn remove_chaos(&mut self, chaos: SwarmChaos) -> Result<()> {
+        info!("Removing chaos: {:?}", chaos);
+        let validators = self.validator_peer_ids();
+        self.chaos_mut()?.remove(&chaos, &validators)
     }
 
     async fn remove_all_chaos(&mut self) -> Result<()> {
-        todo!()
+        if let Some(chaos) = self.chaos.as_mut() {
+            chaos.remove_all();
+        }
+        Ok(())
     }
 
     async fn ensure_no_validator_restart(&self) -> Result<()> {
@@ -50,6 +50,20 @@ impl TestReport {
         self.report_metric(test_name.clone(), "p50_latency", rate.p50_latency as f64);
         self.report_metric(test_name.clone(), "p90_latency", rate.p90_latency as f64);
         self.report_metric(test_name.clone(), "p99_latency", rate.p99_latency as f64);
+        for (stage, percentiles) in &stats.latency_report().stages {
+            for (name, value) in [
+                ("p50", percentiles.p50_ms),
+                ("p90", percentiles.p90_ms),
+                ("p99", percentiles.p99_ms),
+                ("p999", percentiles.p999_ms),
+            ] {
+                self.report_metric(
+                    test_name.clone(),
+                    format!("{}_{}_latency", stage.name(), name),
+                    value as f64,
+                );
+            }
+        }
         self.report_text(format!("{} : {}", test_name, rate));
     }
 
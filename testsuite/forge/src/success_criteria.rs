@@ -11,7 +11,7 @@ use crate::{
 use anyhow::{bail, Context};
 use aptos::node::analyze::fetch_metadata::FetchMetadata;
 use aptos_sdk::types::PeerId;
-use aptos_transaction_emitter_lib::{TxnStats, TxnStatsRate};
+use aptos_transaction_emitter_lib::{LatencyStage, TxnStats, TxnStatsRate};
 use prometheus_http_query::response::Sample;
 use std::{collections::BTreeMap, time::Duration};
 
@@ -143,6 +143,8 @@ pub struct SuccessCriteria {
     pub min_avg_tps: usize,
     latency_thresholds: Vec<(Duration, LatencyType)>,
     latency_breakdown_thresholds: Option<LatencyBreakdownThreshold>,
+    // Thresholds on the per-stage latencies, recorded by the open-loop emitter.
+    stage_latency_thresholds: Vec<(Duration, LatencyStage, LatencyType)>,
     check_no_restarts: bool,
     check_no_errors: bool,
     max_expired_tps: Option<usize>,
@@ -159,6 +161,7 @@ impl SuccessCriteria {
             min_avg_tps,
             latency_thresholds: Vec::new(),
             latency_breakdown_thresholds: None,
+            stage_latency_thresholds: Vec::new(),
             check_no_restarts: false,
             check_no_errors: true,
             max_expired_tps: None,
@@ -210,6 +213,20 @@ impl SuccessCriteria {
         self
     }
 
+    pub fn add_stage_latency_threshold(
+        mut self,
+        threshold_s: f32,
+        stage: LatencyStage,
+        latency_type: LatencyType,
+    ) -> Self {
+        self.stage_latency_thresholds.push((
+            Duration::from_secs_f32(threshold_s),
+            stage,
+            latency_type,
+        ));
+        self
+    }
+
     pub fn add_latency_breakdown_threshold(mut self, threshold: LatencyBreakdownThreshold) -> Self {
         self.latency_breakdown_thresholds = Some(threshold);
         self
@@ -282,6 +299,8 @@ impl SuccessCriteriaChecker {
             &no_traffic_name_addition,
         )?;
 
+        Self::check_stage_latency(&success_criteria.stage_latency_thresholds, stats)?;
+
         if let Some(latency_breakdown_thresholds) = &success_criteria.latency_breakdown_thresholds {
             latency_breakdown_thresholds
                 .ensure_threshold(latency_breakdown, &no_traffic_name_addition)?;
@@ -504,6 +523,51 @@ impl SuccessCriteriaChecker {
         Ok(())
     }
 
+    pub fn check_stage_latency(
+        stage_latency_thresholds: &[(Duration, LatencyStage, LatencyType)],
+        stats: &TxnStats,
+    ) -> anyhow::Result<()> {
+        let report = stats.latency_report();
+        let mut failures = Vec::new();
+        for (latency_threshold, stage, latency_type) in stage_latency_thresholds {
+            let Some(percentiles) = report.stages.get(stage) else {
+                failures.push(format!(
+                    "No {:?} latency was recorded, is the emitter running open-loop?",
+                    stage
+                ));
+                continue;
+            };
+            let latency = Duration::from_millis(match latency_type {
+                LatencyType::Average => percentiles.mean_ms as u64,
+                LatencyType::P50 => percentiles.p50_ms,
+                LatencyType::P90 => percentiles.p90_ms,
+                LatencyType::P99 => percentiles.p99_ms,
+            });
+            if latency > *latency_threshold {
+                failures.push(format!(
+                    "{:?} {:?} latency is {}s and exceeds limit of {}s",
+                    latency_type,
+                    stage,
+                    latency.as_secs_f32(),
+                    latency_threshold.as_secs_f32()
+                ));
+            } else {
+                println!(
+                    "{:?} {:?} latency is {}s and is within limit of {}s",
+                    latency_type,
+                    stage,
+                    latency.as_secs_f32(),
+                    latency_threshold.as_secs_f32()
+                );
+            }
+        }
+        if !failures.is_empty() {
+            bail!("Failed stage latency check, for {:?}", failures);
+        } else {
+            Ok(())
+        }
+    }
+
     pub fn check_latency(
         latency_thresholds: &[(Duration, LatencyType)],
         stats_rate: &TxnStatsRate,
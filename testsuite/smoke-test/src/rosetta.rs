@@ -27,17 +27,19 @@ use aptos_rosetta::{
     common::{native_coin, BlockHash, BLOCKCHAIN, Y2K_MS},
     types::{
         AccountBalanceRequest, AccountBalanceResponse, AccountIdentifier, BlockIdentifier,
-        BlockRequest, BlockResponse, NetworkIdentifier, NetworkRequest, Operation,
-        OperationStatusType, OperationType, PartialBlockIdentifier, TransactionIdentifier,
-        TransactionType, STAKING_CONTRACT_MODULE, SWITCH_OPERATOR_WITH_SAME_COMMISSION_FUNCTION,
+        BlockRequest, BlockResponse, Currency, CurrencyMetadata, NetworkIdentifier, NetworkRequest,
+        Operation, OperationStatusType, OperationType, PartialBlockIdentifier,
+        TransactionIdentifier, TransactionType, STAKING_CONTRACT_MODULE,
+        SWITCH_OPERATOR_WITH_SAME_COMMISSION_FUNCTION,
     },
     ROSETTA_VERSION,
 };
 use aptos_sdk::{transaction_builder::TransactionFactory, types::LocalAccount};
 use aptos_types::{
-    account_address::AccountAddress,
+    account_address::{create_object_address, AccountAddress},
     account_config::CORE_CODE_ADDRESS,
     chain_id::ChainId,
+    move_utils::MemberId,
     on_chain_config::{GasScheduleV2, OnChainRandomnessConfig},
     transaction::SignedTransaction,
 };
@@ -55,6 +57,8 @@ const EPOCH_DURATION_S: u64 = 5;
 const DEFAULT_TRANSFER_AMOUNT: u64 = 20;
 const DEFAULT_MAX_WAIT_S: u64 = 5;
 const DEFAULT_INTERVAL_MS: u64 = 100;
+const FA_COIN_SYMBOL: &str = "FA";
+const FA_COIN_DECIMALS: u8 = 8;
 static DEFAULT_MAX_WAIT_DURATION: Duration = Duration::from_secs(DEFAULT_MAX_WAIT_S);
 static DEFAULT_INTERVAL_DURATION: Duration = Duration::from_millis(DEFAULT_INTERVAL_MS);
 
@@ -114,6 +118,7 @@ async fn setup_test(
             validator.rest_api_endpoint(),
         )),
         cli.addresses(),
+        HashSet::from([fa_coin_currency(cli.account_id(0))]),
     )
     .await
     .unwrap();
@@ -126,6 +131,20 @@ async fn setup_test(
     (swarm, cli, faucet, rosetta_client)
 }
 
+/// The fungible asset of the `fa_coin` example, once it's published by the account
+fn fa_coin_currency(publisher: AccountAddress) -> Currency {
+    Currency {
+        symbol: FA_COIN_SYMBOL.to_string(),
+        decimals: FA_COIN_DECIMALS,
+        metadata: Some(CurrencyMetadata {
+            move_type: None,
+            fa_address: Some(
+                create_object_address(publisher, FA_COIN_SYMBOL.as_bytes()).to_hex_literal(),
+            ),
+        }),
+    }
+}
+
 #[tokio::test]
 async fn test_block_transactions() {
     const NUM_TXNS_PER_PAGE: u16 = 2;
@@ -1907,6 +1926,157 @@ async fn test_invalid_transaction_gas_charged() {
     );
 }
 
+#[tokio::test]
+async fn test_fungible_asset_transfer() {
+    const MINT_AMOUNT: u64 = 1000;
+    const TRANSFER_AMOUNT: u64 = 400;
+
+    let (swarm, mut cli, _faucet, rosetta_client) = setup_simple_test(2).await;
+    let chain_id = swarm.chain_id();
+    let publisher = cli.account_id(0);
+    let receiver = cli.account_id(1);
+    let currency = fa_coin_currency(publisher);
+    let fa_address = create_object_address(publisher, FA_COIN_SYMBOL.as_bytes());
+
+    // Publish the fungible asset, which is a supported currency of the rosetta server
+    cli.init_move_dir();
+    let mut package_addresses = BTreeMap::new();
+    package_addresses.insert("FACoin", "_");
+    cli.init_package(
+        "FACoin".to_string(),
+        package_addresses,
+        Some(CliTestFramework::aptos_framework_dir()),
+    )
+    .await
+    .unwrap();
+    cli.add_file_in_package(
+        "sources/FACoin.move",
+        include_str!(
+            "../../../aptos-move/move-examples/fungible_asset/fa_coin/sources/FACoin.move"
+        )
+        .to_string(),
+    );
+    let publisher_str = publisher.to_hex_literal();
+    let mut named_addresses = BTreeMap::new();
+    named_addresses.insert("FACoin", publisher_str.as_str());
+    cli.publish_package(0, None, named_addresses, None)
+        .await
+        .unwrap();
+
+    let publisher_arg = format!("address:{}", publisher_str);
+    let mint_amount_arg = format!("u64:{}", MINT_AMOUNT);
+    cli.run_function(
+        0,
+        None,
+        MemberId::from_str(&format!("{}::fa_coin::mint", publisher_str)).unwrap(),
+        vec![publisher_arg.as_str(), mint_amount_arg.as_str()],
+        vec![],
+    )
+    .await
+    .unwrap();
+
+    // Transfer the fungible asset between primary stores
+    let metadata_arg = format!("address:{}", fa_address.to_hex_literal());
+    let receiver_arg = format!("address:{}", receiver.to_hex_literal());
+    let transfer_amount_arg = format!("u64:{}", TRANSFER_AMOUNT);
+    let response = cli
+        .run_function(
+            0,
+            None,
+            MemberId::from_str("0x1::primary_fungible_store::transfer").unwrap(),
+            vec![
+                metadata_arg.as_str(),
+                receiver_arg.as_str(),
+                transfer_amount_arg.as_str(),
+            ],
+            vec!["0x1::fungible_asset::Metadata"],
+        )
+        .await
+        .unwrap();
+    let version = response.version.unwrap();
+
+    let rest_client = swarm.validators().next().unwrap().rest_client();
+    let block_height = rest_client
+        .get_block_by_version_bcs(version, false)
+        .await
+        .unwrap()
+        .into_inner()
+        .block_height;
+    let block = rosetta_client
+        .block(&BlockRequest::by_index(chain_id, block_height))
+        .await
+        .unwrap()
+        .block;
+    let rosetta_txn = block
+        .transactions
+        .iter()
+        .find(|txn| txn.metadata.version.0 == version)
+        .unwrap();
+
+    // The transfer shows up in the fungible asset, and gas only as the fee of the native coin
+    let operations: Vec<_> = rosetta_txn
+        .operations
+        .iter()
+        .map(|operation| {
+            let amount = operation.amount.as_ref().unwrap();
+            (
+                operation.operation_type.clone(),
+                operation.account.clone().unwrap(),
+                amount.currency.clone(),
+                amount.value.clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            (
+                OperationType::Withdraw.to_string(),
+                AccountIdentifier::base_account(publisher),
+                currency.clone(),
+                format!("-{}", TRANSFER_AMOUNT),
+            ),
+            (
+                OperationType::Deposit.to_string(),
+                AccountIdentifier::base_account(receiver),
+                currency.clone(),
+                TRANSFER_AMOUNT.to_string(),
+            ),
+            (
+                OperationType::Fee.to_string(),
+                AccountIdentifier::base_account(publisher),
+                native_coin(),
+                format!(
+                    "-{}",
+                    response.gas_used.unwrap() * response.gas_unit_price.unwrap()
+                ),
+            ),
+        ],
+        operations
+    );
+
+    // And in the balances of the fungible asset
+    for (account, expected_balance) in [
+        (publisher, MINT_AMOUNT - TRANSFER_AMOUNT),
+        (receiver, TRANSFER_AMOUNT),
+    ] {
+        let request = AccountBalanceRequest {
+            network_identifier: chain_id.into(),
+            account_identifier: AccountIdentifier::base_account(account),
+            block_identifier: Some(PartialBlockIdentifier {
+                index: Some(block_height),
+                hash: None,
+            }),
+            currencies: Some(vec![currency.clone()]),
+        };
+        let response = try_until_ok_default(|| rosetta_client.account_balance(&request))
+            .await
+            .unwrap();
+        assert_eq!(1, response.balances.len());
+        assert_eq!(currency, response.balances[0].currency);
+        assert_eq!(expected_balance.to_string(), response.balances[0].value);
+    }
+}
+
 fn assert_failed_transfer_transaction(
     sender: AccountAddress,
     receiver: AccountAddress,
@@ -29,6 +29,7 @@ pub struct SwarmBuilder {
     vfn_config: Option<NodeConfig>,
     init_genesis_stake: Option<InitGenesisStakeFn>,
     init_genesis_config: Option<InitGenesisConfigFn>,
+    enable_chaos_proxies: bool,
 }
 
 impl SwarmBuilder {
@@ -42,6 +43,7 @@ impl SwarmBuilder {
             vfn_config: None,
             init_genesis_stake: None,
             init_genesis_config: None,
+            enable_chaos_proxies: false,
         }
     }
 
@@ -84,6 +86,13 @@ impl SwarmBuilder {
         self
     }
 
+    /// Puts the validators behind proxies, so that the test can inject chaos in the validator
+    /// network
+    pub fn with_chaos_proxies(mut self) -> Self {
+        self.enable_chaos_proxies = true;
+        self
+    }
+
     // Gas is not enabled with this setup, it's enabled via forge instance.
     pub async fn build_inner(&mut self) -> anyhow::Result<LocalSwarm> {
         ::aptos_logger::Logger::new().init();
@@ -118,6 +127,7 @@ impl SwarmBuilder {
                         (init_genesis_config)(genesis_config);
                     }
                 })),
+                builder.enable_chaos_proxies,
                 guard,
             )
             .await
@@ -12,6 +12,7 @@ pub mod new;
 pub mod prove;
 pub mod test;
 pub mod test_validation;
+pub mod update_lock;
 
 use move_package::source_package::layout::SourcePackageLayout;
 use std::path::PathBuf;
@@ -0,0 +1,20 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::reroot_path;
+use clap::*;
+use move_package::BuildConfig;
+use std::path::PathBuf;
+
+/// Pin the latest version of the dependencies of the package at `path` in its Move.lock,
+/// including dev dependencies. If no path is provided defaults to current directory.
+#[derive(Parser)]
+#[clap(name = "update-lock")]
+pub struct UpdateLock;
+
+impl UpdateLock {
+    pub fn execute(self, path: Option<PathBuf>, config: BuildConfig) -> anyhow::Result<()> {
+        let rerooted_path = reroot_path(path)?;
+        config.update_lock_file_for_package(&rerooted_path, &mut std::io::stdout())
+    }
+}
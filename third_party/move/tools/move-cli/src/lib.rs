@@ -5,6 +5,7 @@
 use base::{
     build::Build, coverage::Coverage, disassemble::Disassemble, docgen::Docgen, errmap::Errmap,
     movey_login::MoveyLogin, movey_upload::MoveyUpload, new::New, prove::Prove, test::Test,
+    update_lock::UpdateLock,
 };
 use move_package::BuildConfig;
 
@@ -72,6 +73,7 @@ pub enum Command {
     New(New),
     Prove(Prove),
     Test(Test),
+    UpdateLock(UpdateLock),
     /// Execute a sandbox command.
     #[clap(name = "sandbox")]
     Sandbox {
@@ -113,6 +115,7 @@ pub fn run_cli(
             genesis,
             Some(cost_table.clone()),
         ),
+        Command::UpdateLock(c) => c.execute(move_args.package_path, move_args.build_config),
         Command::Sandbox { storage_dir, cmd } => cmd.handle_command(
             natives,
             cost_table,
@@ -14,7 +14,10 @@ use crate::{
         build_plan::BuildPlan, compiled_package::CompiledPackage, model_builder::ModelBuilder,
     },
     package_lock::PackageLock,
-    resolution::resolution_graph::{ResolutionGraph, ResolvedGraph},
+    resolution::{
+        lock_file::{self, LockFile},
+        resolution_graph::{ResolutionGraph, ResolvedGraph},
+    },
     source_package::manifest_parser,
 };
 use anyhow::{bail, Result};
@@ -148,6 +151,11 @@ pub struct BuildConfig {
     #[clap(long = "skip-fetch-latest-git-deps", global = true)]
     pub skip_fetch_latest_git_deps: bool,
 
+    /// Fail if the dependencies differ from the ones pinned in Move.lock, instead of updating it
+    #[clap(long = "locked", global = true)]
+    #[serde(default)]
+    pub locked: bool,
+
     #[clap(flatten)]
     pub compiler_config: CompilerConfig,
 }
@@ -255,7 +263,14 @@ impl BuildConfig {
         // This should be locked as it inspects the environment for `MOVE_HOME` which could
         // possibly be set by a different process in parallel.
         let manifest = manifest_parser::parse_source_manifest(toml_manifest)?;
-        ResolutionGraph::download_dependency_repos(&manifest, self, &path, writer)?;
+        let lock_file = LockFile::read(&path)?;
+        ResolutionGraph::download_dependency_repos(
+            &manifest,
+            self,
+            &path,
+            lock_file.as_ref(),
+            writer,
+        )?;
         mutx.unlock();
         Ok(())
     }
@@ -275,12 +290,43 @@ impl BuildConfig {
         // This should be locked as it inspects the environment for `MOVE_HOME` which could
         // possibly be set by a different process in parallel.
         let manifest = manifest_parser::parse_source_manifest(toml_manifest)?;
-        let resolution_graph = ResolutionGraph::new(manifest, path, self, writer)?;
-        let ret = resolution_graph.resolve();
+        let lock_file = LockFile::read(&path)?;
+        let resolution_graph =
+            ResolutionGraph::new_with_lock_file(manifest, path, self, lock_file.as_ref(), writer)?;
+        let ret = resolution_graph.resolve().and_then(|resolved_graph| {
+            lock_file::check_or_update(&resolved_graph, lock_file, writer)?;
+            Ok(resolved_graph)
+        });
         mutx.unlock();
         ret
     }
 
+    /// Pins the latest version of the dependencies of the package at `path` in its Move.lock,
+    /// ignoring the versions it currently pins. Dev dependencies are pinned as well.
+    pub fn update_lock_file_for_package<W: Write>(
+        mut self,
+        path: &Path,
+        writer: &mut W,
+    ) -> Result<()> {
+        if self.locked {
+            bail!("Cannot update the lock file with --locked")
+        }
+        self.dev_mode = true;
+        self.skip_fetch_latest_git_deps = false;
+        let path = SourcePackageLayout::try_find_root(path)?;
+        let toml_manifest =
+            self.parse_toml_manifest(path.join(SourcePackageLayout::Manifest.path()))?;
+        let mutx = PackageLock::lock();
+        // This should be locked as it inspects the environment for `MOVE_HOME` which could
+        // possibly be set by a different process in parallel.
+        let manifest = manifest_parser::parse_source_manifest(toml_manifest)?;
+        let resolution_graph =
+            ResolutionGraph::new_with_lock_file(manifest, path.clone(), self, None, writer)?;
+        LockFile::from_graph(&resolution_graph)?.write(&path)?;
+        mutx.unlock();
+        Ok(())
+    }
+
     fn parse_toml_manifest(&self, path: PathBuf) -> Result<toml::Value> {
         let manifest_string = std::fs::read_to_string(path)?;
         manifest_parser::parse_move_manifest_string(manifest_string)
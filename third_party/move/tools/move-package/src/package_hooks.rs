@@ -27,6 +27,13 @@ pub trait PackageHooks {
         dep_name: Symbol,
         info: &CustomDepInfo,
     ) -> anyhow::Result<()>;
+
+    /// Returns the version of a custom dependency resolved by `resolve_custom_dependency`, e.g.,
+    /// the upgrade number of a package downloaded from a node, so that it is recorded in the
+    /// lock file. Returns `None` if versions are not tracked.
+    fn custom_dependency_version(&self, _info: &CustomDepInfo) -> anyhow::Result<Option<u64>> {
+        Ok(None)
+    }
 }
 static HOOKS: Lazy<Mutex<Option<Box<dyn PackageHooks + Send + Sync>>>> =
     Lazy::new(|| Mutex::new(None));
@@ -48,6 +55,15 @@ pub(crate) fn resolve_custom_dependency(
     }
 }
 
+/// Calls any registered hook to return the version of a resolved custom dependency.
+pub(crate) fn custom_dependency_version(info: &CustomDepInfo) -> anyhow::Result<Option<u64>> {
+    if let Some(hooks) = &*HOOKS.lock().unwrap() {
+        hooks.custom_dependency_version(info)
+    } else {
+        Ok(None)
+    }
+}
+
 pub(crate) fn custom_dependency_key() -> Option<String> {
     if let Some(hooks) = &*HOOKS.lock().unwrap() {
         hooks.custom_dependency_key()
@@ -0,0 +1,371 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The `Move.lock` file of a package, which pins the dependencies it was built with.
+//!
+//! For each dependency in the package graph, the lock file records the digest of its sources, the
+//! commit a git dependency was resolved to, and the upgrade number of an on-chain dependency.
+//! Git dependencies are checked out at their locked commit rather than at the latest state of
+//! their `rev`, until the lock file is explicitly updated. On-chain dependencies can only be
+//! downloaded as currently published, so they are compared against the lock file instead.
+//!
+//! Not to be confused with `PackageLock`, which serializes accesses to the file system.
+
+use crate::{
+    package_hooks,
+    resolution::{digest::compute_digest, resolution_graph::ResolutionGraph},
+    source_package::{
+        layout::SourcePackageLayout,
+        parsed_manifest::{GitInfo, PackageName},
+    },
+};
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt, io::Write, path::Path, process::Command};
+
+/// The version of the lock file format
+const LOCK_FILE_VERSION: u64 = 1;
+
+const LOCK_FILE_HEADER: &str =
+    "# @generated by the Move package system from the dependencies in Move.toml.\n\
+    # Update it with the package update command rather than by hand.\n\n";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LockFile {
+    pub version: u64,
+    /// The dependencies in the package graph, by package name
+    #[serde(default, rename = "package")]
+    pub packages: BTreeMap<String, LockedPackage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LockedPackage {
+    /// The digest of the manifest and sources of the package, regardless of the build mode
+    pub source_digest: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git: Option<LockedGitSource>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_chain: Option<LockedOnChainSource>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LockedGitSource {
+    pub url: String,
+    /// The revision given in the manifest, e.g., a branch
+    pub rev: String,
+    pub subdir: String,
+    /// The commit the revision was resolved to
+    pub commit: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LockedOnChainSource {
+    pub node_url: String,
+    pub address: String,
+    /// Only known if the registered package hooks provide it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upgrade_number: Option<u64>,
+}
+
+impl LockFile {
+    /// Reads the lock file of the package at `root_path`, if it has one.
+    pub fn read(root_path: &Path) -> Result<Option<Self>> {
+        let path = root_path.join(SourcePackageLayout::LockFile.path());
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let contents = std::fs::read_to_string(&path)?;
+        let lock_file: Self = toml::from_str(&contents)
+            .with_context(|| format!("Unable to parse lock file {}", path.display()))?;
+        if lock_file.version != LOCK_FILE_VERSION {
+            bail!(
+                "Unsupported version {} of lock file {}, expected version {}",
+                lock_file.version,
+                path.display(),
+                LOCK_FILE_VERSION
+            )
+        }
+        Ok(Some(lock_file))
+    }
+
+    pub fn write(&self, root_path: &Path) -> Result<()> {
+        let path = root_path.join(SourcePackageLayout::LockFile.path());
+        let contents = toml::to_string(self)?;
+        std::fs::write(&path, format!("{}{}", LOCK_FILE_HEADER, contents))
+            .with_context(|| format!("Unable to write lock file {}", path.display()))
+    }
+
+    /// Builds the lock file pinning the dependencies of a package graph. The graph doesn't need
+    /// its named addresses to be resolved.
+    pub fn from_graph<T>(graph: &ResolutionGraph<T>) -> Result<Self> {
+        let root_name = graph.root_package.package.name;
+        let mut packages = BTreeMap::new();
+        for (name, package) in &graph.package_table {
+            if *name != root_name {
+                packages.insert(name.to_string(), LockedPackage {
+                    source_digest: lock_digest(&package.package_path)?,
+                    git: None,
+                    on_chain: None,
+                });
+            }
+        }
+
+        // Where a package comes from is given by the dependencies on it
+        for package in graph.package_table.values() {
+            let manifest = &package.source_package;
+            for (dep_name, dep) in manifest
+                .dependencies
+                .iter()
+                .chain(manifest.dev_dependencies.iter())
+            {
+                // Dev dependencies are not part of the graph outside of dev mode
+                let locked = match packages.get_mut(dep_name.as_str()) {
+                    Some(locked) => locked,
+                    None => continue,
+                };
+                if let Some(git_info) = &dep.git_info {
+                    locked.git = Some(LockedGitSource {
+                        url: git_info.git_url.to_string(),
+                        rev: git_info.git_rev.to_string(),
+                        subdir: git_info.subdir.to_string_lossy().into_owned(),
+                        commit: git_head_commit(&git_info.download_to)?,
+                    });
+                }
+                if let Some(node_info) = &dep.node_info {
+                    locked.on_chain = Some(LockedOnChainSource {
+                        node_url: node_info.node_url.to_string(),
+                        address: node_info.package_address.to_string(),
+                        upgrade_number: package_hooks::custom_dependency_version(node_info)?,
+                    });
+                }
+            }
+        }
+
+        Ok(Self {
+            version: LOCK_FILE_VERSION,
+            packages,
+        })
+    }
+
+    /// Returns the commit a git dependency is pinned to, if it is locked with the same git
+    /// source as in the manifest.
+    pub fn locked_commit(&self, dep_name: PackageName, git_info: &GitInfo) -> Option<&str> {
+        let git = self.packages.get(dep_name.as_str())?.git.as_ref()?;
+        if git.url == git_info.git_url.as_str()
+            && git.rev == git_info.git_rev.as_str()
+            && git.subdir == git_info.subdir.to_string_lossy()
+        {
+            Some(git.commit.as_str())
+        } else {
+            None
+        }
+    }
+
+    /// Checks that every package of `resolved` is pinned to the same source in this lock file.
+    /// Packages only in this lock file are ignored, as they may be dev dependencies.
+    pub fn check_drift(&self, resolved: &LockFile) -> Result<()> {
+        let mut drifts = vec![];
+        for (name, package) in &resolved.packages {
+            let locked = match self.packages.get(name) {
+                Some(locked) => locked,
+                None => {
+                    drifts.push(format!("'{}' is not in the lock file", name));
+                    continue;
+                },
+            };
+            if locked.git != package.git || locked.on_chain != package.on_chain {
+                drifts.push(format!(
+                    "'{}' resolved to {} but {} is locked",
+                    name,
+                    package.source(),
+                    locked.source()
+                ));
+            } else if locked.source_digest != package.source_digest {
+                drifts.push(format!(
+                    "'{}' has source digest {} but {} is locked",
+                    name, package.source_digest, locked.source_digest
+                ));
+            }
+        }
+        if !drifts.is_empty() {
+            bail!(
+                "Dependencies do not match {}:\n  {}\nBuild without --locked or update the lock file to accept the changes",
+                SourcePackageLayout::LockFile.location_str(),
+                drifts.join("\n  ")
+            )
+        }
+        Ok(())
+    }
+
+    /// Updates the packages of `resolved` in this lock file, and returns true if any changed.
+    /// Other packages are kept, as they may be dev dependencies.
+    pub fn merge(&mut self, resolved: LockFile) -> bool {
+        let mut changed = false;
+        for (name, package) in resolved.packages {
+            if self.packages.get(&name) != Some(&package) {
+                self.packages.insert(name, package);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    fn has_remote_packages(&self) -> bool {
+        self.packages
+            .values()
+            .any(|package| package.git.is_some() || package.on_chain.is_some())
+    }
+}
+
+impl LockedPackage {
+    fn source(&self) -> Source<'_> {
+        Source(self)
+    }
+}
+
+struct Source<'a>(&'a LockedPackage);
+
+impl fmt::Display for Source<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.0.git, &self.0.on_chain) {
+            (Some(git), _) => write!(
+                f,
+                "git {} rev {} subdir '{}' at commit {}",
+                git.url, git.rev, git.subdir, git.commit
+            ),
+            (None, Some(on_chain)) => {
+                write!(f, "{} at {}", on_chain.address, on_chain.node_url)?;
+                if let Some(upgrade_number) = on_chain.upgrade_number {
+                    write!(f, " with upgrade number {}", upgrade_number)?;
+                }
+                Ok(())
+            },
+            (None, None) => write!(f, "a local path"),
+        }
+    }
+}
+
+/// Checks the dependencies of a resolved package graph against the lock file for `--locked`
+/// builds. Otherwise, records them in the lock file if the package has one or has remote
+/// dependencies.
+pub(crate) fn check_or_update<T, W: Write>(
+    graph: &ResolutionGraph<T>,
+    lock_file: Option<LockFile>,
+    writer: &mut W,
+) -> Result<()> {
+    let resolved = LockFile::from_graph(graph)?;
+    match lock_file {
+        Some(lock_file) if graph.build_options.locked => lock_file.check_drift(&resolved),
+        None if graph.build_options.locked => bail!(
+            "Package has no {} to build with --locked",
+            SourcePackageLayout::LockFile.location_str()
+        ),
+        Some(mut lock_file) => {
+            if lock_file.merge(resolved) {
+                writeln!(
+                    writer,
+                    "{} {}",
+                    "UPDATING".bold().green(),
+                    SourcePackageLayout::LockFile.location_str()
+                )?;
+                lock_file.write(&graph.root_package_path)?;
+            }
+            Ok(())
+        },
+        None => {
+            if resolved.has_remote_packages() {
+                resolved.write(&graph.root_package_path)?;
+            }
+            Ok(())
+        },
+    }
+}
+
+/// The digest of a package recorded in the lock file, which covers the manifest, sources and
+/// scripts, so that it doesn't depend on the build mode.
+fn lock_digest(package_path: &Path) -> Result<String> {
+    let paths = [
+        SourcePackageLayout::Sources,
+        SourcePackageLayout::Scripts,
+        SourcePackageLayout::Manifest,
+    ]
+    .iter()
+    .map(|layout_path| package_path.join(layout_path.path()))
+    .filter(|path| path.exists())
+    .collect::<Vec<_>>();
+    Ok(compute_digest(&paths)?.to_string())
+}
+
+pub(crate) fn git_head_commit(repo_path: &Path) -> Result<String> {
+    let output = Command::new("git")
+        .args(["-C", &repo_path.display().to_string(), "rev-parse", "HEAD"])
+        .output()
+        .with_context(|| format!("Failed to get the Git commit of {}", repo_path.display()))?;
+    if !output.status.success() {
+        bail!(
+            "Failed to get the Git commit of {} | Exit status: {}",
+            repo_path.display(),
+            output.status
+        )
+    }
+    Ok(String::from_utf8(output.stdout)?.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LockFile, LockedGitSource, LockedPackage, LOCK_FILE_VERSION};
+    use std::collections::BTreeMap;
+
+    fn lock_file(commit: &str, source_digest: &str) -> LockFile {
+        let mut packages = BTreeMap::new();
+        packages.insert("MoveStdlib".to_string(), LockedPackage {
+            source_digest: source_digest.to_string(),
+            git: Some(LockedGitSource {
+                url: "https://github.com/move-language/move.git".to_string(),
+                rev: "main".to_string(),
+                subdir: "language/move-stdlib".to_string(),
+                commit: commit.to_string(),
+            }),
+            on_chain: None,
+        });
+        LockFile {
+            version: LOCK_FILE_VERSION,
+            packages,
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(LockFile::read(dir.path()).unwrap(), None);
+
+        let lock_file = lock_file("abc", "DIGEST");
+        lock_file.write(dir.path()).unwrap();
+        assert_eq!(LockFile::read(dir.path()).unwrap(), Some(lock_file));
+    }
+
+    #[test]
+    fn test_check_drift() {
+        let locked = lock_file("abc", "DIGEST");
+        locked.check_drift(&lock_file("abc", "DIGEST")).unwrap();
+
+        let err = locked.check_drift(&lock_file("def", "DIGEST")).unwrap_err();
+        assert!(err.to_string().contains("at commit def but"));
+
+        let err = locked.check_drift(&lock_file("abc", "OTHER")).unwrap_err();
+        assert!(err.to_string().contains("has source digest OTHER"));
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut locked = lock_file("abc", "DIGEST");
+        assert!(!locked.merge(lock_file("abc", "DIGEST")));
+        assert!(locked.merge(lock_file("def", "DIGEST")));
+        assert_eq!(locked, lock_file("def", "DIGEST"));
+    }
+}
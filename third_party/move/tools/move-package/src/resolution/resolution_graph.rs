@@ -4,12 +4,15 @@
 
 use crate::{
     package_hooks,
-    resolution::digest::compute_digest,
+    resolution::{
+        digest::compute_digest,
+        lock_file::{git_head_commit, LockFile},
+    },
     source_package::{
         layout::SourcePackageLayout,
         manifest_parser::{parse_move_manifest_string, parse_source_manifest},
         parsed_manifest::{
-            Dependencies, Dependency, FileName, NamedAddress, PackageDigest, PackageName,
+            Dependencies, Dependency, FileName, GitInfo, NamedAddress, PackageDigest, PackageName,
             SourceManifest, SubstOrRename,
         },
     },
@@ -94,10 +97,31 @@ pub struct ResolutionPackage<T> {
 }
 
 impl ResolvingGraph {
+    /// Builds the graph of the package at `root_package_path`, with the git dependencies pinned
+    /// in its lock file, if any.
     pub fn new<W: Write>(
+        root_package: SourceManifest,
+        root_package_path: PathBuf,
+        build_options: BuildConfig,
+        writer: &mut W,
+    ) -> Result<ResolvingGraph> {
+        let lock_file = LockFile::read(&root_package_path)?;
+        Self::new_with_lock_file(
+            root_package,
+            root_package_path,
+            build_options,
+            lock_file.as_ref(),
+            writer,
+        )
+    }
+
+    /// Builds the graph of the package at `root_package_path`, with the git dependencies pinned
+    /// in `lock_file`. The latest version of git dependencies is used if there is none.
+    pub fn new_with_lock_file<W: Write>(
         root_package: SourceManifest,
         root_package_path: PathBuf,
         mut build_options: BuildConfig,
+        lock_file: Option<&LockFile>,
         writer: &mut W,
     ) -> Result<ResolvingGraph> {
         if build_options.architecture.is_none() {
@@ -114,7 +138,13 @@ impl ResolvingGraph {
         };
 
         resolution_graph
-            .build_resolution_graph(root_package.clone(), root_package_path, true, writer)
+            .build_resolution_graph(
+                root_package.clone(),
+                root_package_path,
+                true,
+                lock_file,
+                writer,
+            )
             .with_context(|| {
                 format!(
                     "Unable to resolve packages for package '{}'",
@@ -199,6 +229,7 @@ impl ResolvingGraph {
         package: SourceManifest,
         package_path: PathBuf,
         is_root_package: bool,
+        lock_file: Option<&LockFile>,
         writer: &mut W,
     ) -> Result<()> {
         let package_name = package.package.name;
@@ -252,7 +283,7 @@ impl ResolvingGraph {
             self.graph.add_edge(package_node_id, dep_node_id, ());
 
             let (dep_renaming, dep_resolution_table) = self
-                .process_dependency(dep_name, dep, package_path.clone(), writer)
+                .process_dependency(dep_name, dep, package_path.clone(), lock_file, writer)
                 .with_context(|| {
                     format!(
                         "While resolving dependency '{}' in package '{}'",
@@ -393,21 +424,27 @@ impl ResolvingGraph {
         dep_name_in_pkg: PackageName,
         dep: Dependency,
         root_path: PathBuf,
+        lock_file: Option<&LockFile>,
         writer: &mut W,
     ) -> Result<(Renaming, ResolvingTable)> {
         Self::download_and_update_if_remote(
             dep_name_in_pkg,
             &dep,
             self.build_options.skip_fetch_latest_git_deps,
+            lock_file,
             writer,
         )?;
         let (dep_package, dep_package_dir) =
             Self::parse_package_manifest(&dep, &dep_name_in_pkg, root_path)
                 .with_context(|| format!("While processing dependency '{}'", dep_name_in_pkg))?;
-        self.build_resolution_graph(dep_package.clone(), dep_package_dir, false, writer)
-            .with_context(|| {
-                format!("Unable to resolve package dependency '{}'", dep_name_in_pkg)
-            })?;
+        self.build_resolution_graph(
+            dep_package.clone(),
+            dep_package_dir,
+            false,
+            lock_file,
+            writer,
+        )
+        .with_context(|| format!("Unable to resolve package dependency '{}'", dep_name_in_pkg))?;
 
         if dep_name_in_pkg != dep_package.package.name {
             bail!("Name of dependency declared in package '{}' does not match dependency's package name '{}'",
@@ -527,6 +564,7 @@ impl ResolvingGraph {
         manifest: &SourceManifest,
         build_options: &BuildConfig,
         root_path: &Path,
+        lock_file: Option<&LockFile>,
         writer: &mut W,
     ) -> Result<()> {
         // include dev dependencies if in dev mode
@@ -543,6 +581,7 @@ impl ResolvingGraph {
                 *dep_name,
                 dep,
                 build_options.skip_fetch_latest_git_deps,
+                lock_file,
                 writer,
             )?;
 
@@ -550,7 +589,13 @@ impl ResolvingGraph {
                 Self::parse_package_manifest(dep, dep_name, root_path.to_path_buf())
                     .with_context(|| format!("While processing dependency '{}'", *dep_name))?;
             // download dependencies of dependencies
-            Self::download_dependency_repos(&dep_manifest, build_options, root_path, writer)?;
+            Self::download_dependency_repos(
+                &dep_manifest,
+                build_options,
+                root_path,
+                lock_file,
+                writer,
+            )?;
         }
         Ok(())
     }
@@ -559,9 +604,16 @@ impl ResolvingGraph {
         dep_name: PackageName,
         dep: &Dependency,
         skip_fetch_latest_git_deps: bool,
+        lock_file: Option<&LockFile>,
         writer: &mut W,
     ) -> Result<()> {
         if let Some(git_info) = &dep.git_info {
+            if let Some(commit) =
+                lock_file.and_then(|lock_file| lock_file.locked_commit(dep_name, git_info))
+            {
+                return Self::checkout_locked_commit(dep_name, git_info, commit, writer);
+            }
+
             let git_url = git_info.git_url.as_str();
             let git_rev = git_info.git_rev.as_str();
             let git_path = &git_info.download_to.display().to_string();
@@ -694,6 +746,69 @@ impl ResolvingGraph {
         }
         Ok(())
     }
+
+    /// Checks out the commit a git dependency is pinned to in the lock file, fetching it if it
+    /// is not available locally.
+    fn checkout_locked_commit<W: Write>(
+        dep_name: PackageName,
+        git_info: &GitInfo,
+        commit: &str,
+        writer: &mut W,
+    ) -> Result<()> {
+        let git_url = git_info.git_url.as_str();
+        let git_path = &git_info.download_to.display().to_string();
+        confirm_git_available()?;
+
+        if git_info.download_to.exists() {
+            if git_head_commit(&git_info.download_to)? == commit {
+                return Ok(());
+            }
+        } else {
+            writeln!(
+                writer,
+                "{} {}",
+                "FETCHING GIT DEPENDENCY".bold().green(),
+                git_url,
+            )?;
+            run_git(&["clone", git_url, git_path], || {
+                format!("Failed to clone Git repository for package '{}'", dep_name)
+            })?;
+        }
+
+        // The locked commit may be more recent than the cached repository
+        let has_commit = Command::new("git")
+            .args([
+                "-C",
+                git_path,
+                "cat-file",
+                "-e",
+                &format!("{}^{{commit}}", commit),
+            ])
+            .stderr(Stdio::null())
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false);
+        if !has_commit {
+            writeln!(
+                writer,
+                "{} {}",
+                "UPDATING GIT DEPENDENCY".bold().green(),
+                git_url,
+            )?;
+            run_git(&["-C", git_path, "fetch", "origin"], || {
+                format!(
+                    "Failed to fetch latest Git state for package '{}'",
+                    dep_name
+                )
+            })?;
+        }
+        run_git(&["-C", git_path, "checkout", "--force", commit], || {
+            format!(
+                "Failed to checkout locked Git commit '{}' for package '{}'",
+                commit, dep_name
+            )
+        })
+    }
 }
 
 impl ResolvingPackage {
@@ -946,6 +1061,19 @@ impl ResolvedPackage {
     }
 }
 
+fn run_git(args: &[&str], error_msg: impl Fn() -> String) -> Result<()> {
+    let status = Command::new("git")
+        .args(args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .with_context(&error_msg)?;
+    if !status.success() {
+        bail!("{} | Exit status: {}", error_msg(), status)
+    }
+    Ok(())
+}
+
 fn confirm_git_available() -> Result<()> {
     match Command::new("git").arg("--version").output() {
         Ok(_) => Ok(()),